@@ -53,6 +53,7 @@ pub(crate) fn kind_for_content_path(rel_path: &str) -> NodeKind {
         Some("md" | "html" | "htm") => NodeKind::Page,
         Some("link") => NodeKind::Redirect,
         Some("png" | "jpg" | "jpeg" | "gif" | "webp" | "svg") => NodeKind::Asset,
+        Some("mp3" | "ogg" | "wav" | "mp4" | "webm") => NodeKind::Asset,
         Some("pdf") => NodeKind::Document,
         Some("app") => NodeKind::App,
         Some("json") => NodeKind::Data,
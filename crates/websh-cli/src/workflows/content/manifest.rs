@@ -4,7 +4,7 @@ use std::path::{Path, PathBuf};
 use websh_core::domain::{ContentManifestDocument, ContentManifestEntry};
 
 use crate::CliResult;
-use crate::infra::json::write_json;
+use crate::infra::json::{read_json, write_json};
 
 use super::files::{
     CONTENT_MANIFEST_FILE, collect_files_recursive, relative_path_from, resolve_path,
@@ -119,8 +119,14 @@ fn bundle_manifest(
     file_entries.sort_by(|a, b| a.path.cmp(&b.path));
     entries.extend(file_entries);
 
-    let manifest = ContentManifestDocument { entries };
-    write_json(&content_root.join(CONTENT_MANIFEST_FILE), &manifest)?;
+    // The banner is hand-authored on the manifest itself rather than
+    // projected from sidecars, so carry it over from the previous bundle.
+    let manifest_path = content_root.join(CONTENT_MANIFEST_FILE);
+    let banner = read_json::<ContentManifestDocument>(&manifest_path)
+        .ok()
+        .and_then(|previous| previous.banner);
+    let manifest = ContentManifestDocument { banner, entries };
+    write_json(&manifest_path, &manifest)?;
     Ok(manifest)
 }
 
@@ -157,7 +163,7 @@ mod tests {
     use super::*;
     use std::sync::atomic::{AtomicU64, Ordering};
     use websh_core::domain::{
-        AccessFilter, Fields, NodeKind, NodeMetadata, Recipient, SCHEMA_VERSION,
+        AccessFilter, Fields, ListingVisibility, NodeKind, NodeMetadata, Recipient, SCHEMA_VERSION,
     };
 
     fn tempdir() -> PathBuf {
@@ -225,6 +231,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn preserves_hand_authored_banner_across_syncs() {
+        let dir = tempdir();
+        fs::write(dir.join("note.md"), "body\n").unwrap();
+        fs::write(
+            dir.join("manifest.json"),
+            "{\n  \"banner\": \"hello from the archive\",\n  \"entries\": []\n}\n",
+        )
+        .unwrap();
+
+        let manifest = sync_content(&dir, Path::new(".")).expect("sync ok");
+
+        assert_eq!(manifest.banner.as_deref(), Some("hello from the archive"));
+        assert!(manifest.entries.iter().any(|entry| entry.path == "note.md"));
+    }
+
     #[test]
     fn preserves_sidecar_only_authored_fields() {
         let dir = tempdir();
@@ -240,6 +262,7 @@ mod tests {
                     recipients: vec![Recipient {
                         address: "0xabc".to_string(),
                     }],
+                    listing: ListingVisibility::Listed,
                 }),
                 ..Fields::default()
             },
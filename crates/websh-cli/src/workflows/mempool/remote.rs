@@ -354,6 +354,7 @@ mod tests {
     #[test]
     fn manifest_add_replaces_entry_and_sorts() {
         let manifest = ContentManifestDocument {
+            banner: None,
             entries: vec![
                 entry("writing/z.md"),
                 entry("writing/foo.md"),
@@ -394,6 +395,7 @@ mod tests {
     #[test]
     fn manifest_drop_removes_entry_and_reports_change() {
         let manifest = ContentManifestDocument {
+            banner: None,
             entries: vec![entry("writing/a.md"), entry("writing/foo.md")],
         };
 
@@ -407,6 +409,7 @@ mod tests {
     #[test]
     fn manifest_drop_absent_is_noop() {
         let manifest = ContentManifestDocument {
+            banner: None,
             entries: vec![entry("writing/a.md")],
         };
 
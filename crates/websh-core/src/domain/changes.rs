@@ -141,6 +141,38 @@ impl ChangeSet {
         )
     }
 
+    /// Whether `path` only exists as an uncommitted create.
+    pub fn is_pending_create(&self, path: &VirtualPath) -> bool {
+        matches!(
+            self.entries.get(path).map(|e| &e.change),
+            Some(
+                ChangeType::CreateFile { .. }
+                    | ChangeType::CreateBinary { .. }
+                    | ChangeType::CreateDirectory { .. }
+            )
+        )
+    }
+
+    /// Whether `path` is a local draft: created or edited here and not yet
+    /// committed.
+    pub fn is_draft(&self, path: &VirtualPath) -> bool {
+        self.is_pending_create(path)
+            || matches!(
+                self.entries.get(path).map(|e| &e.change),
+                Some(ChangeType::UpdateFile { .. })
+            )
+    }
+
+    /// Text of the draft at `path`, for created or edited text files.
+    pub fn draft_text(&self, path: &VirtualPath) -> Option<&str> {
+        match self.entries.get(path).map(|e| &e.change)? {
+            ChangeType::CreateFile { content, .. } | ChangeType::UpdateFile { content, .. } => {
+                Some(content)
+            }
+            _ => None,
+        }
+    }
+
     pub fn iter_all(&self) -> impl Iterator<Item = (&VirtualPath, &Entry)> {
         self.entries.iter()
     }
@@ -241,6 +273,30 @@ mod tests {
         assert_eq!(cs.get(&p("/a.md")).unwrap().timestamp, 1234);
     }
 
+    #[test]
+    fn drafts_are_pending_creates_and_edits() {
+        let mut cs = ChangeSet::new();
+        upsert(&mut cs, "/new.md", create_file("new"));
+        upsert(
+            &mut cs,
+            "/old.md",
+            ChangeType::UpdateFile {
+                content: "edited".to_string(),
+                meta: None,
+                extensions: None,
+            },
+        );
+        upsert(&mut cs, "/gone.md", ChangeType::DeleteFile);
+
+        assert!(cs.is_draft(&p("/new.md")));
+        assert!(cs.is_draft(&p("/old.md")));
+        assert!(!cs.is_draft(&p("/gone.md")));
+        assert!(!cs.is_draft(&p("/other.md")));
+        assert_eq!(cs.draft_text(&p("/new.md")), Some("new"));
+        assert_eq!(cs.draft_text(&p("/old.md")), Some("edited"));
+        assert_eq!(cs.draft_text(&p("/gone.md")), None);
+    }
+
     #[test]
     fn unstage_then_stage_roundtrip() {
         let mut cs = ChangeSet::new();
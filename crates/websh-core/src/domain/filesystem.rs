@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fmt;
 
 use super::mempool::MempoolFields;
@@ -42,6 +42,31 @@ pub struct DirEntry {
     pub is_dir: bool,
     pub title: String,
     pub meta: Option<NodeMetadata>,
+    /// Name and title stand in for an entry the viewer can't read (see
+    /// [`ListingVisibility::Masked`](crate::domain::ListingVisibility)).
+    /// `path` still points at the real entry, so it must not be shown.
+    pub masked: bool,
+}
+
+impl DirEntry {
+    /// Whether the entry carries an access filter; masked entries always do.
+    pub fn is_restricted(&self) -> bool {
+        self.masked || self.meta.as_ref().is_some_and(NodeMetadata::is_restricted)
+    }
+
+    /// Key of the canonical listing order: directories first, hidden
+    /// (dot-prefixed) entries after visible ones, then by name. Listings
+    /// from [`GlobalFs::list_dir`](crate::filesystem::GlobalFs::list_dir)
+    /// already come in this order; callers wanting another order sort by
+    /// their own key.
+    pub fn sort_key(&self) -> (bool, bool, &str) {
+        (!self.is_dir, self.name.starts_with('.'), &self.name)
+    }
+
+    /// Path a listing may link to; `None` for masked entries.
+    pub fn link_path(&self) -> Option<crate::domain::VirtualPath> {
+        (!self.masked).then(|| self.path.clone())
+    }
 }
 
 /// Supported file types for the reader
@@ -51,6 +76,8 @@ pub enum FileType {
     Markdown,
     Pdf,
     Image,
+    Audio,
+    Video,
     Link,
     Unknown,
 }
@@ -63,19 +90,26 @@ impl FileType {
             Some("md") => Self::Markdown,
             Some("pdf") => Self::Pdf,
             Some("png" | "jpg" | "jpeg" | "gif" | "webp" | "svg") => Self::Image,
+            Some("mp3" | "ogg" | "wav") => Self::Audio,
+            Some("mp4" | "webm") => Self::Video,
             Some("link") => Self::Link,
             _ => Self::Unknown,
         }
     }
 }
 
+/// Children of a directory, keyed and iterated by name. The ordered map
+/// keeps iteration deterministic and lets a listing produce the canonical
+/// order (see [`DirEntry::sort_key`]) in one linear pass.
+pub type DirChildren = BTreeMap<String, FsEntry>;
+
 /// Represents an entry in the canonical filesystem tree. Each entry now
 /// carries a single [`NodeMetadata`] record covering both authored and
 /// derived fields.
 #[derive(Clone, Debug)]
 pub enum FsEntry {
     Directory {
-        children: HashMap<String, FsEntry>,
+        children: DirChildren,
         meta: NodeMetadata,
     },
     File {
@@ -146,6 +180,11 @@ mod tests {
         assert_eq!(FileType::from_path("papers/research.pdf"), FileType::Pdf);
         assert_eq!(FileType::from_path("images/photo.png"), FileType::Image);
         assert_eq!(FileType::from_path("images/photo.JPG"), FileType::Image);
+        assert_eq!(FileType::from_path("talks/intro.mp3"), FileType::Audio);
+        assert_eq!(FileType::from_path("talks/intro.ogg"), FileType::Audio);
+        assert_eq!(FileType::from_path("talks/intro.WAV"), FileType::Audio);
+        assert_eq!(FileType::from_path("talks/demo.mp4"), FileType::Video);
+        assert_eq!(FileType::from_path("talks/demo.webm"), FileType::Video);
         assert_eq!(FileType::from_path("links/github.link"), FileType::Link);
         assert_eq!(FileType::from_path("unknown/file.xyz"), FileType::Unknown);
     }
@@ -10,6 +10,11 @@ use super::node_metadata::NodeMetadata;
 #[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ContentManifestDocument {
+    /// Optional text printed under the ASCII logo when the terminal boots.
+    /// Only the bootstrap mount's banner is shown; absent keeps the stock
+    /// boot output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub banner: Option<String>,
     #[serde(default)]
     pub entries: Vec<ContentManifestEntry>,
 }
@@ -35,5 +40,19 @@ mod tests {
         let manifest: ContentManifestDocument = serde_json::from_str(body).expect("parse");
         let encoded = serde_json::to_string_pretty(&manifest).expect("serialize");
         assert_eq!(encoded.trim_end(), body.trim_end());
+        assert_eq!(manifest.banner, None);
+    }
+
+    #[test]
+    fn content_manifest_document_reads_optional_banner() {
+        let body = r#"{"banner":"welcome to the archive\nmind the gap","entries":[]}"#;
+        let manifest: ContentManifestDocument = serde_json::from_str(body).expect("parse");
+        assert_eq!(
+            manifest.banner.as_deref(),
+            Some("welcome to the archive\nmind the gap")
+        );
+
+        let encoded = serde_json::to_string(&manifest).expect("serialize");
+        assert_eq!(encoded, body);
     }
 }
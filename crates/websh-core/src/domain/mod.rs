@@ -11,7 +11,9 @@ mod virtual_path;
 mod wallet;
 
 pub use changes::{ChangeSet, ChangeType, Entry as ChangeEntry, Summary as ChangeSummary};
-pub use filesystem::{DirEntry, DisplayPermissions, EntryExtensions, FileType, FsEntry};
+pub use filesystem::{
+    DirChildren, DirEntry, DisplayPermissions, EntryExtensions, FileType, FsEntry,
+};
 pub use manifest::{ContentManifestDocument, ContentManifestEntry};
 pub use mempool::{MempoolFields, MempoolStatus, Priority};
 pub use mount::{
@@ -21,9 +23,9 @@ pub use mount::{
 #[cfg(test)]
 pub(crate) use node_metadata::test_support;
 pub use node_metadata::{
-    AccessFilter, Fields, ImageDim, NodeKind, NodeMetadata, PageSize, Recipient, RendererKind,
-    SCHEMA_VERSION, TrustLevel,
+    AccessFilter, Fields, ImageDim, ListingVisibility, NodeKind, NodeMetadata, PageSize, Recipient,
+    RendererKind, SCHEMA_VERSION, TrustLevel,
 };
 pub use site::{DerivedIndex, MountDeclaration, RouteIndexEntry};
 pub use virtual_path::{VirtualPath, VirtualPathParseError};
-pub use wallet::{WalletState, chain_name};
+pub use wallet::{EnsAvatar, WalletState, chain_name, parse_ens_avatar};
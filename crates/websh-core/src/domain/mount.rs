@@ -58,6 +58,8 @@ pub struct RuntimeMount {
     pub label: String,
     pub backend_kind: RuntimeBackendKind,
     pub writable: bool,
+    /// Declared redirect allowlist for `.link` files under `root`.
+    pub redirect_domains: Option<Vec<String>>,
 }
 
 impl RuntimeMount {
@@ -72,9 +74,15 @@ impl RuntimeMount {
             label: label.into(),
             backend_kind,
             writable,
+            redirect_domains: None,
         }
     }
 
+    pub fn with_redirect_domains(mut self, domains: Option<Vec<String>>) -> Self {
+        self.redirect_domains = domains;
+        self
+    }
+
     pub fn contains(&self, path: &VirtualPath) -> bool {
         path.starts_with(&self.root)
     }
@@ -285,6 +285,28 @@ pub enum TrustLevel {
 #[serde(deny_unknown_fields)]
 pub struct AccessFilter {
     pub recipients: Vec<Recipient>,
+    /// How directory listings show the entry to viewers who can't read it.
+    #[serde(default, skip_serializing_if = "ListingVisibility::is_listed")]
+    pub listing: ListingVisibility,
+}
+
+/// Listing treatment of an entry the viewer can't read.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListingVisibility {
+    /// Listed under its real name and title, with a lock.
+    #[default]
+    Listed,
+    /// Listed as `••••••.<ext>` with a lock and no title.
+    Masked,
+    /// Left out of the listing.
+    Hidden,
+}
+
+impl ListingVisibility {
+    pub fn is_listed(&self) -> bool {
+        matches!(self, Self::Listed)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -19,6 +19,10 @@ pub struct MountDeclaration {
     pub name: Option<String>,
     #[serde(default)]
     pub writable: bool,
+    /// Domains `.link` files under this mount may redirect to. Narrows the
+    /// reader's built-in allowlist; `None` leaves it as is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redirect_domains: Option<Vec<String>>,
 }
 
 /// One route entry in the derived index.
@@ -59,6 +63,24 @@ mod tests {
         assert_eq!(decl.repo.as_deref(), Some("0xwonj/db"));
         assert_eq!(decl.branch.as_deref(), Some("main"));
         assert!(decl.writable);
+        assert_eq!(decl.redirect_domains, None);
+    }
+
+    #[test]
+    fn mount_declaration_parses_redirect_domains() {
+        let decl: MountDeclaration = serde_json::from_str(
+            r#"{
+                "backend": "github",
+                "mount_at": "/db",
+                "redirect_domains": ["github.com", "mirror.xyz"]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            decl.redirect_domains,
+            Some(vec!["github.com".to_string(), "mirror.xyz".to_string()])
+        );
     }
 
     #[test]
@@ -20,6 +20,60 @@ pub fn chain_name(chain_id: u64) -> &'static str {
     }
 }
 
+/// Public gateway used to turn `ipfs://` avatar records into URLs.
+pub const IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+
+/// Where an ENS `avatar` text record points (ENSIP-12).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EnsAvatar {
+    /// Directly loadable image: `https:`, `data:image/`, or a gateway URL
+    /// for `ipfs://` / `ar://`.
+    Url(String),
+    /// ERC-721/1155 token (`eip155:1/erc721:0x…/42`); the image comes
+    /// from the token metadata.
+    Nft {
+        chain_id: u64,
+        contract: String,
+        token_id: String,
+    },
+}
+
+/// Parse an ENS avatar record. Returns `None` for empty or unsupported
+/// records (including plain `http:`, which browsers block as mixed content).
+pub fn parse_ens_avatar(record: &str) -> Option<EnsAvatar> {
+    let record = record.trim();
+    if record.starts_with("https://") || record.starts_with("data:image/") {
+        return Some(EnsAvatar::Url(record.to_string()));
+    }
+    if let Some(rest) = record.strip_prefix("ipfs://") {
+        let rest = rest.strip_prefix("ipfs/").unwrap_or(rest);
+        return (!rest.is_empty()).then(|| EnsAvatar::Url(format!("{IPFS_GATEWAY}{rest}")));
+    }
+    if let Some(rest) = record.strip_prefix("ar://") {
+        return (!rest.is_empty()).then(|| EnsAvatar::Url(format!("https://arweave.net/{rest}")));
+    }
+
+    let rest = record.strip_prefix("eip155:")?;
+    let (chain_id, rest) = rest.split_once('/')?;
+    let (standard, rest) = rest.split_once(':')?;
+    let (contract, token_id) = rest.split_once('/')?;
+    let valid_contract = contract.len() == 42
+        && contract.starts_with("0x")
+        && contract[2..].chars().all(|c| c.is_ascii_hexdigit());
+    if !matches!(standard.to_ascii_lowercase().as_str(), "erc721" | "erc1155")
+        || !valid_contract
+        || token_id.is_empty()
+        || !token_id.chars().all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    Some(EnsAvatar::Nft {
+        chain_id: chain_id.parse().ok()?,
+        contract: contract.to_string(),
+        token_id: token_id.to_string(),
+    })
+}
+
 /// Wallet connection state
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum WalletState {
@@ -65,6 +119,63 @@ impl WalletState {
 mod tests {
     use super::*;
 
+    #[test]
+    fn parses_url_avatar_records() {
+        assert_eq!(
+            parse_ens_avatar(" https://example.com/a.png "),
+            Some(EnsAvatar::Url("https://example.com/a.png".to_string()))
+        );
+        assert_eq!(
+            parse_ens_avatar("ipfs://QmHash/avatar.png"),
+            Some(EnsAvatar::Url(format!("{IPFS_GATEWAY}QmHash/avatar.png")))
+        );
+        assert_eq!(
+            parse_ens_avatar("ipfs://ipfs/QmHash"),
+            Some(EnsAvatar::Url(format!("{IPFS_GATEWAY}QmHash")))
+        );
+        assert_eq!(
+            parse_ens_avatar("ar://tx-id"),
+            Some(EnsAvatar::Url("https://arweave.net/tx-id".to_string()))
+        );
+        assert!(matches!(
+            parse_ens_avatar("data:image/svg+xml;base64,AAAA"),
+            Some(EnsAvatar::Url(_))
+        ));
+    }
+
+    #[test]
+    fn parses_nft_avatar_records() {
+        let contract = "0xb47e3cd837dDF8e4c57F05d70Ab865de6e193BBB";
+        assert_eq!(
+            parse_ens_avatar(&format!("eip155:1/erc721:{contract}/1234")),
+            Some(EnsAvatar::Nft {
+                chain_id: 1,
+                contract: contract.to_string(),
+                token_id: "1234".to_string(),
+            })
+        );
+        assert!(matches!(
+            parse_ens_avatar(&format!("eip155:137/ERC1155:{contract}/7")),
+            Some(EnsAvatar::Nft { chain_id: 137, .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_unsupported_avatar_records() {
+        for record in [
+            "",
+            "http://example.com/a.png",
+            "ipfs://",
+            "eip155:1/erc20:0xb47e3cd837dDF8e4c57F05d70Ab865de6e193BBB/1",
+            "eip155:1/erc721:0x1234/1",
+            "eip155:x/erc721:0xb47e3cd837dDF8e4c57F05d70Ab865de6e193BBB/1",
+            "eip155:1/erc721:0xb47e3cd837dDF8e4c57F05d70Ab865de6e193BBB/abc",
+            "not a uri",
+        ] {
+            assert_eq!(parse_ens_avatar(record), None, "record: {record:?}");
+        }
+    }
+
     #[test]
     fn test_disconnected_state() {
         let state = WalletState::Disconnected;
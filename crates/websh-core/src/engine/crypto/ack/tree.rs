@@ -44,8 +44,10 @@ impl MerkleTree {
                 current.push(last);
             }
             let next = current
-                .chunks_exact(2)
-                .map(|pair| node_hash(&pair[0], &pair[1]))
+                .as_chunks::<2>()
+                .0
+                .iter()
+                .map(|[left, right]| node_hash(left, right))
                 .collect::<Vec<_>>();
             levels.push(next);
         }
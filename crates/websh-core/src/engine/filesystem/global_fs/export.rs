@@ -27,7 +27,11 @@ impl GlobalFs {
         collect_scanned_directories(mount_root, "", children, &excluded_roots, &mut directories);
         directories.sort_by(|a, b| a.path.cmp(&b.path));
 
-        Some(ScannedSubtree { files, directories })
+        Some(ScannedSubtree {
+            files,
+            directories,
+            banner: None,
+        })
     }
 
     fn export_excluded_roots(&self, mount_root: &VirtualPath) -> Vec<VirtualPath> {
@@ -30,6 +30,15 @@ pub enum FsMutationError {
     TargetMissing { path: VirtualPath },
 }
 
+/// Why [`GlobalFs::lookup`] found no entry at a path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LookupMiss {
+    /// A segment of the path does not exist.
+    NotFound,
+    /// The path descends through a file.
+    NotADirectory,
+}
+
 /// Minimal engine trait for the canonical-path read surface.
 pub trait FsEngine {
     fn stat(&self, path: &VirtualPath) -> Option<&FsEntry>;
@@ -1,6 +1,4 @@
-use std::collections::HashMap;
-
-use crate::domain::{EntryExtensions, FsEntry, NodeMetadata, VirtualPath};
+use crate::domain::{DirChildren, EntryExtensions, FsEntry, NodeMetadata, VirtualPath};
 
 use super::super::tree::{get_tree_entry_mut, insert_tree_entry, remove_tree_entry};
 use super::{FsMutationError, GlobalFs};
@@ -118,7 +116,7 @@ impl GlobalFs {
             &mut self.root,
             &path,
             FsEntry::Directory {
-                children: HashMap::new(),
+                children: DirChildren::new(),
                 meta,
             },
         )?;
@@ -1,24 +1,32 @@
 use crate::domain::{
-    DirEntry, DisplayPermissions, FsEntry, NodeMetadata, VirtualPath, WalletState,
+    DirEntry, DisplayPermissions, FsEntry, ListingVisibility, NodeMetadata, VirtualPath,
+    WalletState,
 };
 
+use crate::support::text::edit_distance;
+
+use super::super::tags::TagIndex;
 use super::super::tree::{collect_metadata_entries, sorted_dir_entries};
-use super::GlobalFs;
+use super::{GlobalFs, LookupMiss};
 
 impl GlobalFs {
     pub fn get_entry(&self, path: &VirtualPath) -> Option<&FsEntry> {
-        if path.is_root() {
-            return Some(&self.root);
-        }
+        self.lookup(path).ok()
+    }
 
+    /// [`Self::get_entry`] that tells a missing segment apart from one
+    /// reached through a file.
+    pub fn lookup(&self, path: &VirtualPath) -> Result<&FsEntry, LookupMiss> {
         let mut current = &self.root;
         for part in path.segments() {
             current = match current {
-                FsEntry::Directory { children, .. } => children.get(part)?,
-                FsEntry::File { .. } => return None,
+                FsEntry::Directory { children, .. } => {
+                    children.get(part).ok_or(LookupMiss::NotFound)?
+                }
+                FsEntry::File { .. } => return Err(LookupMiss::NotADirectory),
             };
         }
-        Some(current)
+        Ok(current)
     }
 
     pub fn exists(&self, path: &VirtualPath) -> bool {
@@ -45,15 +53,65 @@ impl GlobalFs {
 
     pub fn child_names(&self, path: &VirtualPath) -> Option<Vec<String>> {
         match self.get_entry(path)? {
-            FsEntry::Directory { children, .. } => {
-                let mut names = children.keys().cloned().collect::<Vec<_>>();
-                names.sort();
-                Some(names)
-            }
+            FsEntry::Directory { children, .. } => Some(children.keys().cloned().collect()),
             FsEntry::File { .. } => None,
         }
     }
 
+    /// Deepest directory along `path` that exists, falling back to `/`.
+    /// `path` itself is returned when it is an existing directory.
+    pub fn nearest_existing_ancestor(&self, path: &VirtualPath) -> VirtualPath {
+        let mut current = VirtualPath::root();
+        for segment in path.segments() {
+            let next = current.join(segment);
+            if !self.is_directory(&next) {
+                break;
+            }
+            current = next;
+        }
+        current
+    }
+
+    /// Entries beside the first missing segment of `path` whose names look
+    /// like a typo of it, closest first. Hidden entries are skipped.
+    pub fn near_miss_suggestions(&self, path: &VirtualPath, limit: usize) -> Vec<VirtualPath> {
+        let ancestor = self.nearest_existing_ancestor(path);
+        let Some(missing) = path
+            .strip_prefix(&ancestor)
+            .and_then(|rest| rest.trim_start_matches('/').split('/').next())
+            .filter(|segment| !segment.is_empty())
+        else {
+            return Vec::new();
+        };
+        let Some(names) = self.child_names(&ancestor) else {
+            return Vec::new();
+        };
+
+        let missing = missing.to_lowercase();
+        let missing_stem = file_stem(&missing);
+        let mut scored = names
+            .into_iter()
+            .filter(|name| !name.starts_with('.'))
+            .filter_map(|name| {
+                let lower = name.to_lowercase();
+                let stem = file_stem(&lower);
+                let distance =
+                    edit_distance(&missing, &lower).min(edit_distance(missing_stem, stem));
+                let max_distance = (missing_stem.chars().count() / 3).max(1);
+                let related = !missing_stem.is_empty()
+                    && !stem.is_empty()
+                    && (stem.starts_with(missing_stem) || missing_stem.starts_with(stem));
+                (distance <= max_distance || related).then_some((distance, name))
+            })
+            .collect::<Vec<_>>();
+        scored.sort();
+        scored
+            .into_iter()
+            .take(limit)
+            .map(|(_, name)| ancestor.join(&name))
+            .collect()
+    }
+
     pub fn list_dir(&self, path: &VirtualPath) -> Option<Vec<DirEntry>> {
         match self.get_entry(path)? {
             FsEntry::Directory { children, .. } => Some(sorted_dir_entries(path, children)),
@@ -61,6 +119,96 @@ impl GlobalFs {
         }
     }
 
+    /// The file introducing directory `path`: `README.md`, else `index.md`,
+    /// matched without regard to case.
+    pub fn readme_for(&self, path: &VirtualPath) -> Option<VirtualPath> {
+        let FsEntry::Directory { children, .. } = self.get_entry(path)? else {
+            return None;
+        };
+        README_NAMES.iter().find_map(|wanted| {
+            children
+                .iter()
+                .find(|(name, entry)| !entry.is_directory() && name.eq_ignore_ascii_case(wanted))
+                .map(|(name, _)| path.join(name))
+        })
+    }
+
+    /// Directory listing as `wallet` sees it. Entries it can't read are
+    /// kept, masked or left out according to their access filter's
+    /// [`ListingVisibility`]. Listings shown to a viewer (`ls`, the home
+    /// feed) go through here so they agree on what is visible.
+    pub fn list_dir_for(&self, path: &VirtualPath, wallet: &WalletState) -> Option<Vec<DirEntry>> {
+        let entries = self.list_dir(path)?;
+        Some(
+            entries
+                .into_iter()
+                .filter_map(|entry| {
+                    let listing = entry
+                        .meta
+                        .as_ref()
+                        .and_then(|meta| meta.access())
+                        .map(|filter| filter.listing)
+                        .unwrap_or_default();
+                    if listing.is_listed()
+                        || self
+                            .get_entry(&entry.path)
+                            .is_none_or(|fs_entry| can_read(fs_entry, wallet))
+                    {
+                        return Some(entry);
+                    }
+                    match listing {
+                        ListingVisibility::Hidden => None,
+                        _ => Some(mask_entry(entry)),
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Files listed just before and just after `path` in its directory as
+    /// `wallet` sees it. Directories, hidden files, masked entries and files
+    /// `wallet` can't read are stepped over; a path missing from that
+    /// listing has no neighbours.
+    pub fn adjacent_entries(
+        &self,
+        path: &VirtualPath,
+        wallet: &WalletState,
+    ) -> (Option<DirEntry>, Option<DirEntry>) {
+        let Some(entries) = path
+            .parent()
+            .and_then(|parent| self.list_dir_for(&parent, wallet))
+        else {
+            return (None, None);
+        };
+        let mut files = entries
+            .into_iter()
+            .filter(|entry| !entry.is_dir && !entry.masked && !entry.name.starts_with('.'))
+            .filter(|entry| {
+                self.get_entry(&entry.path)
+                    .is_none_or(|fs_entry| can_read(fs_entry, wallet))
+            })
+            .collect::<Vec<_>>();
+        let Some(index) = files.iter().position(|file| &file.path == path) else {
+            return (None, None);
+        };
+        let next = (index + 1 < files.len()).then(|| files.swap_remove(index + 1));
+        let previous = index.checked_sub(1).map(|index| files.swap_remove(index));
+        (previous, next)
+    }
+
+    /// Paths of [`adjacent_entries`](Self::adjacent_entries).
+    pub fn adjacent_files(
+        &self,
+        path: &VirtualPath,
+        wallet: &WalletState,
+    ) -> (Option<VirtualPath>, Option<VirtualPath>) {
+        let (previous, next) = self.adjacent_entries(path, wallet);
+        (
+            previous.map(|entry| entry.path),
+            next.map(|entry| entry.path),
+        )
+    }
+
     pub fn get_permissions(
         &self,
         entry: &FsEntry,
@@ -68,23 +216,10 @@ impl GlobalFs {
         writable: bool,
     ) -> DisplayPermissions {
         let is_dir = entry.is_directory();
-        let read = match entry {
-            FsEntry::Directory { .. } => true,
-            FsEntry::File { meta, .. } => match meta.access() {
-                None => true,
-                Some(filter) => match wallet {
-                    WalletState::Connected { address, .. } => filter
-                        .recipients
-                        .iter()
-                        .any(|r| r.address.eq_ignore_ascii_case(address)),
-                    _ => false,
-                },
-            },
-        };
 
         DisplayPermissions {
             is_dir,
-            read,
+            read: can_read(entry, wallet),
             write: writable,
             execute: is_dir,
         }
@@ -97,4 +232,59 @@ impl GlobalFs {
         collect_metadata_entries(&VirtualPath::root(), &self.root, &mut out);
         out
     }
+
+    /// Tag index over every node at or below `root`.
+    pub fn tag_index(&self, root: &VirtualPath) -> TagIndex {
+        TagIndex::build(
+            self.metadata_entries()
+                .into_iter()
+                .filter(|(path, _)| path.starts_with(root)),
+        )
+    }
+}
+
+/// Directories are always readable; files with an access filter only by
+/// a connected recipient.
+fn can_read(entry: &FsEntry, wallet: &WalletState) -> bool {
+    match entry {
+        FsEntry::Directory { .. } => true,
+        FsEntry::File { meta, .. } => match meta.access() {
+            None => true,
+            Some(filter) => match wallet {
+                WalletState::Connected { address, .. } => filter
+                    .recipients
+                    .iter()
+                    .any(|r| r.address.eq_ignore_ascii_case(address)),
+                _ => false,
+            },
+        },
+    }
+}
+
+/// `••••••.md` in place of `secret.md`; the extension is kept so the entry
+/// still reads as a document of its kind.
+fn mask_entry(entry: DirEntry) -> DirEntry {
+    let name = match entry.name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => format!("{MASKED_NAME}.{ext}"),
+        _ => MASKED_NAME.to_string(),
+    };
+    DirEntry {
+        name,
+        title: String::new(),
+        meta: None,
+        masked: true,
+        ..entry
+    }
+}
+
+const MASKED_NAME: &str = "••••••";
+
+/// Names [`GlobalFs::readme_for`] looks for, in order of preference.
+const README_NAMES: [&str; 2] = ["README.md", "index.md"];
+
+fn file_stem(name: &str) -> &str {
+    match name.rsplit_once('.') {
+        Some((stem, _)) if !stem.is_empty() => stem,
+        _ => name,
+    }
 }
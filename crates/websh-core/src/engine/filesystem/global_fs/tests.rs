@@ -1,6 +1,6 @@
-use std::collections::HashMap;
-
-use crate::domain::{EntryExtensions, Fields, NodeKind, NodeMetadata, SCHEMA_VERSION};
+use crate::domain::{
+    DirEntry, EntryExtensions, Fields, NodeKind, NodeMetadata, SCHEMA_VERSION, WalletState,
+};
 use crate::ports::{ScannedDirectory, ScannedFile, ScannedSubtree};
 
 use super::*;
@@ -47,6 +47,7 @@ fn snapshot(files: &[&str], directories: &[&str]) -> ScannedSubtree {
                 meta: dir_meta(path.rsplit('/').next().unwrap_or(path)),
             })
             .collect(),
+        banner: None,
     }
 }
 
@@ -75,6 +76,30 @@ fn mounts_scanned_subtrees_under_canonical_prefixes() {
     );
 }
 
+#[test]
+fn lookup_tells_missing_segments_from_descending_through_files() {
+    let mut global = GlobalFs::empty();
+    global
+        .mount_scanned_subtree(VirtualPath::root(), &snapshot(&["blog/post.md"], &["blog"]))
+        .unwrap();
+    let path = |raw: &str| VirtualPath::from_absolute(raw).unwrap();
+
+    assert!(global.lookup(&path("/blog/post.md")).is_ok());
+    assert_eq!(
+        global.lookup(&path("/blog/missing.md")).err(),
+        Some(LookupMiss::NotFound)
+    );
+    assert_eq!(
+        global.lookup(&path("/blog/post.md/sub")).err(),
+        Some(LookupMiss::NotADirectory)
+    );
+    assert_eq!(
+        global.lookup(&path("/blog/post.md/sub/deeper")).err(),
+        Some(LookupMiss::NotADirectory)
+    );
+    assert!(global.get_entry(&path("/blog/post.md/sub")).is_none());
+}
+
 #[test]
 fn mutation_rejects_file_ancestor_without_replacing_it() {
     let mut global = GlobalFs::empty();
@@ -208,7 +233,7 @@ fn remounting_root_replaces_mount_registry() {
         .mount_subtree(
             VirtualPath::root(),
             FsEntry::Directory {
-                children: HashMap::new(),
+                children: Default::default(),
                 meta: dir_meta(""),
             },
         )
@@ -238,6 +263,188 @@ fn list_dir_uses_global_absolute_paths() {
     assert_eq!(entries[0].path.as_str(), "/blog/hello.md");
 }
 
+#[test]
+fn listing_order_is_canonical_whatever_the_insertion_order() {
+    let files = [
+        "zeta.md",
+        ".hidden.md",
+        "blog/post.md",
+        "Alpha.md",
+        ".cache/index.json",
+        "about.md",
+    ];
+    let directories = ["blog", ".cache", "assets"];
+    let names = |files: &[&str], directories: &[&str]| {
+        let mut global = GlobalFs::empty();
+        global
+            .mount_scanned_subtree(VirtualPath::root(), &snapshot(files, directories))
+            .unwrap();
+        let entries = global.list_dir(&VirtualPath::root()).unwrap();
+        assert!(entries.is_sorted_by_key(DirEntry::sort_key));
+        entries
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect::<Vec<_>>()
+    };
+
+    let forward = names(&files, &directories);
+    assert_eq!(
+        forward,
+        [
+            "assets",
+            "blog",
+            ".cache",
+            "Alpha.md",
+            "about.md",
+            "zeta.md",
+            ".hidden.md"
+        ]
+    );
+
+    let mut reversed_files = files;
+    reversed_files.reverse();
+    let mut reversed_dirs = directories;
+    reversed_dirs.reverse();
+    assert_eq!(names(&reversed_files, &reversed_dirs), forward);
+}
+
+#[test]
+fn entries_under_a_file_are_skipped_and_reported_by_the_lint() {
+    for files in [["blog", "blog/post.md"], ["blog/post.md", "blog"]] {
+        let scan = snapshot(&files, &[]);
+        let mut global = GlobalFs::empty();
+        global
+            .mount_scanned_subtree(VirtualPath::root(), &scan)
+            .unwrap();
+
+        let blog = VirtualPath::from_absolute("/blog").unwrap();
+        assert!(
+            global
+                .get_entry(&blog)
+                .is_some_and(|entry| !entry.is_directory())
+        );
+        assert!(!global.exists(&blog.join("post.md")));
+
+        let issues = crate::ports::lint_manifest_snapshot(&scan)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(issues, ["error: blog/post.md: parent `blog` is a file"]);
+    }
+}
+
+#[test]
+fn adjacent_files_step_over_directories_and_hidden_files() {
+    let mut global = GlobalFs::empty();
+    global
+        .mount_scanned_subtree(
+            VirtualPath::root(),
+            &snapshot(
+                &[
+                    "blog/a.md",
+                    "blog/b.pdf",
+                    "blog/.draft.md",
+                    "blog/c.md",
+                    "blog/nested/d.md",
+                ],
+                &["blog", "blog/nested"],
+            ),
+        )
+        .unwrap();
+    let wallet = WalletState::Disconnected;
+    let path = |path: &str| VirtualPath::from_absolute(path).unwrap();
+    let adjacent = |at: &str| global.adjacent_files(&path(at), &wallet);
+
+    assert_eq!(adjacent("/blog/a.md"), (None, Some(path("/blog/b.pdf"))));
+    assert_eq!(
+        adjacent("/blog/b.pdf"),
+        (Some(path("/blog/a.md")), Some(path("/blog/c.md")))
+    );
+    assert_eq!(adjacent("/blog/c.md"), (Some(path("/blog/b.pdf")), None));
+    assert_eq!(adjacent("/blog/nested/d.md"), (None, None));
+    assert_eq!(adjacent("/blog/.draft.md"), (None, None));
+    assert_eq!(adjacent("/blog/gone.md"), (None, None));
+    assert_eq!(adjacent("/blog"), (None, None));
+}
+
+#[test]
+fn adjacent_entries_carry_titles_and_handle_lone_files() {
+    let mut global = GlobalFs::empty();
+    global
+        .mount_scanned_subtree(
+            VirtualPath::root(),
+            &snapshot(
+                &["solo/only.md", "blog/a.md", "blog/b.md"],
+                &["solo", "blog"],
+            ),
+        )
+        .unwrap();
+    let wallet = WalletState::Disconnected;
+    let path = |path: &str| VirtualPath::from_absolute(path).unwrap();
+
+    assert_eq!(
+        global.adjacent_files(&path("/solo/only.md"), &wallet),
+        (None, None)
+    );
+    let (previous, next) = global.adjacent_entries(&path("/blog/a.md"), &wallet);
+    assert!(previous.is_none());
+    let next = next.unwrap();
+    assert_eq!(next.path, path("/blog/b.md"));
+    assert_eq!(next.title, "b.md");
+}
+
+#[test]
+fn adjacent_files_skip_entries_the_wallet_cannot_read() {
+    let global = restricted_listing_fs();
+    let path = |path: &str| VirtualPath::from_absolute(path).unwrap();
+    let public = path("/blog/public.md");
+
+    // `listed.md` is listed but unreadable; `masked.md` is masked.
+    assert_eq!(
+        global.adjacent_files(&public, &WalletState::Disconnected),
+        (None, None)
+    );
+    assert_eq!(
+        global.adjacent_files(&public, &connected("0xabc")),
+        (Some(path("/blog/masked.md")), None)
+    );
+}
+
+#[test]
+fn tree_walks_visit_children_by_name() {
+    let mut global = GlobalFs::empty();
+    global
+        .mount_scanned_subtree(
+            VirtualPath::root(),
+            &snapshot(&["b/two.md", "a/one.md", "c.md"], &[]),
+        )
+        .unwrap();
+    global.upsert_file(
+        VirtualPath::from_absolute("/a/zero.md").unwrap(),
+        String::new(),
+        file_meta(NodeKind::Page),
+        EntryExtensions::default(),
+    );
+
+    let paths = global
+        .metadata_entries()
+        .into_iter()
+        .map(|(path, _)| path.as_str().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        [
+            "/",
+            "/a",
+            "/a/one.md",
+            "/a/zero.md",
+            "/b",
+            "/b/two.md",
+            "/c.md"
+        ]
+    );
+}
+
 #[test]
 fn child_summary_avoids_full_dir_entry_materialization() {
     let mut global = GlobalFs::empty();
@@ -334,6 +541,7 @@ fn exported_mount_snapshot_sorts_regardless_of_input_order() {
                 meta: tagged_dir("A", "area"),
             },
         ],
+        banner: None,
     };
 
     let mut global = GlobalFs::empty();
@@ -452,3 +660,205 @@ fn descendant_mount_export_includes_only_mount_relative_files() {
 
     assert_eq!(files, vec!["fresh.md"]);
 }
+
+#[test]
+fn nearest_existing_ancestor_stops_at_the_deepest_directory() {
+    let mut global = GlobalFs::empty();
+    global
+        .mount_scanned_subtree(
+            VirtualPath::root(),
+            &snapshot(&["blog/hello.md", "blog/world.md"], &["blog"]),
+        )
+        .unwrap();
+
+    let path = |value: &str| VirtualPath::from_absolute(value).unwrap();
+    assert_eq!(
+        global.nearest_existing_ancestor(&path("/blog/missing/deeper.md")),
+        path("/blog")
+    );
+    assert_eq!(
+        global.nearest_existing_ancestor(&path("/blog")),
+        path("/blog")
+    );
+    // A file in the middle of the path is not a directory to descend into.
+    assert_eq!(
+        global.nearest_existing_ancestor(&path("/blog/hello.md/extra")),
+        path("/blog")
+    );
+    assert_eq!(
+        global.nearest_existing_ancestor(&path("/nope/at/all")),
+        VirtualPath::root()
+    );
+}
+
+#[test]
+fn near_miss_suggestions_rank_close_siblings() {
+    let mut global = GlobalFs::empty();
+    global
+        .mount_scanned_subtree(
+            VirtualPath::root(),
+            &snapshot(
+                &[
+                    "blog/hello.md",
+                    "blog/help.md",
+                    "blog/world.md",
+                    "blog/.draft.md",
+                ],
+                &["blog"],
+            ),
+        )
+        .unwrap();
+
+    let path = |value: &str| VirtualPath::from_absolute(value).unwrap();
+    assert_eq!(
+        global.near_miss_suggestions(&path("/blog/helo.md"), 5),
+        vec![path("/blog/hello.md"), path("/blog/help.md")]
+    );
+    assert_eq!(
+        global.near_miss_suggestions(&path("/blog/hello"), 5),
+        vec![path("/blog/hello.md")]
+    );
+    assert_eq!(
+        global.near_miss_suggestions(&path("/blgo/world.md"), 5),
+        vec![path("/blog")]
+    );
+    assert!(
+        global
+            .near_miss_suggestions(&path("/blog/unrelated.md"), 5)
+            .is_empty()
+    );
+    assert!(global.near_miss_suggestions(&path("/blog"), 5).is_empty());
+}
+
+fn restricted_listing_fs() -> GlobalFs {
+    use crate::domain::{AccessFilter, ListingVisibility, Recipient};
+
+    let restricted = |listing: ListingVisibility| NodeMetadata {
+        authored: Fields {
+            title: Some("Secret plans".to_string()),
+            access: Some(AccessFilter {
+                recipients: vec![Recipient {
+                    address: "0xAbC".to_string(),
+                }],
+                listing,
+            }),
+            ..Fields::default()
+        },
+        ..file_meta(NodeKind::Page)
+    };
+    let mut subtree = snapshot(&["blog/public.md"], &["blog"]);
+    for (path, listing) in [
+        ("blog/listed.md", ListingVisibility::Listed),
+        ("blog/masked.md", ListingVisibility::Masked),
+        ("blog/hidden.md", ListingVisibility::Hidden),
+    ] {
+        subtree.files.push(ScannedFile {
+            path: path.to_string(),
+            meta: restricted(listing),
+            extensions: EntryExtensions::default(),
+        });
+    }
+
+    let mut global = GlobalFs::empty();
+    global
+        .mount_scanned_subtree(VirtualPath::root(), &subtree)
+        .unwrap();
+    global
+}
+
+fn connected(address: &str) -> WalletState {
+    WalletState::Connected {
+        address: address.to_string(),
+        ens_name: None,
+        chain_id: None,
+    }
+}
+
+#[test]
+fn list_dir_for_masks_or_hides_unreadable_entries() {
+    let global = restricted_listing_fs();
+    let entries = global
+        .list_dir_for(
+            &VirtualPath::from_absolute("/blog").unwrap(),
+            &WalletState::Disconnected,
+        )
+        .unwrap();
+
+    let names: Vec<_> = entries.iter().map(|entry| entry.name.as_str()).collect();
+    assert_eq!(names, ["listed.md", "••••••.md", "public.md"]);
+
+    let masked = &entries[1];
+    assert!(masked.masked);
+    assert!(masked.title.is_empty());
+    assert!(masked.meta.is_none());
+    assert!(masked.is_restricted());
+    assert_eq!(masked.link_path(), None);
+
+    assert!(!entries[0].masked);
+    assert_eq!(entries[0].title, "Secret plans");
+}
+
+#[test]
+fn list_dir_for_reveals_entries_to_a_recipient() {
+    let global = restricted_listing_fs();
+    let blog = VirtualPath::from_absolute("/blog").unwrap();
+
+    let names = |wallet: &WalletState| {
+        global
+            .list_dir_for(&blog, wallet)
+            .unwrap()
+            .into_iter()
+            .map(|entry| (entry.name, entry.masked))
+            .collect::<Vec<_>>()
+    };
+    let revealed = vec![
+        ("hidden.md".to_string(), false),
+        ("listed.md".to_string(), false),
+        ("masked.md".to_string(), false),
+        ("public.md".to_string(), false),
+    ];
+    assert_eq!(names(&connected("0xabc")), revealed);
+    assert_eq!(names(&connected("0xdef")).len(), 3);
+    assert_eq!(
+        global.list_dir(&blog).unwrap().len(),
+        revealed.len(),
+        "the unfiltered listing is unchanged"
+    );
+}
+
+#[test]
+fn readme_for_prefers_readme_over_index_in_any_case() {
+    let mut global = GlobalFs::empty();
+    global
+        .mount_scanned_subtree(
+            VirtualPath::root(),
+            &snapshot(
+                &[
+                    "docs/index.md",
+                    "docs/Readme.MD",
+                    "blog/INDEX.md",
+                    "blog/post.md",
+                    "notes/readme.txt",
+                ],
+                &["docs", "blog", "notes", "empty", "empty/readme.md"],
+            ),
+        )
+        .unwrap();
+
+    let path = |value: &str| VirtualPath::from_absolute(value).unwrap();
+    assert_eq!(
+        global.readme_for(&path("/docs")),
+        Some(path("/docs/Readme.MD"))
+    );
+    assert_eq!(
+        global.readme_for(&path("/blog")),
+        Some(path("/blog/INDEX.md"))
+    );
+    assert_eq!(global.readme_for(&path("/notes")), None);
+    assert_eq!(
+        global.readme_for(&path("/empty")),
+        None,
+        "directories don't count"
+    );
+    assert_eq!(global.readme_for(&path("/blog/post.md")), None);
+}
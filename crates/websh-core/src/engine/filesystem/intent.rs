@@ -59,10 +59,12 @@ fn content_intent_for_node(path: &VirtualPath) -> RenderIntent {
         FileType::Markdown => RenderIntent::MarkdownContent {
             node_path: path.clone(),
         },
-        FileType::Pdf | FileType::Image => RenderIntent::Asset {
-            node_path: path.clone(),
-            media_type: media_type_for_path(path.as_str()).to_string(),
-        },
+        FileType::Pdf | FileType::Image | FileType::Audio | FileType::Video => {
+            RenderIntent::Asset {
+                node_path: path.clone(),
+                media_type: media_type_for_path(path.as_str()).to_string(),
+            }
+        }
         FileType::Link => RenderIntent::Redirect {
             node_path: path.clone(),
         },
@@ -115,6 +117,7 @@ mod tests {
                     meta: make_dir_meta(path.rsplit('/').next().unwrap_or(path)),
                 })
                 .collect(),
+            banner: None,
         };
 
         let mut global = GlobalFs::empty();
@@ -252,6 +255,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn builds_asset_intents_for_audio_and_video() {
+        let fs = site(&["talks/intro.mp3", "talks/demo.webm"], &["talks"]);
+        for (path, media_type) in [
+            ("/talks/intro.mp3", "audio/mpeg"),
+            ("/talks/demo.webm", "video/webm"),
+        ] {
+            let resolution = resolve_route(&fs, &RouteRequest::new(path)).unwrap();
+            assert_eq!(
+                build_render_intent(&resolution).unwrap(),
+                RenderIntent::Asset {
+                    node_path: VirtualPath::from_absolute(path).unwrap(),
+                    media_type: media_type.to_string(),
+                }
+            );
+        }
+    }
+
     #[test]
     fn builds_redirect_intent_for_link_document() {
         let fs = site(&["links/x.link"], &["links"]);
@@ -148,6 +148,61 @@ mod tests {
         assert!(merged.get_entry(&p("/note.md")).is_some());
     }
 
+    #[test]
+    fn listing_is_the_union_of_base_and_overlay() {
+        let mut fs = base();
+        fs.upsert_file(
+            p("/blog/a.md"),
+            "a".into(),
+            file_meta(),
+            EntryExtensions::default(),
+        );
+        let mut cs = ChangeSet::new();
+        upsert(
+            &mut cs,
+            p("/blog/b.md"),
+            ChangeType::CreateFile {
+                content: "b".into(),
+                meta: file_meta(),
+                extensions: EntryExtensions::default(),
+            },
+        );
+        let merged = merged(&fs, &cs);
+        let names: Vec<_> = merged
+            .list_dir(&p("/blog"))
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect();
+        assert_eq!(names, ["a.md", "b.md"]);
+        // The base is left alone.
+        assert!(fs.get_entry(&p("/blog/b.md")).is_none());
+    }
+
+    #[test]
+    fn recreating_a_deleted_path_shadows_the_tombstone() {
+        let mut fs = base();
+        fs.upsert_file(
+            p("/a.md"),
+            "old".into(),
+            file_meta(),
+            EntryExtensions::default(),
+        );
+        let mut cs = ChangeSet::new();
+        upsert(&mut cs, p("/a.md"), ChangeType::DeleteFile);
+        upsert(
+            &mut cs,
+            p("/a.md"),
+            ChangeType::CreateFile {
+                content: "new".into(),
+                meta: file_meta(),
+                extensions: EntryExtensions::default(),
+            },
+        );
+        let merged = merged(&fs, &cs);
+        assert_eq!(merged.read_pending_text(&p("/a.md")).unwrap(), "new");
+    }
+
     #[test]
     fn delete_removes_from_merged() {
         let mut fs = base();
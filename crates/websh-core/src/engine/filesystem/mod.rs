@@ -8,6 +8,7 @@ mod intent;
 pub(crate) mod merge;
 mod routing;
 mod snapshot;
+mod tags;
 mod tree;
 
 pub use crate::domain::{NodeKind, RendererKind, TrustLevel};
@@ -16,10 +17,12 @@ pub use content::{BackendRegistry, ContentReadError, public_read_url, read_bytes
 pub use content_routes::{
     attestation_route_for_node_path, content_href_for_path, content_route_for_path,
 };
-pub use global_fs::{FsEngine, FsMutationError, GlobalFs, MountError};
+pub use global_fs::{FsEngine, FsMutationError, GlobalFs, LookupMiss, MountError};
 pub use intent::{RenderIntent, build_render_intent};
 pub use routing::{
-    ResolvedKind, RouteFrame, RouteRequest, RouteResolution, RouteSurface, canonicalize_user_path,
-    display_path_for, is_new_request_path, parent_request_path, request_path_for_canonical_path,
-    resolve_route, route_cwd,
+    DeepLink, ResolvedKind, RouteFrame, RouteRequest, RouteResolution, RouteSurface, UserPathError,
+    canonicalize_user_path, display_path_for, is_new_request_path, lookup_user_path,
+    parent_request_path, request_path_for_canonical_path, request_target_path, resolve_route,
+    route_cwd, route_for_user_path, shell_route_for_frame,
 };
+pub use tags::TagIndex;
@@ -1,35 +1,150 @@
 use std::collections::BTreeMap;
 
+use thiserror::Error;
+
 use crate::domain::{NodeKind, RendererKind, VirtualPath};
 
-use super::global_fs::GlobalFs;
+use super::global_fs::{GlobalFs, LookupMiss};
 use super::intent::RenderIntent;
 
 const SHELL_ROUTE_PREFIX: &str = "/websh";
 
 /// Browser request normalized into a filesystem-first input shape.
+///
+/// A `?query` after the path is parsed into [`DeepLink`] extras and kept
+/// out of `url_path`, so plain routes resolve the same with or without one.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RouteRequest {
     pub url_path: String,
+    pub link: DeepLink,
 }
 
 impl RouteRequest {
     pub fn new(url_path: impl Into<String>) -> Self {
         let raw = url_path.into();
-        if raw.is_empty() {
-            return Self {
-                url_path: "/".to_string(),
-            };
+        let (path, link) = match raw.split_once('?') {
+            Some((path, query)) => (path, DeepLink::parse(query)),
+            None => (raw.as_str(), DeepLink::default()),
+        };
+        let url_path = if path.is_empty() {
+            "/".to_string()
+        } else if path.starts_with('/') {
+            normalize_request_path(path)
+        } else {
+            normalize_request_path(&format!("/{}", path))
+        };
+        Self { url_path, link }
+    }
+
+    /// The request with `link` attached.
+    pub fn with_link(mut self, link: DeepLink) -> Self {
+        self.link = link;
+        self
+    }
+
+    /// Serialize back to a hash path: `url_path`, then `?query` when the
+    /// request carries deep-link extras.
+    pub fn to_path(&self) -> String {
+        if self.link.is_empty() {
+            self.url_path.clone()
+        } else {
+            format!("{}?{}", self.url_path, self.link.to_query())
         }
-        if raw.starts_with('/') {
-            return Self {
-                url_path: normalize_request_path(&raw),
+    }
+}
+
+/// One-shot extras a shared link can carry after the route path:
+/// `h=<heading id>` scrolls the reader to a heading, `q=<query>` pre-fills a
+/// search, and `l=<line>` highlights a line of a text file. Unknown keys and
+/// malformed values are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeepLink {
+    pub heading: Option<String>,
+    pub search: Option<String>,
+    pub line: Option<u32>,
+}
+
+impl DeepLink {
+    /// Parse a `key=value&...` query (without the leading `?`). The first
+    /// usable value of each key wins.
+    pub fn parse(query: &str) -> Self {
+        let mut link = Self::default();
+        for pair in query.split('&') {
+            let Some((key, value)) = pair.split_once('=') else {
+                continue;
             };
+            let Some(value) = percent_decode(value).filter(|value| !value.is_empty()) else {
+                continue;
+            };
+            match key {
+                "h" if link.heading.is_none() => link.heading = Some(value),
+                "q" if link.search.is_none() => link.search = Some(value),
+                "l" if link.line.is_none() => {
+                    link.line = value.parse::<u32>().ok().filter(|line| *line > 0);
+                }
+                _ => {}
+            }
         }
-        Self {
-            url_path: normalize_request_path(&format!("/{}", raw)),
+        link
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// The query string, keys in `h`, `q`, `l` order.
+    pub fn to_query(&self) -> String {
+        let mut pairs = Vec::new();
+        if let Some(heading) = &self.heading {
+            pairs.push(format!("h={}", percent_encode(heading)));
+        }
+        if let Some(search) = &self.search {
+            pairs.push(format!("q={}", percent_encode(search)));
+        }
+        if let Some(line) = self.line {
+            pairs.push(format!("l={line}"));
+        }
+        pairs.join("&")
+    }
+}
+
+/// Percent-encode everything but unreserved URL characters.
+fn percent_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
         }
     }
+    out
+}
+
+/// Decode `%XX` escapes and `+` as a space. `None` for malformed escapes
+/// or non-UTF-8 results.
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'%' => {
+                let hex = value.get(index + 1..index + 3)?;
+                out.push(u8::from_str_radix(hex, 16).ok()?);
+                index += 3;
+            }
+            b'+' => {
+                out.push(b' ');
+                index += 1;
+            }
+            byte => {
+                out.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8(out).ok()
 }
 
 /// User-facing route surface.
@@ -149,6 +264,16 @@ pub fn parent_request_path(path: &str) -> String {
     }
 }
 
+/// Filesystem path a request names by convention, ignoring the route index.
+/// Used to explain misses: shell routes strip their surface prefix.
+pub fn request_target_path(request: &RouteRequest) -> Option<(RouteSurface, VirtualPath)> {
+    let path = normalize_request_path(&request.url_path);
+    if is_reserved_request_path(&path) {
+        return surface_target_from_request(&path);
+    }
+    normalize_absolute_path(&path).map(|path| (RouteSurface::Content, path))
+}
+
 pub fn route_cwd(frame: &RouteFrame) -> VirtualPath {
     if let Some(cwd) = frame.resolution.params.get("cwd")
         && let Ok(path) = VirtualPath::from_absolute(cwd.clone())
@@ -166,6 +291,14 @@ pub fn route_cwd(frame: &RouteFrame) -> VirtualPath {
     }
 }
 
+/// Shell route opening the terminal where `frame` is: the listed
+/// directory, or the parent of the open file. Falls back to the nearest
+/// existing ancestor, as ledger category routes need not be directories.
+pub fn shell_route_for_frame(fs: &GlobalFs, frame: &RouteFrame) -> RouteRequest {
+    let cwd = fs.nearest_existing_ancestor(&route_cwd(frame));
+    RouteRequest::new(request_path_for_canonical_path(&cwd, RouteSurface::Shell))
+}
+
 pub fn display_path_for(path: &VirtualPath) -> String {
     if path.is_root() {
         return "~".to_string();
@@ -193,6 +326,57 @@ pub fn canonicalize_user_path(cwd: &VirtualPath, raw: &str) -> Option<VirtualPat
     normalize_absolute_path(&input)
 }
 
+/// Why a path typed by the user could not be opened.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum UserPathError {
+    #[error("invalid path '{0}'")]
+    Invalid(String),
+    #[error("no such file or directory: {0}")]
+    NotFound(String),
+    /// The path descends through a file, as in `post.md/sub`.
+    #[error("not a directory: {0}")]
+    NotADirectory(String),
+}
+
+/// Resolve a path typed by the user (`~`-prefixed, relative to `cwd`, or
+/// absolute) to an existing node. Returns its canonical path and whether it
+/// is a directory.
+pub fn lookup_user_path(
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+    raw: &str,
+) -> Result<(VirtualPath, bool), UserPathError> {
+    let path =
+        canonicalize_user_path(cwd, raw).ok_or_else(|| UserPathError::Invalid(raw.to_string()))?;
+    let is_dir = match fs.lookup(&path) {
+        Ok(entry) => entry.is_directory(),
+        Err(LookupMiss::NotFound) => return Err(UserPathError::NotFound(raw.to_string())),
+        Err(LookupMiss::NotADirectory) => {
+            return Err(UserPathError::NotADirectory(raw.to_string()));
+        }
+    };
+    Ok((path, is_dir))
+}
+
+/// Route that opens a typed path the way `cd` and `cat` do: directories on
+/// `dir_surface`, files in the content reader.
+pub fn route_for_user_path(
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+    raw: &str,
+    dir_surface: RouteSurface,
+) -> Result<RouteRequest, UserPathError> {
+    let (path, is_dir) = lookup_user_path(fs, cwd, raw)?;
+    let surface = if is_dir {
+        dir_surface
+    } else {
+        RouteSurface::Content
+    };
+    Ok(RouteRequest::new(request_path_for_canonical_path(
+        &path, surface,
+    )))
+}
+
 /// Resolve routes in priority order:
 /// 1. reserved shell route
 /// 2. derived index
@@ -335,6 +519,7 @@ fn classify_candidate(fs: &GlobalFs, candidate: &VirtualPath) -> Option<Resolved
         "md" | "html" => ResolvedKind::Page,
         "link" => ResolvedKind::Redirect,
         "png" | "jpg" | "jpeg" | "gif" | "webp" | "svg" => ResolvedKind::Asset,
+        "mp3" | "ogg" | "wav" | "mp4" | "webm" => ResolvedKind::Asset,
         "pdf" => ResolvedKind::Document,
         _ => ResolvedKind::Document,
     })
@@ -463,6 +648,7 @@ mod tests {
                     meta: make_dir_meta(path.rsplit('/').next().unwrap_or(path)),
                 })
                 .collect(),
+            banner: None,
         };
 
         let mut global = GlobalFs::empty();
@@ -594,6 +780,167 @@ mod tests {
         assert_eq!(canonicalize_user_path(&cwd, "/db").unwrap().as_str(), "/db");
     }
 
+    #[test]
+    fn user_paths_resolve_home_relative_and_mount_prefixed_inputs() {
+        let mut fs = site(&["blog/post.md"], &["blog"]);
+        let work = ScannedSubtree {
+            files: vec![ScannedFile {
+                path: "docs/spec.md".to_string(),
+                meta: make_meta(NodeKind::Page),
+                extensions: EntryExtensions::default(),
+            }],
+            directories: vec![ScannedDirectory {
+                path: "docs".to_string(),
+                meta: make_dir_meta("docs"),
+            }],
+            banner: None,
+        };
+        fs.mount_scanned_subtree(VirtualPath::from_absolute("/work").unwrap(), &work)
+            .unwrap();
+        let cwd = VirtualPath::from_absolute("/work/docs").unwrap();
+        let route = |raw: &str, surface| {
+            route_for_user_path(&fs, &cwd, raw, surface).map(|route| route.url_path)
+        };
+
+        assert_eq!(
+            route("~/blog", RouteSurface::Shell),
+            Ok("/websh/blog".to_string())
+        );
+        assert_eq!(
+            route("~/blog", RouteSurface::Content),
+            Ok("/blog".to_string())
+        );
+        assert_eq!(
+            route("~/blog/post.md", RouteSurface::Shell),
+            Ok("/blog/post.md".to_string())
+        );
+        assert_eq!(
+            route("/work/docs/spec.md", RouteSurface::Shell),
+            Ok("/work/docs/spec.md".to_string())
+        );
+        assert_eq!(
+            route("spec.md", RouteSurface::Shell),
+            Ok("/work/docs/spec.md".to_string())
+        );
+        assert_eq!(
+            route("..", RouteSurface::Shell),
+            Ok("/websh/work".to_string())
+        );
+    }
+
+    #[test]
+    fn user_path_errors_name_the_input() {
+        let fs = site(&["blog/post.md"], &["blog"]);
+        let cwd = VirtualPath::root();
+        assert_eq!(
+            lookup_user_path(&fs, &cwd, "~/blog/missing.md"),
+            Err(UserPathError::NotFound("~/blog/missing.md".to_string()))
+        );
+        assert_eq!(
+            lookup_user_path(&fs, &cwd, "~/blog").map(|(path, is_dir)| (path.to_string(), is_dir)),
+            Ok(("/blog".to_string(), true))
+        );
+        assert_eq!(
+            lookup_user_path(&fs, &cwd, "~/blog/post.md/sub"),
+            Err(UserPathError::NotADirectory(
+                "~/blog/post.md/sub".to_string()
+            ))
+        );
+        assert_eq!(
+            lookup_user_path(&fs, &cwd, "~/nope/post.md/sub"),
+            Err(UserPathError::NotFound("~/nope/post.md/sub".to_string()))
+        );
+    }
+
+    #[test]
+    fn plain_routes_carry_no_deep_link() {
+        for raw in ["/", "", "/blog/post.md", "blog/", "/websh/blog"] {
+            let request = RouteRequest::new(raw);
+            assert!(request.link.is_empty(), "{raw}");
+            assert_eq!(request.to_path(), request.url_path);
+        }
+        assert_eq!(RouteRequest::new("/blog/?").url_path, "/blog");
+        assert!(RouteRequest::new("/blog/?").link.is_empty());
+    }
+
+    #[test]
+    fn deep_link_params_parse_alone_and_together() {
+        let heading = RouteRequest::new("/blog/post.md?h=installation");
+        assert_eq!(heading.url_path, "/blog/post.md");
+        assert_eq!(heading.link.heading.as_deref(), Some("installation"));
+
+        let search = RouteRequest::new("/websh/blog/?q=rust");
+        assert_eq!(search.url_path, "/websh/blog");
+        assert_eq!(search.link.search.as_deref(), Some("rust"));
+
+        let line = RouteRequest::new("/notes/todo.txt?l=42");
+        assert_eq!(line.link.line, Some(42));
+
+        let all = RouteRequest::new("/a.md?l=7&q=wasm+rust&h=setup");
+        assert_eq!(
+            all.link,
+            DeepLink {
+                heading: Some("setup".to_string()),
+                search: Some("wasm rust".to_string()),
+                line: Some(7),
+            }
+        );
+    }
+
+    #[test]
+    fn deep_link_ignores_unknown_and_malformed_params() {
+        let request = RouteRequest::new("/a.md?x=1&l=abc&h=&q=%ZZ&l=0&flag&h=intro&h=later");
+        assert_eq!(request.url_path, "/a.md");
+        assert_eq!(
+            request.link,
+            DeepLink {
+                heading: Some("intro".to_string()),
+                search: None,
+                line: None,
+            }
+        );
+        assert!(RouteRequest::new("/a.md?utm_source=feed").link.is_empty());
+    }
+
+    #[test]
+    fn deep_links_round_trip_through_to_path() {
+        let link = DeepLink {
+            heading: Some("getting-started".to_string()),
+            search: Some("rust & wasm/2024".to_string()),
+            line: Some(12),
+        };
+        let request = RouteRequest::new("/blog/post.md").with_link(link.clone());
+        let path = request.to_path();
+        assert_eq!(
+            path,
+            "/blog/post.md?h=getting-started&q=rust%20%26%20wasm%2F2024&l=12"
+        );
+        let parsed = RouteRequest::new(path);
+        assert_eq!(parsed, request);
+        assert_eq!(parsed.link, link);
+    }
+
+    #[test]
+    fn request_target_path_maps_both_surfaces() {
+        let target = |url: &str| {
+            request_target_path(&RouteRequest::new(url))
+                .map(|(surface, path)| (surface, path.to_string()))
+        };
+        assert_eq!(
+            target("/blog/missing.md"),
+            Some((RouteSurface::Content, "/blog/missing.md".to_string()))
+        );
+        assert_eq!(
+            target("/websh/blog/gone"),
+            Some((RouteSurface::Shell, "/blog/gone".to_string()))
+        );
+        assert_eq!(
+            target("/websh"),
+            Some((RouteSurface::Shell, "/".to_string()))
+        );
+        assert_eq!(target("/"), Some((RouteSurface::Content, "/".to_string())));
+    }
+
     #[test]
     fn request_paths_are_surface_aware() {
         let path = VirtualPath::from_absolute("/blog/hello.md").unwrap();
@@ -622,4 +969,52 @@ mod tests {
         assert!(!is_new_request_path(&RouteRequest::new("/edit")));
         assert!(!is_new_request_path(&RouteRequest::new("/ledger")));
     }
+
+    #[test]
+    fn shell_route_for_frame_opens_the_terminal_where_the_frame_is() {
+        let fs = site(&["blog/post.md", "about.md"], &["blog"]);
+        let frame = |path: &str| {
+            let request = RouteRequest::new(path);
+            let resolution = resolve_route(&fs, &request).unwrap();
+            let intent = super::super::build_render_intent(&resolution).unwrap();
+            RouteFrame {
+                request,
+                resolution,
+                intent,
+            }
+        };
+        let shell_route = |path: &str| shell_route_for_frame(&fs, &frame(path)).url_path;
+
+        assert_eq!(shell_route("/blog"), "/websh/blog");
+        assert_eq!(shell_route("/blog/post.md"), "/websh/blog");
+        assert_eq!(shell_route("/about.md"), "/websh");
+        assert_eq!(shell_route("/websh/blog"), "/websh/blog");
+    }
+
+    #[test]
+    fn shell_route_for_frame_falls_back_to_an_existing_ancestor() {
+        let fs = site(&["blog/post.md"], &["blog"]);
+        let frame = |node_path: &str| RouteFrame {
+            request: RouteRequest::new(node_path),
+            resolution: RouteResolution {
+                request_path: node_path.to_string(),
+                surface: RouteSurface::Content,
+                node_path: VirtualPath::from_absolute(node_path).unwrap(),
+                kind: ResolvedKind::Directory,
+                params: BTreeMap::new(),
+            },
+            intent: RenderIntent::DirectoryListing {
+                node_path: VirtualPath::from_absolute(node_path).unwrap(),
+            },
+        };
+
+        assert_eq!(
+            shell_route_for_frame(&fs, &frame("/writing")).url_path,
+            "/websh"
+        );
+        assert_eq!(
+            shell_route_for_frame(&fs, &frame("/blog/drafts")).url_path,
+            "/websh/blog"
+        );
+    }
 }
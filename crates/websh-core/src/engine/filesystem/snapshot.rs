@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::domain::{FsEntry, NodeMetadata, VirtualPath};
+use crate::domain::{DirChildren, FsEntry, NodeMetadata, VirtualPath};
 use crate::ports::{ScannedDirectory, ScannedFile, ScannedSubtree};
 
 use super::tree::directory_metadata;
@@ -12,7 +12,7 @@ pub(super) fn scanned_subtree_root(snapshot: &ScannedSubtree) -> FsEntry {
         .map(|dir| (dir.path.clone(), dir))
         .collect();
 
-    let mut children = HashMap::new();
+    let mut children = DirChildren::new();
 
     for file in &snapshot.files {
         insert_scanned_file(&mut children, file, &dir_meta_map);
@@ -35,8 +35,11 @@ pub(super) fn scanned_subtree_root(snapshot: &ScannedSubtree) -> FsEntry {
     }
 }
 
+/// Entries below a path that is already a file are dropped, whatever order
+/// they arrive in; `lint_manifest_snapshot` reports each one so the drop is
+/// visible at boot and through `manifest lint`.
 fn insert_scanned_file(
-    tree: &mut HashMap<String, FsEntry>,
+    tree: &mut DirChildren,
     file: &ScannedFile,
     dir_meta_map: &HashMap<String, &ScannedDirectory>,
 ) {
@@ -82,7 +85,7 @@ fn insert_scanned_file(
 }
 
 fn ensure_scanned_directory(
-    tree: &mut HashMap<String, FsEntry>,
+    tree: &mut DirChildren,
     path: &str,
     dir_meta_map: &HashMap<String, &ScannedDirectory>,
 ) {
@@ -113,7 +116,7 @@ fn scanned_directory_entry(
     dir_meta_map: &HashMap<String, &ScannedDirectory>,
 ) -> FsEntry {
     FsEntry::Directory {
-        children: HashMap::new(),
+        children: DirChildren::new(),
         meta: dir_meta_map
             .get(path)
             .map(|dir| dir.meta.clone())
@@ -124,14 +127,11 @@ fn scanned_directory_entry(
 pub(super) fn collect_scanned_files(
     mount_root: &VirtualPath,
     prefix: &str,
-    children: &HashMap<String, FsEntry>,
+    children: &DirChildren,
     excluded_roots: &[VirtualPath],
     out: &mut Vec<ScannedFile>,
 ) {
-    let mut names: Vec<&String> = children.keys().collect();
-    names.sort();
-    for name in names {
-        let entry = &children[name];
+    for (name, entry) in children {
         let rel = if prefix.is_empty() {
             name.clone()
         } else {
@@ -170,14 +170,11 @@ pub(super) fn collect_scanned_files(
 pub(super) fn collect_scanned_directories(
     mount_root: &VirtualPath,
     prefix: &str,
-    children: &HashMap<String, FsEntry>,
+    children: &DirChildren,
     excluded_roots: &[VirtualPath],
     out: &mut Vec<ScannedDirectory>,
 ) {
-    let mut names: Vec<&String> = children.keys().collect();
-    names.sort();
-    for name in names {
-        let entry = &children[name];
+    for (name, entry) in children {
         if let FsEntry::Directory {
             children: sub,
             meta,
@@ -208,7 +205,7 @@ pub(super) fn collect_scanned_directories(
 fn exportable_children_empty(
     mount_root: &VirtualPath,
     prefix: &str,
-    children: &HashMap<String, FsEntry>,
+    children: &DirChildren,
     excluded_roots: &[VirtualPath],
 ) -> bool {
     !children.iter().any(|(name, _)| {
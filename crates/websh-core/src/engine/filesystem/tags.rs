@@ -0,0 +1,104 @@
+//! Tag → path index over node metadata.
+
+use std::collections::BTreeMap;
+
+use crate::domain::{NodeMetadata, VirtualPath};
+
+/// Every tag carried by a set of nodes, with the paths that carry it.
+///
+/// Tags are matched case-insensitively and stored lowercased; each tag's
+/// paths are sorted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TagIndex {
+    tags: BTreeMap<String, Vec<VirtualPath>>,
+}
+
+impl TagIndex {
+    pub fn build<'a>(entries: impl IntoIterator<Item = (VirtualPath, &'a NodeMetadata)>) -> Self {
+        let mut tags: BTreeMap<String, Vec<VirtualPath>> = BTreeMap::new();
+        for (path, meta) in entries {
+            for tag in meta.tags().unwrap_or_default() {
+                let key = normalize_tag(tag);
+                if key.is_empty() {
+                    continue;
+                }
+                tags.entry(key).or_default().push(path.clone());
+            }
+        }
+        for paths in tags.values_mut() {
+            paths.sort();
+            paths.dedup();
+        }
+        Self { tags }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// `(tag, number of paths)` in tag order.
+    pub fn counts(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.tags
+            .iter()
+            .map(|(tag, paths)| (tag.as_str(), paths.len()))
+    }
+
+    /// Paths carrying `tag`; empty when no node does.
+    pub fn paths(&self, tag: &str) -> &[VirtualPath] {
+        self.tags
+            .get(&normalize_tag(tag))
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Fields, NodeKind, SCHEMA_VERSION};
+
+    fn tagged(tags: &[&str]) -> NodeMetadata {
+        NodeMetadata {
+            schema: SCHEMA_VERSION,
+            kind: NodeKind::Page,
+            authored: Fields {
+                tags: Some(tags.iter().map(|tag| tag.to_string()).collect()),
+                ..Fields::default()
+            },
+            derived: Fields::default(),
+        }
+    }
+
+    fn vp(path: &str) -> VirtualPath {
+        VirtualPath::from_absolute(path).unwrap()
+    }
+
+    #[test]
+    fn groups_paths_by_normalized_tag() {
+        let a = tagged(&["Rust", "wasm"]);
+        let b = tagged(&["rust ", ""]);
+        let c = tagged(&[]);
+        let index = TagIndex::build([
+            (vp("/blog/a.md"), &a),
+            (vp("/blog/b.md"), &b),
+            (vp("/blog/c.md"), &c),
+        ]);
+
+        assert_eq!(
+            index.counts().collect::<Vec<_>>(),
+            vec![("rust", 2), ("wasm", 1)]
+        );
+        assert_eq!(index.paths("RUST"), &[vp("/blog/a.md"), vp("/blog/b.md")]);
+        assert!(index.paths("missing").is_empty());
+    }
+
+    #[test]
+    fn repeated_tag_on_one_node_counts_once() {
+        let a = tagged(&["rust", "Rust"]);
+        let index = TagIndex::build([(vp("/a.md"), &a)]);
+        assert_eq!(index.paths("rust"), &[vp("/a.md")]);
+    }
+}
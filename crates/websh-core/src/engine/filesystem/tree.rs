@@ -1,7 +1,5 @@
-use std::collections::HashMap;
-
 use crate::domain::{
-    DirEntry, Fields, FsEntry, NodeKind, NodeMetadata, SCHEMA_VERSION, VirtualPath,
+    DirChildren, DirEntry, Fields, FsEntry, NodeKind, NodeMetadata, SCHEMA_VERSION, VirtualPath,
 };
 
 use super::global_fs::FsMutationError;
@@ -45,44 +43,30 @@ pub(super) fn collect_metadata_entries<'a>(
     }
 }
 
-pub(super) fn sorted_dir_entries(
-    base: &VirtualPath,
-    children: &HashMap<String, FsEntry>,
-) -> Vec<DirEntry> {
-    let mut items: Vec<_> = children
-        .iter()
-        .map(|(name, entry)| {
-            let is_dir = entry.is_directory();
-            let title = match entry {
-                FsEntry::Directory { meta, .. } => {
-                    meta.title().unwrap_or(name.as_str()).to_string()
-                }
-                FsEntry::File { meta, .. } => meta.title().unwrap_or(name.as_str()).to_string(),
-            };
-            DirEntry {
-                name: name.clone(),
-                path: base.join(name),
-                is_dir,
-                title,
-                meta: Some(entry.meta().clone()),
-            }
-        })
-        .collect();
-
-    items.sort_by(|a, b| {
-        let a_hidden = a.name.starts_with('.');
-        let b_hidden = b.name.starts_with('.');
-
-        match (a.is_dir, b.is_dir, a_hidden, b_hidden) {
-            (true, false, _, _) => std::cmp::Ordering::Less,
-            (false, true, _, _) => std::cmp::Ordering::Greater,
-            (_, _, false, true) => std::cmp::Ordering::Less,
-            (_, _, true, false) => std::cmp::Ordering::Greater,
-            _ => a.name.cmp(&b.name),
+/// Entries of `children` in canonical order ([`DirEntry::sort_key`]).
+/// The children already iterate by name, so bucketing them by kind and
+/// visibility keeps each bucket sorted: one pass, no sort.
+pub(super) fn sorted_dir_entries(base: &VirtualPath, children: &DirChildren) -> Vec<DirEntry> {
+    let mut buckets: [Vec<DirEntry>; 4] = Default::default();
+    for entry in children.iter().map(|(name, entry)| {
+        let is_dir = entry.is_directory();
+        let title = match entry {
+            FsEntry::Directory { meta, .. } => meta.title().unwrap_or(name.as_str()).to_string(),
+            FsEntry::File { meta, .. } => meta.title().unwrap_or(name.as_str()).to_string(),
+        };
+        DirEntry {
+            name: name.clone(),
+            path: base.join(name),
+            is_dir,
+            title,
+            meta: Some(entry.meta().clone()),
+            masked: false,
         }
-    });
-
-    items
+    }) {
+        let (is_file, hidden, _) = entry.sort_key();
+        buckets[usize::from(is_file) * 2 + usize::from(hidden)].push(entry);
+    }
+    buckets.into_iter().flatten().collect()
 }
 
 pub(super) fn insert_tree_entry(
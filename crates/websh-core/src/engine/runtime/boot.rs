@@ -103,6 +103,7 @@ mod tests {
                 path: "".to_string(),
                 meta: dir_meta("home"),
             }],
+            banner: None,
         };
 
         let fs =
@@ -49,9 +49,12 @@ pub(super) async fn prepare_commit(
 
     merge::apply_staged_changes_to_global_for_root(&mut merged, &normalized_changes, mount_root);
 
-    let merged_snapshot = merged
+    let mut merged_snapshot = merged
         .export_mount_snapshot(mount_root)
         .ok_or_else(|| StorageError::BadRequest(format!("missing mount root {mount_root}")))?;
+    // The banner lives on the manifest, not on any node, so the export
+    // can't reconstruct it.
+    merged_snapshot.banner = base_snapshot.banner.clone();
 
     Ok(CommitRequest {
         delta,
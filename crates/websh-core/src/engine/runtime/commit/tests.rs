@@ -104,6 +104,7 @@ async fn prepared_commit_contains_merged_staged_snapshot() {
                 extensions: EntryExtensions::default(),
             }],
             directories: vec![],
+            banner: Some("welcome".to_string()),
         })),
     });
     let mut changes = ChangeSet::new();
@@ -146,6 +147,7 @@ async fn prepared_commit_contains_merged_staged_snapshot() {
         .map(|file| file.path.as_str())
         .collect();
     assert_eq!(paths, vec!["keep.md", "new.md"]);
+    assert_eq!(request.merged_snapshot.banner.as_deref(), Some("welcome"));
     assert!(request.delta.deletions.is_empty());
     assert_eq!(request.delta.additions.len(), 1);
     assert_eq!(request.cleanup_paths, vec![p("/new.md")]);
@@ -196,6 +198,7 @@ async fn prepared_commit_uses_backend_commit_base_expected_head() {
                     extensions: EntryExtensions::default(),
                 }],
                 directories: vec![],
+                banner: None,
             },
         })),
     });
@@ -271,6 +274,7 @@ async fn prepared_commit_rejects_deleting_commit_root() {
                 extensions: EntryExtensions::default(),
             }],
             directories: vec![],
+            banner: None,
         })),
     });
     let mut changes = ChangeSet::new();
@@ -315,6 +319,7 @@ async fn prepared_commit_expands_directory_delete_to_descendant_files() {
                 },
             ],
             directories: vec![],
+            banner: None,
         })),
     });
     let mut changes = ChangeSet::new();
@@ -350,6 +355,7 @@ async fn prepared_commit_delete_directory_suppresses_descendant_additions() {
                 extensions: EntryExtensions::default(),
             }],
             directories: vec![],
+            banner: None,
         })),
     });
     let mut changes = ChangeSet::new();
@@ -398,6 +404,7 @@ async fn prepared_commit_collapses_nested_directory_deletes() {
                 },
             ],
             directories: vec![],
+            banner: None,
         })),
     });
     let mut changes = ChangeSet::new();
@@ -443,6 +450,7 @@ async fn update_file_change_propagates_meta_into_exported_snapshot() {
                 extensions: EntryExtensions::default(),
             }],
             directories: vec![],
+            banner: None,
         })),
     });
     let mut changes = ChangeSet::new();
@@ -494,6 +502,7 @@ async fn update_file_change_propagates_extensions_into_exported_snapshot() {
                 },
             }],
             directories: vec![],
+            banner: None,
         })),
     });
     let new_ext = EntryExtensions {
@@ -549,6 +558,7 @@ async fn update_file_with_none_meta_preserves_base_scan_meta() {
                 extensions: EntryExtensions::default(),
             }],
             directories: vec![],
+            banner: None,
         })),
     });
     let mut changes = ChangeSet::new();
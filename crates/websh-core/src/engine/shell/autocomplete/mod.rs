@@ -4,6 +4,10 @@
 //! - Command names (e.g., "cl" → "clear")
 //! - Directory paths for `cd`, `ls` commands
 //! - File paths for `cat` commands
+//! - Command flags when the current token starts with `-` (e.g., "ls -" → "ls -l")
+//! - Command names as the topic of `help` and `man` (e.g., "man gr" → "man grep")
+//! - Names with spaces, escaped (`My\ Notes.md`) or inside the quote the
+//!   user opened (`"My Notes.md"`)
 //!
 //! The autocomplete system supports:
 //! - Single match: Complete immediately
@@ -15,6 +19,10 @@ use crate::domain::VirtualPath;
 use crate::engine::filesystem::{GlobalFs, canonicalize_user_path};
 use crate::engine::shell::Command;
 
+mod word;
+
+use word::{PartialWord, Quoting, split_last_word};
+
 /// Result of an autocomplete attempt.
 #[derive(Clone, Debug, PartialEq)]
 pub enum AutocompleteResult {
@@ -26,15 +34,21 @@ pub enum AutocompleteResult {
     None,
 }
 
-/// Commands that accept directory paths as arguments.
-const DIR_COMMANDS: &[&str] = &["cd", "ls", "mkdir", "rmdir"];
+/// Commands that accept only directory paths as arguments.
+const DIR_COMMANDS: &[&str] = &["cd", "feed", "mkdir", "rmdir"];
 
 /// Commands that accept file paths as arguments.
 ///
 /// These commands also match directories during tab completion so users
 /// can drill into subdirectories — the filter just doesn't restrict to
-/// directories only (unlike `DIR_COMMANDS`).
-const FILE_COMMANDS: &[&str] = &["cat", "touch", "rm", "edit"];
+/// directories only (unlike `DIR_COMMANDS`). `ls` lists a file as readily
+/// as a directory, so it lives here.
+const FILE_COMMANDS: &[&str] = &[
+    "cat", "explorer", "ls", "touch", "rm", "edit", "sha256", "tee",
+];
+
+/// Commands whose single argument is another command's name.
+const TOPIC_COMMANDS: &[&str] = &["help", "man"];
 
 /// Subcommands for `sync` (first positional arg).
 const SYNC_SUBCOMMANDS: &[&str] = &["status", "commit", "refresh", "auth"];
@@ -47,9 +61,9 @@ const SYNC_AUTH_SUBCOMMANDS: &[&str] = &["set", "clear"];
 enum CompletionMode {
     /// Complete command names only.
     Command,
-    /// Complete directory paths (for cd, ls).
+    /// Complete directory paths (for cd, mkdir, rmdir).
     DirectoryPath,
-    /// Complete file paths (for cat).
+    /// Complete file and directory paths (for cat, ls).
     FilePath,
     /// No completion available.
     None,
@@ -93,7 +107,8 @@ struct ParsedPath<'a> {
 }
 
 impl<'a> ParsedPath<'a> {
-    /// Parse a partial path and resolve the search directory.
+    /// Parse a partial path, already unquoted, and resolve the search
+    /// directory.
     fn parse(partial: &'a str, cwd: &VirtualPath, _fs: &GlobalFs) -> Option<Self> {
         let (dir_part, name_part) = match partial.rfind('/') {
             Some(idx) => (&partial[..=idx], &partial[idx + 1..]),
@@ -114,6 +129,12 @@ impl<'a> ParsedPath<'a> {
     }
 }
 
+/// `text` as one unquoted word, escaped the way completion writes names
+/// back: `My Docs/README.md` becomes `My\ Docs/README.md`.
+pub(crate) fn escape_word(text: &str) -> String {
+    Quoting::Escaped.escape(text)
+}
+
 /// Perform autocomplete on Tab press.
 ///
 /// Returns a completion result based on the current input and filesystem state.
@@ -131,15 +152,46 @@ pub fn autocomplete(input: &str, cwd: &VirtualPath, fs: &GlobalFs) -> Autocomple
         return complete_sync(parts[1]);
     }
 
+    if let Some(topic) = command_topic(&parts) {
+        return suggest_subcommand(parts[0], topic, Command::names());
+    }
+
+    if mode != CompletionMode::Command
+        && let Some((head, partial)) = split_flag_token(parts[1])
+    {
+        return complete_flag(parts[0], head, partial);
+    }
+
     match mode {
         CompletionMode::Command => complete_command(parts[0]),
         CompletionMode::DirectoryPath | CompletionMode::FilePath => {
-            complete_path(parts[0], parts[1], cwd, fs, mode.dirs_only())
+            let (head, partial) = split_last_word(parts[1]);
+            let cmd = format!("{} {}", parts[0], head);
+            complete_path(cmd.trim_end(), partial, cwd, fs, mode.dirs_only())
         }
         CompletionMode::None => AutocompleteResult::None,
     }
 }
 
+/// Complete a bare path (no command word), matching files and directories.
+///
+/// Used by path inputs outside the terminal prompt. Completions carry the
+/// path alone; a completed directory keeps its trailing `/`.
+pub fn autocomplete_path(partial: &str, cwd: &VirtualPath, fs: &GlobalFs) -> AutocompleteResult {
+    let strip = |completed: &str| {
+        let path = completed.strip_prefix("cat ").unwrap_or(completed);
+        path.strip_suffix(' ').unwrap_or(path).to_string()
+    };
+    let word = PartialWord::literal(partial.trim_start());
+    match complete_word("cat", &word, cwd, fs, false) {
+        AutocompleteResult::Single(completed) => AutocompleteResult::Single(strip(&completed)),
+        AutocompleteResult::Multiple(common, names) => {
+            AutocompleteResult::Multiple(strip(&common), names)
+        }
+        AutocompleteResult::None => AutocompleteResult::None,
+    }
+}
+
 /// Get autocomplete suggestion for ghost text hint (while typing).
 ///
 /// Returns the suffix that would complete the current input.
@@ -155,15 +207,38 @@ pub fn get_hint(input: &str, cwd: &VirtualPath, fs: &GlobalFs) -> Option<String>
         return get_sync_hint(parts[1]);
     }
 
+    if let Some(topic) = command_topic(&parts) {
+        return subcommand_hint(topic, Command::names());
+    }
+
+    if mode != CompletionMode::Command
+        && let Some((_, partial)) = split_flag_token(parts[1])
+    {
+        return get_flag_hint(parts[0], partial);
+    }
+
     match mode {
         CompletionMode::Command => get_command_hint(parts[0]),
         CompletionMode::DirectoryPath | CompletionMode::FilePath => {
-            get_path_hint(parts[1], cwd, fs, mode.dirs_only())
+            get_path_hint(split_last_word(parts[1]).1, cwd, fs, mode.dirs_only())
         }
         CompletionMode::None => None,
     }
 }
 
+/// The partial topic of `help <topic>` / `man <topic>`, if `parts` is one.
+fn command_topic<'a>(parts: &[&'a str]) -> Option<&'a str> {
+    match parts {
+        [command, topic]
+            if TOPIC_COMMANDS.contains(&command.to_lowercase().as_str())
+                && !topic.contains(' ') =>
+        {
+            Some(topic)
+        }
+        _ => None,
+    }
+}
+
 /// Complete command name.
 fn complete_command(partial: &str) -> AutocompleteResult {
     let partial_lower = partial.to_lowercase();
@@ -192,6 +267,49 @@ fn get_command_hint(partial: &str) -> Option<String> {
         .map(|cmd| cmd[partial.len()..].to_string())
 }
 
+/// Split the argument tail into the text before the last token and the last
+/// token itself, if that token is a flag (starts with `-`).
+fn split_flag_token(tail: &str) -> Option<(&str, &str)> {
+    let (head, token) = split_last_word(tail);
+    token.starts_with('-').then_some((head, token))
+}
+
+/// Get the command's flags that extend the partial flag.
+///
+/// Flags are case-sensitive (`-E` and `-e` differ), unlike command names.
+fn matching_flags(cmd: &str, partial: &str) -> Vec<&'static str> {
+    Command::flags(cmd)
+        .iter()
+        .map(|flag| flag.flag)
+        .filter(|flag| flag.starts_with(partial))
+        .collect()
+}
+
+/// Complete a flag for `cmd`. `head` is the argument text before the flag.
+fn complete_flag(cmd: &str, head: &str, partial: &str) -> AutocompleteResult {
+    let matches: Vec<String> = matching_flags(cmd, partial)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+
+    match matches.len() {
+        0 => AutocompleteResult::None,
+        1 => AutocompleteResult::Single(format!("{} {}{} ", cmd, head, matches[0])),
+        _ => {
+            let common = find_common_prefix(&matches);
+            AutocompleteResult::Multiple(format!("{} {}{}", cmd, head, common), matches)
+        }
+    }
+}
+
+/// Get hint for flag completion.
+fn get_flag_hint(cmd: &str, partial: &str) -> Option<String> {
+    matching_flags(cmd, partial)
+        .into_iter()
+        .find(|flag| *flag != partial)
+        .map(|flag| flag[partial.len()..].to_string())
+}
+
 /// Complete `sync` subcommands.
 ///
 /// `tail` is everything after `sync ` — e.g. `""`, `"s"`, `"auth "`, `"auth s"`,
@@ -256,7 +374,8 @@ fn subcommand_hint(partial: &str, options: &[&str]) -> Option<String> {
         .map(|opt| opt[partial.len()..].to_string())
 }
 
-/// Complete file/directory path.
+/// Complete file/directory path. `partial` is the word as typed, quotes
+/// and escapes included.
 fn complete_path(
     cmd: &str,
     partial: &str,
@@ -264,7 +383,18 @@ fn complete_path(
     fs: &GlobalFs,
     dirs_only: bool,
 ) -> AutocompleteResult {
-    let Some(parsed) = ParsedPath::parse(partial, cwd, fs) else {
+    complete_word(cmd, &PartialWord::parse(partial), cwd, fs, dirs_only)
+}
+
+/// Complete an unquoted path word, writing matches back in its quoting.
+fn complete_word(
+    cmd: &str,
+    word: &PartialWord,
+    cwd: &VirtualPath,
+    fs: &GlobalFs,
+    dirs_only: bool,
+) -> AutocompleteResult {
+    let Some(parsed) = ParsedPath::parse(&word.value, cwd, fs) else {
         return AutocompleteResult::None;
     };
 
@@ -273,7 +403,7 @@ fn complete_path(
     };
 
     let matches = get_matching_entries(&entries, parsed.name_part, dirs_only);
-    build_path_result(cmd, &parsed, matches)
+    build_path_result(cmd, word, &parsed, matches)
 }
 
 /// Get hint for path completion.
@@ -283,7 +413,8 @@ fn get_path_hint(
     fs: &GlobalFs,
     dirs_only: bool,
 ) -> Option<String> {
-    let parsed = ParsedPath::parse(partial, cwd, fs)?;
+    let word = PartialWord::parse(partial);
+    let parsed = ParsedPath::parse(&word.value, cwd, fs)?;
     let entries = fs.list_dir(&parsed.search_dir)?;
     let matches = get_matching_entries(&entries, parsed.name_part, dirs_only);
 
@@ -293,8 +424,9 @@ fn get_path_hint(
         .iter()
         .find(|(name, _)| name.to_lowercase() != name_lower)
         .map(|(name, is_dir)| {
-            let suffix = if *is_dir { "/" } else { "" };
-            format!("{}{}", &name[parsed.name_part.len()..], suffix)
+            let rest = word.quoting.escape(&name[parsed.name_part.len()..]);
+            let suffix = if *is_dir { "/" } else { word.quoting.close() };
+            format!("{}{}", rest, suffix)
         })
 }
 
@@ -320,6 +452,7 @@ fn get_matching_entries<'a>(
 /// Build the autocomplete result from matched paths.
 fn build_path_result(
     cmd: &str,
+    word: &PartialWord,
     parsed: &ParsedPath,
     matches: Vec<(&String, bool)>,
 ) -> AutocompleteResult {
@@ -336,8 +469,13 @@ fn build_path_result(
         0 => AutocompleteResult::None,
         1 => {
             let (path, is_dir) = &full_matches[0];
-            let suffix = if *is_dir { "/" } else { " " };
-            AutocompleteResult::Single(format!("{} {}{}", cmd, path, suffix))
+            // A directory keeps an opened quote open for the next segment.
+            let suffix = if *is_dir {
+                "/".to_string()
+            } else {
+                format!("{} ", word.quoting.close())
+            };
+            AutocompleteResult::Single(format!("{} {}{}", cmd, quoted(word, path), suffix))
         }
         _ => {
             let paths: Vec<String> = full_matches.iter().map(|(p, _)| p.clone()).collect();
@@ -355,12 +493,17 @@ fn build_path_result(
                 })
                 .collect();
 
-            let common_with_cmd = format!("{} {}", cmd, common);
+            let common_with_cmd = format!("{} {}", cmd, quoted(word, &common));
             AutocompleteResult::Multiple(common_with_cmd, display_names)
         }
     }
 }
 
+/// `path` opened and escaped in the quoting of `word`, still unclosed.
+fn quoted(word: &PartialWord, path: &str) -> String {
+    format!("{}{}", word.quoting.open(), word.quoting.escape(path))
+}
+
 /// Find the common prefix of multiple strings (case-insensitive).
 ///
 /// Operates on Unicode codepoints (chars), not bytes — safe for multi-byte UTF-8.
@@ -492,6 +635,14 @@ mod tests {
     /// These names all share the prefix `h`, so a `/h`-style partial
     /// exercises both the dir-only and file+dir classification paths.
     fn write_cmd_fixture() -> GlobalFs {
+        fixture(
+            &["hello.md", "hero.md", "home/readme.md", "help/readme.md"],
+            &[("home", "Home"), ("help", "Help")],
+        )
+    }
+
+    /// Files and `(path, title)` directories mounted at `/`.
+    fn fixture(files: &[&str], directories: &[(&str, &str)]) -> GlobalFs {
         use crate::domain::{EntryExtensions, Fields, NodeKind, NodeMetadata, SCHEMA_VERSION};
         use crate::engine::filesystem::GlobalFs;
         use crate::ports::{ScannedDirectory, ScannedFile, ScannedSubtree};
@@ -515,39 +666,22 @@ mod tests {
             }
         }
         let snapshot = ScannedSubtree {
-            files: vec![
-                ScannedFile {
-                    path: "hello.md".to_string(),
-                    meta: file_meta(),
-                    extensions: EntryExtensions::default(),
-                },
-                ScannedFile {
-                    path: "hero.md".to_string(),
-                    meta: file_meta(),
-                    extensions: EntryExtensions::default(),
-                },
-                // Give the dirs a file each so they exist as directories.
-                ScannedFile {
-                    path: "home/readme.md".to_string(),
-                    meta: file_meta(),
-                    extensions: EntryExtensions::default(),
-                },
-                ScannedFile {
-                    path: "help/readme.md".to_string(),
+            files: files
+                .iter()
+                .map(|path| ScannedFile {
+                    path: path.to_string(),
                     meta: file_meta(),
                     extensions: EntryExtensions::default(),
-                },
-            ],
-            directories: vec![
-                ScannedDirectory {
-                    path: "home".to_string(),
-                    meta: directory_meta("Home"),
-                },
-                ScannedDirectory {
-                    path: "help".to_string(),
-                    meta: directory_meta("Help"),
-                },
-            ],
+                })
+                .collect(),
+            directories: directories
+                .iter()
+                .map(|(path, title)| ScannedDirectory {
+                    path: path.to_string(),
+                    meta: directory_meta(title),
+                })
+                .collect(),
+            banner: None,
         };
         let mut fs = GlobalFs::empty();
         fs.mount_scanned_subtree(VirtualPath::root(), &snapshot)
@@ -674,6 +808,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bare_path_completion_omits_command_word() {
+        let fs = write_cmd_fixture();
+        let root = VirtualPath::root();
+        assert_eq!(
+            autocomplete_path("hom", &root, &fs),
+            AutocompleteResult::Single("home/".to_string())
+        );
+        assert_eq!(
+            autocomplete_path("hel", &root, &fs),
+            AutocompleteResult::Multiple(
+                "hel".to_string(),
+                vec!["help/".to_string(), "hello.md".to_string()]
+            )
+        );
+        assert_eq!(
+            autocomplete_path("/hero", &root, &fs),
+            AutocompleteResult::Single("/hero.md".to_string())
+        );
+    }
+
     #[test]
     fn test_classification_write_commands() {
         let (mode, _) = CompletionMode::from_input("touch foo");
@@ -688,6 +843,44 @@ mod tests {
         assert_eq!(mode, CompletionMode::DirectoryPath);
     }
 
+    #[test]
+    fn test_cd_completes_dirs_only() {
+        let fs = write_cmd_fixture();
+        let root = VirtualPath::root();
+        let names = matches_set(&autocomplete("cd h", &root, &fs));
+        assert!(names.iter().any(|n| n == "home/"), "got {:?}", names);
+        assert!(names.iter().any(|n| n == "help/"), "got {:?}", names);
+        assert!(
+            !names.iter().any(|n| n.ends_with(".md")),
+            "cd must NOT surface files; got {:?}",
+            names
+        );
+        assert_eq!(
+            autocomplete("cd hel", &root, &fs),
+            AutocompleteResult::Single("cd help/".to_string())
+        );
+        assert_eq!(
+            autocomplete("cd help/r", &root, &fs),
+            AutocompleteResult::None
+        );
+        assert_eq!(get_hint("cd hel", &root, &fs), Some("p/".to_string()));
+    }
+
+    #[test]
+    fn test_ls_completes_files_and_dirs() {
+        let fs = write_cmd_fixture();
+        let root = VirtualPath::root();
+        let (mode, _) = CompletionMode::from_input("ls foo");
+        assert_eq!(mode, CompletionMode::FilePath);
+        let names = matches_set(&autocomplete("ls h", &root, &fs));
+        assert!(names.iter().any(|n| n == "hello.md"), "got {:?}", names);
+        assert!(names.iter().any(|n| n == "home/"), "got {:?}", names);
+        assert_eq!(
+            autocomplete("ls help/r", &root, &fs),
+            AutocompleteResult::Single("ls help/readme.md ".to_string())
+        );
+    }
+
     #[test]
     fn test_sync_empty_suggests_all_subcommands() {
         let result = complete_sync("");
@@ -777,4 +970,219 @@ mod tests {
         assert_eq!(get_sync_hint("commit message"), None);
         assert_eq!(get_sync_hint("auth set token"), None);
     }
+
+    #[test]
+    fn test_help_and_man_complete_command_names() {
+        let fs = GlobalFs::empty();
+        let cwd = VirtualPath::root();
+        assert_eq!(
+            autocomplete("man gr", &cwd, &fs),
+            AutocompleteResult::Single("man grep ".to_string())
+        );
+        assert_eq!(get_hint("help whoa", &cwd, &fs), Some("mi".to_string()));
+        match autocomplete("help l", &cwd, &fs) {
+            AutocompleteResult::Multiple(common, matches) => {
+                assert_eq!(common, "help l");
+                assert!(matches.contains(&"ls".to_string()));
+                assert!(matches.contains(&"login".to_string()));
+            }
+            other => panic!("expected Multiple, got {:?}", other),
+        }
+        assert_eq!(
+            autocomplete("man ls extra", &cwd, &fs),
+            AutocompleteResult::None
+        );
+    }
+
+    #[test]
+    fn test_flag_completion_single() {
+        let fs = GlobalFs::empty();
+        let cwd = VirtualPath::root();
+        assert_eq!(
+            autocomplete("export-session -", &cwd, &fs),
+            AutocompleteResult::Single("export-session --html ".to_string())
+        );
+        assert_eq!(
+            autocomplete("head -", &cwd, &fs),
+            AutocompleteResult::Multiple(
+                "head -".to_string(),
+                vec!["-n".to_string(), "-c".to_string()]
+            )
+        );
+        assert_eq!(
+            autocomplete("rm --r", &cwd, &fs),
+            AutocompleteResult::Single("rm --recursive ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_flag_completion_multiple() {
+        let fs = GlobalFs::empty();
+        let cwd = VirtualPath::root();
+        match autocomplete("grep -", &cwd, &fs) {
+            AutocompleteResult::Multiple(common, matches) => {
+                assert_eq!(common, "grep -");
+                assert!(matches.contains(&"-i".to_string()));
+                assert!(matches.contains(&"--fixed-strings".to_string()));
+            }
+            other => panic!("expected Multiple, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_flag_completion_keeps_preceding_args() {
+        let fs = GlobalFs::empty();
+        let cwd = VirtualPath::root();
+        assert_eq!(
+            autocomplete("grep foo --inv", &cwd, &fs),
+            AutocompleteResult::Single("grep foo --invert-match ".to_string())
+        );
+        assert_eq!(
+            autocomplete("head -c", &cwd, &fs),
+            AutocompleteResult::Single("head -c ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_flag_completion_is_case_sensitive() {
+        let fs = GlobalFs::empty();
+        let cwd = VirtualPath::root();
+        assert_eq!(autocomplete("grep -e", &cwd, &fs), AutocompleteResult::None);
+        assert_eq!(
+            autocomplete("grep -E", &cwd, &fs),
+            AutocompleteResult::Single("grep -E ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_flag_completion_for_command_without_flags() {
+        let fs = GlobalFs::empty();
+        let cwd = VirtualPath::root();
+        assert_eq!(autocomplete("cd -", &cwd, &fs), AutocompleteResult::None);
+        assert_eq!(
+            autocomplete("whoami -", &cwd, &fs),
+            AutocompleteResult::None
+        );
+        assert_eq!(get_hint("cat -", &cwd, &fs), None);
+    }
+
+    #[test]
+    fn test_flag_hint() {
+        let fs = GlobalFs::empty();
+        let cwd = VirtualPath::root();
+        assert_eq!(get_hint("ls -", &cwd, &fs), Some("l".to_string()));
+        assert_eq!(get_hint("grep -", &cwd, &fs), Some("i".to_string()));
+        assert_eq!(
+            get_hint("grep --ig", &cwd, &fs),
+            Some("nore-case".to_string())
+        );
+        // Already complete — no hint.
+        assert_eq!(get_hint("ls -l", &cwd, &fs), None);
+    }
+
+    #[test]
+    fn test_non_flag_token_still_completes_paths() {
+        let fs = write_cmd_fixture();
+        let result = autocomplete("cd ho", &VirtualPath::root(), &fs);
+        assert_eq!(result, AutocompleteResult::Single("cd home/".to_string()));
+    }
+
+    /// `My Notes.md` and `My Novel.md`, plus `Old Files/` holding `a b.md`.
+    fn spaced_name_fixture() -> GlobalFs {
+        fixture(
+            &["My Notes.md", "My Novel.md", "Old Files/a b.md"],
+            &[("Old Files", "Old Files")],
+        )
+    }
+
+    #[test]
+    fn test_spaced_names_complete_escaped() {
+        let fs = spaced_name_fixture();
+        let root = VirtualPath::root();
+        assert_eq!(
+            autocomplete("cat My\\ Not", &root, &fs),
+            AutocompleteResult::Single("cat My\\ Notes.md ".to_string())
+        );
+        assert_eq!(
+            autocomplete("cat My", &root, &fs),
+            AutocompleteResult::Multiple(
+                "cat My\\ No".to_string(),
+                vec!["My Notes.md".to_string(), "My Novel.md".to_string()]
+            )
+        );
+        assert_eq!(
+            autocomplete("cd Ol", &root, &fs),
+            AutocompleteResult::Single("cd Old\\ Files/".to_string())
+        );
+        assert_eq!(
+            autocomplete("cat Old\\ Files/a", &root, &fs),
+            AutocompleteResult::Single("cat Old\\ Files/a\\ b.md ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_spaced_names_complete_inside_an_open_quote() {
+        let fs = spaced_name_fixture();
+        let root = VirtualPath::root();
+        assert_eq!(
+            autocomplete("cat \"My Not", &root, &fs),
+            AutocompleteResult::Single("cat \"My Notes.md\" ".to_string())
+        );
+        assert_eq!(
+            autocomplete("cat 'My Nov", &root, &fs),
+            AutocompleteResult::Single("cat 'My Novel.md' ".to_string())
+        );
+        // A directory leaves the quote open for the next segment.
+        assert_eq!(
+            autocomplete("cat \"Old", &root, &fs),
+            AutocompleteResult::Single("cat \"Old Files/".to_string())
+        );
+        assert_eq!(
+            autocomplete("cat \"Old Files/a", &root, &fs),
+            AutocompleteResult::Single("cat \"Old Files/a b.md\" ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_spaced_names_complete_the_last_word_only() {
+        let fs = spaced_name_fixture();
+        let root = VirtualPath::root();
+        assert_eq!(
+            autocomplete("ls -l My\\ Nov", &root, &fs),
+            AutocompleteResult::Single("ls -l My\\ Novel.md ".to_string())
+        );
+        // An unescaped space starts a new word, which matches nothing.
+        assert_eq!(
+            autocomplete("cat My Not", &root, &fs),
+            AutocompleteResult::None
+        );
+    }
+
+    #[test]
+    fn test_spaced_name_hints_are_escaped() {
+        let fs = spaced_name_fixture();
+        let root = VirtualPath::root();
+        assert_eq!(
+            get_hint("cat My\\ Not", &root, &fs),
+            Some("es.md".to_string())
+        );
+        assert_eq!(
+            get_hint("cat Ol", &root, &fs),
+            Some("d\\ Files/".to_string())
+        );
+        assert_eq!(
+            get_hint("cat \"My Not", &root, &fs),
+            Some("es.md\"".to_string())
+        );
+        assert_eq!(get_hint("cat My\\ Notes.md", &root, &fs), None);
+    }
+
+    #[test]
+    fn test_bare_path_completion_keeps_spaces_literal() {
+        let fs = spaced_name_fixture();
+        assert_eq!(
+            autocomplete_path("My Not", &VirtualPath::root(), &fs),
+            AutocompleteResult::Single("My Notes.md".to_string())
+        );
+    }
 }
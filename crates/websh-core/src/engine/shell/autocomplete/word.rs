@@ -0,0 +1,181 @@
+//! Quoting of the word being completed.
+//!
+//! Path completion works on the last word of the input, read the way the
+//! lexer reads it: `My\ No`, `"My No` and `'My No` all name `My No`. A
+//! completed path is written back in the style the user started, inside
+//! the quote they opened or with backslash escapes, so the lexer hands the
+//! command one argument even when the name has spaces.
+
+/// How a completed path is written back into the input.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum Quoting {
+    /// As is; for path inputs that are not lexed.
+    Literal,
+    /// Unquoted, with a backslash before each special character.
+    Escaped,
+    /// Inside a quote the user opened and has not closed yet.
+    Open(char),
+}
+
+/// The word under completion, with its quotes and escapes removed.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct PartialWord {
+    pub(super) value: String,
+    pub(super) quoting: Quoting,
+}
+
+impl PartialWord {
+    /// Read a typed word the way the lexer would, leaving `$` alone. A
+    /// trailing lone `\` is dropped, as it escapes a character not yet
+    /// typed.
+    pub(super) fn parse(raw: &str) -> Self {
+        let mut value = String::new();
+        let mut open = None;
+        let mut chars = raw.chars();
+        while let Some(c) = chars.next() {
+            match (open, c) {
+                (None, '\\') => value.extend(chars.next()),
+                (None, '\'' | '"') => open = Some(c),
+                (Some(quote), _) if c == quote => open = None,
+                (Some('"'), '\\') => match chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    escaped => value.extend(escaped),
+                },
+                _ => value.push(c),
+            }
+        }
+        Self {
+            value,
+            quoting: open.map_or(Quoting::Escaped, Quoting::Open),
+        }
+    }
+
+    /// Take the text verbatim.
+    pub(super) fn literal(raw: &str) -> Self {
+        Self {
+            value: raw.to_string(),
+            quoting: Quoting::Literal,
+        }
+    }
+}
+
+impl Quoting {
+    /// The opening quote a rewritten word starts with.
+    pub(super) fn open(self) -> &'static str {
+        match self {
+            Self::Open('\'') => "'",
+            Self::Open(_) => "\"",
+            Self::Literal | Self::Escaped => "",
+        }
+    }
+
+    /// The quote that ends a finished word.
+    pub(super) fn close(self) -> &'static str {
+        self.open()
+    }
+
+    /// `text` escaped for where it lands: unquoted, or inside the open
+    /// quote.
+    pub(super) fn escape(self, text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            match self {
+                Self::Literal => {}
+                Self::Escaped if needs_escape(c) => escaped.push('\\'),
+                Self::Open('\'') if c == '\'' => {
+                    // Close, add an escaped quote, reopen.
+                    escaped.push_str("'\\''");
+                    continue;
+                }
+                Self::Open('"') if matches!(c, '"' | '\\' | '$') => escaped.push('\\'),
+                Self::Escaped | Self::Open(_) => {}
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+}
+
+/// Characters the lexer would otherwise read as syntax outside quotes.
+fn needs_escape(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '\\' | '\'' | '"' | '|' | '!' | '$')
+}
+
+/// Split `tail` before its last word, where the lexer would: at whitespace
+/// that is neither quoted nor escaped.
+pub(super) fn split_last_word(tail: &str) -> (&str, &str) {
+    let mut start = 0;
+    let mut open = None;
+    let mut escaped = false;
+    for (index, c) in tail.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match (open, c) {
+            (None | Some('"'), '\\') => escaped = true,
+            (None, '\'' | '"') => open = Some(c),
+            (Some(quote), _) if c == quote => open = None,
+            (None, _) if c.is_whitespace() => start = index + c.len_utf8(),
+            _ => {}
+        }
+    }
+    tail.split_at(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_escapes_and_quotes() {
+        assert_eq!(
+            PartialWord::parse("My\\ No"),
+            PartialWord {
+                value: "My No".to_string(),
+                quoting: Quoting::Escaped,
+            }
+        );
+        assert_eq!(PartialWord::parse("\"My No").quoting, Quoting::Open('"'));
+        assert_eq!(PartialWord::parse("\"My No").value, "My No");
+        assert_eq!(PartialWord::parse("'My No").quoting, Quoting::Open('\''));
+        assert_eq!(PartialWord::parse("'a b'/c\\ d").value, "a b/c d");
+        assert_eq!(PartialWord::parse("'a b'/c").quoting, Quoting::Escaped);
+        // A trailing backslash escapes a character still to come.
+        assert_eq!(PartialWord::parse("My\\").value, "My");
+    }
+
+    #[test]
+    fn escapes_for_the_quoting_in_use() {
+        assert_eq!(Quoting::Escaped.escape("My Notes.md"), "My\\ Notes.md");
+        assert_eq!(Quoting::Escaped.escape("a$b|c"), "a\\$b\\|c");
+        assert_eq!(Quoting::Open('"').escape("say \"hi\""), "say \\\"hi\\\"");
+        assert_eq!(Quoting::Open('\'').escape("it's"), "it'\\''s");
+        assert_eq!(Quoting::Literal.escape("My Notes.md"), "My Notes.md");
+    }
+
+    #[test]
+    fn escaped_words_parse_back_to_the_name() {
+        for name in ["My Notes.md", "it's a \"test\" $1.md", "back\\slash"] {
+            for quoting in [Quoting::Escaped, Quoting::Open('"'), Quoting::Open('\'')] {
+                let written = format!(
+                    "{}{}{}",
+                    quoting.open(),
+                    quoting.escape(name),
+                    quoting.close()
+                );
+                assert_eq!(PartialWord::parse(&written).value, name, "{written}");
+            }
+        }
+    }
+
+    #[test]
+    fn splits_before_the_last_unquoted_word() {
+        assert_eq!(split_last_word("-l My\\ No"), ("-l ", "My\\ No"));
+        assert_eq!(split_last_word("\"My No"), ("", "\"My No"));
+        assert_eq!(split_last_word("a 'b c' d"), ("a 'b c' ", "d"));
+        assert_eq!(split_last_word("a "), ("a ", ""));
+        assert_eq!(split_last_word("hero"), ("", "hero"));
+    }
+}
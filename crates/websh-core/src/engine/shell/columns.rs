@@ -0,0 +1,193 @@
+//! Column layout for shell output.
+//!
+//! [`layout_columns`] mirrors GNU `ls -C`: entries fill columns top to
+//! bottom, and the widest arrangement that fits the line wins.
+//! [`side_by_side`] sets two text blocks next to each other, as `neofetch`
+//! does with its art and info.
+
+/// Spaces between two adjacent columns.
+pub(crate) const COLUMN_GAP: usize = 2;
+
+/// Result of [`layout_columns`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ColumnLayout {
+    /// Entry indices per row, left to right.
+    pub rows: Vec<Vec<usize>>,
+    /// Display width of each column, excluding the gap.
+    pub column_widths: Vec<usize>,
+}
+
+/// Arrange entries of the given display widths into column-major rows that
+/// fit within `line_width`.
+///
+/// Falls back to a single column when even two columns would overflow, so
+/// overly long names never get truncated.
+pub(crate) fn layout_columns(widths: &[usize], line_width: usize) -> ColumnLayout {
+    if widths.is_empty() {
+        return ColumnLayout {
+            rows: Vec::new(),
+            column_widths: Vec::new(),
+        };
+    }
+
+    for columns in (2..=widths.len()).rev() {
+        let rows = widths.len().div_ceil(columns);
+        // With `rows` fixed, fewer columns may be needed than requested
+        // (e.g. 5 entries over 4 columns only fill 3).
+        let used_columns = widths.len().div_ceil(rows);
+        let column_widths = column_widths(widths, rows, used_columns);
+        let total = column_widths.iter().sum::<usize>() + COLUMN_GAP * (used_columns - 1);
+        if total <= line_width {
+            return ColumnLayout {
+                rows: row_indices(widths.len(), rows),
+                column_widths,
+            };
+        }
+    }
+
+    ColumnLayout {
+        rows: (0..widths.len()).map(|idx| vec![idx]).collect(),
+        column_widths: vec![widths.iter().copied().max().unwrap_or(0)],
+    }
+}
+
+/// Merge two blocks of lines into one, `right` starting `gap` spaces after
+/// the widest `left` line so it stays aligned in a monospace grid. The
+/// shorter side is padded with blank lines.
+pub(crate) fn side_by_side(left: &[&str], right: &[String], gap: usize) -> Vec<String> {
+    let left_width = left
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    let rows = left.len().max(right.len());
+
+    (0..rows)
+        .map(|row| {
+            let left_line = left.get(row).copied().unwrap_or("");
+            let right_line = right.get(row).map(String::as_str).unwrap_or("");
+            let pad = left_width - left_line.chars().count() + gap;
+            let merged = format!("{left_line}{}{right_line}", " ".repeat(pad));
+            merged.trim_end().to_string()
+        })
+        .collect()
+}
+
+fn column_widths(widths: &[usize], rows: usize, columns: usize) -> Vec<usize> {
+    (0..columns)
+        .map(|column| {
+            widths
+                .iter()
+                .skip(column * rows)
+                .take(rows)
+                .copied()
+                .max()
+                .unwrap_or(0)
+        })
+        .collect()
+}
+
+fn row_indices(len: usize, rows: usize) -> Vec<Vec<usize>> {
+    (0..rows)
+        .map(|row| (row..len).step_by(rows).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_input_has_no_rows() {
+        let layout = layout_columns(&[], 80);
+        assert!(layout.rows.is_empty());
+        assert!(layout.column_widths.is_empty());
+    }
+
+    #[test]
+    fn everything_fits_on_one_row_when_wide() {
+        let layout = layout_columns(&[4, 5, 6], 80);
+        assert_eq!(layout.rows, vec![vec![0, 1, 2]]);
+        assert_eq!(layout.column_widths, vec![4, 5, 6]);
+    }
+
+    #[test]
+    fn fills_columns_top_to_bottom() {
+        // 4 + 2 + 4 + 2 + 4 = 16 fits three columns, but not four (22).
+        let layout = layout_columns(&[4; 7], 16);
+        assert_eq!(
+            layout.rows,
+            vec![vec![0, 3, 6], vec![1, 4], vec![2, 5]],
+            "entries run down each column before moving right"
+        );
+        assert_eq!(layout.column_widths, vec![4, 4, 4]);
+    }
+
+    #[test]
+    fn column_width_is_the_widest_entry_in_that_column() {
+        let layout = layout_columns(&[2, 10, 3, 3], 20);
+        assert_eq!(layout.rows, vec![vec![0, 2], vec![1, 3]]);
+        assert_eq!(layout.column_widths, vec![10, 3]);
+    }
+
+    #[test]
+    fn drops_unfilled_trailing_columns() {
+        // Four columns would need two rows, leaving the last column empty.
+        let layout = layout_columns(&[1; 5], 12);
+        assert_eq!(layout.rows, vec![vec![0, 2, 4], vec![1, 3]]);
+        assert_eq!(layout.column_widths.len(), 3);
+    }
+
+    #[test]
+    fn exact_fit_is_accepted() {
+        let layout = layout_columns(&[3, 3], 8);
+        assert_eq!(layout.rows, vec![vec![0, 1]]);
+    }
+
+    #[test]
+    fn narrow_width_falls_back_to_one_column() {
+        let layout = layout_columns(&[30, 12, 8], 20);
+        assert_eq!(layout.rows, vec![vec![0], vec![1], vec![2]]);
+        assert_eq!(layout.column_widths, vec![30]);
+    }
+
+    fn strings(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn side_by_side_aligns_right_block_after_widest_left_line() {
+        let merged = side_by_side(&["ab", "abcd"], &strings(&["x", "y"]), 2);
+        assert_eq!(merged, vec!["ab    x", "abcd  y"]);
+    }
+
+    #[test]
+    fn side_by_side_counts_characters_not_bytes() {
+        let merged = side_by_side(&["╔═╗", "║"], &strings(&["x", "y"]), 1);
+        assert_eq!(merged, vec!["╔═╗ x", "║   y"]);
+    }
+
+    #[test]
+    fn side_by_side_keeps_longer_left_block() {
+        let merged = side_by_side(&["a", "b", "c"], &strings(&["x"]), 1);
+        assert_eq!(merged, vec!["a x", "b", "c"]);
+    }
+
+    #[test]
+    fn side_by_side_keeps_longer_right_block() {
+        let merged = side_by_side(&["ab"], &strings(&["x", "y", "z"]), 1);
+        assert_eq!(merged, vec!["ab x", "   y", "   z"]);
+    }
+
+    #[test]
+    fn side_by_side_with_empty_left_is_right_block() {
+        let merged = side_by_side(&[], &strings(&["x", "y"]), 2);
+        assert_eq!(merged, vec!["  x", "  y"]);
+    }
+
+    #[test]
+    fn zero_width_still_lists_every_entry() {
+        let layout = layout_columns(&[1, 1], 0);
+        assert_eq!(layout.rows.concat(), vec![0, 1]);
+    }
+}
@@ -0,0 +1,300 @@
+//! Typed command failures.
+//!
+//! Commands report failures as [`CommandError`]s; the executor and the pipe
+//! filters turn them into an error line and exit status in one place
+//! ([`CommandError::into_result`]). The wording of each command is picked
+//! here from the message catalog; `Display` is the English line.
+
+use std::fmt;
+
+use crate::support::i18n::{Locale, Message};
+
+use super::CommandResult;
+
+/// Why a command failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CommandError {
+    /// `path` does not exist.
+    NotFound { cmd: String, path: String },
+    /// `path` exists but the command needs a directory.
+    NotADirectory { cmd: String, path: String },
+    /// `path` is a directory but the command needs a file.
+    IsADirectory { cmd: String, path: String },
+    /// `path` does not form a valid virtual path.
+    InvalidPath { cmd: String, path: String },
+    /// Writing to `path` needs an admin login.
+    PermissionDenied { cmd: String, path: String },
+    /// `path` lies on the read-only runtime overlay.
+    ReadOnly { cmd: String, path: String },
+    /// A required argument was left out; `operand` names it.
+    MissingOperand { cmd: String, operand: &'static str },
+    /// Malformed flags or arguments.
+    Usage { cmd: String, message: String },
+    /// A pipe stage that is not a filter.
+    UnknownFilter { name: String },
+    /// A command name the shell does not know.
+    CommandNotFound {
+        name: String,
+        suggestion: Option<String>,
+    },
+    /// A request the target made on the command's behalf failed.
+    Network { cmd: String, message: String },
+}
+
+impl CommandError {
+    pub fn not_found(cmd: impl Into<String>, path: impl fmt::Display) -> Self {
+        Self::NotFound {
+            cmd: cmd.into(),
+            path: path.to_string(),
+        }
+    }
+
+    pub fn not_a_directory(cmd: impl Into<String>, path: impl fmt::Display) -> Self {
+        Self::NotADirectory {
+            cmd: cmd.into(),
+            path: path.to_string(),
+        }
+    }
+
+    pub fn is_a_directory(cmd: impl Into<String>, path: impl fmt::Display) -> Self {
+        Self::IsADirectory {
+            cmd: cmd.into(),
+            path: path.to_string(),
+        }
+    }
+
+    pub fn invalid_path(cmd: impl Into<String>, path: impl fmt::Display) -> Self {
+        Self::InvalidPath {
+            cmd: cmd.into(),
+            path: path.to_string(),
+        }
+    }
+
+    pub fn usage(cmd: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Usage {
+            cmd: cmd.into(),
+            message: message.into(),
+        }
+    }
+
+    /// POSIX-style status: 127 for unknown commands and filters, 2 for
+    /// usage errors, 1 otherwise.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::UnknownFilter { .. } | Self::CommandNotFound { .. } => 127,
+            Self::Usage { .. } => 2,
+            _ => 1,
+        }
+    }
+
+    /// The error line in `locale`. `Usage` and `Network` messages come from
+    /// the command and are printed as given.
+    pub fn localized(&self, locale: Locale) -> String {
+        let at = |message: Message, cmd: &str, path: &str| {
+            message.format(locale, &[("cmd", cmd), ("path", path)])
+        };
+        match self {
+            Self::NotFound { cmd, path } => match cmd.as_str() {
+                "ls" => at(Message::LsNotFound, cmd, path),
+                "cd" if !path.is_empty() => at(Message::CdNotFound, cmd, path),
+                _ => at(Message::NotFound, cmd, path),
+            },
+            Self::NotADirectory { cmd, path } => match cmd.as_str() {
+                "ls" => at(Message::LsNotADirectory, cmd, path),
+                "cd" => at(Message::CdNotADirectory, cmd, path),
+                _ => at(Message::NotADirectory, cmd, path),
+            },
+            Self::IsADirectory { cmd, path } => match cmd.as_str() {
+                "cat" => at(Message::CatIsADirectory, cmd, path),
+                _ => at(Message::IsADirectory, cmd, path),
+            },
+            Self::InvalidPath { cmd, path } => at(Message::InvalidPath, cmd, path),
+            Self::PermissionDenied { cmd, path } => at(Message::PermissionDenied, cmd, path),
+            Self::ReadOnly { cmd, path } => at(Message::ReadOnly, cmd, path),
+            Self::MissingOperand { cmd, operand } => {
+                Message::MissingOperand.format(locale, &[("cmd", cmd), ("operand", operand)])
+            }
+            Self::Usage { cmd, message } | Self::Network { cmd, message } => {
+                format!("{cmd}: {message}")
+            }
+            Self::UnknownFilter { name } => {
+                Message::UnknownFilter.format(locale, &[("name", name)])
+            }
+            Self::CommandNotFound { name, suggestion } => match suggestion {
+                Some(suggestion) => Message::CommandNotFoundSuggestion
+                    .format(locale, &[("name", name), ("suggestion", suggestion)]),
+                None => Message::CommandNotFound.format(locale, &[("name", name)]),
+            },
+        }
+    }
+
+    /// The error line and exit status as a result, worded for `locale`.
+    pub fn into_result(self, locale: Locale) -> CommandResult {
+        CommandResult::error_line(self.localized(locale)).with_exit_code(self.exit_code())
+    }
+}
+
+/// The English error line.
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.localized(Locale::En))
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl From<CommandError> for CommandResult {
+    fn from(error: CommandError) -> Self {
+        error.into_result(Locale::En)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::shell::OutputLineData;
+
+    #[test]
+    fn display_keeps_each_commands_wording() {
+        let cases = [
+            (
+                CommandError::not_found("ls", "gone"),
+                "ls: cannot access 'gone': No such file or directory",
+            ),
+            (
+                CommandError::not_found("cd", "gone"),
+                "cd: no such file or directory: gone",
+            ),
+            (
+                CommandError::not_found("cd", ""),
+                "cd: : No such file or directory",
+            ),
+            (
+                CommandError::not_found("cat", "gone.md"),
+                "cat: gone.md: No such file or directory",
+            ),
+            (
+                CommandError::not_a_directory("ls", "a.md"),
+                "ls: cannot access 'a.md': Not a directory",
+            ),
+            (
+                CommandError::not_a_directory("cd", "a.md"),
+                "cd: not a directory: a.md",
+            ),
+            (
+                CommandError::is_a_directory("cat", "blog"),
+                "cat: blog: Is a directory",
+            ),
+            (
+                CommandError::is_a_directory("edit", "blog"),
+                "edit: blog: is a directory",
+            ),
+            (
+                CommandError::invalid_path("touch", "~nobody/x"),
+                "touch: invalid path '~nobody/x'",
+            ),
+            (
+                CommandError::PermissionDenied {
+                    cmd: "rm".to_string(),
+                    path: "/a.md".to_string(),
+                },
+                "rm: permission denied (admin login required)",
+            ),
+            (
+                CommandError::ReadOnly {
+                    cmd: "mkdir".to_string(),
+                    path: "/.websh/x".to_string(),
+                },
+                "mkdir: read-only filesystem",
+            ),
+            (
+                CommandError::MissingOperand {
+                    cmd: "cat".to_string(),
+                    operand: "file operand",
+                },
+                "cat: missing file operand",
+            ),
+            (
+                CommandError::usage("grep", "missing pattern"),
+                "grep: missing pattern",
+            ),
+            (
+                CommandError::UnknownFilter {
+                    name: "sort".to_string(),
+                },
+                "Pipe: unknown filter 'sort'. Supported: grep, head, tail, tee, wc, xargs",
+            ),
+            (
+                CommandError::CommandNotFound {
+                    name: "lss".to_string(),
+                    suggestion: Some("ls".to_string()),
+                },
+                "Command not found: lss. Did you mean 'ls'?",
+            ),
+            (
+                CommandError::CommandNotFound {
+                    name: "zzz".to_string(),
+                    suggestion: None,
+                },
+                "Command not found: zzz. Type 'help' for available commands.",
+            ),
+            (
+                CommandError::Network {
+                    cmd: "fetch".to_string(),
+                    message: "request timed out".to_string(),
+                },
+                "fetch: request timed out",
+            ),
+        ];
+        for (error, expected) in cases {
+            assert_eq!(error.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn localized_lines_follow_the_locale() {
+        let error = CommandError::not_found("ls", "gone");
+        assert_eq!(error.localized(Locale::En), error.to_string());
+        assert_eq!(
+            error.localized(Locale::Ko),
+            "ls: 'gone'에 접근할 수 없습니다: 그런 파일이나 디렉터리가 없습니다"
+        );
+        // Messages written by the command itself are printed as given.
+        let usage = CommandError::usage("grep", "missing pattern");
+        assert_eq!(usage.localized(Locale::Ko), "grep: missing pattern");
+
+        let result = CommandError::CommandNotFound {
+            name: "zzz".to_string(),
+            suggestion: None,
+        }
+        .into_result(Locale::Ko);
+        assert_eq!(result.exit_code, 127);
+        assert!(matches!(
+            &result.output[0].data,
+            OutputLineData::Error(message) if message.starts_with("명령을 찾을 수 없습니다: zzz")
+        ));
+    }
+
+    #[test]
+    fn exit_codes_follow_the_failure_kind() {
+        assert_eq!(CommandError::not_found("cat", "x").exit_code(), 1);
+        assert_eq!(CommandError::usage("head", "bad count").exit_code(), 2);
+        let unknown = CommandError::CommandNotFound {
+            name: "zzz".to_string(),
+            suggestion: None,
+        };
+        assert_eq!(unknown.exit_code(), 127);
+    }
+
+    #[test]
+    fn converts_to_a_single_error_line() {
+        let result = CommandResult::from(CommandError::usage("wc", "unknown option: -z"));
+        assert_eq!(result.exit_code, 2);
+        assert!(result.side_effects.is_empty());
+        assert_eq!(result.output.len(), 1);
+        assert!(matches!(
+            &result.output[0].data,
+            OutputLineData::Error(message) if message == "wc: unknown option: -z"
+        ));
+    }
+}
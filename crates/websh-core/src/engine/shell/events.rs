@@ -0,0 +1,223 @@
+//! App events recorded by the target and printed by `dmesg`.
+//!
+//! The target appends events as they happen (wallet transitions, mount
+//! loads, manifest syncs, failed fetches); this module owns their text so
+//! every surface renders an event the same way.
+
+use std::fmt;
+
+use crate::domain::chain_name;
+
+/// Group an event belongs to, selected with `dmesg -f`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventCategory {
+    Wallet,
+    Mount,
+    Manifest,
+    Fetch,
+}
+
+impl EventCategory {
+    pub const ALL: [Self; 4] = [Self::Wallet, Self::Mount, Self::Manifest, Self::Fetch];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Wallet => "wallet",
+            Self::Mount => "mount",
+            Self::Manifest => "manifest",
+            Self::Fetch => "fetch",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.as_str().eq_ignore_ascii_case(name))
+    }
+}
+
+impl fmt::Display for EventCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What happened.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AppEventKind {
+    WalletConnected {
+        address: String,
+        chain_id: Option<u64>,
+    },
+    WalletDisconnected,
+    AccountChanged {
+        address: String,
+    },
+    ChainChanged {
+        chain_id: Option<u64>,
+    },
+    /// A mount finished scanning; `files` is the number of files it holds.
+    MountLoaded {
+        root: String,
+        files: usize,
+    },
+    MountFailed {
+        root: String,
+        error: String,
+    },
+    /// The manifest poller picked up a change and reloaded the runtime.
+    ManifestSynced {
+        summary: String,
+    },
+    /// A network read failed; `target` is the URL or content path.
+    FetchFailed {
+        target: String,
+        error: String,
+    },
+}
+
+impl AppEventKind {
+    pub fn category(&self) -> EventCategory {
+        match self {
+            Self::WalletConnected { .. }
+            | Self::WalletDisconnected
+            | Self::AccountChanged { .. }
+            | Self::ChainChanged { .. } => EventCategory::Wallet,
+            Self::MountLoaded { .. } | Self::MountFailed { .. } => EventCategory::Mount,
+            Self::ManifestSynced { .. } => EventCategory::Manifest,
+            Self::FetchFailed { .. } => EventCategory::Fetch,
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Self::WalletConnected { address, chain_id } => {
+                format!("connected {address} on {}", chain_label(*chain_id))
+            }
+            Self::WalletDisconnected => "disconnected".to_string(),
+            Self::AccountChanged { address } => format!("account changed to {address}"),
+            Self::ChainChanged { chain_id } => {
+                format!("chain changed to {}", chain_label(*chain_id))
+            }
+            Self::MountLoaded { root, files } => {
+                let noun = if *files == 1 { "file" } else { "files" };
+                format!("{root} loaded ({files} {noun})")
+            }
+            Self::MountFailed { root, error } => format!("{root} failed: {error}"),
+            Self::ManifestSynced { summary } => format!("updated: {summary}"),
+            Self::FetchFailed { target, error } => format!("{target}: {error}"),
+        }
+    }
+}
+
+/// One logged event. `at_ms` is milliseconds since the page loaded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppEvent {
+    pub at_ms: u64,
+    pub kind: AppEventKind,
+}
+
+impl AppEvent {
+    pub fn new(at_ms: u64, kind: AppEventKind) -> Self {
+        Self { at_ms, kind }
+    }
+
+    pub fn category(&self) -> EventCategory {
+        self.kind.category()
+    }
+
+    /// The `dmesg` line, e.g. `[      12.345] wallet: disconnected`.
+    pub fn render(&self) -> String {
+        format!(
+            "[{:>8}.{:03}] {}: {}",
+            self.at_ms / 1000,
+            self.at_ms % 1000,
+            self.category(),
+            self.kind.message()
+        )
+    }
+}
+
+fn chain_label(chain_id: Option<u64>) -> String {
+    match chain_id {
+        Some(id) => format!("chain {id} ({})", chain_name(id)),
+        None => "an unknown chain".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_pads_the_timestamp_like_dmesg() {
+        let event = AppEvent::new(12_345, AppEventKind::WalletDisconnected);
+        assert_eq!(event.render(), "[      12.345] wallet: disconnected");
+        let event = AppEvent::new(7, AppEventKind::WalletDisconnected);
+        assert_eq!(event.render(), "[       0.007] wallet: disconnected");
+    }
+
+    #[test]
+    fn render_describes_each_kind() {
+        let cases = [
+            (
+                AppEventKind::WalletConnected {
+                    address: "0xabc".to_string(),
+                    chain_id: Some(1),
+                },
+                "wallet: connected 0xabc on chain 1 (Ethereum)",
+            ),
+            (
+                AppEventKind::AccountChanged {
+                    address: "0xdef".to_string(),
+                },
+                "wallet: account changed to 0xdef",
+            ),
+            (
+                AppEventKind::ChainChanged { chain_id: None },
+                "wallet: chain changed to an unknown chain",
+            ),
+            (
+                AppEventKind::MountLoaded {
+                    root: "/".to_string(),
+                    files: 1,
+                },
+                "mount: / loaded (1 file)",
+            ),
+            (
+                AppEventKind::MountFailed {
+                    root: "/mempool".to_string(),
+                    error: "HTTP 404".to_string(),
+                },
+                "mount: /mempool failed: HTTP 404",
+            ),
+            (
+                AppEventKind::ManifestSynced {
+                    summary: "1 added".to_string(),
+                },
+                "manifest: updated: 1 added",
+            ),
+            (
+                AppEventKind::FetchFailed {
+                    target: "https://example.com".to_string(),
+                    error: "timed out".to_string(),
+                },
+                "fetch: https://example.com: timed out",
+            ),
+        ];
+        for (kind, expected) in cases {
+            let line = AppEvent::new(0, kind).render();
+            assert_eq!(line.split_once("] ").unwrap().1, expected);
+        }
+    }
+
+    #[test]
+    fn categories_parse_case_insensitively() {
+        assert_eq!(EventCategory::parse("wallet"), Some(EventCategory::Wallet));
+        assert_eq!(EventCategory::parse("FETCH"), Some(EventCategory::Fetch));
+        assert_eq!(EventCategory::parse("disk"), None);
+        for category in EventCategory::ALL {
+            assert_eq!(EventCategory::parse(category.as_str()), Some(category));
+        }
+    }
+}
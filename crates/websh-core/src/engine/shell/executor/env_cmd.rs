@@ -1,7 +1,10 @@
 use std::collections::BTreeMap;
 
+use crate::domain::{VirtualPath, WalletState};
+use crate::engine::filesystem::display_path_for;
 use crate::engine::shell::OutputLine;
-use crate::engine::shell::{CommandResult, SideEffect};
+use crate::engine::shell::{CommandError, CommandResult, SideEffect};
+use crate::support::i18n::{LANG_VAR, Locale, Message};
 
 /// Execute `export` command against a target-provided environment snapshot.
 ///
@@ -9,6 +12,11 @@ use crate::engine::shell::{CommandResult, SideEffect};
 ///   - `KEY=value` -> request setting the variable
 ///   - `KEY` alone -> print `KEY=<value>` if set (silent otherwise)
 ///
+/// Assignments arrive as lexed words, so the value is stored exactly as the
+/// lexer left it: `$VAR` already expanded (inside double quotes too, not
+/// inside single quotes) and the quotes removed. `PATH=$PATH:/extra`
+/// appends that way.
+///
 /// An empty list prints all user variables. Invalid assignments emit an error
 /// line and set exit_code=1; subsequent assignments are still processed.
 pub(super) fn execute_export(
@@ -31,7 +39,6 @@ pub(super) fn execute_export(
     for arg in assignments {
         if let Some((key, value)) = arg.split_once('=') {
             let key = key.trim();
-            let value = value.trim().trim_matches('"').trim_matches('\'');
             if is_valid_var_name(key) {
                 side_effects.push(SideEffect::SetEnvVar {
                     key: key.to_string(),
@@ -69,6 +76,36 @@ pub(super) fn execute_export(
     }
 }
 
+/// User variables plus the live built-ins `HOME`, `PWD` and `USER`: what
+/// `env` prints and what `$VAR` expands from. Built-ins win over user
+/// variables of the same name, since they describe the current session.
+pub fn session_env(
+    wallet_state: &WalletState,
+    cwd: &VirtualPath,
+    env: &BTreeMap<String, String>,
+) -> BTreeMap<String, String> {
+    let mut vars = env.clone();
+    vars.insert("HOME".to_string(), display_path_for(&VirtualPath::root()));
+    vars.insert("PWD".to_string(), display_path_for(cwd));
+    vars.insert("USER".to_string(), wallet_state.display_name());
+    vars
+}
+
+/// Execute `env`: the [`session_env`], one `KEY=value` per line in key
+/// order.
+pub(super) fn execute_env(
+    wallet_state: &WalletState,
+    cwd: &VirtualPath,
+    env: &BTreeMap<String, String>,
+) -> CommandResult {
+    CommandResult::output(
+        session_env(wallet_state, cwd, env)
+            .into_iter()
+            .map(|(key, value)| OutputLine::text(format!("{}={}", key, value)))
+            .collect(),
+    )
+}
+
 /// Execute `unset` command.
 pub(super) fn execute_unset(key: String, env: &BTreeMap<String, String>) -> CommandResult {
     let key = key.trim();
@@ -87,6 +124,44 @@ pub(super) fn execute_unset(key: String, env: &BTreeMap<String, String>) -> Comm
     }
 }
 
+/// Execute `lang`: without a code, print the current language and the
+/// supported ones; with one, set `LANG` and confirm in the new language.
+pub(super) fn execute_lang(
+    code: Option<String>,
+    env: &BTreeMap<String, String>,
+) -> Result<CommandResult, CommandError> {
+    let current = Locale::from_env(env);
+    let Some(code) = code else {
+        let available = Locale::ALL
+            .iter()
+            .map(|locale| format!("{} ({})", locale.as_str(), locale.name()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Ok(CommandResult::output(vec![
+            OutputLine::text(Message::LangCurrent.format(
+                current,
+                &[("name", current.name()), ("code", current.as_str())],
+            )),
+            OutputLine::text(Message::LangAvailable.format(current, &[("locales", &available)])),
+        ]));
+    };
+
+    let Some(locale) = Locale::parse(&code) else {
+        let codes = Locale::ALL.map(Locale::as_str).join(", ");
+        return Err(CommandError::usage(
+            "lang",
+            Message::LangUnknown.format(current, &[("tag", &code), ("locales", &codes)]),
+        ));
+    };
+    Ok(CommandResult::output(vec![OutputLine::text(
+        Message::LangSet.format(locale, &[("name", locale.name())]),
+    )])
+    .with_side_effect(SideEffect::SetEnvVar {
+        key: LANG_VAR.to_string(),
+        value: locale.as_str().to_string(),
+    }))
+}
+
 pub(super) fn is_valid_var_name(name: &str) -> bool {
     if name.is_empty() {
         return false;
@@ -137,7 +212,8 @@ mod tests {
             | OutputLineData::Ascii(text) => text,
             OutputLineData::Command { .. }
             | OutputLineData::Empty
-            | OutputLineData::ListEntry { .. } => "",
+            | OutputLineData::ListEntry { .. }
+            | OutputLineData::ListRow(_) => "",
         }
     }
 
@@ -213,6 +289,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn export_stores_the_lexed_value_verbatim() {
+        // The lexer already removed the quotes; what is left is the value.
+        let result = execute_export(vec!["MSG= \"hi\" ".to_string()], &env());
+        assert_eq!(
+            result.side_effects,
+            vec![SideEffect::SetEnvVar {
+                key: "MSG".to_string(),
+                value: " \"hi\" ".to_string()
+            }]
+        );
+    }
+
     #[test]
     fn export_invalid_name_errors_without_side_effect() {
         let result = execute_export(vec!["1BAD=value".to_string()], &env());
@@ -221,6 +310,45 @@ mod tests {
         assert!(result.side_effects.is_empty());
     }
 
+    fn env_lines(wallet_state: &WalletState, cwd: &str) -> Vec<String> {
+        let cwd = VirtualPath::from_absolute(cwd).unwrap();
+        execute_env(wallet_state, &cwd, &env())
+            .output
+            .iter()
+            .map(|line| line_text(line).to_string())
+            .collect()
+    }
+
+    #[test]
+    fn env_lists_user_vars_and_builtins_in_key_order() {
+        assert_eq!(
+            env_lines(&WalletState::Disconnected, "/"),
+            vec!["EDITOR=vim", "HOME=~", "LANG=en", "PWD=~", "USER=guest"]
+        );
+    }
+
+    #[test]
+    fn env_pwd_reflects_the_current_route() {
+        let lines = env_lines(&WalletState::Disconnected, "/blog/2024");
+        assert!(lines.contains(&"PWD=/blog/2024".to_string()));
+    }
+
+    #[test]
+    fn env_user_follows_the_wallet_and_overrides_user_vars() {
+        let wallet = WalletState::Connected {
+            address: "0x1234567890abcdef1234567890abcdef12345678".to_string(),
+            ens_name: Some("wonj.eth".to_string()),
+            chain_id: Some(1),
+        };
+        let cwd = VirtualPath::root();
+        let mut vars = env();
+        vars.insert("USER".to_string(), "someone".to_string());
+        let result = execute_env(&wallet, &cwd, &vars);
+        let lines = result.output.iter().map(line_text).collect::<Vec<_>>();
+        assert!(lines.contains(&"USER=wonj.eth"));
+        assert!(!lines.contains(&"USER=someone"));
+    }
+
     #[test]
     fn unset_existing_var_requests_unset_side_effect() {
         let result = execute_unset("EDITOR".to_string(), &env());
@@ -237,4 +365,44 @@ mod tests {
         let result = execute_unset("PAGER".to_string(), &env());
         assert!(result.side_effects.is_empty());
     }
+
+    #[test]
+    fn lang_without_code_prints_current_and_available() {
+        let result = execute_lang(None, &env()).unwrap();
+        let lines = result.output.iter().map(line_text).collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec![
+                "Language: English (en)",
+                "Available: en (English), ko (한국어)"
+            ]
+        );
+        assert!(result.side_effects.is_empty());
+    }
+
+    #[test]
+    fn lang_sets_lang_and_confirms_in_the_new_language() {
+        let result = execute_lang(Some("ko-KR".to_string()), &env()).unwrap();
+        assert_eq!(
+            line_text(&result.output[0]),
+            "언어를 한국어(으)로 바꿨습니다"
+        );
+        assert_eq!(
+            result.side_effects,
+            vec![SideEffect::SetEnvVar {
+                key: "LANG".to_string(),
+                value: "ko".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn lang_rejects_unsupported_codes() {
+        let error = execute_lang(Some("fr".to_string()), &env()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "lang: unknown language 'fr' (expected en, ko)"
+        );
+        assert_eq!(error.exit_code(), 2);
+    }
 }
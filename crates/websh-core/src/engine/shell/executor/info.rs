@@ -1,5 +1,23 @@
-use crate::domain::WalletState;
-use crate::engine::shell::{CommandResult, ExecutionContext, OutputLine, SideEffect};
+use crate::domain::{FsEntry, RuntimeMount, VirtualPath, WalletState};
+use crate::engine::filesystem::GlobalFs;
+use crate::engine::shell::columns::side_by_side;
+use crate::engine::shell::{
+    AppEvent, CommandError, CommandResult, EventCategory, ExecutionContext, OutputLine, SideEffect,
+    tracking_enabled,
+};
+use crate::support::format::{DEFAULT_DATE_FORMAT, format_strftime};
+use crate::support::text::expand_backslash_escapes;
+
+use super::mount_for_path;
+
+/// Spaces between the profile art and the info column in `neofetch`.
+const NEOFETCH_GAP: usize = 3;
+
+/// Rows in each `stats` ranking.
+const STATS_TOP: usize = 10;
+
+/// Blocks in the bar of the most used command.
+const STATS_BAR_WIDTH: usize = 24;
 
 pub(super) fn execute_whoami(context: &ExecutionContext) -> CommandResult {
     CommandResult::output(vec![OutputLine::ascii(
@@ -7,6 +25,209 @@ pub(super) fn execute_whoami(context: &ExecutionContext) -> CommandResult {
     )])
 }
 
+/// Execute `echo`: the words joined by single spaces, one output line per
+/// `\n`. Without `newline` (or after `\c`) a trailing empty line is dropped.
+pub(super) fn execute_echo(words: &[String], newline: bool, escapes: bool) -> CommandResult {
+    let mut text = words.join(" ");
+    let mut newline = newline;
+    if escapes {
+        let (expanded, stopped) = expand_backslash_escapes(&text);
+        text = expanded;
+        newline &= !stopped;
+    }
+    if !newline && text.is_empty() {
+        return CommandResult::empty();
+    }
+    let mut lines: Vec<&str> = text.split('\n').collect();
+    if !newline && lines.len() > 1 && lines.last() == Some(&"") {
+        lines.pop();
+    }
+    CommandResult::output(lines.into_iter().map(OutputLine::text).collect())
+}
+
+/// Execute `basename`: one line, see [`basename`].
+pub(super) fn execute_basename(
+    path: Option<String>,
+    suffix: Option<String>,
+) -> Result<CommandResult, CommandError> {
+    let path = path.ok_or_else(|| CommandError::MissingOperand {
+        cmd: "basename".to_string(),
+        operand: "operand",
+    })?;
+    Ok(CommandResult::output(vec![OutputLine::text(basename(
+        &path,
+        suffix.as_deref(),
+    ))]))
+}
+
+/// Execute `dirname`: one line, see [`dirname`].
+pub(super) fn execute_dirname(path: Option<String>) -> Result<CommandResult, CommandError> {
+    let path = path.ok_or_else(|| CommandError::MissingOperand {
+        cmd: "dirname".to_string(),
+        operand: "operand",
+    })?;
+    Ok(CommandResult::output(vec![OutputLine::text(dirname(
+        &path,
+    ))]))
+}
+
+/// `path` without any leading directories, and without `suffix` when it
+/// ends in one and is more than it. Trailing slashes are ignored; a path of
+/// only slashes is `/`.
+fn basename(path: &str, suffix: Option<&str>) -> String {
+    let trimmed = path.trim_end_matches('/');
+    if trimmed.is_empty() {
+        return if path.is_empty() { "" } else { "/" }.to_string();
+    }
+    let name = trimmed.rsplit('/').next().unwrap_or(trimmed);
+    match suffix {
+        Some(suffix) if !suffix.is_empty() && name.len() > suffix.len() => {
+            name.strip_suffix(suffix).unwrap_or(name).to_string()
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// `path` without its last component and the slashes before it: `.` when
+/// there is no directory part, `/` when only the root is left.
+fn dirname(path: &str) -> String {
+    let trimmed = path.trim_end_matches('/');
+    if trimmed.is_empty() {
+        return if path.is_empty() { "." } else { "/" }.to_string();
+    }
+    match trimmed.rfind('/') {
+        None => ".".to_string(),
+        Some(slash) => match trimmed[..slash].trim_end_matches('/') {
+            "" => "/".to_string(),
+            parent => parent.to_string(),
+        },
+    }
+}
+
+/// Execute `date`: the context's current time in its local zone.
+pub(super) fn execute_date(format: Option<&str>, context: &ExecutionContext) -> CommandResult {
+    CommandResult::output(vec![OutputLine::text(format_strftime(
+        context.now,
+        context.utc_offset_minutes,
+        format.unwrap_or(DEFAULT_DATE_FORMAT),
+    ))])
+}
+
+/// Execute `dmesg`: one line per logged event, oldest first, limited to
+/// `category` when one is given.
+pub(super) fn execute_dmesg(
+    category: Option<&str>,
+    events: &[AppEvent],
+) -> Result<CommandResult, CommandError> {
+    let wanted = match category {
+        Some(name) => Some(EventCategory::parse(name).ok_or_else(|| {
+            let known: Vec<&str> = EventCategory::ALL.iter().map(|c| c.as_str()).collect();
+            CommandError::usage(
+                "dmesg",
+                format!("unknown category '{name}' (expected {})", known.join(", ")),
+            )
+        })?),
+        None => None,
+    };
+    Ok(CommandResult::output(
+        events
+            .iter()
+            .filter(|event| wanted.is_none_or(|wanted| event.category() == wanted))
+            .map(|event| OutputLine::text(event.render()))
+            .collect(),
+    ))
+}
+
+/// Execute `stats`: the most used commands (with a bar each), files and
+/// directories across sessions, and this session against all time.
+/// `--reset` asks the target to clear the counters instead.
+pub(super) fn execute_stats(reset: bool, context: &ExecutionContext) -> CommandResult {
+    if reset {
+        return CommandResult::output(vec![OutputLine::success("stats: counters cleared")])
+            .with_side_effect(SideEffect::ResetUsageStats);
+    }
+
+    let usage = &context.usage;
+    let all_time = &usage.all_time;
+    let mut lines = Vec::new();
+    if !tracking_enabled(&context.env) {
+        lines.push(OutputLine::info(
+            "Tracking is off; unset TRACKING to count again.",
+        ));
+    }
+    if all_time.is_empty() {
+        lines.push(OutputLine::text("No activity recorded yet."));
+        return CommandResult::output(lines);
+    }
+
+    lines.push(OutputLine::text("Top commands"));
+    lines.extend(ranking_lines(&all_time.top_commands(STATS_TOP), true));
+    for (title, ranking) in [
+        ("Top files", all_time.top_files(STATS_TOP)),
+        ("Top directories", all_time.top_dirs(STATS_TOP)),
+    ] {
+        if !ranking.is_empty() {
+            lines.push(OutputLine::empty());
+            lines.push(OutputLine::text(title));
+            lines.extend(ranking_lines(&ranking, false));
+        }
+    }
+    lines.push(OutputLine::empty());
+    lines.push(OutputLine::text(format!(
+        "Commands: {} this session, {} all time",
+        usage.session.total_commands(),
+        all_time.total_commands()
+    )));
+    lines.push(OutputLine::text(format!(
+        "Time: {} this session, {} all time",
+        format_duration(usage.session.seconds),
+        format_duration(all_time.seconds)
+    )));
+    CommandResult::output(lines)
+}
+
+/// One aligned `name  count` row per entry, with a bar scaled to the first
+/// (largest) count when `bars` is set.
+fn ranking_lines(ranking: &[(&str, u64)], bars: bool) -> Vec<OutputLine> {
+    let name_width = ranking
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let count_width = ranking
+        .iter()
+        .map(|(_, count)| count.to_string().len())
+        .max()
+        .unwrap_or(0);
+    let max = ranking.first().map_or(1, |(_, count)| (*count).max(1));
+    ranking
+        .iter()
+        .map(|(name, count)| {
+            let mut line = format!("  {name:<name_width$}  {count:>count_width$}");
+            if bars {
+                line.push_str("  ");
+                line.push_str(&stats_bar(*count, max));
+            }
+            OutputLine::text(line)
+        })
+        .collect()
+}
+
+/// Bar for `count` out of `max`: at least one block for any use.
+fn stats_bar(count: u64, max: u64) -> String {
+    let blocks = (count as f64 / max as f64 * STATS_BAR_WIDTH as f64).round() as usize;
+    "█".repeat(blocks.clamp(1, STATS_BAR_WIDTH))
+}
+
+/// `42s`, `5m 12s`, or `3h 07m`.
+fn format_duration(seconds: u64) -> String {
+    match (seconds / 3600, seconds % 3600 / 60, seconds % 60) {
+        (0, 0, secs) => format!("{secs}s"),
+        (0, mins, secs) => format!("{mins}m {secs}s"),
+        (hours, mins, _) => format!("{hours}h {mins:02}m"),
+    }
+}
+
 /// Execute `id` command.
 pub(super) fn execute_id(wallet_state: &WalletState, context: &ExecutionContext) -> CommandResult {
     let mut lines = vec![OutputLine::empty()];
@@ -56,9 +277,145 @@ pub(super) fn execute_id(wallet_state: &WalletState, context: &ExecutionContext)
     CommandResult::output(lines)
 }
 
+/// Execute `neofetch`: the profile art with a key/value summary beside it.
+pub(super) fn execute_neofetch(
+    wallet_state: &WalletState,
+    runtime_mounts: &[RuntimeMount],
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+    context: &ExecutionContext,
+) -> CommandResult {
+    let art: Vec<&str> = context.shell_text.profile.lines().collect();
+    let info = neofetch_info(wallet_state, runtime_mounts, fs, cwd, context);
+    let merged = side_by_side(&art, &info, NEOFETCH_GAP);
+    CommandResult::output(vec![OutputLine::ascii(merged.join("\n"))])
+}
+
+fn neofetch_info(
+    wallet_state: &WalletState,
+    runtime_mounts: &[RuntimeMount],
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+    context: &ExecutionContext,
+) -> Vec<String> {
+    let title = format!("{}@websh", wallet_state.display_name());
+    let mut lines = vec![title.clone(), "-".repeat(title.chars().count())];
+    let system = &context.system_info;
+    let mut field = |key: &str, value: Option<String>| {
+        if let Some(value) = value {
+            lines.push(format!("{key}: {value}"));
+        }
+    };
+
+    field("Version", system.version.clone());
+    field("Uptime", system.uptime.clone());
+    field("Browser", system.user_agent.clone());
+    field("Viewport", system.viewport.clone());
+    field(
+        "Wallet",
+        Some(match wallet_state {
+            WalletState::Connected { .. } => wallet_state.display_name(),
+            WalletState::Connecting => "connecting".to_string(),
+            WalletState::Disconnected => "disconnected".to_string(),
+        }),
+    );
+    field(
+        "Network",
+        wallet_state
+            .chain_id()
+            .map(|chain_id| crate::domain::chain_name(chain_id).to_string()),
+    );
+    field(
+        "Mount",
+        mount_for_path(runtime_mounts, cwd).map(|mount| {
+            let files = fs.get_entry(&mount.root).map_or(0, count_files);
+            let noun = if files == 1 { "file" } else { "files" };
+            format!("{} ({files} {noun})", mount.label)
+        }),
+    );
+    field(
+        "History",
+        system.history_lines.map(|count| {
+            let noun = if count == 1 { "line" } else { "lines" };
+            format!("{count} {noun}")
+        }),
+    );
+    field("Theme", context.env.get("THEME").cloned());
+    lines
+}
+
+fn count_files(entry: &FsEntry) -> usize {
+    match entry {
+        FsEntry::File { .. } => 1,
+        FsEntry::Directory { children, .. } => children.values().map(count_files).sum(),
+    }
+}
+
 pub(super) fn execute_theme(requested: Option<String>) -> CommandResult {
     match requested {
         Some(theme) => CommandResult::empty().with_side_effect(SideEffect::SetTheme { theme }),
         None => CommandResult::empty().with_side_effect(SideEffect::ListThemes),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basename_strips_the_directory() {
+        assert_eq!(basename("/blog/post.md", None), "post.md");
+        assert_eq!(basename("blog/post.md", None), "post.md");
+        assert_eq!(basename("post.md", None), "post.md");
+        assert_eq!(basename("~/blog", None), "blog");
+    }
+
+    #[test]
+    fn basename_ignores_trailing_slashes() {
+        assert_eq!(basename("/blog/", None), "blog");
+        assert_eq!(basename("blog//", None), "blog");
+        assert_eq!(basename("/", None), "/");
+        assert_eq!(basename("///", None), "/");
+        assert_eq!(basename("", None), "");
+    }
+
+    #[test]
+    fn basename_strips_a_suffix_but_never_the_whole_name() {
+        assert_eq!(basename("/blog/post.md", Some(".md")), "post");
+        assert_eq!(basename("/blog/post.md/", Some(".md")), "post");
+        assert_eq!(basename("/blog/post.md", Some(".txt")), "post.md");
+        assert_eq!(basename(".md", Some(".md")), ".md");
+        assert_eq!(basename("post.md", Some("")), "post.md");
+    }
+
+    #[test]
+    fn dirname_keeps_the_parent() {
+        assert_eq!(dirname("/blog/post.md"), "/blog");
+        assert_eq!(dirname("blog/2024/post.md"), "blog/2024");
+        assert_eq!(dirname("/blog"), "/");
+        assert_eq!(dirname("~/blog"), "~");
+    }
+
+    #[test]
+    fn dirname_edge_cases() {
+        assert_eq!(dirname("post.md"), ".");
+        assert_eq!(dirname(""), ".");
+        assert_eq!(dirname("/"), "/");
+        assert_eq!(dirname("//"), "/");
+        assert_eq!(dirname("/blog/"), "/");
+        assert_eq!(dirname("blog/"), ".");
+        assert_eq!(dirname("a//b//"), "a");
+    }
+
+    #[test]
+    fn missing_operands_are_errors() {
+        assert_eq!(
+            execute_basename(None, None).unwrap_err().to_string(),
+            "basename: missing operand"
+        );
+        assert_eq!(
+            execute_dirname(None).unwrap_err().to_string(),
+            "dirname: missing operand"
+        );
+    }
+}
@@ -6,14 +6,21 @@
 use crate::domain::{ChangeSet, RuntimeMount, VirtualPath, WalletState, is_runtime_overlay_path};
 use crate::engine::filesystem::{GlobalFs, canonicalize_user_path};
 
-use super::{AccessPolicy, Command, CommandResult, ExecutionContext, OutputLine, SideEffect};
+use super::{
+    AccessPolicy, Command, CommandError, CommandResult, ExecutionContext, SideEffect, help,
+    suggest_command,
+};
 
 mod env_cmd;
 mod info;
 mod read;
+mod session;
 mod sync;
 mod write;
 
+pub use env_cmd::session_env;
+pub use read::selection_zip_export;
+
 /// Execute a parsed command and return output lines.
 ///
 /// This function may have side effects on the terminal state (e.g., clearing
@@ -62,63 +69,159 @@ pub fn execute_command_with_context(
     remote_head: Option<&str>,
     context: &ExecutionContext,
 ) -> CommandResult {
+    dispatch(
+        cmd,
+        wallet_state,
+        runtime_mounts,
+        fs,
+        cwd,
+        changes,
+        remote_head,
+        context,
+    )
+    .unwrap_or_else(|error| error.into_result(context.locale()))
+}
+
+/// Run `cmd`. Failures come back as a [`CommandError`], which
+/// [`execute_command_with_context`] turns into the printed line and status.
+#[allow(clippy::too_many_arguments)]
+fn dispatch(
+    cmd: Command,
+    wallet_state: &WalletState,
+    runtime_mounts: &[RuntimeMount],
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+    changes: &ChangeSet,
+    remote_head: Option<&str>,
+    context: &ExecutionContext,
+) -> Result<CommandResult, CommandError> {
     match cmd {
-        Command::Ls { path, long } => read::execute_ls(
+        Command::Ls {
             path,
             long,
+            one_per_line,
+        } => read::execute_ls(
+            path,
+            long,
+            context.terminal_columns.filter(|_| !one_per_line),
+            context.terminal_columns.is_some(),
+            context.now,
             wallet_state,
             &context.access_policy,
             runtime_mounts,
             fs,
             cwd,
+            changes,
+        ),
+        Command::LsDiff(path) => read::execute_ls_diff(
+            path,
+            context.manifest_sync.as_deref(),
+            wallet_state,
+            runtime_mounts,
+            fs,
+            cwd,
+            changes,
         ),
         Command::Cd(path) => read::execute_cd(path, fs, cwd),
-        Command::Pwd => CommandResult::output(vec![OutputLine::text(cwd.as_str())]),
+        Command::CdHistory(offset) => Ok(read::execute_cd_history(offset, &context.navigation)),
+        Command::Dirs { clear } => Ok(read::execute_dirs(clear, &context.navigation)),
+        Command::Pwd { physical } => Ok(read::execute_pwd(physical, runtime_mounts, cwd)),
         Command::Cat(file) => match file {
             Some(f) => read::execute_cat(f, fs, cwd),
-            None => CommandResult::error_line("cat: missing file operand"),
+            None => Err(CommandError::MissingOperand {
+                cmd: "cat".to_string(),
+                operand: "file operand",
+            }),
         },
-        Command::Whoami => info::execute_whoami(context),
-        Command::Id => info::execute_id(wallet_state, context),
-        Command::Help => CommandResult::output(
-            context
-                .shell_text
-                .help
-                .lines()
-                .map(OutputLine::text)
-                .collect(),
-        ),
-        Command::Theme(requested) => info::execute_theme(requested),
-        Command::Clear => CommandResult {
-            output: vec![],
-            exit_code: 0,
-            side_effects: vec![SideEffect::ClearHistory],
+        Command::Explorer(path) => read::execute_explorer(path, fs, cwd),
+        Command::Whoami => Ok(info::execute_whoami(context)),
+        Command::Id => Ok(info::execute_id(wallet_state, context)),
+        Command::Date(format) => Ok(info::execute_date(format.as_deref(), context)),
+        Command::Neofetch => Ok(info::execute_neofetch(
+            wallet_state,
+            runtime_mounts,
+            fs,
+            cwd,
+            context,
+        )),
+        Command::Tags(tag) => Ok(read::execute_tags(tag, runtime_mounts, fs, cwd)),
+        Command::Search(query) => Ok(read::execute_search(query, runtime_mounts, fs, cwd)),
+        Command::Feed { path, output } => read::execute_feed(path, output, fs, cwd),
+        Command::Sha256 { path, check } => read::execute_sha256(path, check, fs, cwd),
+        Command::ExportZip(path) => read::execute_export_zip(path, wallet_state, fs, cwd),
+        Command::ExportDraft(path) => match path {
+            Some(path) => read::execute_export_draft(path, fs, cwd, changes),
+            None => Err(CommandError::MissingOperand {
+                cmd: "export-draft".to_string(),
+                operand: "file operand",
+            }),
         },
-        Command::Echo(text) => CommandResult::output(vec![OutputLine::text(text)]),
-        Command::Export(assignments) => env_cmd::execute_export(assignments, &context.env),
+        Command::Fetch(url) => Ok(CommandResult::empty().with_side_effect(SideEffect::Fetch {
+            url,
+            filters: Vec::new(),
+        })),
+        Command::ManifestLint => {
+            Ok(CommandResult::empty().with_side_effect(SideEffect::ReportManifestIssues))
+        }
+        Command::Dmesg { category } => info::execute_dmesg(category.as_deref(), &context.events),
+        Command::Stats { reset } => Ok(info::execute_stats(reset, context)),
+        Command::Help(topic) => Ok(help::execute_help(
+            topic,
+            context.shell_text.help,
+            context.locale(),
+        )),
+        Command::Man(topic) => Ok(help::execute_man(topic, context.locale())),
+        Command::Which(names) => Ok(help::execute_which(names)),
+        Command::Theme(requested) => Ok(info::execute_theme(requested)),
+        Command::Clear => Ok(CommandResult::empty().with_side_effect(SideEffect::ClearScreen)),
+        Command::Reset => Ok(CommandResult::empty().with_side_effect(SideEffect::ClearHistory)),
+        Command::Copy => Ok(CommandResult::empty().with_side_effect(SideEffect::CopySession)),
+        Command::ExportSession { filename, html } => Ok(session::execute_export_session(
+            filename,
+            html,
+            runtime_mounts,
+            cwd,
+        )),
+        Command::Tutorial(action) => {
+            Ok(CommandResult::empty().with_side_effect(SideEffect::Tutorial(action)))
+        }
+        Command::Session(action) => session::execute_session(action),
+        Command::Basename { path, suffix } => info::execute_basename(path, suffix),
+        Command::Dirname(path) => info::execute_dirname(path),
+        Command::Echo {
+            words,
+            newline,
+            escapes,
+        } => Ok(info::execute_echo(&words, newline, escapes)),
+        Command::Export(assignments) => Ok(env_cmd::execute_export(assignments, &context.env)),
+        Command::Env => Ok(env_cmd::execute_env(wallet_state, cwd, &context.env)),
         Command::Unset(key) => match key {
-            Some(k) => env_cmd::execute_unset(k, &context.env),
-            None => CommandResult::error_line("unset: missing variable name"),
+            Some(k) => Ok(env_cmd::execute_unset(k, &context.env)),
+            None => Err(CommandError::MissingOperand {
+                cmd: "unset".to_string(),
+                operand: "variable name",
+            }),
         },
-        Command::Login => CommandResult::login(),
-        Command::Logout => CommandResult::logout(),
-        Command::Touch { path } => write::execute_touch(
+        Command::Lang(code) => env_cmd::execute_lang(code, &context.env),
+        Command::Login => Ok(CommandResult::login()),
+        Command::Logout => Ok(CommandResult::logout()),
+        Command::Touch { path } => Ok(write::execute_touch(
             path,
             wallet_state,
             &context.access_policy,
             runtime_mounts,
             fs,
             cwd,
-        ),
-        Command::Mkdir { path } => write::execute_mkdir(
+        )),
+        Command::Mkdir { path } => Ok(write::execute_mkdir(
             path,
             wallet_state,
             &context.access_policy,
             runtime_mounts,
             fs,
             cwd,
-        ),
-        Command::Rm { path, recursive } => write::execute_rm(
+        )),
+        Command::Rm { path, recursive } => Ok(write::execute_rm(
             path,
             recursive,
             write::WriteCommandContext {
@@ -129,8 +232,34 @@ pub fn execute_command_with_context(
                 cwd,
                 changes,
             },
-        ),
-        Command::Rmdir { path } => write::execute_rmdir(
+        )),
+        Command::Mv { src, dst } => Ok(write::execute_transfer(
+            src,
+            dst,
+            true,
+            write::WriteCommandContext {
+                wallet_state,
+                access_policy: &context.access_policy,
+                runtime_mounts,
+                fs,
+                cwd,
+                changes,
+            },
+        )),
+        Command::Cp { src, dst } => Ok(write::execute_transfer(
+            src,
+            dst,
+            false,
+            write::WriteCommandContext {
+                wallet_state,
+                access_policy: &context.access_policy,
+                runtime_mounts,
+                fs,
+                cwd,
+                changes,
+            },
+        )),
+        Command::Rmdir { path } => Ok(write::execute_rmdir(
             path,
             wallet_state,
             &context.access_policy,
@@ -138,16 +267,16 @@ pub fn execute_command_with_context(
             fs,
             cwd,
             changes,
-        ),
-        Command::Edit { path } => write::execute_edit(
+        )),
+        Command::Edit { path } => Ok(write::execute_edit(
             path,
             wallet_state,
             &context.access_policy,
             runtime_mounts,
             fs,
             cwd,
-        ),
-        Command::EchoRedirect { body, path } => write::execute_echo_redirect(
+        )),
+        Command::EchoRedirect { body, path } => Ok(write::execute_echo_redirect(
             body,
             path,
             wallet_state,
@@ -155,8 +284,8 @@ pub fn execute_command_with_context(
             runtime_mounts,
             fs,
             cwd,
-        ),
-        Command::Sync(sub) => sync::execute_sync(
+        )),
+        Command::Sync(sub) => Ok(sync::execute_sync(
             sub,
             wallet_state,
             &context.access_policy,
@@ -164,61 +293,83 @@ pub fn execute_command_with_context(
             cwd,
             changes,
             remote_head,
-        ),
-        Command::Unknown(cmd) => CommandResult::error_line(format!(
-            "Command not found: {}. Type 'help' for available commands.",
-            cmd
-        ))
-        .with_exit_code(127),
+        )),
+        Command::Unknown(cmd) => Err(command_not_found(cmd)),
+    }
+}
+
+/// Run the `tee` pipe stage over `lines`; see [`write::execute_tee`].
+#[allow(clippy::too_many_arguments)]
+pub(super) fn execute_tee(
+    args: &[String],
+    lines: Vec<super::OutputLine>,
+    wallet_state: &WalletState,
+    runtime_mounts: &[RuntimeMount],
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+    changes: &ChangeSet,
+    context: &ExecutionContext,
+) -> CommandResult {
+    write::execute_tee(
+        args,
+        lines,
+        write::WriteCommandContext {
+            wallet_state,
+            access_policy: &context.access_policy,
+            runtime_mounts,
+            fs,
+            cwd,
+            changes,
+        },
+    )
+}
+
+/// Report an unknown command, suggesting the closest known name if any.
+fn command_not_found(cmd: String) -> CommandError {
+    CommandError::CommandNotFound {
+        suggestion: suggest_command(&cmd).map(str::to_string),
+        name: cmd,
     }
 }
 
 /// Resolve an admin + mount preflight for write commands. Returns the write
 /// target mount when the caller may write to `current_route`, or a
-/// `CommandResult` error otherwise.
+/// [`CommandError`] otherwise.
 ///
 /// Centralising this lets every write arm emit the same error string and keeps
 /// admin gating in one place.
-#[allow(clippy::result_large_err)]
 pub(super) fn require_write_access(
     cmd_label: &str,
     wallet_state: &WalletState,
     access_policy: &AccessPolicy,
     runtime_mounts: &[RuntimeMount],
     path: &VirtualPath,
-) -> Result<(), CommandResult> {
+) -> Result<(), CommandError> {
     if is_runtime_overlay_path(path) {
-        return Err(CommandResult::error_line(format!(
-            "{}: read-only filesystem",
-            cmd_label
-        )));
+        return Err(CommandError::ReadOnly {
+            cmd: cmd_label.to_string(),
+            path: path.to_string(),
+        });
     }
 
-    let Some(mount) = mount_for_path(runtime_mounts, path) else {
-        return Err(CommandResult::error_line(format!(
-            "{}: permission denied (admin login required)",
-            cmd_label
-        )));
+    let denied = || CommandError::PermissionDenied {
+        cmd: cmd_label.to_string(),
+        path: path.to_string(),
     };
-
+    let mount = mount_for_path(runtime_mounts, path).ok_or_else(denied)?;
     if access_policy.can_write_to(wallet_state, mount.writable) {
         Ok(())
     } else {
-        Err(CommandResult::error_line(format!(
-            "{}: permission denied (admin login required)",
-            cmd_label
-        )))
+        Err(denied())
     }
 }
 
-#[allow(clippy::result_large_err)]
 pub(super) fn resolve_path_arg(
     cmd_label: &str,
     raw: &str,
     cwd: &VirtualPath,
-) -> Result<VirtualPath, CommandResult> {
-    canonicalize_user_path(cwd, raw)
-        .ok_or_else(|| CommandResult::error_line(format!("{}: invalid path '{}'", cmd_label, raw)))
+) -> Result<VirtualPath, CommandError> {
+    canonicalize_user_path(cwd, raw).ok_or_else(|| CommandError::invalid_path(cmd_label, raw))
 }
 
 pub(super) fn mount_for_path(
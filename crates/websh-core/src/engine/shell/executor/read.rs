@@ -1,55 +1,139 @@
-use crate::domain::{DirEntry, RuntimeMount, VirtualPath, WalletState};
+use crate::domain::{ChangeSet, DirEntry, FsEntry, RuntimeMount, VirtualPath, WalletState};
 use crate::engine::filesystem::{
-    GlobalFs, RouteRequest, RouteSurface, request_path_for_canonical_path,
+    GlobalFs, LookupMiss, RouteRequest, RouteSurface, UserPathError, content_route_for_path,
+    display_path_for, lookup_user_path, request_path_for_canonical_path, route_for_user_path,
 };
-use crate::engine::shell::{AccessPolicy, CommandResult, OutputLine, PathArg};
+use crate::engine::shell::autocomplete::escape_word;
+use crate::engine::shell::columns::layout_columns;
+use crate::engine::shell::{
+    AccessPolicy, ChecksumTarget, CommandError, CommandResult, FeedOutput, ListCell, ManifestSync,
+    NavigationHistory, OutputLine, PathArg, SideEffect, ZipExport, ZipExportFile,
+};
+use crate::ports::{EntryChange, diff_directory};
+
+use crate::support::feed::{AtomFeed, FeedEntry, rfc3339};
+use crate::support::format::iso_date_prefix;
+
+use super::{can_write_path, mount_for_path, resolve_path_arg};
 
-use super::{can_write_path, resolve_path_arg};
+const DEFAULT_FEED_DIR: &str = "~/blog";
+/// Archive name for `export-zip /`, which has no folder name of its own.
+const ROOT_ARCHIVE_NAME: &str = "root";
+/// Archive and folder name for files downloaded from a selection.
+const SELECTION_ARCHIVE_NAME: &str = "selection";
 
 /// Execute `ls` command.
+///
+/// Short listings fill `terminal_columns` columns when given; otherwise each
+/// entry gets its own line with its description. With `readme_hint`, a
+/// directory that has a README ends with a line pointing at it. A file is
+/// listed on its own, named as the user wrote it. Entries with uncommitted
+/// creates or edits in `changes` are marked as local drafts.
+#[allow(clippy::too_many_arguments)]
 pub(super) fn execute_ls(
     path: Option<PathArg>,
     long: bool,
+    terminal_columns: Option<usize>,
+    readme_hint: bool,
+    now: u64,
     wallet_state: &WalletState,
     access_policy: &AccessPolicy,
     runtime_mounts: &[RuntimeMount],
     fs: &GlobalFs,
     cwd: &VirtualPath,
-) -> CommandResult {
+    changes: &ChangeSet,
+) -> Result<CommandResult, CommandError> {
     let target = path.as_ref().map(|p| p.as_str()).unwrap_or(".");
-    let resolved = match resolve_path_arg("ls", target, cwd) {
-        Ok(path) => path,
-        Err(e) => return e,
-    };
-
-    if let Some(entries) = fs.list_dir(&resolved) {
-        return CommandResult::output(format_ls_output(
-            &entries,
-            long,
-            wallet_state,
-            access_policy,
-            runtime_mounts,
-            fs,
-        ));
+    let resolved = resolve_path_arg("ls", target, cwd)?;
+
+    let listing = match fs.list_dir_for(&resolved, wallet_state) {
+        Some(entries) => Some((entries, true)),
+        None => match fs.lookup(&resolved) {
+            Ok(entry) if !entry.is_directory() && !target.ends_with('/') => {
+                listed_file(&resolved, target, wallet_state, fs).map(|entry| (vec![entry], false))
+            }
+            Ok(_) | Err(LookupMiss::NotADirectory) => {
+                return Err(CommandError::not_a_directory("ls", target));
+            }
+            Err(LookupMiss::NotFound) => None,
+        },
+    };
+
+    if let Some((entries, is_dir)) = listing {
+        let mut lines = if !long && let Some(line_width) = terminal_columns {
+            format_ls_columns(&entries, line_width, changes)
+        } else {
+            format_ls_output(
+                &entries,
+                long,
+                now,
+                wallet_state,
+                access_policy,
+                runtime_mounts,
+                fs,
+                changes,
+            )
+        };
+        if readme_hint
+            && is_dir
+            && let Some(readme) = fs.readme_for(&resolved)
+            && entries
+                .iter()
+                .any(|entry| entry.path == readme && !entry.masked)
+        {
+            let name = readme.file_name().unwrap_or_default();
+            let shown = match path.as_ref().map(PathArg::as_str) {
+                Some(dir) if !dir.is_empty() && dir != "." => {
+                    format!("{}/{name}", dir.trim_end_matches('/'))
+                }
+                _ => name.to_string(),
+            };
+            lines.push(OutputLine::info(format!(
+                "{name} available — 'cat {}'",
+                escape_word(&shown)
+            )));
+        }
+        return Ok(CommandResult::output(lines));
     }
 
-    if fs.exists(&resolved) {
-        CommandResult::error_line(format!("ls: cannot access '{}': Not a directory", target))
-    } else {
-        CommandResult::error_line(format!(
-            "ls: cannot access '{}': No such file or directory",
-            target
-        ))
+    Err(CommandError::not_found("ls", target))
+}
+
+/// The file at `path` as its directory listing shows it to `wallet_state`,
+/// renamed to `target` unless the listing masks it. `None` when the
+/// listing leaves it out.
+fn listed_file(
+    path: &VirtualPath,
+    target: &str,
+    wallet_state: &WalletState,
+    fs: &GlobalFs,
+) -> Option<DirEntry> {
+    let mut entry = fs
+        .list_dir_for(&path.parent()?, wallet_state)?
+        .into_iter()
+        .find(|entry| entry.path == *path)?;
+    if !entry.masked {
+        entry.name = target.to_string();
     }
+    Some(entry)
+}
+
+/// Whether `entry` is listed as a local draft. Masked entries never are,
+/// since that would give away what they stand in for.
+fn is_listed_draft(entry: &DirEntry, changes: &ChangeSet) -> bool {
+    !entry.masked && changes.is_draft(&entry.path)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn format_ls_output(
     entries: &[DirEntry],
     long: bool,
+    now: u64,
     wallet_state: &WalletState,
     access_policy: &AccessPolicy,
     runtime_mounts: &[RuntimeMount],
     fs: &GlobalFs,
+    changes: &ChangeSet,
 ) -> Vec<OutputLine> {
     if long {
         entries
@@ -61,71 +145,629 @@ fn format_ls_output(
                 let perms = fs_entry
                     .map(|e| fs.get_permissions(e, wallet_state, writable))
                     .unwrap_or_default();
-                OutputLine::long_entry(entry, &perms)
+                OutputLine::long_entry(entry, &perms, now)
+                    .with_draft(is_listed_draft(entry, changes))
             })
             .collect()
     } else {
         entries
             .iter()
-            .map(|entry| {
-                if entry.is_dir {
-                    OutputLine::dir_entry(&entry.name, &entry.title)
-                } else {
-                    let is_restricted = entry
-                        .meta
-                        .as_ref()
-                        .map(|m| m.is_restricted())
-                        .unwrap_or(false);
-                    OutputLine::file_entry(&entry.name, &entry.title, is_restricted)
-                }
-            })
+            .map(|entry| short_entry(entry, changes))
             .collect()
     }
 }
 
+/// A short listing line for `entry`: name, title, and draft marker.
+fn short_entry(entry: &DirEntry, changes: &ChangeSet) -> OutputLine {
+    let line = if entry.is_dir {
+        OutputLine::dir_entry(&entry.name, &entry.title)
+    } else {
+        OutputLine::file_entry(&entry.name, &entry.title, entry.is_restricted())
+    };
+    let line = line.with_draft(is_listed_draft(entry, changes));
+    match entry.link_path() {
+        Some(path) => line.with_path(path),
+        None => line,
+    }
+}
+
+/// Execute `ls --diff`: list a directory one entry per line behind a `+`
+/// (added), `~` (modified) or blank marker, then the entries the last
+/// manifest sync removed, then a summary. Only the root manifest is synced,
+/// so directories in other mounts have nothing to compare. Masked entries
+/// and removed restricted ones are never marked or named.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn execute_ls_diff(
+    path: Option<PathArg>,
+    sync: Option<&ManifestSync>,
+    wallet_state: &WalletState,
+    runtime_mounts: &[RuntimeMount],
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+    changes: &ChangeSet,
+) -> Result<CommandResult, CommandError> {
+    let target = path.as_ref().map(|p| p.as_str()).unwrap_or(".");
+    let resolved = resolve_path_arg("ls", target, cwd)?;
+    let Some(entries) = fs.list_dir_for(&resolved, wallet_state) else {
+        return Err(match fs.lookup(&resolved) {
+            Err(LookupMiss::NotFound) => CommandError::not_found("ls", target),
+            _ => CommandError::not_a_directory("ls", target),
+        });
+    };
+    if !mount_for_path(runtime_mounts, &resolved).is_some_and(|mount| mount.root.is_root()) {
+        return Ok(CommandResult::error_line(format!(
+            "ls: {target}: --diff only covers the site manifest"
+        ))
+        .with_exit_code(1));
+    }
+    let Some(sync) = sync else {
+        return Ok(CommandResult::error_line(
+            "ls: no manifest sync has changed anything yet; set MANIFEST_POLL to check for updates",
+        )
+        .with_exit_code(1));
+    };
+
+    let diff = diff_directory(&sync.previous, &sync.current, resolved.as_str());
+    let mut lines: Vec<OutputLine> = entries
+        .iter()
+        .map(|entry| {
+            let change = if entry.masked {
+                None
+            } else {
+                diff.change_of(&entry.name)
+            };
+            short_entry(entry, changes).with_change(change)
+        })
+        .collect();
+    let was_restricted = |name: &str| {
+        let path = resolved.join(name);
+        let path = path.as_str().trim_start_matches('/');
+        let files = sync
+            .previous
+            .files
+            .iter()
+            .map(|file| (&file.path, &file.meta));
+        let directories = sync
+            .previous
+            .directories
+            .iter()
+            .map(|dir| (&dir.path, &dir.meta));
+        files
+            .chain(directories)
+            .any(|(entry_path, meta)| entry_path == path && meta.is_restricted())
+    };
+    lines.extend(
+        diff.removed()
+            .filter(|removed| !was_restricted(&removed.name))
+            .map(|removed| {
+                let line = if removed.is_dir {
+                    OutputLine::dir_entry(&removed.name, "")
+                } else {
+                    OutputLine::file_entry(&removed.name, "", false)
+                };
+                line.with_change(Some(EntryChange::Removed))
+            }),
+    );
+    lines.push(OutputLine::info(if diff.is_empty() {
+        "no changes since the previous manifest".to_string()
+    } else {
+        format!("{} since the previous manifest", diff.summary())
+    }));
+    Ok(CommandResult::output(lines))
+}
+
+fn format_ls_columns(
+    entries: &[DirEntry],
+    line_width: usize,
+    changes: &ChangeSet,
+) -> Vec<OutputLine> {
+    let cells = entries
+        .iter()
+        .map(|entry| ListCell::new(entry).with_draft(is_listed_draft(entry, changes)))
+        .collect::<Vec<_>>();
+    let widths = cells
+        .iter()
+        .map(ListCell::display_width)
+        .collect::<Vec<_>>();
+    let layout = layout_columns(&widths, line_width);
+    layout
+        .rows
+        .iter()
+        .map(|row| {
+            let row_cells = row
+                .iter()
+                .enumerate()
+                .map(|(column, &idx)| ListCell {
+                    width: layout.column_widths[column],
+                    ..cells[idx].clone()
+                })
+                .collect();
+            OutputLine::list_row(row_cells)
+        })
+        .collect()
+}
+
 /// Execute `cd` command.
-pub(super) fn execute_cd(path: PathArg, fs: &GlobalFs, cwd: &VirtualPath) -> CommandResult {
+pub(super) fn execute_cd(
+    path: PathArg,
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+) -> Result<CommandResult, CommandError> {
     let target = path.as_str();
     if target.is_empty() {
-        return CommandResult::error_line("cd: : No such file or directory");
+        return Err(CommandError::not_found("cd", ""));
     }
 
-    let resolved = match resolve_path_arg("cd", target, cwd) {
-        Ok(path) => path,
-        Err(e) => return e,
+    let resolved = match lookup_user_path(fs, cwd, target) {
+        Ok((resolved, true)) => resolved,
+        Ok((_, false)) => return Err(CommandError::not_a_directory("cd", path)),
+        Err(UserPathError::Invalid(_)) => return Err(CommandError::invalid_path("cd", target)),
+        Err(UserPathError::NotFound(_)) => return Err(CommandError::not_found("cd", path)),
+        Err(UserPathError::NotADirectory(_)) => {
+            return Err(CommandError::not_a_directory("cd", path));
+        }
     };
 
-    if !fs.exists(&resolved) {
-        return CommandResult::error_line(format!("cd: no such file or directory: {}", path));
+    Ok(CommandResult::navigate(RouteRequest::new(
+        request_path_for_canonical_path(&resolved, RouteSurface::Shell),
+    )))
+}
+
+/// Execute `pwd`. The logical path is the one `$PWD` holds (`~` for the
+/// root); the physical path is `cwd` relative to the mount holding it,
+/// `.` at a mount root.
+pub(super) fn execute_pwd(
+    physical: bool,
+    runtime_mounts: &[RuntimeMount],
+    cwd: &VirtualPath,
+) -> CommandResult {
+    let path = if physical {
+        mount_for_path(runtime_mounts, cwd)
+            .and_then(|mount| cwd.strip_prefix(&mount.root).map(str::to_string))
+            .map(|path| {
+                if path.is_empty() {
+                    ".".to_string()
+                } else {
+                    path
+                }
+            })
+            .unwrap_or_else(|| cwd.as_str().to_string())
+    } else {
+        display_path_for(cwd)
+    };
+    CommandResult::output(vec![OutputLine::text(path)])
+}
+
+/// Execute `cd -N` / `cd +N`: request a jump through the navigation
+/// history when it holds that many entries in the chosen direction.
+pub(super) fn execute_cd_history(offset: isize, history: &NavigationHistory) -> CommandResult {
+    let (available, direction) = if offset < 0 {
+        (history.back.len(), "back")
+    } else {
+        (history.forward.len(), "forward")
+    };
+    if offset.unsigned_abs() > available {
+        let noun = if available == 1 { "entry" } else { "entries" };
+        return CommandResult::error_line(format!(
+            "cd: {offset:+}: only {available} {noun} {direction} in the history"
+        ));
     }
+    CommandResult::empty().with_side_effect(SideEffect::JumpHistory { offset })
+}
 
-    if !fs.is_directory(&resolved) {
-        return CommandResult::error_line(format!("cd: not a directory: {}", path));
+/// Execute `dirs`: the current route as `0`, earlier routes as `-1`, `-2`,
+/// … (most recent first), then later ones as `+1`, `+2`, … — the offsets
+/// `cd` accepts.
+pub(super) fn execute_dirs(clear: bool, history: &NavigationHistory) -> CommandResult {
+    if clear {
+        return CommandResult::empty().with_side_effect(SideEffect::ClearNavigationHistory);
     }
+    let Some(current) = &history.current else {
+        return CommandResult::output(vec![OutputLine::info("dirs: no navigation history")]);
+    };
 
-    CommandResult::navigate(RouteRequest::new(request_path_for_canonical_path(
-        &resolved,
-        RouteSurface::Shell,
-    )))
+    let mut output = vec![OutputLine::text(format!("{:>4}  {}", 0, current))];
+    output.extend(history.back.iter().enumerate().map(|(index, route)| {
+        OutputLine::text(format!("{:>4}  {}", -(index as isize + 1), route))
+    }));
+    output.extend(history.forward.iter().enumerate().map(|(index, route)| {
+        OutputLine::text(format!("{:>4}  {}", format!("+{}", index + 1), route))
+    }));
+    CommandResult::output(output)
 }
 
 /// Execute `cat` command.
-pub(super) fn execute_cat(file: PathArg, fs: &GlobalFs, cwd: &VirtualPath) -> CommandResult {
-    let resolved = match resolve_path_arg("cat", file.as_str(), cwd) {
-        Ok(path) => path,
-        Err(e) => return e,
+pub(super) fn execute_cat(
+    file: PathArg,
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+) -> Result<CommandResult, CommandError> {
+    let resolved = match lookup_user_path(fs, cwd, file.as_str()) {
+        Ok((resolved, false)) => resolved,
+        Ok((_, true)) => return Err(CommandError::is_a_directory("cat", file)),
+        Err(UserPathError::Invalid(raw)) => return Err(CommandError::invalid_path("cat", raw)),
+        Err(UserPathError::NotFound(_)) => return Err(CommandError::not_found("cat", file)),
+        Err(UserPathError::NotADirectory(_)) => {
+            return Err(CommandError::not_a_directory("cat", file));
+        }
+    };
+
+    Ok(CommandResult::navigate(RouteRequest::new(
+        request_path_for_canonical_path(&resolved, RouteSurface::Content),
+    )))
+}
+
+/// Execute `explorer`: switch to the content view of `path`, or of `cwd`
+/// itself, so the view picks up where the terminal is.
+pub(super) fn execute_explorer(
+    path: Option<PathArg>,
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+) -> Result<CommandResult, CommandError> {
+    let Some(path) = path else {
+        return Ok(CommandResult::open_explorer(RouteRequest::new(
+            request_path_for_canonical_path(cwd, RouteSurface::Content),
+        )));
     };
 
-    if !fs.exists(&resolved) {
-        return CommandResult::error_line(format!("cat: {}: No such file or directory", file));
+    match route_for_user_path(fs, cwd, path.as_str(), RouteSurface::Content) {
+        Ok(route) => Ok(CommandResult::open_explorer(route)),
+        Err(UserPathError::Invalid(raw)) => Err(CommandError::invalid_path("explorer", raw)),
+        Err(UserPathError::NotFound(_)) => Err(CommandError::not_found("explorer", path)),
+        Err(UserPathError::NotADirectory(_)) => {
+            Err(CommandError::not_a_directory("explorer", path))
+        }
     }
+}
+
+/// Execute `search`: hand every markdown file in the mount holding `cwd`
+/// to the target, which reads and ranks them.
+pub(super) fn execute_search(
+    query: String,
+    runtime_mounts: &[RuntimeMount],
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+) -> CommandResult {
+    let root = mount_root_for(runtime_mounts, cwd);
+    let mut paths: Vec<VirtualPath> = fs
+        .metadata_entries()
+        .into_iter()
+        .map(|(path, _)| path)
+        .filter(|path| {
+            path.starts_with(&root)
+                && path.as_str().ends_with(".md")
+                && matches!(fs.get_entry(path), Some(FsEntry::File { .. }))
+        })
+        .collect();
+    if paths.is_empty() {
+        return CommandResult::error_line("search: no markdown files in this mount");
+    }
+    paths.sort();
+    CommandResult::empty().with_side_effect(SideEffect::SearchContent { query, paths })
+}
+
+/// Execute `feed`: collect the dated markdown files under `path`
+/// (default `~/blog`) for the target to render as an Atom document.
+/// Hidden and access-restricted files are left out; an authored `date`
+/// wins over the modification time.
+pub(super) fn execute_feed(
+    path: Option<PathArg>,
+    output: FeedOutput,
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+) -> Result<CommandResult, CommandError> {
+    let raw = path.as_ref().map_or(DEFAULT_FEED_DIR, PathArg::as_str);
+    let dir = match lookup_user_path(fs, cwd, raw) {
+        Ok((dir, true)) => dir,
+        Ok(_) => return Err(CommandError::not_a_directory("feed", raw)),
+        Err(UserPathError::Invalid(_)) => return Err(CommandError::invalid_path("feed", raw)),
+        Err(UserPathError::NotFound(_)) => return Err(CommandError::not_found("feed", raw)),
+        Err(UserPathError::NotADirectory(_)) => {
+            return Err(CommandError::not_a_directory("feed", raw));
+        }
+    };
 
-    if fs.is_directory(&resolved) {
-        return CommandResult::error_line(format!("cat: {}: Is a directory", file));
+    let entries = fs
+        .metadata_entries()
+        .into_iter()
+        .filter(|(path, meta)| {
+            path.starts_with(&dir)
+                && path.as_str().ends_with(".md")
+                && !meta.is_restricted()
+                && !path.segments().any(|segment| segment.starts_with('.'))
+                && matches!(fs.get_entry(path), Some(FsEntry::File { .. }))
+        })
+        .filter_map(|(path, meta)| {
+            let updated = match meta.date().and_then(iso_date_prefix) {
+                Some(day) => format!("{day}T00:00:00Z"),
+                None => rfc3339(meta.modified_at()?),
+            };
+            let title = meta
+                .title()
+                .or_else(|| path.file_name()?.strip_suffix(".md"))
+                .unwrap_or_default()
+                .to_string();
+            Some(FeedEntry {
+                title,
+                route: content_route_for_path(path.as_str()),
+                updated,
+                summary: meta.description().map(str::to_string),
+            })
+        })
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        return Ok(CommandResult::error_line(format!(
+            "feed: {raw}: no dated markdown files"
+        )));
     }
 
-    CommandResult::navigate(RouteRequest::new(request_path_for_canonical_path(
+    let title = fs
+        .get_entry(&dir)
+        .and_then(|entry| entry.meta().title().map(str::to_string))
+        .or_else(|| dir.file_name().map(str::to_string))
+        .unwrap_or_else(|| "~".to_string());
+    let feed = AtomFeed::new(title, content_route_for_path(dir.as_str()), entries);
+    Ok(CommandResult::empty().with_side_effect(SideEffect::PublishFeed { feed, output }))
+}
+
+/// Execute `sha256`: hand the target the file to digest or, with `check`,
+/// the file or every file under a directory that has a recorded checksum.
+/// Files are named as the user wrote the path, so the report reads like
+/// `sha256sum` run from the current directory.
+pub(super) fn execute_sha256(
+    path: PathArg,
+    check: bool,
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+) -> Result<CommandResult, CommandError> {
+    let raw = path.as_str();
+    let (resolved, is_dir) = match lookup_user_path(fs, cwd, raw) {
+        Ok(found) => found,
+        Err(UserPathError::Invalid(_)) => return Err(CommandError::invalid_path("sha256", raw)),
+        Err(UserPathError::NotFound(_)) => return Err(CommandError::not_found("sha256", raw)),
+        Err(UserPathError::NotADirectory(_)) => {
+            return Err(CommandError::not_a_directory("sha256", raw));
+        }
+    };
+    let expected = |path: &VirtualPath| {
+        fs.get_entry(path)
+            .and_then(|entry| entry.meta().content_sha256().map(str::to_string))
+    };
+
+    let targets = if !is_dir {
+        let expected = expected(&resolved);
+        if check && expected.is_none() {
+            return Ok(CommandResult::error_line(format!(
+                "sha256: {raw}: no recorded checksum"
+            )));
+        }
+        vec![ChecksumTarget {
+            path: resolved,
+            display: raw.to_string(),
+            expected,
+        }]
+    } else if !check {
+        return Err(CommandError::is_a_directory("sha256", raw));
+    } else {
+        let prefix = raw.trim_end_matches('/');
+        let mut targets: Vec<_> = fs
+            .metadata_entries()
+            .into_iter()
+            .filter(|(path, _)| {
+                path.starts_with(&resolved)
+                    && matches!(fs.get_entry(path), Some(FsEntry::File { .. }))
+            })
+            .filter_map(|(path, meta)| {
+                let expected = meta.content_sha256()?.to_string();
+                let relative = path.strip_prefix(&resolved)?;
+                let display = if prefix.is_empty() || prefix == "." {
+                    relative.to_string()
+                } else {
+                    format!("{prefix}/{relative}")
+                };
+                Some(ChecksumTarget {
+                    display,
+                    expected: Some(expected),
+                    path,
+                })
+            })
+            .collect();
+        if targets.is_empty() {
+            return Ok(CommandResult::error_line(format!(
+                "sha256: {raw}: no files with recorded checksums"
+            )));
+        }
+        targets.sort_by(|a, b| a.path.cmp(&b.path));
+        targets
+    };
+    Ok(CommandResult::empty().with_side_effect(SideEffect::Checksum { targets, check }))
+}
+
+/// Execute `export-zip`: every readable file under a directory, named
+/// under the directory's own name, for the target to fetch and pack.
+pub(super) fn execute_export_zip(
+    path: Option<PathArg>,
+    wallet_state: &WalletState,
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+) -> Result<CommandResult, CommandError> {
+    let raw = path.as_ref().map_or(".", PathArg::as_str);
+    let resolved = match lookup_user_path(fs, cwd, raw) {
+        Ok((resolved, true)) => resolved,
+        Ok((_, false)) | Err(UserPathError::NotADirectory(_)) => {
+            return Err(CommandError::not_a_directory("export-zip", raw));
+        }
+        Err(UserPathError::Invalid(_)) => {
+            return Err(CommandError::invalid_path("export-zip", raw));
+        }
+        Err(UserPathError::NotFound(_)) => return Err(CommandError::not_found("export-zip", raw)),
+    };
+    let folder = resolved
+        .file_name()
+        .unwrap_or(ROOT_ARCHIVE_NAME)
+        .to_string();
+    let export = zip_export(
+        format!("{folder}.zip"),
+        &folder,
+        std::slice::from_ref(&resolved),
         &resolved,
-        RouteSurface::Content,
-    )))
+        wallet_state,
+        fs,
+    );
+    if export.files.is_empty() {
+        let reason = if export.skipped.is_empty() {
+            "no files to export"
+        } else {
+            "no readable files to export"
+        };
+        return Ok(CommandResult::error_line(format!(
+            "export-zip: {raw}: {reason}"
+        )));
+    }
+    Ok(CommandResult::empty().with_side_effect(SideEffect::ExportZip(export)))
+}
+
+/// Files selected together, for the target to pack into one archive:
+/// selected directories bring every file below them. Entries are named by
+/// their path below the closest directory holding the whole selection.
+/// `None` when nothing selected is readable.
+pub fn selection_zip_export(
+    paths: &[VirtualPath],
+    wallet_state: &WalletState,
+    fs: &GlobalFs,
+) -> Option<ZipExport> {
+    let first = paths.first()?;
+    let mut base = first.parent().unwrap_or_else(VirtualPath::root);
+    while !paths.iter().all(|path| path.starts_with(&base)) {
+        base = base.parent().unwrap_or_else(VirtualPath::root);
+    }
+    let export = zip_export(
+        format!("{SELECTION_ARCHIVE_NAME}.zip"),
+        SELECTION_ARCHIVE_NAME,
+        paths,
+        &base,
+        wallet_state,
+        fs,
+    );
+    (!export.files.is_empty()).then_some(export)
+}
+
+/// Every file at or under one of `roots`, named `{folder}/{path below
+/// base}`. Hidden files below a root are left out as `ls` leaves them out;
+/// files the wallet can't read are listed as skipped.
+fn zip_export(
+    filename: String,
+    folder: &str,
+    roots: &[VirtualPath],
+    base: &VirtualPath,
+    wallet_state: &WalletState,
+    fs: &GlobalFs,
+) -> ZipExport {
+    let mut entries: Vec<_> = fs
+        .metadata_entries()
+        .into_iter()
+        .filter_map(|(path, meta)| {
+            let entry = fs.get_entry(&path).filter(|entry| !entry.is_directory())?;
+            let below_root = roots.iter().find_map(|root| path.strip_prefix(root))?;
+            if below_root
+                .split('/')
+                .any(|segment| segment.starts_with('.'))
+            {
+                return None;
+            }
+            let readable = fs.get_permissions(entry, wallet_state, false).read;
+            let name = format!("{folder}/{}", path.strip_prefix(base)?);
+            Some((path, name, meta.size_bytes(), readable))
+        })
+        .collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut export = ZipExport {
+        filename,
+        files: Vec::new(),
+        skipped: Vec::new(),
+        total_bytes: 0,
+    };
+    for (path, name, size, readable) in entries {
+        if readable {
+            export.total_bytes += size.unwrap_or(0);
+            export.files.push(ZipExportFile { path, name });
+        } else {
+            export.skipped.push(name);
+        }
+    }
+    export
+}
+
+/// Execute `export-draft`: hand the text of a local draft to the target
+/// to download under the file's own name, ready to commit elsewhere.
+pub(super) fn execute_export_draft(
+    path: PathArg,
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+    changes: &ChangeSet,
+) -> Result<CommandResult, CommandError> {
+    let raw = path.as_str();
+    let resolved = resolve_path_arg("export-draft", raw, cwd)?;
+    let Some(content) = changes.draft_text(&resolved) else {
+        return Err(match fs.get_entry(&resolved) {
+            Some(entry) if entry.is_directory() => {
+                CommandError::is_a_directory("export-draft", raw)
+            }
+            Some(_) => CommandError::usage("export-draft", format!("{raw}: no local draft")),
+            None => CommandError::not_found("export-draft", raw),
+        });
+    };
+    let filename = resolved.file_name().unwrap_or(raw).to_string();
+    Ok(
+        CommandResult::empty().with_side_effect(SideEffect::DownloadDraft {
+            filename,
+            content: content.to_string(),
+        }),
+    )
+}
+
+fn mount_root_for(runtime_mounts: &[RuntimeMount], cwd: &VirtualPath) -> VirtualPath {
+    mount_for_path(runtime_mounts, cwd).map_or_else(VirtualPath::root, |mount| mount.root)
+}
+
+/// Execute `tags`: tag counts across the mount holding `cwd`, or with a
+/// tag, one path per line for every entry carrying it.
+pub(super) fn execute_tags(
+    tag: Option<String>,
+    runtime_mounts: &[RuntimeMount],
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+) -> CommandResult {
+    let root =
+        mount_for_path(runtime_mounts, cwd).map_or_else(VirtualPath::root, |mount| mount.root);
+    let index = fs.tag_index(&root);
+
+    match tag {
+        Some(tag) => {
+            let paths = index.paths(&tag);
+            if paths.is_empty() {
+                return CommandResult::error_line(format!("tags: no entries tagged '{tag}'"));
+            }
+            CommandResult::output(
+                paths
+                    .iter()
+                    .map(|path| OutputLine::text(path.as_str()))
+                    .collect(),
+            )
+        }
+        None => {
+            let width = index
+                .counts()
+                .map(|(tag, _)| tag.chars().count())
+                .max()
+                .unwrap_or(0);
+            CommandResult::output(
+                index
+                    .counts()
+                    .map(|(tag, count)| OutputLine::text(format!("{tag:<width$}  {count}")))
+                    .collect(),
+            )
+        }
+    }
 }
@@ -0,0 +1,182 @@
+use crate::domain::{RuntimeMount, VirtualPath};
+use crate::engine::shell::session::is_valid_session_name;
+use crate::engine::shell::{CommandError, CommandResult, SessionAction, SideEffect};
+
+use super::mount_for_path;
+
+const DEFAULT_TRANSCRIPT_STEM: &str = "websh-session";
+const MAX_FILENAME_LEN: usize = 64;
+
+/// Execute `export-session` command.
+///
+/// Resolves the download filename and the mount owning `cwd`; the target
+/// renders the transcript and performs the download.
+pub(super) fn execute_export_session(
+    filename: Option<String>,
+    html: bool,
+    runtime_mounts: &[RuntimeMount],
+    cwd: &VirtualPath,
+) -> CommandResult {
+    let filename = match filename {
+        Some(requested) => match sanitize_transcript_filename(&requested) {
+            Some(name) => name,
+            None => {
+                return CommandResult::error_line(format!(
+                    "export-session: invalid filename '{requested}'"
+                ));
+            }
+        },
+        None => DEFAULT_TRANSCRIPT_STEM.to_string(),
+    };
+
+    CommandResult::empty().with_side_effect(SideEffect::ExportSession {
+        filename: with_transcript_extension(filename, html),
+        html,
+        cwd: cwd.clone(),
+        mount: mount_for_path(runtime_mounts, cwd),
+    })
+}
+
+/// Reduce a user-supplied name to a safe download filename.
+///
+/// Directory components are dropped, characters outside `[A-Za-z0-9._-]`
+/// become `_`, and leading dots are stripped so the result is never hidden
+/// or a relative path. Returns `None` when nothing usable remains.
+/// Execute `session`: check the slot name, then hand the action to the
+/// target, which captures or applies the state and owns the slots.
+pub(super) fn execute_session(action: SessionAction) -> Result<CommandResult, CommandError> {
+    if let SessionAction::Save(name) | SessionAction::Load(name) | SessionAction::Remove(name) =
+        &action
+        && !is_valid_session_name(name)
+    {
+        return Err(CommandError::usage(
+            "session",
+            format!("invalid name '{name}' (letters, digits, '-', '_' and '.', up to 32)"),
+        ));
+    }
+    Ok(CommandResult::empty().with_side_effect(SideEffect::Session(action)))
+}
+
+fn sanitize_transcript_filename(requested: &str) -> Option<String> {
+    let base = requested.rsplit(['/', '\\']).next().unwrap_or_default();
+    let sanitized: String = base
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '.' | '-' | '_') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .skip_while(|ch| *ch == '.')
+        .take(MAX_FILENAME_LEN)
+        .collect();
+
+    if sanitized.chars().any(|ch| ch.is_ascii_alphanumeric()) {
+        Some(sanitized)
+    } else {
+        None
+    }
+}
+
+fn with_transcript_extension(filename: String, html: bool) -> String {
+    let lower = filename.to_ascii_lowercase();
+    if html {
+        if lower.ends_with(".html") || lower.ends_with(".htm") {
+            filename
+        } else {
+            format!("{filename}.html")
+        }
+    } else if filename.contains('.') {
+        filename
+    } else {
+        format!("{filename}.txt")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_keeps_safe_names() {
+        assert_eq!(
+            sanitize_transcript_filename("transcript.txt").as_deref(),
+            Some("transcript.txt")
+        );
+        assert_eq!(
+            sanitize_transcript_filename("my-log_2.html").as_deref(),
+            Some("my-log_2.html")
+        );
+    }
+
+    #[test]
+    fn sanitize_drops_directories_and_unsafe_characters() {
+        assert_eq!(
+            sanitize_transcript_filename("../../etc/passwd").as_deref(),
+            Some("passwd")
+        );
+        assert_eq!(
+            sanitize_transcript_filename("C:\\tmp\\my log?.txt").as_deref(),
+            Some("my_log_.txt")
+        );
+        assert_eq!(
+            sanitize_transcript_filename(".hidden").as_deref(),
+            Some("hidden")
+        );
+    }
+
+    #[test]
+    fn sanitize_rejects_names_without_content() {
+        assert_eq!(sanitize_transcript_filename(""), None);
+        assert_eq!(sanitize_transcript_filename(".."), None);
+        assert_eq!(sanitize_transcript_filename("dir/"), None);
+        assert_eq!(sanitize_transcript_filename("???"), None);
+    }
+
+    #[test]
+    fn sanitize_truncates_long_names() {
+        let long = "a".repeat(200);
+        assert_eq!(
+            sanitize_transcript_filename(&long).map(|name| name.len()),
+            Some(MAX_FILENAME_LEN)
+        );
+    }
+
+    #[test]
+    fn extension_matches_format() {
+        assert_eq!(with_transcript_extension("log".into(), false), "log.txt");
+        assert_eq!(with_transcript_extension("log.md".into(), false), "log.md");
+        assert_eq!(with_transcript_extension("log".into(), true), "log.html");
+        assert_eq!(with_transcript_extension("log.HTM".into(), true), "log.HTM");
+        assert_eq!(
+            with_transcript_extension("log.txt".into(), true),
+            "log.txt.html"
+        );
+    }
+
+    #[test]
+    fn session_hands_valid_actions_to_the_target() {
+        for action in [
+            SessionAction::Save("work".to_string()),
+            SessionAction::Load("work".to_string()),
+            SessionAction::Remove("work".to_string()),
+            SessionAction::List,
+            SessionAction::RestoreLast,
+        ] {
+            let result = execute_session(action.clone()).expect("valid action");
+            assert_eq!(result.side_effects, vec![SideEffect::Session(action)]);
+        }
+    }
+
+    #[test]
+    fn session_rejects_invalid_names() {
+        let error = execute_session(SessionAction::Save("my work".to_string())).unwrap_err();
+        assert_eq!(error.exit_code(), 2);
+        assert!(
+            error
+                .to_string()
+                .starts_with("session: invalid name 'my work'")
+        );
+    }
+}
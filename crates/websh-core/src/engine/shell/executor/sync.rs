@@ -121,7 +121,7 @@ fn execute_sync_commit(
         runtime_mounts,
         &mount_root,
     ) {
-        return e;
+        return e.into();
     }
 
     CommandResult {
@@ -1,8 +1,12 @@
 use super::super::SideEffect;
 use super::*;
-use crate::domain::{ChangeSet, ChangeType, EntryExtensions, NodeKind, WalletState};
+use crate::domain::{ChangeSet, ChangeType, EntryExtensions, NodeKind, NodeMetadata, WalletState};
 use crate::engine::filesystem::{GlobalFs, RouteRequest};
-use crate::engine::shell::{AuthAction, OutputLineData, PathArg, SyncSubcommand};
+use crate::engine::shell::{
+    AuthAction, ChecksumTarget, EntryCopyKind, EntryRemoval, FeedOutput, FileStage, ListFormat,
+    ManifestSync, NavigationHistory, OutputLine, OutputLineData, PathArg, StageFile,
+    SyncSubcommand, TutorialAction, UsageReport, UsageStats, ViewMode,
+};
 
 use super::sync::sync_mount_root;
 use super::write::{blank_dir_meta, blank_file_meta};
@@ -43,6 +47,20 @@ fn home_vpath(path: &str) -> VirtualPath {
     home_cwd(path)
 }
 
+/// A filesystem holding empty files at the given absolute paths.
+fn fs_with_files(files: &[(&str, NodeMetadata)]) -> GlobalFs {
+    let mut fs = GlobalFs::empty();
+    for (path, meta) in files {
+        fs.upsert_file(
+            VirtualPath::from_absolute(*path).unwrap(),
+            String::new(),
+            meta.clone(),
+            EntryExtensions::default(),
+        );
+    }
+    fs
+}
+
 fn upsert(changes: &mut ChangeSet, path: VirtualPath, change: ChangeType) {
     changes.upsert_at(path, change, 1234);
 }
@@ -108,6 +126,18 @@ fn test_theme_lists_available_palettes() {
     );
 }
 
+#[test]
+fn test_manifest_lint_requests_report() {
+    let (ws, fs) = empty_state();
+    let cs = ChangeSet::new();
+    let result = execute_command(Command::ManifestLint, &ws, &fs, &root_cwd(), &cs, None);
+    assert!(result.output.is_empty());
+    assert_eq!(
+        result.side_effects.first().cloned(),
+        Some(SideEffect::ReportManifestIssues)
+    );
+}
+
 #[test]
 fn test_theme_sets_known_palette() {
     let (ws, fs) = empty_state();
@@ -178,1025 +208,1753 @@ fn test_cat_navigates_content_surface() {
 }
 
 #[test]
-fn test_unknown_command_exit_127() {
-    let (ws, fs) = empty_state();
-    let cs = ChangeSet::new();
-    let result = execute_command(
-        Command::Unknown("foobar".into()),
-        &ws,
-        &fs,
-        &root_cwd(),
-        &cs,
-        None,
+fn test_cat_resolves_names_with_escaped_or_quoted_spaces() {
+    use crate::engine::shell::execute_pipeline_with_context;
+    use crate::engine::shell::parser::parse_input;
+
+    let mut fs = GlobalFs::empty();
+    fs.upsert_file(
+        VirtualPath::from_absolute("/blog/My Notes.md").unwrap(),
+        "notes".into(),
+        blank_file_meta(NodeKind::Asset),
+        EntryExtensions::default(),
     );
-    assert_eq!(result.exit_code, 127);
+    let cat = |input: &str| {
+        execute_pipeline_with_context(
+            &parse_input(input, &[]),
+            &WalletState::Disconnected,
+            &[],
+            &fs,
+            &home_cwd("blog"),
+            &ChangeSet::new(),
+            None,
+            &ExecutionContext::default(),
+        )
+    };
+
+    for input in [
+        "cat My\\ Notes.md",
+        "cat \"My Notes.md\"",
+        "cat 'My Notes.md'",
+    ] {
+        let result = cat(input);
+        assert_eq!(result.exit_code, 0, "{input}");
+        assert_eq!(
+            result.side_effects,
+            vec![SideEffect::Navigate(RouteRequest::new("/blog/My Notes.md"))],
+            "{input}"
+        );
+    }
+    // Unescaped, the name splits into two arguments.
+    assert_ne!(cat("cat My Notes.md").exit_code, 0);
 }
 
 #[test]
-fn test_ls_nonexistent_exit_1() {
-    let (ws, fs) = empty_state();
+fn test_explorer_switches_to_the_content_view_in_place() {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_file(
+        VirtualPath::from_absolute("/blog/hello.md").unwrap(),
+        "hello".into(),
+        blank_file_meta(NodeKind::Asset),
+        EntryExtensions::default(),
+    );
+    let ws = WalletState::Disconnected;
     let cs = ChangeSet::new();
-    let result = execute_command(
-        Command::Ls {
-            path: Some(super::super::PathArg::new("nonexistent")),
-            long: false,
-        },
-        &ws,
-        &fs,
-        &root_cwd(),
-        &cs,
-        None,
+    let blog = VirtualPath::from_absolute("/blog").unwrap();
+    let explorer = |path: Option<&str>| {
+        execute_command(
+            Command::Explorer(path.map(PathArg::new)),
+            &ws,
+            &fs,
+            &blog,
+            &cs,
+            None,
+        )
+    };
+    let opens = |route: &str| {
+        vec![SideEffect::SwitchViewAndNavigate(
+            ViewMode::Explorer,
+            RouteRequest::new(route),
+        )]
+    };
+
+    assert_eq!(explorer(None).side_effects, opens("/blog"));
+    assert_eq!(
+        explorer(Some("hello.md")).side_effects,
+        opens("/blog/hello.md")
     );
-    assert_eq!(result.exit_code, 1);
-    assert!(!result.output.is_empty());
-}
+    assert_eq!(explorer(Some("..")).side_effects, opens("/"));
 
-#[test]
-fn test_cat_missing_operand_exit_1() {
-    let (ws, fs) = empty_state();
-    let cs = ChangeSet::new();
-    let result = execute_command(Command::Cat(None), &ws, &fs, &root_cwd(), &cs, None);
-    assert_eq!(result.exit_code, 1);
-    assert!(
-            result
-                .output
-                .iter()
-                .any(|l| matches!(&l.data, crate::engine::shell::OutputLineData::Error(s) if s == "cat: missing file operand"))
-        );
+    let missing = explorer(Some("gone.md"));
+    assert_eq!(missing.exit_code, 1);
+    assert!(missing.side_effects.is_empty());
+    assert_eq!(
+        output_text(&missing),
+        ["explorer: gone.md: No such file or directory"]
+    );
 }
 
-#[test]
-fn test_unset_missing_operand_exit_1() {
-    let (ws, fs) = empty_state();
-    let cs = ChangeSet::new();
-    let result = execute_command(Command::Unset(None), &ws, &fs, &root_cwd(), &cs, None);
-    assert_eq!(result.exit_code, 1);
+fn output_text(result: &CommandResult) -> Vec<String> {
+    result
+        .output
+        .iter()
+        .map(OutputLine::to_plain_text)
+        .collect()
 }
 
 #[test]
-fn test_execute_export_multi_processes_each_assignment() {
-    let (ws, fs) = empty_state();
-    let cs = ChangeSet::new();
+fn test_tags_counts_tags_across_mount() {
+    let tagged = |tags: &[&str]| {
+        let mut meta = blank_file_meta(NodeKind::Page);
+        meta.authored.tags = Some(tags.iter().map(|tag| tag.to_string()).collect());
+        meta
+    };
+    let fs = fs_with_files(&[
+        ("/blog/rust.md", tagged(&["rust", "wasm"])),
+        ("/blog/notes.md", tagged(&["Rust"])),
+        ("/papers/tabula.pdf", tagged(&["math"])),
+    ]);
     let result = execute_command(
-        Command::Export(vec![
-            "FOO_P2_A=alpha".to_string(),
-            "BAR_P2_A=beta".to_string(),
-        ]),
-        &ws,
+        Command::Tags(None),
+        &WalletState::Disconnected,
         &fs,
-        &root_cwd(),
-        &cs,
+        &home_cwd("blog"),
+        &ChangeSet::new(),
         None,
     );
     assert_eq!(result.exit_code, 0);
-    assert!(result.output.is_empty());
-    assert_eq!(
-        result.side_effects,
-        vec![
-            SideEffect::SetEnvVar {
-                key: "FOO_P2_A".to_string(),
-                value: "alpha".to_string(),
-            },
-            SideEffect::SetEnvVar {
-                key: "BAR_P2_A".to_string(),
-                value: "beta".to_string(),
-            },
-        ]
-    );
+    assert_eq!(output_text(&result), vec!["math  1", "rust  2", "wasm  1"]);
 }
 
 #[test]
-fn test_cd_empty_string_exit_1() {
-    // POSIX bash: `cd ""` errors with "cd: : No such file or directory".
-    // Must exercise a non-Root route so the early `at_root` branch doesn't
-    // short-circuit to the generic mount-alias error.
-    let (ws, fs) = empty_state();
-    let cs = ChangeSet::new();
-    let browse_route = home_cwd("");
+fn test_tags_lists_paths_for_one_tag() {
+    let tagged = |tags: &[&str]| {
+        let mut meta = blank_file_meta(NodeKind::Page);
+        meta.authored.tags = Some(tags.iter().map(|tag| tag.to_string()).collect());
+        meta
+    };
+    let fs = fs_with_files(&[
+        ("/blog/rust.md", tagged(&["rust", "wasm"])),
+        ("/blog/notes.md", tagged(&["Rust"])),
+        ("/papers/tabula.pdf", tagged(&["math"])),
+    ]);
     let result = execute_command(
-        Command::Cd(super::super::PathArg::new("")),
-        &ws,
+        Command::Tags(Some("RUST".to_string())),
+        &WalletState::Disconnected,
         &fs,
-        &browse_route,
-        &cs,
+        &root_cwd(),
+        &ChangeSet::new(),
         None,
     );
-    assert_eq!(result.exit_code, 1);
-    assert!(result.side_effects.first().cloned().is_none());
-    assert!(
-        result.output.iter().any(|l| matches!(
-            &l.data,
-            crate::engine::shell::OutputLineData::Error(s) if s == "cd: : No such file or directory"
-        )),
-        "expected POSIX cd error; got: {:?}",
-        result.output
+    assert_eq!(
+        output_text(&result),
+        vec!["/blog/notes.md", "/blog/rust.md"]
     );
 }
 
 #[test]
-fn test_touch_requires_admin() {
-    let (ws, fs) = empty_state();
-    let cs = ChangeSet::new();
+fn test_tags_unknown_tag_fails() {
+    let mut meta = blank_file_meta(NodeKind::Page);
+    meta.authored.tags = Some(vec!["rust".to_string()]);
+    let fs = fs_with_files(&[("/blog/rust.md", meta)]);
     let result = execute_command(
-        Command::Touch {
-            path: PathArg::new("new.md"),
-        },
-        &ws,
+        Command::Tags(Some("cooking".to_string())),
+        &WalletState::Disconnected,
         &fs,
-        &home_cwd(""),
-        &cs,
+        &root_cwd(),
+        &ChangeSet::new(),
         None,
     );
     assert_eq!(result.exit_code, 1);
-    assert!(result.side_effects.first().cloned().is_none());
 }
 
 #[test]
-fn test_write_rejects_runtime_state_tree() {
-    let (_ws, fs) = empty_state();
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
+fn test_feed_collects_dated_markdown_under_blog() {
+    let post = |title: Option<&str>, date: Option<&str>, modified: Option<u64>| {
+        let mut meta = blank_file_meta(NodeKind::Page);
+        meta.authored.title = title.map(str::to_string);
+        meta.authored.date = date.map(str::to_string);
+        meta.derived.modified_at = modified;
+        meta
+    };
+    let fs = fs_with_files(&[
+        (
+            "/blog/old.md",
+            post(Some("Old & <new>"), None, Some(86_400)),
+        ),
+        (
+            "/blog/2024/dated.md",
+            post(None, Some("2024-09-12"), Some(0)),
+        ),
+        ("/blog/undated.md", post(Some("Undated"), None, None)),
+        (
+            "/blog/.draft.md",
+            post(Some("Draft"), Some("2025-01-01"), None),
+        ),
+        (
+            "/blog/data.json",
+            post(Some("Data"), Some("2025-01-01"), None),
+        ),
+    ]);
     let result = execute_command(
-        Command::Touch {
-            path: PathArg::new("/.websh/state/new.md"),
+        Command::Feed {
+            path: None,
+            output: FeedOutput::Download,
         },
-        &ws,
+        &WalletState::Disconnected,
         &fs,
-        &home_cwd(""),
-        &cs,
+        &home_cwd("papers"),
+        &ChangeSet::new(),
         None,
     );
-
-    assert_eq!(result.exit_code, 1);
-    assert!(result.side_effects.first().cloned().is_none());
-    assert!(result.output.iter().any(|line| {
-        matches!(
-            &line.data,
-            crate::engine::shell::OutputLineData::Error(message)
-                if message.contains("read-only filesystem")
-        )
-    }));
+    assert_eq!(result.exit_code, 0);
+    let [SideEffect::PublishFeed { feed, output }] = result.side_effects.as_slice() else {
+        panic!("expected a feed, got {:?}", result.side_effects);
+    };
+    assert_eq!(*output, FeedOutput::Download);
+    assert_eq!(feed.title, "blog");
+    assert_eq!(feed.route, "/blog");
+    let entries: Vec<_> = feed
+        .entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.title.as_str(),
+                entry.route.as_str(),
+                entry.updated.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        entries,
+        [
+            ("dated", "/blog/2024/dated", "2024-09-12T00:00:00Z"),
+            ("Old & <new>", "/blog/old", "1970-01-02T00:00:00Z"),
+        ]
+    );
 }
 
 #[test]
-fn test_touch_creates_apply_change_side_effect() {
-    let (_ws, fs) = empty_state();
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
-    let result = execute_command(
-        Command::Touch {
-            path: PathArg::new("new.md"),
+fn test_feed_rejects_files_and_dirs_without_posts() {
+    let fs = fs_with_files(&[("/blog/old.md", blank_file_meta(NodeKind::Page))]);
+    let run = |path: &str| {
+        execute_command(
+            Command::Feed {
+                path: Some(PathArg::new(path)),
+                output: FeedOutput::Print,
+            },
+            &WalletState::Disconnected,
+            &fs,
+            &root_cwd(),
+            &ChangeSet::new(),
+            None,
+        )
+    };
+
+    let file = run("blog/old.md");
+    assert_eq!(file.exit_code, 1);
+    assert_eq!(output_text(&file), ["feed: blog/old.md: not a directory"]);
+
+    let missing = run("nope");
+    assert_eq!(
+        output_text(&missing),
+        ["feed: nope: No such file or directory"]
+    );
+
+    let mut fs = GlobalFs::empty();
+    fs.upsert_directory(home_vpath("empty"), blank_dir_meta());
+    let empty = execute_command(
+        Command::Feed {
+            path: Some(PathArg::new("empty")),
+            output: FeedOutput::Print,
         },
-        &ws,
+        &WalletState::Disconnected,
         &fs,
-        &home_cwd(""),
-        &cs,
+        &root_cwd(),
+        &ChangeSet::new(),
         None,
     );
-    assert_eq!(result.exit_code, 0);
-    match result.side_effects.first().cloned() {
-        Some(SideEffect::ApplyChange {
-            ref path,
-            ref change,
-        }) => {
-            assert_eq!(path.as_str(), "/new.md");
-            assert!(matches!(change.as_ref(), ChangeType::CreateFile { .. }));
-        }
-        other => panic!("expected ApplyChange, got {:?}", other),
-    }
+    assert_eq!(empty.exit_code, 1);
+    assert_eq!(
+        output_text(&empty),
+        ["feed: empty: no dated markdown files"]
+    );
 }
 
 #[test]
-fn test_touch_errors_when_path_exists_in_fs() {
-    // Build an fs with a file at "new.md"
-    let mut fs = GlobalFs::empty();
-    fs.upsert_file(
-        home_vpath("new.md"),
-        String::new(),
-        blank_file_meta(NodeKind::Asset),
-        EntryExtensions::default(),
-    );
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
+fn test_sha256_hands_one_file_to_the_target() {
+    let fs = fs_with_files(&[("/blog/unhashed.md", blank_file_meta(NodeKind::Page))]);
     let result = execute_command(
-        Command::Touch {
-            path: PathArg::new("new.md"),
+        Command::Sha256 {
+            path: PathArg::new("blog/unhashed.md"),
+            check: false,
         },
-        &ws,
+        &WalletState::Disconnected,
         &fs,
-        &home_cwd(""),
-        &cs,
+        &root_cwd(),
+        &ChangeSet::new(),
         None,
     );
-    assert_eq!(result.exit_code, 1);
+    assert_eq!(
+        result.side_effects,
+        vec![SideEffect::Checksum {
+            targets: vec![ChecksumTarget {
+                path: home_vpath("blog/unhashed.md"),
+                display: "blog/unhashed.md".to_string(),
+                expected: None,
+            }],
+            check: false,
+        }]
+    );
 }
 
 #[test]
-fn test_touch_errors_when_parent_is_file() {
-    let mut fs = GlobalFs::empty();
-    fs.upsert_file(
-        home_vpath("file"),
-        String::new(),
-        blank_file_meta(NodeKind::Asset),
-        EntryExtensions::default(),
-    );
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
+fn test_sha256_check_walks_hashed_files_under_a_directory() {
+    let hashed = |sha: &str| {
+        let mut meta = blank_file_meta(NodeKind::Page);
+        meta.derived.content_sha256 = Some(sha.to_string());
+        meta
+    };
+    let fs = fs_with_files(&[
+        ("/blog/b.md", hashed("0xbb")),
+        ("/blog/a.md", hashed("0xaa")),
+        ("/blog/drafts/c.md", hashed("0xcc")),
+        ("/blog/unhashed.md", blank_file_meta(NodeKind::Page)),
+    ]);
     let result = execute_command(
-        Command::Touch {
-            path: PathArg::new("file/child.md"),
+        Command::Sha256 {
+            path: PathArg::new("blog/"),
+            check: true,
         },
-        &ws,
+        &WalletState::Disconnected,
         &fs,
-        &home_cwd(""),
-        &cs,
+        &root_cwd(),
+        &ChangeSet::new(),
         None,
     );
-    assert_eq!(result.exit_code, 1);
-    assert!(result.output.iter().any(|line| {
-        matches!(
-            &line.data,
-            crate::engine::shell::OutputLineData::Error(message)
-                if message.contains("parent is not a directory")
-        )
-    }));
-}
-
-#[test]
-fn test_touch_errors_when_parent_is_missing() {
-    let (_ws, fs) = empty_state();
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
+    let Some(SideEffect::Checksum { targets, check }) = result.side_effects.first() else {
+        panic!("expected a checksum, got {:?}", result.side_effects);
+    };
+    assert!(check);
+    let listed: Vec<_> = targets
+        .iter()
+        .map(|target| (target.display.as_str(), target.expected.as_deref()))
+        .collect();
+    assert_eq!(
+        listed,
+        [
+            ("blog/a.md", Some("0xaa")),
+            ("blog/b.md", Some("0xbb")),
+            ("blog/drafts/c.md", Some("0xcc")),
+        ]
+    );
+}
+
+#[test]
+fn test_sha256_reports_unusable_targets() {
+    let mut fs = fs_with_files(&[("/blog/unhashed.md", blank_file_meta(NodeKind::Page))]);
+    fs.upsert_directory(home_vpath("empty"), blank_dir_meta());
+    let cases = [
+        ("blog", false, "sha256: blog: is a directory"),
+        (
+            "nope.md",
+            false,
+            "sha256: nope.md: No such file or directory",
+        ),
+        (
+            "blog/unhashed.md",
+            true,
+            "sha256: blog/unhashed.md: no recorded checksum",
+        ),
+        (
+            "empty",
+            true,
+            "sha256: empty: no files with recorded checksums",
+        ),
+    ];
+    for (path, check, expected) in cases {
+        let result = execute_command(
+            Command::Sha256 {
+                path: PathArg::new(path),
+                check,
+            },
+            &WalletState::Disconnected,
+            &fs,
+            &root_cwd(),
+            &ChangeSet::new(),
+            None,
+        );
+        assert_eq!(output_text(&result), [expected], "{path}");
+        assert_eq!(result.exit_code, 1, "{path}");
+        assert!(result.side_effects.is_empty(), "{path}");
+    }
+}
+
+#[test]
+fn test_export_zip_collects_readable_files_under_the_folder_name() {
+    use crate::domain::{AccessFilter, ListingVisibility, Recipient};
+
+    let sized = |size: u64| {
+        let mut meta = blank_file_meta(NodeKind::Asset);
+        meta.derived.size_bytes = Some(size);
+        meta
+    };
+    let mut private = sized(500);
+    private.authored.access = Some(AccessFilter {
+        recipients: vec![Recipient {
+            address: ADMIN_ADDRESS.to_string(),
+        }],
+        listing: ListingVisibility::Masked,
+    });
+    let fs = fs_with_files(&[
+        ("/talks/b.pdf", sized(2_000)),
+        ("/talks/a.pdf", sized(1_000)),
+        ("/talks/2024/keynote.pdf", blank_file_meta(NodeKind::Asset)),
+        ("/talks/private.pdf", private),
+        ("/talks/.notes.md", sized(10)),
+    ]);
     let result = execute_command(
-        Command::Touch {
-            path: PathArg::new("missing/child.md"),
-        },
-        &ws,
+        Command::ExportZip(Some(PathArg::new("talks/"))),
+        &WalletState::Disconnected,
         &fs,
-        &home_cwd(""),
-        &cs,
+        &root_cwd(),
+        &ChangeSet::new(),
         None,
     );
-    assert_eq!(result.exit_code, 1);
-    assert!(result.output.iter().any(|line| {
-        matches!(
-            &line.data,
-            crate::engine::shell::OutputLineData::Error(message)
-                if message.contains("parent directory does not exist")
-        )
-    }));
+    let Some(SideEffect::ExportZip(export)) = result.side_effects.first() else {
+        panic!("expected an export, got {:?}", result.side_effects);
+    };
+    assert_eq!(export.filename, "talks.zip");
+    let files: Vec<_> = export
+        .files
+        .iter()
+        .map(|file| (file.path.as_str(), file.name.as_str()))
+        .collect();
+    assert_eq!(
+        files,
+        [
+            ("/talks/2024/keynote.pdf", "talks/2024/keynote.pdf"),
+            ("/talks/a.pdf", "talks/a.pdf"),
+            ("/talks/b.pdf", "talks/b.pdf"),
+        ]
+    );
+    assert_eq!(export.skipped, ["talks/private.pdf"]);
+    assert_eq!(export.total_bytes, 3_000);
 }
 
 #[test]
-fn test_mkdir_creates_apply_change_side_effect() {
-    let (_ws, fs) = empty_state();
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
+fn test_export_zip_includes_files_the_wallet_can_read() {
+    use crate::domain::{AccessFilter, ListingVisibility, Recipient};
+
+    let sized = |size: u64| {
+        let mut meta = blank_file_meta(NodeKind::Asset);
+        meta.derived.size_bytes = Some(size);
+        meta
+    };
+    let mut private = sized(500);
+    private.authored.access = Some(AccessFilter {
+        recipients: vec![Recipient {
+            address: ADMIN_ADDRESS.to_string(),
+        }],
+        listing: ListingVisibility::Masked,
+    });
+    let fs = fs_with_files(&[
+        ("/talks/b.pdf", sized(2_000)),
+        ("/talks/a.pdf", sized(1_000)),
+        ("/talks/2024/keynote.pdf", blank_file_meta(NodeKind::Asset)),
+        ("/talks/private.pdf", private),
+        ("/talks/.notes.md", sized(10)),
+    ]);
     let result = execute_command(
-        Command::Mkdir {
-            path: PathArg::new("newdir"),
-        },
-        &ws,
+        Command::ExportZip(Some(PathArg::new("/talks"))),
+        &admin_wallet(),
         &fs,
-        &home_cwd(""),
-        &cs,
+        &root_cwd(),
+        &ChangeSet::new(),
         None,
     );
-    assert_eq!(result.exit_code, 0);
-    match result.side_effects.first().cloned() {
-        Some(SideEffect::ApplyChange {
-            ref path,
-            ref change,
-        }) => {
-            assert_eq!(path.as_str(), "/newdir");
-            assert!(matches!(
-                change.as_ref(),
-                ChangeType::CreateDirectory { .. }
-            ));
-        }
-        other => panic!("expected ApplyChange, got {:?}", other),
+    let Some(SideEffect::ExportZip(export)) = result.side_effects.first() else {
+        panic!("expected an export, got {:?}", result.side_effects);
+    };
+    assert!(export.skipped.is_empty());
+    assert!(
+        export
+            .files
+            .iter()
+            .any(|file| file.name == "talks/private.pdf")
+    );
+    assert_eq!(export.total_bytes, 3_500);
+}
+
+#[test]
+fn test_export_zip_defaults_to_the_current_directory() {
+    let fs = fs_with_files(&[
+        ("/talks/a.pdf", blank_file_meta(NodeKind::Asset)),
+        ("/talks/2024/keynote.pdf", blank_file_meta(NodeKind::Asset)),
+    ]);
+    let result = execute_command(
+        Command::ExportZip(None),
+        &WalletState::Disconnected,
+        &fs,
+        &home_cwd("talks/2024"),
+        &ChangeSet::new(),
+        None,
+    );
+    let Some(SideEffect::ExportZip(export)) = result.side_effects.first() else {
+        panic!("expected an export, got {:?}", result.side_effects);
+    };
+    assert_eq!(export.filename, "2024.zip");
+    assert_eq!(export.files.len(), 1);
+}
+
+#[test]
+fn test_export_zip_reports_unusable_targets() {
+    let mut locked = blank_file_meta(NodeKind::Asset);
+    locked.authored.access = Some(crate::domain::AccessFilter {
+        recipients: Vec::new(),
+        listing: crate::domain::ListingVisibility::Masked,
+    });
+    let mut fs = fs_with_files(&[
+        ("/talks/a.pdf", blank_file_meta(NodeKind::Asset)),
+        ("/locked/only.pdf", locked),
+    ]);
+    fs.upsert_directory(home_vpath("empty"), blank_dir_meta());
+    let cases = [
+        ("talks/a.pdf", "export-zip: talks/a.pdf: not a directory"),
+        ("nope", "export-zip: nope: No such file or directory"),
+        ("empty", "export-zip: empty: no files to export"),
+        ("locked", "export-zip: locked: no readable files to export"),
+    ];
+    for (path, expected) in cases {
+        let result = execute_command(
+            Command::ExportZip(Some(PathArg::new(path))),
+            &WalletState::Disconnected,
+            &fs,
+            &root_cwd(),
+            &ChangeSet::new(),
+            None,
+        );
+        assert_eq!(output_text(&result), [expected], "{path}");
+        assert_eq!(result.exit_code, 1, "{path}");
+        assert!(result.side_effects.is_empty(), "{path}");
     }
 }
 
+fn selection_names(
+    fs: &GlobalFs,
+    paths: &[&str],
+    wallet: &WalletState,
+) -> Option<(Vec<String>, Vec<String>)> {
+    let paths: Vec<_> = paths
+        .iter()
+        .map(|path| VirtualPath::from_absolute(*path).unwrap())
+        .collect();
+    let export = selection_zip_export(&paths, wallet, fs)?;
+    assert_eq!(export.filename, "selection.zip");
+    let names = export.files.into_iter().map(|file| file.name).collect();
+    Some((names, export.skipped))
+}
+
 #[test]
-fn test_mkdir_errors_when_path_exists() {
-    let mut fs = GlobalFs::empty();
-    fs.upsert_directory(home_vpath("dir"), blank_dir_meta());
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
+fn test_selection_zip_names_files_below_the_common_directory() {
+    let fs = fs_with_files(&[
+        ("/talks/b.pdf", blank_file_meta(NodeKind::Asset)),
+        ("/talks/a.pdf", blank_file_meta(NodeKind::Asset)),
+        ("/talks/2024/keynote.pdf", blank_file_meta(NodeKind::Asset)),
+    ]);
+    let (names, skipped) = selection_names(
+        &fs,
+        &["/talks/a.pdf", "/talks/2024"],
+        &WalletState::Disconnected,
+    )
+    .unwrap();
+    assert_eq!(names, ["selection/2024/keynote.pdf", "selection/a.pdf"]);
+    assert!(skipped.is_empty());
+
+    // A file inside a selected directory is packed once.
+    let (names, _) =
+        selection_names(&fs, &["/talks", "/talks/b.pdf"], &WalletState::Disconnected).unwrap();
+    assert_eq!(
+        names,
+        [
+            "selection/talks/2024/keynote.pdf",
+            "selection/talks/a.pdf",
+            "selection/talks/b.pdf",
+        ]
+    );
+}
+
+#[test]
+fn test_selection_zip_skips_unreadable_files() {
+    use crate::domain::{AccessFilter, ListingVisibility, Recipient};
+
+    let mut private = blank_file_meta(NodeKind::Asset);
+    private.authored.access = Some(AccessFilter {
+        recipients: vec![Recipient {
+            address: ADMIN_ADDRESS.to_string(),
+        }],
+        listing: ListingVisibility::Masked,
+    });
+    let fs = fs_with_files(&[
+        ("/talks/a.pdf", blank_file_meta(NodeKind::Asset)),
+        ("/talks/private.pdf", private),
+    ]);
+    let (names, skipped) = selection_names(
+        &fs,
+        &["/talks/a.pdf", "/talks/private.pdf"],
+        &WalletState::Disconnected,
+    )
+    .unwrap();
+    assert_eq!(names, ["selection/a.pdf"]);
+    assert_eq!(skipped, ["selection/private.pdf"]);
+
+    assert_eq!(
+        selection_names(&fs, &["/talks/private.pdf"], &WalletState::Disconnected),
+        None
+    );
+    assert_eq!(selection_names(&fs, &[], &WalletState::Disconnected), None);
+}
+
+/// `base` with `/talks/new.md` created and `/talks/a.pdf` and
+/// `/talks/private.pdf` edited locally, and the changes that staged them.
+fn with_talk_drafts(base: &GlobalFs) -> (GlobalFs, ChangeSet) {
+    let mut cs = ChangeSet::new();
+    upsert(
+        &mut cs,
+        VirtualPath::from_absolute("/talks/new.md").unwrap(),
+        ChangeType::CreateFile {
+            content: "# New talk".to_string(),
+            meta: blank_file_meta(NodeKind::Page),
+            extensions: EntryExtensions::default(),
+        },
+    );
+    upsert(
+        &mut cs,
+        VirtualPath::from_absolute("/talks/a.pdf").unwrap(),
+        ChangeType::UpdateFile {
+            content: "edited".to_string(),
+            meta: None,
+            extensions: None,
+        },
+    );
+    upsert(
+        &mut cs,
+        VirtualPath::from_absolute("/talks/private.pdf").unwrap(),
+        ChangeType::UpdateFile {
+            content: "secret".to_string(),
+            meta: None,
+            extensions: None,
+        },
+    );
+    let fs = crate::engine::runtime::build_content_view_global_fs(base, &cs);
+    (fs, cs)
+}
+
+#[test]
+fn test_ls_marks_local_drafts() {
+    use crate::domain::{AccessFilter, ListingVisibility, Recipient};
+
+    let mut private = blank_file_meta(NodeKind::Asset);
+    private.authored.access = Some(AccessFilter {
+        recipients: vec![Recipient {
+            address: ADMIN_ADDRESS.to_string(),
+        }],
+        listing: ListingVisibility::Masked,
+    });
+    let (fs, cs) = with_talk_drafts(&fs_with_files(&[
+        ("/talks/a.pdf", blank_file_meta(NodeKind::Asset)),
+        ("/talks/b.pdf", blank_file_meta(NodeKind::Asset)),
+        ("/talks/private.pdf", private),
+    ]));
     let result = execute_command(
-        Command::Mkdir {
-            path: PathArg::new("dir"),
+        Command::Ls {
+            path: Some(PathArg::new("/talks")),
+            long: false,
+            one_per_line: false,
         },
-        &ws,
+        &WalletState::Disconnected,
         &fs,
-        &home_cwd(""),
+        &root_cwd(),
         &cs,
         None,
     );
-    assert_eq!(result.exit_code, 1);
+    let drafts: Vec<_> = result
+        .output
+        .iter()
+        .filter_map(|line| match &line.data {
+            OutputLineData::ListEntry {
+                name, draft, path, ..
+            } => Some((name.as_str(), *draft, path.is_none())),
+            _ => None,
+        })
+        .collect();
+    assert!(drafts.contains(&("new.md", true, false)));
+    assert!(drafts.contains(&("a.pdf", true, false)));
+    assert!(drafts.contains(&("b.pdf", false, false)));
+    // The masked stand-in does not give away the draft behind it.
+    assert!(drafts.iter().any(|&(_, _, masked)| masked));
+    assert!(drafts.iter().all(|&(_, draft, masked)| !(draft && masked)));
 }
 
 #[test]
-fn test_mkdir_errors_when_parent_is_file() {
-    let mut fs = GlobalFs::empty();
-    fs.upsert_file(
-        home_vpath("file"),
-        String::new(),
-        blank_file_meta(NodeKind::Asset),
-        EntryExtensions::default(),
+fn test_export_draft_downloads_the_draft_text() {
+    let (fs, cs) = with_talk_drafts(&fs_with_files(&[
+        ("/talks/a.pdf", blank_file_meta(NodeKind::Asset)),
+        ("/talks/b.pdf", blank_file_meta(NodeKind::Asset)),
+        ("/talks/private.pdf", blank_file_meta(NodeKind::Asset)),
+    ]));
+    for (path, filename, content) in [
+        ("/talks/new.md", "new.md", "# New talk"),
+        ("talks/a.pdf", "a.pdf", "edited"),
+    ] {
+        let result = execute_command(
+            Command::ExportDraft(Some(PathArg::new(path))),
+            &admin_wallet(),
+            &fs,
+            &root_cwd(),
+            &cs,
+            None,
+        );
+        assert_eq!(
+            result.side_effects,
+            [SideEffect::DownloadDraft {
+                filename: filename.to_string(),
+                content: content.to_string(),
+            }],
+            "{path}"
+        );
+    }
+}
+
+#[test]
+fn test_export_draft_reports_paths_without_a_draft() {
+    let (fs, cs) = with_talk_drafts(&fs_with_files(&[
+        ("/talks/a.pdf", blank_file_meta(NodeKind::Asset)),
+        ("/talks/b.pdf", blank_file_meta(NodeKind::Asset)),
+        ("/talks/private.pdf", blank_file_meta(NodeKind::Asset)),
+    ]));
+    let cases = [
+        (
+            "talks/b.pdf",
+            "export-draft: talks/b.pdf: no local draft",
+            2,
+        ),
+        ("talks", "export-draft: talks: is a directory", 1),
+        (
+            "nope.md",
+            "export-draft: nope.md: No such file or directory",
+            1,
+        ),
+    ];
+    for (path, expected, code) in cases {
+        let result = execute_command(
+            Command::ExportDraft(Some(PathArg::new(path))),
+            &admin_wallet(),
+            &fs,
+            &root_cwd(),
+            &cs,
+            None,
+        );
+        assert_eq!(output_text(&result), [expected], "{path}");
+        assert_eq!(result.exit_code, code, "{path}");
+        assert!(result.side_effects.is_empty(), "{path}");
+    }
+    let missing = execute_command(
+        Command::ExportDraft(None),
+        &admin_wallet(),
+        &fs,
+        &root_cwd(),
+        &cs,
+        None,
     );
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
+    assert_eq!(
+        output_text(&missing),
+        ["export-draft: missing file operand"]
+    );
+}
+
+#[test]
+fn test_search_requests_markdown_files_in_mount() {
+    let fs = fs_with_files(&[
+        ("/blog/rust.md", blank_file_meta(NodeKind::Page)),
+        ("/blog/notes.md", blank_file_meta(NodeKind::Page)),
+        ("/papers/tabula.pdf", blank_file_meta(NodeKind::Page)),
+        ("/blog/data.json", blank_file_meta(NodeKind::Data)),
+    ]);
     let result = execute_command(
-        Command::Mkdir {
-            path: PathArg::new("file/child"),
-        },
+        Command::Search("rust".to_string()),
+        &WalletState::Disconnected,
+        &fs,
+        &root_cwd(),
+        &ChangeSet::new(),
+        None,
+    );
+    assert_eq!(
+        result.side_effects,
+        vec![SideEffect::SearchContent {
+            query: "rust".to_string(),
+            paths: vec![home_vpath("blog/notes.md"), home_vpath("blog/rust.md")],
+        }]
+    );
+}
+
+#[test]
+fn test_search_without_markdown_fails() {
+    let (ws, fs) = empty_state();
+    let result = execute_command(
+        Command::Search("rust".to_string()),
         &ws,
         &fs,
-        &home_cwd(""),
-        &cs,
+        &root_cwd(),
+        &ChangeSet::new(),
         None,
     );
     assert_eq!(result.exit_code, 1);
+    assert!(result.side_effects.is_empty());
 }
 
 #[test]
-fn test_rm_file_side_effect() {
-    let mut fs = GlobalFs::empty();
-    fs.upsert_file(
-        home_vpath("doomed.md"),
-        String::new(),
-        blank_file_meta(NodeKind::Asset),
-        EntryExtensions::default(),
-    );
-    let ws = admin_wallet();
+fn test_unknown_command_exit_127() {
+    let (ws, fs) = empty_state();
     let cs = ChangeSet::new();
     let result = execute_command(
-        Command::Rm {
-            path: PathArg::new("doomed.md"),
-            recursive: false,
-        },
+        Command::Unknown("foobar".into()),
         &ws,
         &fs,
-        &home_cwd(""),
+        &root_cwd(),
         &cs,
         None,
     );
-    assert_eq!(result.exit_code, 0);
-    match result.side_effects.first().cloned() {
-        Some(SideEffect::ApplyChange {
-            ref path,
-            ref change,
-        }) => {
-            assert_eq!(path.as_str(), "/doomed.md");
-            assert!(matches!(change.as_ref(), ChangeType::DeleteFile));
-        }
-        other => panic!("expected DeleteFile ApplyChange, got {:?}", other),
-    }
+    assert_eq!(result.exit_code, 127);
 }
 
 #[test]
-fn test_rm_directory_without_r_errors() {
-    let mut fs = GlobalFs::empty();
-    fs.upsert_directory(home_vpath("dir"), blank_dir_meta());
-    let ws = admin_wallet();
+fn test_clear_hides_screen_and_reset_drops_history() {
+    let (ws, fs) = empty_state();
+    let cs = ChangeSet::new();
+    let clear = execute_command(Command::Clear, &ws, &fs, &root_cwd(), &cs, None);
+    assert_eq!(clear.side_effects, vec![SideEffect::ClearScreen]);
+
+    let reset = execute_command(Command::Reset, &ws, &fs, &root_cwd(), &cs, None);
+    assert_eq!(reset.side_effects, vec![SideEffect::ClearHistory]);
+}
+
+#[test]
+fn test_tutorial_hands_the_action_to_the_target() {
+    let (ws, fs) = empty_state();
     let cs = ChangeSet::new();
     let result = execute_command(
-        Command::Rm {
-            path: PathArg::new("dir"),
-            recursive: false,
-        },
+        Command::Tutorial(TutorialAction::Skip),
         &ws,
         &fs,
-        &home_cwd(""),
+        &root_cwd(),
         &cs,
         None,
     );
-    assert_eq!(result.exit_code, 1);
+    assert_eq!(result.exit_code, 0);
+    assert!(result.output.is_empty());
+    assert_eq!(
+        result.side_effects,
+        vec![SideEffect::Tutorial(TutorialAction::Skip)]
+    );
 }
 
 #[test]
-fn test_rm_directory_recursive_side_effect() {
-    let mut fs = GlobalFs::empty();
-    fs.upsert_directory(home_vpath("dir"), blank_dir_meta());
-    let ws = admin_wallet();
+fn test_export_session_resolves_filename_and_mount() {
+    let (ws, fs) = empty_state();
     let cs = ChangeSet::new();
     let result = execute_command(
-        Command::Rm {
-            path: PathArg::new("dir"),
-            recursive: true,
+        Command::ExportSession {
+            filename: Some("../notes".into()),
+            html: true,
         },
         &ws,
         &fs,
-        &home_cwd(""),
+        &root_cwd(),
         &cs,
         None,
     );
     assert_eq!(result.exit_code, 0);
-    match result.side_effects.first().cloned() {
-        Some(SideEffect::ApplyChange {
-            ref path,
-            ref change,
-        }) => {
-            assert_eq!(path.as_str(), "/dir");
-            assert!(matches!(change.as_ref(), ChangeType::DeleteDirectory));
+    match result.side_effects.as_slice() {
+        [
+            SideEffect::ExportSession {
+                filename,
+                html,
+                cwd,
+                mount,
+            },
+        ] => {
+            assert_eq!(filename, "notes.html");
+            assert!(*html);
+            assert_eq!(cwd, &root_cwd());
+            assert_eq!(mount.as_ref().map(|m| m.root.clone()), Some(root_cwd()));
         }
-        other => panic!("expected DeleteDirectory ApplyChange, got {:?}", other),
+        other => panic!("unexpected side effects: {other:?}"),
     }
-}
-
-#[test]
-fn test_rm_recursive_rejects_mount_root() {
-    let db_root = VirtualPath::from_absolute("/db").unwrap();
-    let mut fs = GlobalFs::empty();
-    fs.upsert_directory(db_root.clone(), blank_dir_meta());
-    let runtime_mounts = vec![
-        crate::engine::runtime::boot::bootstrap_runtime_mount(&bootstrap_source()),
-        crate::domain::RuntimeMount::new(
-            db_root,
-            "db",
-            crate::domain::RuntimeBackendKind::GitHub,
-            true,
-        ),
-    ];
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
 
-    let result = super::execute_command_with_context(
-        Command::Rm {
-            path: PathArg::new("/db"),
-            recursive: true,
+    let default = execute_command(
+        Command::ExportSession {
+            filename: None,
+            html: false,
         },
         &ws,
-        &runtime_mounts,
         &fs,
         &root_cwd(),
         &cs,
         None,
-        &ExecutionContext {
-            access_policy: ACCESS_POLICY,
-            ..ExecutionContext::default()
-        },
     );
-
-    assert_eq!(result.exit_code, 1);
     assert!(matches!(
-        result.output.first().map(|line| &line.data),
-        Some(OutputLineData::Error(message)) if message.contains("cannot remove mount root")
+        default.side_effects.as_slice(),
+        [SideEffect::ExportSession { filename, .. }] if filename == "websh-session.txt"
     ));
-}
 
-#[test]
-fn test_rm_nonexistent_path_errors() {
-    let (_ws, fs) = empty_state();
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
-    let result = execute_command(
-        Command::Rm {
-            path: PathArg::new("ghost.md"),
-            recursive: false,
+    let invalid = execute_command(
+        Command::ExportSession {
+            filename: Some("..".into()),
+            html: false,
         },
         &ws,
         &fs,
-        &home_cwd(""),
+        &root_cwd(),
         &cs,
         None,
     );
-    assert_eq!(result.exit_code, 1);
+    assert_eq!(invalid.exit_code, 1);
+    assert!(invalid.side_effects.is_empty());
 }
 
 #[test]
-fn test_rmdir_empty_directory_side_effect() {
-    let mut fs = GlobalFs::empty();
-    fs.upsert_directory(home_vpath("empty"), blank_dir_meta());
-    let ws = admin_wallet();
+fn test_unknown_command_suggests_closest_name() {
+    let (ws, fs) = empty_state();
     let cs = ChangeSet::new();
     let result = execute_command(
-        Command::Rmdir {
-            path: PathArg::new("empty"),
-        },
+        Command::Unknown("gerp".into()),
         &ws,
         &fs,
-        &home_cwd(""),
+        &root_cwd(),
         &cs,
         None,
     );
-    assert_eq!(result.exit_code, 0);
-    match result.side_effects.first().cloned() {
-        Some(SideEffect::ApplyChange { ref change, .. }) => {
-            assert!(matches!(change.as_ref(), ChangeType::DeleteDirectory));
-        }
-        other => panic!("expected DeleteDirectory, got {:?}", other),
-    }
+    assert_eq!(result.exit_code, 127);
+    assert!(matches!(
+        &result.output[0].data,
+        OutputLineData::Error(s) if s == "Command not found: gerp. Did you mean 'grep'?"
+    ));
 }
 
 #[test]
-fn test_rmdir_rejects_mount_root() {
-    let db_root = VirtualPath::from_absolute("/db").unwrap();
-    let mut fs = GlobalFs::empty();
-    fs.upsert_directory(db_root.clone(), blank_dir_meta());
-    let runtime_mounts = vec![
-        crate::engine::runtime::boot::bootstrap_runtime_mount(&bootstrap_source()),
-        crate::domain::RuntimeMount::new(
-            db_root,
-            "db",
-            crate::domain::RuntimeBackendKind::GitHub,
-            true,
-        ),
-    ];
-    let ws = admin_wallet();
+fn test_unknown_command_suggests_only_close_names() {
+    let (ws, fs) = empty_state();
     let cs = ChangeSet::new();
+    let message = |name: &str| {
+        let result = execute_command(
+            Command::Unknown(name.into()),
+            &ws,
+            &fs,
+            &root_cwd(),
+            &cs,
+            None,
+        );
+        assert_eq!(result.exit_code, 127);
+        match &result.output[0].data {
+            OutputLineData::Error(s) => s.clone(),
+            other => panic!("expected an error line, got {other:?}"),
+        }
+    };
 
-    let result = super::execute_command_with_context(
-        Command::Rmdir {
-            path: PathArg::new("/db"),
-        },
+    assert_eq!(
+        message("cta"),
+        "Command not found: cta. Did you mean 'cat'?"
+    );
+    assert_eq!(
+        message("xyzzy"),
+        "Command not found: xyzzy. Type 'help' for available commands."
+    );
+}
+
+#[test]
+fn test_help_topic_renders_command_page() {
+    let (ws, fs) = empty_state();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Help(Some("ls".into())),
         &ws,
-        &runtime_mounts,
         &fs,
         &root_cwd(),
         &cs,
         None,
-        &ExecutionContext {
-            access_policy: ACCESS_POLICY,
-            ..ExecutionContext::default()
-        },
     );
-
-    assert_eq!(result.exit_code, 1);
+    assert_eq!(result.exit_code, 0);
     assert!(matches!(
-        result.output.first().map(|line| &line.data),
-        Some(OutputLineData::Error(message)) if message.contains("cannot remove mount root")
+        &result.output[0].data,
+        OutputLineData::Text(s) if s == "ls - List directory contents"
     ));
 }
 
-#[test]
-fn test_rmdir_nonempty_directory_errors() {
-    let mut fs = GlobalFs::empty();
-    fs.upsert_directory(home_vpath("dir"), blank_dir_meta());
-    fs.upsert_file(
-        home_vpath("dir/child.md"),
-        String::new(),
-        blank_file_meta(NodeKind::Asset),
-        EntryExtensions::default(),
+fn neofetch_text(wallet_state: &WalletState, context: &ExecutionContext) -> String {
+    let runtime_mounts = [crate::engine::runtime::boot::bootstrap_runtime_mount(
+        &bootstrap_source(),
+    )];
+    let result = super::execute_command_with_context(
+        Command::Neofetch,
+        wallet_state,
+        &runtime_mounts,
+        &GlobalFs::empty(),
+        &root_cwd(),
+        &ChangeSet::new(),
+        None,
+        context,
     );
-    let ws = admin_wallet();
+    assert_eq!(result.exit_code, 0);
+    assert_eq!(result.output.len(), 1);
+    match &result.output[0].data {
+        OutputLineData::Ascii(text) => text.clone(),
+        other => panic!("expected ascii output, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_neofetch_sets_info_beside_profile_art() {
+    let context = ExecutionContext {
+        shell_text: crate::engine::shell::ShellText::new("/\\\n\\/", ""),
+        system_info: crate::engine::shell::SystemInfo {
+            uptime: Some("3m 2s".to_string()),
+            version: Some("0.1.0 (abc1234)".to_string()),
+            history_lines: Some(42),
+            ..Default::default()
+        },
+        env: [("THEME".to_string(), "amber".to_string())].into(),
+        access_policy: ACCESS_POLICY,
+        ..ExecutionContext::default()
+    };
+    let text = neofetch_text(&WalletState::Disconnected, &context);
+    let lines: Vec<&str> = text.lines().collect();
+
+    assert_eq!(lines[0], "/\\   guest@websh");
+    assert_eq!(lines[1], "\\/   -----------");
+    assert_eq!(lines[2], "     Version: 0.1.0 (abc1234)");
+    assert!(lines.contains(&"     Uptime: 3m 2s"));
+    assert!(lines.contains(&"     Wallet: disconnected"));
+    assert!(lines.contains(&"     History: 42 lines"));
+    assert!(lines.contains(&"     Theme: amber"));
+    assert!(lines.iter().any(|line| line.contains("Mount: ")));
+    assert!(!text.contains("Network:"), "no network without a wallet");
+    assert!(!text.contains("Viewport:"), "unknown facts are omitted");
+}
+
+#[test]
+fn test_neofetch_reports_connected_wallet_network() {
+    let context = ExecutionContext {
+        access_policy: ACCESS_POLICY,
+        ..ExecutionContext::default()
+    };
+    let text = neofetch_text(&admin_wallet(), &context);
+    assert!(text.contains("Network: Ethereum"), "{text}");
+    assert!(!text.contains("Wallet: disconnected"), "{text}");
+}
+
+#[test]
+fn test_date_prints_local_time_in_requested_format() {
+    let (ws, fs) = empty_state();
+    let runtime_mounts = [crate::engine::runtime::boot::bootstrap_runtime_mount(
+        &bootstrap_source(),
+    )];
+    let context = ExecutionContext {
+        now: 1_792_213_389,
+        utc_offset_minutes: 9 * 60,
+        access_policy: ACCESS_POLICY,
+        ..ExecutionContext::default()
+    };
+    let date = |format: Option<&str>| {
+        let result = super::execute_command_with_context(
+            Command::Date(format.map(str::to_string)),
+            &ws,
+            &runtime_mounts,
+            &fs,
+            &root_cwd(),
+            &ChangeSet::new(),
+            None,
+            &context,
+        );
+        assert_eq!(result.exit_code, 0);
+        output_text(&result)
+    };
+
+    assert_eq!(date(None), vec!["Sat, 17 Oct 2026 14:03:09 +0900"]);
+    assert_eq!(date(Some("%Y-%m-%d")), vec!["2026-10-17"]);
+}
+
+#[test]
+fn test_ls_nonexistent_exit_1() {
+    let (ws, fs) = empty_state();
     let cs = ChangeSet::new();
     let result = execute_command(
-        Command::Rmdir {
-            path: PathArg::new("dir"),
+        Command::Ls {
+            path: Some(super::super::PathArg::new("nonexistent")),
+            long: false,
+            one_per_line: false,
         },
         &ws,
         &fs,
-        &home_cwd(""),
+        &root_cwd(),
         &cs,
         None,
     );
     assert_eq!(result.exit_code, 1);
+    assert!(!result.output.is_empty());
 }
 
 #[test]
-fn test_rmdir_on_file_errors() {
+fn test_ls_entries_carry_canonical_paths() {
     let mut fs = GlobalFs::empty();
     fs.upsert_file(
-        home_vpath("file.md"),
+        home_vpath("docs/readme.md"),
         String::new(),
         blank_file_meta(NodeKind::Asset),
         EntryExtensions::default(),
     );
-    let ws = admin_wallet();
+    let (ws, _) = empty_state();
     let cs = ChangeSet::new();
     let result = execute_command(
-        Command::Rmdir {
-            path: PathArg::new("file.md"),
+        Command::Ls {
+            path: Some(PathArg::new("docs")),
+            long: false,
+            one_per_line: false,
         },
         &ws,
         &fs,
-        &home_cwd(""),
+        &root_cwd(),
         &cs,
         None,
     );
-    assert_eq!(result.exit_code, 1);
+    assert_eq!(result.exit_code, 0);
+    assert!(result.output.iter().any(|line| matches!(
+        &line.data,
+        OutputLineData::ListEntry { name, path: Some(path), .. }
+            if name == "readme.md" && *path == home_vpath("docs/readme.md")
+    )));
 }
 
 #[test]
-fn test_edit_opens_editor_for_existing_file() {
+fn test_ls_masks_unreadable_entries_until_a_recipient_connects() {
+    use crate::domain::{AccessFilter, ListingVisibility, Recipient};
+
     let mut fs = GlobalFs::empty();
+    let mut meta = blank_file_meta(NodeKind::Page);
+    meta.authored.access = Some(AccessFilter {
+        recipients: vec![Recipient {
+            address: ADMIN_ADDRESS.to_string(),
+        }],
+        listing: ListingVisibility::Masked,
+    });
     fs.upsert_file(
-        home_vpath("note.md"),
-        "hi".to_string(),
-        blank_file_meta(NodeKind::Asset),
+        home_vpath("docs/plans.md"),
+        String::new(),
+        meta,
         EntryExtensions::default(),
     );
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
-    let result = execute_command(
-        Command::Edit {
-            path: PathArg::new("note.md"),
+    let ls = |wallet: &WalletState| {
+        execute_command(
+            Command::Ls {
+                path: Some(PathArg::new("docs")),
+                long: false,
+                one_per_line: false,
+            },
+            wallet,
+            &fs,
+            &root_cwd(),
+            &ChangeSet::new(),
+            None,
+        )
+    };
+
+    let guest = ls(&WalletState::Disconnected);
+    assert!(matches!(
+        &guest.output[..],
+        [line] if matches!(
+            &line.data,
+            OutputLineData::ListEntry { name, encrypted: true, path: None, .. }
+                if name == "••••••.md"
+        )
+    ));
+
+    let recipient = ls(&admin_wallet());
+    assert!(matches!(
+        &recipient.output[..],
+        [line] if matches!(
+            &line.data,
+            OutputLineData::ListEntry { name, encrypted: true, path: Some(_), .. }
+                if name == "plans.md"
+        )
+    ));
+}
+
+#[test]
+fn test_ls_lays_out_columns_when_terminal_width_is_known() {
+    let asset = || blank_file_meta(NodeKind::Asset);
+    let fs = fs_with_files(&[
+        ("/docs/a.md", asset()),
+        ("/docs/b.md", asset()),
+        ("/docs/c.md", asset()),
+        ("/docs/d.md", asset()),
+        ("/docs/e.md", asset()),
+    ]);
+    let runtime_mounts = [crate::engine::runtime::boot::bootstrap_runtime_mount(
+        &bootstrap_source(),
+    )];
+    let result = super::execute_command_with_context(
+        Command::Ls {
+            path: Some(PathArg::new("docs")),
+            long: false,
+            one_per_line: false,
         },
-        &ws,
+        &WalletState::Disconnected,
+        &runtime_mounts,
         &fs,
-        &home_cwd(""),
-        &cs,
+        &root_cwd(),
+        &ChangeSet::new(),
         None,
+        &ExecutionContext {
+            access_policy: ACCESS_POLICY,
+            terminal_columns: Some(16),
+            ..ExecutionContext::default()
+        },
     );
     assert_eq!(result.exit_code, 0);
-    match result.side_effects.first().cloned() {
-        Some(SideEffect::OpenEditor { ref path }) => {
-            assert_eq!(path.as_str(), "/note.md");
-        }
-        other => panic!("expected OpenEditor, got {:?}", other),
-    }
+    let rows = result
+        .output
+        .iter()
+        .map(|line| line.to_plain_text())
+        .collect::<Vec<_>>();
+    assert_eq!(rows, vec!["a.md  c.md  e.md", "b.md  d.md"]);
+    assert!(result.output.iter().all(|line| matches!(
+        &line.data,
+        OutputLineData::ListRow(cells) if cells.iter().all(|cell| cell.path.is_some())
+    )));
 }
 
 #[test]
-fn test_edit_on_missing_file_opens_editor() {
-    // Create-on-save: `edit` on a non-existent path still yields OpenEditor.
-    let (_ws, fs) = empty_state();
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
-    let result = execute_command(
-        Command::Edit {
-            path: PathArg::new("fresh.md"),
+fn test_ls_points_at_a_readme_on_the_terminal_only() {
+    let fs = fs_with_files(&[
+        ("/docs/a.md", blank_file_meta(NodeKind::Asset)),
+        ("/docs/README.md", blank_file_meta(NodeKind::Asset)),
+    ]);
+    let runtime_mounts = [crate::engine::runtime::boot::bootstrap_runtime_mount(
+        &bootstrap_source(),
+    )];
+    let ls_docs = |fs: &GlobalFs, one_per_line: bool| {
+        super::execute_command_with_context(
+            Command::Ls {
+                path: Some(PathArg::new("docs")),
+                long: false,
+                one_per_line,
+            },
+            &WalletState::Disconnected,
+            &runtime_mounts,
+            fs,
+            &root_cwd(),
+            &ChangeSet::new(),
+            None,
+            &ExecutionContext {
+                access_policy: ACCESS_POLICY,
+                terminal_columns: Some(80),
+                ..ExecutionContext::default()
+            },
+        )
+    };
+    let last_line = |result: &CommandResult| result.output.last().map(OutputLine::to_plain_text);
+
+    let result = ls_docs(&fs, false);
+    assert_eq!(
+        last_line(&result).as_deref(),
+        Some("README.md available — 'cat docs/README.md'")
+    );
+    assert!(matches!(
+        result.output.last().map(|line| &line.data),
+        Some(OutputLineData::Info(_))
+    ));
+    assert_eq!(
+        last_line(&ls_docs(&fs, true)).as_deref(),
+        Some("README.md available — 'cat docs/README.md'")
+    );
+
+    let (ws, _) = empty_state();
+    let piped = execute_command(
+        Command::Ls {
+            path: Some(PathArg::new("docs")),
+            long: false,
+            one_per_line: false,
         },
         &ws,
         &fs,
-        &home_cwd(""),
-        &cs,
+        &root_cwd(),
+        &ChangeSet::new(),
         None,
     );
-    assert_eq!(result.exit_code, 0);
-    assert!(matches!(
-        result.side_effects.first().cloned(),
-        Some(SideEffect::OpenEditor { .. })
-    ));
+    assert_eq!(piped.output.len(), 2);
+    let without_readme = fs_with_files(&[("/docs/a.md", blank_file_meta(NodeKind::Asset))]);
+    assert!(
+        ls_docs(&without_readme, false)
+            .output
+            .iter()
+            .all(|line| !matches!(line.data, OutputLineData::Info(_)))
+    );
 }
 
 #[test]
-fn test_edit_on_directory_errors() {
-    let mut fs = GlobalFs::empty();
-    fs.upsert_directory(home_vpath("dir"), blank_dir_meta());
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
-    let result = execute_command(
-        Command::Edit {
-            path: PathArg::new("dir"),
-        },
-        &ws,
-        &fs,
-        &home_cwd(""),
-        &cs,
-        None,
+fn test_ls_readme_hint_names_the_listed_directory() {
+    let fs = fs_with_files(&[
+        ("/README.md", blank_file_meta(NodeKind::Asset)),
+        ("/docs/README.md", blank_file_meta(NodeKind::Asset)),
+        ("/My Docs/README.md", blank_file_meta(NodeKind::Asset)),
+    ]);
+    let runtime_mounts = [crate::engine::runtime::boot::bootstrap_runtime_mount(
+        &bootstrap_source(),
+    )];
+    let hint = |target: &str, cwd: &VirtualPath| {
+        let result = super::execute_command_with_context(
+            Command::Ls {
+                path: Some(PathArg::new(target)),
+                long: false,
+                one_per_line: false,
+            },
+            &WalletState::Disconnected,
+            &runtime_mounts,
+            &fs,
+            cwd,
+            &ChangeSet::new(),
+            None,
+            &ExecutionContext {
+                access_policy: ACCESS_POLICY,
+                terminal_columns: Some(80),
+                ..ExecutionContext::default()
+            },
+        );
+        result.output.last().map(OutputLine::to_plain_text)
+    };
+
+    assert_eq!(
+        hint("/", &home_cwd("docs")).as_deref(),
+        Some("README.md available — 'cat /README.md'")
+    );
+    assert_eq!(
+        hint("My Docs", &root_cwd()).as_deref(),
+        Some("README.md available — 'cat My\\ Docs/README.md'")
+    );
+    assert_eq!(
+        hint(".", &home_cwd("docs")).as_deref(),
+        Some("README.md available — 'cat README.md'")
     );
-    assert_eq!(result.exit_code, 1);
 }
 
 #[test]
-fn test_echo_redirect_writes_content() {
-    let (_ws, fs) = empty_state();
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
-    let result = execute_command(
-        Command::EchoRedirect {
-            body: "hello".to_string(),
-            path: PathArg::new("greeting.md"),
+fn test_ls_one_per_line_flag_skips_columns() {
+    let fs = fs_with_files(&[
+        ("/docs/a.md", blank_file_meta(NodeKind::Asset)),
+        ("/docs/b.md", blank_file_meta(NodeKind::Asset)),
+    ]);
+    let runtime_mounts = [crate::engine::runtime::boot::bootstrap_runtime_mount(
+        &bootstrap_source(),
+    )];
+    let result = super::execute_command_with_context(
+        Command::Ls {
+            path: Some(PathArg::new("docs")),
+            long: false,
+            one_per_line: true,
         },
-        &ws,
+        &WalletState::Disconnected,
+        &runtime_mounts,
         &fs,
-        &home_cwd(""),
-        &cs,
+        &root_cwd(),
+        &ChangeSet::new(),
         None,
+        &ExecutionContext {
+            access_policy: ACCESS_POLICY,
+            terminal_columns: Some(80),
+            ..ExecutionContext::default()
+        },
+    );
+    assert_eq!(result.output.len(), 2);
+    assert!(
+        result
+            .output
+            .iter()
+            .all(|line| matches!(&line.data, OutputLineData::ListEntry { .. }))
     );
-    assert_eq!(result.exit_code, 0);
-    match result.side_effects.first().cloned() {
-        Some(SideEffect::ApplyChange {
-            ref path,
-            ref change,
-        }) => {
-            assert_eq!(path.as_str(), "/greeting.md");
-            match change.as_ref() {
-                ChangeType::CreateFile { content, .. } => assert_eq!(content, "hello"),
-                other => panic!("expected CreateFile, got {:?}", other),
-            }
-        }
-        other => panic!("expected ApplyChange, got {:?}", other),
-    }
 }
 
 #[test]
-fn test_echo_redirect_updates_existing_file() {
+fn test_ls_long_dates_entries_relative_to_now() {
     let mut fs = GlobalFs::empty();
+    let mut meta = blank_file_meta(NodeKind::Asset);
+    // 2024-03-12 14:03:00 UTC
+    meta.derived.modified_at = Some(1_710_252_180);
     fs.upsert_file(
-        home_vpath("greet.md"),
-        "old".to_string(),
-        blank_file_meta(NodeKind::Asset),
+        home_vpath("docs/notes.md"),
+        String::new(),
+        meta,
         EntryExtensions::default(),
     );
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
+    let runtime_mounts = [crate::engine::runtime::boot::bootstrap_runtime_mount(
+        &bootstrap_source(),
+    )];
+    let ls_long_at = |now: u64| {
+        super::execute_command_with_context(
+            Command::Ls {
+                path: Some(PathArg::new("docs")),
+                long: true,
+                one_per_line: false,
+            },
+            &WalletState::Disconnected,
+            &runtime_mounts,
+            &fs,
+            &root_cwd(),
+            &ChangeSet::new(),
+            None,
+            &ExecutionContext {
+                access_policy: ACCESS_POLICY,
+                now,
+                ..ExecutionContext::default()
+            },
+        )
+        .output[0]
+            .to_plain_text()
+    };
+
+    assert!(ls_long_at(1_710_252_180 + 86400).contains("yesterday"));
+    assert!(ls_long_at(1_710_252_180 + 30 * 86400).contains("Mar 12 14:03"));
+    assert!(ls_long_at(1_710_252_180 + 365 * 86400).contains("Mar 12  2024"));
+}
+
+#[test]
+fn test_ls_file_lists_the_file_as_written() {
+    let mut meta = blank_file_meta(NodeKind::Page);
+    meta.authored.title = Some("Hello".to_string());
+    meta.derived.size_bytes = Some(1_234);
+    let fs = fs_with_files(&[("/blog/hello.md", meta)]);
     let result = execute_command(
-        Command::EchoRedirect {
-            body: "new".to_string(),
-            path: PathArg::new("greet.md"),
+        Command::Ls {
+            path: Some(PathArg::new("blog/hello.md")),
+            long: false,
+            one_per_line: false,
         },
-        &ws,
+        &WalletState::Disconnected,
         &fs,
-        &home_cwd(""),
-        &cs,
+        &root_cwd(),
+        &ChangeSet::new(),
         None,
     );
     assert_eq!(result.exit_code, 0);
-    match result.side_effects.first().cloned() {
-        Some(SideEffect::ApplyChange { ref change, .. }) => match change.as_ref() {
-            ChangeType::UpdateFile { content, .. } => assert_eq!(content, "new"),
-            other => panic!("expected UpdateFile, got {:?}", other),
-        },
-        other => panic!("expected UpdateFile, got {:?}", other),
-    }
+    assert!(matches!(
+        &result.output[..],
+        [line] if matches!(
+            &line.data,
+            OutputLineData::ListEntry { name, description, path: Some(path), .. }
+                if name == "blog/hello.md"
+                    && description == "Hello"
+                    && *path == home_vpath("blog/hello.md")
+        )
+    ));
 }
 
 #[test]
-fn test_echo_redirect_errors_when_parent_is_file() {
-    let mut fs = GlobalFs::empty();
-    fs.upsert_file(
-        home_vpath("file"),
-        String::new(),
-        blank_file_meta(NodeKind::Asset),
-        EntryExtensions::default(),
-    );
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
+fn test_ls_long_file_is_a_single_long_entry() {
+    let mut meta = blank_file_meta(NodeKind::Page);
+    meta.authored.title = Some("Hello".to_string());
+    meta.derived.size_bytes = Some(1_234);
+    let fs = fs_with_files(&[("/blog/hello.md", meta)]);
     let result = execute_command(
-        Command::EchoRedirect {
-            body: "hello".to_string(),
-            path: PathArg::new("file/child.md"),
+        Command::Ls {
+            path: Some(PathArg::new("blog/hello.md")),
+            long: true,
+            one_per_line: false,
         },
-        &ws,
+        &WalletState::Disconnected,
         &fs,
-        &home_cwd(""),
-        &cs,
+        &root_cwd(),
+        &ChangeSet::new(),
         None,
     );
-    assert_eq!(result.exit_code, 1);
+    assert_eq!(result.exit_code, 0);
+    assert!(matches!(
+        &result.output[..],
+        [line] if matches!(
+            &line.data,
+            OutputLineData::ListEntry {
+                name,
+                format: ListFormat::Long { permissions, size: Some(1_234), .. },
+                ..
+            } if name == "blog/hello.md" && permissions.starts_with("-r")
+        )
+    ));
 }
 
 #[test]
-fn test_echo_redirect_requires_admin() {
-    let (ws, fs) = empty_state();
-    let cs = ChangeSet::new();
+fn test_ls_file_with_a_trailing_slash_is_not_a_directory() {
+    let fs = fs_with_files(&[("/blog/hello.md", blank_file_meta(NodeKind::Page))]);
     let result = execute_command(
-        Command::EchoRedirect {
-            body: "x".to_string(),
-            path: PathArg::new("a.md"),
+        Command::Ls {
+            path: Some(PathArg::new("blog/hello.md/")),
+            long: false,
+            one_per_line: false,
         },
-        &ws,
+        &WalletState::Disconnected,
         &fs,
-        &home_cwd(""),
-        &cs,
+        &root_cwd(),
+        &ChangeSet::new(),
         None,
     );
     assert_eq!(result.exit_code, 1);
+    assert_eq!(
+        output_text(&result),
+        ["ls: cannot access 'blog/hello.md/': Not a directory"]
+    );
 }
 
 #[test]
-fn test_sync_status_clean_tree() {
-    let (_ws, fs) = empty_state();
-    let ws = admin_wallet();
+fn test_cat_missing_operand_exit_1() {
+    let (ws, fs) = empty_state();
+    let cs = ChangeSet::new();
+    let result = execute_command(Command::Cat(None), &ws, &fs, &root_cwd(), &cs, None);
+    assert_eq!(result.exit_code, 1);
+    assert!(
+            result
+                .output
+                .iter()
+                .any(|l| matches!(&l.data, crate::engine::shell::OutputLineData::Error(s) if s == "cat: missing file operand"))
+        );
+}
+
+#[test]
+fn test_unset_missing_operand_exit_1() {
+    let (ws, fs) = empty_state();
+    let cs = ChangeSet::new();
+    let result = execute_command(Command::Unset(None), &ws, &fs, &root_cwd(), &cs, None);
+    assert_eq!(result.exit_code, 1);
+}
+
+#[test]
+fn test_execute_export_multi_processes_each_assignment() {
+    let (ws, fs) = empty_state();
     let cs = ChangeSet::new();
     let result = execute_command(
-        Command::Sync(SyncSubcommand::Status),
+        Command::Export(vec![
+            "FOO_P2_A=alpha".to_string(),
+            "BAR_P2_A=beta".to_string(),
+        ]),
         &ws,
         &fs,
-        &home_cwd(""),
+        &root_cwd(),
         &cs,
         None,
     );
     assert_eq!(result.exit_code, 0);
-    let rendered: String = result
-        .output
-        .iter()
-        .filter_map(|l| match &l.data {
-            crate::engine::shell::OutputLineData::Text(s) => Some(s.clone()),
-            _ => None,
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
-    assert!(
-        rendered.contains("clean") || rendered.contains("nothing to commit"),
-        "expected clean-tree message, got:\n{}",
-        rendered
+    assert!(result.output.is_empty());
+    assert_eq!(
+        result.side_effects,
+        vec![
+            SideEffect::SetEnvVar {
+                key: "FOO_P2_A".to_string(),
+                value: "alpha".to_string(),
+            },
+            SideEffect::SetEnvVar {
+                key: "BAR_P2_A".to_string(),
+                value: "beta".to_string(),
+            },
+        ]
     );
 }
 
 #[test]
-fn test_sync_status_with_remote_head() {
-    let (_ws, fs) = empty_state();
-    let ws = admin_wallet();
-    let cs = ChangeSet::new();
-    let result = execute_command(
-        Command::Sync(SyncSubcommand::Status),
+fn test_export_stores_tilde_expanded_path() {
+    use crate::engine::shell::execute_pipeline_with_context;
+    use crate::engine::shell::parser::parse_input;
+
+    let (ws, fs) = empty_state();
+    let result = execute_pipeline_with_context(
+        &parse_input("export CONTENT=~/blog", &[]),
         &ws,
+        &[],
         &fs,
-        &home_cwd(""),
-        &cs,
-        Some("abcdef1234567890"),
+        &root_cwd(),
+        &ChangeSet::new(),
+        None,
+        &ExecutionContext::default(),
     );
     assert_eq!(result.exit_code, 0);
-    let has_head = result.output.iter().any(
-            |l| matches!(&l.data, crate::engine::shell::OutputLineData::Text(s) if s.contains("abcdef12")),
-        );
-    assert!(has_head, "expected remote HEAD prefix in output");
+    assert_eq!(
+        result.side_effects,
+        vec![SideEffect::SetEnvVar {
+            key: "CONTENT".to_string(),
+            value: "/blog".to_string(),
+        }]
+    );
 }
 
-#[test]
-fn test_sync_status_reports_entries() {
-    let (_ws, fs) = empty_state();
-    let ws = admin_wallet();
-    let mut cs = ChangeSet::new();
-    upsert(
-        &mut cs,
-        home_vpath("new.md"),
-        ChangeType::CreateFile {
-            content: "x".to_string(),
-            meta: blank_file_meta(NodeKind::Asset),
-            extensions: EntryExtensions::default(),
-        },
-    );
-    upsert(&mut cs, home_vpath("del.md"), ChangeType::DeleteFile);
-    cs.unstage(&home_vpath("del.md"));
-    let result = execute_command(
-        Command::Sync(SyncSubcommand::Status),
+/// `export` through the lexer, with `$VAR` expanded from the session env the
+/// terminal hands the parser.
+fn run_export(input: &str, env: &[(&str, &str)]) -> Vec<SideEffect> {
+    use crate::engine::shell::execute_pipeline_with_context;
+    use crate::engine::shell::parser::parse_input_with_env;
+
+    let (ws, fs) = empty_state();
+    let user_env: std::collections::BTreeMap<String, String> = env
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    let env = session_env(&ws, &root_cwd(), &user_env);
+    let result = execute_pipeline_with_context(
+        &parse_input_with_env(input, &[], &env),
         &ws,
+        &[],
         &fs,
-        &home_cwd(""),
-        &cs,
+        &root_cwd(),
+        &ChangeSet::new(),
         None,
+        &ExecutionContext {
+            env: user_env,
+            ..ExecutionContext::default()
+        },
     );
-    assert_eq!(result.exit_code, 0);
-    let rendered: String = result
-        .output
-        .iter()
-        .filter_map(|l| match &l.data {
-            crate::engine::shell::OutputLineData::Text(s) => Some(s.clone()),
-            _ => None,
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
-    assert!(
-        rendered.contains("/new.md"),
-        "missing /new.md: {}",
-        rendered
+    assert_eq!(result.exit_code, 0, "{input}");
+    result.side_effects
+}
+
+fn set_env(key: &str, value: &str) -> SideEffect {
+    SideEffect::SetEnvVar {
+        key: key.to_string(),
+        value: value.to_string(),
+    }
+}
+
+#[test]
+fn test_export_appends_to_an_existing_variable() {
+    assert_eq!(
+        run_export("export PATH=$PATH:/extra", &[("PATH", "/bin")]),
+        vec![set_env("PATH", "/bin:/extra")]
     );
-    assert!(
-        rendered.contains("/del.md"),
-        "missing /del.md: {}",
-        rendered
+    assert_eq!(
+        run_export("export PATH=/extra:${PATH}", &[("PATH", "/bin")]),
+        vec![set_env("PATH", "/extra:/bin")]
+    );
+    // Unset variables expand to nothing.
+    assert_eq!(
+        run_export("export PATH=$PATH:/extra", &[]),
+        vec![set_env("PATH", ":/extra")]
     );
 }
 
 #[test]
-fn test_sync_commit_side_effect() {
-    let (_ws, fs) = empty_state();
-    let ws = admin_wallet();
-    let mut cs = ChangeSet::new();
-    upsert(
-        &mut cs,
-        home_vpath("a.md"),
-        ChangeType::CreateFile {
-            content: "x".to_string(),
-            meta: blank_file_meta(NodeKind::Asset),
-            extensions: EntryExtensions::default(),
-        },
+fn test_export_expands_inside_double_quotes_only() {
+    let user = empty_state().0.display_name();
+    assert_eq!(
+        run_export("export MSG=\"hello $USER\"", &[]),
+        vec![set_env("MSG", &format!("hello {user}"))]
     );
-    let result = execute_command(
-        Command::Sync(SyncSubcommand::Commit {
-            message: "feat: x".to_string(),
-        }),
-        &ws,
-        &fs,
-        &home_cwd(""),
-        &cs,
-        Some("deadbeef"),
+    assert_eq!(
+        run_export("export MSG='hello $USER'", &[]),
+        vec![set_env("MSG", "hello $USER")]
+    );
+    assert_eq!(
+        run_export("export AT=\"$PWD\" HOME_TOO=$HOME", &[]),
+        vec![set_env("AT", "~"), set_env("HOME_TOO", "~")]
     );
-    assert_eq!(result.exit_code, 0);
-    match result.side_effects.first().cloned() {
-        Some(SideEffect::Commit {
-            ref message,
-            ref mount_root,
-        }) => {
-            assert_eq!(message, "feat: x");
-            assert_eq!(mount_root.as_str(), "/");
-        }
-        other => panic!("expected Commit, got {:?}", other),
-    }
 }
 
 #[test]
-fn test_sync_commit_requires_staged_changes() {
-    let (_ws, fs) = empty_state();
-    let ws = admin_wallet();
+fn test_export_keeps_quoted_spaces_and_inner_quotes() {
+    assert_eq!(
+        run_export("export MSG=\"  say \\\"hi\\\"  \"", &[]),
+        vec![set_env("MSG", "  say \"hi\"  ")]
+    );
+    assert_eq!(
+        run_export("export Q=\"'single'\"", &[]),
+        vec![set_env("Q", "'single'")]
+    );
+}
+
+#[test]
+fn test_cd_empty_string_exit_1() {
+    // POSIX bash: `cd ""` errors with "cd: : No such file or directory".
+    // Must exercise a non-Root route so the early `at_root` branch doesn't
+    // short-circuit to the generic mount-alias error.
+    let (ws, fs) = empty_state();
     let cs = ChangeSet::new();
+    let browse_route = home_cwd("");
     let result = execute_command(
-        Command::Sync(SyncSubcommand::Commit {
-            message: "msg".to_string(),
-        }),
+        Command::Cd(super::super::PathArg::new("")),
         &ws,
         &fs,
-        &home_cwd(""),
+        &browse_route,
         &cs,
         None,
     );
     assert_eq!(result.exit_code, 1);
-}
-
-#[test]
-fn test_sync_commit_rejects_changes_across_multiple_mounts() {
-    let runtime_mounts = vec![
-        crate::engine::runtime::boot::bootstrap_runtime_mount(&bootstrap_source()),
-        crate::domain::RuntimeMount::new(
-            VirtualPath::from_absolute("/db").unwrap(),
-            "db",
-            crate::domain::RuntimeBackendKind::GitHub,
-            true,
-        ),
-    ];
-    let mut cs = ChangeSet::new();
-    upsert(
-        &mut cs,
-        home_vpath("a.md"),
-        ChangeType::CreateFile {
-            content: "site".to_string(),
-            meta: blank_file_meta(NodeKind::Asset),
-            extensions: EntryExtensions::default(),
-        },
-    );
-    upsert(
-        &mut cs,
-        VirtualPath::from_absolute("/db/b.md").unwrap(),
-        ChangeType::CreateFile {
-            content: "db".to_string(),
-            meta: blank_file_meta(NodeKind::Asset),
-            extensions: EntryExtensions::default(),
-        },
+    assert!(result.side_effects.first().cloned().is_none());
+    assert!(
+        result.output.iter().any(|l| matches!(
+            &l.data,
+            crate::engine::shell::OutputLineData::Error(s) if s == "cd: : No such file or directory"
+        )),
+        "expected POSIX cd error; got: {:?}",
+        result.output
     );
-
-    let err = sync_mount_root(&runtime_mounts, &home_cwd(""), &cs)
-        .expect_err("mixed mount changes must not select a single backend");
-    assert_eq!(err.exit_code, 1);
 }
 
 #[test]
-fn test_sync_refresh_side_effect() {
-    let (_ws, fs) = empty_state();
-    let ws = admin_wallet();
+fn test_touch_requires_admin() {
+    let (ws, fs) = empty_state();
     let cs = ChangeSet::new();
     let result = execute_command(
-        Command::Sync(SyncSubcommand::Refresh),
+        Command::Touch {
+            path: PathArg::new("new.md"),
+        },
         &ws,
         &fs,
         &home_cwd(""),
         &cs,
         None,
     );
-    assert_eq!(result.exit_code, 0);
-    assert_eq!(
-        result.side_effects.first().cloned(),
-        Some(SideEffect::ReloadRuntimeMount {
-            mount_root: VirtualPath::root(),
-        })
-    );
+    assert_eq!(result.exit_code, 1);
+    assert!(result.side_effects.first().cloned().is_none());
 }
 
 #[test]
-fn test_sync_auth_set_side_effect() {
+fn test_write_rejects_runtime_state_tree() {
     let (_ws, fs) = empty_state();
     let ws = admin_wallet();
     let cs = ChangeSet::new();
     let result = execute_command(
-        Command::Sync(SyncSubcommand::Auth(AuthAction::Set {
-            token: "ghp_abc".to_string(),
-        })),
+        Command::Touch {
+            path: PathArg::new("/.websh/state/new.md"),
+        },
         &ws,
         &fs,
         &home_cwd(""),
         &cs,
         None,
     );
-    assert_eq!(result.exit_code, 0);
-    match result.side_effects.first().cloned() {
-        Some(SideEffect::SetAuthToken { ref token }) => assert_eq!(token, "ghp_abc"),
-        other => panic!("expected SetAuthToken, got {:?}", other),
-    }
+
+    assert_eq!(result.exit_code, 1);
+    assert!(result.side_effects.first().cloned().is_none());
+    assert!(result.output.iter().any(|line| {
+        matches!(
+            &line.data,
+            crate::engine::shell::OutputLineData::Error(message)
+                if message.contains("read-only filesystem")
+        )
+    }));
 }
 
 #[test]
-fn test_sync_auth_clear_side_effect() {
+fn test_touch_creates_apply_change_side_effect() {
     let (_ws, fs) = empty_state();
     let ws = admin_wallet();
     let cs = ChangeSet::new();
     let result = execute_command(
-        Command::Sync(SyncSubcommand::Auth(AuthAction::Clear)),
+        Command::Touch {
+            path: PathArg::new("new.md"),
+        },
         &ws,
         &fs,
         &home_cwd(""),
@@ -1204,185 +1962,247 @@ fn test_sync_auth_clear_side_effect() {
         None,
     );
     assert_eq!(result.exit_code, 0);
-    assert_eq!(
-        result.side_effects.first().cloned(),
-        Some(SideEffect::ClearAuthToken)
-    );
-}
-
-#[test]
-fn test_has_children_empty_dir_is_false() {
-    let mut fs = GlobalFs::empty();
-    fs.upsert_directory(home_vpath("empty"), blank_dir_meta());
-    assert!(!fs.has_children(&home_vpath("empty")));
+    match result.side_effects.first().cloned() {
+        Some(SideEffect::ApplyChange {
+            ref path,
+            ref change,
+        }) => {
+            assert_eq!(path.as_str(), "/new.md");
+            assert!(matches!(change.as_ref(), ChangeType::CreateFile { .. }));
+        }
+        other => panic!("expected ApplyChange, got {:?}", other),
+    }
 }
 
 #[test]
-fn test_has_children_with_child_is_true() {
+fn test_touch_errors_when_path_exists_in_fs() {
+    // Build an fs with a file at "new.md"
     let mut fs = GlobalFs::empty();
-    fs.upsert_directory(home_vpath("dir"), blank_dir_meta());
     fs.upsert_file(
-        home_vpath("dir/child.md"),
+        home_vpath("new.md"),
         String::new(),
         blank_file_meta(NodeKind::Asset),
         EntryExtensions::default(),
     );
-    assert!(fs.has_children(&home_vpath("dir")));
-}
-
-#[test]
-fn test_has_children_nonexistent_is_false() {
-    let fs = GlobalFs::empty();
-    assert!(!fs.has_children(&home_vpath("ghost")));
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Touch {
+            path: PathArg::new("new.md"),
+        },
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 1);
 }
 
 #[test]
-fn test_has_children_file_is_false() {
+fn test_touch_errors_when_parent_is_file() {
     let mut fs = GlobalFs::empty();
     fs.upsert_file(
-        home_vpath("file.md"),
+        home_vpath("file"),
         String::new(),
         blank_file_meta(NodeKind::Asset),
         EntryExtensions::default(),
     );
-    assert!(!fs.has_children(&home_vpath("file.md")));
-}
-
-/// Build the merged "current view" that the terminal dispatcher sees.
-fn view(base: &GlobalFs, changes: &ChangeSet) -> GlobalFs {
-    crate::engine::runtime::build_view_global_fs(
-        base,
-        changes,
-        &WalletState::Disconnected,
-        &crate::engine::runtime::RuntimeStateSnapshot::default(),
-    )
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Touch {
+            path: PathArg::new("file/child.md"),
+        },
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 1);
+    assert!(result.output.iter().any(|line| {
+        matches!(
+            &line.data,
+            crate::engine::shell::OutputLineData::Error(message)
+                if message.contains("parent is not a directory")
+        )
+    }));
 }
 
 #[test]
-fn test_rm_on_pending_create_file_emits_discard_change() {
-    // Base fs empty; ChangeSet has a pending CreateFile at /a.md.
-    let base = GlobalFs::empty();
-    let mut cs = ChangeSet::new();
-    upsert(
-        &mut cs,
-        home_vpath("a.md"),
-        ChangeType::CreateFile {
-            content: String::new(),
-            meta: blank_file_meta(NodeKind::Asset),
-            extensions: EntryExtensions::default(),
+fn test_touch_errors_when_parent_is_missing() {
+    let (_ws, fs) = empty_state();
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Touch {
+            path: PathArg::new("missing/child.md"),
         },
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
     );
-    let merged = view(&base, &cs);
+    assert_eq!(result.exit_code, 1);
+    assert!(result.output.iter().any(|line| {
+        matches!(
+            &line.data,
+            crate::engine::shell::OutputLineData::Error(message)
+                if message.contains("parent directory does not exist")
+        )
+    }));
+}
 
+#[test]
+fn test_mkdir_creates_apply_change_side_effect() {
+    let (_ws, fs) = empty_state();
     let ws = admin_wallet();
+    let cs = ChangeSet::new();
     let result = execute_command(
-        Command::Rm {
-            path: PathArg::new("a.md"),
-            recursive: false,
+        Command::Mkdir {
+            path: PathArg::new("newdir"),
         },
         &ws,
-        &merged,
+        &fs,
         &home_cwd(""),
         &cs,
         None,
     );
     assert_eq!(result.exit_code, 0);
     match result.side_effects.first().cloned() {
-        Some(SideEffect::DiscardChange { ref path }) => {
-            assert_eq!(path.as_str(), "/a.md");
+        Some(SideEffect::ApplyChange {
+            ref path,
+            ref change,
+        }) => {
+            assert_eq!(path.as_str(), "/newdir");
+            assert!(matches!(
+                change.as_ref(),
+                ChangeType::CreateDirectory { .. }
+            ));
         }
-        other => panic!("expected DiscardChange, got {:?}", other),
+        other => panic!("expected ApplyChange, got {:?}", other),
     }
 }
 
 #[test]
-fn test_rm_recursive_on_pending_create_directory_emits_discard_change() {
-    let base = GlobalFs::empty();
-    let mut cs = ChangeSet::new();
-    upsert(
-        &mut cs,
-        home_vpath("d"),
-        ChangeType::CreateDirectory {
-            meta: blank_dir_meta(),
+fn test_mkdir_errors_when_path_exists() {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_directory(home_vpath("dir"), blank_dir_meta());
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Mkdir {
+            path: PathArg::new("dir"),
         },
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
     );
-    let merged = view(&base, &cs);
+    assert_eq!(result.exit_code, 1);
+}
 
+#[test]
+fn test_mkdir_errors_when_parent_is_file() {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_file(
+        home_vpath("file"),
+        String::new(),
+        blank_file_meta(NodeKind::Asset),
+        EntryExtensions::default(),
+    );
     let ws = admin_wallet();
+    let cs = ChangeSet::new();
     let result = execute_command(
-        Command::Rm {
-            path: PathArg::new("d"),
-            recursive: true,
+        Command::Mkdir {
+            path: PathArg::new("file/child"),
         },
         &ws,
-        &merged,
+        &fs,
         &home_cwd(""),
         &cs,
         None,
     );
-    assert_eq!(result.exit_code, 0);
-    match result.side_effects.first().cloned() {
-        Some(SideEffect::DiscardChange { ref path }) => {
-            assert_eq!(path.as_str(), "/d");
-        }
-        other => panic!("expected DiscardChange, got {:?}", other),
-    }
+    assert_eq!(result.exit_code, 1);
 }
 
 #[test]
-fn test_rmdir_on_pending_create_directory_emits_discard_change() {
-    let base = GlobalFs::empty();
-    let mut cs = ChangeSet::new();
-    upsert(
-        &mut cs,
-        home_vpath("d"),
-        ChangeType::CreateDirectory {
-            meta: blank_dir_meta(),
-        },
+fn test_rm_file_side_effect() {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_file(
+        home_vpath("doomed.md"),
+        String::new(),
+        blank_file_meta(NodeKind::Asset),
+        EntryExtensions::default(),
     );
-    let merged = view(&base, &cs);
-
     let ws = admin_wallet();
+    let cs = ChangeSet::new();
     let result = execute_command(
-        Command::Rmdir {
-            path: PathArg::new("d"),
+        Command::Rm {
+            path: PathArg::new("doomed.md"),
+            recursive: false,
         },
         &ws,
-        &merged,
+        &fs,
         &home_cwd(""),
         &cs,
         None,
     );
     assert_eq!(result.exit_code, 0);
     match result.side_effects.first().cloned() {
-        Some(SideEffect::DiscardChange { ref path }) => {
-            assert_eq!(path.as_str(), "/d");
+        Some(SideEffect::ApplyChange {
+            ref path,
+            ref change,
+        }) => {
+            assert_eq!(path.as_str(), "/doomed.md");
+            assert!(matches!(change.as_ref(), ChangeType::DeleteFile));
         }
-        other => panic!("expected DiscardChange, got {:?}", other),
+        other => panic!("expected DeleteFile ApplyChange, got {:?}", other),
     }
 }
 
 #[test]
-fn test_rm_on_base_file_still_emits_apply_change_delete() {
-    // File is in base fs, NOT in ChangeSet -> Delete, not Discard.
-    let mut base = GlobalFs::empty();
-    base.upsert_file(
-        home_vpath("existing.md"),
-        "hi".into(),
-        blank_file_meta(NodeKind::Asset),
-        EntryExtensions::default(),
-    );
+fn test_rm_directory_without_r_errors() {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_directory(home_vpath("dir"), blank_dir_meta());
+    let ws = admin_wallet();
     let cs = ChangeSet::new();
-    let merged = view(&base, &cs);
+    let result = execute_command(
+        Command::Rm {
+            path: PathArg::new("dir"),
+            recursive: false,
+        },
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 1);
+    assert!(result.side_effects.is_empty());
+    assert!(matches!(
+        result.output.first().map(|line| &line.data),
+        Some(OutputLineData::Error(message))
+            if message == "rm: cannot remove 'dir': Is a directory (use -r)"
+    ));
+}
 
+#[test]
+fn test_rm_directory_recursive_side_effect() {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_directory(home_vpath("dir"), blank_dir_meta());
     let ws = admin_wallet();
+    let cs = ChangeSet::new();
     let result = execute_command(
         Command::Rm {
-            path: PathArg::new("existing.md"),
-            recursive: false,
+            path: PathArg::new("dir"),
+            recursive: true,
         },
         &ws,
-        &merged,
+        &fs,
         &home_cwd(""),
         &cs,
         None,
@@ -1393,42 +2213,2025 @@ fn test_rm_on_base_file_still_emits_apply_change_delete() {
             ref path,
             ref change,
         }) => {
-            assert_eq!(path.as_str(), "/existing.md");
-            assert!(matches!(change.as_ref(), ChangeType::DeleteFile));
+            assert_eq!(path.as_str(), "/dir");
+            assert!(matches!(change.as_ref(), ChangeType::DeleteDirectory));
         }
-        other => panic!("expected ApplyChange(DeleteFile), got {:?}", other),
+        other => panic!("expected DeleteDirectory ApplyChange, got {:?}", other),
     }
 }
 
 #[test]
-fn test_touch_errors_when_path_is_pending_create_in_merged_view() {
-    // Base does not contain /a.md, but the ChangeSet does as CreateFile.
-    // After the merged runtime view is computed and passed to execute, the
-    // existing `fs.get_entry(...).is_some()` guard must fire.
-    let base = GlobalFs::empty();
-    let mut cs = ChangeSet::new();
-    upsert(
-        &mut cs,
-        home_vpath("a.md"),
-        ChangeType::CreateFile {
-            content: String::new(),
-            meta: blank_file_meta(NodeKind::Asset),
-            extensions: EntryExtensions::default(),
+fn test_rm_recursive_rejects_mount_root() {
+    let db_root = VirtualPath::from_absolute("/db").unwrap();
+    let mut fs = GlobalFs::empty();
+    fs.upsert_directory(db_root.clone(), blank_dir_meta());
+    let runtime_mounts = vec![
+        crate::engine::runtime::boot::bootstrap_runtime_mount(&bootstrap_source()),
+        crate::domain::RuntimeMount::new(
+            db_root,
+            "db",
+            crate::domain::RuntimeBackendKind::GitHub,
+            true,
+        ),
+    ];
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+
+    let result = super::execute_command_with_context(
+        Command::Rm {
+            path: PathArg::new("/db"),
+            recursive: true,
+        },
+        &ws,
+        &runtime_mounts,
+        &fs,
+        &root_cwd(),
+        &cs,
+        None,
+        &ExecutionContext {
+            access_policy: ACCESS_POLICY,
+            ..ExecutionContext::default()
         },
     );
-    let merged = view(&base, &cs);
 
+    assert_eq!(result.exit_code, 1);
+    assert!(matches!(
+        result.output.first().map(|line| &line.data),
+        Some(OutputLineData::Error(message)) if message.contains("cannot remove mount root")
+    ));
+}
+
+#[test]
+fn test_rm_nonexistent_path_errors() {
+    let (_ws, fs) = empty_state();
     let ws = admin_wallet();
+    let cs = ChangeSet::new();
     let result = execute_command(
-        Command::Touch {
-            path: PathArg::new("a.md"),
+        Command::Rm {
+            path: PathArg::new("ghost.md"),
+            recursive: false,
         },
         &ws,
-        &merged,
+        &fs,
         &home_cwd(""),
         &cs,
         None,
     );
     assert_eq!(result.exit_code, 1);
-    assert!(result.side_effects.first().cloned().is_none());
+}
+
+fn transfer_fs() -> GlobalFs {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_file(
+        home_vpath("draft.md"),
+        String::new(),
+        blank_file_meta(NodeKind::Page),
+        EntryExtensions::default(),
+    );
+    fs.upsert_directory(home_vpath("blog"), blank_dir_meta());
+    fs.upsert_file(
+        home_vpath("blog/one.md"),
+        String::new(),
+        blank_file_meta(NodeKind::Page),
+        EntryExtensions::default(),
+    );
+    fs.upsert_file(
+        home_vpath("blog/nested/two.md"),
+        String::new(),
+        blank_file_meta(NodeKind::Page),
+        EntryExtensions::default(),
+    );
+    fs
+}
+
+fn transfer(cmd: Command, fs: &GlobalFs) -> CommandResult {
+    execute_command(
+        cmd,
+        &admin_wallet(),
+        fs,
+        &home_cwd(""),
+        &ChangeSet::new(),
+        None,
+    )
+}
+
+fn transfer_plan(result: &CommandResult) -> (Vec<(String, String)>, Option<EntryRemoval>) {
+    match result.side_effects.as_slice() {
+        [
+            SideEffect::TransferEntries {
+                copies,
+                remove_source,
+            },
+        ] => (
+            copies
+                .iter()
+                .map(|copy| (copy.from.as_str().to_string(), copy.to.as_str().to_string()))
+                .collect(),
+            remove_source.clone(),
+        ),
+        other => panic!("unexpected side effects: {other:?}"),
+    }
+}
+
+#[test]
+fn test_mv_renames_file() {
+    let fs = transfer_fs();
+    let result = transfer(
+        Command::Mv {
+            src: PathArg::new("draft.md"),
+            dst: PathArg::new("post.md"),
+        },
+        &fs,
+    );
+    assert_eq!(result.exit_code, 0);
+    let (copies, removal) = transfer_plan(&result);
+    assert_eq!(copies, vec![("/draft.md".into(), "/post.md".into())]);
+    assert_eq!(
+        removal,
+        Some(EntryRemoval {
+            path: home_vpath("draft.md"),
+            is_directory: false,
+        })
+    );
+}
+
+#[test]
+fn test_mv_moves_file_into_existing_directory() {
+    let fs = transfer_fs();
+    let result = transfer(
+        Command::Mv {
+            src: PathArg::new("draft.md"),
+            dst: PathArg::new("blog"),
+        },
+        &fs,
+    );
+    assert_eq!(result.exit_code, 0);
+    let (copies, _) = transfer_plan(&result);
+    assert_eq!(copies, vec![("/draft.md".into(), "/blog/draft.md".into())]);
+}
+
+#[test]
+fn test_cp_copies_directory_subtree_parents_first() {
+    let fs = transfer_fs();
+    let result = transfer(
+        Command::Cp {
+            src: PathArg::new("blog"),
+            dst: PathArg::new("archive"),
+        },
+        &fs,
+    );
+    assert_eq!(result.exit_code, 0);
+    let (copies, removal) = transfer_plan(&result);
+    assert_eq!(
+        copies,
+        vec![
+            ("/blog".into(), "/archive".into()),
+            ("/blog/nested".into(), "/archive/nested".into()),
+            (
+                "/blog/nested/two.md".into(),
+                "/archive/nested/two.md".into()
+            ),
+            ("/blog/one.md".into(), "/archive/one.md".into()),
+        ]
+    );
+    assert_eq!(removal, None);
+}
+
+#[test]
+fn test_cp_over_existing_file_marks_overwrite() {
+    let fs = transfer_fs();
+    let result = transfer(
+        Command::Cp {
+            src: PathArg::new("draft.md"),
+            dst: PathArg::new("blog/one.md"),
+        },
+        &fs,
+    );
+    assert!(matches!(
+        result.side_effects.as_slice(),
+        [SideEffect::TransferEntries { copies, .. }]
+            if matches!(copies[0].kind, EntryCopyKind::File { overwrite: true, .. })
+    ));
+}
+
+#[test]
+fn test_transfer_rejects_kind_mismatch_and_self_nesting() {
+    let mut fs = transfer_fs();
+    fs.upsert_directory(home_vpath("blog/draft.md"), blank_dir_meta());
+    let cases = [
+        (
+            Command::Cp {
+                src: PathArg::new("draft.md"),
+                dst: PathArg::new("blog"),
+            },
+            "cp: cannot overwrite directory '/blog/draft.md' with non-directory",
+        ),
+        (
+            Command::Mv {
+                src: PathArg::new("blog"),
+                dst: PathArg::new("draft.md"),
+            },
+            "mv: cannot overwrite non-directory '/draft.md' with directory 'blog'",
+        ),
+        (
+            Command::Cp {
+                src: PathArg::new("blog"),
+                dst: PathArg::new("blog/nested"),
+            },
+            "cp: cannot copy 'blog' into a subdirectory of itself",
+        ),
+        (
+            Command::Mv {
+                src: PathArg::new("missing.md"),
+                dst: PathArg::new("blog"),
+            },
+            "mv: cannot stat 'missing.md': No such file or directory",
+        ),
+    ];
+    for (cmd, expected) in cases {
+        let result = transfer(cmd, &fs);
+        assert_eq!(result.exit_code, 1, "{expected}");
+        assert!(result.side_effects.is_empty(), "{expected}");
+        assert!(
+            matches!(
+                result.output.first().map(|line| &line.data),
+                Some(OutputLineData::Error(message)) if message == expected
+            ),
+            "expected {expected:?}, got {:?}",
+            result.output
+        );
+    }
+}
+
+#[test]
+fn test_mv_rejects_mount_root_and_requires_admin() {
+    let fs = transfer_fs();
+    let root = transfer(
+        Command::Mv {
+            src: PathArg::new("/"),
+            dst: PathArg::new("blog"),
+        },
+        &fs,
+    );
+    assert_eq!(root.exit_code, 1);
+
+    let (ws, _) = empty_state();
+    let guest = execute_command(
+        Command::Cp {
+            src: PathArg::new("draft.md"),
+            dst: PathArg::new("copy.md"),
+        },
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &ChangeSet::new(),
+        None,
+    );
+    assert_eq!(guest.exit_code, 1);
+    assert!(guest.side_effects.is_empty());
+}
+
+#[test]
+fn test_rmdir_empty_directory_side_effect() {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_directory(home_vpath("empty"), blank_dir_meta());
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Rmdir {
+            path: PathArg::new("empty"),
+        },
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 0);
+    match result.side_effects.first().cloned() {
+        Some(SideEffect::ApplyChange { ref change, .. }) => {
+            assert!(matches!(change.as_ref(), ChangeType::DeleteDirectory));
+        }
+        other => panic!("expected DeleteDirectory, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_rmdir_rejects_mount_root() {
+    let db_root = VirtualPath::from_absolute("/db").unwrap();
+    let mut fs = GlobalFs::empty();
+    fs.upsert_directory(db_root.clone(), blank_dir_meta());
+    let runtime_mounts = vec![
+        crate::engine::runtime::boot::bootstrap_runtime_mount(&bootstrap_source()),
+        crate::domain::RuntimeMount::new(
+            db_root,
+            "db",
+            crate::domain::RuntimeBackendKind::GitHub,
+            true,
+        ),
+    ];
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+
+    let result = super::execute_command_with_context(
+        Command::Rmdir {
+            path: PathArg::new("/db"),
+        },
+        &ws,
+        &runtime_mounts,
+        &fs,
+        &root_cwd(),
+        &cs,
+        None,
+        &ExecutionContext {
+            access_policy: ACCESS_POLICY,
+            ..ExecutionContext::default()
+        },
+    );
+
+    assert_eq!(result.exit_code, 1);
+    assert!(matches!(
+        result.output.first().map(|line| &line.data),
+        Some(OutputLineData::Error(message)) if message.contains("cannot remove mount root")
+    ));
+}
+
+#[test]
+fn test_rmdir_nonempty_directory_errors() {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_directory(home_vpath("dir"), blank_dir_meta());
+    fs.upsert_file(
+        home_vpath("dir/child.md"),
+        String::new(),
+        blank_file_meta(NodeKind::Asset),
+        EntryExtensions::default(),
+    );
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Rmdir {
+            path: PathArg::new("dir"),
+        },
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 1);
+}
+
+#[test]
+fn test_rmdir_on_file_errors() {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_file(
+        home_vpath("file.md"),
+        String::new(),
+        blank_file_meta(NodeKind::Asset),
+        EntryExtensions::default(),
+    );
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Rmdir {
+            path: PathArg::new("file.md"),
+        },
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 1);
+}
+
+#[test]
+fn test_edit_opens_editor_for_existing_file() {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_file(
+        home_vpath("note.md"),
+        "hi".to_string(),
+        blank_file_meta(NodeKind::Asset),
+        EntryExtensions::default(),
+    );
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Edit {
+            path: PathArg::new("note.md"),
+        },
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 0);
+    match result.side_effects.first().cloned() {
+        Some(SideEffect::OpenEditor { ref path }) => {
+            assert_eq!(path.as_str(), "/note.md");
+        }
+        other => panic!("expected OpenEditor, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_edit_on_missing_file_opens_editor() {
+    // Create-on-save: `edit` on a non-existent path still yields OpenEditor.
+    let (_ws, fs) = empty_state();
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Edit {
+            path: PathArg::new("fresh.md"),
+        },
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 0);
+    assert!(matches!(
+        result.side_effects.first().cloned(),
+        Some(SideEffect::OpenEditor { .. })
+    ));
+}
+
+#[test]
+fn test_edit_on_directory_errors() {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_directory(home_vpath("dir"), blank_dir_meta());
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Edit {
+            path: PathArg::new("dir"),
+        },
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 1);
+}
+
+fn run_echo(words: &[&str], newline: bool, escapes: bool) -> Vec<String> {
+    let (ws, fs) = empty_state();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Echo {
+            words: words.iter().map(|word| word.to_string()).collect(),
+            newline,
+            escapes,
+        },
+        &ws,
+        &fs,
+        &root_cwd(),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 0);
+    output_text(&result)
+}
+
+#[test]
+fn test_echo_keeps_quoted_internal_spaces() {
+    assert_eq!(run_echo(&["a    b", "c"], true, false), vec!["a    b c"]);
+    assert_eq!(run_echo(&[], true, false), vec![""]);
+}
+
+#[test]
+fn test_echo_e_expands_escapes_into_lines() {
+    assert_eq!(
+        run_echo(&["one\\ttab\\ntwo"], true, true),
+        vec!["one\ttab", "two"]
+    );
+    // Without -e the backslashes are printed as written.
+    assert_eq!(run_echo(&["a\\nb"], true, false), vec!["a\\nb"]);
+}
+
+#[test]
+fn test_echo_n_drops_trailing_empty_line() {
+    assert_eq!(run_echo(&["a\\n"], true, true), vec!["a", ""]);
+    assert_eq!(run_echo(&["a\\n"], false, true), vec!["a"]);
+    assert!(run_echo(&[], false, false).is_empty());
+    assert_eq!(run_echo(&["kept\\cgone\\n"], true, true), vec!["kept"]);
+}
+
+#[test]
+fn test_echo_redirect_writes_content() {
+    let (_ws, fs) = empty_state();
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::EchoRedirect {
+            body: "hello".to_string(),
+            path: PathArg::new("greeting.md"),
+        },
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 0);
+    match result.side_effects.first().cloned() {
+        Some(SideEffect::ApplyChange {
+            ref path,
+            ref change,
+        }) => {
+            assert_eq!(path.as_str(), "/greeting.md");
+            match change.as_ref() {
+                ChangeType::CreateFile { content, .. } => assert_eq!(content, "hello"),
+                other => panic!("expected CreateFile, got {:?}", other),
+            }
+        }
+        other => panic!("expected ApplyChange, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_echo_redirect_updates_existing_file() {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_file(
+        home_vpath("greet.md"),
+        "old".to_string(),
+        blank_file_meta(NodeKind::Asset),
+        EntryExtensions::default(),
+    );
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::EchoRedirect {
+            body: "new".to_string(),
+            path: PathArg::new("greet.md"),
+        },
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 0);
+    match result.side_effects.first().cloned() {
+        Some(SideEffect::ApplyChange { ref change, .. }) => match change.as_ref() {
+            ChangeType::UpdateFile { content, .. } => assert_eq!(content, "new"),
+            other => panic!("expected UpdateFile, got {:?}", other),
+        },
+        other => panic!("expected UpdateFile, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_echo_redirect_errors_when_parent_is_file() {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_file(
+        home_vpath("file"),
+        String::new(),
+        blank_file_meta(NodeKind::Asset),
+        EntryExtensions::default(),
+    );
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::EchoRedirect {
+            body: "hello".to_string(),
+            path: PathArg::new("file/child.md"),
+        },
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 1);
+}
+
+#[test]
+fn test_echo_redirect_requires_admin() {
+    let (ws, fs) = empty_state();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::EchoRedirect {
+            body: "x".to_string(),
+            path: PathArg::new("a.md"),
+        },
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 1);
+}
+
+#[test]
+fn test_sync_status_clean_tree() {
+    let (_ws, fs) = empty_state();
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Sync(SyncSubcommand::Status),
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 0);
+    let rendered: String = result
+        .output
+        .iter()
+        .filter_map(|l| match &l.data {
+            crate::engine::shell::OutputLineData::Text(s) => Some(s.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(
+        rendered.contains("clean") || rendered.contains("nothing to commit"),
+        "expected clean-tree message, got:\n{}",
+        rendered
+    );
+}
+
+#[test]
+fn test_sync_status_with_remote_head() {
+    let (_ws, fs) = empty_state();
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Sync(SyncSubcommand::Status),
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        Some("abcdef1234567890"),
+    );
+    assert_eq!(result.exit_code, 0);
+    let has_head = result.output.iter().any(
+            |l| matches!(&l.data, crate::engine::shell::OutputLineData::Text(s) if s.contains("abcdef12")),
+        );
+    assert!(has_head, "expected remote HEAD prefix in output");
+}
+
+#[test]
+fn test_sync_status_reports_entries() {
+    let (_ws, fs) = empty_state();
+    let ws = admin_wallet();
+    let mut cs = ChangeSet::new();
+    upsert(
+        &mut cs,
+        home_vpath("new.md"),
+        ChangeType::CreateFile {
+            content: "x".to_string(),
+            meta: blank_file_meta(NodeKind::Asset),
+            extensions: EntryExtensions::default(),
+        },
+    );
+    upsert(&mut cs, home_vpath("del.md"), ChangeType::DeleteFile);
+    cs.unstage(&home_vpath("del.md"));
+    let result = execute_command(
+        Command::Sync(SyncSubcommand::Status),
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 0);
+    let rendered: String = result
+        .output
+        .iter()
+        .filter_map(|l| match &l.data {
+            crate::engine::shell::OutputLineData::Text(s) => Some(s.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(
+        rendered.contains("/new.md"),
+        "missing /new.md: {}",
+        rendered
+    );
+    assert!(
+        rendered.contains("/del.md"),
+        "missing /del.md: {}",
+        rendered
+    );
+}
+
+#[test]
+fn test_sync_commit_side_effect() {
+    let (_ws, fs) = empty_state();
+    let ws = admin_wallet();
+    let mut cs = ChangeSet::new();
+    upsert(
+        &mut cs,
+        home_vpath("a.md"),
+        ChangeType::CreateFile {
+            content: "x".to_string(),
+            meta: blank_file_meta(NodeKind::Asset),
+            extensions: EntryExtensions::default(),
+        },
+    );
+    let result = execute_command(
+        Command::Sync(SyncSubcommand::Commit {
+            message: "feat: x".to_string(),
+        }),
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        Some("deadbeef"),
+    );
+    assert_eq!(result.exit_code, 0);
+    match result.side_effects.first().cloned() {
+        Some(SideEffect::Commit {
+            ref message,
+            ref mount_root,
+        }) => {
+            assert_eq!(message, "feat: x");
+            assert_eq!(mount_root.as_str(), "/");
+        }
+        other => panic!("expected Commit, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_sync_commit_requires_staged_changes() {
+    let (_ws, fs) = empty_state();
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Sync(SyncSubcommand::Commit {
+            message: "msg".to_string(),
+        }),
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 1);
+}
+
+#[test]
+fn test_sync_commit_rejects_changes_across_multiple_mounts() {
+    let runtime_mounts = vec![
+        crate::engine::runtime::boot::bootstrap_runtime_mount(&bootstrap_source()),
+        crate::domain::RuntimeMount::new(
+            VirtualPath::from_absolute("/db").unwrap(),
+            "db",
+            crate::domain::RuntimeBackendKind::GitHub,
+            true,
+        ),
+    ];
+    let mut cs = ChangeSet::new();
+    upsert(
+        &mut cs,
+        home_vpath("a.md"),
+        ChangeType::CreateFile {
+            content: "site".to_string(),
+            meta: blank_file_meta(NodeKind::Asset),
+            extensions: EntryExtensions::default(),
+        },
+    );
+    upsert(
+        &mut cs,
+        VirtualPath::from_absolute("/db/b.md").unwrap(),
+        ChangeType::CreateFile {
+            content: "db".to_string(),
+            meta: blank_file_meta(NodeKind::Asset),
+            extensions: EntryExtensions::default(),
+        },
+    );
+
+    let err = sync_mount_root(&runtime_mounts, &home_cwd(""), &cs)
+        .expect_err("mixed mount changes must not select a single backend");
+    assert_eq!(err.exit_code, 1);
+}
+
+#[test]
+fn test_sync_refresh_side_effect() {
+    let (_ws, fs) = empty_state();
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Sync(SyncSubcommand::Refresh),
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 0);
+    assert_eq!(
+        result.side_effects.first().cloned(),
+        Some(SideEffect::ReloadRuntimeMount {
+            mount_root: VirtualPath::root(),
+        })
+    );
+}
+
+#[test]
+fn test_sync_auth_set_side_effect() {
+    let (_ws, fs) = empty_state();
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Sync(SyncSubcommand::Auth(AuthAction::Set {
+            token: "ghp_abc".to_string(),
+        })),
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 0);
+    match result.side_effects.first().cloned() {
+        Some(SideEffect::SetAuthToken { ref token }) => assert_eq!(token, "ghp_abc"),
+        other => panic!("expected SetAuthToken, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_sync_auth_clear_side_effect() {
+    let (_ws, fs) = empty_state();
+    let ws = admin_wallet();
+    let cs = ChangeSet::new();
+    let result = execute_command(
+        Command::Sync(SyncSubcommand::Auth(AuthAction::Clear)),
+        &ws,
+        &fs,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 0);
+    assert_eq!(
+        result.side_effects.first().cloned(),
+        Some(SideEffect::ClearAuthToken)
+    );
+}
+
+#[test]
+fn test_has_children_empty_dir_is_false() {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_directory(home_vpath("empty"), blank_dir_meta());
+    assert!(!fs.has_children(&home_vpath("empty")));
+}
+
+#[test]
+fn test_has_children_with_child_is_true() {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_directory(home_vpath("dir"), blank_dir_meta());
+    fs.upsert_file(
+        home_vpath("dir/child.md"),
+        String::new(),
+        blank_file_meta(NodeKind::Asset),
+        EntryExtensions::default(),
+    );
+    assert!(fs.has_children(&home_vpath("dir")));
+}
+
+#[test]
+fn test_has_children_nonexistent_is_false() {
+    let fs = GlobalFs::empty();
+    assert!(!fs.has_children(&home_vpath("ghost")));
+}
+
+#[test]
+fn test_has_children_file_is_false() {
+    let mut fs = GlobalFs::empty();
+    fs.upsert_file(
+        home_vpath("file.md"),
+        String::new(),
+        blank_file_meta(NodeKind::Asset),
+        EntryExtensions::default(),
+    );
+    assert!(!fs.has_children(&home_vpath("file.md")));
+}
+
+/// Build the merged "current view" that the terminal dispatcher sees.
+fn view(base: &GlobalFs, changes: &ChangeSet) -> GlobalFs {
+    crate::engine::runtime::build_view_global_fs(
+        base,
+        changes,
+        &WalletState::Disconnected,
+        &crate::engine::runtime::RuntimeStateSnapshot::default(),
+    )
+}
+
+#[test]
+fn test_rm_on_pending_create_file_emits_discard_change() {
+    // Base fs empty; ChangeSet has a pending CreateFile at /a.md.
+    let base = GlobalFs::empty();
+    let mut cs = ChangeSet::new();
+    upsert(
+        &mut cs,
+        home_vpath("a.md"),
+        ChangeType::CreateFile {
+            content: String::new(),
+            meta: blank_file_meta(NodeKind::Asset),
+            extensions: EntryExtensions::default(),
+        },
+    );
+    let merged = view(&base, &cs);
+
+    let ws = admin_wallet();
+    let result = execute_command(
+        Command::Rm {
+            path: PathArg::new("a.md"),
+            recursive: false,
+        },
+        &ws,
+        &merged,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 0);
+    match result.side_effects.first().cloned() {
+        Some(SideEffect::DiscardChange { ref path }) => {
+            assert_eq!(path.as_str(), "/a.md");
+        }
+        other => panic!("expected DiscardChange, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_rm_recursive_on_pending_create_directory_emits_discard_change() {
+    let base = GlobalFs::empty();
+    let mut cs = ChangeSet::new();
+    upsert(
+        &mut cs,
+        home_vpath("d"),
+        ChangeType::CreateDirectory {
+            meta: blank_dir_meta(),
+        },
+    );
+    let merged = view(&base, &cs);
+
+    let ws = admin_wallet();
+    let result = execute_command(
+        Command::Rm {
+            path: PathArg::new("d"),
+            recursive: true,
+        },
+        &ws,
+        &merged,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 0);
+    match result.side_effects.first().cloned() {
+        Some(SideEffect::DiscardChange { ref path }) => {
+            assert_eq!(path.as_str(), "/d");
+        }
+        other => panic!("expected DiscardChange, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_rmdir_on_pending_create_directory_emits_discard_change() {
+    let base = GlobalFs::empty();
+    let mut cs = ChangeSet::new();
+    upsert(
+        &mut cs,
+        home_vpath("d"),
+        ChangeType::CreateDirectory {
+            meta: blank_dir_meta(),
+        },
+    );
+    let merged = view(&base, &cs);
+
+    let ws = admin_wallet();
+    let result = execute_command(
+        Command::Rmdir {
+            path: PathArg::new("d"),
+        },
+        &ws,
+        &merged,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 0);
+    match result.side_effects.first().cloned() {
+        Some(SideEffect::DiscardChange { ref path }) => {
+            assert_eq!(path.as_str(), "/d");
+        }
+        other => panic!("expected DiscardChange, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_rm_on_base_file_still_emits_apply_change_delete() {
+    // File is in base fs, NOT in ChangeSet -> Delete, not Discard.
+    let mut base = GlobalFs::empty();
+    base.upsert_file(
+        home_vpath("existing.md"),
+        "hi".into(),
+        blank_file_meta(NodeKind::Asset),
+        EntryExtensions::default(),
+    );
+    let cs = ChangeSet::new();
+    let merged = view(&base, &cs);
+
+    let ws = admin_wallet();
+    let result = execute_command(
+        Command::Rm {
+            path: PathArg::new("existing.md"),
+            recursive: false,
+        },
+        &ws,
+        &merged,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 0);
+    match result.side_effects.first().cloned() {
+        Some(SideEffect::ApplyChange {
+            ref path,
+            ref change,
+        }) => {
+            assert_eq!(path.as_str(), "/existing.md");
+            assert!(matches!(change.as_ref(), ChangeType::DeleteFile));
+        }
+        other => panic!("expected ApplyChange(DeleteFile), got {:?}", other),
+    }
+}
+
+#[test]
+fn test_touch_errors_when_path_is_pending_create_in_merged_view() {
+    // Base does not contain /a.md, but the ChangeSet does as CreateFile.
+    // After the merged runtime view is computed and passed to execute, the
+    // existing `fs.get_entry(...).is_some()` guard must fire.
+    let base = GlobalFs::empty();
+    let mut cs = ChangeSet::new();
+    upsert(
+        &mut cs,
+        home_vpath("a.md"),
+        ChangeType::CreateFile {
+            content: String::new(),
+            meta: blank_file_meta(NodeKind::Asset),
+            extensions: EntryExtensions::default(),
+        },
+    );
+    let merged = view(&base, &cs);
+
+    let ws = admin_wallet();
+    let result = execute_command(
+        Command::Touch {
+            path: PathArg::new("a.md"),
+        },
+        &ws,
+        &merged,
+        &home_cwd(""),
+        &cs,
+        None,
+    );
+    assert_eq!(result.exit_code, 1);
+    assert!(result.side_effects.first().cloned().is_none());
+}
+
+fn navigation() -> NavigationHistory {
+    NavigationHistory {
+        back: vec!["/websh/blog".to_string(), "/websh".to_string()],
+        current: Some("/websh/blog/2024".to_string()),
+        forward: vec!["/notes/todo.md".to_string()],
+    }
+}
+
+fn run_with_navigation(cmd: Command, navigation: NavigationHistory) -> CommandResult {
+    let (ws, fs) = empty_state();
+    super::execute_command_with_context(
+        cmd,
+        &ws,
+        &[],
+        &fs,
+        &root_cwd(),
+        &ChangeSet::new(),
+        None,
+        &ExecutionContext {
+            navigation,
+            ..ExecutionContext::default()
+        },
+    )
+}
+
+#[test]
+fn test_errors_are_worded_for_lang() {
+    let (ws, fs) = empty_state();
+    let context = ExecutionContext {
+        env: [("LANG".to_string(), "ko".to_string())].into(),
+        ..ExecutionContext::default()
+    };
+    let result = super::execute_command_with_context(
+        Command::Cat(None),
+        &ws,
+        &[],
+        &fs,
+        &root_cwd(),
+        &ChangeSet::new(),
+        None,
+        &context,
+    );
+    assert_eq!(result.exit_code, 1);
+    assert_eq!(result.output[0].to_plain_text(), "cat: file operand 누락");
+}
+
+#[test]
+fn test_dirs_lists_history_with_cd_offsets() {
+    let result = run_with_navigation(Command::Dirs { clear: false }, navigation());
+    let lines: Vec<String> = result.output.iter().map(|l| l.to_plain_text()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "   0  /websh/blog/2024",
+            "  -1  /websh/blog",
+            "  -2  /websh",
+            "  +1  /notes/todo.md",
+        ]
+    );
+}
+
+#[test]
+fn test_dirs_clear_requests_clearing_the_history() {
+    let result = run_with_navigation(Command::Dirs { clear: true }, navigation());
+    assert!(result.output.is_empty());
+    assert_eq!(
+        result.side_effects,
+        vec![SideEffect::ClearNavigationHistory]
+    );
+}
+
+#[test]
+fn test_cd_history_jumps_within_the_stacks() {
+    let result = run_with_navigation(Command::CdHistory(-2), navigation());
+    assert_eq!(result.exit_code, 0);
+    assert_eq!(
+        result.side_effects,
+        vec![SideEffect::JumpHistory { offset: -2 }]
+    );
+    let result = run_with_navigation(Command::CdHistory(1), navigation());
+    assert_eq!(
+        result.side_effects,
+        vec![SideEffect::JumpHistory { offset: 1 }]
+    );
+}
+
+#[test]
+fn test_cd_history_past_the_stack_is_an_error() {
+    let result = run_with_navigation(Command::CdHistory(-3), navigation());
+    assert_eq!(result.exit_code, 1);
+    assert!(result.side_effects.is_empty());
+    assert_eq!(
+        result.output[0].to_plain_text(),
+        "cd: -3: only 2 entries back in the history"
+    );
+    let result = run_with_navigation(Command::CdHistory(2), navigation());
+    assert_eq!(
+        result.output[0].to_plain_text(),
+        "cd: +2: only 1 entry forward in the history"
+    );
+}
+
+/// Failure lines and statuses, pinned to the wording the terminal has
+/// always printed.
+#[test]
+fn test_failure_messages_and_exit_codes() {
+    use crate::engine::shell::execute_pipeline_with_context;
+    use crate::engine::shell::parser::parse_input;
+
+    let fs = pipeline_fs();
+    let runtime_mounts = [crate::engine::runtime::boot::bootstrap_runtime_mount(
+        &bootstrap_source(),
+    )];
+    let context = ExecutionContext {
+        access_policy: ACCESS_POLICY,
+        ..ExecutionContext::default()
+    };
+    let cases = [
+        (
+            "ls nope",
+            "ls: cannot access 'nope': No such file or directory",
+            1,
+        ),
+        (
+            "ls blog/rust.md/x",
+            "ls: cannot access 'blog/rust.md/x': Not a directory",
+            1,
+        ),
+        ("cd nope", "cd: no such file or directory: nope", 1),
+        ("cd blog/rust.md", "cd: not a directory: blog/rust.md", 1),
+        (
+            "cd blog/rust.md/sub",
+            "cd: not a directory: blog/rust.md/sub",
+            1,
+        ),
+        (
+            "cd blog/nope/sub",
+            "cd: no such file or directory: blog/nope/sub",
+            1,
+        ),
+        (
+            "cat blog/rust.md/x",
+            "cat: blog/rust.md/x: not a directory",
+            1,
+        ),
+        ("cd ''", "cd: : No such file or directory", 1),
+        ("cat blog", "cat: blog: Is a directory", 1),
+        ("cat nope.md", "cat: nope.md: No such file or directory", 1),
+        ("cat", "cat: missing file operand", 1),
+        ("unset", "unset: missing variable name", 1),
+        (
+            "touch blog/new.md",
+            "touch: permission denied (admin login required)",
+            1,
+        ),
+        ("lss", "Command not found: lss. Did you mean 'ls'?", 127),
+        (
+            "ls | sort",
+            "Pipe: unknown filter 'sort'. Supported: grep, head, tail, tee, wc, xargs",
+            127,
+        ),
+        ("ls | grep", "grep: missing pattern", 2),
+        ("ls | grep -z x", "grep: unknown option: -z", 2),
+        (
+            "ls | grep a b",
+            "grep: extra argument (multiple patterns or file args are not supported)",
+            2,
+        ),
+        ("ls | grep '('", "grep: invalid regex:", 2),
+        ("ls | head -n", "head: option requires an argument: -n", 2),
+        ("ls | tail --lines", "tail: unknown option: --lines", 2),
+        ("ls | wc -z", "wc: unknown option: -z", 2),
+        (
+            "ls | xargs cd",
+            "xargs: cannot run 'cd' (supported: echo, help, ls, man, which, cat, wc)",
+            2,
+        ),
+        ("ls | xargs -n 0 echo", "xargs: invalid number: 0", 2),
+        ("ls | xargs -p echo", "xargs: unknown option: -p", 2),
+    ];
+    for (input, expected, exit_code) in cases {
+        let result = execute_pipeline_with_context(
+            &parse_input(input, &[]),
+            &WalletState::Disconnected,
+            &runtime_mounts,
+            &fs,
+            &root_cwd(),
+            &ChangeSet::new(),
+            None,
+            &context,
+        );
+        let lines = output_text(&result);
+        assert_eq!(lines.len(), 1, "{input}: {lines:?}");
+        // The regex crate words its own errors; only the prefix is ours.
+        if expected.ends_with(':') {
+            assert!(lines[0].starts_with(expected), "{input}: {lines:?}");
+        } else {
+            assert_eq!(lines[0], expected, "{input}");
+        }
+        assert!(
+            matches!(result.output[0].data, OutputLineData::Error(_)),
+            "{input}"
+        );
+        assert_eq!(result.exit_code, exit_code, "{input}");
+        assert!(result.side_effects.is_empty(), "{input}");
+    }
+}
+
+#[test]
+fn test_pwd_prints_logical_and_physical_paths() {
+    use crate::domain::RuntimeBackendKind;
+
+    let runtime_mounts = [
+        crate::engine::runtime::boot::bootstrap_runtime_mount(&bootstrap_source()),
+        RuntimeMount::new(home_vpath("db"), "db", RuntimeBackendKind::GitHub, true),
+    ];
+    let pwd = |physical: bool, cwd: &VirtualPath| {
+        let result = super::execute_command_with_context(
+            Command::Pwd { physical },
+            &WalletState::Disconnected,
+            &runtime_mounts,
+            &GlobalFs::empty(),
+            cwd,
+            &ChangeSet::new(),
+            None,
+            &ExecutionContext::default(),
+        );
+        output_text(&result)
+    };
+
+    let posts = home_cwd("blog/posts");
+    assert_eq!(pwd(false, &posts), ["/blog/posts"]);
+    assert_eq!(pwd(true, &posts), ["blog/posts"]);
+
+    assert_eq!(pwd(false, &root_cwd()), ["~"]);
+    assert_eq!(pwd(true, &root_cwd()), ["."]);
+
+    let notes = home_cwd("db/notes");
+    assert_eq!(pwd(false, &notes), ["/db/notes"]);
+    assert_eq!(pwd(true, &notes), ["notes"]);
+    assert_eq!(pwd(true, &home_cwd("db")), ["."]);
+}
+
+/// The tree the pipeline tests run against: two posts and a paper.
+fn pipeline_fs() -> GlobalFs {
+    fs_with_files(&[
+        ("/blog/rust.md", blank_file_meta(NodeKind::Page)),
+        ("/blog/notes.md", blank_file_meta(NodeKind::Page)),
+        ("/papers/tabula.pdf", blank_file_meta(NodeKind::Page)),
+    ])
+}
+
+fn run_pipeline(input: &str) -> CommandResult {
+    run_pipeline_as(input, &WalletState::Disconnected)
+}
+
+fn run_pipeline_as(input: &str, wallet_state: &WalletState) -> CommandResult {
+    run_pipeline_in(input, wallet_state, &root_cwd())
+}
+
+fn run_pipeline_in(input: &str, wallet_state: &WalletState, cwd: &VirtualPath) -> CommandResult {
+    use crate::engine::shell::execute_pipeline_with_context;
+    use crate::engine::shell::parser::parse_input;
+
+    let runtime_mounts = [crate::engine::runtime::boot::bootstrap_runtime_mount(
+        &bootstrap_source(),
+    )];
+    execute_pipeline_with_context(
+        &parse_input(input, &[]),
+        wallet_state,
+        &runtime_mounts,
+        &pipeline_fs(),
+        cwd,
+        &ChangeSet::new(),
+        None,
+        &ExecutionContext {
+            access_policy: ACCESS_POLICY,
+            ..ExecutionContext::default()
+        },
+    )
+}
+
+fn run_with_manifest_sync(input: &str, sync: Option<ManifestSync>) -> CommandResult {
+    use crate::engine::shell::execute_pipeline_with_context;
+    use crate::engine::shell::parser::parse_input;
+
+    let runtime_mounts = [crate::engine::runtime::boot::bootstrap_runtime_mount(
+        &bootstrap_source(),
+    )];
+    execute_pipeline_with_context(
+        &parse_input(input, &[]),
+        &WalletState::Disconnected,
+        &runtime_mounts,
+        &pipeline_fs(),
+        &root_cwd(),
+        &ChangeSet::new(),
+        None,
+        &ExecutionContext {
+            access_policy: ACCESS_POLICY,
+            manifest_sync: sync.map(std::sync::Arc::new),
+            ..ExecutionContext::default()
+        },
+    )
+}
+
+fn scanned(files: &[(&str, &str)]) -> crate::ports::ScannedSubtree {
+    crate::ports::ScannedSubtree {
+        files: files
+            .iter()
+            .map(|(path, title)| {
+                let mut meta = blank_file_meta(NodeKind::Page);
+                meta.authored.title = Some(title.to_string());
+                crate::ports::ScannedFile {
+                    path: path.to_string(),
+                    meta,
+                    extensions: EntryExtensions::default(),
+                }
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn blog_sync() -> ManifestSync {
+    ManifestSync {
+        previous: scanned(&[
+            ("blog/rust.md", "Rust"),
+            ("blog/old.md", "Old"),
+            ("papers/tabula.pdf", "Tabula"),
+        ]),
+        current: scanned(&[
+            ("blog/rust.md", "Rust, revised"),
+            ("blog/notes.md", "Notes"),
+            ("papers/tabula.pdf", "Tabula"),
+        ]),
+    }
+}
+
+#[test]
+fn test_ls_diff_marks_changes_since_the_previous_manifest() {
+    let result = run_with_manifest_sync("ls --diff blog", Some(blog_sync()));
+    assert_eq!(result.exit_code, 0);
+    assert_eq!(
+        output_text(&result),
+        [
+            "+ notes.md  notes.md",
+            "~ rust.md  rust.md",
+            "- old.md",
+            "1 added, 1 modified, 1 removed since the previous manifest",
+        ]
+    );
+    assert!(matches!(
+        result.output[2].data,
+        OutputLineData::ListEntry {
+            format: ListFormat::Diff {
+                change: Some(crate::ports::EntryChange::Removed)
+            },
+            ..
+        }
+    ));
+
+    let result = run_with_manifest_sync("ls --diff", Some(blog_sync()));
+    assert_eq!(
+        output_text(&result),
+        [
+            "~ blog/  blog",
+            "  papers/  papers",
+            "1 modified since the previous manifest"
+        ]
+    );
+}
+
+#[test]
+fn test_ls_diff_reports_an_unchanged_directory() {
+    let result = run_with_manifest_sync("ls --diff papers", Some(blog_sync()));
+    assert_eq!(
+        output_text(&result),
+        [
+            "  tabula.pdf  tabula.pdf",
+            "no changes since the previous manifest"
+        ]
+    );
+}
+
+#[test]
+fn test_ls_diff_needs_a_manifest_sync() {
+    let result = run_with_manifest_sync("ls --diff", None);
+    assert_eq!(result.exit_code, 1);
+    assert!(output_text(&result)[0].starts_with("ls: no manifest sync"));
+
+    let result = run_with_manifest_sync("ls --diff nowhere", Some(blog_sync()));
+    assert_ne!(result.exit_code, 0);
+    let result = run_with_manifest_sync("ls --diff blog/rust.md", Some(blog_sync()));
+    assert_ne!(result.exit_code, 0);
+}
+
+/// Run `input` as the terminal does, with each `$(...)` run through the
+/// same pipeline executor.
+fn run_with_substitution(input: &str) -> CommandResult {
+    use crate::engine::shell::parser::parse_input_with_substitution;
+    use crate::engine::shell::substitution_text;
+
+    let run = |pipeline: &crate::engine::shell::parser::Pipeline| {
+        let runtime_mounts = [crate::engine::runtime::boot::bootstrap_runtime_mount(
+            &bootstrap_source(),
+        )];
+        crate::engine::shell::execute_pipeline_with_context(
+            pipeline,
+            &WalletState::Disconnected,
+            &runtime_mounts,
+            &pipeline_fs(),
+            &root_cwd(),
+            &ChangeSet::new(),
+            None,
+            &ExecutionContext {
+                access_policy: ACCESS_POLICY,
+                ..ExecutionContext::default()
+            },
+        )
+    };
+    let pipeline = parse_input_with_substitution(
+        input,
+        &[],
+        &std::collections::BTreeMap::new(),
+        &mut |inner| substitution_text(&run(inner).output),
+    );
+    run(&pipeline)
+}
+
+#[test]
+fn test_substitution_inserts_command_output() {
+    let pwd = output_text(&run_pipeline("pwd"));
+    assert_eq!(output_text(&run_with_substitution("echo $(pwd)")), pwd);
+    assert_eq!(
+        output_text(&run_with_substitution("echo in $(ls | grep p)")),
+        ["in papers"]
+    );
+    assert_eq!(
+        output_text(&run_with_substitution("basename $(ls blog | head -1) .md")),
+        ["notes"]
+    );
+}
+
+#[test]
+fn test_substitution_splits_multi_line_output_into_words() {
+    let result = run_with_substitution("echo $(ls blog) end");
+    assert_eq!(output_text(&result), ["notes.md rust.md end"]);
+
+    let result = run_with_substitution("echo $(ls blog | wc -l)");
+    assert_eq!(output_text(&result), ["2"]);
+}
+
+#[test]
+fn test_substitution_nests_and_leaves_errors_out() {
+    let result = run_with_substitution("echo $(echo $(echo deep))");
+    assert_eq!(output_text(&result), ["deep"]);
+
+    let result = run_with_substitution("echo x $(cat missing.md)");
+    assert_eq!(output_text(&result), ["x"]);
+}
+
+#[test]
+fn test_substitution_joins_the_text_around_it() {
+    assert_eq!(
+        output_text(&run_with_substitution("ls $(echo blo)g/$(echo rust).md")),
+        ["blog/rust.md  rust.md"]
+    );
+    assert_eq!(
+        output_text(&run_with_substitution("echo /$(ls blog)/")),
+        ["/notes.md rust.md/"]
+    );
+}
+
+#[test]
+fn test_substitution_drops_inner_side_effects() {
+    let result = run_with_substitution("echo $(cd blog)");
+    assert!(result.side_effects.is_empty());
+    assert_eq!(result.exit_code, 0);
+}
+
+#[test]
+fn test_xargs_runs_echo_once_per_line_by_default() {
+    let result = run_pipeline("ls | xargs echo dir:");
+    assert_eq!(output_text(&result), ["dir: blog", "dir: papers"]);
+    assert_eq!(result.exit_code, 0);
+
+    // With no command, xargs echoes its input.
+    assert_eq!(output_text(&run_pipeline("ls | xargs")), ["blog", "papers"]);
+}
+
+#[test]
+fn test_xargs_batches_lines_with_n() {
+    let result = run_pipeline("ls blog | xargs -n 2 echo posts:");
+    assert_eq!(output_text(&result), ["posts: notes.md rust.md"]);
+
+    let result = run_pipeline("ls | xargs -n 1 echo | head -1");
+    assert_eq!(output_text(&result), ["blog"]);
+}
+
+#[test]
+fn test_xargs_lists_each_piped_directory() {
+    let result = run_pipeline("ls | grep p | xargs ls");
+    let names: Vec<_> = result
+        .output
+        .iter()
+        .filter_map(OutputLine::highlight_text)
+        .collect();
+    assert_eq!(names, ["tabula.pdf"]);
+    assert!(result.side_effects.is_empty());
+}
+
+#[test]
+fn test_xargs_keeps_going_after_a_failed_run() {
+    let result = run_pipeline("ls blog | xargs ls");
+    assert_eq!(
+        output_text(&result),
+        [
+            "ls: cannot access 'notes.md': No such file or directory",
+            "ls: cannot access 'rust.md': No such file or directory",
+        ]
+    );
+    assert_eq!(result.exit_code, 123);
+}
+
+/// The files an `xargs cat` / `xargs wc` stage left for the target.
+fn file_stage(result: &CommandResult) -> &FileStage {
+    let [SideEffect::ReadFiles(stage)] = result.side_effects.as_slice() else {
+        panic!("expected a file stage, got {:?}", result.side_effects);
+    };
+    stage
+}
+
+fn stage_file(path: &str, display: &str) -> StageFile {
+    StageFile {
+        path: VirtualPath::from_absolute(path).unwrap(),
+        display: display.to_string(),
+    }
+}
+
+#[test]
+fn test_xargs_wc_reads_the_piped_files() {
+    let result = run_pipeline_in(
+        "ls | grep .md | xargs -n 2 wc -l | head -2",
+        &WalletState::Disconnected,
+        &home_cwd("blog"),
+    );
+    assert!(result.output.is_empty());
+    assert_eq!(result.exit_code, 0);
+    let stage = file_stage(&result);
+    assert_eq!(stage.command.name, "wc");
+    assert_eq!(stage.command.args, ["-l"]);
+    assert_eq!(
+        stage.files,
+        [
+            stage_file("/blog/notes.md", "notes.md"),
+            stage_file("/blog/rust.md", "rust.md"),
+        ]
+    );
+    assert_eq!(stage.per_run, 2);
+    assert_eq!(stage.filters.len(), 1);
+    assert_eq!(stage.filters[0].name, "head");
+}
+
+#[test]
+fn test_xargs_cat_reports_arguments_that_are_not_files() {
+    let result = run_pipeline("ls | grep p | xargs cat");
+    assert_eq!(output_text(&result), ["cat: papers: Is a directory"]);
+    assert_eq!(result.exit_code, 123);
+    assert!(result.side_effects.is_empty());
+
+    // Names are resolved against the cwd, so a bare `ls blog` misses them.
+    let result = run_pipeline("ls blog | xargs cat");
+    assert_eq!(
+        output_text(&result),
+        [
+            "cat: notes.md: No such file or directory",
+            "cat: rust.md: No such file or directory",
+        ]
+    );
+    assert_eq!(result.exit_code, 123);
+
+    let result = run_pipeline("ls | xargs cat -n");
+    assert_eq!(output_text(&result), ["cat: unknown option: -n"]);
+    assert!(result.side_effects.is_empty());
+    let result = run_pipeline("ls | xargs wc -z");
+    assert_eq!(output_text(&result), ["wc: unknown option: -z"]);
+    assert!(result.side_effects.is_empty());
+}
+
+#[test]
+fn test_file_stage_output_runs_the_command_then_the_filters() {
+    use crate::engine::shell::file_stage_output;
+    use crate::engine::shell::parser::ParsedCommand;
+    use crate::support::i18n::Locale;
+
+    let command = |name: &str, args: &[&str]| ParsedCommand {
+        name: name.to_string(),
+        args: args.iter().map(|arg| arg.to_string()).collect(),
+    };
+    let texts = || {
+        vec![
+            Ok("one two\nthree".to_string()),
+            Err("offline".to_string()),
+            Ok("four".to_string()),
+        ]
+    };
+    let mut stage = FileStage {
+        command: command("cat", &[]),
+        files: vec![
+            stage_file("/a.md", "a.md"),
+            stage_file("/b.md", "b.md"),
+            stage_file("/c.md", "c.md"),
+        ],
+        per_run: 1,
+        filters: vec![command("grep", &["o"])],
+    };
+    let result = file_stage_output(&stage, texts(), Locale::En);
+    assert_eq!(
+        output_text(&result),
+        ["cat: b.md: offline", "one two", "four"]
+    );
+    assert_eq!(result.exit_code, 0);
+
+    stage.command = command("wc", &["-w"]);
+    stage.per_run = 3;
+    stage.filters.clear();
+    let result = file_stage_output(&stage, texts(), Locale::En);
+    assert_eq!(
+        output_text(&result),
+        ["wc: b.md: offline", "3 a.md", "1 c.md", "4 total"]
+    );
+    assert_eq!(result.exit_code, 123);
+}
+
+#[test]
+fn test_tee_passes_lines_through_and_writes_their_text() {
+    let plain = run_pipeline_as("ls blog | head -n 100", &admin_wallet());
+    let teed = run_pipeline_as("ls blog | tee ~/blog/list.txt", &admin_wallet());
+    let data = |result: &CommandResult| {
+        result
+            .output
+            .iter()
+            .map(|line| line.data.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(data(&teed), data(&plain));
+    assert_eq!(teed.exit_code, 0);
+    assert_eq!(
+        teed.side_effects,
+        vec![SideEffect::ApplyChange {
+            path: home_vpath("blog/list.txt"),
+            change: Box::new(ChangeType::CreateFile {
+                content: crate::engine::shell::lines_to_plain_text(&plain.output),
+                meta: blank_file_meta(NodeKind::Asset),
+                extensions: EntryExtensions::default(),
+            }),
+        }]
+    );
+}
+
+#[test]
+fn test_tee_mid_pipeline_keeps_the_stream_and_the_write() {
+    let result = run_pipeline_as("ls | grep p | tee found.txt | wc -l", &admin_wallet());
+    assert_eq!(output_text(&result), ["1"]);
+    let [SideEffect::ApplyChange { path, change }] = result.side_effects.as_slice() else {
+        panic!("expected one write, got {:?}", result.side_effects);
+    };
+    assert_eq!(path, &home_vpath("found.txt"));
+    let ChangeType::CreateFile { content, .. } = change.as_ref() else {
+        panic!("expected a new file, got {change:?}");
+    };
+    assert!(content.starts_with("papers/"), "{content:?}");
+    assert_eq!(content.lines().count(), 1);
+}
+
+#[test]
+fn test_tee_replaces_or_appends_to_existing_files() {
+    let replaced = run_pipeline_as("ls | tee blog/rust.md", &admin_wallet());
+    assert!(matches!(
+        replaced.side_effects.as_slice(),
+        [SideEffect::ApplyChange { change, .. }]
+            if matches!(change.as_ref(), ChangeType::UpdateFile { meta: None, .. })
+    ));
+
+    let appended = run_pipeline_as("ls | tee -a blog/rust.md", &admin_wallet());
+    assert_eq!(
+        appended.side_effects,
+        vec![SideEffect::AppendFile {
+            path: home_vpath("blog/rust.md"),
+            text: crate::engine::shell::lines_to_plain_text(&appended.output),
+        }]
+    );
+
+    // Appending to a file that does not exist yet creates it.
+    let created = run_pipeline_as("ls | tee --append new.txt", &admin_wallet());
+    assert!(matches!(
+        created.side_effects.as_slice(),
+        [SideEffect::ApplyChange { change, .. }]
+            if matches!(change.as_ref(), ChangeType::CreateFile { .. })
+    ));
+}
+
+#[test]
+fn test_tee_failures_replace_the_stream() {
+    let cases = [
+        (
+            "ls | tee out.txt",
+            WalletState::Disconnected,
+            "tee: permission denied (admin login required)",
+        ),
+        ("ls | tee", admin_wallet(), "tee: missing file operand"),
+        ("ls | tee a b", admin_wallet(), "tee: extra operand 'b'"),
+        ("ls | tee -x a", admin_wallet(), "tee: unknown option: -x"),
+        ("ls | tee blog", admin_wallet(), "tee: blog: is a directory"),
+        (
+            "ls | tee nope/out.txt",
+            admin_wallet(),
+            "tee: nope/out.txt: parent directory does not exist",
+        ),
+    ];
+    for (input, wallet, expected) in cases {
+        let result = run_pipeline_as(input, &wallet);
+        assert_eq!(output_text(&result), [expected], "{input}");
+        assert_ne!(result.exit_code, 0, "{input}");
+        assert!(result.side_effects.is_empty(), "{input}");
+    }
+}
+
+fn logged_events() -> Vec<crate::engine::shell::AppEvent> {
+    use crate::engine::shell::{AppEvent, AppEventKind};
+
+    vec![
+        AppEvent::new(
+            1_200,
+            AppEventKind::MountLoaded {
+                root: "/".to_string(),
+                files: 3,
+            },
+        ),
+        AppEvent::new(
+            4_050,
+            AppEventKind::WalletConnected {
+                address: "0xabc".to_string(),
+                chain_id: Some(1),
+            },
+        ),
+        AppEvent::new(
+            9_000,
+            AppEventKind::FetchFailed {
+                target: "/blog/rust.md".to_string(),
+                error: "HTTP 404".to_string(),
+            },
+        ),
+        AppEvent::new(61_500, AppEventKind::WalletDisconnected),
+    ]
+}
+
+fn run_dmesg(input: &str) -> CommandResult {
+    use crate::engine::shell::execute_pipeline_with_context;
+    use crate::engine::shell::parser::parse_input;
+
+    let (ws, fs) = empty_state();
+    execute_pipeline_with_context(
+        &parse_input(input, &[]),
+        &ws,
+        &[],
+        &fs,
+        &root_cwd(),
+        &ChangeSet::new(),
+        None,
+        &ExecutionContext {
+            events: logged_events(),
+            ..ExecutionContext::default()
+        },
+    )
+}
+
+#[test]
+fn test_dmesg_prints_events_oldest_first() {
+    let result = run_dmesg("dmesg");
+    assert_eq!(
+        output_text(&result),
+        [
+            "[       1.200] mount: / loaded (3 files)",
+            "[       4.050] wallet: connected 0xabc on chain 1 (Ethereum)",
+            "[       9.000] fetch: /blog/rust.md: HTTP 404",
+            "[      61.500] wallet: disconnected",
+        ]
+    );
+
+    let (ws, fs) = empty_state();
+    let empty = execute_command(
+        Command::Dmesg { category: None },
+        &ws,
+        &fs,
+        &root_cwd(),
+        &ChangeSet::new(),
+        None,
+    );
+    assert!(output_text(&empty).is_empty());
+}
+
+#[test]
+fn test_dmesg_filters_by_category() {
+    let result = run_dmesg("dmesg -f wallet");
+    assert_eq!(
+        output_text(&result),
+        [
+            "[       4.050] wallet: connected 0xabc on chain 1 (Ethereum)",
+            "[      61.500] wallet: disconnected",
+        ]
+    );
+}
+
+#[test]
+fn test_dmesg_output_feeds_pipe_filters() {
+    let result = run_dmesg("dmesg | grep -F 404");
+    assert_eq!(result.exit_code, 0);
+    assert_eq!(
+        output_text(&result),
+        ["[       9.000] fetch: /blog/rust.md: HTTP 404"]
+    );
+}
+
+#[test]
+fn test_dmesg_rejects_unknown_category() {
+    let result = run_dmesg("dmesg -f disk");
+    assert_eq!(
+        output_text(&result),
+        ["dmesg: unknown category 'disk' (expected wallet, mount, manifest, fetch)"]
+    );
+    assert_eq!(result.exit_code, 2);
+}
+
+fn run_stats(input: &str, usage: UsageReport, env: &[(&str, &str)]) -> CommandResult {
+    use crate::engine::shell::execute_pipeline_with_context;
+    use crate::engine::shell::parser::parse_input;
+
+    let (ws, fs) = empty_state();
+    execute_pipeline_with_context(
+        &parse_input(input, &[]),
+        &ws,
+        &[],
+        &fs,
+        &root_cwd(),
+        &ChangeSet::new(),
+        None,
+        &ExecutionContext {
+            env: env
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            usage,
+            ..ExecutionContext::default()
+        },
+    )
+}
+
+fn recorded_usage() -> UsageReport {
+    let mut all_time = UsageStats::default();
+    for (name, count) in [("ls", 8), ("cd", 4), ("cat", 1)] {
+        for _ in 0..count {
+            all_time.record_command(name);
+        }
+    }
+    all_time.record_file("/blog/rust.md");
+    all_time.record_file("/blog/rust.md");
+    all_time.record_dir("/blog");
+    all_time.seconds = 3 * 3600 + 7 * 60;
+    let mut session = UsageStats::default();
+    session.record_command("ls");
+    session.record_command("stats");
+    session.seconds = 312;
+    UsageReport { all_time, session }
+}
+
+#[test]
+fn test_stats_ranks_commands_with_bars_and_totals() {
+    let result = run_stats("stats", recorded_usage(), &[]);
+    let bar = |blocks: usize| "█".repeat(blocks);
+    assert_eq!(
+        output_text(&result),
+        [
+            "Top commands".to_string(),
+            format!("  ls   8  {}", bar(24)),
+            format!("  cd   4  {}", bar(12)),
+            format!("  cat  1  {}", bar(3)),
+            String::new(),
+            "Top files".to_string(),
+            "  /blog/rust.md  2".to_string(),
+            String::new(),
+            "Top directories".to_string(),
+            "  /blog  1".to_string(),
+            String::new(),
+            "Commands: 2 this session, 13 all time".to_string(),
+            "Time: 5m 12s this session, 3h 07m all time".to_string(),
+        ]
+    );
+    assert!(result.side_effects.is_empty());
+}
+
+#[test]
+fn test_stats_without_activity_and_with_tracking_off() {
+    let empty = run_stats("stats", UsageReport::default(), &[]);
+    assert_eq!(output_text(&empty), ["No activity recorded yet."]);
+
+    let off = run_stats("stats", recorded_usage(), &[("TRACKING", "off")]);
+    assert_eq!(
+        output_text(&off)[0],
+        "Tracking is off; unset TRACKING to count again."
+    );
+    assert_eq!(output_text(&off)[1], "Top commands");
+}
+
+#[test]
+fn test_stats_reset_requests_clearing_the_counters() {
+    let result = run_stats("stats --reset", recorded_usage(), &[]);
+    assert_eq!(output_text(&result), ["stats: counters cleared"]);
+    assert_eq!(result.side_effects, vec![SideEffect::ResetUsageStats]);
+}
+
+#[test]
+fn test_basename_and_dirname_work_on_paths_that_do_not_exist() {
+    let result = run_pipeline("basename /no/such/post.md .md");
+    assert_eq!(result.exit_code, 0);
+    assert_eq!(output_text(&result), ["post"]);
+
+    let result = run_pipeline("dirname /no/such/post.md");
+    assert_eq!(output_text(&result), ["/no/such"]);
+
+    let result = run_pipeline("basename");
+    assert_eq!(result.exit_code, 1);
+    assert_eq!(output_text(&result), ["basename: missing operand"]);
 }
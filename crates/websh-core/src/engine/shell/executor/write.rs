@@ -1,11 +1,14 @@
 use crate::domain::{
-    ChangeSet, ChangeType, EntryExtensions, Fields, NodeKind, NodeMetadata, RuntimeMount,
+    ChangeSet, ChangeType, EntryExtensions, Fields, FsEntry, NodeKind, NodeMetadata, RuntimeMount,
     SCHEMA_VERSION, VirtualPath, WalletState,
 };
 use crate::engine::filesystem::GlobalFs;
-use crate::engine::shell::{AccessPolicy, CommandResult, PathArg, SideEffect};
+use crate::engine::shell::{
+    AccessPolicy, CommandResult, EntryCopy, EntryCopyKind, EntryRemoval, OutputLine, PathArg,
+    SideEffect, lines_to_plain_text,
+};
 
-use super::{require_write_access, resolve_path_arg};
+use super::{CommandError, require_write_access, resolve_path_arg};
 
 pub(super) struct WriteCommandContext<'a> {
     pub(super) wallet_state: &'a WalletState,
@@ -34,12 +37,11 @@ pub(super) fn blank_dir_meta() -> NodeMetadata {
     }
 }
 
-#[allow(clippy::result_large_err)]
 fn resolve_abs_path(
     cmd_label: &str,
     path: &PathArg,
     cwd: &VirtualPath,
-) -> Result<VirtualPath, CommandResult> {
+) -> Result<VirtualPath, CommandError> {
     resolve_path_arg(cmd_label, path.as_str(), cwd)
 }
 
@@ -54,12 +56,12 @@ pub(super) fn execute_touch(
 ) -> CommandResult {
     let vp = match resolve_abs_path("touch", &path, cwd) {
         Ok(v) => v,
-        Err(e) => return e,
+        Err(e) => return e.into(),
     };
 
     if let Err(e) = require_write_access("touch", wallet_state, access_policy, runtime_mounts, &vp)
     {
-        return e;
+        return e.into();
     }
 
     if fs.exists(&vp) {
@@ -95,12 +97,12 @@ pub(super) fn execute_mkdir(
 ) -> CommandResult {
     let vp = match resolve_abs_path("mkdir", &path, cwd) {
         Ok(v) => v,
-        Err(e) => return e,
+        Err(e) => return e.into(),
     };
 
     if let Err(e) = require_write_access("mkdir", wallet_state, access_policy, runtime_mounts, &vp)
     {
-        return e;
+        return e.into();
     }
 
     if fs.exists(&vp) {
@@ -131,7 +133,7 @@ pub(super) fn execute_rm(
 ) -> CommandResult {
     let vp = match resolve_abs_path("rm", &path, ctx.cwd) {
         Ok(v) => v,
-        Err(e) => return e,
+        Err(e) => return e.into(),
     };
 
     if let Err(e) = require_write_access(
@@ -141,7 +143,7 @@ pub(super) fn execute_rm(
         ctx.runtime_mounts,
         &vp,
     ) {
-        return e;
+        return e.into();
     }
 
     let Some(entry) = ctx.fs.get_entry(&vp) else {
@@ -149,14 +151,17 @@ pub(super) fn execute_rm(
     };
 
     if entry.is_directory() && !recursive {
-        return CommandResult::error_line(format!("rm: {}: is a directory (use -r)", path));
+        return CommandResult::error_line(format!(
+            "rm: cannot remove '{}': Is a directory (use -r)",
+            path
+        ));
     }
 
     if entry.is_directory() && is_runtime_mount_root(ctx.runtime_mounts, &vp) {
         return CommandResult::error_line(format!("rm: {}: cannot remove mount root", path));
     }
 
-    if is_pending_create(ctx.changes, &vp) {
+    if ctx.changes.is_pending_create(&vp) {
         return CommandResult {
             output: vec![],
             exit_code: 0,
@@ -192,12 +197,12 @@ pub(super) fn execute_rmdir(
 ) -> CommandResult {
     let vp = match resolve_abs_path("rmdir", &path, cwd) {
         Ok(v) => v,
-        Err(e) => return e,
+        Err(e) => return e.into(),
     };
 
     if let Err(e) = require_write_access("rmdir", wallet_state, access_policy, runtime_mounts, &vp)
     {
-        return e;
+        return e.into();
     }
 
     let Some(entry) = fs.get_entry(&vp) else {
@@ -216,7 +221,7 @@ pub(super) fn execute_rmdir(
         return CommandResult::error_line(format!("rmdir: {}: directory not empty", path));
     }
 
-    if is_pending_create(changes, &vp) {
+    if changes.is_pending_create(&vp) {
         return CommandResult {
             output: vec![],
             exit_code: 0,
@@ -234,19 +239,151 @@ pub(super) fn execute_rmdir(
     }
 }
 
-fn is_runtime_mount_root(runtime_mounts: &[RuntimeMount], path: &VirtualPath) -> bool {
-    runtime_mounts.iter().any(|mount| mount.root == *path)
+/// Execute `mv` (`remove_source`) or `cp` — copy `src` to `dst`.
+///
+/// When `dst` is an existing directory the source lands inside it. File
+/// content is read by the target, so the result is a single
+/// `TransferEntries` side effect listing every entry to create.
+pub(super) fn execute_transfer(
+    src: PathArg,
+    dst: PathArg,
+    remove_source: bool,
+    ctx: WriteCommandContext<'_>,
+) -> CommandResult {
+    let label = if remove_source { "mv" } else { "cp" };
+    let src_vp = match resolve_abs_path(label, &src, ctx.cwd) {
+        Ok(v) => v,
+        Err(e) => return e.into(),
+    };
+    let dst_vp = match resolve_abs_path(label, &dst, ctx.cwd) {
+        Ok(v) => v,
+        Err(e) => return e.into(),
+    };
+
+    let Some(src_entry) = ctx.fs.get_entry(&src_vp) else {
+        return CommandResult::error_line(format!(
+            "{label}: cannot stat '{src}': No such file or directory"
+        ));
+    };
+    let src_is_dir = src_entry.is_directory();
+
+    if remove_source && (src_vp.is_root() || is_runtime_mount_root(ctx.runtime_mounts, &src_vp)) {
+        return CommandResult::error_line(format!("{label}: {src}: cannot move mount root"));
+    }
+
+    let target = match (ctx.fs.get_entry(&dst_vp), src_vp.file_name()) {
+        (Some(entry), Some(name)) if entry.is_directory() => dst_vp.join(name),
+        _ => dst_vp,
+    };
+
+    for path in std::iter::once(&target).chain(remove_source.then_some(&src_vp)) {
+        if let Err(e) = require_write_access(
+            label,
+            ctx.wallet_state,
+            ctx.access_policy,
+            ctx.runtime_mounts,
+            path,
+        ) {
+            return e.into();
+        }
+    }
+
+    if target == src_vp {
+        return CommandResult::error_line(format!(
+            "{label}: '{src}' and '{dst}' are the same file"
+        ));
+    }
+    if src_is_dir && target.starts_with(&src_vp) {
+        return CommandResult::error_line(format!(
+            "{label}: cannot copy '{src}' into a subdirectory of itself"
+        ));
+    }
+
+    let overwrite = match ctx.fs.get_entry(&target) {
+        None => false,
+        Some(existing) => match (src_is_dir, existing.is_directory()) {
+            (false, false) => true,
+            (false, true) => {
+                return CommandResult::error_line(format!(
+                    "{label}: cannot overwrite directory '{}' with non-directory",
+                    target.as_str()
+                ));
+            }
+            (true, false) => {
+                return CommandResult::error_line(format!(
+                    "{label}: cannot overwrite non-directory '{}' with directory '{src}'",
+                    target.as_str()
+                ));
+            }
+            (true, true) => {
+                return CommandResult::error_line(format!(
+                    "{label}: '{}': directory already exists",
+                    target.as_str()
+                ));
+            }
+        },
+    };
+
+    if let Err(e) = require_parent_directory(label, &dst, ctx.fs, &target) {
+        return e;
+    }
+
+    let mut copies = Vec::new();
+    collect_entry_copies(src_entry, &src_vp, &target, overwrite, &mut copies);
+
+    CommandResult::empty().with_side_effect(SideEffect::TransferEntries {
+        copies,
+        remove_source: remove_source.then_some(EntryRemoval {
+            path: src_vp,
+            is_directory: src_is_dir,
+        }),
+    })
+}
+
+/// Flatten `entry` into copies, parents before children. Children are
+/// visited in name order so the plan is deterministic.
+fn collect_entry_copies(
+    entry: &FsEntry,
+    from: &VirtualPath,
+    to: &VirtualPath,
+    overwrite: bool,
+    out: &mut Vec<EntryCopy>,
+) {
+    match entry {
+        FsEntry::File {
+            meta, extensions, ..
+        } => out.push(EntryCopy {
+            from: from.clone(),
+            to: to.clone(),
+            kind: EntryCopyKind::File {
+                meta: meta.clone(),
+                extensions: extensions.clone(),
+                overwrite,
+            },
+        }),
+        FsEntry::Directory { children, meta } => {
+            out.push(EntryCopy {
+                from: from.clone(),
+                to: to.clone(),
+                kind: EntryCopyKind::Directory { meta: meta.clone() },
+            });
+            let mut names: Vec<&String> = children.keys().collect();
+            names.sort();
+            for name in names {
+                collect_entry_copies(
+                    &children[name],
+                    &from.join(name),
+                    &to.join(name),
+                    false,
+                    out,
+                );
+            }
+        }
+    }
 }
 
-fn is_pending_create(changes: &ChangeSet, path: &VirtualPath) -> bool {
-    matches!(
-        changes.get(path).map(|e| &e.change),
-        Some(
-            ChangeType::CreateFile { .. }
-                | ChangeType::CreateBinary { .. }
-                | ChangeType::CreateDirectory { .. }
-        )
-    )
+fn is_runtime_mount_root(runtime_mounts: &[RuntimeMount], path: &VirtualPath) -> bool {
+    runtime_mounts.iter().any(|mount| mount.root == *path)
 }
 
 /// Execute `edit` — request the editor UI open for a file.
@@ -260,11 +397,11 @@ pub(super) fn execute_edit(
 ) -> CommandResult {
     let vp = match resolve_abs_path("edit", &path, cwd) {
         Ok(v) => v,
-        Err(e) => return e,
+        Err(e) => return e.into(),
     };
 
     if let Err(e) = require_write_access("edit", wallet_state, access_policy, runtime_mounts, &vp) {
-        return e;
+        return e.into();
     }
 
     if let Some(entry) = fs.get_entry(&vp)
@@ -298,11 +435,11 @@ pub(super) fn execute_echo_redirect(
 ) -> CommandResult {
     let vp = match resolve_abs_path("echo", &path, cwd) {
         Ok(v) => v,
-        Err(e) => return e,
+        Err(e) => return e.into(),
     };
 
     if let Err(e) = require_write_access("echo", wallet_state, access_policy, runtime_mounts, &vp) {
-        return e;
+        return e.into();
     }
 
     let change = match fs.get_entry(&vp) {
@@ -336,6 +473,81 @@ pub(super) fn execute_echo_redirect(
     }
 }
 
+/// Execute the `tee [-a] <file>` pipe stage: pass `lines` through unchanged
+/// and write their rendered text to `file`, replacing it or, with `-a`,
+/// appending to it. Writing follows the rules of `echo > file`.
+pub(super) fn execute_tee(
+    args: &[String],
+    lines: Vec<OutputLine>,
+    ctx: WriteCommandContext<'_>,
+) -> CommandResult {
+    let mut append = false;
+    let mut file = None;
+    for arg in args {
+        match arg.as_str() {
+            "-a" | "--append" => append = true,
+            flag if flag.starts_with('-') && flag.len() > 1 => {
+                return CommandError::usage("tee", format!("unknown option: {flag}")).into();
+            }
+            _ if file.is_none() => file = Some(PathArg::new(arg)),
+            extra => {
+                return CommandError::usage("tee", format!("extra operand '{extra}'")).into();
+            }
+        }
+    }
+    let Some(path) = file else {
+        return CommandError::MissingOperand {
+            cmd: "tee".to_string(),
+            operand: "file operand",
+        }
+        .into();
+    };
+
+    let vp = match resolve_abs_path("tee", &path, ctx.cwd) {
+        Ok(v) => v,
+        Err(e) => return e.into(),
+    };
+    if let Err(e) = require_write_access(
+        "tee",
+        ctx.wallet_state,
+        ctx.access_policy,
+        ctx.runtime_mounts,
+        &vp,
+    ) {
+        return e.into();
+    }
+
+    let text = lines_to_plain_text(&lines);
+    let effect = match ctx.fs.get_entry(&vp) {
+        Some(entry) if entry.is_directory() => {
+            return CommandResult::error_line(format!("tee: {}: is a directory", path));
+        }
+        Some(_) if append => SideEffect::AppendFile { path: vp, text },
+        Some(_) => SideEffect::ApplyChange {
+            path: vp,
+            change: Box::new(ChangeType::UpdateFile {
+                content: text,
+                meta: None,
+                extensions: None,
+            }),
+        },
+        None => {
+            if let Err(e) = require_parent_directory("tee", &path, ctx.fs, &vp) {
+                return e;
+            }
+            SideEffect::ApplyChange {
+                path: vp,
+                change: Box::new(ChangeType::CreateFile {
+                    content: text,
+                    meta: blank_file_meta(NodeKind::Asset),
+                    extensions: EntryExtensions::default(),
+                }),
+            }
+        }
+    };
+    CommandResult::output(lines).with_side_effect(effect)
+}
+
 #[allow(clippy::result_large_err)]
 fn require_parent_directory(
     cmd_label: &str,
@@ -0,0 +1,205 @@
+//! Terminal output for `fetch` responses.
+//!
+//! The target performs the request; this module turns the response into
+//! output lines so the formatting rules stay testable without a network.
+
+use super::OutputLine;
+
+/// Most body bytes printed for one response; the rest is cut with a note.
+pub const FETCH_BODY_LIMIT: usize = 64 * 1024;
+
+/// Headers printed under the status line, in this order.
+const SHOWN_HEADERS: &[&str] = &[
+    "content-type",
+    "content-length",
+    "last-modified",
+    "etag",
+    "cache-control",
+];
+
+/// A completed HTTP response, as the target received it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FetchResponse {
+    pub status: u16,
+    pub status_text: String,
+    /// Header names are lowercase.
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl FetchResponse {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn is_json(&self) -> bool {
+        self.header("content-type").is_some_and(|value| {
+            let media_type = value.split(';').next().unwrap_or_default().trim();
+            media_type.eq_ignore_ascii_case("application/json") || media_type.ends_with("+json")
+        })
+    }
+}
+
+/// Output of one response. A piped `fetch` sends only `body` through the
+/// filters; `head` and `truncated` are printed as they are.
+#[derive(Clone, Debug)]
+pub struct FetchLines {
+    /// Status line and the headers in [`SHOWN_HEADERS`].
+    pub head: Vec<OutputLine>,
+    /// Body text, pretty-printed when it is JSON, one line per line.
+    pub body: Vec<OutputLine>,
+    /// Note printed after a body cut at [`FETCH_BODY_LIMIT`].
+    pub truncated: Option<OutputLine>,
+}
+
+pub fn fetch_response_lines(response: &FetchResponse) -> FetchLines {
+    let status = format!("HTTP {} {}", response.status, response.status_text);
+    let status = status.trim_end();
+    let mut head = vec![if (200..300).contains(&response.status) {
+        OutputLine::success(status)
+    } else {
+        OutputLine::error(status)
+    }];
+    head.extend(SHOWN_HEADERS.iter().filter_map(|name| {
+        response
+            .header(name)
+            .map(|value| OutputLine::info(format!("{name}: {value}")))
+    }));
+
+    let body = if response.is_json() {
+        pretty_json(&response.body).unwrap_or_else(|| response.body.clone())
+    } else {
+        response.body.clone()
+    };
+    let (shown, cut) = truncate_body(&body, FETCH_BODY_LIMIT);
+    let truncated = cut.then(|| {
+        OutputLine::info(format!(
+            "fetch: body truncated to {} of {} bytes",
+            shown.len(),
+            body.len()
+        ))
+    });
+
+    FetchLines {
+        head,
+        body: shown.lines().map(OutputLine::text).collect(),
+        truncated,
+    }
+}
+
+/// `text` re-indented when it parses as JSON.
+fn pretty_json(text: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(text).ok()?;
+    serde_json::to_string_pretty(&value).ok()
+}
+
+/// At most `limit` bytes of `text`, ending at a line break when one lies in
+/// the second half, and whether anything was cut.
+fn truncate_body(text: &str, limit: usize) -> (&str, bool) {
+    if text.len() <= limit {
+        return (text, false);
+    }
+    let mut end = limit;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let end = text[..end]
+        .rfind('\n')
+        .filter(|&at| at >= limit / 2)
+        .unwrap_or(end);
+    (&text[..end], true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::shell::OutputLineData;
+
+    fn response(content_type: &str, body: &str) -> FetchResponse {
+        FetchResponse {
+            status: 200,
+            status_text: "OK".to_string(),
+            headers: vec![
+                ("etag".to_string(), "\"abc\"".to_string()),
+                ("content-type".to_string(), content_type.to_string()),
+                ("x-request-id".to_string(), "42".to_string()),
+            ],
+            body: body.to_string(),
+        }
+    }
+
+    fn texts(lines: &[OutputLine]) -> Vec<String> {
+        lines.iter().map(OutputLine::to_plain_text).collect()
+    }
+
+    #[test]
+    fn head_is_the_status_then_selected_headers_in_order() {
+        let lines = fetch_response_lines(&response("text/plain", "hi"));
+        assert_eq!(
+            texts(&lines.head),
+            ["HTTP 200 OK", "content-type: text/plain", "etag: \"abc\""]
+        );
+        assert!(matches!(lines.head[0].data, OutputLineData::Success(_)));
+    }
+
+    #[test]
+    fn error_statuses_are_shown_as_errors() {
+        let lines = fetch_response_lines(&FetchResponse {
+            status: 404,
+            ..FetchResponse::default()
+        });
+        assert_eq!(texts(&lines.head), ["HTTP 404"]);
+        assert!(matches!(lines.head[0].data, OutputLineData::Error(_)));
+    }
+
+    #[test]
+    fn json_bodies_are_pretty_printed() {
+        let lines = fetch_response_lines(&response(
+            "application/json; charset=utf-8",
+            r#"{"name":"websh","version":2}"#,
+        ));
+        assert_eq!(
+            texts(&lines.body),
+            ["{", "  \"name\": \"websh\",", "  \"version\": 2", "}"]
+        );
+        assert!(lines.truncated.is_none());
+    }
+
+    #[test]
+    fn vendor_json_types_count_and_invalid_json_is_kept() {
+        let vendor = fetch_response_lines(&response("application/vnd.github+json", "[1]"));
+        assert_eq!(texts(&vendor.body), ["[", "  1", "]"]);
+        let broken = fetch_response_lines(&response("application/json", "{nope"));
+        assert_eq!(texts(&broken.body), ["{nope"]);
+        let plain = fetch_response_lines(&response("text/plain", "[1]"));
+        assert_eq!(texts(&plain.body), ["[1]"]);
+    }
+
+    #[test]
+    fn long_bodies_are_cut_at_a_line_with_a_note() {
+        let body = "line\n".repeat(FETCH_BODY_LIMIT / 5 + 10);
+        let lines = fetch_response_lines(&response("text/plain", &body));
+        let shown: usize = lines
+            .body
+            .iter()
+            .map(|line| line.to_plain_text().len() + 1)
+            .sum();
+        assert!(shown <= FETCH_BODY_LIMIT);
+        assert!(lines.body.iter().all(|line| line.to_plain_text() == "line"));
+        let note = lines.truncated.expect("truncation note").to_plain_text();
+        assert!(note.starts_with("fetch: body truncated to "), "{note}");
+        assert!(
+            note.ends_with(&format!("of {} bytes", body.len())),
+            "{note}"
+        );
+    }
+
+    #[test]
+    fn truncation_stays_on_a_char_boundary() {
+        assert_eq!(truncate_body("abcdé", 5), ("abcd", true));
+        assert_eq!(truncate_body("abc", 5), ("abc", false));
+    }
+}
@@ -1,32 +1,62 @@
 //! Pipe filter commands (grep, head, tail, wc).
 //!
+//! `xargs` and `tee` are pipe stages too, but they run commands or write
+//! files, so [`super::execute_pipeline_with_context`] handles them.
+//!
 //! These filters operate on output lines from other commands,
 //! enabling Unix-style piping: `ls | grep foo | head -5`
 
 use crate::engine::shell::config::pipe_filters;
+use crate::support::i18n::Locale;
+
+use super::{CommandError, CommandHelp, CommandResult, OutputLine, OutputLineData};
 
-use super::{CommandResult, OutputLine, OutputLineData};
+const EXTRA_GREP_ARGUMENT: &str =
+    "extra argument (multiple patterns or file args are not supported)";
 
 /// Apply a filter command to output lines.
 pub fn apply_filter(cmd: &str, args: &[String], lines: Vec<OutputLine>) -> CommandResult {
+    apply_filter_in(cmd, args, lines, Locale::En)
+}
+
+/// Apply a filter command to output lines, with errors and help worded for
+/// `locale`.
+pub fn apply_filter_in(
+    cmd: &str,
+    args: &[String],
+    lines: Vec<OutputLine>,
+    locale: Locale,
+) -> CommandResult {
+    if args.iter().any(|arg| arg == "--help")
+        && let Some(help) = CommandHelp::lookup(cmd)
+    {
+        return CommandResult::output(help.render_in(locale));
+    }
+
     match cmd.to_lowercase().as_str() {
         "grep" => filter_grep(args, lines),
         "head" => filter_head(args, lines),
         "tail" => filter_tail(args, lines),
-        "wc" => filter_wc(lines),
-        _ => CommandResult::error_line(format!(
-            "Pipe: unknown filter '{}'. Supported: grep, head, tail, wc",
-            cmd
-        ))
-        .with_exit_code(127),
+        "wc" => filter_wc(args, lines),
+        // These run commands or write files, so the pipeline executor
+        // handles them; a stage list replayed on a fetched body cannot.
+        "xargs" | "tee" => Err(CommandError::usage(
+            cmd.to_lowercase(),
+            "only runs after a local command",
+        )),
+        _ => Err(CommandError::UnknownFilter {
+            name: cmd.to_string(),
+        }),
     }
+    .unwrap_or_else(|error| error.into_result(locale))
 }
 
-fn filter_grep(args: &[String], lines: Vec<OutputLine>) -> CommandResult {
+fn filter_grep(args: &[String], lines: Vec<OutputLine>) -> Result<CommandResult, CommandError> {
     // Parse flags and pattern.
     let mut ignore_case = false;
     let mut invert = false;
     let mut fixed_strings = false;
+    let mut color = true;
     let mut pattern: Option<&str> = None;
 
     for arg in args {
@@ -36,9 +66,12 @@ fn filter_grep(args: &[String], lines: Vec<OutputLine>) -> CommandResult {
                 "--invert-match" => invert = true,
                 "--extended-regexp" => {} // no-op: regex crate is always extended
                 "--fixed-strings" => fixed_strings = true,
+                "--no-color" => color = false,
                 _ => {
-                    return CommandResult::error_line(format!("grep: unknown option: {}", arg))
-                        .with_exit_code(2);
+                    return Err(CommandError::usage(
+                        "grep",
+                        format!("unknown option: {}", arg),
+                    ));
                 }
             }
         } else if let Some(rest) = arg.strip_prefix('-') {
@@ -47,11 +80,7 @@ fn filter_grep(args: &[String], lines: Vec<OutputLine>) -> CommandResult {
                 if pattern.is_none() {
                     pattern = Some(arg.as_str());
                 } else {
-                    return CommandResult::error_line(
-                        "grep: extra argument (multiple patterns or file args are not supported)"
-                            .to_string(),
-                    )
-                    .with_exit_code(2);
+                    return Err(CommandError::usage("grep", EXTRA_GREP_ARGUMENT));
                 }
             } else {
                 for ch in rest.chars() {
@@ -61,11 +90,10 @@ fn filter_grep(args: &[String], lines: Vec<OutputLine>) -> CommandResult {
                         'E' => {} // no-op
                         'F' => fixed_strings = true,
                         other => {
-                            return CommandResult::error_line(format!(
-                                "grep: unknown option: -{}",
-                                other
-                            ))
-                            .with_exit_code(2);
+                            return Err(CommandError::usage(
+                                "grep",
+                                format!("unknown option: -{}", other),
+                            ));
                         }
                     }
                 }
@@ -74,16 +102,12 @@ fn filter_grep(args: &[String], lines: Vec<OutputLine>) -> CommandResult {
             pattern = Some(arg.as_str());
         } else {
             // extra positional arg: not supported
-            return CommandResult::error_line(
-                "grep: extra argument (multiple patterns or file args are not supported)"
-                    .to_string(),
-            )
-            .with_exit_code(2);
+            return Err(CommandError::usage("grep", EXTRA_GREP_ARGUMENT));
         }
     }
 
     let Some(pat) = pattern else {
-        return CommandResult::error_line("grep: missing pattern").with_exit_code(2);
+        return Err(CommandError::usage("grep", "missing pattern"));
     };
 
     // With -F, escape regex metacharacters so the pattern matches literally.
@@ -97,8 +121,7 @@ fn filter_grep(args: &[String], lines: Vec<OutputLine>) -> CommandResult {
     let regex = match build_grep_regex(&effective_pattern, ignore_case) {
         Ok(r) => r,
         Err(e) => {
-            return CommandResult::error_line(format!("grep: invalid regex: {}", e))
-                .with_exit_code(2);
+            return Err(CommandError::usage("grep", format!("invalid regex: {}", e)));
         }
     };
 
@@ -108,10 +131,17 @@ fn filter_grep(args: &[String], lines: Vec<OutputLine>) -> CommandResult {
             let is_match = regex_matches_line(&regex, &line.data);
             is_match ^ invert
         })
+        .map(|line| {
+            if color && !invert {
+                highlight_matches(&regex, line)
+            } else {
+                line
+            }
+        })
         .collect();
 
     let exit_code = if matched.is_empty() { 1 } else { 0 };
-    CommandResult::output(matched).with_exit_code(exit_code)
+    Ok(CommandResult::output(matched).with_exit_code(exit_code))
 }
 
 fn build_grep_regex(pattern: &str, ignore_case: bool) -> Result<regex::Regex, regex::Error> {
@@ -120,6 +150,20 @@ fn build_grep_regex(pattern: &str, ignore_case: bool) -> Result<regex::Regex, re
         .build()
 }
 
+/// Mark every non-empty match in the line's highlightable text. Lines
+/// without such text (listing rows) keep whatever they carried.
+fn highlight_matches(re: &regex::Regex, line: OutputLine) -> OutputLine {
+    let Some(text) = line.highlight_text() else {
+        return line;
+    };
+    let ranges = re
+        .find_iter(text)
+        .map(|found| found.range())
+        .filter(|range| !range.is_empty())
+        .collect();
+    line.with_highlights(ranges)
+}
+
 fn regex_matches_line(re: &regex::Regex, data: &OutputLineData) -> bool {
     match data {
         OutputLineData::Text(s)
@@ -128,59 +172,269 @@ fn regex_matches_line(re: &regex::Regex, data: &OutputLineData) -> bool {
         | OutputLineData::Info(s)
         | OutputLineData::Ascii(s) => re.is_match(s),
         OutputLineData::ListEntry { name, .. } => re.is_match(name),
+        OutputLineData::ListRow(cells) => cells.iter().any(|cell| re.is_match(&cell.name)),
         OutputLineData::Command { input, .. } => re.is_match(input),
         OutputLineData::Empty => false,
     }
 }
 
-fn filter_head(args: &[String], lines: Vec<OutputLine>) -> CommandResult {
-    let n = match parse_count(args, pipe_filters::DEFAULT_HEAD_LINES) {
-        Ok(n) => n,
+fn filter_head(args: &[String], lines: Vec<OutputLine>) -> Result<CommandResult, CommandError> {
+    let count = match parse_count(args, pipe_filters::DEFAULT_HEAD_LINES, true) {
+        Ok(count) => count,
         Err(msg) => {
-            return CommandResult::error_line(format!("head: {}", msg)).with_exit_code(2);
+            return Err(CommandError::usage("head", msg));
         }
     };
-    CommandResult::output(lines.into_iter().take(n).collect())
+    let output = match count {
+        Count::Lines(n) => lines.into_iter().take(n).collect(),
+        Count::AllButLastLines(n) => {
+            let keep = lines.len().saturating_sub(n);
+            lines.into_iter().take(keep).collect()
+        }
+        Count::Chars(n) => head_chars(lines, n),
+    };
+    Ok(CommandResult::output(output))
 }
 
-fn filter_tail(args: &[String], lines: Vec<OutputLine>) -> CommandResult {
-    let n = match parse_count(args, pipe_filters::DEFAULT_TAIL_LINES) {
-        Ok(n) => n,
+fn filter_tail(args: &[String], lines: Vec<OutputLine>) -> Result<CommandResult, CommandError> {
+    let count = match parse_count(args, pipe_filters::DEFAULT_TAIL_LINES, false) {
+        Ok(count) => count,
         Err(msg) => {
-            return CommandResult::error_line(format!("tail: {}", msg)).with_exit_code(2);
+            return Err(CommandError::usage("tail", msg));
+        }
+    };
+    let output = match count {
+        Count::Lines(n) | Count::AllButLastLines(n) => {
+            let len = lines.len();
+            lines.into_iter().skip(len.saturating_sub(n)).collect()
         }
+        Count::Chars(n) => tail_chars(lines, n),
     };
-    let len = lines.len();
-    CommandResult::output(lines.into_iter().skip(len.saturating_sub(n)).collect())
+    Ok(CommandResult::output(output))
 }
 
-fn filter_wc(lines: Vec<OutputLine>) -> CommandResult {
-    let count = lines
-        .iter()
-        .filter(|l| !matches!(l.data, OutputLineData::Empty))
-        .count();
-    CommandResult::output(vec![OutputLine::text(format!("{}", count))])
+/// First `n` characters of the lines' rendered text, each line followed by
+/// a newline as in a real pipe. Whole lines pass through unchanged; a line
+/// cut short becomes plain text.
+fn head_chars(lines: Vec<OutputLine>, mut n: usize) -> Vec<OutputLine> {
+    let mut output = Vec::new();
+    for line in lines {
+        if n == 0 {
+            break;
+        }
+        let text = line.to_plain_text();
+        let len = text.chars().count();
+        if n > len {
+            n -= len + 1;
+            output.push(line);
+        } else {
+            output.push(OutputLine::text(text.chars().take(n).collect::<String>()));
+            break;
+        }
+    }
+    output
+}
+
+/// Last `n` characters of the lines' rendered text, counted the same way as
+/// [`head_chars`].
+fn tail_chars(lines: Vec<OutputLine>, mut n: usize) -> Vec<OutputLine> {
+    let mut output = Vec::new();
+    for line in lines.into_iter().rev() {
+        if n == 0 {
+            break;
+        }
+        let text = line.to_plain_text();
+        let len = text.chars().count();
+        if n > len {
+            n -= len + 1;
+            output.push(line);
+        } else {
+            // The budget covers the newline first, then the end of the text.
+            let kept = n - 1;
+            if kept > 0 {
+                output.push(OutputLine::text(
+                    text.chars().skip(len - kept).collect::<String>(),
+                ));
+            }
+            break;
+        }
+    }
+    output.reverse();
+    output
+}
+
+/// Counts selected by `wc` flags. All three when none are given.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct WcSelection {
+    lines: bool,
+    words: bool,
+    chars: bool,
+}
+
+fn parse_wc_args(args: &[String]) -> Result<WcSelection, String> {
+    let mut selection = WcSelection::default();
+    for arg in args {
+        match arg.as_str() {
+            "--lines" => selection.lines = true,
+            "--words" => selection.words = true,
+            "--chars" => selection.chars = true,
+            long if long.starts_with("--") => return Err(format!("unknown option: {}", long)),
+            short if short.len() > 1 && short.starts_with('-') => {
+                for ch in short[1..].chars() {
+                    match ch {
+                        'l' => selection.lines = true,
+                        'w' => selection.words = true,
+                        'c' => selection.chars = true,
+                        other => return Err(format!("unknown option: -{}", other)),
+                    }
+                }
+            }
+            other => {
+                return Err(format!(
+                    "extra argument '{}' (file args are not supported)",
+                    other
+                ));
+            }
+        }
+    }
+    if selection == WcSelection::default() {
+        selection = WcSelection {
+            lines: true,
+            words: true,
+            chars: true,
+        };
+    }
+    Ok(selection)
+}
+
+/// Lines, words, and characters `wc` counted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct WcCounts {
+    lines: usize,
+    words: usize,
+    chars: usize,
+}
+
+impl WcCounts {
+    /// Count the rendered text of non-empty lines. Each counted line
+    /// contributes its trailing newline to the character count, as in a
+    /// real pipe.
+    fn of(lines: &[OutputLine]) -> Self {
+        let mut counts = Self::default();
+        for line in lines
+            .iter()
+            .filter(|l| !matches!(l.data, OutputLineData::Empty))
+        {
+            let text = line.to_plain_text();
+            counts.lines += 1;
+            counts.words += text.split_whitespace().count();
+            counts.chars += text.chars().count() + 1;
+        }
+        counts
+    }
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            lines: self.lines + other.lines,
+            words: self.words + other.words,
+            chars: self.chars + other.chars,
+        }
+    }
+
+    /// The selected counts: a single one bare, several right-aligned.
+    fn render(self, selection: WcSelection) -> String {
+        let counts = [
+            (selection.lines, self.lines),
+            (selection.words, self.words),
+            (selection.chars, self.chars),
+        ]
+        .into_iter()
+        .filter_map(|(selected, count)| selected.then_some(count))
+        .collect::<Vec<_>>();
+
+        if let [count] = counts.as_slice() {
+            count.to_string()
+        } else {
+            counts
+                .iter()
+                .map(|count| format!("{:>7}", count))
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+    }
+}
+
+fn filter_wc(args: &[String], lines: Vec<OutputLine>) -> Result<CommandResult, CommandError> {
+    let selection = parse_wc_args(args).map_err(|msg| CommandError::usage("wc", msg))?;
+    let text = WcCounts::of(&lines).render(selection);
+    Ok(CommandResult::output(vec![OutputLine::text(text)]))
+}
+
+/// Check `wc` flags before any file is read.
+pub(super) fn check_wc_args(args: &[String]) -> Result<(), CommandError> {
+    parse_wc_args(args)
+        .map(|_| ())
+        .map_err(|msg| CommandError::usage("wc", msg))
+}
+
+/// `wc` over named files, as `xargs wc` prints it: one line per file
+/// ending in its name, then a total when there is more than one.
+pub(super) fn wc_files(
+    args: &[String],
+    files: &[(String, Vec<OutputLine>)],
+) -> Result<Vec<OutputLine>, CommandError> {
+    let selection = parse_wc_args(args).map_err(|msg| CommandError::usage("wc", msg))?;
+    let mut total = WcCounts::default();
+    let mut output = Vec::new();
+    for (name, lines) in files {
+        let counts = WcCounts::of(lines);
+        total = total.add(counts);
+        output.push(OutputLine::text(format!(
+            "{} {name}",
+            counts.render(selection)
+        )));
+    }
+    if files.len() > 1 {
+        output.push(OutputLine::text(format!(
+            "{} total",
+            total.render(selection)
+        )));
+    }
+    Ok(output)
+}
+
+/// What `head`/`tail` keep.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Count {
+    /// The first (head) or last (tail) N lines.
+    Lines(usize),
+    /// Everything but the last N lines (`head -n -N`).
+    AllButLastLines(usize),
+    /// The first or last N characters.
+    Chars(usize),
 }
 
 /// Parse the count argument for head/tail.
 ///
 /// Supports:
-/// - No args: returns `default`.
+/// - No args: returns `default` lines.
 /// - `-N` where N is a non-negative integer (e.g., `-5`).
-/// - `-n N` where N is a non-negative integer (e.g., `-n 5`).
+/// - `-n N` where N is a non-negative integer (e.g., `-n 5`), or `-n -N`
+///   for all but the last N lines when `allow_negative` is set.
+/// - `-c N` for N characters.
 ///
 /// Rejects:
 /// - `--N`, `---N`, etc.
 /// - Non-numeric: `-abc`, `abc`.
 /// - Unknown flags.
-fn parse_count(args: &[String], default: usize) -> Result<usize, String> {
+fn parse_count(args: &[String], default: usize, allow_negative: bool) -> Result<Count, String> {
     match args.len() {
-        0 => Ok(default),
+        0 => Ok(Count::Lines(default)),
         1 => {
             let arg = &args[0];
-            // `-n` alone is incomplete; fallthrough to error below
-            if arg == "-n" {
-                return Err("option requires an argument: -n".to_string());
+            // `-n`/`-c` alone are incomplete
+            if arg == "-n" || arg == "-c" {
+                return Err(format!("option requires an argument: {}", arg));
             }
             // Bulk reject any double-dash prefix
             if arg.starts_with("--") {
@@ -189,6 +443,7 @@ fn parse_count(args: &[String], default: usize) -> Result<usize, String> {
             if let Some(rest) = arg.strip_prefix('-') {
                 // must be `-N` where N is integer
                 rest.parse::<usize>()
+                    .map(Count::Lines)
                     .map_err(|_| format!("invalid option: -{}", rest))
             } else {
                 // bare positional like "5" is not POSIX but also not accepted
@@ -196,12 +451,24 @@ fn parse_count(args: &[String], default: usize) -> Result<usize, String> {
             }
         }
         2 => {
-            if args[0] == "-n" {
-                args[1]
+            let value = &args[1];
+            let invalid = || format!("invalid number: {}", value);
+            match args[0].as_str() {
+                "-n" => match value.strip_prefix('-') {
+                    Some(rest) if allow_negative => rest
+                        .parse::<usize>()
+                        .map(Count::AllButLastLines)
+                        .map_err(|_| invalid()),
+                    _ => value
+                        .parse::<usize>()
+                        .map(Count::Lines)
+                        .map_err(|_| invalid()),
+                },
+                "-c" => value
                     .parse::<usize>()
-                    .map_err(|_| format!("invalid number: {}", args[1]))
-            } else {
-                Err(format!("unknown options: {} {}", args[0], args[1]))
+                    .map(Count::Chars)
+                    .map_err(|_| invalid()),
+                _ => Err(format!("unknown options: {} {}", args[0], args[1])),
             }
         }
         _ => Err("too many arguments".to_string()),
@@ -180,13 +180,117 @@ fn test_tail_with_dash() {
     assert_eq!(result.output.len(), 3);
 }
 
+fn texts(result: &CommandResult) -> Vec<String> {
+    result
+        .output
+        .iter()
+        .map(OutputLine::to_plain_text)
+        .collect()
+}
+
 #[test]
-fn test_wc_filter() {
-    let lines = test_lines();
-    let result = apply_filter("wc", &[], lines);
+fn test_head_chars_filter() {
+    // "apple\nbanana\n..." — the 9th character is the 'n' in "banana".
+    let result = apply_filter("head", &args(&["-c", "9"]), test_lines());
+    assert_eq!(result.exit_code, 0);
+    assert_eq!(texts(&result), vec!["apple", "ban"]);
+    assert!(matches!(&result.output[0].data, OutputLineData::Text(s) if s == "apple"));
+}
+
+#[test]
+fn test_head_chars_at_line_boundary() {
+    let result = apply_filter("head", &args(&["-c", "6"]), test_lines());
+    assert_eq!(texts(&result), vec!["apple"]);
+    let result = apply_filter("head", &args(&["-c", "5"]), test_lines());
+    assert_eq!(texts(&result), vec!["apple"]);
+}
+
+#[test]
+fn test_head_chars_larger_than_input_passes_everything() {
+    let result = apply_filter("head", &args(&["-c", "1000"]), test_lines());
+    assert_eq!(result.output.len(), 5);
+}
+
+#[test]
+fn test_head_chars_zero_yields_nothing() {
+    let result = apply_filter("head", &args(&["-c", "0"]), test_lines());
+    assert_eq!(result.exit_code, 0);
+    assert!(result.output.is_empty());
+}
+
+#[test]
+fn test_head_negative_lines_drops_the_tail() {
+    let result = apply_filter("head", &args(&["-n", "-2"]), test_lines());
+    assert_eq!(result.exit_code, 0);
+    assert_eq!(texts(&result), vec!["apple", "banana", "cherry"]);
+    let result = apply_filter("head", &args(&["-n", "-9"]), test_lines());
+    assert!(result.output.is_empty());
+}
+
+#[test]
+fn test_tail_chars_filter() {
+    // "...date\nelderberry\n" — the last 14 characters.
+    let result = apply_filter("tail", &args(&["-c", "14"]), test_lines());
+    assert_eq!(result.exit_code, 0);
+    assert_eq!(texts(&result), vec!["te", "elderberry"]);
+}
+
+#[test]
+fn test_tail_chars_counts_the_trailing_newline() {
+    let result = apply_filter("tail", &args(&["-c", "4"]), test_lines());
+    assert_eq!(texts(&result), vec!["rry"]);
+    let result = apply_filter("tail", &args(&["-c", "1"]), test_lines());
+    assert!(result.output.is_empty());
+}
+
+#[test]
+fn test_tail_chars_larger_than_input_passes_everything() {
+    let result = apply_filter("tail", &args(&["-c", "1000"]), test_lines());
+    assert_eq!(result.output.len(), 5);
+    let result = apply_filter("tail", &args(&["-c", "0"]), test_lines());
+    assert!(result.output.is_empty());
+}
+
+#[test]
+fn test_tail_rejects_negative_counts() {
+    let result = apply_filter("tail", &args(&["-n", "-2"]), test_lines());
+    assert_eq!(result.exit_code, 2);
+    let result = apply_filter("head", &args(&["-c", "-2"]), test_lines());
+    assert_eq!(result.exit_code, 2);
+    let result = apply_filter("head", &args(&["-c"]), test_lines());
+    assert_eq!(result.exit_code, 2);
+}
+
+fn wc_text(args_: &[&str], lines: Vec<OutputLine>) -> String {
+    let result = apply_filter("wc", &args(args_), lines);
     assert_eq!(result.exit_code, 0);
     assert_eq!(result.output.len(), 1);
-    assert!(matches!(&result.output[0].data, OutputLineData::Text(s) if s == "5"));
+    match &result.output[0].data {
+        OutputLineData::Text(s) => s.clone(),
+        other => panic!("expected Text, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_wc_filter_prints_all_counts_by_default() {
+    // 5 lines, 5 words, 31 characters + 5 newlines.
+    assert_eq!(wc_text(&[], test_lines()), "      5       5      36");
+}
+
+#[test]
+fn test_wc_single_flags() {
+    assert_eq!(wc_text(&["-l"], test_lines()), "5");
+    assert_eq!(wc_text(&["-w"], test_lines()), "5");
+    assert_eq!(wc_text(&["-c"], test_lines()), "36");
+    assert_eq!(wc_text(&["--words"], test_lines()), "5");
+}
+
+#[test]
+fn test_wc_combined_flags_keep_standard_order() {
+    let lines = vec![OutputLine::text("hello big world"), OutputLine::text("hi")];
+    assert_eq!(wc_text(&["-c", "-l"], lines.clone()), "      2      19");
+    assert_eq!(wc_text(&["-wl"], lines.clone()), "      2       4");
+    assert_eq!(wc_text(&["-lwc"], lines), "      2       4      19");
 }
 
 #[test]
@@ -197,9 +301,30 @@ fn test_wc_excludes_empty() {
         OutputLine::text("line2"),
         OutputLine::empty(),
     ];
-    let result = apply_filter("wc", &[], lines);
-    assert_eq!(result.exit_code, 0);
-    assert!(matches!(&result.output[0].data, OutputLineData::Text(s) if s == "2"));
+    assert_eq!(wc_text(&["-l"], lines), "2");
+}
+
+#[test]
+fn test_wc_counts_rendered_text_of_entries_and_commands() {
+    let lines = vec![
+        OutputLine::command("guest@websh:~", "ls"),
+        OutputLine::dir_entry("docs", "Documentation"),
+        OutputLine::file_entry("notes.md", "", false),
+    ];
+    // "guest@websh:~$ ls" (2 words, 17 chars), "docs/  Documentation"
+    // (2 words, 20 chars), "notes.md" (1 word, 8 chars).
+    assert_eq!(wc_text(&[], lines), "      3       5      48");
+}
+
+#[test]
+fn test_wc_rejects_unknown_flags_and_operands() {
+    for bad in [&["-x"][..], &["--bytes"], &["file.txt"]] {
+        let result = apply_filter("wc", &args(bad), test_lines());
+        assert_eq!(result.exit_code, 2, "{bad:?}");
+        assert!(
+            matches!(&result.output[0].data, OutputLineData::Error(s) if s.starts_with("wc: "))
+        );
+    }
 }
 
 #[test]
@@ -310,3 +435,54 @@ fn test_grep_extra_positional_error_message() {
     };
     assert!(msg.contains("extra argument"), "msg: {}", msg);
 }
+
+#[test]
+fn test_filter_help_flag_prints_usage() {
+    let result = apply_filter("grep", &args(&["--help"]), test_lines());
+    assert_eq!(result.exit_code, 0);
+    assert!(matches!(
+        &result.output[0].data,
+        OutputLineData::Text(s) if s.starts_with("grep - ")
+    ));
+}
+
+#[test]
+fn test_grep_highlights_each_match() {
+    let lines = vec![OutputLine::text("banana bread"), OutputLine::text("cherry")];
+    let result = apply_filter("grep", &args(&["an"]), lines);
+    assert_eq!(result.output.len(), 1);
+    assert_eq!(result.output[0].highlights, vec![1..3, 3..5]);
+}
+
+#[test]
+fn test_grep_highlights_list_entry_names() {
+    let lines = vec![OutputLine::dir_entry("blog", "Blog posts")];
+    let result = apply_filter("grep", &args(&["log"]), lines);
+    let line = &result.output[0];
+    assert_eq!(line.highlight_text(), Some("blog"));
+    assert_eq!(line.highlights, vec![1..4]);
+}
+
+#[test]
+fn test_grep_no_color_and_invert_skip_highlights() {
+    let result = apply_filter(
+        "grep",
+        &args(&["--no-color", "an"]),
+        vec![OutputLine::text("banana")],
+    );
+    assert!(result.output[0].highlights.is_empty());
+
+    let result = apply_filter(
+        "grep",
+        &args(&["-v", "x"]),
+        vec![OutputLine::text("banana")],
+    );
+    assert!(result.output[0].highlights.is_empty());
+}
+
+#[test]
+fn test_grep_empty_matches_are_not_highlighted() {
+    let result = apply_filter("grep", &args(&["z*"]), vec![OutputLine::text("abc")]);
+    assert_eq!(result.exit_code, 0);
+    assert!(result.output[0].highlights.is_empty());
+}
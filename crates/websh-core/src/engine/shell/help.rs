@@ -0,0 +1,990 @@
+//! Per-command help table.
+//!
+//! The table drives both `help <command>` (and `<command> --help`) and the
+//! general `help` listing, so the listing cannot drift from the commands the
+//! shell actually implements.
+
+use crate::support::i18n::{Locale, Message};
+use crate::support::text::edit_distance;
+
+use super::{Command, CommandFlag, CommandResult, OutputLine};
+
+/// Usage documentation for one shell command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommandHelp {
+    /// Canonical command name, as listed by [`Command::names`].
+    pub name: &'static str,
+    /// Alternative names accepted by the parser (e.g. `cls` for `clear`).
+    pub aliases: &'static [&'static str],
+    /// Heading the command is grouped under in the `help` listing.
+    pub section: Message,
+    /// Usage line, e.g. `ls [-l] [dir]`.
+    pub synopsis: &'static str,
+    /// One-line description.
+    pub summary: &'static str,
+    /// Example invocations.
+    pub examples: &'static [&'static str],
+}
+
+impl CommandHelp {
+    /// All help entries, in `help` listing order.
+    pub fn all() -> &'static [CommandHelp] {
+        COMMAND_HELP
+    }
+
+    /// Look up help by command name or alias (case-insensitive).
+    pub fn lookup(name: &str) -> Option<&'static CommandHelp> {
+        let name = name.to_lowercase();
+        COMMAND_HELP
+            .iter()
+            .find(|help| help.name == name || help.aliases.contains(&name.as_str()))
+    }
+
+    /// Flags accepted by the command, from the shared flag table.
+    pub fn flags(&self) -> &'static [CommandFlag] {
+        Command::flags(self.name)
+    }
+
+    /// Render the detailed `help <command>` page.
+    pub fn render(&self) -> Vec<OutputLine> {
+        self.render_in(Locale::En)
+    }
+
+    /// Render the `help <command>` page with its headings in `locale`.
+    /// Summaries and flag descriptions stay in English.
+    pub fn render_in(&self, locale: Locale) -> Vec<OutputLine> {
+        let mut lines = vec![
+            OutputLine::text(format!("{} - {}", self.name, self.summary)),
+            OutputLine::empty(),
+            OutputLine::text(Message::HelpUsage.format(locale, &[("synopsis", self.synopsis)])),
+        ];
+
+        if !self.aliases.is_empty() {
+            lines.push(OutputLine::text(
+                Message::HelpAliases.format(locale, &[("aliases", &self.aliases.join(", "))]),
+            ));
+        }
+
+        let flags = self.flags();
+        if !flags.is_empty() {
+            let width = flags.iter().map(|f| f.flag.len()).max().unwrap_or(0);
+            lines.push(OutputLine::empty());
+            lines.push(OutputLine::text(Message::HelpFlags.text(locale)));
+            for flag in flags {
+                lines.push(OutputLine::text(format!(
+                    "  {:width$}  {}",
+                    flag.flag, flag.description
+                )));
+            }
+        }
+
+        if !self.examples.is_empty() {
+            lines.push(OutputLine::empty());
+            lines.push(OutputLine::text(Message::HelpExamples.text(locale)));
+            for example in self.examples {
+                lines.push(OutputLine::text(format!("  {}", example)));
+            }
+        }
+
+        lines
+    }
+}
+
+const COMMAND_HELP: &[CommandHelp] = &[
+    // Navigation
+    CommandHelp {
+        name: "ls",
+        aliases: &[],
+        section: Message::HelpSectionNavigation,
+        synopsis: "ls [-l] [-1] [--diff] [dir]",
+        summary: "List directory contents",
+        examples: &["ls", "ls -l ~", "ls --diff"],
+    },
+    CommandHelp {
+        name: "cd",
+        aliases: &[],
+        section: Message::HelpSectionNavigation,
+        synopsis: "cd [dir | -N | +N]",
+        summary: "Change directory (supports `.`, `..`, `~`, absolute paths, history offsets)",
+        examples: &["cd ..", "cd ~", "cd -2"],
+    },
+    CommandHelp {
+        name: "dirs",
+        aliases: &[],
+        section: Message::HelpSectionNavigation,
+        synopsis: "dirs [-c]",
+        summary: "List the navigation history (-c clears it)",
+        examples: &["dirs", "dirs -c"],
+    },
+    CommandHelp {
+        name: "pwd",
+        aliases: &[],
+        section: Message::HelpSectionNavigation,
+        synopsis: "pwd [-L|-P]",
+        summary: "Print working directory (-P: path within its mount)",
+        examples: &["pwd", "pwd -P"],
+    },
+    CommandHelp {
+        name: "cat",
+        aliases: &[],
+        section: Message::HelpSectionNavigation,
+        synopsis: "cat <file>",
+        summary: "View file contents (opens reader)",
+        examples: &["cat .profile"],
+    },
+    CommandHelp {
+        name: "explorer",
+        aliases: &[],
+        section: Message::HelpSectionNavigation,
+        synopsis: "explorer [path]",
+        summary: "Open a directory or file in the content view",
+        examples: &["explorer", "explorer blog/post.md"],
+    },
+    // Information
+    CommandHelp {
+        name: "whoami",
+        aliases: &[],
+        section: Message::HelpSectionInformation,
+        synopsis: "whoami",
+        summary: "Display user profile",
+        examples: &[],
+    },
+    CommandHelp {
+        name: "id",
+        aliases: &[],
+        section: Message::HelpSectionInformation,
+        synopsis: "id",
+        summary: "Show current session info",
+        examples: &[],
+    },
+    CommandHelp {
+        name: "date",
+        aliases: &[],
+        section: Message::HelpSectionInformation,
+        synopsis: "date [+FORMAT]",
+        summary: "Print the local date and time (%Y %m %d %H %M %S %A %B ...)",
+        examples: &["date", "date +%Y-%m-%d"],
+    },
+    CommandHelp {
+        name: "neofetch",
+        aliases: &["sysinfo"],
+        section: Message::HelpSectionInformation,
+        synopsis: "neofetch",
+        summary: "Show the profile beside a system summary",
+        examples: &[],
+    },
+    CommandHelp {
+        name: "tags",
+        aliases: &[],
+        section: Message::HelpSectionInformation,
+        synopsis: "tags [tag]",
+        summary: "Count tags in the current mount, or list paths with a tag",
+        examples: &["tags", "tags rust", "tags rust | grep blog"],
+    },
+    CommandHelp {
+        name: "feed",
+        aliases: &[],
+        section: Message::HelpSectionInformation,
+        synopsis: "feed [-c|-d] [dir]",
+        summary: "Atom feed of the newest posts under a directory (default ~/blog)",
+        examples: &["feed", "feed -d", "feed -c ~/notes"],
+    },
+    CommandHelp {
+        name: "search",
+        aliases: &[],
+        section: Message::HelpSectionInformation,
+        synopsis: "search <query>",
+        summary: "Search the text of markdown files in the current mount",
+        examples: &["search wasm", "search zero knowledge"],
+    },
+    CommandHelp {
+        name: "sha256",
+        aliases: &[],
+        section: Message::HelpSectionInformation,
+        synopsis: "sha256 [--check] <path>",
+        summary: "Print a file's SHA-256, or check files against the manifest",
+        examples: &["sha256 blog/hello.md", "sha256 --check blog"],
+    },
+    CommandHelp {
+        name: "fetch",
+        aliases: &[],
+        section: Message::HelpSectionInformation,
+        synopsis: "fetch <url>",
+        summary: "GET an https URL from an allowed host the page CSP can reach",
+        examples: &[
+            "fetch https://api.github.com/repos/0xwonj/websh",
+            "fetch https://api.github.com/zen | grep -i design",
+            "export FETCH_ALLOW=raw.githubusercontent.com",
+        ],
+    },
+    CommandHelp {
+        name: "manifest",
+        aliases: &[],
+        section: Message::HelpSectionInformation,
+        synopsis: "manifest lint",
+        summary: "Report problems found in the loaded content manifests",
+        examples: &["manifest lint"],
+    },
+    CommandHelp {
+        name: "dmesg",
+        aliases: &[],
+        section: Message::HelpSectionInformation,
+        synopsis: "dmesg [-f wallet|mount|manifest|fetch]",
+        summary: "Show wallet, mount, manifest and fetch events since the page loaded",
+        examples: &["dmesg", "dmesg -f wallet", "dmesg | grep failed"],
+    },
+    CommandHelp {
+        name: "stats",
+        aliases: &[],
+        section: Message::HelpSectionInformation,
+        synopsis: "stats [--reset]",
+        summary: "Show your most used commands and files, counted only in this browser",
+        examples: &["stats", "stats --reset", "export TRACKING=off"],
+    },
+    CommandHelp {
+        name: "help",
+        aliases: &["?"],
+        section: Message::HelpSectionInformation,
+        synopsis: "help [command|keys]",
+        summary: "Show all commands, usage for one command, or keyboard shortcuts",
+        examples: &["help", "help grep", "ls --help", "help keys"],
+    },
+    CommandHelp {
+        name: "tutorial",
+        aliases: &[],
+        section: Message::HelpSectionInformation,
+        synopsis: "tutorial [skip|reset]",
+        summary: "Take a short guided tour, resuming where you left off",
+        examples: &["tutorial", "tutorial skip", "tutorial reset"],
+    },
+    CommandHelp {
+        name: "man",
+        aliases: &[],
+        section: Message::HelpSectionInformation,
+        synopsis: "man <command>",
+        summary: "Show the usage, flags, and examples for a command",
+        examples: &["man ls", "man grep"],
+    },
+    CommandHelp {
+        name: "which",
+        aliases: &[],
+        section: Message::HelpSectionInformation,
+        synopsis: "which <name>...",
+        summary: "Show how each name resolves: builtin, alias, or unknown",
+        examples: &["which ls", "which cls grep"],
+    },
+    // System
+    CommandHelp {
+        name: "clear",
+        aliases: &["cls"],
+        section: Message::HelpSectionSystem,
+        synopsis: "clear",
+        summary: "Clear terminal screen (earlier output stays recoverable)",
+        examples: &[],
+    },
+    CommandHelp {
+        name: "reset",
+        aliases: &[],
+        section: Message::HelpSectionSystem,
+        synopsis: "reset",
+        summary: "Clear terminal screen and discard all earlier output",
+        examples: &[],
+    },
+    CommandHelp {
+        name: "copy",
+        aliases: &[],
+        section: Message::HelpSectionSystem,
+        synopsis: "copy",
+        summary: "Copy the terminal session to the clipboard",
+        examples: &[],
+    },
+    CommandHelp {
+        name: "export-session",
+        aliases: &[],
+        section: Message::HelpSectionSystem,
+        synopsis: "export-session [--html] [file]",
+        summary: "Download the terminal session as a transcript file",
+        examples: &["export-session", "export-session --html notes.html"],
+    },
+    CommandHelp {
+        name: "export-zip",
+        aliases: &[],
+        section: Message::HelpSectionSystem,
+        synopsis: "export-zip [dir]",
+        summary: "Download a folder as a zip archive",
+        examples: &["export-zip talks", "export-zip"],
+    },
+    CommandHelp {
+        name: "echo",
+        aliases: &[],
+        section: Message::HelpSectionSystem,
+        synopsis: "echo [-neE] <text> [> path]",
+        summary: "Display text, or write it to a file (admin-only)",
+        examples: &["echo hello", "echo -e 'a\\tb\\nc'", "echo draft > notes.md"],
+    },
+    CommandHelp {
+        name: "basename",
+        aliases: &[],
+        section: Message::HelpSectionSystem,
+        synopsis: "basename <path> [suffix]",
+        summary: "Strip the directory, and a suffix, from a path (no lookup)",
+        examples: &["basename /blog/post.md", "basename /blog/post.md .md"],
+    },
+    CommandHelp {
+        name: "dirname",
+        aliases: &[],
+        section: Message::HelpSectionSystem,
+        synopsis: "dirname <path>",
+        summary: "Strip the last component from a path (no lookup)",
+        examples: &["dirname /blog/post.md", "dirname post.md"],
+    },
+    CommandHelp {
+        name: "theme",
+        aliases: &[],
+        section: Message::HelpSectionSystem,
+        synopsis: "theme [name]",
+        summary: "List or set palette",
+        examples: &["theme", "theme nord"],
+    },
+    // Filesystem (write, admin-only)
+    CommandHelp {
+        name: "edit",
+        aliases: &[],
+        section: Message::HelpSectionWrite,
+        synopsis: "edit <path>",
+        summary: "Open file in the editor (creates on save)",
+        examples: &["edit notes.md"],
+    },
+    CommandHelp {
+        name: "mkdir",
+        aliases: &[],
+        section: Message::HelpSectionWrite,
+        synopsis: "mkdir <path>",
+        summary: "Create a directory",
+        examples: &["mkdir drafts"],
+    },
+    CommandHelp {
+        name: "rm",
+        aliases: &[],
+        section: Message::HelpSectionWrite,
+        synopsis: "rm [-r] <path>",
+        summary: "Remove a file or directory",
+        examples: &["rm notes.md", "rm -r drafts"],
+    },
+    CommandHelp {
+        name: "mv",
+        aliases: &[],
+        section: Message::HelpSectionWrite,
+        synopsis: "mv <source> <dest>",
+        summary: "Move or rename a file or directory",
+        examples: &["mv draft.md post.md", "mv post.md blog/"],
+    },
+    CommandHelp {
+        name: "cp",
+        aliases: &[],
+        section: Message::HelpSectionWrite,
+        synopsis: "cp <source> <dest>",
+        summary: "Copy a file, or a directory and its contents",
+        examples: &["cp post.md post-v2.md", "cp blog archive"],
+    },
+    CommandHelp {
+        name: "rmdir",
+        aliases: &[],
+        section: Message::HelpSectionWrite,
+        synopsis: "rmdir <path>",
+        summary: "Remove an empty directory",
+        examples: &["rmdir drafts"],
+    },
+    CommandHelp {
+        name: "export-draft",
+        aliases: &[],
+        section: Message::HelpSectionWrite,
+        synopsis: "export-draft <file>",
+        summary: "Download a local draft so it can be committed elsewhere",
+        examples: &["export-draft notes.md"],
+    },
+    CommandHelp {
+        name: "touch",
+        aliases: &[],
+        section: Message::HelpSectionWrite,
+        synopsis: "touch <path>",
+        summary: "Create an empty file",
+        examples: &["touch notes.md"],
+    },
+    // Environment
+    CommandHelp {
+        name: "export",
+        aliases: &[],
+        section: Message::HelpSectionEnvironment,
+        synopsis: "export [KEY=value ...]",
+        summary: "Show all variables, or set them (localStorage)",
+        examples: &[
+            "export",
+            "export GREETING=hello",
+            "export PATH=$PATH:/extra",
+        ],
+    },
+    CommandHelp {
+        name: "lang",
+        aliases: &[],
+        section: Message::HelpSectionEnvironment,
+        synopsis: "lang [en|ko]",
+        summary: "Show or set the interface language (sets LANG)",
+        examples: &["lang", "lang ko"],
+    },
+    CommandHelp {
+        name: "env",
+        aliases: &[],
+        section: Message::HelpSectionEnvironment,
+        synopsis: "env",
+        summary: "Print every variable as KEY=value, with HOME, PWD and USER",
+        examples: &["env", "env | grep PWD"],
+    },
+    CommandHelp {
+        name: "unset",
+        aliases: &[],
+        section: Message::HelpSectionEnvironment,
+        synopsis: "unset KEY",
+        summary: "Remove variable",
+        examples: &["unset GREETING"],
+    },
+    CommandHelp {
+        name: "session",
+        aliases: &[],
+        section: Message::HelpSectionEnvironment,
+        synopsis: "session [list | save|load|rm <name> | restore-last]",
+        summary: "Save and restore the route, theme and variables under a name (localStorage)",
+        examples: &[
+            "session save work",
+            "session load work",
+            "session list",
+            "session restore-last",
+        ],
+    },
+    // Wallet
+    CommandHelp {
+        name: "login",
+        aliases: &[],
+        section: Message::HelpSectionWallet,
+        synopsis: "login",
+        summary: "Connect MetaMask wallet",
+        examples: &[],
+    },
+    CommandHelp {
+        name: "logout",
+        aliases: &[],
+        section: Message::HelpSectionWallet,
+        synopsis: "logout",
+        summary: "Disconnect wallet",
+        examples: &[],
+    },
+    // Sync
+    CommandHelp {
+        name: "sync",
+        aliases: &[],
+        section: Message::HelpSectionSync,
+        synopsis: "sync [status|refresh|commit <message>|auth set <token>|auth clear]",
+        summary: "Show, commit, or reload changes; manage the GitHub token",
+        examples: &[
+            "sync",
+            "sync commit Add new post",
+            "sync refresh",
+            "sync auth clear",
+        ],
+    },
+    // Pipe filters
+    CommandHelp {
+        name: "grep",
+        aliases: &[],
+        section: Message::HelpSectionPipeFilters,
+        synopsis: "grep [-i] [-v] [-F] <pattern>",
+        summary: "Filter lines (regex by default)",
+        examples: &["ls | grep -i md", "help | grep -v sync"],
+    },
+    CommandHelp {
+        name: "head",
+        aliases: &[],
+        section: Message::HelpSectionPipeFilters,
+        synopsis: "head [-N | -n [-]N | -c N]",
+        summary: "First N lines or characters (default: 10 lines)",
+        examples: &["ls | head -5", "ls | head -n -2", "help | head -c 80"],
+    },
+    CommandHelp {
+        name: "tail",
+        aliases: &[],
+        section: Message::HelpSectionPipeFilters,
+        synopsis: "tail [-N | -n N | -c N]",
+        summary: "Last N lines or characters (default: 10 lines)",
+        examples: &["ls | tail -n 3"],
+    },
+    CommandHelp {
+        name: "tee",
+        aliases: &[],
+        section: Message::HelpSectionPipeFilters,
+        synopsis: "tee [-a] <file>",
+        summary: "Pass lines through and also write them to a file",
+        examples: &[
+            "ls | grep md | tee posts.txt | wc -l",
+            "date | tee -a log.txt",
+        ],
+    },
+    CommandHelp {
+        name: "wc",
+        aliases: &[],
+        section: Message::HelpSectionPipeFilters,
+        synopsis: "wc [-l] [-w] [-c]",
+        summary: "Count lines, words, and characters",
+        examples: &["ls | wc", "cat notes.md | wc -w"],
+    },
+    CommandHelp {
+        name: "xargs",
+        aliases: &[],
+        section: Message::HelpSectionPipeFilters,
+        synopsis: "xargs [-n N] [cat|echo|help|ls|man|wc|which] [args...]",
+        summary: "Run a command with each input line as an argument",
+        examples: &[
+            "ls | xargs ls",
+            "ls | grep .md | xargs -n 10 wc",
+            "ls blog | grep rust | xargs -n 5 echo posts:",
+        ],
+    },
+];
+
+/// Keyboard shortcuts listed by `help keys`, grouped by where they apply.
+const KEY_BINDINGS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "Terminal",
+        &[
+            ("Tab", "complete a command or path"),
+            ("Up / Down", "step through command history"),
+            ("Right", "accept the suggested completion"),
+            ("Ctrl+R", "search command history"),
+            (
+                "/pattern",
+                "search the output; n / N step older / newer, Esc closes",
+            ),
+            ("Ctrl+C", "abandon the current line"),
+            ("Ctrl+L", "clear the screen"),
+            ("Ctrl+U", "delete back to the start of the line"),
+            ("Ctrl+A / Ctrl+E", "move to the start / end of the line"),
+        ],
+    ),
+    (
+        "Reader",
+        &[
+            ("j / k", "scroll down / up"),
+            ("d / u", "scroll half a page down / up"),
+            ("g / G", "jump to the top / bottom"),
+            (
+                "n / p or ] / [",
+                "open the next / previous file in the directory",
+            ),
+            ("e", "edit the page (admin)"),
+            ("r", "preview while editing"),
+            ("Ctrl+S", "save while editing"),
+        ],
+    ),
+    (
+        "Anywhere",
+        &[
+            ("Ctrl+Shift+G", "open the path bar"),
+            ("Ctrl+L", "open the path bar (outside the terminal)"),
+        ],
+    ),
+];
+
+/// Synopses longer than this get their summary on the following line.
+const LISTING_COLUMN: usize = 32;
+
+/// Execute `help [command]`.
+///
+/// Without a topic, lists every command grouped by section, followed by the
+/// target-provided help text (tips and site-specific notes). `help keys`
+/// lists the keyboard shortcuts.
+pub(super) fn execute_help(
+    topic: Option<String>,
+    site_text: &str,
+    locale: Locale,
+) -> CommandResult {
+    match topic {
+        Some(topic) if topic.eq_ignore_ascii_case("keys") => {
+            CommandResult::output(render_key_bindings())
+        }
+        Some(topic) => match CommandHelp::lookup(&topic) {
+            Some(help) => CommandResult::output(help.render_in(locale)),
+            None => {
+                let mut message = Message::HelpNoTopic.format(locale, &[("topic", &topic)]);
+                if let Some(suggestion) = suggest_command(&topic) {
+                    message.push_str(
+                        &Message::HelpDidYouMean.format(locale, &[("suggestion", suggestion)]),
+                    );
+                }
+                CommandResult::error_line(message)
+            }
+        },
+        None => CommandResult::output(render_listing(site_text, locale)),
+    }
+}
+
+fn render_listing(site_text: &str, locale: Locale) -> Vec<OutputLine> {
+    let mut lines = vec![OutputLine::text(
+        Message::HelpAvailableCommands.text(locale),
+    )];
+    let mut section = None;
+
+    for help in COMMAND_HELP {
+        if section != Some(help.section) {
+            section = Some(help.section);
+            lines.push(OutputLine::empty());
+            lines.push(OutputLine::text(format!(
+                "  {}:",
+                help.section.text(locale)
+            )));
+        }
+        if help.synopsis.len() < LISTING_COLUMN {
+            lines.push(OutputLine::text(format!(
+                "    {:LISTING_COLUMN$}{}",
+                help.synopsis, help.summary
+            )));
+        } else {
+            lines.push(OutputLine::text(format!("    {}", help.synopsis)));
+            lines.push(OutputLine::text(format!(
+                "    {:LISTING_COLUMN$}{}",
+                "", help.summary
+            )));
+        }
+    }
+
+    if !site_text.trim().is_empty() {
+        lines.push(OutputLine::empty());
+        lines.extend(site_text.lines().map(OutputLine::text));
+    }
+
+    lines
+}
+
+fn render_key_bindings() -> Vec<OutputLine> {
+    let width = KEY_BINDINGS
+        .iter()
+        .flat_map(|(_, bindings)| bindings.iter())
+        .map(|(keys, _)| keys.len())
+        .max()
+        .unwrap_or(0);
+    let mut lines = vec![OutputLine::text("Keyboard shortcuts:")];
+    for (context, bindings) in KEY_BINDINGS {
+        lines.push(OutputLine::empty());
+        lines.push(OutputLine::text(format!("  {context}:")));
+        for (keys, description) in *bindings {
+            lines.push(OutputLine::text(format!(
+                "    {keys:width$}  {description}"
+            )));
+        }
+    }
+    lines.push(OutputLine::empty());
+    lines.push(OutputLine::text(
+        "Single-key shortcuts are ignored while a text field has focus.",
+    ));
+    lines
+}
+
+/// Execute `man`: the `help <command>` page, with man's wording for
+/// missing and unknown topics.
+pub(super) fn execute_man(topic: Option<String>, locale: Locale) -> CommandResult {
+    let Some(topic) = topic else {
+        return CommandResult::error_line(Message::ManMissingTopic.text(locale));
+    };
+    match CommandHelp::lookup(&topic) {
+        Some(help) => CommandResult::output(help.render_in(locale)),
+        None => CommandResult::error_line(Message::ManNoEntry.format(locale, &[("topic", &topic)])),
+    }
+}
+
+/// How `which` classifies a command name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum NameResolution {
+    /// A command the shell implements directly.
+    Builtin,
+    /// An alternative name for the given canonical command.
+    Alias(&'static str),
+    Unknown,
+}
+
+/// Classify `name` against the command registry and alias table.
+/// Matching is case-insensitive, like the parser.
+pub(crate) fn resolve_name(name: &str) -> NameResolution {
+    let name = name.to_lowercase();
+    if let Some(help) = COMMAND_HELP
+        .iter()
+        .find(|help| help.aliases.contains(&name.as_str()))
+    {
+        NameResolution::Alias(help.name)
+    } else if Command::names().contains(&name.as_str()) {
+        NameResolution::Builtin
+    } else {
+        NameResolution::Unknown
+    }
+}
+
+/// Execute `which`: one line per name, exit 1 if any name is unknown.
+pub(super) fn execute_which(names: Vec<String>) -> CommandResult {
+    if names.is_empty() {
+        return CommandResult::error_line("which: missing command name");
+    }
+
+    let mut any_unknown = false;
+    let lines = names
+        .iter()
+        .map(|name| match resolve_name(name) {
+            NameResolution::Builtin => OutputLine::text(format!("{name}: shell builtin")),
+            NameResolution::Alias(target) => {
+                OutputLine::text(format!("{name}: aliased to '{target}'"))
+            }
+            NameResolution::Unknown => {
+                any_unknown = true;
+                OutputLine::error(format!("which: no {name} in commands"))
+            }
+        })
+        .collect();
+
+    CommandResult::output(lines).with_exit_code(if any_unknown { 1 } else { 0 })
+}
+
+/// Suggest the known command closest to `name` by edit distance.
+///
+/// Returns `None` when nothing is close enough to be a plausible typo.
+pub fn suggest_command(name: &str) -> Option<&'static str> {
+    let name = name.to_lowercase();
+    if Command::names().contains(&name.as_str()) {
+        return None;
+    }
+    let max_distance = if name.chars().count() <= 3 { 1 } else { 2 };
+
+    Command::names()
+        .iter()
+        .map(|candidate| (edit_distance(&name, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::shell::OutputLineData;
+
+    fn texts(lines: &[OutputLine]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| match &line.data {
+                OutputLineData::Text(s) | OutputLineData::Error(s) => s.clone(),
+                _ => String::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn man_renders_the_command_page() {
+        let lines = texts(&execute_man(Some("ls".to_string()), Locale::En).output);
+        assert_eq!(lines[0], "ls - List directory contents");
+        assert!(lines.contains(&"Usage: ls [-l] [-1] [--diff] [dir]".to_string()));
+        assert!(lines.iter().any(|l| l.starts_with("  -1 ")));
+
+        let alias = texts(&execute_man(Some("cls".to_string()), Locale::En).output);
+        assert_eq!(
+            alias[0],
+            "clear - Clear terminal screen (earlier output stays recoverable)"
+        );
+    }
+
+    #[test]
+    fn man_reports_unknown_and_missing_topics() {
+        let unknown = execute_man(Some("vim".to_string()), Locale::En);
+        assert_eq!(texts(&unknown.output), vec!["No manual entry for vim"]);
+        assert_eq!(unknown.exit_code, 1);
+        assert_eq!(execute_man(None, Locale::En).exit_code, 1);
+    }
+
+    #[test]
+    fn resolve_name_classifies_builtins_aliases_and_unknowns() {
+        assert_eq!(resolve_name("ls"), NameResolution::Builtin);
+        assert_eq!(resolve_name("GREP"), NameResolution::Builtin);
+        assert_eq!(resolve_name("cls"), NameResolution::Alias("clear"));
+        assert_eq!(resolve_name("?"), NameResolution::Alias("help"));
+        assert_eq!(resolve_name("vim"), NameResolution::Unknown);
+        assert_eq!(resolve_name(""), NameResolution::Unknown);
+    }
+
+    #[test]
+    fn which_prints_one_line_per_name() {
+        let result = execute_which(vec!["ls".into(), "cls".into(), "vim".into()]);
+        assert_eq!(
+            texts(&result.output),
+            vec![
+                "ls: shell builtin",
+                "cls: aliased to 'clear'",
+                "which: no vim in commands",
+            ]
+        );
+        assert_eq!(result.exit_code, 1);
+        assert_eq!(execute_which(vec!["pwd".into()]).exit_code, 0);
+        assert_eq!(execute_which(Vec::new()).exit_code, 1);
+    }
+
+    #[test]
+    fn every_command_name_has_help() {
+        for name in Command::names() {
+            assert!(
+                CommandHelp::lookup(name).is_some(),
+                "missing help entry for {name}"
+            );
+        }
+    }
+
+    #[test]
+    fn every_help_entry_is_a_known_command() {
+        for help in CommandHelp::all() {
+            assert!(
+                Command::names().contains(&help.name),
+                "help entry for unknown command {}",
+                help.name
+            );
+        }
+    }
+
+    #[test]
+    fn lookup_accepts_aliases_and_case() {
+        assert_eq!(CommandHelp::lookup("cls").map(|h| h.name), Some("clear"));
+        assert_eq!(CommandHelp::lookup("?").map(|h| h.name), Some("help"));
+        assert_eq!(CommandHelp::lookup("LS").map(|h| h.name), Some("ls"));
+        assert!(CommandHelp::lookup("nope").is_none());
+    }
+
+    #[test]
+    fn render_includes_usage_flags_and_examples() {
+        let lines = texts(&CommandHelp::lookup("grep").unwrap().render());
+        assert_eq!(lines[0], "grep - Filter lines (regex by default)");
+        assert!(lines.contains(&"Usage: grep [-i] [-v] [-F] <pattern>".to_string()));
+        assert!(lines.contains(&"Flags:".to_string()));
+        assert!(lines.iter().any(|l| l.starts_with("  -i ")));
+        assert!(lines.contains(&"  ls | grep -i md".to_string()));
+    }
+
+    #[test]
+    fn every_synopsis_flag_has_a_table_entry() {
+        for help in CommandHelp::all() {
+            let flags = help.flags();
+            for flag in help
+                .synopsis
+                .split(['[', ']', '|', ' '])
+                // `-N` is a count placeholder, not a flag.
+                .filter(|word| word.len() > 1 && word.starts_with('-') && *word != "-N")
+            {
+                let known = |name: &str| flags.iter().any(|f| f.flag == name);
+                // Bundled short flags (`-neE`) count when each one is known.
+                let bundled =
+                    !flag.starts_with("--") && flag[1..].chars().all(|c| known(&format!("-{c}")));
+                assert!(
+                    known(flag) || bundled,
+                    "{}: {flag} in the synopsis has no flag table entry",
+                    help.name
+                );
+            }
+        }
+        let lines = texts(&CommandHelp::lookup("echo").unwrap().render());
+        assert!(lines.contains(&"Flags:".to_string()));
+        assert!(lines.iter().any(|l| l.starts_with("  -e ")));
+    }
+
+    #[test]
+    fn render_omits_empty_sections() {
+        let lines = texts(&CommandHelp::lookup("whoami").unwrap().render());
+        assert!(!lines.contains(&"Flags:".to_string()));
+        assert!(!lines.contains(&"Examples:".to_string()));
+    }
+
+    #[test]
+    fn listing_covers_every_command_and_appends_site_text() {
+        let result = execute_help(None, "  Tips:\n    - Use Tab", Locale::En);
+        let lines = texts(&result.output);
+        for help in CommandHelp::all() {
+            assert!(
+                lines
+                    .iter()
+                    .any(|l| l.trim_start().starts_with(help.synopsis)),
+                "listing is missing {}",
+                help.name
+            );
+        }
+        assert_eq!(lines.last().map(String::as_str), Some("    - Use Tab"));
+    }
+
+    #[test]
+    fn listing_groups_each_section_once() {
+        let mut seen: Vec<Message> = Vec::new();
+        for help in CommandHelp::all() {
+            if seen.last() != Some(&help.section) {
+                assert!(
+                    !seen.contains(&help.section),
+                    "{} is apart from the rest of its section",
+                    help.name
+                );
+                seen.push(help.section);
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_topic_suggests_closest_command() {
+        let result = execute_help(Some("gerp".to_string()), "", Locale::En);
+        assert_eq!(result.exit_code, 1);
+        assert_eq!(
+            texts(&result.output),
+            vec!["help: no help topic for 'gerp'. Did you mean 'grep'?"]
+        );
+    }
+
+    #[test]
+    fn headings_follow_the_locale() {
+        let listing = texts(&execute_help(None, "", Locale::Ko).output);
+        assert_eq!(listing[0], "사용할 수 있는 명령:");
+        assert!(listing.contains(&"  탐색:".to_string()));
+
+        let page = texts(&CommandHelp::lookup("grep").unwrap().render_in(Locale::Ko));
+        assert!(page.contains(&"사용법: grep [-i] [-v] [-F] <pattern>".to_string()));
+        assert!(page.contains(&"플래그:".to_string()));
+
+        let unknown = execute_man(Some("vim".to_string()), Locale::Ko);
+        assert_eq!(texts(&unknown.output), vec!["vim에 대한 매뉴얼이 없습니다"]);
+    }
+
+    #[test]
+    fn help_keys_lists_shortcuts_by_context() {
+        let lines = texts(&execute_help(Some("KEYS".to_string()), "", Locale::En).output);
+        assert_eq!(lines[0], "Keyboard shortcuts:");
+        assert!(lines.contains(&"  Reader:".to_string()));
+        assert!(
+            lines
+                .iter()
+                .any(|line| line.starts_with("    j / k ") && line.ends_with("scroll down / up"))
+        );
+        assert!(lines.iter().any(|line| line.starts_with("    n / p ")));
+    }
+
+    #[test]
+    fn suggest_command_finds_typos() {
+        assert_eq!(suggest_command("gerp"), Some("grep"));
+        assert_eq!(suggest_command("cta"), Some("cat"));
+        assert_eq!(suggest_command("sl"), Some("ls"));
+        assert_eq!(suggest_command("claer"), Some("clear"));
+        assert_eq!(suggest_command("THEM"), Some("theme"));
+        assert_eq!(suggest_command("whoamii"), Some("whoami"));
+    }
+
+    #[test]
+    fn suggest_command_rejects_distant_or_exact_names() {
+        assert_eq!(suggest_command("ls"), None);
+        assert_eq!(suggest_command("x"), None);
+        assert_eq!(suggest_command("python"), None);
+        assert_eq!(suggest_command(""), None);
+    }
+}
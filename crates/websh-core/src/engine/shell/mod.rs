@@ -2,24 +2,54 @@
 
 pub(crate) mod access;
 pub(crate) mod autocomplete;
+mod columns;
 pub(crate) mod config;
+mod error;
+mod events;
 mod executor;
+mod fetch;
 mod filters;
+mod help;
 mod model;
 mod output;
 pub(crate) mod parser;
 mod pipeline;
+mod prompt;
+mod session;
+mod stats;
+mod tutorial;
 
 pub use access::{AccessPolicy, AdminStatus};
-pub use autocomplete::{AutocompleteResult, autocomplete, get_hint};
-pub use executor::{execute_command, execute_command_with_context};
-pub use filters::apply_filter;
+pub use autocomplete::{AutocompleteResult, autocomplete, autocomplete_path, get_hint};
+pub use error::CommandError;
+pub use events::{AppEvent, AppEventKind, EventCategory};
+pub use executor::{
+    execute_command, execute_command_with_context, selection_zip_export, session_env,
+};
+pub use fetch::{FETCH_BODY_LIMIT, FetchLines, FetchResponse, fetch_response_lines};
+pub use filters::{apply_filter, apply_filter_in};
+pub use help::{CommandHelp, suggest_command};
 pub use model::{
-    AuthAction, AuthEffect, Command, CommandResult, EditorEffect, EnvironmentEffect,
-    ExecutionContext, FilesystemEffect, NavigationEffect, PathArg, RuntimeEffect, ShellEffect,
-    ShellText, SideEffect, SyncSubcommand, SystemEffect, SystemInfo, ThemeEffect, ViewEffect,
-    ViewMode,
+    AuthAction, AuthEffect, ChecksumTarget, Command, CommandFlag, CommandResult, EditorEffect,
+    EntryCopy, EntryCopyKind, EntryRemoval, EnvironmentEffect, ExecutionContext, FeedOutput,
+    FileStage, FilesystemEffect, ManifestSync, NavigationEffect, NavigationHistory, PathArg,
+    RuntimeEffect, ShellEffect, ShellText, SideEffect, StageFile, SyncSubcommand, SystemEffect,
+    SystemInfo, ThemeEffect, ViewEffect, ViewMode, ZipExport, ZipExportFile,
+};
+pub use output::{
+    ListCell, ListFormat, OutputLine, OutputLineData, OutputLineId, TextStyle, find_output_lines,
+    lines_to_plain_text,
+};
+pub use parser::{
+    ParsedCommand, Pipeline, parse_input, parse_input_with_env, parse_input_with_substitution,
+};
+pub use pipeline::{
+    execute_pipeline, execute_pipeline_with_context, file_stage_output, substitution_text,
+};
+pub use prompt::{Prompt, PromptSegment, PromptVars, render_prompt};
+pub use session::{
+    MAX_SESSION_SLOTS, RestorePlan, SessionAction, SessionChange, SessionSnapshot, SessionStore,
+    is_valid_session_name, plan_restore, restore_lines,
 };
-pub use output::{ListFormat, OutputLine, OutputLineData, OutputLineId, TextStyle};
-pub use parser::{parse_input, parse_input_with_env};
-pub use pipeline::{execute_pipeline, execute_pipeline_with_context};
+pub use stats::{MAX_TRACKED_KEYS, TRACKING_VAR, UsageReport, UsageStats, tracking_enabled};
+pub use tutorial::{TUTORIAL_STEPS, TutorialAction, TutorialProgress, TutorialStep};
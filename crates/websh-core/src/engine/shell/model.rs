@@ -3,7 +3,11 @@
 //! Command execution result type.
 
 use crate::engine::filesystem::RouteRequest;
-use crate::engine::shell::{AccessPolicy, OutputLine};
+use crate::engine::shell::{
+    AccessPolicy, AppEvent, CommandHelp, OutputLine, SessionAction, TutorialAction, UsageReport,
+};
+use crate::support::i18n::Locale;
+use crate::support::text::expand_backslash_escapes;
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum ViewMode {
@@ -21,6 +25,13 @@ pub enum ViewMode {
 pub enum SideEffect {
     /// Navigate to a new route.
     Navigate(RouteRequest),
+    /// Move `offset` entries through the navigation history: negative goes
+    /// back, positive forward.
+    JumpHistory {
+        offset: isize,
+    },
+    /// Forget the navigation history around the current route.
+    ClearNavigationHistory,
     /// Initiate wallet login (async).
     Login,
     /// Perform wallet logout.
@@ -35,6 +46,35 @@ pub enum SideEffect {
     },
     /// Request the target to list available color palettes.
     ListThemes,
+    /// Request the target to print the manifest lint report from the last load.
+    ReportManifestIssues,
+    /// Read `paths` and print those matching `query`, best first.
+    SearchContent {
+        query: String,
+        paths: Vec<crate::domain::VirtualPath>,
+    },
+    /// GET `url` and print the response. `filters` are the pipe stages to
+    /// run over the body once it arrives; empty when `fetch` is not piped.
+    Fetch {
+        url: String,
+        filters: Vec<super::parser::ParsedCommand>,
+    },
+    /// Read each target's bytes and print its SHA-256, or with `check`
+    /// compare it against the recorded checksum.
+    Checksum {
+        targets: Vec<ChecksumTarget>,
+        check: bool,
+    },
+    /// Read every file of a folder and download them as one ZIP archive.
+    ExportZip(ZipExport),
+    /// Read the files a piped `xargs cat` or `xargs wc` names and print
+    /// what [`crate::shell::file_stage_output`] makes of them.
+    ReadFiles(FileStage),
+    /// Download the text of a local draft as `filename`.
+    DownloadDraft {
+        filename: String,
+        content: String,
+    },
     /// Set a target-owned user environment variable.
     SetEnvVar {
         key: String,
@@ -44,8 +84,31 @@ pub enum SideEffect {
     UnsetEnvVar {
         key: String,
     },
+    /// Hide current terminal output; the ring buffer keeps it as scrollback.
+    ClearScreen,
     /// Reset the terminal output ring buffer.
     ClearHistory,
+    /// Copy the terminal session transcript to the clipboard.
+    CopySession,
+    /// Download the terminal session as a transcript file.
+    ExportSession {
+        filename: String,
+        html: bool,
+        cwd: crate::domain::VirtualPath,
+        mount: Option<crate::domain::RuntimeMount>,
+    },
+    /// Start, skip or reset the guided tour; the target owns its progress.
+    Tutorial(TutorialAction),
+    /// Save, load, list or remove session snapshots; the target reads
+    /// and applies the state and keeps the slots.
+    Session(SessionAction),
+    /// Zero the `stats` counters, this session's and the stored ones.
+    ResetUsageStats,
+    /// Render `feed` against the page URL and print, copy, or download it.
+    PublishFeed {
+        feed: crate::support::feed::AtomFeed,
+        output: FeedOutput,
+    },
 
     // Filesystem mutations
     ApplyChange {
@@ -63,6 +126,18 @@ pub enum SideEffect {
     },
     StageAll,
     UnstageAll,
+    /// Copy entries whose file content the target must read first, then
+    /// optionally remove the source (`mv`).
+    TransferEntries {
+        copies: Vec<EntryCopy>,
+        remove_source: Option<EntryRemoval>,
+    },
+    /// Append `text` to an existing file whose content the target must
+    /// read first (`tee -a`).
+    AppendFile {
+        path: crate::domain::VirtualPath,
+        text: String,
+    },
     Commit {
         message: String,
         mount_root: crate::domain::VirtualPath,
@@ -83,6 +158,8 @@ pub enum SideEffect {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum NavigationEffect {
     Navigate(RouteRequest),
+    JumpHistory { offset: isize },
+    ClearHistory,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -102,6 +179,91 @@ pub enum FilesystemEffect {
     },
     StageAll,
     UnstageAll,
+    TransferEntries {
+        copies: Vec<EntryCopy>,
+        remove_source: Option<EntryRemoval>,
+    },
+    AppendFile {
+        path: crate::domain::VirtualPath,
+        text: String,
+    },
+}
+
+/// One entry created by `cp`/`mv`, in creation order (parents first).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryCopy {
+    pub from: crate::domain::VirtualPath,
+    pub to: crate::domain::VirtualPath,
+    pub kind: EntryCopyKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EntryCopyKind {
+    Directory {
+        meta: crate::domain::NodeMetadata,
+    },
+    /// `overwrite` is set when `to` is an existing file being replaced.
+    File {
+        meta: crate::domain::NodeMetadata,
+        extensions: crate::domain::EntryExtensions,
+        overwrite: bool,
+    },
+}
+
+/// A file `sha256` reads, named as the user wrote it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChecksumTarget {
+    pub path: crate::domain::VirtualPath,
+    pub display: String,
+    /// The manifest's `content_sha256`, if any.
+    pub expected: Option<String>,
+}
+
+/// An `xargs cat` or `xargs wc` stage waiting on the files it reads.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileStage {
+    /// `cat`, or `wc` with its flags.
+    pub command: super::parser::ParsedCommand,
+    /// Files to read, named as they reached `xargs`.
+    pub files: Vec<StageFile>,
+    /// Files per run, from `xargs -n`; every `wc` run over more than one
+    /// file ends with a total.
+    pub per_run: usize,
+    /// Pipe stages after `xargs`, run over the output once it is ready.
+    pub filters: Vec<super::parser::ParsedCommand>,
+}
+
+/// A file an `xargs` stage reads, named as it reached `xargs`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StageFile {
+    pub path: crate::domain::VirtualPath,
+    pub display: String,
+}
+
+/// A folder `export-zip` packs into one download.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZipExport {
+    /// Download name, `talks.zip`; every entry sits under `talks/`.
+    pub filename: String,
+    pub files: Vec<ZipExportFile>,
+    /// Archive names of the files the wallet can't read, left out.
+    pub skipped: Vec<String>,
+    /// Sum of the recorded file sizes; files without one count as empty.
+    pub total_bytes: u64,
+}
+
+/// A file `export-zip` reads, and its name inside the archive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZipExportFile {
+    pub path: crate::domain::VirtualPath,
+    pub name: String,
+}
+
+/// Source removed by `mv` once every copy has been applied.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryRemoval {
+    pub path: crate::domain::VirtualPath,
+    pub is_directory: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -114,6 +276,25 @@ pub enum RuntimeEffect {
         mount_root: crate::domain::VirtualPath,
     },
     InvalidateRuntimeState,
+    ReportManifestIssues,
+    SearchContent {
+        query: String,
+        paths: Vec<crate::domain::VirtualPath>,
+    },
+    Fetch {
+        url: String,
+        filters: Vec<super::parser::ParsedCommand>,
+    },
+    Checksum {
+        targets: Vec<ChecksumTarget>,
+        check: bool,
+    },
+    ExportZip(ZipExport),
+    ReadFiles(FileStage),
+    DownloadDraft {
+        filename: String,
+        content: String,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -149,7 +330,22 @@ pub enum EditorEffect {
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SystemEffect {
+    ClearScreen,
     ClearHistory,
+    CopySession,
+    ExportSession {
+        filename: String,
+        html: bool,
+        cwd: crate::domain::VirtualPath,
+        mount: Option<crate::domain::RuntimeMount>,
+    },
+    Tutorial(TutorialAction),
+    Session(SessionAction),
+    ResetUsageStats,
+    PublishFeed {
+        feed: crate::support::feed::AtomFeed,
+        output: FeedOutput,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -169,6 +365,10 @@ impl From<SideEffect> for ShellEffect {
     fn from(effect: SideEffect) -> Self {
         match effect {
             SideEffect::Navigate(route) => Self::Navigation(NavigationEffect::Navigate(route)),
+            SideEffect::JumpHistory { offset } => {
+                Self::Navigation(NavigationEffect::JumpHistory { offset })
+            }
+            SideEffect::ClearNavigationHistory => Self::Navigation(NavigationEffect::ClearHistory),
             SideEffect::Login => Self::Auth(AuthEffect::Login),
             SideEffect::Logout => Self::Auth(AuthEffect::Logout),
             SideEffect::SwitchView(mode) => Self::View(ViewEffect::SwitchView(mode)),
@@ -177,13 +377,47 @@ impl From<SideEffect> for ShellEffect {
             }
             SideEffect::SetTheme { theme } => Self::Theme(ThemeEffect::SetTheme { theme }),
             SideEffect::ListThemes => Self::Theme(ThemeEffect::ListThemes),
+            SideEffect::ReportManifestIssues => Self::Runtime(RuntimeEffect::ReportManifestIssues),
+            SideEffect::SearchContent { query, paths } => {
+                Self::Runtime(RuntimeEffect::SearchContent { query, paths })
+            }
+            SideEffect::Fetch { url, filters } => {
+                Self::Runtime(RuntimeEffect::Fetch { url, filters })
+            }
+            SideEffect::Checksum { targets, check } => {
+                Self::Runtime(RuntimeEffect::Checksum { targets, check })
+            }
+            SideEffect::ExportZip(export) => Self::Runtime(RuntimeEffect::ExportZip(export)),
+            SideEffect::ReadFiles(stage) => Self::Runtime(RuntimeEffect::ReadFiles(stage)),
+            SideEffect::DownloadDraft { filename, content } => {
+                Self::Runtime(RuntimeEffect::DownloadDraft { filename, content })
+            }
             SideEffect::SetEnvVar { key, value } => {
                 Self::Environment(EnvironmentEffect::SetEnvVar { key, value })
             }
             SideEffect::UnsetEnvVar { key } => {
                 Self::Environment(EnvironmentEffect::UnsetEnvVar { key })
             }
+            SideEffect::ClearScreen => Self::System(SystemEffect::ClearScreen),
             SideEffect::ClearHistory => Self::System(SystemEffect::ClearHistory),
+            SideEffect::CopySession => Self::System(SystemEffect::CopySession),
+            SideEffect::ExportSession {
+                filename,
+                html,
+                cwd,
+                mount,
+            } => Self::System(SystemEffect::ExportSession {
+                filename,
+                html,
+                cwd,
+                mount,
+            }),
+            SideEffect::Tutorial(action) => Self::System(SystemEffect::Tutorial(action)),
+            SideEffect::Session(action) => Self::System(SystemEffect::Session(action)),
+            SideEffect::ResetUsageStats => Self::System(SystemEffect::ResetUsageStats),
+            SideEffect::PublishFeed { feed, output } => {
+                Self::System(SystemEffect::PublishFeed { feed, output })
+            }
             SideEffect::ApplyChange { path, change } => {
                 Self::Filesystem(FilesystemEffect::ApplyChange { path, change })
             }
@@ -198,6 +432,16 @@ impl From<SideEffect> for ShellEffect {
             }
             SideEffect::StageAll => Self::Filesystem(FilesystemEffect::StageAll),
             SideEffect::UnstageAll => Self::Filesystem(FilesystemEffect::UnstageAll),
+            SideEffect::TransferEntries {
+                copies,
+                remove_source,
+            } => Self::Filesystem(FilesystemEffect::TransferEntries {
+                copies,
+                remove_source,
+            }),
+            SideEffect::AppendFile { path, text } => {
+                Self::Filesystem(FilesystemEffect::AppendFile { path, text })
+            }
             SideEffect::Commit {
                 message,
                 mount_root,
@@ -322,6 +566,9 @@ impl CommandResult {
 use std::fmt;
 
 use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::ports::ScannedSubtree;
 
 /// A path argument passed to a command (e.g., `cd foo`, `cat bar.md`).
 ///
@@ -381,6 +628,47 @@ pub struct ExecutionContext {
     pub env: BTreeMap<String, String>,
     pub access_policy: AccessPolicy,
     pub shell_text: ShellText,
+    /// Width of the terminal in characters, when the target knows it.
+    /// Short `ls` listings lay out in columns, and `ls` points at a
+    /// directory's README, only when this is set.
+    pub terminal_columns: Option<usize>,
+    /// Current Unix time in seconds; `ls -l` dates are shown relative to it.
+    pub now: u64,
+    /// Local time zone as minutes east of UTC; `date` prints local time.
+    pub utc_offset_minutes: i32,
+    /// Routes visited around the current one, for `dirs` and `cd -N`.
+    pub navigation: NavigationHistory,
+    /// Recent app events, oldest first, for `dmesg`.
+    pub events: Vec<AppEvent>,
+    /// Local usage counters for `stats`.
+    pub usage: UsageReport,
+    /// The root manifest around the last sync that changed it, for
+    /// `ls --diff`.
+    pub manifest_sync: Option<Arc<ManifestSync>>,
+}
+
+impl ExecutionContext {
+    /// Locale the output is worded in, from `LANG`.
+    pub fn locale(&self) -> Locale {
+        Locale::from_env(&self.env)
+    }
+}
+
+/// The root manifest before and after the last sync that changed it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ManifestSync {
+    pub previous: ScannedSubtree,
+    pub current: ScannedSubtree,
+}
+
+/// Snapshot of the target's navigation history, as route paths.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NavigationHistory {
+    /// Earlier routes, most recent first.
+    pub back: Vec<String>,
+    pub current: Option<String>,
+    /// Routes left by going back, nearest first.
+    pub forward: Vec<String>,
 }
 
 /// Optional system facts supplied by the runtime shell.
@@ -388,6 +676,12 @@ pub struct ExecutionContext {
 pub struct SystemInfo {
     pub uptime: Option<String>,
     pub user_agent: Option<String>,
+    /// App version, with the build's git commit when known.
+    pub version: Option<String>,
+    /// Visible page area, e.g. `1280x720`.
+    pub viewport: Option<String>,
+    /// Lines currently held in the terminal output history.
+    pub history_lines: Option<usize>,
 }
 
 /// Target-owned static shell text.
@@ -412,24 +706,121 @@ impl Default for ShellText {
 /// Parsed terminal command
 #[derive(Clone, Debug)]
 pub enum Command {
-    /// List directory contents. bool = long format (-l)
+    /// List directory contents. `long` = long format (-l), `one_per_line`
+    /// = one entry per line instead of columns (-1)
     Ls {
         path: Option<PathArg>,
         long: bool,
+        one_per_line: bool,
     },
+    /// `ls --diff [dir]`: list a directory marking what the last manifest
+    /// sync added, removed or modified.
+    LsDiff(Option<PathArg>),
     Cd(PathArg),
-    Pwd,
+    /// `cd -N` / `cd +N`: move N entries back (negative) or forward through
+    /// the navigation history. `cd -` is `cd -1`.
+    CdHistory(isize),
+    /// `dirs [-c]`: list the navigation history, or clear it.
+    Dirs {
+        clear: bool,
+    },
+    /// `pwd [-L|-P]`: the logical path (as `$PWD` shows it), or with `-P`
+    /// the physical path within the mount holding it.
+    Pwd {
+        physical: bool,
+    },
     Cat(Option<PathArg>),
+    /// `explorer [path]`: open the content view at `path`, or at the
+    /// current directory.
+    Explorer(Option<PathArg>),
     Whoami,
     Id,
-    Help,
+    /// `date [+FORMAT]`: current local time, optionally strftime-formatted.
+    Date(Option<String>),
+    /// `neofetch` / `sysinfo`: profile art beside a system summary.
+    Neofetch,
+    /// `tags [tag]`: tag counts across the current mount, or the paths
+    /// carrying one tag.
+    Tags(Option<String>),
+    /// `search <query>`: full-text search over markdown in the current mount.
+    Search(String),
+    /// `manifest lint`: re-print the manifest report collected at load.
+    ManifestLint,
+    /// `fetch <url>`: GET a URL and print the response.
+    Fetch(String),
+    /// `sha256 [--check] <path>`: digest a file, or verify a file or every
+    /// file under a directory against the manifest.
+    Sha256 {
+        path: PathArg,
+        check: bool,
+    },
+    /// `dmesg [-f category]`: the app event log, optionally one category.
+    Dmesg {
+        category: Option<String>,
+    },
+    /// `stats [--reset]`: local usage counters, or clear them.
+    Stats {
+        reset: bool,
+    },
+    /// `help [command|keys]`. `None` lists every command.
+    Help(Option<String>),
+    /// `man <command>`: one command's help page.
+    Man(Option<String>),
+    /// `which <name>...`: report how each name resolves.
+    Which(Vec<String>),
     Theme(Option<String>),
+    /// Hide prior output; it stays in the buffer and can be shown again.
     Clear,
-    Echo(String),
+    /// Empty the terminal output buffer.
+    Reset,
+    /// Copy the terminal session to the clipboard.
+    Copy,
+    /// `export-session [--html] [file]`. `None` uses the default filename.
+    ExportSession {
+        filename: Option<String>,
+        html: bool,
+    },
+    /// `export-zip [dir]`: download a folder (default: the current one)
+    /// as a ZIP archive.
+    ExportZip(Option<PathArg>),
+    /// `export-draft <file>`: download a local draft's text.
+    ExportDraft(Option<PathArg>),
+    /// `tutorial [skip|reset]`: the guided tour.
+    Tutorial(TutorialAction),
+    /// `session [save|load|rm <name> | list | restore-last]`: named
+    /// snapshots of the route, theme and variables.
+    Session(SessionAction),
+    /// `feed [-c|-d] [dir]`: Atom feed of the markdown under `dir`
+    /// (default `~/blog`).
+    Feed {
+        path: Option<PathArg>,
+        output: FeedOutput,
+    },
+    /// `basename <path> [suffix]`: `path` without its directory, and
+    /// without `suffix` when it ends in one. Pure string work; nothing is
+    /// looked up.
+    Basename {
+        path: Option<String>,
+        suffix: Option<String>,
+    },
+    /// `dirname <path>`: `path` without its last component, or `.`.
+    Dirname(Option<String>),
+    /// `echo [-neE] [word ...]`. Words are kept as lexed, so quoted
+    /// whitespace survives; `-n` drops the trailing empty line and `-e`
+    /// expands backslash escapes.
+    Echo {
+        words: Vec<String>,
+        newline: bool,
+        escapes: bool,
+    },
     /// `export` command. Each element is one raw `KEY=value` assignment
     /// (or a bare `KEY` for display). Empty Vec prints all variables.
     Export(Vec<String>),
+    /// `env`: every variable as `KEY=value`, built-ins included.
+    Env,
     Unset(Option<String>),
+    /// `lang [code]`: show or set the interface language.
+    Lang(Option<String>),
     Login,
     Logout,
 
@@ -447,6 +838,15 @@ pub enum Command {
     Rmdir {
         path: PathArg,
     },
+    Mv {
+        src: PathArg,
+        dst: PathArg,
+    },
+    /// Copy a file, or a directory with everything under it.
+    Cp {
+        src: PathArg,
+        dst: PathArg,
+    },
     Edit {
         path: PathArg,
     },
@@ -469,6 +869,15 @@ pub enum SyncSubcommand {
     Auth(AuthAction),
 }
 
+/// Where `feed` sends the rendered document.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FeedOutput {
+    #[default]
+    Print,
+    Copy,
+    Download,
+}
+
 /// Auth token actions for `sync auth`.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AuthAction {
@@ -476,50 +885,465 @@ pub enum AuthAction {
     Clear,
 }
 
+/// A command-line flag accepted by a shell command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommandFlag {
+    /// Flag as typed, e.g. `-l` or `--ignore-case`.
+    pub flag: &'static str,
+    /// One-line description shown in help output.
+    pub description: &'static str,
+}
+
+impl CommandFlag {
+    const fn new(flag: &'static str, description: &'static str) -> Self {
+        Self { flag, description }
+    }
+}
+
+/// `-`, `-N` or `+N` as a navigation history offset for `cd`.
+fn parse_history_offset(arg: &str) -> Option<isize> {
+    if arg == "-" {
+        return Some(-1);
+    }
+    let (sign, digits) = match arg.split_at_checked(1)? {
+        ("-", digits) => (-1, digits),
+        ("+", digits) => (1, digits),
+        _ => return None,
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits
+        .parse::<isize>()
+        .ok()
+        .filter(|steps| *steps > 0)
+        .map(|steps| sign * steps)
+}
+
+/// Per-command flag table, keyed by the names in [`Command::names`].
+///
+/// Flags are listed in suggestion order: the first entry is the one offered
+/// as a ghost hint after a bare `-`.
+const COMMAND_FLAGS: &[(&str, &[CommandFlag])] = &[
+    (
+        "grep",
+        &[
+            CommandFlag::new("-i", "ignore case distinctions"),
+            CommandFlag::new("-v", "select non-matching lines"),
+            CommandFlag::new("-E", "extended regular expressions (default)"),
+            CommandFlag::new("-F", "match the pattern as a fixed string"),
+            CommandFlag::new("--ignore-case", "same as -i"),
+            CommandFlag::new("--invert-match", "same as -v"),
+            CommandFlag::new("--extended-regexp", "same as -E"),
+            CommandFlag::new("--fixed-strings", "same as -F"),
+            CommandFlag::new("--no-color", "do not highlight matches"),
+        ],
+    ),
+    (
+        "dirs",
+        &[CommandFlag::new("-c", "clear the navigation history")],
+    ),
+    (
+        "dmesg",
+        &[CommandFlag::new(
+            "-f",
+            "show one category: wallet, mount, manifest, fetch",
+        )],
+    ),
+    (
+        "echo",
+        &[
+            CommandFlag::new("-n", "no trailing empty line"),
+            CommandFlag::new("-e", "interpret \\n, \\t escapes"),
+            CommandFlag::new("-E", "no escape interpretation"),
+        ],
+    ),
+    (
+        "export-session",
+        &[CommandFlag::new(
+            "--html",
+            "export a styled HTML transcript",
+        )],
+    ),
+    (
+        "feed",
+        &[
+            CommandFlag::new("-c", "copy the feed to the clipboard"),
+            CommandFlag::new("-d", "download the feed as feed.xml"),
+        ],
+    ),
+    (
+        "head",
+        &[
+            CommandFlag::new("-n", "print the first N lines, or all but the last -N"),
+            CommandFlag::new("-c", "print the first N characters"),
+        ],
+    ),
+    (
+        "ls",
+        &[
+            CommandFlag::new("-l", "use a long listing format"),
+            CommandFlag::new("-1", "list one entry per line"),
+            CommandFlag::new("--diff", "mark changes since the previous manifest sync"),
+        ],
+    ),
+    (
+        "pwd",
+        &[
+            CommandFlag::new("-L", "print the logical path, as $PWD shows it (default)"),
+            CommandFlag::new("-P", "print the physical path within the mount"),
+        ],
+    ),
+    (
+        "rm",
+        &[
+            CommandFlag::new("-r", "remove directories and their contents"),
+            CommandFlag::new("--recursive", "same as -r"),
+        ],
+    ),
+    (
+        "sha256",
+        &[
+            CommandFlag::new("--check", "verify against the manifest checksums"),
+            CommandFlag::new("-c", "same as --check"),
+        ],
+    ),
+    (
+        "stats",
+        &[CommandFlag::new(
+            "--reset",
+            "clear the session and stored counters",
+        )],
+    ),
+    (
+        "tail",
+        &[
+            CommandFlag::new("-n", "print the last N lines"),
+            CommandFlag::new("-c", "print the last N characters"),
+        ],
+    ),
+    (
+        "tee",
+        &[
+            CommandFlag::new("-a", "append to the file instead of replacing it"),
+            CommandFlag::new("--append", "same as -a"),
+        ],
+    ),
+    (
+        "wc",
+        &[
+            CommandFlag::new("-l", "print the line count"),
+            CommandFlag::new("-w", "print the word count"),
+            CommandFlag::new("-c", "print the character count"),
+            CommandFlag::new("--lines", "same as -l"),
+            CommandFlag::new("--words", "same as -w"),
+            CommandFlag::new("--chars", "same as -c"),
+        ],
+    ),
+    (
+        "xargs",
+        &[CommandFlag::new("-n", "pass N input lines to each run")],
+    ),
+];
+
 impl Command {
     /// Get all available command names for autocomplete.
     ///
     /// Includes both regular commands and pipe filter commands.
     pub fn names() -> &'static [&'static str] {
         &[
-            "cat", "cd", "clear", "cls", "echo", "edit", "export", "grep", "head", "help", "id",
-            "login", "logout", "ls", "mkdir", "pwd", "rm", "rmdir", "sync", "tail", "theme",
-            "touch", "unset", "wc", "whoami",
+            "basename",
+            "cat",
+            "cd",
+            "clear",
+            "cls",
+            "copy",
+            "cp",
+            "date",
+            "dirname",
+            "dirs",
+            "dmesg",
+            "echo",
+            "edit",
+            "env",
+            "explorer",
+            "export",
+            "export-draft",
+            "export-session",
+            "export-zip",
+            "feed",
+            "fetch",
+            "grep",
+            "head",
+            "help",
+            "id",
+            "lang",
+            "login",
+            "logout",
+            "ls",
+            "man",
+            "manifest",
+            "mkdir",
+            "mv",
+            "neofetch",
+            "pwd",
+            "reset",
+            "rm",
+            "rmdir",
+            "search",
+            "session",
+            "sha256",
+            "stats",
+            "sync",
+            "sysinfo",
+            "tags",
+            "tail",
+            "tee",
+            "theme",
+            "touch",
+            "tutorial",
+            "unset",
+            "wc",
+            "which",
+            "whoami",
+            "xargs",
         ]
     }
 
+    /// Get the flags a command accepts, for autocomplete and help output.
+    ///
+    /// Returns an empty slice for commands without flags.
+    pub fn flags(name: &str) -> &'static [CommandFlag] {
+        let name = name.to_lowercase();
+        COMMAND_FLAGS
+            .iter()
+            .find(|(command, _)| *command == name)
+            .map_or(&[], |(_, flags)| flags)
+    }
+
     /// Parse command from name and arguments.
     pub fn parse(name: &str, args: &[String]) -> Self {
+        // `<command> --help` shows that command's help page. `echo` prints
+        // its arguments verbatim, so it is the one exception.
+        if args.iter().any(|arg| arg == "--help")
+            && !name.eq_ignore_ascii_case("echo")
+            && let Some(help) = CommandHelp::lookup(name)
+        {
+            return Self::Help(Some(help.name.to_string()));
+        }
+
         match name.to_lowercase().as_str() {
+            "ls" if args.iter().any(|arg| arg == "--diff") => {
+                match args
+                    .iter()
+                    .filter(|arg| *arg != "--diff")
+                    .collect::<Vec<_>>()
+                    .as_slice()
+                {
+                    [] => Self::LsDiff(None),
+                    [path] if !path.starts_with('-') => {
+                        Self::LsDiff(Some(PathArg::new(path.as_str())))
+                    }
+                    _ => Self::Unknown("ls".to_string()),
+                }
+            }
             "ls" => {
                 let mut long = false;
+                let mut one_per_line = false;
                 let mut path = None;
                 for arg in args {
                     if arg == "-l" {
                         long = true;
+                    } else if arg == "-1" {
+                        one_per_line = true;
                     } else if path.is_none() {
                         path = Some(PathArg::new(arg));
                     }
                 }
-                Self::Ls { path, long }
+                Self::Ls {
+                    path,
+                    long,
+                    one_per_line,
+                }
             }
-            "cd" => Self::Cd(
-                args.first()
-                    .map(PathArg::new)
-                    .unwrap_or_else(|| PathArg::new("~")),
-            ),
-            "pwd" => Self::Pwd,
+            "cd" => match args.first().and_then(|arg| parse_history_offset(arg)) {
+                Some(offset) => Self::CdHistory(offset),
+                None => Self::Cd(
+                    args.first()
+                        .map(PathArg::new)
+                        .unwrap_or_else(|| PathArg::new("~")),
+                ),
+            },
+            "dirs" => match args {
+                [] => Self::Dirs { clear: false },
+                [flag] if flag == "-c" => Self::Dirs { clear: true },
+                _ => Self::Unknown("dirs".to_string()),
+            },
+            "pwd" => match args {
+                [] => Self::Pwd { physical: false },
+                [flag] if flag == "-L" => Self::Pwd { physical: false },
+                [flag] if flag == "-P" => Self::Pwd { physical: true },
+                _ => Self::Unknown("pwd".to_string()),
+            },
             "cat" => Self::Cat(args.first().map(PathArg::new)),
+            "explorer" => match args {
+                [] => Self::Explorer(None),
+                [path] => Self::Explorer(Some(PathArg::new(path))),
+                _ => Self::Unknown("explorer".to_string()),
+            },
             "whoami" => Self::Whoami,
             "id" => Self::Id,
-            "help" | "?" => Self::Help,
+            "date" => match args {
+                [] => Self::Date(None),
+                [format] if format.starts_with('+') => Self::Date(Some(format[1..].to_string())),
+                _ => Self::Unknown("date".to_string()),
+            },
+            "neofetch" | "sysinfo" => Self::Neofetch,
+            "tags" => {
+                if args.len() > 1 {
+                    return Self::Unknown("tags".to_string());
+                }
+                Self::Tags(args.first().cloned())
+            }
+            "search" => {
+                let query = args.join(" ");
+                if query.trim().is_empty() {
+                    return Self::Unknown("search".to_string());
+                }
+                Self::Search(query)
+            }
+            "feed" => {
+                let mut output = FeedOutput::Print;
+                let mut path = None;
+                for arg in args {
+                    match arg.as_str() {
+                        "-c" => output = FeedOutput::Copy,
+                        "-d" => output = FeedOutput::Download,
+                        _ if path.is_none() && !arg.starts_with('-') => {
+                            path = Some(PathArg::new(arg))
+                        }
+                        _ => return Self::Unknown("feed".to_string()),
+                    }
+                }
+                Self::Feed { path, output }
+            }
+            "fetch" => match args {
+                [url] => Self::Fetch(url.clone()),
+                _ => Self::Unknown("fetch".to_string()),
+            },
+            "sha256" => match args {
+                [path] if !path.starts_with('-') => Self::Sha256 {
+                    path: PathArg::new(path),
+                    check: false,
+                },
+                [flag, path] if flag == "--check" || flag == "-c" => Self::Sha256 {
+                    path: PathArg::new(path),
+                    check: true,
+                },
+                _ => Self::Unknown("sha256".to_string()),
+            },
+            "dmesg" => match args {
+                [] => Self::Dmesg { category: None },
+                [flag, category] if flag == "-f" => Self::Dmesg {
+                    category: Some(category.clone()),
+                },
+                _ => Self::Unknown("dmesg".to_string()),
+            },
+            "stats" => match args {
+                [] => Self::Stats { reset: false },
+                [flag] if flag == "--reset" => Self::Stats { reset: true },
+                _ => Self::Unknown("stats".to_string()),
+            },
+            "manifest" => match args {
+                [sub] if sub == "lint" => Self::ManifestLint,
+                _ => Self::Unknown("manifest".to_string()),
+            },
+            "help" | "?" => {
+                if args.len() > 1 {
+                    return Self::Unknown("help".to_string());
+                }
+                Self::Help(args.first().cloned())
+            }
             "theme" => {
                 if args.len() > 1 {
                     return Self::Unknown("theme".to_string());
                 }
                 Self::Theme(args.first().cloned())
             }
+            "man" => {
+                if args.len() > 1 {
+                    return Self::Unknown("man".to_string());
+                }
+                Self::Man(args.first().cloned())
+            }
+            "which" => Self::Which(args.to_vec()),
             "clear" | "cls" => Self::Clear,
+            "reset" => Self::Reset,
+            "copy" => {
+                if !args.is_empty() {
+                    return Self::Unknown("copy".to_string());
+                }
+                Self::Copy
+            }
+            "export-session" => {
+                let mut html = false;
+                let mut filename = None;
+                for arg in args {
+                    if arg == "--html" {
+                        html = true;
+                    } else if filename.is_none() {
+                        filename = Some(arg.clone());
+                    } else {
+                        return Self::Unknown("export-session".to_string());
+                    }
+                }
+                Self::ExportSession { filename, html }
+            }
+            "export-draft" => match args {
+                [] => Self::ExportDraft(None),
+                [path] => Self::ExportDraft(Some(PathArg::new(path))),
+                _ => Self::Unknown("export-draft".to_string()),
+            },
+            "export-zip" => match args {
+                [] => Self::ExportZip(None),
+                [path] if !path.starts_with('-') => Self::ExportZip(Some(PathArg::new(path))),
+                _ => Self::Unknown("export-zip".to_string()),
+            },
+            "tutorial" => match args {
+                [] => Self::Tutorial(TutorialAction::Start),
+                [action] if action == "skip" => Self::Tutorial(TutorialAction::Skip),
+                [action] if action == "reset" => Self::Tutorial(TutorialAction::Reset),
+                _ => Self::Unknown("tutorial".to_string()),
+            },
+            "session" => match args {
+                [] => Self::Session(SessionAction::List),
+                [sub] if sub == "list" => Self::Session(SessionAction::List),
+                [sub] if sub == "restore-last" => Self::Session(SessionAction::RestoreLast),
+                [sub, name] if sub == "save" => Self::Session(SessionAction::Save(name.clone())),
+                [sub, name] if sub == "load" => Self::Session(SessionAction::Load(name.clone())),
+                [sub, name] if sub == "rm" => Self::Session(SessionAction::Remove(name.clone())),
+                _ => Self::Unknown("session".to_string()),
+            },
+            "basename" => match args {
+                [] => Self::Basename {
+                    path: None,
+                    suffix: None,
+                },
+                [path] => Self::Basename {
+                    path: Some(path.clone()),
+                    suffix: None,
+                },
+                [path, suffix] => Self::Basename {
+                    path: Some(path.clone()),
+                    suffix: Some(suffix.clone()),
+                },
+                _ => Self::Unknown("basename".to_string()),
+            },
+            "dirname" => match args {
+                [] => Self::Dirname(None),
+                [path] => Self::Dirname(Some(path.clone())),
+                _ => Self::Unknown("dirname".to_string()),
+            },
             "echo" => {
                 // Scan args for a whole-token redirect operator ">".
                 // The lexer strips quotes, so a quoted `">"` arrives as a
@@ -529,9 +1353,13 @@ impl Command {
                 // still parsed as a redirect here; that matches the
                 // tokenizer's declared contract (quotes are lost after
                 // lexing).
-                if let Some(idx) = args.iter().position(|a| a == ">") {
-                    let body = args[..idx].join(" ");
-                    let targets = &args[idx + 1..];
+                let (newline, escapes, words) = parse_echo_flags(args);
+                if let Some(idx) = words.iter().position(|a| a == ">") {
+                    let mut body = words[..idx].join(" ");
+                    if escapes {
+                        body = expand_backslash_escapes(&body).0;
+                    }
+                    let targets = &words[idx + 1..];
                     if body.is_empty() || targets.len() != 1 {
                         return Self::Unknown("echo".to_string());
                     }
@@ -540,11 +1368,22 @@ impl Command {
                         path: PathArg::new(&targets[0]),
                     }
                 } else {
-                    Self::Echo(args.join(" "))
+                    Self::Echo {
+                        words: words.to_vec(),
+                        newline,
+                        escapes,
+                    }
                 }
             }
             "export" => Self::Export(args.to_vec()),
+            "env" => Self::Env,
             "unset" => Self::Unset(args.first().cloned()),
+            "lang" => {
+                if args.len() > 1 {
+                    return Self::Unknown("lang".to_string());
+                }
+                Self::Lang(args.first().cloned())
+            }
             "login" => Self::Login,
             "logout" => Self::Logout,
             "touch" => {
@@ -588,6 +1427,17 @@ impl Command {
                     recursive,
                 }
             }
+            "mv" | "cp" => {
+                let [src, dst] = args else {
+                    return Self::Unknown(name.to_lowercase());
+                };
+                let (src, dst) = (PathArg::new(src), PathArg::new(dst));
+                if name.eq_ignore_ascii_case("mv") {
+                    Self::Mv { src, dst }
+                } else {
+                    Self::Cp { src, dst }
+                }
+            }
             "edit" => {
                 if args.len() != 1 {
                     return Self::Unknown("edit".to_string());
@@ -631,6 +1481,32 @@ impl Command {
     }
 }
 
+/// Split leading `echo` options (`-n`, `-e`, `-E`, or a run like `-ne`) from
+/// the words to print. Returns `(newline, escapes, words)`; the first
+/// argument that is not an option ends option parsing, as in bash.
+fn parse_echo_flags(args: &[String]) -> (bool, bool, &[String]) {
+    let mut newline = true;
+    let mut escapes = false;
+    let mut consumed = 0;
+    for arg in args {
+        let Some(letters) = arg.strip_prefix('-') else {
+            break;
+        };
+        if letters.is_empty() || !letters.chars().all(|c| matches!(c, 'n' | 'e' | 'E')) {
+            break;
+        }
+        for letter in letters.chars() {
+            match letter {
+                'n' => newline = false,
+                'e' => escapes = true,
+                _ => escapes = false,
+            }
+        }
+        consumed += 1;
+    }
+    (newline, escapes, &args[consumed..])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -663,26 +1539,78 @@ mod tests {
             Command::parse("ls", &[]),
             Command::Ls {
                 path: None,
-                long: false
+                long: false,
+                ..
             }
         ));
         assert!(matches!(
             Command::parse("ls", &args(&["projects"])),
-            Command::Ls { path: Some(ref p), long: false } if p == "projects"
+            Command::Ls { path: Some(ref p), long: false, .. } if p == "projects"
         ));
         assert!(matches!(
             Command::parse("ls", &args(&["-l"])),
             Command::Ls {
                 path: None,
-                long: true
+                long: true,
+                ..
             }
         ));
         assert!(matches!(
             Command::parse("ls", &args(&["-l", "blog"])),
-            Command::Ls { path: Some(ref p), long: true } if p == "blog"
+            Command::Ls { path: Some(ref p), long: true, .. } if p == "blog"
+        ));
+        assert!(matches!(
+            Command::parse("ls", &args(&["-1", "blog"])),
+            Command::Ls { path: Some(ref p), long: false, one_per_line: true } if p == "blog"
+        ));
+        assert!(matches!(
+            Command::parse("ls", &[]),
+            Command::Ls {
+                one_per_line: false,
+                ..
+            }
         ));
     }
 
+    #[test]
+    fn test_parse_ls_diff() {
+        assert!(matches!(
+            Command::parse("ls", &args(&["--diff"])),
+            Command::LsDiff(None)
+        ));
+        assert!(matches!(
+            Command::parse("ls", &args(&["--diff", "blog"])),
+            Command::LsDiff(Some(ref p)) if p == "blog"
+        ));
+        assert!(matches!(
+            Command::parse("ls", &args(&["blog", "--diff"])),
+            Command::LsDiff(Some(ref p)) if p == "blog"
+        ));
+        assert!(matches!(
+            Command::parse("ls", &args(&["--diff", "-l"])),
+            Command::Unknown(_)
+        ));
+        assert!(matches!(
+            Command::parse("ls", &args(&["--diff", "a", "b"])),
+            Command::Unknown(_)
+        ));
+    }
+
+    #[test]
+    fn test_flag_table_covers_known_commands_only() {
+        for (name, flags) in COMMAND_FLAGS {
+            assert!(
+                Command::names().contains(name),
+                "flag table entry for unknown command: {name}"
+            );
+            assert!(!flags.is_empty(), "empty flag list for {name}");
+        }
+        assert_eq!(Command::flags("ls")[0].flag, "-l");
+        assert_eq!(Command::flags("LS")[0].flag, "-l");
+        assert!(Command::flags("cd").is_empty());
+        assert!(Command::flags("nonexistent").is_empty());
+    }
+
     #[test]
     fn test_parse_cd() {
         assert!(matches!(
@@ -695,6 +1623,114 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_parse_cd_history_offsets() {
+        assert!(matches!(
+            Command::parse("cd", &args(&["-"])),
+            Command::CdHistory(-1)
+        ));
+        assert!(matches!(
+            Command::parse("cd", &args(&["-2"])),
+            Command::CdHistory(-2)
+        ));
+        assert!(matches!(
+            Command::parse("cd", &args(&["+3"])),
+            Command::CdHistory(3)
+        ));
+        for path in ["-0", "+", "-x", "-2a", "+1.5", "2"] {
+            assert!(
+                matches!(Command::parse("cd", &args(&[path])), Command::Cd(_)),
+                "{path} is a path"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_dirs() {
+        assert!(matches!(
+            Command::parse("dirs", &[]),
+            Command::Dirs { clear: false }
+        ));
+        assert!(matches!(
+            Command::parse("dirs", &args(&["-c"])),
+            Command::Dirs { clear: true }
+        ));
+        assert!(matches!(
+            Command::parse("dirs", &args(&["-x"])),
+            Command::Unknown(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_dmesg() {
+        assert!(matches!(
+            Command::parse("dmesg", &[]),
+            Command::Dmesg { category: None }
+        ));
+        assert!(matches!(
+            Command::parse("dmesg", &args(&["-f", "wallet"])),
+            Command::Dmesg { category: Some(ref c) } if c == "wallet"
+        ));
+        for bad in [&["-f"][..], &["wallet"], &["-x", "wallet"]] {
+            assert!(matches!(
+                Command::parse("dmesg", &args(bad)),
+                Command::Unknown(ref name) if name == "dmesg"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_stats() {
+        assert!(matches!(
+            Command::parse("stats", &[]),
+            Command::Stats { reset: false }
+        ));
+        assert!(matches!(
+            Command::parse("stats", &args(&["--reset"])),
+            Command::Stats { reset: true }
+        ));
+        assert!(matches!(
+            Command::parse("stats", &args(&["-r"])),
+            Command::Unknown(ref name) if name == "stats"
+        ));
+    }
+
+    #[test]
+    fn test_parse_pwd() {
+        assert!(matches!(
+            Command::parse("pwd", &[]),
+            Command::Pwd { physical: false }
+        ));
+        assert!(matches!(
+            Command::parse("pwd", &args(&["-L"])),
+            Command::Pwd { physical: false }
+        ));
+        assert!(matches!(
+            Command::parse("pwd", &args(&["-P"])),
+            Command::Pwd { physical: true }
+        ));
+        assert!(matches!(
+            Command::parse("pwd", &args(&["-P", "-L"])),
+            Command::Unknown(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_explorer() {
+        assert!(matches!(
+            Command::parse("explorer", &[]),
+            Command::Explorer(None)
+        ));
+        assert!(matches!(
+            Command::parse("explorer", &args(&["blog"])),
+            Command::Explorer(Some(ref p)) if p == "blog"
+        ));
+        assert!(matches!(
+            Command::parse("explorer", &args(&["a", "b"])),
+            Command::Unknown(_)
+        ));
+    }
+
     #[test]
     fn test_parse_cat() {
         assert!(matches!(
@@ -708,6 +1744,143 @@ mod tests {
         assert!(matches!(Command::parse("cat", &[]), Command::Cat(None)));
     }
 
+    #[test]
+    fn test_parse_sha256() {
+        assert!(matches!(
+            Command::parse("sha256", &args(&["blog/a.md"])),
+            Command::Sha256 { ref path, check: false } if path == "blog/a.md"
+        ));
+        assert!(matches!(
+            Command::parse("sha256", &args(&["--check", "blog"])),
+            Command::Sha256 { ref path, check: true } if path == "blog"
+        ));
+        assert!(matches!(
+            Command::parse("sha256", &args(&["-c", "blog"])),
+            Command::Sha256 { check: true, .. }
+        ));
+        for bad in [&[][..], &["-x"], &["a", "b"], &["--check"]] {
+            assert!(matches!(
+                Command::parse("sha256", &args(bad)),
+                Command::Unknown(ref name) if name == "sha256"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_feed() {
+        assert!(matches!(
+            Command::parse("feed", &[]),
+            Command::Feed {
+                path: None,
+                output: FeedOutput::Print
+            }
+        ));
+        assert!(matches!(
+            Command::parse("feed", &args(&["-d", "notes"])),
+            Command::Feed {
+                path: Some(ref p),
+                output: FeedOutput::Download
+            } if p == "notes"
+        ));
+        assert!(matches!(
+            Command::parse("feed", &args(&["-c"])),
+            Command::Feed {
+                output: FeedOutput::Copy,
+                ..
+            }
+        ));
+        assert!(matches!(
+            Command::parse("feed", &args(&["a", "b"])),
+            Command::Unknown(ref name) if name == "feed"
+        ));
+        assert!(matches!(
+            Command::parse("feed", &args(&["-x"])),
+            Command::Unknown(ref name) if name == "feed"
+        ));
+    }
+
+    #[test]
+    fn test_parse_tutorial() {
+        assert!(matches!(
+            Command::parse("tutorial", &[]),
+            Command::Tutorial(TutorialAction::Start)
+        ));
+        assert!(matches!(
+            Command::parse("tutorial", &args(&["skip"])),
+            Command::Tutorial(TutorialAction::Skip)
+        ));
+        assert!(matches!(
+            Command::parse("tutorial", &args(&["reset"])),
+            Command::Tutorial(TutorialAction::Reset)
+        ));
+        assert!(matches!(
+            Command::parse("tutorial", &args(&["again"])),
+            Command::Unknown(ref name) if name == "tutorial"
+        ));
+    }
+
+    #[test]
+    fn test_parse_basename_and_dirname() {
+        assert!(matches!(
+            Command::parse("basename", &args(&["/a/b.md"])),
+            Command::Basename { path: Some(ref path), suffix: None } if path == "/a/b.md"
+        ));
+        assert!(matches!(
+            Command::parse("basename", &args(&["/a/b.md", ".md"])),
+            Command::Basename { path: Some(_), suffix: Some(ref suffix) } if suffix == ".md"
+        ));
+        assert!(matches!(
+            Command::parse("basename", &[]),
+            Command::Basename { path: None, .. }
+        ));
+        assert!(matches!(
+            Command::parse("basename", &args(&["a", "b", "c"])),
+            Command::Unknown(ref name) if name == "basename"
+        ));
+        assert!(matches!(
+            Command::parse("dirname", &args(&["/a/b.md"])),
+            Command::Dirname(Some(ref path)) if path == "/a/b.md"
+        ));
+        assert!(matches!(
+            Command::parse("dirname", &args(&["a", "b"])),
+            Command::Unknown(ref name) if name == "dirname"
+        ));
+    }
+
+    #[test]
+    fn test_parse_session() {
+        assert!(matches!(
+            Command::parse("session", &[]),
+            Command::Session(SessionAction::List)
+        ));
+        assert!(matches!(
+            Command::parse("session", &args(&["list"])),
+            Command::Session(SessionAction::List)
+        ));
+        assert!(matches!(
+            Command::parse("session", &args(&["save", "work"])),
+            Command::Session(SessionAction::Save(ref name)) if name == "work"
+        ));
+        assert!(matches!(
+            Command::parse("session", &args(&["load", "work"])),
+            Command::Session(SessionAction::Load(ref name)) if name == "work"
+        ));
+        assert!(matches!(
+            Command::parse("session", &args(&["rm", "work"])),
+            Command::Session(SessionAction::Remove(ref name)) if name == "work"
+        ));
+        assert!(matches!(
+            Command::parse("session", &args(&["restore-last"])),
+            Command::Session(SessionAction::RestoreLast)
+        ));
+        for bad in [&["save"][..], &["load", "a", "b"], &["drop", "a"]] {
+            assert!(matches!(
+                Command::parse("session", &args(bad)),
+                Command::Unknown(ref name) if name == "session"
+            ));
+        }
+    }
+
     #[test]
     fn test_parse_export() {
         assert!(matches!(
@@ -737,27 +1910,261 @@ mod tests {
         assert!(matches!(Command::parse("unset", &[]), Command::Unset(None)));
     }
 
+    #[test]
+    fn test_parse_lang() {
+        assert!(matches!(Command::parse("lang", &[]), Command::Lang(None)));
+        assert!(matches!(
+            Command::parse("lang", &args(&["ko"])),
+            Command::Lang(Some(ref code)) if code == "ko"
+        ));
+        assert!(matches!(
+            Command::parse("lang", &args(&["ko", "en"])),
+            Command::Unknown(ref name) if name == "lang"
+        ));
+        assert!(matches!(Command::parse("env", &[]), Command::Env));
+    }
+
     #[test]
     fn test_parse_case_insensitive() {
         assert!(matches!(
             Command::parse("LS", &[]),
             Command::Ls {
                 path: None,
-                long: false
+                long: false,
+                ..
             }
         ));
         assert!(matches!(
             Command::parse("CD", &args(&["/"])),
             Command::Cd(_)
         ));
-        assert!(matches!(Command::parse("HELP", &[]), Command::Help));
+        assert!(matches!(Command::parse("HELP", &[]), Command::Help(None)));
         assert!(matches!(Command::parse("CleAr", &[]), Command::Clear));
     }
 
     #[test]
     fn test_parse_aliases() {
-        assert!(matches!(Command::parse("?", &[]), Command::Help));
+        assert!(matches!(Command::parse("?", &[]), Command::Help(None)));
         assert!(matches!(Command::parse("cls", &[]), Command::Clear));
+        assert!(matches!(Command::parse("sysinfo", &[]), Command::Neofetch));
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        assert!(matches!(Command::parse("tags", &[]), Command::Tags(None)));
+        assert!(matches!(
+            Command::parse("tags", &args(&["rust"])),
+            Command::Tags(Some(tag)) if tag == "rust"
+        ));
+        assert!(matches!(
+            Command::parse("tags", &args(&["a", "b"])),
+            Command::Unknown(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert!(matches!(Command::parse("date", &[]), Command::Date(None)));
+        assert!(matches!(
+            Command::parse("date", &args(&["+%Y-%m-%d"])),
+            Command::Date(Some(format)) if format == "%Y-%m-%d"
+        ));
+        assert!(matches!(
+            Command::parse("date", &args(&["%Y"])),
+            Command::Unknown(name) if name == "date"
+        ));
+    }
+
+    #[test]
+    fn test_parse_search() {
+        assert!(matches!(
+            Command::parse("search", &args(&["rust", "wasm"])),
+            Command::Search(query) if query == "rust wasm"
+        ));
+        assert!(matches!(
+            Command::parse("search", &[]),
+            Command::Unknown(name) if name == "search"
+        ));
+    }
+
+    #[test]
+    fn test_parse_fetch() {
+        assert!(matches!(
+            Command::parse("fetch", &args(&["https://api.github.com/zen"])),
+            Command::Fetch(url) if url == "https://api.github.com/zen"
+        ));
+        assert!(matches!(
+            Command::parse("fetch", &[]),
+            Command::Unknown(name) if name == "fetch"
+        ));
+        assert!(matches!(
+            Command::parse("fetch", &args(&["a", "b"])),
+            Command::Unknown(name) if name == "fetch"
+        ));
+    }
+
+    #[test]
+    fn test_parse_manifest_lint() {
+        assert!(matches!(
+            Command::parse("manifest", &args(&["lint"])),
+            Command::ManifestLint
+        ));
+        assert!(matches!(
+            Command::parse("manifest", &[]),
+            Command::Unknown(name) if name == "manifest"
+        ));
+        assert!(matches!(
+            Command::parse("manifest", &args(&["lint", "extra"])),
+            Command::Unknown(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_man() {
+        assert!(matches!(
+            Command::parse("man", &args(&["grep"])),
+            Command::Man(Some(ref topic)) if topic == "grep"
+        ));
+        assert!(matches!(Command::parse("man", &[]), Command::Man(None)));
+        assert!(matches!(
+            Command::parse("man", &args(&["ls", "cd"])),
+            Command::Unknown(ref name) if name == "man"
+        ));
+    }
+
+    #[test]
+    fn test_parse_which() {
+        assert!(matches!(
+            Command::parse("which", &args(&["ls", "cls"])),
+            Command::Which(ref names) if names == &args(&["ls", "cls"])
+        ));
+        assert!(matches!(
+            Command::parse("which", &[]),
+            Command::Which(ref names) if names.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_parse_copy() {
+        assert!(matches!(Command::parse("copy", &[]), Command::Copy));
+        assert!(matches!(
+            Command::parse("copy", &args(&["extra"])),
+            Command::Unknown(ref name) if name == "copy"
+        ));
+    }
+
+    #[test]
+    fn test_parse_export_session() {
+        assert!(matches!(
+            Command::parse("export-session", &[]),
+            Command::ExportSession {
+                filename: None,
+                html: false
+            }
+        ));
+        assert!(matches!(
+            Command::parse("export-session", &args(&["--html", "log.html"])),
+            Command::ExportSession {
+                filename: Some(ref name),
+                html: true
+            } if name == "log.html"
+        ));
+        assert!(matches!(
+            Command::parse("export-session", &args(&["a.txt", "b.txt"])),
+            Command::Unknown(ref name) if name == "export-session"
+        ));
+    }
+
+    #[test]
+    fn test_parse_export_draft() {
+        assert!(matches!(
+            Command::parse("export-draft", &[]),
+            Command::ExportDraft(None)
+        ));
+        assert!(matches!(
+            Command::parse("export-draft", &args(&["notes.md"])),
+            Command::ExportDraft(Some(ref path)) if path == "notes.md"
+        ));
+        assert!(matches!(
+            Command::parse("export-draft", &args(&["a.md", "b.md"])),
+            Command::Unknown(ref name) if name == "export-draft"
+        ));
+    }
+
+    #[test]
+    fn test_parse_export_zip() {
+        assert!(matches!(
+            Command::parse("export-zip", &[]),
+            Command::ExportZip(None)
+        ));
+        assert!(matches!(
+            Command::parse("export-zip", &args(&["talks"])),
+            Command::ExportZip(Some(ref path)) if path == "talks"
+        ));
+        for bad in [&["-r"][..], &["a", "b"]] {
+            assert!(matches!(
+                Command::parse("export-zip", &args(bad)),
+                Command::Unknown(ref name) if name == "export-zip"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_mv_and_cp() {
+        assert!(matches!(
+            Command::parse("mv", &args(&["a.md", "b.md"])),
+            Command::Mv { ref src, ref dst } if src == "a.md" && dst == "b.md"
+        ));
+        assert!(matches!(
+            Command::parse("CP", &args(&["a", "b"])),
+            Command::Cp { ref src, ref dst } if src == "a" && dst == "b"
+        ));
+        assert!(matches!(
+            Command::parse("mv", &args(&["a.md"])),
+            Command::Unknown(ref name) if name == "mv"
+        ));
+        assert!(matches!(
+            Command::parse("cp", &args(&["a", "b", "c"])),
+            Command::Unknown(ref name) if name == "cp"
+        ));
+    }
+
+    #[test]
+    fn test_parse_reset() {
+        assert!(matches!(Command::parse("reset", &[]), Command::Reset));
+        assert!(matches!(Command::parse("RESET", &[]), Command::Reset));
+    }
+
+    #[test]
+    fn test_parse_help_topic() {
+        assert!(matches!(
+            Command::parse("help", &args(&["ls"])),
+            Command::Help(Some(ref topic)) if topic == "ls"
+        ));
+        assert!(matches!(
+            Command::parse("help", &args(&["ls", "cd"])),
+            Command::Unknown(ref name) if name == "help"
+        ));
+    }
+
+    #[test]
+    fn test_parse_dash_dash_help() {
+        assert!(matches!(
+            Command::parse("ls", &args(&["--help"])),
+            Command::Help(Some(ref topic)) if topic == "ls"
+        ));
+        assert!(matches!(
+            Command::parse("cls", &args(&["--help"])),
+            Command::Help(Some(ref topic)) if topic == "clear"
+        ));
+        assert!(matches!(
+            Command::parse("grep", &args(&["-i", "--help"])),
+            Command::Help(Some(ref topic)) if topic == "grep"
+        ));
+        assert!(matches!(
+            Command::parse("echo", &args(&["--help"])),
+            Command::Echo { ref words, .. } if words == &["--help"]
+        ));
     }
 
     #[test]
@@ -791,7 +2198,7 @@ mod tests {
         assert!(names.contains(&"login"));
         assert!(names.contains(&"logout"));
         assert!(names.contains(&"theme"));
-        assert!(!names.contains(&"explorer"));
+        assert!(names.contains(&"explorer"));
         // Filter commands should be included for autocomplete
         assert!(names.contains(&"grep"));
         assert!(names.contains(&"head"));
@@ -832,6 +2239,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_piped_fetch_carries_its_filters() {
+        use crate::domain::ChangeSet;
+        use crate::domain::{VirtualPath, WalletState};
+        use crate::engine::filesystem::GlobalFs;
+        use crate::engine::shell::parser::parse_input;
+
+        let fs = GlobalFs::empty();
+        let run = |input: &str| {
+            execute_pipeline(
+                &parse_input(input, &[]),
+                &WalletState::Disconnected,
+                &runtime_mounts(),
+                &fs,
+                &VirtualPath::root(),
+                &ChangeSet::new(),
+                None,
+            )
+        };
+
+        let piped = run("fetch https://api.github.com/zen | grep -i design | head -1");
+        let Some(super::SideEffect::Fetch { url, filters }) = piped.side_effects.first() else {
+            panic!("expected a fetch, got {:?}", piped.side_effects);
+        };
+        assert_eq!(url, "https://api.github.com/zen");
+        let stages: Vec<_> = filters
+            .iter()
+            .map(|stage| (stage.name.as_str(), stage.args.join(" ")))
+            .collect();
+        assert_eq!(
+            stages,
+            [
+                ("grep", "-i design".to_string()),
+                ("head", "-1".to_string())
+            ]
+        );
+
+        let alone = run("fetch https://api.github.com/zen");
+        assert_eq!(
+            alone.side_effects,
+            vec![super::SideEffect::Fetch {
+                url: "https://api.github.com/zen".to_string(),
+                filters: Vec::new(),
+            }]
+        );
+    }
+
     #[test]
     fn test_pipeline_drops_side_effect_when_piped() {
         // When a command has filters attached, side effects are discarded.
@@ -858,6 +2312,48 @@ mod tests {
         assert!(result.side_effects.first().cloned().is_none());
     }
 
+    #[test]
+    fn test_piped_ls_lists_one_entry_per_line() {
+        use crate::domain::{ChangeSet, EntryExtensions, NodeMetadata, VirtualPath, WalletState};
+        use crate::engine::filesystem::GlobalFs;
+        use crate::engine::shell::execute_pipeline_with_context;
+        use crate::engine::shell::parser::parse_input;
+
+        let mut fs = GlobalFs::empty();
+        for name in ["a.md", "b.md", "c.md"] {
+            fs.upsert_file(
+                VirtualPath::root().join(name),
+                String::new(),
+                NodeMetadata::default(),
+                EntryExtensions::default(),
+            );
+        }
+        let context = ExecutionContext {
+            terminal_columns: Some(80),
+            ..ExecutionContext::default()
+        };
+        let run = |input: &str| {
+            execute_pipeline_with_context(
+                &parse_input(input, &[]),
+                &WalletState::Disconnected,
+                &runtime_mounts(),
+                &fs,
+                &VirtualPath::root(),
+                &ChangeSet::new(),
+                None,
+                &context,
+            )
+        };
+
+        assert_eq!(run("ls").output.len(), 1);
+        let piped = run("ls | head -5");
+        assert_eq!(piped.output.len(), 3);
+        assert!(matches!(
+            &piped.output[0].data,
+            crate::engine::shell::OutputLineData::ListEntry { name, .. } if name == "a.md"
+        ));
+    }
+
     #[test]
     fn test_pipeline_exit_code_is_last_stage() {
         use crate::domain::ChangeSet;
@@ -1176,8 +2672,53 @@ mod tests {
     fn test_parse_echo_plain_no_redirect() {
         assert!(matches!(
             Command::parse("echo", &args(&["hello"])),
-            Command::Echo(ref s) if s == "hello"
+            Command::Echo { ref words, newline: true, escapes: false } if words == &["hello"]
+        ));
+    }
+
+    #[test]
+    fn test_parse_echo_flags() {
+        assert!(matches!(
+            Command::parse("echo", &args(&["-n", "-e", "a\\tb"])),
+            Command::Echo { ref words, newline: false, escapes: true } if words == &["a\\tb"]
+        ));
+        assert!(matches!(
+            Command::parse("echo", &args(&["-neE", "x"])),
+            Command::Echo {
+                newline: false,
+                escapes: false,
+                ..
+            }
+        ));
+        // The first non-option word ends option parsing.
+        assert!(matches!(
+            Command::parse("echo", &args(&["x", "-n"])),
+            Command::Echo { ref words, newline: true, .. } if words == &["x", "-n"]
         ));
+        assert!(matches!(
+            Command::parse("echo", &args(&["-nx"])),
+            Command::Echo { ref words, newline: true, .. } if words == &["-nx"]
+        ));
+    }
+
+    #[test]
+    fn test_parse_echo_keeps_quoted_whitespace() {
+        use crate::engine::shell::parser::parse_input;
+
+        let pipeline = parse_input("echo \"a    b\"  c", &[]);
+        let parsed = &pipeline.commands[0];
+        assert!(matches!(
+            Command::parse(&parsed.name, &parsed.args),
+            Command::Echo { ref words, .. } if words == &["a    b", "c"]
+        ));
+    }
+
+    #[test]
+    fn test_parse_echo_redirect_expands_escapes() {
+        match Command::parse("echo", &args(&["-e", "a\\nb", ">", "/tmp/a.md"])) {
+            Command::EchoRedirect { body, .. } => assert_eq!(body, "a\nb"),
+            other => panic!("expected EchoRedirect, got {other:?}"),
+        }
     }
 
     #[test]
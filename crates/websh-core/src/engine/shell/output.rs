@@ -1,7 +1,12 @@
 //! Terminal-related data types for output rendering.
 
+use std::ops::Range;
 use std::sync::atomic::{AtomicU64, Ordering};
 
+use super::Prompt;
+use crate::ports::EntryChange;
+use crate::support::format::{format_date_listing, format_size};
+
 /// Unique identifier for an `OutputLine`, used as a stable UI list key.
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub struct OutputLineId(pub u64);
@@ -26,8 +31,13 @@ pub enum ListFormat {
     Long {
         permissions: String,
         size: Option<u64>,
-        modified: Option<u64>,
+        /// Modification date, already formatted relative to the time the
+        /// listing ran (see [`format_date_listing`]).
+        modified: String,
     },
+    /// Short format behind a change marker (`ls --diff`); `None` for an
+    /// unchanged entry
+    Diff { change: Option<EntryChange> },
 }
 
 /// Represents a single line of output in the terminal with a unique ID
@@ -37,13 +47,16 @@ pub struct OutputLine {
     pub id: OutputLineId,
     /// The actual output data
     pub data: OutputLineData,
+    /// Byte ranges of [`OutputLine::highlight_text`] to emphasize, such as
+    /// `grep` matches. Empty for most lines.
+    pub highlights: Vec<Range<usize>>,
 }
 
 /// The actual content of an output line
 #[derive(Clone, Debug, PartialEq)]
 pub enum OutputLineData {
     /// Command with prompt and user input
-    Command { prompt: String, input: String },
+    Command { prompt: Prompt, input: String },
     /// Plain text output
     Text(String),
     /// Error message (red)
@@ -62,8 +75,99 @@ pub enum OutputLineData {
         description: String,
         style: TextStyle,
         encrypted: bool,
+        /// A local draft: created or edited here and not yet committed.
+        draft: bool,
         format: ListFormat,
+        /// Canonical path of the listed entry, when known, so the UI can
+        /// open it.
+        path: Option<crate::domain::VirtualPath>,
     },
+    /// One row of a multi-column short listing (`ls` without `-l`)
+    ListRow(Vec<ListCell>),
+}
+
+/// One cell of a multi-column listing row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListCell {
+    pub name: String,
+    pub style: TextStyle,
+    pub encrypted: bool,
+    /// A local draft, as for [`OutputLineData::ListEntry`].
+    pub draft: bool,
+    /// Canonical path of the listed entry, so the UI can open it.
+    pub path: Option<crate::domain::VirtualPath>,
+    /// Column width the cell is padded to, in characters.
+    pub width: usize,
+}
+
+impl ListCell {
+    /// Marker appended to encrypted names in plain text. Two characters wide,
+    /// matching the space the lock icon takes in the terminal.
+    const ENCRYPTED_MARKER: &'static str = " *";
+    /// Marker appended to draft names in plain text, as wide as the draft
+    /// badge.
+    const DRAFT_MARKER: &'static str = " +";
+
+    /// Create a cell for a listed entry; `width` starts at the label width.
+    pub fn new(entry: &crate::domain::DirEntry) -> Self {
+        let style = if entry.is_dir {
+            TextStyle::Directory
+        } else if entry.name.starts_with('.') {
+            TextStyle::Hidden
+        } else {
+            TextStyle::File
+        };
+        let mut cell = Self {
+            name: entry.name.clone(),
+            style,
+            encrypted: entry.is_restricted(),
+            draft: false,
+            path: entry.link_path(),
+            width: 0,
+        };
+        cell.width = cell.display_width();
+        cell
+    }
+
+    /// Mark the cell as a local draft, widening it for the marker.
+    pub fn with_draft(mut self, draft: bool) -> Self {
+        self.draft = draft;
+        self.width = self.width.max(self.display_width());
+        self
+    }
+
+    /// Name as shown in the listing, with a trailing `/` on directories.
+    pub fn label(&self) -> String {
+        if self.style == TextStyle::Directory {
+            format!("{}/", self.name)
+        } else {
+            self.name.clone()
+        }
+    }
+
+    /// Width of the label plus its markers, in characters.
+    pub fn display_width(&self) -> usize {
+        self.label().chars().count() + self.markers().len()
+    }
+
+    fn markers(&self) -> String {
+        let mut markers = String::new();
+        if self.encrypted {
+            markers.push_str(Self::ENCRYPTED_MARKER);
+        }
+        if self.draft {
+            markers.push_str(Self::DRAFT_MARKER);
+        }
+        markers
+    }
+
+    fn to_plain_text(&self) -> String {
+        format!(
+            "{:<width$}",
+            format!("{}{}", self.label(), self.markers()),
+            width = self.width
+        )
+    }
 }
 
 // Global counter for generating unique IDs
@@ -75,6 +179,7 @@ impl OutputLine {
         Self {
             id: OutputLineId(OUTPUT_LINE_COUNTER.fetch_add(1, Ordering::Relaxed)),
             data,
+            highlights: Vec::new(),
         }
     }
 }
@@ -100,7 +205,7 @@ impl OutputLine {
         Self::new(OutputLineData::Ascii(s.into()))
     }
 
-    pub fn command(prompt: impl Into<String>, input: impl Into<String>) -> Self {
+    pub fn command(prompt: impl Into<Prompt>, input: impl Into<String>) -> Self {
         Self::new(OutputLineData::Command {
             prompt: prompt.into(),
             input: input.into(),
@@ -114,7 +219,9 @@ impl OutputLine {
             description: description.into(),
             style: TextStyle::Directory,
             encrypted: false,
+            draft: false,
             format: ListFormat::Short,
+            path: None,
         })
     }
 
@@ -135,14 +242,18 @@ impl OutputLine {
             description: description.into(),
             style,
             encrypted,
+            draft: false,
             format: ListFormat::Short,
+            path: None,
         })
     }
 
-    /// Create a long listing entry (ls -l)
+    /// Create a long listing entry (ls -l), dating it relative to `now`
+    /// (Unix seconds).
     pub fn long_entry(
         entry: &crate::domain::DirEntry,
         perms: &crate::domain::DisplayPermissions,
+        now: u64,
     ) -> Self {
         let style = if entry.is_dir {
             TextStyle::Directory
@@ -156,19 +267,169 @@ impl OutputLine {
             name: entry.name.clone(),
             description: entry.title.clone(),
             style,
-            encrypted: meta.map(|m| m.is_restricted()).unwrap_or(false),
+            encrypted: entry.is_restricted(),
+            draft: false,
             format: ListFormat::Long {
                 permissions: perms.to_string(),
                 size: meta.and_then(|m| m.size_bytes()),
-                modified: meta.and_then(|m| m.modified_at()),
+                modified: format_date_listing(meta.and_then(|m| m.modified_at()), now),
             },
+            path: entry.link_path(),
         })
     }
 
+    /// Create one row of a multi-column listing
+    pub fn list_row(cells: Vec<ListCell>) -> Self {
+        Self::new(OutputLineData::ListRow(cells))
+    }
+
+    /// Attach the canonical path of a listing entry. No-op for other lines.
+    pub fn with_path(mut self, entry_path: crate::domain::VirtualPath) -> Self {
+        if let OutputLineData::ListEntry { path, .. } = &mut self.data {
+            *path = Some(entry_path);
+        }
+        self
+    }
+
+    /// Show a short listing entry behind the marker for `change`. No-op
+    /// for other lines.
+    pub fn with_change(mut self, entry_change: Option<EntryChange>) -> Self {
+        if let OutputLineData::ListEntry { format, .. } = &mut self.data {
+            *format = ListFormat::Diff {
+                change: entry_change,
+            };
+        }
+        self
+    }
+
+    /// Mark a listing entry as a local draft. No-op for other lines.
+    pub fn with_draft(mut self, is_draft: bool) -> Self {
+        if let OutputLineData::ListEntry { draft, .. } = &mut self.data {
+            *draft = is_draft;
+        }
+        self
+    }
+
+    /// Mark byte ranges of [`OutputLine::highlight_text`] for emphasis.
+    pub fn with_highlights(mut self, highlights: Vec<Range<usize>>) -> Self {
+        self.highlights = highlights;
+        self
+    }
+
+    /// The text highlight ranges index into: the message of text lines,
+    /// the name of a list entry, or the input of a command. Listing rows
+    /// and empty lines have none.
+    pub fn highlight_text(&self) -> Option<&str> {
+        match &self.data {
+            OutputLineData::Text(s)
+            | OutputLineData::Error(s)
+            | OutputLineData::Success(s)
+            | OutputLineData::Info(s)
+            | OutputLineData::Ascii(s) => Some(s),
+            OutputLineData::ListEntry { name, .. } => Some(name),
+            OutputLineData::Command { input, .. } => Some(input),
+            OutputLineData::ListRow(_) | OutputLineData::Empty => None,
+        }
+    }
+
     /// Create an empty line
     pub fn empty() -> Self {
         Self::new(OutputLineData::Empty)
     }
+
+    /// Render the line as plain text, mirroring the terminal layout.
+    ///
+    /// Commands keep their `prompt$ ` prefix; list entries render as
+    /// `name  description` (short) or `perms size date name` (long), with a
+    /// trailing `/` on directories. Listing rows pad each cell to its column
+    /// width.
+    pub fn to_plain_text(&self) -> String {
+        match &self.data {
+            OutputLineData::Command { prompt, input } => {
+                format!("{prompt}{} {input}", prompt.separator)
+            }
+            OutputLineData::Text(s)
+            | OutputLineData::Error(s)
+            | OutputLineData::Success(s)
+            | OutputLineData::Info(s)
+            | OutputLineData::Ascii(s) => s.clone(),
+            OutputLineData::Empty => String::new(),
+            OutputLineData::ListEntry {
+                name,
+                description,
+                style,
+                encrypted,
+                draft,
+                format,
+                ..
+            } => {
+                let suffix = if *style == TextStyle::Directory {
+                    "/"
+                } else {
+                    ""
+                };
+                let lock = if *encrypted { " [encrypted]" } else { "" };
+                let badge = if *draft { " [draft]" } else { "" };
+                let display_name = format!("{}{}{}{}", name, suffix, lock, badge);
+                match format {
+                    ListFormat::Short if description.is_empty() => display_name,
+                    ListFormat::Short => format!("{}  {}", display_name, description),
+                    ListFormat::Diff { change } => {
+                        let marker = change.map_or(' ', EntryChange::marker);
+                        if description.is_empty() {
+                            format!("{marker} {display_name}")
+                        } else {
+                            format!("{marker} {display_name}  {description}")
+                        }
+                    }
+                    ListFormat::Long {
+                        permissions,
+                        size,
+                        modified,
+                    } => format!(
+                        "{} {} {} {}",
+                        permissions,
+                        format_size(*size, true),
+                        modified,
+                        display_name
+                    ),
+                }
+            }
+            OutputLineData::ListRow(cells) => cells
+                .iter()
+                .map(ListCell::to_plain_text)
+                .collect::<Vec<_>>()
+                .join(&" ".repeat(super::columns::COLUMN_GAP))
+                .trim_end()
+                .to_string(),
+        }
+    }
+}
+
+/// Join output lines into a plain-text transcript, one line per entry.
+pub fn lines_to_plain_text<'a>(lines: impl IntoIterator<Item = &'a OutputLine>) -> String {
+    lines
+        .into_iter()
+        .map(|line| line.to_plain_text() + "\n")
+        .collect()
+}
+
+/// Ids of the lines whose plain text contains `query`, ignoring case,
+/// oldest first. Empty lines never match, and neither does an empty query.
+pub fn find_output_lines<'a>(
+    lines: impl IntoIterator<Item = &'a OutputLine>,
+    query: &str,
+) -> Vec<OutputLineId> {
+    let query = query.to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    lines
+        .into_iter()
+        .filter(|line| !matches!(line.data, OutputLineData::Empty))
+        .filter(|line| line.to_plain_text().to_lowercase().contains(&query))
+        .map(|line| line.id)
+        .collect()
 }
 
 #[cfg(test)]
@@ -220,13 +481,17 @@ mod tests {
                 description,
                 style,
                 encrypted,
+                draft,
                 format,
+                path,
             } => {
                 assert_eq!(name, "docs");
                 assert_eq!(description, "Documentation");
                 assert_eq!(style, TextStyle::Directory);
                 assert!(!encrypted);
+                assert!(!draft);
                 assert_eq!(format, ListFormat::Short);
+                assert_eq!(path, None);
             }
             _ => panic!("Expected ListEntry variant"),
         }
@@ -256,6 +521,149 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_with_path_targets_list_entries_only() {
+        let path = crate::domain::VirtualPath::root().join("docs");
+        let entry = OutputLine::dir_entry("docs", "").with_path(path.clone());
+        assert!(matches!(
+            entry.data,
+            OutputLineData::ListEntry { path: Some(ref p), .. } if *p == path
+        ));
+        assert_eq!(
+            OutputLine::text("docs").with_path(path).data,
+            OutputLineData::Text("docs".to_string())
+        );
+    }
+
+    #[test]
+    fn test_plain_text_messages() {
+        assert_eq!(OutputLine::text("hello").to_plain_text(), "hello");
+        assert_eq!(OutputLine::error("boom").to_plain_text(), "boom");
+        assert_eq!(OutputLine::success("ok").to_plain_text(), "ok");
+        assert_eq!(OutputLine::info("note").to_plain_text(), "note");
+        assert_eq!(OutputLine::ascii(" /\\_/\\").to_plain_text(), " /\\_/\\");
+        assert_eq!(OutputLine::empty().to_plain_text(), "");
+    }
+
+    #[test]
+    fn test_plain_text_command_has_prompt_prefix() {
+        let line = OutputLine::command("guest@websh:~", "ls -l");
+        assert_eq!(line.to_plain_text(), "guest@websh:~$ ls -l");
+    }
+
+    #[test]
+    fn test_plain_text_short_entries() {
+        assert_eq!(
+            OutputLine::dir_entry("docs", "Documentation").to_plain_text(),
+            "docs/  Documentation"
+        );
+        assert_eq!(
+            OutputLine::file_entry("notes.md", "", false).to_plain_text(),
+            "notes.md"
+        );
+        assert_eq!(
+            OutputLine::file_entry("secret.md", "Private", true).to_plain_text(),
+            "secret.md [encrypted]  Private"
+        );
+    }
+
+    #[test]
+    fn test_plain_text_long_entry() {
+        let line = OutputLine::new(OutputLineData::ListEntry {
+            name: "post.md".to_string(),
+            description: "Post".to_string(),
+            style: TextStyle::File,
+            encrypted: false,
+            draft: false,
+            format: ListFormat::Long {
+                permissions: "-r--r--r--".to_string(),
+                size: Some(2048),
+                modified: format_date_listing(None, 0),
+            },
+            path: None,
+        });
+        assert_eq!(
+            line.to_plain_text(),
+            format!(
+                "-r--r--r-- {} {} post.md",
+                format_size(Some(2048), true),
+                format_date_listing(None, 0)
+            )
+        );
+    }
+
+    #[test]
+    fn test_lines_to_plain_text() {
+        let lines = [
+            OutputLine::command("guest@websh:~", "pwd"),
+            OutputLine::text("/"),
+            OutputLine::empty(),
+        ];
+        assert_eq!(lines_to_plain_text(&lines), "guest@websh:~$ pwd\n/\n\n");
+        assert_eq!(lines_to_plain_text(&[]), "");
+    }
+
+    #[test]
+    fn test_plain_text_list_row_pads_cells_to_column_width() {
+        let cell = |name: &str, style, encrypted, width| ListCell {
+            name: name.to_string(),
+            style,
+            encrypted,
+            draft: false,
+            path: None,
+            width,
+        };
+        let row = OutputLine::list_row(vec![
+            cell("blog", TextStyle::Directory, false, 8),
+            cell("key.md", TextStyle::File, true, 9),
+            cell("a.md", TextStyle::File, false, 6),
+        ]);
+        assert_eq!(row.to_plain_text(), "blog/     key.md *   a.md");
+
+        let draft = ListCell {
+            draft: true,
+            ..cell("new.md", TextStyle::File, true, 0)
+        }
+        .with_draft(true);
+        assert_eq!(draft.width, 10);
+        assert_eq!(draft.to_plain_text(), "new.md * +");
+    }
+
+    #[test]
+    fn test_plain_text_marks_drafts() {
+        assert_eq!(
+            OutputLine::file_entry("new.md", "New", false)
+                .with_draft(true)
+                .to_plain_text(),
+            "new.md [draft]  New"
+        );
+        assert_eq!(OutputLine::text("x").with_draft(true).to_plain_text(), "x");
+    }
+
+    #[test]
+    fn test_find_output_lines_matches_plain_text_ignoring_case() {
+        let lines = [
+            OutputLine::text("Rust notes"),
+            OutputLine::empty(),
+            OutputLine::dir_entry("rust", ""),
+            OutputLine::error("cat: gone.md: No such file or directory"),
+            OutputLine::command("guest@websh:~", "grep rust"),
+        ];
+        assert_eq!(
+            find_output_lines(&lines, "RUST"),
+            vec![lines[0].id, lines[2].id, lines[4].id]
+        );
+        assert_eq!(find_output_lines(&lines, "gone.md"), vec![lines[3].id]);
+        assert!(find_output_lines(&lines, "python").is_empty());
+    }
+
+    #[test]
+    fn test_find_output_lines_skips_empty_lines_and_queries() {
+        let lines = [OutputLine::empty(), OutputLine::text("x")];
+        assert!(find_output_lines(&lines, "").is_empty());
+        assert!(find_output_lines(&lines[..1], " ").is_empty());
+    }
+
     #[test]
     fn test_unique_ids() {
         let line1 = OutputLine::text("first");
@@ -1,20 +1,35 @@
-//! Token expansion for history.
+//! Token expansion for history, command substitution and braces.
 //!
 //! Variable expansion is performed inline by the lexer while building
-//! `Word` tokens. This module only handles history references (`!!`,
-//! `!n`, `!-n`).
+//! `Word` tokens. This module handles history references (`!!`, `!n`,
+//! `!-n`), then command substitution (`$(cmd)`), then brace expansion
+//! (`{a,b}`).
+//!
+//! History stores the raw input line, quotes included, so a recalled
+//! command is lexed from scratch exactly as it was first typed.
+
+use std::collections::BTreeMap;
+
+use super::ShellParseError;
+use super::lexer::{Lexer, Token, WordPart, word_token};
 
-use super::lexer::{Lexer, Token};
+/// Most words a single word may brace-expand to.
+const MAX_BRACE_EXPANSIONS: usize = 64;
 
-/// Expand history references in tokens.
-pub fn expand_tokens(tokens: Vec<Token>, history: &[String]) -> Vec<Token> {
+/// Expand history references in tokens. Recalled commands are re-lexed
+/// with `env`, so their quoting and variables behave as on first entry.
+pub fn expand_tokens(
+    tokens: Vec<Token>,
+    history: &[String],
+    env: &BTreeMap<String, String>,
+) -> Vec<Token> {
     tokens
         .into_iter()
         .flat_map(|token| match token {
             Token::HistoryLast => {
                 let cmd = history.last().cloned().unwrap_or_default();
                 // Re-tokenize the history command (without further history expansion)
-                Lexer::new(&cmd)
+                Lexer::new_with_env(&cmd, env)
                     .filter(|t| !matches!(t, Token::HistoryLast | Token::HistoryIndex(_)))
                     .collect()
             }
@@ -27,7 +42,7 @@ pub fn expand_tokens(tokens: Vec<Token>, history: &[String]) -> Vec<Token> {
                     idx.and_then(|i| history.get(i).cloned())
                         .unwrap_or_default()
                 };
-                Lexer::new(&cmd)
+                Lexer::new_with_env(&cmd, env)
                     .filter(|t| !matches!(t, Token::HistoryLast | Token::HistoryIndex(_)))
                     .collect()
             }
@@ -36,6 +51,127 @@ pub fn expand_tokens(tokens: Vec<Token>, history: &[String]) -> Vec<Token> {
         .collect()
 }
 
+/// Replace each `Substitution` with the words of its output. `run` parses
+/// and runs the inner command line.
+///
+/// Output splits on whitespace, and its first and last words join the text
+/// next to the `$(...)`, so `$(pwd)/notes.md` stays one word. Words that
+/// come out empty are dropped. Braces in the word's own text still expand.
+pub fn expand_substitutions(
+    tokens: Vec<Token>,
+    run: &mut dyn FnMut(&str) -> Result<String, ShellParseError>,
+) -> Result<Vec<Token>, ShellParseError> {
+    let mut expanded = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let Token::Substitution(parts) = token else {
+            expanded.push(token);
+            continue;
+        };
+        let mut fields = Vec::new();
+        let mut word = String::new();
+        let mut braces = Vec::new();
+        for part in parts {
+            match part {
+                WordPart::Text { text, braces: at } => {
+                    braces.extend(at.into_iter().map(|at| at + word.len()));
+                    word.push_str(&text);
+                }
+                WordPart::Command(command) => {
+                    let output = run(&command)?;
+                    let mut pieces = output.split(char::is_whitespace);
+                    word.push_str(pieces.next().unwrap_or_default());
+                    for piece in pieces {
+                        fields.push((std::mem::take(&mut word), std::mem::take(&mut braces)));
+                        word.push_str(piece);
+                    }
+                }
+            }
+        }
+        fields.push((word, braces));
+        expanded.extend(
+            fields
+                .into_iter()
+                .filter(|(word, _)| !word.is_empty())
+                .map(|(word, braces)| word_token(word, braces)),
+        );
+    }
+    Ok(expanded)
+}
+
+/// Expand each `BraceWord` into its words: `x{a,b}y` becomes `xay xby`.
+///
+/// Only braces the lexer marked as unquoted take part. A `{` without a
+/// matching `}` or without a `,` inside stays literal, as do the braces
+/// left around it, and words that expand to nothing are dropped.
+pub fn expand_braces(tokens: Vec<Token>) -> Result<Vec<Token>, ShellParseError> {
+    let mut expanded = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let Token::BraceWord { word, braces } = token else {
+            expanded.push(token);
+            continue;
+        };
+        let marked: Vec<(char, bool)> = word
+            .char_indices()
+            .map(|(at, c)| (c, braces.contains(&at)))
+            .collect();
+        let words = expand_marked(&marked).ok_or_else(|| ShellParseError::TooManyExpansions {
+            word: word.clone(),
+            limit: MAX_BRACE_EXPANSIONS,
+        })?;
+        expanded.extend(
+            words
+                .into_iter()
+                .filter(|chars| !chars.is_empty())
+                .map(|chars| Token::Word(chars.into_iter().map(|(c, _)| c).collect())),
+        );
+    }
+    Ok(expanded)
+}
+
+/// Expand the first brace group in `word`, then each result again.
+/// `None` once the results pass [`MAX_BRACE_EXPANSIONS`].
+fn expand_marked(word: &[(char, bool)]) -> Option<Vec<Vec<(char, bool)>>> {
+    let Some((open, commas, close)) = find_group(word) else {
+        return Some(vec![word.to_vec()]);
+    };
+    let mut results = Vec::new();
+    let mut start = open + 1;
+    for end in commas.into_iter().chain([close]) {
+        let mut alternative = word[..open].to_vec();
+        alternative.extend_from_slice(&word[start..end]);
+        alternative.extend_from_slice(&word[close + 1..]);
+        results.extend(expand_marked(&alternative)?);
+        if results.len() > MAX_BRACE_EXPANSIONS {
+            return None;
+        }
+        start = end + 1;
+    }
+    Some(results)
+}
+
+/// The first `{` with a matching `}` and a top-level `,` between them,
+/// as `(open, commas, close)` indices.
+fn find_group(word: &[(char, bool)]) -> Option<(usize, Vec<usize>, usize)> {
+    let is = |at: usize, wanted: char| word[at] == (wanted, true);
+    (0..word.len()).filter(|&at| is(at, '{')).find_map(|open| {
+        let mut depth = 0;
+        let mut commas = Vec::new();
+        for at in open + 1..word.len() {
+            if is(at, '{') {
+                depth += 1;
+            } else if is(at, ',') && depth == 0 {
+                commas.push(at);
+            } else if is(at, '}') {
+                if depth == 0 {
+                    return (!commas.is_empty()).then_some((open, commas, at));
+                }
+                depth -= 1;
+            }
+        }
+        None
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -44,7 +180,7 @@ mod tests {
     fn test_history_expansion() {
         let history = vec!["ls -la".to_string(), "pwd".to_string()];
         let tokens = vec![Token::HistoryLast];
-        let expanded = expand_tokens(tokens, &history);
+        let expanded = expand_tokens(tokens, &history, &BTreeMap::new());
         assert_eq!(expanded, vec![Token::Word("pwd".to_string())]);
     }
 
@@ -52,7 +188,7 @@ mod tests {
     fn test_history_index_expansion() {
         let history = vec!["ls -la".to_string(), "pwd".to_string()];
         let tokens = vec![Token::HistoryIndex(0)];
-        let expanded = expand_tokens(tokens, &history);
+        let expanded = expand_tokens(tokens, &history, &BTreeMap::new());
         assert_eq!(
             expanded,
             vec![
@@ -61,4 +197,33 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_recalled_quotes_keep_arguments_whole() {
+        let history = vec![r#"grep "foo bar" 'x  y'"#.to_string()];
+        let expanded = expand_tokens(vec![Token::HistoryLast], &history, &BTreeMap::new());
+        assert_eq!(
+            expanded,
+            vec![
+                Token::Word("grep".to_string()),
+                Token::Word("foo bar".to_string()),
+                Token::Word("x  y".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recalled_variables_expand_with_the_environment() {
+        let history = vec![r#"echo "$NAME" '$NAME'"#.to_string()];
+        let env = BTreeMap::from([("NAME".to_string(), "a b".to_string())]);
+        let expanded = expand_tokens(vec![Token::HistoryIndex(-1)], &history, &env);
+        assert_eq!(
+            expanded,
+            vec![
+                Token::Word("echo".to_string()),
+                Token::Word("a b".to_string()),
+                Token::Word("$NAME".to_string()),
+            ]
+        );
+    }
 }
@@ -6,6 +6,9 @@
 //! - Variable references (`$VAR`, `${VAR}`)
 //! - History expansion (`!!`, `!n`, `!-n`)
 //! - Quote handling (single and double quotes)
+//! - Tilde expansion after the `=` of an assignment (`KEY=~/path`)
+//! - Marking unquoted `{`, `,`, `}` for brace expansion
+//! - Command substitution words (`$(cmd)`)
 
 use std::collections::BTreeMap;
 
@@ -23,6 +26,21 @@ pub enum Token {
     HistoryLast,
     /// History by index `!n` or `!-n`
     HistoryIndex(i32),
+    /// A word with an unquoted `{`. `braces` holds the byte offsets of its
+    /// unquoted `{`, `,` and `}`, the only ones brace expansion acts on.
+    BraceWord { word: String, braces: Vec<usize> },
+    /// A word with an unquoted `$(...)`, as its literal text and command
+    /// lines in order: `$(pwd)/notes.md` is a command then a text part
+    Substitution(Vec<WordPart>),
+}
+
+/// One part of a word holding command substitutions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WordPart {
+    /// Text between substitutions, with its brace offsets as in `BraceWord`
+    Text { text: String, braces: Vec<usize> },
+    /// The command line of a `$(...)`
+    Command(String),
 }
 
 /// Result of reading a variable name after `$`
@@ -45,6 +63,7 @@ pub struct Lexer<'a> {
 
 impl<'a> Lexer<'a> {
     /// Create a new lexer for the given input
+    #[cfg(test)]
     pub fn new(input: &'a str) -> Self {
         Self {
             input,
@@ -187,8 +206,24 @@ impl<'a> Lexer<'a> {
             // Rewind past `!` and keep `self.pos` pointed at the char after `!`.
             self.pos = hist_start + 1;
             let mut word = String::from("!");
-            if let Some(Token::Word(rest)) = self.parse_word_segment() {
-                word.push_str(&rest);
+            match self.parse_word_segment() {
+                Some(Token::Word(rest)) => word.push_str(&rest),
+                Some(Token::BraceWord { word: rest, braces }) => {
+                    word.push_str(&rest);
+                    let braces = braces.into_iter().map(|at| at + 1).collect();
+                    return Some(Token::BraceWord { word, braces });
+                }
+                Some(Token::Substitution(mut parts)) => {
+                    parts.insert(
+                        0,
+                        WordPart::Text {
+                            text: word,
+                            braces: Vec::new(),
+                        },
+                    );
+                    return Some(Token::Substitution(parts));
+                }
+                _ => {}
             }
             Some(Token::Word(word))
         }
@@ -205,11 +240,28 @@ impl<'a> Lexer<'a> {
     /// (POSIX: an unquoted empty expansion is removed). If any quoted
     /// segment (even empty), any literal char, or any non-empty variable
     /// expansion appears, the word is emitted (possibly empty).
+    ///
+    /// Outside quotes a backslash takes the next character literally, so
+    /// `My\ Notes.md` is one word; a backslash ending the input stays.
+    ///
+    /// An unquoted `~` or `~/` right after the `=` of an assignment-shaped
+    /// word (`KEY=~/blog`) expands to the home path `/`, as the value is
+    /// meant to be a path. `~name` and tildes anywhere else stay literal;
+    /// path arguments resolve a leading `~` themselves.
+    ///
+    /// A word with an unquoted, unescaped `{` comes out as a `BraceWord`
+    /// so expansion can tell its braces from quoted or expanded ones.
+    ///
+    /// A word with an unquoted `$(...)` comes out as a `Substitution` of
+    /// its text and command parts; inside double quotes `$(` stays literal.
     fn parse_word_segment(&mut self) -> Option<Token> {
+        let mut parts = Vec::new();
         let mut acc = String::new();
+        let mut braces = Vec::new();
         let mut had_quoted = false;
         let mut had_literal = false;
         let mut any_var_nonempty = false;
+        let mut plain = true;
 
         while self.pos < self.input.len() {
             let c = self.current_char();
@@ -241,6 +293,7 @@ impl<'a> Lexer<'a> {
                         return None;
                     }
                     had_quoted = true;
+                    plain = false;
                 }
                 '"' => {
                     let quote_start = self.pos;
@@ -291,8 +344,19 @@ impl<'a> Lexer<'a> {
                         return None;
                     }
                     had_quoted = true;
+                    plain = false;
+                }
+                '$' if self.input[self.pos..].starts_with("$(") => {
+                    let command = self.parse_substitution()?;
+                    parts.push(WordPart::Text {
+                        text: std::mem::take(&mut acc),
+                        braces: std::mem::take(&mut braces),
+                    });
+                    parts.push(WordPart::Command(command));
+                    plain = false;
                 }
                 '$' => {
+                    plain = false;
                     self.pos += 1; // skip $
                     if self.pos >= self.input.len() {
                         // bare `$` at EOF → literal $
@@ -325,7 +389,29 @@ impl<'a> Lexer<'a> {
                         }
                     }
                 }
+                '\\' => {
+                    self.pos += 1;
+                    if self.pos < self.input.len() {
+                        let escaped = self.current_char();
+                        acc.push(escaped);
+                        self.pos += escaped.len_utf8();
+                    } else {
+                        acc.push('\\');
+                    }
+                    had_literal = true;
+                    plain = false;
+                }
+                '~' if plain && is_assignment_prefix(&acc) && self.tilde_ends_prefix() => {
+                    self.pos += 1;
+                    if !self.input[self.pos..].starts_with('/') {
+                        acc.push('/');
+                    }
+                    had_literal = true;
+                }
                 _ => {
+                    if matches!(c, '{' | ',' | '}') {
+                        braces.push(acc.len());
+                    }
                     acc.push(c);
                     self.pos += c.len_utf8();
                     had_literal = true;
@@ -333,8 +419,11 @@ impl<'a> Lexer<'a> {
             }
         }
 
-        if had_quoted || had_literal || any_var_nonempty {
-            Some(Token::Word(acc))
+        if !parts.is_empty() {
+            parts.push(WordPart::Text { text: acc, braces });
+            Some(Token::Substitution(parts))
+        } else if had_quoted || had_literal || any_var_nonempty {
+            Some(word_token(acc, braces))
         } else {
             // Pure-empty-unquoted-var word → drop.
             None
@@ -342,6 +431,67 @@ impl<'a> Lexer<'a> {
     }
 }
 
+/// `word` as a `BraceWord` when one of `braces` marks a `{`, otherwise as
+/// a plain `Word`.
+pub(super) fn word_token(word: String, braces: Vec<usize>) -> Token {
+    if braces.iter().any(|&at| word[at..].starts_with('{')) {
+        Token::BraceWord { word, braces }
+    } else {
+        Token::Word(word)
+    }
+}
+
+impl Lexer<'_> {
+    /// Read `$(...)` up to its matching `)` and return the command line
+    /// inside. Parentheses inside quotes or after a backslash don't count.
+    fn parse_substitution(&mut self) -> Option<String> {
+        let position = self.pos;
+        self.pos += 2; // skip $(
+        let start = self.pos;
+        let mut depth = 0;
+        let mut quote = None;
+        while self.pos < self.input.len() {
+            let c = self.current_char();
+            self.pos += c.len_utf8();
+            match (quote, c) {
+                (Some(open), _) if c == open => quote = None,
+                (Some('"'), '\\') | (None, '\\') if self.pos < self.input.len() => {
+                    self.pos += self.current_char().len_utf8();
+                }
+                (Some(_), _) => {}
+                (None, '\'' | '"') => quote = Some(c),
+                (None, '(') => depth += 1,
+                (None, ')') if depth > 0 => depth -= 1,
+                (None, ')') => return Some(self.input[start..self.pos - 1].to_string()),
+                _ => {}
+            }
+        }
+        self.error = Some(super::ShellParseError::UnclosedSubstitution { position });
+        None
+    }
+
+    /// Whether the `~` at `pos` is a whole tilde-prefix: followed by `/` or
+    /// by the end of the word.
+    fn tilde_ends_prefix(&self) -> bool {
+        self.input[self.pos + 1..]
+            .chars()
+            .next()
+            .is_none_or(|next| next == '/' || next.is_whitespace() || next == '|' || next == '!')
+    }
+}
+
+/// Whether `word` is `NAME=` with a valid variable name, so what follows
+/// is an assignment value.
+fn is_assignment_prefix(word: &str) -> bool {
+    word.strip_suffix('=').is_some_and(|name| {
+        let mut chars = name.chars();
+        chars
+            .next()
+            .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
 impl Iterator for Lexer<'_> {
     type Item = Token;
 
@@ -475,6 +625,147 @@ mod tests {
         );
     }
 
+    fn words(input: &str) -> Vec<String> {
+        Lexer::new(input)
+            .map(|token| match token {
+                Token::Word(word) | Token::BraceWord { word, .. } => word,
+                other => panic!("unexpected token {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tilde_after_assignment_equals_expands() {
+        assert_eq!(words("export CONTENT=~/blog"), ["export", "CONTENT=/blog"]);
+        assert_eq!(words("export HOME_DIR=~"), ["export", "HOME_DIR=/"]);
+        assert_eq!(words("export A=~/x B=~"), ["export", "A=/x", "B=/"]);
+    }
+
+    #[test]
+    fn test_tilde_stays_literal_outside_assignment_values() {
+        // No users to look up, so `~name` is never a home directory.
+        assert_eq!(words("export DIR=~foo"), ["export", "DIR=~foo"]);
+        assert_eq!(words("cd ~foo"), ["cd", "~foo"]);
+        // Leading `~` path arguments are resolved by the commands.
+        assert_eq!(words("cd ~/blog"), ["cd", "~/blog"]);
+        assert_eq!(words("echo a~/b x=y~/z"), ["echo", "a~/b", "x=y~/z"]);
+        assert_eq!(words("echo 1X=~/a"), ["echo", "1X=~/a"]);
+        assert_eq!(words("echo =~"), ["echo", "=~"]);
+    }
+
+    #[test]
+    fn test_quoted_tilde_after_equals_stays_literal() {
+        assert_eq!(words("export DIR='~/blog'"), ["export", "DIR=~/blog"]);
+        assert_eq!(words("export DIR=\"~\""), ["export", "DIR=~"]);
+        assert_eq!(words("export \"DIR\"=~/blog"), ["export", "DIR=~/blog"]);
+    }
+
+    #[test]
+    fn test_backslash_escapes_a_space_outside_quotes() {
+        assert_eq!(words("cat My\\ Notes.md"), ["cat", "My Notes.md"]);
+        assert_eq!(words("cat a\\ b\\ c"), ["cat", "a b c"]);
+        assert_eq!(
+            words("echo \\\\ \\| \\$HOME \\!x"),
+            ["echo", "\\", "|", "$HOME", "!x"]
+        );
+        // A backslash ending the input has nothing to escape.
+        assert_eq!(words("echo a\\"), ["echo", "a\\"]);
+        // Inside single quotes it stays literal.
+        assert_eq!(words("echo 'a\\ b'"), ["echo", "a\\ b"]);
+    }
+
+    #[test]
+    fn test_unquoted_braces_are_marked() {
+        let tokens = Lexer::new("echo x{a,'b,c'}\\{ \"{d}\" ${HOME}").tokenize();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Word("echo".to_string()),
+                Token::BraceWord {
+                    word: "x{a,b,c}{".to_string(),
+                    braces: vec![1, 3, 7],
+                },
+                Token::Word("{d}".to_string()),
+            ]
+        );
+    }
+
+    fn text(text: &str) -> WordPart {
+        WordPart::Text {
+            text: text.to_string(),
+            braces: Vec::new(),
+        }
+    }
+
+    fn command(command: &str) -> WordPart {
+        WordPart::Command(command.to_string())
+    }
+
+    #[test]
+    fn test_substitution_is_one_token() {
+        let tokens = Lexer::new("cat $(ls | grep ')' | head -1) x").tokenize();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Word("cat".to_string()),
+                Token::Substitution(vec![text(""), command("ls | grep ')' | head -1"), text("")]),
+                Token::Word("x".to_string()),
+            ]
+        );
+        assert_eq!(
+            Lexer::new("echo $(echo $(pwd))|wc").tokenize()[1],
+            Token::Substitution(vec![text(""), command("echo $(pwd)"), text("")])
+        );
+        // Inside double quotes it is plain text.
+        assert_eq!(words("echo \"$(pwd)\""), ["echo", "$(pwd)"]);
+    }
+
+    #[test]
+    fn test_substitution_errors() {
+        let mut lexer = Lexer::new("echo $(pwd");
+        let _: Vec<Token> = (&mut lexer).collect();
+        assert_eq!(
+            lexer.error(),
+            Some(&super::super::ShellParseError::UnclosedSubstitution { position: 5 })
+        );
+
+        let mut lexer = Lexer::new("cat x$(pwd");
+        let _: Vec<Token> = (&mut lexer).collect();
+        assert_eq!(
+            lexer.error(),
+            Some(&super::super::ShellParseError::UnclosedSubstitution { position: 5 })
+        );
+    }
+
+    #[test]
+    fn test_substitution_keeps_the_text_around_it() {
+        assert_eq!(
+            Lexer::new("cat $(pwd)/notes.md").tokenize()[1],
+            Token::Substitution(vec![text(""), command("pwd"), text("/notes.md")])
+        );
+        assert_eq!(
+            Lexer::new("echo 'a b'$(x)\\ $(y)z").tokenize()[1],
+            Token::Substitution(vec![
+                text("a b"),
+                command("x"),
+                text(" "),
+                command("y"),
+                text("z"),
+            ])
+        );
+        assert_eq!(
+            Lexer::new("ls {a,b}/$(x)").tokenize()[1],
+            Token::Substitution(vec![
+                WordPart::Text {
+                    text: "{a,b}/".to_string(),
+                    braces: vec![0, 2, 4],
+                },
+                command("x"),
+                text(""),
+            ])
+        );
+    }
+
     #[test]
     fn test_history_last() {
         let lexer = Lexer::new("!!");
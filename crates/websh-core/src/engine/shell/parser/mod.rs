@@ -5,6 +5,9 @@
 //! - History expansion: `!!` (last command), `!n` (nth command), `!-n` (nth from last)
 //! - Pipe operator: `cmd1 | cmd2`
 //! - Quote handling: `"string with spaces"`, `'literal string'`
+//! - Brace expansion: `{a,b,c}`, `~/blog/{2023,2024}`
+//! - Command substitution: `echo $(pwd)`, `cat $(pwd)/notes.md`
+//! - Tilde expansion in assignment values: `KEY=~/path`
 
 mod expand;
 mod lexer;
@@ -13,7 +16,7 @@ pub use lexer::{Lexer, Token};
 
 use std::collections::BTreeMap;
 
-use expand::expand_tokens;
+use expand::{expand_braces, expand_substitutions, expand_tokens};
 use thiserror::Error;
 
 /// Structured error type for shell pipeline parsing failures.
@@ -34,10 +37,16 @@ pub enum ShellParseError {
         if *kind == '"' { "double" } else { "single" }
     )]
     UnclosedQuote { kind: char, position: usize },
+    /// `$(` without its closing `)`.
+    #[error("syntax error: unclosed $( starting at position {position}")]
+    UnclosedSubstitution { position: usize },
+    /// A word that brace-expands past the limit: `{a,b}{a,b}{a,b}...`
+    #[error("brace expansion: {word} expands to more than {limit} words")]
+    TooManyExpansions { word: String, limit: usize },
 }
 
 /// A single command in a pipeline
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParsedCommand {
     pub name: String,
     pub args: Vec<String>,
@@ -70,27 +79,58 @@ pub fn parse_input(input: &str, history: &[String]) -> Pipeline {
 }
 
 /// Parse input using a target-provided environment snapshot for variable
-/// expansion.
+/// expansion. A `$(...)` substitutes nothing; see
+/// [`parse_input_with_substitution`].
 pub fn parse_input_with_env(
     input: &str,
     history: &[String],
     env: &BTreeMap<String, String>,
 ) -> Pipeline {
-    let mut lexer = Lexer::new_with_env(input, env);
-    let tokens: Vec<Token> = (&mut lexer).collect();
+    parse_input_with_substitution(input, history, env, &mut |_| String::new())
+}
 
-    if let Some(err) = lexer.error().cloned() {
-        return Pipeline {
+/// Parse input like [`parse_input_with_env`], replacing each `$(...)` with
+/// the words of what `run` prints for its pipeline. The output is split on
+/// whitespace, so each line and each word on it becomes one argument; the
+/// first and last join any text around the `$(...)` in its word.
+/// Substitutions nest, and a syntax error inside one fails the whole line.
+pub fn parse_input_with_substitution(
+    input: &str,
+    history: &[String],
+    env: &BTreeMap<String, String>,
+    run: &mut dyn FnMut(&Pipeline) -> String,
+) -> Pipeline {
+    match expand_input(input, history, env, run) {
+        Ok(tokens) => parse_pipeline(tokens),
+        Err(err) => Pipeline {
             commands: vec![],
             error: Some(err),
-        };
+        },
     }
+}
 
-    // Expand variables and history
-    let expanded = expand_tokens(tokens, history);
+/// Lex `input` and expand history, substitutions, then braces.
+fn expand_input(
+    input: &str,
+    history: &[String],
+    env: &BTreeMap<String, String>,
+    run: &mut dyn FnMut(&Pipeline) -> String,
+) -> Result<Vec<Token>, ShellParseError> {
+    let mut lexer = Lexer::new_with_env(input, env);
+    let tokens: Vec<Token> = (&mut lexer).collect();
+    if let Some(err) = lexer.error().cloned() {
+        return Err(err);
+    }
 
-    // Split into pipeline stages
-    parse_pipeline(expanded)
+    let expanded = expand_tokens(tokens, history, env);
+    let expanded = expand_substitutions(expanded, &mut |command| {
+        let pipeline = parse_input_with_substitution(command, history, env, run);
+        match pipeline.error {
+            Some(err) => Err(err),
+            None => Ok(run(&pipeline)),
+        }
+    })?;
+    expand_braces(expanded)
 }
 
 fn parse_pipeline(tokens: Vec<Token>) -> Pipeline {
@@ -182,6 +222,19 @@ mod tests {
         assert_eq!(pipeline.commands[0].args, vec!["-la"]);
     }
 
+    #[test]
+    fn test_history_expansion_keeps_quoted_arguments() {
+        let history = vec![r#"echo "a b""#.to_string()];
+        let pipeline = parse_input("!!", &history);
+        assert_eq!(pipeline.commands.len(), 1);
+        assert_eq!(pipeline.commands[0].name, "echo");
+        assert_eq!(pipeline.commands[0].args, vec!["a b"]);
+
+        let pipeline = parse_input("!0 | grep a", &history);
+        assert_eq!(pipeline.commands[0].args, vec!["a b"]);
+        assert_eq!(pipeline.commands[1].args, vec!["a"]);
+    }
+
     #[test]
     fn test_empty_pipe_leading() {
         let pipeline = parse_input("| grep foo", &[]);
@@ -263,4 +316,162 @@ mod tests {
         assert!(!pipeline.has_error());
         assert_eq!(pipeline.commands[0].args, vec!["", "hello"]);
     }
+
+    fn args(input: &str) -> Vec<String> {
+        let pipeline = parse_input(input, &[]);
+        assert!(!pipeline.has_error(), "{:?}", pipeline.error);
+        pipeline.commands[0].args.clone()
+    }
+
+    #[test]
+    fn test_brace_list_expands_to_words() {
+        assert_eq!(args("echo {a,b,c}"), ["a", "b", "c"]);
+        assert_eq!(args("echo {a,,c}"), ["a", "c"]);
+    }
+
+    #[test]
+    fn test_brace_expansion_keeps_prefix_and_suffix() {
+        assert_eq!(
+            args("ls ~/blog/{2023,2024}"),
+            ["~/blog/2023", "~/blog/2024"]
+        );
+        assert_eq!(args("echo x{a,b}.md y"), ["xa.md", "xb.md", "y"]);
+        assert_eq!(args("echo {a,b}{1,2}"), ["a1", "a2", "b1", "b2"]);
+        assert_eq!(args("echo file{,.bak}"), ["file", "file.bak"]);
+    }
+
+    #[test]
+    fn test_nested_braces_expand_one_level() {
+        assert_eq!(args("echo {a,b{1,2}}"), ["a", "b1", "b2"]);
+        assert_eq!(args("echo x{a,{b,c}d}"), ["xa", "xbd", "xcd"]);
+    }
+
+    #[test]
+    fn test_braces_without_a_list_stay_literal() {
+        assert_eq!(args("echo {} {a} {a,b"), ["{}", "{a}", "{a,b"]);
+        assert_eq!(args("echo {a{b,c}"), ["{ab", "{ac"]);
+        assert_eq!(args("echo {a}{b,c}"), ["{a}b", "{a}c"]);
+        assert_eq!(args("echo {x,y}}"), ["x}", "y}"]);
+    }
+
+    #[test]
+    fn test_quoted_braces_do_not_expand() {
+        assert_eq!(args("echo '{a,b}'"), ["{a,b}"]);
+        assert_eq!(args("echo \"{a,b}\""), ["{a,b}"]);
+        assert_eq!(args("echo \\{a,b}"), ["{a,b}"]);
+        assert_eq!(args("echo {a,'b,c'}"), ["a", "b,c"]);
+    }
+
+    #[test]
+    fn test_variable_values_do_not_expand() {
+        let env = BTreeMap::from([("LIST".to_string(), "{a,b}".to_string())]);
+        let pipeline = parse_input_with_env("echo $LIST {$LIST,c}", &[], &env);
+        assert_eq!(pipeline.commands[0].args, vec!["{a,b}", "{a,b}", "c"]);
+    }
+
+    #[test]
+    fn test_brace_expansion_limit() {
+        assert_eq!(args("echo {a,b}{a,b}{a,b}{a,b}{a,b}{a,b}").len(), 64);
+
+        let pipeline = parse_input("echo {a,b}{a,b}{a,b}{a,b}{a,b}{a,b}{a,b}", &[]);
+        assert!(pipeline.commands.is_empty());
+        assert!(matches!(
+            pipeline.error,
+            Some(ShellParseError::TooManyExpansions { limit: 64, .. })
+        ));
+    }
+
+    #[test]
+    fn test_recalled_history_brace_expands() {
+        let history = vec!["echo {a,b}".to_string()];
+        let pipeline = parse_input("!!", &history);
+        assert_eq!(pipeline.commands[0].args, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_substitution_word_splits_output() {
+        let mut ran = Vec::new();
+        let pipeline = parse_input_with_substitution(
+            "echo $(ls | head -2) end",
+            &[],
+            &BTreeMap::new(),
+            &mut |inner| {
+                ran.push(inner.commands.clone());
+                "a b\n\nc\n".to_string()
+            },
+        );
+        assert!(!pipeline.has_error());
+        assert_eq!(pipeline.commands[0].args, vec!["a", "b", "c", "end"]);
+        assert_eq!(ran.len(), 1);
+        assert_eq!(ran[0][0].name, "ls");
+        assert_eq!(ran[0][1].args, vec!["-2"]);
+    }
+
+    #[test]
+    fn test_empty_substitution_output_drops_the_word() {
+        let pipeline =
+            parse_input_with_substitution("echo $(true) x", &[], &BTreeMap::new(), &mut |_| {
+                String::new()
+            });
+        assert_eq!(pipeline.commands[0].args, vec!["x"]);
+        // Without a runner, substitutions produce nothing.
+        assert_eq!(args("echo $(pwd) x"), ["x"]);
+    }
+
+    #[test]
+    fn test_substitution_syntax_error_fails_the_line() {
+        let pipeline =
+            parse_input_with_substitution("echo $(ls |)", &[], &BTreeMap::new(), &mut |_| {
+                unreachable!("an invalid pipeline is not run")
+            });
+        assert!(pipeline.commands.is_empty());
+        assert!(matches!(
+            pipeline.error,
+            Some(ShellParseError::TrailingPipe { .. })
+        ));
+    }
+
+    #[test]
+    fn test_substituted_words_do_not_brace_expand() {
+        let pipeline =
+            parse_input_with_substitution("echo $(x) {1,2}", &[], &BTreeMap::new(), &mut |_| {
+                "{a,b}".to_string()
+            });
+        assert_eq!(pipeline.commands[0].args, vec!["{a,b}", "1", "2"]);
+    }
+
+    /// Parse `input` with every `$(...)` printing `output`.
+    fn substituted(input: &str, output: &str) -> Vec<String> {
+        let pipeline = parse_input_with_substitution(input, &[], &BTreeMap::new(), &mut |_| {
+            output.to_string()
+        });
+        assert!(!pipeline.has_error(), "{input}");
+        pipeline.commands[0].args.clone()
+    }
+
+    #[test]
+    fn test_substitution_joins_the_text_around_it() {
+        assert_eq!(
+            substituted("cat $(pwd)/notes.md", "/home"),
+            ["/home/notes.md"]
+        );
+        assert_eq!(
+            substituted("ls ~/blog/$(echo 2024)", "2024"),
+            ["~/blog/2024"]
+        );
+        // The first and last output words join their neighbours; the rest
+        // stand alone.
+        assert_eq!(substituted("echo x$(ls)y", "a b\nc"), ["xa", "b", "cy"]);
+        // Leading or trailing whitespace in the output keeps them apart.
+        assert_eq!(substituted("echo x$(ls)y", " a "), ["x", "a", "y"]);
+        // Empty output leaves the text as it is.
+        assert_eq!(substituted("echo x$(true)y", ""), ["xy"]);
+        assert_eq!(substituted("export P=$(pwd)", "/blog"), ["P=/blog"]);
+    }
+
+    #[test]
+    fn test_text_around_a_substitution_still_brace_expands() {
+        assert_eq!(substituted("ls {a,b}/$(x)", "c"), ["a/c", "b/c"]);
+        assert_eq!(substituted("echo $(x){1,2}", "{a,b}"), ["{a,b}1", "{a,b}2"]);
+    }
 }
@@ -1,10 +1,27 @@
 //! Pipeline execution for parsed shell commands.
 
 use crate::domain::{ChangeSet, RuntimeMount, VirtualPath, WalletState};
-use crate::engine::filesystem::GlobalFs;
-use crate::engine::shell::parser::Pipeline;
+use crate::engine::filesystem::{GlobalFs, UserPathError, lookup_user_path};
+use crate::engine::shell::parser::{ParsedCommand, Pipeline};
+use crate::support::i18n::Locale;
 
-use super::{Command, CommandResult, ExecutionContext, apply_filter, execute_command_with_context};
+use super::executor::execute_tee;
+use super::filters::{check_wc_args, wc_files};
+use super::{
+    Command, CommandError, CommandResult, ExecutionContext, FileStage, OutputLine, OutputLineData,
+    SideEffect, StageFile, apply_filter_in, execute_command_with_context,
+};
+
+/// Commands `xargs` may run. They only print, so running one per input
+/// line cannot navigate or change anything mid-pipe.
+const XARGS_COMMANDS: &[&str] = &["echo", "help", "ls", "man", "which"];
+
+/// Commands `xargs` runs over file arguments. File bodies arrive after the
+/// pipe returns, so these hand the target a [`FileStage`] to finish.
+const XARGS_FILE_COMMANDS: &[&str] = &["cat", "wc"];
+
+/// Exit status of `xargs` when any invocation failed, as in GNU xargs.
+const XARGS_FAILED: i32 = 123;
 
 /// Execute a pipeline of commands with pipe filtering.
 ///
@@ -52,9 +69,20 @@ pub fn execute_pipeline_with_context(
         return CommandResult::empty();
     }
 
-    // Execute first command.
+    // Execute first command. Piped output stays one entry per line so
+    // filters see each listed entry on its own.
     let first = &pipeline.commands[0];
     let cmd = Command::parse(&first.name, &first.args);
+    let piped_context;
+    let first_context = if pipeline.commands.len() > 1 {
+        piped_context = ExecutionContext {
+            terminal_columns: None,
+            ..context.clone()
+        };
+        &piped_context
+    } else {
+        context
+    };
     let mut result = execute_command_with_context(
         cmd,
         wallet_state,
@@ -63,23 +91,283 @@ pub fn execute_pipeline_with_context(
         cwd,
         changes,
         remote_head,
-        context,
+        first_context,
     );
 
     if pipeline.commands.len() == 1 {
         return result;
     }
 
-    // Pipeline mode: side effects are discarded (cannot navigate or mutate mid-pipe).
+    // A fetch finishes after the pipe returns, so it takes the filter
+    // stages along and applies them to the body itself.
+    if let Some(SideEffect::Fetch { url, .. }) = result
+        .side_effects
+        .iter()
+        .find(|effect| matches!(effect, SideEffect::Fetch { .. }))
+    {
+        let filters = pipeline.commands[1..].to_vec();
+        return CommandResult::output(result.output)
+            .with_exit_code(result.exit_code)
+            .with_side_effect(SideEffect::Fetch {
+                url: url.clone(),
+                filters,
+            });
+    }
+
+    // Pipeline mode: side effects are discarded (cannot navigate or mutate
+    // mid-pipe), apart from the file writes of `tee` stages below.
     result.side_effects.clear();
     let mut current_lines = result.output;
     let mut current_exit = result.exit_code;
 
-    for filter_cmd in pipeline.commands.iter().skip(1) {
-        let stage = apply_filter(&filter_cmd.name, &filter_cmd.args, current_lines);
+    let run = |cmd: Command| {
+        execute_command_with_context(
+            cmd,
+            wallet_state,
+            runtime_mounts,
+            fs,
+            cwd,
+            changes,
+            remote_head,
+            first_context,
+        )
+    };
+    // `tee` writes are the one effect a pipe stage may carry out.
+    let mut writes = Vec::new();
+    for (index, filter_cmd) in pipeline.commands.iter().enumerate().skip(1) {
+        let name = filter_cmd.name.to_lowercase();
+        let mut stage = match name.as_str() {
+            "xargs" => execute_xargs(&filter_cmd.args, current_lines, fs, cwd, context, run),
+            "tee" => execute_tee(
+                &filter_cmd.args,
+                current_lines,
+                wallet_state,
+                runtime_mounts,
+                fs,
+                cwd,
+                changes,
+                context,
+            ),
+            _ => apply_filter_in(
+                &filter_cmd.name,
+                &filter_cmd.args,
+                current_lines,
+                context.locale(),
+            ),
+        };
+        // Files an `xargs` stage reads take the remaining stages along,
+        // as a fetch does.
+        if let Some(SideEffect::ReadFiles(read)) = stage
+            .side_effects
+            .iter_mut()
+            .find(|effect| matches!(effect, SideEffect::ReadFiles(_)))
+        {
+            read.filters = pipeline.commands[index + 1..].to_vec();
+            writes.extend(stage.side_effects);
+            let mut result = CommandResult::output(stage.output).with_exit_code(stage.exit_code);
+            result.side_effects = writes;
+            return result;
+        }
         current_lines = stage.output;
         current_exit = stage.exit_code;
+        writes.extend(stage.side_effects);
+    }
+
+    let mut result = CommandResult::output(current_lines).with_exit_code(current_exit);
+    result.side_effects = writes;
+    result
+}
+
+/// What a `$(...)` pipeline substitutes: its output lines as text, listed
+/// entries by name. Error lines are left out; the target shows them.
+pub fn substitution_text(output: &[OutputLine]) -> String {
+    output
+        .iter()
+        .filter(|line| !matches!(line.data, OutputLineData::Error(_)))
+        .filter_map(xargs_argument)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `xargs [-n N] [command [args...]]`: run `command` (default `echo`) with
+/// the incoming lines appended as arguments, `N` lines per run (default 1),
+/// and concatenate the outputs. Each non-empty line is one argument, so
+/// names containing spaces stay whole. Listed entries pass their name.
+/// `cat` and `wc` take the arguments as files; see [`xargs_read_files`].
+fn execute_xargs(
+    args: &[String],
+    lines: Vec<OutputLine>,
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+    context: &ExecutionContext,
+    run: impl Fn(Command) -> CommandResult,
+) -> CommandResult {
+    let (per_run, command) = match parse_xargs_args(args) {
+        Ok(parsed) => parsed,
+        Err(message) => return CommandError::usage("xargs", message).into(),
+    };
+    let (name, fixed) = command
+        .split_first()
+        .map_or(("echo", &[][..]), |(name, rest)| (name.as_str(), rest));
+    let inputs: Vec<String> = lines.iter().filter_map(xargs_argument).collect();
+
+    if let Some(file_command) = XARGS_FILE_COMMANDS
+        .iter()
+        .find(|allowed| name.eq_ignore_ascii_case(allowed))
+    {
+        let locale = context.locale();
+        return xargs_read_files(file_command, fixed, inputs, per_run, fs, cwd, locale)
+            .unwrap_or_else(|error| error.into_result(locale));
+    }
+    if !XARGS_COMMANDS
+        .iter()
+        .any(|allowed| name.eq_ignore_ascii_case(allowed))
+    {
+        let supported = [XARGS_COMMANDS, XARGS_FILE_COMMANDS].concat();
+        return CommandError::usage(
+            "xargs",
+            format!("cannot run '{name}' (supported: {})", supported.join(", ")),
+        )
+        .into();
+    }
+
+    let mut output = Vec::new();
+    let mut exit_code = 0;
+    for batch in inputs.chunks(per_run) {
+        let args: Vec<String> = fixed.iter().cloned().chain(batch.iter().cloned()).collect();
+        let result = run(Command::parse(name, &args));
+        if result.exit_code != 0 {
+            exit_code = XARGS_FAILED;
+        }
+        output.extend(result.output);
     }
+    CommandResult::output(output).with_exit_code(exit_code)
+}
+
+/// `xargs cat` / `xargs wc`: resolve the fixed file arguments and the
+/// incoming ones, report those that are not files, and leave reading the
+/// rest to the target. `wc` flags among the fixed arguments apply to every
+/// run; `cat` takes none.
+fn xargs_read_files(
+    name: &str,
+    fixed: &[String],
+    inputs: Vec<String>,
+    per_run: usize,
+    fs: &GlobalFs,
+    cwd: &VirtualPath,
+    locale: Locale,
+) -> Result<CommandResult, CommandError> {
+    let (flags, named): (Vec<String>, Vec<String>) = fixed
+        .iter()
+        .cloned()
+        .partition(|arg| arg.len() > 1 && arg.starts_with('-'));
+    match (name, flags.first()) {
+        ("wc", _) => check_wc_args(&flags)?,
+        (_, Some(flag)) => {
+            return Err(CommandError::usage(name, format!("unknown option: {flag}")));
+        }
+        (_, None) => {}
+    }
+
+    let mut errors = Vec::new();
+    let mut files = Vec::new();
+    for raw in named.into_iter().chain(inputs) {
+        let error = match lookup_user_path(fs, cwd, &raw) {
+            Ok((path, false)) => {
+                files.push(StageFile { path, display: raw });
+                continue;
+            }
+            Ok((_, true)) => CommandError::is_a_directory(name, &raw),
+            Err(UserPathError::Invalid(_)) => CommandError::invalid_path(name, &raw),
+            Err(UserPathError::NotFound(_)) => CommandError::not_found(name, &raw),
+            Err(UserPathError::NotADirectory(_)) => CommandError::not_a_directory(name, &raw),
+        };
+        errors.push(OutputLine::error(error.localized(locale)));
+    }
+
+    let exit_code = if errors.is_empty() { 0 } else { XARGS_FAILED };
+    let result = CommandResult::output(errors).with_exit_code(exit_code);
+    if files.is_empty() {
+        return Ok(result);
+    }
+    Ok(result.with_side_effect(SideEffect::ReadFiles(FileStage {
+        command: ParsedCommand {
+            name: name.to_string(),
+            args: flags,
+        },
+        files,
+        per_run,
+        filters: Vec::new(),
+    })))
+}
 
-    CommandResult::output(current_lines).with_exit_code(current_exit)
+/// What an `xargs cat` or `xargs wc` stage prints once the target has read
+/// its files. `texts` holds each file's text, or why it could not be read,
+/// in the order of `stage.files`. Read errors come first; the remaining
+/// pipe stages run over the rest.
+pub fn file_stage_output(
+    stage: &FileStage,
+    texts: Vec<Result<String, String>>,
+    locale: Locale,
+) -> CommandResult {
+    let name = stage.command.name.as_str();
+    let mut errors = Vec::new();
+    let mut output = Vec::new();
+    let read: Vec<_> = stage.files.iter().zip(texts).collect();
+    for batch in read.chunks(stage.per_run.max(1)) {
+        let mut files = Vec::new();
+        for (file, text) in batch {
+            match text {
+                Ok(text) => files.push((
+                    file.display.clone(),
+                    text.lines().map(OutputLine::text).collect::<Vec<_>>(),
+                )),
+                Err(error) => errors.push(OutputLine::error(format!(
+                    "{name}: {}: {error}",
+                    file.display
+                ))),
+            }
+        }
+        if name == "wc" {
+            match wc_files(&stage.command.args, &files) {
+                Ok(lines) => output.extend(lines),
+                Err(error) => return error.into_result(locale),
+            }
+        } else {
+            output.extend(files.into_iter().flat_map(|(_, lines)| lines));
+        }
+    }
+
+    let mut exit_code = if errors.is_empty() { 0 } else { XARGS_FAILED };
+    for filter in &stage.filters {
+        let result = apply_filter_in(&filter.name, &filter.args, output, locale);
+        output = result.output;
+        exit_code = result.exit_code;
+    }
+    errors.extend(output);
+    CommandResult::output(errors).with_exit_code(exit_code)
+}
+
+/// The argument a piped line stands for: a listed entry's name, or the
+/// line's trimmed text. Blank lines give none.
+fn xargs_argument(line: &OutputLine) -> Option<String> {
+    let text = match &line.data {
+        OutputLineData::ListEntry { name, .. } => name.clone(),
+        _ => line.to_plain_text(),
+    };
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Split `xargs` arguments into the batch size and the command to run.
+fn parse_xargs_args(args: &[String]) -> Result<(usize, &[String]), String> {
+    match args {
+        [flag] if flag == "-n" => Err("option requires an argument: -n".to_string()),
+        [flag, count, command @ ..] if flag == "-n" => match count.parse::<usize>() {
+            Ok(per_run) if per_run > 0 => Ok((per_run, command)),
+            _ => Err(format!("invalid number: {count}")),
+        },
+        [flag, ..] if flag.starts_with('-') => Err(format!("unknown option: {flag}")),
+        command => Ok((1, command)),
+    }
 }
@@ -0,0 +1,331 @@
+//! Prompt rendering from the `PS1` variable.
+
+use std::fmt;
+
+use super::TextStyle;
+
+/// Values a `PS1` template can refer to.
+#[derive(Clone, Copy, Debug)]
+pub struct PromptVars<'a> {
+    /// `\u`: the connected wallet's display name.
+    pub user: &'a str,
+    /// `\h`: the app name.
+    pub host: &'a str,
+    /// `\w`: the working directory as displayed (`~/blog`). `\W` is its
+    /// last segment.
+    pub path: &'a str,
+    /// `\$` is `#` instead of `$` for an admin wallet.
+    pub admin: bool,
+}
+
+/// A run of prompt text in one style. `None` is the prompt's own colour.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PromptSegment {
+    pub text: String,
+    pub style: Option<TextStyle>,
+}
+
+/// A rendered prompt: the styled text, then the separator the terminal
+/// prints before the input (`$ `, or `# ` when the template ends in `\$`
+/// for an admin).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Prompt {
+    pub segments: Vec<PromptSegment>,
+    pub separator: char,
+}
+
+impl Prompt {
+    /// A prompt of unstyled `text` with the `$` separator.
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self {
+            segments: vec![PromptSegment {
+                text: text.into(),
+                style: None,
+            }],
+            separator: '$',
+        }
+    }
+
+    /// The prompt text without styles or separator.
+    pub fn text(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for Prompt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.segments
+            .iter()
+            .try_for_each(|segment| f.write_str(&segment.text))
+    }
+}
+
+impl From<&str> for Prompt {
+    fn from(text: &str) -> Self {
+        Self::plain(text)
+    }
+}
+
+impl From<String> for Prompt {
+    fn from(text: String) -> Self {
+        Self::plain(text)
+    }
+}
+
+impl PartialEq<&str> for Prompt {
+    fn eq(&self, other: &&str) -> bool {
+        self.segments.iter().all(|segment| segment.style.is_none()) && self.text() == *other
+    }
+}
+
+/// Render the prompt shown before the input.
+///
+/// Without a `PS1` (or with an empty one) this is `user@host:path`.
+/// Otherwise the template's escapes are expanded: `\u`, `\h`, `\w`, `\W`,
+/// `\$` and `\n`, plus `\\` for a backslash. `{cyan}`, `{dim}` and `{fg}`
+/// start a styled run and `{/}` ends it. Any other escape or tag is kept
+/// as written. The terminal always ends the prompt with its separator, so
+/// a trailing `$` or `\$` in the template becomes the separator rather
+/// than being shown twice.
+pub fn render_prompt(ps1: Option<&str>, vars: PromptVars<'_>) -> Prompt {
+    let Some(template) = ps1.filter(|template| !template.trim().is_empty()) else {
+        return Prompt::plain(format!("{}@{}:{}", vars.user, vars.host, vars.path));
+    };
+
+    let mut renderer = Renderer::default();
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('u') => renderer.push_str(vars.user),
+                Some('h') => renderer.push_str(vars.host),
+                Some('w') => renderer.push_str(vars.path),
+                Some('W') => renderer.push_str(last_segment(vars.path)),
+                Some('$') => renderer.push_sign(if vars.admin { '#' } else { '$' }),
+                Some('n') => renderer.push_str("\n"),
+                Some('\\') => renderer.push_str("\\"),
+                Some(other) => {
+                    renderer.push_str("\\");
+                    renderer.push_char(other);
+                }
+                None => renderer.push_str("\\"),
+            },
+            '{' => match parse_tag(chars.as_str()) {
+                Some((style, len)) => {
+                    renderer.style = style;
+                    chars = chars.as_str()[len..].chars();
+                }
+                None => renderer.push_str("{"),
+            },
+            '$' => renderer.push_sign('$'),
+            c => renderer.push_char(c),
+        }
+    }
+    renderer.finish()
+}
+
+/// The style switch of a tag at the start of `rest` (just after its `{`),
+/// and the bytes it spans through the closing `}`. `{/}` returns to the
+/// prompt's own colour.
+fn parse_tag(rest: &str) -> Option<(Option<TextStyle>, usize)> {
+    let (name, _) = rest.split_once('}')?;
+    let style = match name {
+        "/" => None,
+        "cyan" => Some(TextStyle::Directory),
+        "dim" => Some(TextStyle::Hidden),
+        "fg" => Some(TextStyle::File),
+        _ => return None,
+    };
+    Some((style, name.len() + 1))
+}
+
+/// Last segment of a displayed path: `blog` for `~/blog`, `~` for `~`.
+fn last_segment(path: &str) -> &str {
+    path.rsplit('/')
+        .find(|segment| !segment.is_empty())
+        .unwrap_or(path)
+}
+
+/// Accumulates styled runs while a template is expanded.
+#[derive(Default)]
+struct Renderer {
+    segments: Vec<PromptSegment>,
+    style: Option<TextStyle>,
+    /// Prompt sign (`$`, or `#` from `\$`) with nothing but whitespace
+    /// after it so far.
+    trailing_sign: Option<char>,
+}
+
+impl Renderer {
+    fn push_char(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0; 4]));
+    }
+
+    fn push_str(&mut self, text: &str) {
+        if !text.trim().is_empty() {
+            self.trailing_sign = None;
+        }
+        match self.segments.last_mut() {
+            Some(last) if last.style == self.style => last.text.push_str(text),
+            _ => self.segments.push(PromptSegment {
+                text: text.to_string(),
+                style: self.style,
+            }),
+        }
+    }
+
+    fn push_sign(&mut self, sign: char) {
+        self.push_char(sign);
+        self.trailing_sign = Some(sign);
+    }
+
+    /// Trim trailing whitespace and turn a trailing prompt sign into the
+    /// separator.
+    fn finish(mut self) -> Prompt {
+        self.trim_end();
+        let mut separator = '$';
+        if let Some(sign) = self.trailing_sign
+            && let Some(last) = self.segments.last_mut()
+        {
+            last.text.pop();
+            separator = sign;
+            self.trim_end();
+        }
+        Prompt {
+            segments: self.segments,
+            separator,
+        }
+    }
+
+    fn trim_end(&mut self) {
+        while let Some(last) = self.segments.last_mut() {
+            last.text.truncate(last.text.trim_end().len());
+            if !last.text.is_empty() {
+                break;
+            }
+            self.segments.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VARS: PromptVars<'static> = PromptVars {
+        user: "guest",
+        host: "websh",
+        path: "~/blog",
+        admin: false,
+    };
+
+    const ADMIN: PromptVars<'static> = PromptVars {
+        admin: true,
+        ..VARS
+    };
+
+    fn text(ps1: &str, vars: PromptVars<'_>) -> String {
+        render_prompt(Some(ps1), vars).text()
+    }
+
+    #[test]
+    fn falls_back_to_user_at_host_colon_path() {
+        for ps1 in [None, Some(""), Some("  ")] {
+            let prompt = render_prompt(ps1, VARS);
+            assert_eq!(prompt, Prompt::plain("guest@websh:~/blog"));
+        }
+        assert_eq!(render_prompt(None, ADMIN).separator, '$');
+    }
+
+    #[test]
+    fn expands_user_host_and_path() {
+        assert_eq!(text(r"\u@\h \w", VARS), "guest@websh ~/blog");
+        assert_eq!(text(r"[\w]", VARS), "[~/blog]");
+    }
+
+    #[test]
+    fn short_path_is_the_last_segment() {
+        assert_eq!(text(r"\W", VARS), "blog");
+        for (path, expected) in [("~", "~"), ("/", "/"), ("/mempool/a/", "a")] {
+            let vars = PromptVars { path, ..VARS };
+            assert_eq!(text(r"\W", vars), expected, "{path}");
+        }
+    }
+
+    #[test]
+    fn trailing_dollar_is_left_to_the_separator() {
+        let prompt = render_prompt(Some(r"\u@\h \w \$ "), VARS);
+        assert_eq!(prompt.text(), "guest@websh ~/blog");
+        assert_eq!(prompt.separator, '$');
+        assert_eq!(text(r"\w $", VARS), "~/blog");
+        assert_eq!(text(r"cost\$5 \w", VARS), "cost$5 ~/blog");
+    }
+
+    #[test]
+    fn admin_prompt_sign_is_a_hash() {
+        let prompt = render_prompt(Some(r"\w \$ "), ADMIN);
+        assert_eq!(prompt.text(), "~/blog");
+        assert_eq!(prompt.separator, '#');
+        assert_eq!(text(r"a\$b", ADMIN), "a#b");
+        // A literal `$` is not the prompt sign.
+        assert_eq!(render_prompt(Some(r"\w $"), ADMIN).separator, '$');
+    }
+
+    #[test]
+    fn newline_and_backslash_escapes() {
+        assert_eq!(text(r"\w\n\u", VARS), "~/blog\nguest");
+        assert_eq!(text(r"a\\b", VARS), r"a\b");
+    }
+
+    #[test]
+    fn unknown_escapes_are_kept_verbatim() {
+        assert_eq!(text(r"\t \w \", VARS), r"\t ~/blog \");
+    }
+
+    #[test]
+    fn colour_tags_style_runs() {
+        let prompt = render_prompt(Some(r"{cyan}\w{/} {dim}λ{/}"), VARS);
+        assert_eq!(
+            prompt.segments,
+            vec![
+                PromptSegment {
+                    text: "~/blog".to_string(),
+                    style: Some(TextStyle::Directory),
+                },
+                PromptSegment {
+                    text: " ".to_string(),
+                    style: None,
+                },
+                PromptSegment {
+                    text: "λ".to_string(),
+                    style: Some(TextStyle::Hidden),
+                },
+            ]
+        );
+        let prompt = render_prompt(Some(r"{fg}\u{cyan}\w"), VARS);
+        assert_eq!(
+            prompt
+                .segments
+                .iter()
+                .map(|segment| segment.style)
+                .collect::<Vec<_>>(),
+            [Some(TextStyle::File), Some(TextStyle::Directory)]
+        );
+    }
+
+    #[test]
+    fn unknown_or_unclosed_tags_are_kept_verbatim() {
+        for ps1 in ["{red}x{/}", "{cyan x", "{}", "{/", "a{b}c"] {
+            let prompt = render_prompt(Some(ps1), VARS);
+            assert_eq!(prompt.text(), ps1.replace("{/}", ""), "{ps1}");
+        }
+        assert_eq!(text("{red}x", VARS), "{red}x");
+    }
+
+    #[test]
+    fn plain_prompts_compare_with_str() {
+        assert_eq!(Prompt::from("guest@websh:~"), "guest@websh:~");
+        let styled = render_prompt(Some("{cyan}x"), VARS);
+        assert_ne!(styled, "x");
+    }
+}
@@ -0,0 +1,527 @@
+//! Named snapshots of the restorable shell state behind `session`.
+//!
+//! A [`SessionSnapshot`] holds what a visitor sets up by hand: the route
+//! (which also says terminal or reader, and where), the theme, and the user
+//! variables. The target keeps named slots plus an autosaved "last session"
+//! in a [`SessionStore`], and restores one by asking [`plan_restore`] what
+//! differs from the current state, applying each [`SessionChange`] and
+//! printing [`restore_lines`].
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::OutputLine;
+
+/// Named slots kept at most; the autosave does not count against it.
+pub const MAX_SESSION_SLOTS: usize = 20;
+
+/// Longest slot name accepted.
+const MAX_SLOT_NAME_CHARS: usize = 32;
+
+/// Variable a snapshot records in [`SessionSnapshot::theme`] rather than
+/// among its variables, because setting a theme does more than set it.
+const THEME_VAR: &str = "THEME";
+
+/// `session` subcommands.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SessionAction {
+    Save(String),
+    Load(String),
+    List,
+    Remove(String),
+    /// Load the snapshot autosaved when the page was last hidden.
+    RestoreLast,
+}
+
+/// The restorable state at one moment.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    /// Route path, e.g. `/websh/blog` for the terminal in `~/blog`.
+    #[serde(default)]
+    pub route: String,
+    #[serde(default)]
+    pub theme: Option<String>,
+    /// User variables, `THEME` aside.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+}
+
+impl SessionSnapshot {
+    /// Snapshot `route`, `theme` and the user variables in `env`.
+    pub fn capture(route: &str, theme: &str, env: &BTreeMap<String, String>) -> Self {
+        let mut env = env.clone();
+        env.remove(THEME_VAR);
+        Self {
+            route: route.to_string(),
+            theme: Some(theme.to_string()),
+            env,
+        }
+    }
+}
+
+/// Every saved snapshot, as persisted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionStore {
+    #[serde(default)]
+    slots: BTreeMap<String, SessionSnapshot>,
+    #[serde(default)]
+    last: Option<SessionSnapshot>,
+}
+
+impl SessionStore {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Parse stored snapshots. Unreadable input starts over empty.
+    pub fn from_json(json: &str) -> Self {
+        serde_json::from_str(json).unwrap_or_default()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&SessionSnapshot> {
+        self.slots.get(name)
+    }
+
+    /// Store `snapshot` under `name`. Returns whether a snapshot of that
+    /// name was replaced, or an error when a new slot would pass
+    /// [`MAX_SESSION_SLOTS`].
+    pub fn save(&mut self, name: &str, snapshot: SessionSnapshot) -> Result<bool, String> {
+        if !self.slots.contains_key(name) && self.slots.len() >= MAX_SESSION_SLOTS {
+            return Err(format!(
+                "{MAX_SESSION_SLOTS} snapshots saved already; remove one with 'session rm <name>'"
+            ));
+        }
+        Ok(self.slots.insert(name.to_string(), snapshot).is_some())
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.slots.remove(name).is_some()
+    }
+
+    pub fn last(&self) -> Option<&SessionSnapshot> {
+        self.last.as_ref()
+    }
+
+    pub fn set_last(&mut self, snapshot: SessionSnapshot) {
+        self.last = Some(snapshot);
+    }
+
+    /// `session list`: one aligned `name  route  theme  vars` row per slot,
+    /// then the autosave.
+    pub fn list_lines(&self) -> Vec<OutputLine> {
+        if self.slots.is_empty() && self.last.is_none() {
+            return vec![OutputLine::text(
+                "No saved sessions. Save one with 'session save <name>'.",
+            )];
+        }
+        let width = self
+            .slots
+            .keys()
+            .map(|name| name.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut lines: Vec<OutputLine> = self
+            .slots
+            .iter()
+            .map(|(name, snapshot)| {
+                OutputLine::text(format!("{name:<width$}  {}", summary(snapshot)))
+            })
+            .collect();
+        if let Some(last) = &self.last {
+            lines.push(OutputLine::info(format!(
+                "last session: {} (session restore-last)",
+                summary(last)
+            )));
+        }
+        lines
+    }
+}
+
+fn summary(snapshot: &SessionSnapshot) -> String {
+    let vars = match snapshot.env.len() {
+        1 => "1 variable".to_string(),
+        count => format!("{count} variables"),
+    };
+    match &snapshot.theme {
+        Some(theme) => format!("{}  {theme}  {vars}", snapshot.route),
+        None => format!("{}  {vars}", snapshot.route),
+    }
+}
+
+/// Whether `name` can name a slot: letters, digits, `-`, `_` and `.`.
+pub fn is_valid_session_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().count() <= MAX_SLOT_NAME_CHARS
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// One step from the current state toward a snapshot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SessionChange {
+    Route {
+        from: String,
+        to: String,
+    },
+    Theme {
+        from: String,
+        to: String,
+    },
+    SetVar {
+        key: String,
+        from: Option<String>,
+        to: String,
+    },
+    UnsetVar {
+        key: String,
+    },
+}
+
+/// What restoring a snapshot changes, and what it had to leave out.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RestorePlan {
+    pub changes: Vec<SessionChange>,
+    pub warnings: Vec<String>,
+}
+
+/// Compare `current` with `target`. A route that no longer `resolves` or a
+/// theme that is not `known` is skipped with a warning, so the rest of the
+/// snapshot still applies.
+pub fn plan_restore(
+    current: &SessionSnapshot,
+    target: &SessionSnapshot,
+    resolves: impl Fn(&str) -> bool,
+    known_theme: impl Fn(&str) -> bool,
+) -> RestorePlan {
+    let mut plan = RestorePlan::default();
+
+    if !target.route.is_empty() && target.route != current.route {
+        if resolves(&target.route) {
+            plan.changes.push(SessionChange::Route {
+                from: current.route.clone(),
+                to: target.route.clone(),
+            });
+        } else {
+            plan.warnings.push(format!(
+                "{} no longer exists; staying at {}",
+                target.route, current.route
+            ));
+        }
+    }
+
+    if let Some(theme) = &target.theme
+        && current.theme.as_ref() != Some(theme)
+    {
+        if known_theme(theme) {
+            plan.changes.push(SessionChange::Theme {
+                from: current.theme.clone().unwrap_or_default(),
+                to: theme.clone(),
+            });
+        } else {
+            plan.warnings.push(format!(
+                "theme '{theme}' is not available; keeping the current one"
+            ));
+        }
+    }
+
+    for (key, value) in &target.env {
+        let from = current.env.get(key);
+        if from != Some(value) {
+            plan.changes.push(SessionChange::SetVar {
+                key: key.clone(),
+                from: from.cloned(),
+                to: value.clone(),
+            });
+        }
+    }
+    for key in current.env.keys() {
+        if !target.env.contains_key(key) {
+            plan.changes
+                .push(SessionChange::UnsetVar { key: key.clone() });
+        }
+    }
+
+    plan
+}
+
+/// What `session load` prints for `plan`: a header, one line per change,
+/// then the warnings.
+pub fn restore_lines(name: &str, plan: &RestorePlan) -> Vec<OutputLine> {
+    let mut lines = if plan.changes.is_empty() {
+        vec![OutputLine::info(format!(
+            "session: {name} matches the current state"
+        ))]
+    } else {
+        vec![OutputLine::success(format!("session: restored {name}"))]
+    };
+    lines.extend(plan.changes.iter().map(|change| {
+        OutputLine::text(match change {
+            SessionChange::Route { from, to } => format!("  route  {from} -> {to}"),
+            SessionChange::Theme { from, to } => format!("  theme  {from} -> {to}"),
+            SessionChange::SetVar {
+                key,
+                from: Some(from),
+                to,
+            } => format!("  set    {key}={to} (was {from})"),
+            SessionChange::SetVar {
+                key,
+                from: None,
+                to,
+            } => format!("  set    {key}={to}"),
+            SessionChange::UnsetVar { key } => format!("  unset  {key}"),
+        })
+    }));
+    lines.extend(
+        plan.warnings
+            .iter()
+            .map(|warning| OutputLine::error(format!("session: {warning}"))),
+    );
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::shell::OutputLineData;
+
+    fn snapshot(route: &str, theme: &str, env: &[(&str, &str)]) -> SessionSnapshot {
+        SessionSnapshot {
+            route: route.to_string(),
+            theme: Some(theme.to_string()),
+            env: env
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    fn text(line: &OutputLine) -> &str {
+        match &line.data {
+            OutputLineData::Text(text)
+            | OutputLineData::Error(text)
+            | OutputLineData::Success(text)
+            | OutputLineData::Info(text) => text,
+            other => panic!("unexpected line {other:?}"),
+        }
+    }
+
+    #[test]
+    fn capture_moves_the_theme_out_of_the_variables() {
+        let env = BTreeMap::from([
+            ("THEME".to_string(), "dracula".to_string()),
+            ("CRT".to_string(), "on".to_string()),
+        ]);
+        let captured = SessionSnapshot::capture("/websh/blog", "dracula", &env);
+        assert_eq!(
+            captured,
+            snapshot("/websh/blog", "dracula", &[("CRT", "on")])
+        );
+    }
+
+    #[test]
+    fn store_round_trips_through_json() {
+        let mut store = SessionStore::default();
+        store
+            .save("work", snapshot("/websh/blog", "dracula", &[("CRT", "on")]))
+            .unwrap();
+        store.set_last(snapshot("/", "kanagawa", &[]));
+        assert_eq!(SessionStore::from_json(&store.to_json()), store);
+    }
+
+    #[test]
+    fn unreadable_json_starts_empty() {
+        assert_eq!(SessionStore::from_json("not json"), SessionStore::default());
+        let partial = SessionStore::from_json(r#"{"slots":{"a":{"route":"/blog"}}}"#);
+        assert_eq!(
+            partial.get("a"),
+            Some(&SessionSnapshot {
+                route: "/blog".to_string(),
+                ..SessionSnapshot::default()
+            })
+        );
+        assert_eq!(partial.last(), None);
+    }
+
+    #[test]
+    fn save_replaces_by_name_and_caps_new_slots() {
+        let mut store = SessionStore::default();
+        assert_eq!(store.save("a", snapshot("/", "x", &[])), Ok(false));
+        assert_eq!(store.save("a", snapshot("/blog", "x", &[])), Ok(true));
+        assert_eq!(store.get("a").unwrap().route, "/blog");
+
+        for index in 1..MAX_SESSION_SLOTS {
+            store
+                .save(&format!("s{index}"), snapshot("/", "x", &[]))
+                .unwrap();
+        }
+        assert!(store.save("one-more", snapshot("/", "x", &[])).is_err());
+        // Overwriting an existing slot still works when full.
+        assert_eq!(store.save("a", snapshot("/", "x", &[])), Ok(true));
+        assert!(store.remove("a"));
+        assert!(!store.remove("a"));
+        assert_eq!(store.save("one-more", snapshot("/", "x", &[])), Ok(false));
+    }
+
+    #[test]
+    fn session_names() {
+        for name in ["work", "blog-2024", "a.b_c", "x"] {
+            assert!(is_valid_session_name(name), "{name}");
+        }
+        for name in ["", "has space", "slash/y", "ümlaut", &"n".repeat(33)] {
+            assert!(!is_valid_session_name(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn list_shows_slots_and_the_autosave() {
+        assert_eq!(SessionStore::default().list_lines().len(), 1);
+
+        let mut store = SessionStore::default();
+        store
+            .save("work", snapshot("/websh/blog", "dracula", &[("CRT", "on")]))
+            .unwrap();
+        store
+            .save("b", snapshot("/", "kanagawa", &[("A", "1"), ("B", "2")]))
+            .unwrap();
+        store.set_last(snapshot("/websh", "dracula", &[]));
+        let lines = store.list_lines();
+        let lines: Vec<&str> = lines.iter().map(text).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "b     /  kanagawa  2 variables",
+                "work  /websh/blog  dracula  1 variable",
+                "last session: /websh  dracula  0 variables (session restore-last)",
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_lists_every_difference() {
+        let current = snapshot("/websh", "kanagawa", &[("CRT", "off"), ("EDITOR", "vim")]);
+        let target = snapshot("/websh/blog", "dracula", &[("CRT", "on"), ("PS1", "$ ")]);
+        let plan = plan_restore(&current, &target, |_| true, |_| true);
+        assert!(plan.warnings.is_empty());
+        assert_eq!(
+            plan.changes,
+            vec![
+                SessionChange::Route {
+                    from: "/websh".to_string(),
+                    to: "/websh/blog".to_string(),
+                },
+                SessionChange::Theme {
+                    from: "kanagawa".to_string(),
+                    to: "dracula".to_string(),
+                },
+                SessionChange::SetVar {
+                    key: "CRT".to_string(),
+                    from: Some("off".to_string()),
+                    to: "on".to_string(),
+                },
+                SessionChange::SetVar {
+                    key: "PS1".to_string(),
+                    from: None,
+                    to: "$ ".to_string(),
+                },
+                SessionChange::UnsetVar {
+                    key: "EDITOR".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_for_the_current_state_is_empty() {
+        let current = snapshot("/websh", "kanagawa", &[("CRT", "on")]);
+        let plan = plan_restore(&current, &current.clone(), |_| true, |_| true);
+        assert_eq!(plan, RestorePlan::default());
+        let lines = restore_lines("work", &plan);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(text(&lines[0]), "session: work matches the current state");
+    }
+
+    #[test]
+    fn missing_routes_and_themes_become_warnings() {
+        let current = snapshot("/websh", "kanagawa", &[]);
+        let target = snapshot("/websh/gone", "retired", &[("CRT", "on")]);
+        let plan = plan_restore(
+            &current,
+            &target,
+            |route| route != "/websh/gone",
+            |theme| theme != "retired",
+        );
+        // The variables still apply.
+        assert_eq!(
+            plan.changes,
+            vec![SessionChange::SetVar {
+                key: "CRT".to_string(),
+                from: None,
+                to: "on".to_string(),
+            }]
+        );
+        assert_eq!(
+            plan.warnings,
+            vec![
+                "/websh/gone no longer exists; staying at /websh".to_string(),
+                "theme 'retired' is not available; keeping the current one".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshots_without_a_route_or_theme_leave_them_alone() {
+        let current = snapshot("/websh", "kanagawa", &[]);
+        let target = SessionSnapshot::default();
+        assert_eq!(
+            plan_restore(&current, &target, |_| false, |_| false),
+            RestorePlan::default()
+        );
+    }
+
+    #[test]
+    fn restore_lines_describe_each_change() {
+        let plan = RestorePlan {
+            changes: vec![
+                SessionChange::Route {
+                    from: "/websh".to_string(),
+                    to: "/blog".to_string(),
+                },
+                SessionChange::Theme {
+                    from: "kanagawa".to_string(),
+                    to: "dracula".to_string(),
+                },
+                SessionChange::SetVar {
+                    key: "CRT".to_string(),
+                    from: Some("off".to_string()),
+                    to: "on".to_string(),
+                },
+                SessionChange::SetVar {
+                    key: "PS1".to_string(),
+                    from: None,
+                    to: "$ ".to_string(),
+                },
+                SessionChange::UnsetVar {
+                    key: "EDITOR".to_string(),
+                },
+            ],
+            warnings: vec!["theme 'x' is not available; keeping the current one".to_string()],
+        };
+        let lines = restore_lines("work", &plan);
+        let texts: Vec<&str> = lines.iter().map(text).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "session: restored work",
+                "  route  /websh -> /blog",
+                "  theme  kanagawa -> dracula",
+                "  set    CRT=on (was off)",
+                "  set    PS1=$ ",
+                "  unset  EDITOR",
+                "session: theme 'x' is not available; keeping the current one",
+            ]
+        );
+        assert!(matches!(lines[6].data, OutputLineData::Error(_)));
+    }
+}
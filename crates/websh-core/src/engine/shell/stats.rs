@@ -0,0 +1,245 @@
+//! Local usage counters behind `stats`.
+//!
+//! [`UsageStats`] counts command invocations, opened files and visited
+//! directories. The target keeps one copy for the current session and one
+//! persisted across sessions, feeds both from the submit path and route
+//! changes, and hands them to `stats` as a [`UsageReport`]. Nothing leaves
+//! the browser, and `TRACKING=off` stops the counting.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Variable that turns tracking off with `off`, `0`, `false` or `no`.
+pub const TRACKING_VAR: &str = "TRACKING";
+
+/// Distinct keys kept per counter. Past it, the least used key (other than
+/// the one just counted) is dropped, which bounds the persisted size.
+pub const MAX_TRACKED_KEYS: usize = 200;
+
+/// Longest key stored; longer paths are cut to keep entries small.
+const MAX_KEY_CHARS: usize = 256;
+
+/// Counters for one span of use: a session, or all sessions together.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageStats {
+    #[serde(default)]
+    commands: BTreeMap<String, u64>,
+    #[serde(default)]
+    files: BTreeMap<String, u64>,
+    #[serde(default)]
+    dirs: BTreeMap<String, u64>,
+    /// Seconds spent in the span.
+    #[serde(default)]
+    pub seconds: u64,
+}
+
+impl UsageStats {
+    pub fn record_command(&mut self, name: &str) {
+        bump(&mut self.commands, name);
+    }
+
+    pub fn record_file(&mut self, path: &str) {
+        bump(&mut self.files, path);
+    }
+
+    pub fn record_dir(&mut self, path: &str) {
+        bump(&mut self.dirs, path);
+    }
+
+    /// Commands counted, over every name.
+    pub fn total_commands(&self) -> u64 {
+        self.commands.values().sum()
+    }
+
+    /// The `n` most used commands, most used first; ties in name order.
+    pub fn top_commands(&self, n: usize) -> Vec<(&str, u64)> {
+        top(&self.commands, n)
+    }
+
+    pub fn top_files(&self, n: usize) -> Vec<(&str, u64)> {
+        top(&self.files, n)
+    }
+
+    pub fn top_dirs(&self, n: usize) -> Vec<(&str, u64)> {
+        top(&self.dirs, n)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty() && self.files.is_empty() && self.dirs.is_empty()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Parse stored counters. Unreadable input starts over from zero, and
+    /// counters past [`MAX_TRACKED_KEYS`] are trimmed.
+    pub fn from_json(json: &str) -> Self {
+        let mut stats: Self = serde_json::from_str(json).unwrap_or_default();
+        for counter in [&mut stats.commands, &mut stats.files, &mut stats.dirs] {
+            while counter.len() > MAX_TRACKED_KEYS {
+                evict_least_used(counter, None);
+            }
+        }
+        stats
+    }
+}
+
+/// Whether `TRACKING` leaves counting on; it is on unless turned off.
+pub fn tracking_enabled(env: &BTreeMap<String, String>) -> bool {
+    !env.get(TRACKING_VAR).is_some_and(|value| {
+        matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "off" | "0" | "false" | "no"
+        )
+    })
+}
+
+/// What `stats` reports on.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UsageReport {
+    /// Every session so far, this one included.
+    pub all_time: UsageStats,
+    pub session: UsageStats,
+}
+
+fn bump(counter: &mut BTreeMap<String, u64>, key: &str) {
+    let key: String = key.chars().take(MAX_KEY_CHARS).collect();
+    let count = counter.entry(key.clone()).or_default();
+    *count = count.saturating_add(1);
+    if counter.len() > MAX_TRACKED_KEYS {
+        evict_least_used(counter, Some(&key));
+    }
+}
+
+/// Drop the least used key, the last in name order among equals, sparing
+/// `keep`.
+fn evict_least_used(counter: &mut BTreeMap<String, u64>, keep: Option<&str>) {
+    let victim = counter
+        .iter()
+        .filter(|(key, _)| Some(key.as_str()) != keep)
+        .min_by(|(a_key, a), (b_key, b)| a.cmp(b).then(b_key.cmp(a_key)))
+        .map(|(key, _)| key.clone());
+    if let Some(victim) = victim {
+        counter.remove(&victim);
+    }
+}
+
+fn top(counter: &BTreeMap<String, u64>, n: usize) -> Vec<(&str, u64)> {
+    let mut entries: Vec<(&str, u64)> = counter
+        .iter()
+        .map(|(key, count)| (key.as_str(), *count))
+        .collect();
+    entries.sort_by(|(a_key, a), (b_key, b)| b.cmp(a).then(a_key.cmp(b_key)));
+    entries.truncate(n);
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats_with_commands(commands: &[(&str, usize)]) -> UsageStats {
+        let mut stats = UsageStats::default();
+        for (name, count) in commands {
+            for _ in 0..*count {
+                stats.record_command(name);
+            }
+        }
+        stats
+    }
+
+    #[test]
+    fn counts_and_ranks_commands() {
+        let stats = stats_with_commands(&[("cd", 2), ("ls", 5), ("cat", 2), ("pwd", 1)]);
+        assert_eq!(stats.total_commands(), 10);
+        assert_eq!(
+            stats.top_commands(3),
+            vec![("ls", 5), ("cat", 2), ("cd", 2)]
+        );
+        assert_eq!(stats.top_commands(10).len(), 4);
+    }
+
+    #[test]
+    fn files_and_directories_count_separately() {
+        let mut stats = UsageStats::default();
+        stats.record_file("/blog/a.md");
+        stats.record_file("/blog/a.md");
+        stats.record_dir("/blog");
+        assert_eq!(stats.top_files(10), vec![("/blog/a.md", 2)]);
+        assert_eq!(stats.top_dirs(10), vec![("/blog", 1)]);
+        assert_eq!(stats.total_commands(), 0);
+        assert!(!stats.is_empty());
+        assert!(UsageStats::default().is_empty());
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut stats = stats_with_commands(&[("ls", 3)]);
+        stats.record_file("/a.md");
+        stats.seconds = 90;
+        assert_eq!(UsageStats::from_json(&stats.to_json()), stats);
+    }
+
+    #[test]
+    fn unreadable_json_starts_from_zero() {
+        assert_eq!(UsageStats::from_json("not json"), UsageStats::default());
+        let partial = UsageStats::from_json(r#"{"commands":{"ls":4}}"#);
+        assert_eq!(partial.top_commands(1), vec![("ls", 4)]);
+        assert_eq!(partial.seconds, 0);
+    }
+
+    #[test]
+    fn cap_drops_the_least_used_key_but_keeps_the_new_one() {
+        let mut stats = UsageStats::default();
+        for index in 0..MAX_TRACKED_KEYS {
+            stats.record_file(&format!("/{index:03}.md"));
+            stats.record_file(&format!("/{index:03}.md"));
+        }
+        stats.record_file("/000.md");
+        stats.record_file("/new.md");
+
+        assert_eq!(stats.top_files(usize::MAX).len(), MAX_TRACKED_KEYS);
+        let files = stats.top_files(usize::MAX);
+        assert!(files.contains(&("/new.md", 1)));
+        assert!(files.contains(&("/000.md", 3)));
+        // Among the keys seen twice, the last in name order went.
+        let dropped = format!("/{:03}.md", MAX_TRACKED_KEYS - 1);
+        assert!(!files.iter().any(|(key, _)| *key == dropped));
+    }
+
+    #[test]
+    fn stored_counters_over_the_cap_are_trimmed() {
+        let commands: BTreeMap<String, u64> = (0..MAX_TRACKED_KEYS + 5)
+            .map(|index| (format!("cmd{index}"), index as u64 + 1))
+            .collect();
+        let json = serde_json::json!({ "commands": commands }).to_string();
+        let stats = UsageStats::from_json(&json);
+        assert_eq!(stats.top_commands(usize::MAX).len(), MAX_TRACKED_KEYS);
+        // The five least used are gone.
+        assert!(
+            !stats
+                .top_commands(usize::MAX)
+                .iter()
+                .any(|(_, count)| *count <= 5)
+        );
+    }
+
+    #[test]
+    fn long_keys_are_cut() {
+        let mut stats = UsageStats::default();
+        stats.record_file(&"a".repeat(1000));
+        assert_eq!(stats.top_files(1)[0].0.len(), MAX_KEY_CHARS);
+    }
+
+    #[test]
+    fn tracking_is_on_unless_turned_off() {
+        let env = |value: &str| BTreeMap::from([(TRACKING_VAR.to_string(), value.to_string())]);
+        assert!(tracking_enabled(&BTreeMap::new()));
+        assert!(tracking_enabled(&env("on")));
+        for off in ["off", "OFF", "0", "false", " no "] {
+            assert!(!tracking_enabled(&env(off)), "{off}");
+        }
+    }
+}
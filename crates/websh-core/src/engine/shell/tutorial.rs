@@ -0,0 +1,315 @@
+//! Guided tour for first-time visitors.
+//!
+//! The tour is a list of [`TutorialStep`]s. [`TutorialProgress`] is the
+//! state machine walking it: the target feeds it every submitted command
+//! line and prints the lines it returns, and persists [`TutorialProgress::step`]
+//! so `tutorial` resumes where the visitor left off.
+
+use super::OutputLine;
+
+/// One stop on the tour.
+#[derive(Clone, Copy, Debug)]
+pub struct TutorialStep {
+    /// What to try, printed when the step starts.
+    pub prompt: &'static str,
+    /// Whether a command line (its whitespace-separated words) completes
+    /// the step.
+    pub accepts: fn(&[&str]) -> bool,
+    /// Printed when the step is completed.
+    pub success: &'static str,
+    /// Printed after two attempts that did not complete the step.
+    pub hint: &'static str,
+}
+
+pub const TUTORIAL_STEPS: &[TutorialStep] = &[
+    TutorialStep {
+        prompt: "Try typing 'ls' to see what's here.",
+        accepts: |words| matches!(words.first(), Some(&"ls")),
+        success: "That's the site's root directory.",
+        hint: "Type ls and press Enter.",
+    },
+    TutorialStep {
+        prompt: "Move into a directory with 'cd <name>'. Tab completes names.",
+        accepts: |words| matches!(words, ["cd", target] if !target.starts_with('-')),
+        success: "The prompt shows where you are now.",
+        hint: "Pick a directory from the ls listing, e.g. cd blog.",
+    },
+    TutorialStep {
+        prompt: "Type 'pwd' to print the current directory, or 'cd ..' to go back up.",
+        accepts: |words| matches!(words, ["pwd", ..] | ["cd", ".."]),
+        success: "'cd' on its own always takes you home.",
+        hint: "Type pwd and press Enter.",
+    },
+    TutorialStep {
+        prompt: "Type 'help' to list every command, or 'help <command>' for one.",
+        accepts: |words| matches!(words.first(), Some(&"help" | &"man")),
+        success: "Every command has a help page.",
+        hint: "Type help and press Enter.",
+    },
+    TutorialStep {
+        prompt: "Open a file in the reader with 'cat <file>'.",
+        accepts: |words| matches!(words, ["cat", _]),
+        success: "Use the archive bar or your browser's back button to return here.",
+        hint: "Pick a file from an ls listing, e.g. cat README.md.",
+    },
+];
+
+/// Failed attempts at a step before its hint is shown.
+const ATTEMPTS_BEFORE_HINT: u8 = 2;
+
+/// `tutorial` subcommands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TutorialAction {
+    /// Start, or resume at the saved step.
+    Start,
+    /// Move past the current step.
+    Skip,
+    /// Start over from the first step.
+    Reset,
+}
+
+/// Where the visitor is in the tour, and whether it is running.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TutorialProgress {
+    step: usize,
+    misses: u8,
+    active: bool,
+}
+
+impl TutorialProgress {
+    /// Saved progress: `step` steps completed, tour not running.
+    pub fn resumed_at(step: usize) -> Self {
+        Self {
+            step: step.min(TUTORIAL_STEPS.len()),
+            ..Self::default()
+        }
+    }
+
+    /// Steps completed so far; the value to persist.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.step >= TUTORIAL_STEPS.len()
+    }
+
+    pub fn apply(&mut self, action: TutorialAction) -> Vec<OutputLine> {
+        match action {
+            TutorialAction::Start if self.is_complete() => vec![OutputLine::info(
+                "tutorial: already complete. Run 'tutorial reset' to take it again.",
+            )],
+            TutorialAction::Start => {
+                self.active = true;
+                self.misses = 0;
+                self.prompt_lines()
+            }
+            TutorialAction::Skip if self.is_complete() => {
+                vec![OutputLine::info("tutorial: nothing left to skip.")]
+            }
+            TutorialAction::Skip => {
+                self.active = true;
+                self.advance();
+                self.prompt_lines()
+            }
+            TutorialAction::Reset => {
+                *self = Self {
+                    active: true,
+                    ..Self::default()
+                };
+                self.prompt_lines()
+            }
+        }
+    }
+
+    /// Feed a submitted command line and its exit status. Only a
+    /// successful command matching the current step completes it; the
+    /// `tutorial` command itself is not an attempt.
+    pub fn observe(&mut self, input: &str, exit_code: i32) -> Vec<OutputLine> {
+        let words = input.split_whitespace().collect::<Vec<_>>();
+        if !self.active || words.is_empty() || words[0] == "tutorial" {
+            return Vec::new();
+        }
+        let Some(step) = TUTORIAL_STEPS.get(self.step) else {
+            return Vec::new();
+        };
+
+        if exit_code == 0 && (step.accepts)(&words) {
+            self.advance();
+            let mut lines = vec![OutputLine::success(step.success)];
+            lines.extend(self.prompt_lines());
+            return lines;
+        }
+
+        self.misses += 1;
+        if self.misses < ATTEMPTS_BEFORE_HINT {
+            return Vec::new();
+        }
+        self.misses = 0;
+        vec![OutputLine::info(format!("tutorial hint: {}", step.hint))]
+    }
+
+    fn advance(&mut self) {
+        self.step = (self.step + 1).min(TUTORIAL_STEPS.len());
+        self.misses = 0;
+        if self.is_complete() {
+            self.active = false;
+        }
+    }
+
+    fn prompt_lines(&self) -> Vec<OutputLine> {
+        match TUTORIAL_STEPS.get(self.step) {
+            Some(step) => vec![OutputLine::info(format!(
+                "[tutorial {}/{}] {}",
+                self.step + 1,
+                TUTORIAL_STEPS.len(),
+                step.prompt
+            ))],
+            None => vec![OutputLine::success(
+                "tutorial: complete! Type 'help' whenever you need a command.",
+            )],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::shell::OutputLineData;
+
+    fn texts(lines: &[OutputLine]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| match &line.data {
+                OutputLineData::Info(text)
+                | OutputLineData::Success(text)
+                | OutputLineData::Text(text) => text.clone(),
+                other => panic!("unexpected line {other:?}"),
+            })
+            .collect()
+    }
+
+    /// Run `script` (command line, exit status) and collect every line.
+    fn run(progress: &mut TutorialProgress, script: &[(&str, i32)]) -> Vec<String> {
+        script
+            .iter()
+            .flat_map(|(input, exit_code)| texts(&progress.observe(input, *exit_code)))
+            .collect()
+    }
+
+    #[test]
+    fn start_prints_the_first_step() {
+        let mut progress = TutorialProgress::default();
+        assert_eq!(
+            texts(&progress.apply(TutorialAction::Start)),
+            ["[tutorial 1/5] Try typing 'ls' to see what's here."]
+        );
+        assert!(progress.is_active());
+    }
+
+    #[test]
+    fn expected_commands_walk_the_whole_tour() {
+        let mut progress = TutorialProgress::default();
+        progress.apply(TutorialAction::Start);
+        let lines = run(
+            &mut progress,
+            &[
+                ("ls -l", 0),
+                ("cd blog", 0),
+                ("cd ..", 0),
+                ("help ls", 0),
+                ("cat README.md", 0),
+            ],
+        );
+
+        assert_eq!(lines.len(), 10);
+        assert_eq!(
+            lines[1],
+            "[tutorial 2/5] Move into a directory with 'cd <name>'. Tab completes names."
+        );
+        assert_eq!(
+            lines.last().unwrap(),
+            "tutorial: complete! Type 'help' whenever you need a command."
+        );
+        assert!(progress.is_complete());
+        assert!(!progress.is_active());
+        assert_eq!(progress.step(), TUTORIAL_STEPS.len());
+    }
+
+    #[test]
+    fn hint_follows_every_second_miss() {
+        let mut progress = TutorialProgress::default();
+        progress.apply(TutorialAction::Start);
+        assert!(run(&mut progress, &[("whoami", 0)]).is_empty());
+        assert_eq!(
+            run(&mut progress, &[("date", 0)]),
+            ["tutorial hint: Type ls and press Enter."]
+        );
+        assert!(run(&mut progress, &[("lss", 127)]).is_empty());
+        assert_eq!(run(&mut progress, &[("id", 0)]).len(), 1);
+        assert_eq!(progress.step(), 0);
+    }
+
+    #[test]
+    fn failed_or_malformed_variants_do_not_advance() {
+        let mut progress = TutorialProgress::resumed_at(1);
+        progress.apply(TutorialAction::Start);
+        run(&mut progress, &[("cd nope", 1), ("cd", 0), ("cd -1", 0)]);
+        assert_eq!(progress.step(), 1);
+        run(&mut progress, &[("cd blog", 0)]);
+        assert_eq!(progress.step(), 2);
+    }
+
+    #[test]
+    fn tutorial_commands_and_blank_lines_are_not_attempts() {
+        let mut progress = TutorialProgress::default();
+        progress.apply(TutorialAction::Start);
+        assert!(
+            run(
+                &mut progress,
+                &[("tutorial", 0), ("  ", 0), ("tutorial skip", 0)]
+            )
+            .is_empty()
+        );
+        assert!(run(&mut progress, &[("pwd", 0)]).is_empty());
+    }
+
+    #[test]
+    fn inactive_tour_ignores_commands() {
+        let mut progress = TutorialProgress::resumed_at(0);
+        assert!(run(&mut progress, &[("ls", 0), ("x", 0), ("y", 0)]).is_empty());
+        assert_eq!(progress.step(), 0);
+    }
+
+    #[test]
+    fn resume_skip_and_reset() {
+        let mut progress = TutorialProgress::resumed_at(3);
+        assert_eq!(
+            texts(&progress.apply(TutorialAction::Start)),
+            ["[tutorial 4/5] Type 'help' to list every command, or 'help <command>' for one."]
+        );
+        assert_eq!(
+            texts(&progress.apply(TutorialAction::Skip)),
+            ["[tutorial 5/5] Open a file in the reader with 'cat <file>'."]
+        );
+        progress.apply(TutorialAction::Skip);
+        assert!(progress.is_complete());
+        assert_eq!(
+            texts(&progress.apply(TutorialAction::Start)),
+            ["tutorial: already complete. Run 'tutorial reset' to take it again."]
+        );
+
+        progress.apply(TutorialAction::Reset);
+        assert_eq!(progress.step(), 0);
+        assert!(progress.is_active());
+    }
+
+    #[test]
+    fn saved_steps_past_the_end_clamp_to_complete() {
+        assert!(TutorialProgress::resumed_at(99).is_complete());
+    }
+}
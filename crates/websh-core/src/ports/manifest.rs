@@ -1,4 +1,9 @@
-use crate::domain::{ContentManifestDocument, ContentManifestEntry, EntryExtensions, NodeKind};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::domain::{
+    ContentManifestDocument, ContentManifestEntry, EntryExtensions, NodeKind, NodeMetadata,
+};
 
 use super::{ScannedDirectory, ScannedFile, ScannedSubtree, StorageError, StorageResult};
 
@@ -28,7 +33,11 @@ pub fn parse_manifest_snapshot(body: &str) -> StorageResult<ScannedSubtree> {
         }
     }
 
-    Ok(ScannedSubtree { files, directories })
+    Ok(ScannedSubtree {
+        files,
+        directories,
+        banner: manifest.banner,
+    })
 }
 
 pub fn serialize_manifest_snapshot(snapshot: &ScannedSubtree) -> StorageResult<String> {
@@ -51,11 +60,408 @@ pub fn serialize_manifest_snapshot(snapshot: &ScannedSubtree) -> StorageResult<S
         });
     }
 
-    let manifest = ContentManifestDocument { entries };
+    let manifest = ContentManifestDocument {
+        banner: snapshot.banner.clone(),
+        entries,
+    };
     serde_json::to_string_pretty(&manifest)
         .map_err(|error| StorageError::BadRequest(error.to_string()))
 }
 
+/// How serious a [`ManifestIssue`] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IssueSeverity {
+    /// The manifest loads, but an entry probably does not behave as intended.
+    Warning,
+    /// The manifest fails to load, or entries are silently dropped.
+    Error,
+}
+
+impl IssueSeverity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// One problem found by [`lint_manifest`] or [`lint_manifest_snapshot`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestIssue {
+    pub severity: IssueSeverity,
+    /// Manifest path the issue is about; `None` for document-level issues.
+    pub path: Option<String>,
+    pub message: String,
+}
+
+impl ManifestIssue {
+    fn new(severity: IssueSeverity, path: Option<&str>, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            path: path.map(display_path).map(str::to_string),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ManifestIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "{}: {path}: {}", self.severity.label(), self.message),
+            None => write!(f, "{}: {}", self.severity.label(), self.message),
+        }
+    }
+}
+
+/// Checks a raw manifest body and reports every problem instead of stopping
+/// at the first one like [`parse_manifest_snapshot`] does.
+pub fn lint_manifest(body: &str) -> Vec<ManifestIssue> {
+    let manifest: ContentManifestDocument = match serde_json::from_str(body) {
+        Ok(manifest) => manifest,
+        Err(error) => {
+            return vec![ManifestIssue::new(
+                IssueSeverity::Error,
+                None,
+                format!("manifest is not valid: {error}"),
+            )];
+        }
+    };
+
+    let mut issues = Vec::new();
+    let mut valid = Vec::with_capacity(manifest.entries.len());
+    for entry in &manifest.entries {
+        let is_dir = matches!(entry.metadata.effective_kind(), NodeKind::Directory);
+        match validate_manifest_path(&entry.path, is_dir) {
+            Ok(()) => valid.push((entry.path.as_str(), is_dir, &entry.metadata)),
+            Err(message) => issues.push(ManifestIssue::new(
+                IssueSeverity::Error,
+                Some(&entry.path),
+                message,
+            )),
+        }
+    }
+    issues.extend(lint_entries(valid));
+    issues
+}
+
+/// Checks an already-parsed snapshot for entries that load but collide or
+/// can never be read.
+pub fn lint_manifest_snapshot(snapshot: &ScannedSubtree) -> Vec<ManifestIssue> {
+    let directories = snapshot
+        .directories
+        .iter()
+        .map(|dir| (dir.path.as_str(), true, &dir.meta));
+    let files = snapshot
+        .files
+        .iter()
+        .map(|file| (file.path.as_str(), false, &file.meta));
+    lint_entries(directories.chain(files))
+}
+
+fn lint_entries<'a>(
+    entries: impl IntoIterator<Item = (&'a str, bool, &'a NodeMetadata)>,
+) -> Vec<ManifestIssue> {
+    let mut issues = Vec::new();
+    let mut seen: BTreeMap<&str, bool> = BTreeMap::new();
+    let mut reported: BTreeSet<&str> = BTreeSet::new();
+
+    for (path, is_dir, meta) in entries {
+        match seen.get(path) {
+            Some(&seen_dir) if seen_dir != is_dir => {
+                if reported.insert(path) {
+                    issues.push(ManifestIssue::new(
+                        IssueSeverity::Error,
+                        Some(path),
+                        "declared as both a file and a directory",
+                    ));
+                }
+            }
+            Some(_) => {
+                if reported.insert(path) {
+                    issues.push(ManifestIssue::new(
+                        IssueSeverity::Error,
+                        Some(path),
+                        "duplicate entry; only one will be mounted",
+                    ));
+                }
+            }
+            None => {
+                seen.insert(path, is_dir);
+            }
+        }
+
+        if meta
+            .access()
+            .is_some_and(|access| access.recipients.is_empty())
+        {
+            issues.push(ManifestIssue::new(
+                IssueSeverity::Warning,
+                Some(path),
+                "access list has no recipients; nobody can read this entry",
+            ));
+        }
+    }
+
+    for &path in seen.keys() {
+        let mut ancestor = path;
+        while let Some((parent, _)) = ancestor.rsplit_once('/') {
+            if seen.get(parent) == Some(&false) {
+                issues.push(ManifestIssue::new(
+                    IssueSeverity::Error,
+                    Some(path),
+                    format!("parent `{parent}` is a file"),
+                ));
+                break;
+            }
+            ancestor = parent;
+        }
+    }
+
+    issues
+}
+
+/// Entries that differ between two manifest snapshots. File paths are
+/// repo-relative and sorted; directories only count toward
+/// `directories_changed`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ManifestDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+    /// A directory was added, removed, or had its metadata changed.
+    pub directories_changed: bool,
+}
+
+impl ManifestDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && !self.directories_changed
+    }
+
+    /// Short description of the change, e.g. `+2 files, 1 modified`.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.added.is_empty() {
+            parts.push(format!("+{} {}", self.added.len(), files(self.added.len())));
+        }
+        if !self.modified.is_empty() {
+            parts.push(format!("{} modified", self.modified.len()));
+        }
+        if !self.removed.is_empty() {
+            parts.push(format!(
+                "-{} {}",
+                self.removed.len(),
+                files(self.removed.len())
+            ));
+        }
+        if parts.is_empty() && self.directories_changed {
+            parts.push("directories changed".to_string());
+        }
+        parts.join(", ")
+    }
+}
+
+fn files(count: usize) -> &'static str {
+    if count == 1 { "file" } else { "files" }
+}
+
+/// Compares two snapshots of the same manifest. A file counts as modified
+/// when its metadata or extensions changed; when a path is listed twice,
+/// the later entry wins, matching how the snapshot is mounted.
+pub fn diff_manifest_snapshots(old: &ScannedSubtree, new: &ScannedSubtree) -> ManifestDiff {
+    let old_files: BTreeMap<&str, &ScannedFile> = old
+        .files
+        .iter()
+        .map(|file| (file.path.as_str(), file))
+        .collect();
+    let new_files: BTreeMap<&str, &ScannedFile> = new
+        .files
+        .iter()
+        .map(|file| (file.path.as_str(), file))
+        .collect();
+
+    let mut diff = ManifestDiff::default();
+    for (&path, file) in &new_files {
+        match old_files.get(path) {
+            None => diff.added.push(path.to_string()),
+            Some(previous) if previous != file => diff.modified.push(path.to_string()),
+            Some(_) => {}
+        }
+    }
+    diff.removed = old_files
+        .keys()
+        .filter(|path| !new_files.contains_key(*path))
+        .map(|path| path.to_string())
+        .collect();
+
+    let directories = |snapshot: &'_ ScannedSubtree| -> BTreeMap<String, NodeMetadata> {
+        snapshot
+            .directories
+            .iter()
+            .map(|dir| (dir.path.clone(), dir.meta.clone()))
+            .collect()
+    };
+    diff.directories_changed = directories(old) != directories(new);
+    diff
+}
+
+/// How one listed entry differs between two manifest snapshots.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntryChange {
+    Added,
+    Removed,
+    /// A file whose size, modification time or title changed, or a
+    /// directory with such a change anywhere below it.
+    Modified,
+}
+
+impl EntryChange {
+    /// Marker `ls --diff` prints before the entry.
+    pub fn marker(self) -> char {
+        match self {
+            Self::Added => '+',
+            Self::Removed => '-',
+            Self::Modified => '~',
+        }
+    }
+}
+
+/// One changed child of a directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DirectoryChange {
+    pub name: String,
+    pub is_dir: bool,
+    pub change: EntryChange,
+}
+
+/// Changes among the direct children of one directory, sorted by name.
+/// A rename shows up as one removal and one addition.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DirectoryDiff {
+    pub changes: Vec<DirectoryChange>,
+}
+
+impl DirectoryDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Change to the child called `name`, if any.
+    pub fn change_of(&self, name: &str) -> Option<EntryChange> {
+        self.changes
+            .iter()
+            .find(|change| change.name == name)
+            .map(|change| change.change)
+    }
+
+    /// Children that exist only in the older snapshot.
+    pub fn removed(&self) -> impl Iterator<Item = &DirectoryChange> {
+        self.changes
+            .iter()
+            .filter(|change| change.change == EntryChange::Removed)
+    }
+
+    /// Short description, e.g. `2 added, 1 modified, 1 removed`.
+    pub fn summary(&self) -> String {
+        let count = |kind| self.changes.iter().filter(|c| c.change == kind).count();
+        [
+            (count(EntryChange::Added), "added"),
+            (count(EntryChange::Modified), "modified"),
+            (count(EntryChange::Removed), "removed"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{count} {label}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+/// What a listed child looks like in one snapshot: whether it is a
+/// directory, and the title, size and modification time of it and of
+/// everything below it, keyed by path relative to the child.
+#[derive(PartialEq)]
+struct ChildState<'a> {
+    is_dir: bool,
+    entries: BTreeMap<&'a str, EntryStamp<'a>>,
+}
+
+/// Title, size and modification time of one entry.
+type EntryStamp<'a> = (Option<&'a str>, Option<u64>, Option<u64>);
+
+fn children<'a>(snapshot: &'a ScannedSubtree, dir: &str) -> BTreeMap<&'a str, ChildState<'a>> {
+    let mut children: BTreeMap<&str, ChildState> = BTreeMap::new();
+    let files = snapshot
+        .files
+        .iter()
+        .map(|file| (&file.path, &file.meta, false));
+    let directories = snapshot
+        .directories
+        .iter()
+        .map(|directory| (&directory.path, &directory.meta, true));
+    for (path, meta, is_dir) in files.chain(directories) {
+        let relative = if dir.is_empty() {
+            Some(path.as_str())
+        } else {
+            path.strip_prefix(dir)
+                .and_then(|rest| rest.strip_prefix('/'))
+        };
+        let Some(relative) = relative.filter(|relative| !relative.is_empty()) else {
+            continue;
+        };
+        let (name, below) = relative.split_once('/').unwrap_or((relative, ""));
+        let child = children.entry(name).or_insert_with(|| ChildState {
+            is_dir: false,
+            entries: BTreeMap::new(),
+        });
+        child.is_dir |= is_dir || !below.is_empty();
+        child
+            .entries
+            .insert(below, (meta.title(), meta.size_bytes(), meta.modified_at()));
+    }
+    children
+}
+
+/// Compares the direct children of `dir` (repo-relative, `""` for the
+/// root) between two snapshots of the same manifest.
+pub fn diff_directory(old: &ScannedSubtree, new: &ScannedSubtree, dir: &str) -> DirectoryDiff {
+    let dir = dir.trim_matches('/');
+    let old_children = children(old, dir);
+    let new_children = children(new, dir);
+
+    let mut changes = Vec::new();
+    for (&name, child) in &new_children {
+        let change = match old_children.get(name) {
+            None => EntryChange::Added,
+            Some(previous) if previous != child => EntryChange::Modified,
+            Some(_) => continue,
+        };
+        changes.push(DirectoryChange {
+            name: name.to_string(),
+            is_dir: child.is_dir,
+            change,
+        });
+    }
+    for (&name, child) in &old_children {
+        if !new_children.contains_key(name) {
+            changes.push(DirectoryChange {
+                name: name.to_string(),
+                is_dir: child.is_dir,
+                change: EntryChange::Removed,
+            });
+        }
+    }
+    changes.sort_by(|a, b| a.name.cmp(&b.name));
+    DirectoryDiff { changes }
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() { "." } else { path }
+}
+
 fn validate_manifest_path(path: &str, allow_empty: bool) -> Result<(), String> {
     if path.is_empty() {
         return if allow_empty {
@@ -90,6 +496,239 @@ mod tests {
 
     use super::*;
 
+    fn file(path: &str, title: &str) -> ScannedFile {
+        ScannedFile {
+            path: path.to_string(),
+            meta: NodeMetadata {
+                schema: SCHEMA_VERSION,
+                kind: NodeKind::Page,
+                authored: Fields {
+                    title: Some(title.to_string()),
+                    ..Fields::default()
+                },
+                derived: Fields::default(),
+            },
+            extensions: EntryExtensions::default(),
+        }
+    }
+
+    fn dir(path: &str) -> ScannedDirectory {
+        ScannedDirectory {
+            path: path.to_string(),
+            meta: NodeMetadata {
+                schema: SCHEMA_VERSION,
+                kind: NodeKind::Directory,
+                authored: Fields::default(),
+                derived: Fields::default(),
+            },
+        }
+    }
+
+    fn snapshot(files: Vec<ScannedFile>, directories: Vec<ScannedDirectory>) -> ScannedSubtree {
+        ScannedSubtree {
+            files,
+            directories,
+            banner: None,
+        }
+    }
+
+    #[test]
+    fn diff_of_identical_snapshots_is_empty() {
+        let tree = snapshot(vec![file("a.md", "A")], vec![dir("blog")]);
+        let diff = diff_manifest_snapshots(&tree, &tree.clone());
+        assert!(diff.is_empty());
+        assert_eq!(diff, ManifestDiff::default());
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_modified_files_sorted() {
+        let old = snapshot(
+            vec![file("b.md", "B"), file("a.md", "A"), file("gone.md", "G")],
+            vec![],
+        );
+        let new = snapshot(
+            vec![
+                file("a.md", "A"),
+                file("z.md", "Z"),
+                file("b.md", "B, revised"),
+                file("c.md", "C"),
+            ],
+            vec![],
+        );
+        let diff = diff_manifest_snapshots(&old, &new);
+        assert_eq!(diff.added, vec!["c.md", "z.md"]);
+        assert_eq!(diff.removed, vec!["gone.md"]);
+        assert_eq!(diff.modified, vec!["b.md"]);
+        assert!(!diff.directories_changed);
+        assert_eq!(diff.summary(), "+2 files, 1 modified, -1 file");
+    }
+
+    #[test]
+    fn diff_counts_extension_changes_as_modified() {
+        let old = snapshot(vec![file("a.md", "A")], vec![]);
+        let mut changed = file("a.md", "A");
+        changed.extensions.mempool = Some(crate::domain::MempoolFields {
+            status: crate::domain::MempoolStatus::Draft,
+            priority: None,
+            category: None,
+        });
+        let new = snapshot(vec![changed], vec![]);
+        assert_eq!(diff_manifest_snapshots(&old, &new).modified, vec!["a.md"]);
+    }
+
+    #[test]
+    fn diff_ignores_file_order_and_banner() {
+        let old = snapshot(vec![file("a.md", "A"), file("b.md", "B")], vec![]);
+        let mut new = snapshot(vec![file("b.md", "B"), file("a.md", "A")], vec![]);
+        new.banner = Some("hello".to_string());
+        assert!(diff_manifest_snapshots(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn diff_uses_the_last_duplicate_entry() {
+        let old = snapshot(vec![file("a.md", "A")], vec![]);
+        let new = snapshot(vec![file("a.md", "A"), file("a.md", "A2")], vec![]);
+        assert_eq!(diff_manifest_snapshots(&old, &new).modified, vec!["a.md"]);
+        assert!(diff_manifest_snapshots(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn diff_flags_directory_changes_without_listing_them() {
+        let old = snapshot(vec![], vec![dir("blog")]);
+        let new = snapshot(vec![], vec![dir("blog"), dir("notes")]);
+        let diff = diff_manifest_snapshots(&old, &new);
+        assert!(diff.directories_changed);
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert!(!diff.is_empty());
+        assert_eq!(diff.summary(), "directories changed");
+        assert!(diff_manifest_snapshots(&new, &old).directories_changed);
+    }
+
+    #[test]
+    fn diff_from_empty_snapshot_adds_everything() {
+        let new = snapshot(vec![file("a.md", "A")], vec![dir("")]);
+        let diff = diff_manifest_snapshots(&ScannedSubtree::default(), &new);
+        assert_eq!(diff.added, vec!["a.md"]);
+        assert!(diff.directories_changed);
+        assert_eq!(diff.summary(), "+1 file");
+
+        let diff = diff_manifest_snapshots(&new, &ScannedSubtree::default());
+        assert_eq!(diff.removed, vec!["a.md"]);
+        assert_eq!(diff.summary(), "-1 file");
+    }
+
+    fn sized(path: &str, title: &str, size: u64) -> ScannedFile {
+        let mut file = file(path, title);
+        file.meta.derived.size_bytes = Some(size);
+        file
+    }
+
+    fn changes(diff: &DirectoryDiff) -> Vec<(&str, char)> {
+        diff.changes
+            .iter()
+            .map(|change| (change.name.as_str(), change.change.marker()))
+            .collect()
+    }
+
+    #[test]
+    fn directory_diff_lists_added_and_removed_children() {
+        let old = snapshot(
+            vec![file("blog/a.md", "A"), file("blog/old.md", "Old")],
+            vec![dir("blog")],
+        );
+        let new = snapshot(
+            vec![file("blog/a.md", "A"), file("blog/new.md", "New")],
+            vec![dir("blog")],
+        );
+        let diff = diff_directory(&old, &new, "blog");
+        assert_eq!(changes(&diff), [("new.md", '+'), ("old.md", '-')]);
+        assert_eq!(diff.summary(), "1 added, 1 removed");
+        assert_eq!(diff.change_of("a.md"), None);
+        assert_eq!(
+            diff.removed().map(|c| c.name.as_str()).collect::<Vec<_>>(),
+            ["old.md"]
+        );
+    }
+
+    #[test]
+    fn directory_diff_shows_a_rename_as_add_and_remove() {
+        let old = snapshot(vec![file("notes/draft.md", "Post")], vec![]);
+        let new = snapshot(vec![file("notes/final.md", "Post")], vec![]);
+        let diff = diff_directory(&old, &new, "/notes/");
+        assert_eq!(changes(&diff), [("draft.md", '-'), ("final.md", '+')]);
+    }
+
+    #[test]
+    fn directory_diff_marks_metadata_only_changes() {
+        let old = snapshot(
+            vec![
+                sized("a.md", "A", 10),
+                sized("b.md", "B", 10),
+                sized("c.md", "C", 10),
+            ],
+            vec![],
+        );
+        let mut touched = sized("c.md", "C", 10);
+        touched.meta.derived.modified_at = Some(1_700_000_000);
+        let new = snapshot(
+            vec![sized("a.md", "A", 12), sized("b.md", "B2", 10), touched],
+            vec![],
+        );
+        let diff = diff_directory(&old, &new, "");
+        assert_eq!(
+            changes(&diff),
+            [("a.md", '~'), ("b.md", '~'), ("c.md", '~')]
+        );
+        assert_eq!(diff.summary(), "3 modified");
+    }
+
+    #[test]
+    fn directory_diff_ignores_other_metadata() {
+        let old = snapshot(vec![file("a.md", "A")], vec![]);
+        let mut tagged = file("a.md", "A");
+        tagged.meta.authored.tags = Some(vec!["rust".to_string()]);
+        let new = snapshot(vec![tagged], vec![]);
+        assert!(diff_directory(&old, &new, "").is_empty());
+    }
+
+    #[test]
+    fn directory_diff_rolls_changes_up_to_subdirectories() {
+        let old = snapshot(
+            vec![file("blog/2023/a.md", "A"), file("papers/p.pdf", "P")],
+            vec![dir("blog"), dir("papers")],
+        );
+        let new = snapshot(
+            vec![
+                file("blog/2023/a.md", "A"),
+                file("blog/2024/b.md", "B"),
+                file("papers/p.pdf", "P"),
+                file("talks/t.md", "T"),
+            ],
+            vec![dir("blog"), dir("papers")],
+        );
+        let diff = diff_directory(&old, &new, "");
+        assert_eq!(changes(&diff), [("blog", '~'), ("talks", '+')]);
+        assert!(diff.changes.iter().all(|change| change.is_dir));
+
+        let diff = diff_directory(&old, &new, "blog");
+        assert_eq!(changes(&diff), [("2024", '+')]);
+        assert!(diff_directory(&old, &new, "papers").is_empty());
+    }
+
+    #[test]
+    fn directory_diff_is_scoped_to_the_path_prefix() {
+        let old = snapshot(vec![file("blog-old/a.md", "A")], vec![]);
+        let new = snapshot(vec![file("blog/a.md", "A")], vec![]);
+        assert_eq!(
+            changes(&diff_directory(&old, &new, "blog")),
+            [("a.md", '+')]
+        );
+        assert_eq!(
+            changes(&diff_directory(&old, &new, "")),
+            [("blog", '+'), ("blog-old", '-')]
+        );
+    }
+
     #[test]
     fn round_trips_manifest_document() {
         let snapshot = ScannedSubtree {
@@ -125,6 +764,7 @@ mod tests {
                     derived: Fields::default(),
                 },
             }],
+            banner: Some("gm from the archive".to_string()),
         };
 
         let encoded = serialize_manifest_snapshot(&snapshot).expect("serialize");
@@ -143,4 +783,110 @@ mod tests {
         let err = parse_manifest_snapshot(manifest).unwrap_err();
         assert!(matches!(err, StorageError::ValidationFailed(_)));
     }
+
+    fn lint_messages(body: &str) -> Vec<String> {
+        lint_manifest(body)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn lint_reports_unparseable_manifest() {
+        let issues = lint_manifest("{\"entries\": 3}");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, IssueSeverity::Error);
+        assert_eq!(issues[0].path, None);
+    }
+
+    #[test]
+    fn lint_reports_every_bad_path() {
+        let messages = lint_messages(
+            r#"{"entries": [
+                {"path":"/abs.md","metadata":{"kind":"page"}},
+                {"path":"ok.md","metadata":{"kind":"page"}},
+                {"path":"a/../b.md","metadata":{"kind":"page"}}
+            ]}"#,
+        );
+        assert_eq!(
+            messages,
+            vec![
+                "error: /abs.md: path must be repo-relative: /abs.md",
+                "error: a/../b.md: path contains traversal segment: a/../b.md",
+            ]
+        );
+    }
+
+    #[test]
+    fn lint_reports_duplicate_paths_once() {
+        let messages = lint_messages(
+            r#"{"entries": [
+                {"path":"a.md","metadata":{"kind":"page"}},
+                {"path":"a.md","metadata":{"kind":"page"}},
+                {"path":"a.md","metadata":{"kind":"page"}}
+            ]}"#,
+        );
+        assert_eq!(
+            messages,
+            vec!["error: a.md: duplicate entry; only one will be mounted"]
+        );
+    }
+
+    #[test]
+    fn lint_reports_file_directory_collisions() {
+        let messages = lint_messages(
+            r#"{"entries": [
+                {"path":"notes","metadata":{"kind":"directory"}},
+                {"path":"notes","metadata":{"kind":"page"}},
+                {"path":"blog.md","metadata":{"kind":"page"}},
+                {"path":"blog.md/post.md","metadata":{"kind":"page"}}
+            ]}"#,
+        );
+        assert_eq!(
+            messages,
+            vec![
+                "error: notes: declared as both a file and a directory",
+                "error: blog.md/post.md: parent `blog.md` is a file",
+            ]
+        );
+    }
+
+    #[test]
+    fn lint_warns_about_access_without_recipients() {
+        let issues = lint_manifest(
+            r#"{"entries": [
+                {"path":"secret.md","metadata":{"kind":"page","authored":{"access":{"recipients":[]}}}},
+                {"path":"shared.md","metadata":{"kind":"page","authored":{"access":{"recipients":[{"address":"0x1"}]}}}}
+            ]}"#,
+        );
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, IssueSeverity::Warning);
+        assert_eq!(issues[0].path.as_deref(), Some("secret.md"));
+    }
+
+    #[test]
+    fn lint_accepts_clean_manifest() {
+        let body = r#"{"entries": [
+            {"path":"","metadata":{"kind":"directory"}},
+            {"path":"blog","metadata":{"kind":"directory"}},
+            {"path":"blog/post.md","metadata":{"kind":"page"}}
+        ]}"#;
+        assert!(lint_manifest(body).is_empty());
+        let snapshot = parse_manifest_snapshot(body).expect("parse");
+        assert!(lint_manifest_snapshot(&snapshot).is_empty());
+    }
+
+    #[test]
+    fn snapshot_lint_catches_duplicates_that_parse_accepts() {
+        let snapshot = parse_manifest_snapshot(
+            r#"{"entries": [
+                {"path":"a.md","metadata":{"kind":"page"}},
+                {"path":"a.md","metadata":{"kind":"page"}}
+            ]}"#,
+        )
+        .expect("parse");
+        let issues = lint_manifest_snapshot(&snapshot);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path.as_deref(), Some("a.md"));
+    }
 }
@@ -6,7 +6,11 @@ mod storage;
 #[cfg(any(test, feature = "mock"))]
 mod mock;
 
-pub use manifest::{parse_manifest_snapshot, serialize_manifest_snapshot};
+pub use manifest::{
+    DirectoryChange, DirectoryDiff, EntryChange, IssueSeverity, ManifestDiff, ManifestIssue,
+    diff_directory, diff_manifest_snapshots, lint_manifest, lint_manifest_snapshot,
+    parse_manifest_snapshot, serialize_manifest_snapshot,
+};
 pub use storage::{
     CommitBase, CommitDelta, CommitFileAddition, CommitOutcome, CommitRequest, LocalBoxFuture,
     ScannedDirectory, ScannedFile, ScannedSubtree, StorageBackend, StorageBackendRef, StorageError,
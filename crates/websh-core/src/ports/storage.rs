@@ -63,6 +63,8 @@ pub type StorageBackendRef = Rc<dyn StorageBackend>;
 pub struct ScannedSubtree {
     pub files: Vec<ScannedFile>,
     pub directories: Vec<ScannedDirectory>,
+    /// Manifest-level boot banner; carried so commits don't drop it.
+    pub banner: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
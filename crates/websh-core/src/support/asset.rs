@@ -12,6 +12,11 @@ pub fn media_type_for_path(path: &str) -> &'static str {
             "gif" => "image/gif",
             "webp" => "image/webp",
             "svg" => "image/svg+xml",
+            "mp3" => "audio/mpeg",
+            "ogg" => "audio/ogg",
+            "wav" => "audio/wav",
+            "mp4" => "video/mp4",
+            "webm" => "video/webm",
             "pdf" => "application/pdf",
             "md" => "text/markdown; charset=utf-8",
             "txt" | "link" => "text/plain; charset=utf-8",
@@ -31,6 +36,8 @@ mod tests {
         assert_eq!(media_type_for_path("doc.pdf"), "application/pdf");
         assert_eq!(media_type_for_path("photo.JPG"), "image/jpeg");
         assert_eq!(media_type_for_path("icon.svg"), "image/svg+xml");
+        assert_eq!(media_type_for_path("talk.mp3"), "audio/mpeg");
+        assert_eq!(media_type_for_path("demo.webm"), "video/webm");
     }
 
     #[test]
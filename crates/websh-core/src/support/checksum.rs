@@ -0,0 +1,146 @@
+//! SHA-256 digests and `sha256sum`-style reports.
+//!
+//! Hashing is pure; reading the bytes is left to the caller. Recorded
+//! checksums come from the manifest's `content_sha256`, which carries a
+//! `0x` prefix.
+
+use sha2::{Digest, Sha256};
+
+/// Lowercase hex SHA-256 of `bytes`, without a prefix.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// A recorded checksum in the form [`sha256_hex`] produces: `0x` dropped,
+/// surrounding whitespace trimmed, lowercased.
+pub fn normalize_sha256(recorded: &str) -> String {
+    let trimmed = recorded.trim();
+    trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed)
+        .to_ascii_lowercase()
+}
+
+/// One `sha256sum` output line: digest, two spaces, name.
+pub fn sha256sum_line(digest: &str, name: &str) -> String {
+    format!("{digest}  {name}")
+}
+
+/// Result of verifying one file against its recorded checksum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumOutcome {
+    Match,
+    Mismatch,
+    /// The bytes could not be read.
+    Unreadable,
+}
+
+impl ChecksumOutcome {
+    /// Compare the digest of `bytes` with `expected` (any form
+    /// [`normalize_sha256`] accepts).
+    pub fn of(bytes: &[u8], expected: &str) -> Self {
+        if sha256_hex(bytes) == normalize_sha256(expected) {
+            Self::Match
+        } else {
+            Self::Mismatch
+        }
+    }
+
+    pub fn is_match(self) -> bool {
+        self == Self::Match
+    }
+}
+
+/// Per-file line of a `--check` report, as `sha256sum -c` prints it.
+pub fn check_line(name: &str, outcome: ChecksumOutcome) -> String {
+    match outcome {
+        ChecksumOutcome::Match => format!("{name}: OK"),
+        ChecksumOutcome::Mismatch => format!("{name}: FAILED"),
+        ChecksumOutcome::Unreadable => format!("{name}: FAILED open or read"),
+    }
+}
+
+/// Closing line of a `--check` report, e.g. `3 OK, 1 FAILED, 1 unreadable`.
+/// Zero counts other than OK are left out.
+pub fn check_summary(outcomes: &[ChecksumOutcome]) -> String {
+    let count = |wanted| {
+        outcomes
+            .iter()
+            .filter(|outcome| **outcome == wanted)
+            .count()
+    };
+    let mut summary = format!("{} OK", count(ChecksumOutcome::Match));
+    let failed = count(ChecksumOutcome::Mismatch);
+    if failed > 0 {
+        summary.push_str(&format!(", {failed} FAILED"));
+    }
+    let unreadable = count(ChecksumOutcome::Unreadable);
+    if unreadable > 0 {
+        summary.push_str(&format!(", {unreadable} unreadable"));
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    #[test]
+    fn sha256_hex_matches_known_vectors() {
+        assert_eq!(sha256_hex(b"abc"), ABC_SHA256);
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn recorded_checksums_normalize_to_bare_lowercase_hex() {
+        assert_eq!(normalize_sha256("0xABCdef"), "abcdef");
+        assert_eq!(normalize_sha256(" 0Xab\n"), "ab");
+        assert_eq!(normalize_sha256("abc"), "abc");
+    }
+
+    #[test]
+    fn outcome_compares_against_prefixed_and_bare_hashes() {
+        assert_eq!(
+            ChecksumOutcome::of(b"abc", &format!("0x{}", ABC_SHA256.to_uppercase())),
+            ChecksumOutcome::Match
+        );
+        assert_eq!(
+            ChecksumOutcome::of(b"abd", ABC_SHA256),
+            ChecksumOutcome::Mismatch
+        );
+    }
+
+    #[test]
+    fn report_lines_follow_sha256sum() {
+        assert_eq!(
+            sha256sum_line(ABC_SHA256, "blog/a.md"),
+            format!("{ABC_SHA256}  blog/a.md")
+        );
+        assert_eq!(check_line("a.md", ChecksumOutcome::Match), "a.md: OK");
+        assert_eq!(
+            check_line("a.md", ChecksumOutcome::Mismatch),
+            "a.md: FAILED"
+        );
+        assert_eq!(
+            check_line("a.md", ChecksumOutcome::Unreadable),
+            "a.md: FAILED open or read"
+        );
+    }
+
+    #[test]
+    fn summary_counts_each_outcome() {
+        use ChecksumOutcome::*;
+        assert_eq!(check_summary(&[Match, Match]), "2 OK");
+        assert_eq!(
+            check_summary(&[Match, Mismatch, Unreadable, Mismatch]),
+            "1 OK, 2 FAILED, 1 unreadable"
+        );
+        assert_eq!(check_summary(&[]), "0 OK");
+    }
+}
@@ -0,0 +1,186 @@
+//! Atom feed rendering.
+//!
+//! Entries carry hash-route paths; [`AtomFeed::to_xml`] joins them onto the
+//! page URL the target serves the app from.
+
+use super::format::format_strftime;
+
+/// Entries kept in a feed, newest first.
+pub const FEED_ENTRY_LIMIT: usize = 20;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeedEntry {
+    pub title: String,
+    /// Hash route of the entry, e.g. `/blog/hello`.
+    pub route: String,
+    /// RFC 3339 timestamp in UTC.
+    pub updated: String,
+    pub summary: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AtomFeed {
+    pub title: String,
+    /// Hash route of the directory the feed covers.
+    pub route: String,
+    /// Newest first, at most [`FEED_ENTRY_LIMIT`].
+    pub entries: Vec<FeedEntry>,
+}
+
+impl AtomFeed {
+    /// Orders `entries` newest first (ties by title) and keeps the newest
+    /// [`FEED_ENTRY_LIMIT`].
+    pub fn new(
+        title: impl Into<String>,
+        route: impl Into<String>,
+        entries: Vec<FeedEntry>,
+    ) -> Self {
+        let mut entries = entries;
+        entries.sort_by(|a, b| {
+            b.updated
+                .cmp(&a.updated)
+                .then_with(|| a.title.cmp(&b.title))
+        });
+        entries.truncate(FEED_ENTRY_LIMIT);
+        Self {
+            title: title.into(),
+            route: route.into(),
+            entries,
+        }
+    }
+
+    /// Render the feed as an Atom document. `base_url` is the page serving
+    /// the app; any fragment on it is dropped before routes are appended.
+    pub fn to_xml(&self, base_url: &str, author: &str) -> String {
+        let base = base_url.split('#').next().unwrap_or_default();
+        let href = |route: &str| escape_xml(&format!("{base}#{route}"));
+        let updated = self
+            .entries
+            .first()
+            .map_or_else(|| rfc3339(0), |entry| entry.updated.clone());
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+        xml.push_str(&format!("  <title>{}</title>\n", escape_xml(&self.title)));
+        xml.push_str(&format!("  <id>{}</id>\n", href(&self.route)));
+        xml.push_str(&format!("  <link href=\"{}\"/>\n", href(&self.route)));
+        xml.push_str(&format!("  <updated>{}</updated>\n", escape_xml(&updated)));
+        xml.push_str(&format!(
+            "  <author><name>{}</name></author>\n",
+            escape_xml(author)
+        ));
+        for entry in &self.entries {
+            xml.push_str("  <entry>\n");
+            xml.push_str(&format!(
+                "    <title>{}</title>\n",
+                escape_xml(&entry.title)
+            ));
+            xml.push_str(&format!("    <id>{}</id>\n", href(&entry.route)));
+            xml.push_str(&format!("    <link href=\"{}\"/>\n", href(&entry.route)));
+            xml.push_str(&format!(
+                "    <updated>{}</updated>\n",
+                escape_xml(&entry.updated)
+            ));
+            if let Some(summary) = &entry.summary {
+                xml.push_str(&format!("    <summary>{}</summary>\n", escape_xml(summary)));
+            }
+            xml.push_str("  </entry>\n");
+        }
+        xml.push_str("</feed>\n");
+        xml
+    }
+}
+
+/// Format a Unix timestamp (seconds) as an RFC 3339 UTC timestamp.
+pub fn rfc3339(timestamp: u64) -> String {
+    format_strftime(timestamp, 0, "%Y-%m-%dT%H:%M:%SZ")
+}
+
+/// Escape text for XML element content and double-quoted attributes.
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(title: &str, route: &str, updated: &str) -> FeedEntry {
+        FeedEntry {
+            title: title.to_string(),
+            route: route.to_string(),
+            updated: updated.to_string(),
+            summary: None,
+        }
+    }
+
+    #[test]
+    fn rfc3339_formats_utc_seconds() {
+        assert_eq!(rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339(1_726_099_200 + 3_723), "2024-09-12T01:02:03Z");
+    }
+
+    #[test]
+    fn escape_xml_covers_markup_characters() {
+        assert_eq!(
+            escape_xml(r#"Tom & Jerry <3 "quotes" 'too'"#),
+            "Tom &amp; Jerry &lt;3 &quot;quotes&quot; &apos;too&apos;"
+        );
+    }
+
+    #[test]
+    fn entries_are_ordered_newest_first_and_capped() {
+        let mut entries: Vec<_> = (0..25)
+            .map(|day| {
+                entry(
+                    &format!("post {day:02}"),
+                    &format!("/blog/{day}"),
+                    &rfc3339(day * 86_400),
+                )
+            })
+            .collect();
+        entries.push(entry("b tie", "/blog/b", &rfc3339(24 * 86_400)));
+        entries.push(entry("a tie", "/blog/a", &rfc3339(24 * 86_400)));
+
+        let feed = AtomFeed::new("blog", "/blog", entries);
+        let titles: Vec<_> = feed.entries.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles.len(), FEED_ENTRY_LIMIT);
+        assert_eq!(titles[..4], ["a tie", "b tie", "post 24", "post 23"]);
+    }
+
+    #[test]
+    fn xml_escapes_titles_and_builds_absolute_hash_urls() {
+        let mut post = entry("Cats & <Dogs>", "/blog/cats", "2024-09-12T00:00:00Z");
+        post.summary = Some("a < b".to_string());
+        let feed = AtomFeed::new("R&D notes", "/blog", vec![post]);
+        let xml = feed.to_xml("https://example.com/app/#/websh/blog", "Q&A");
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n"));
+        assert!(xml.contains("<title>R&amp;D notes</title>"));
+        assert!(xml.contains("<id>https://example.com/app/#/blog</id>"));
+        assert!(xml.contains("<updated>2024-09-12T00:00:00Z</updated>\n  <author>"));
+        assert!(xml.contains("<author><name>Q&amp;A</name></author>"));
+        assert!(xml.contains("<title>Cats &amp; &lt;Dogs&gt;</title>"));
+        assert!(xml.contains("<link href=\"https://example.com/app/#/blog/cats\"/>"));
+        assert!(xml.contains("<summary>a &lt; b</summary>"));
+        assert!(xml.ends_with("  </entry>\n</feed>\n"));
+    }
+
+    #[test]
+    fn empty_feed_still_has_an_updated_stamp() {
+        let xml = AtomFeed::new("blog", "/blog", Vec::new()).to_xml("https://x.dev/", "me");
+        assert!(xml.contains("<updated>1970-01-01T00:00:00Z</updated>"));
+        assert!(!xml.contains("<entry>"));
+    }
+}
@@ -34,31 +34,175 @@ pub fn format_size(size: Option<u64>, right_align: bool) -> String {
     }
 }
 
-/// Format Unix timestamp for terminal display (e.g., "Jan  5 12:34").
+const MONTH_ABBREVIATIONS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Seconds in half a Gregorian year, the cutoff `ls` uses between showing
+/// a time of day and a year.
+const SIX_MONTHS_SECS: u64 = 15_778_476;
+
+/// Format Unix timestamp `ls -l` style, relative to `now`.
 ///
-/// Uses approximate month/day calculation for simplicity.
-pub fn format_date_short(timestamp: Option<u64>) -> String {
-    match timestamp {
-        None => "            ".to_string(),
-        Some(ts) => {
-            let months = [
-                "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
-            ];
-            // Approximate: days since epoch
-            let days = ts / 86400;
-            let month = ((days % 365) / 30) as usize % 12;
-            let day = ((days % 365) % 30) + 1;
-            let hour = (ts % 86400) / 3600;
-            let min = (ts % 3600) / 60;
-            format!("{} {:2} {:02}:{:02}", months[month], day, hour, min)
+/// Timestamps from the last six months show the time of day
+/// (`"Mar 12 14:03"`); older or future ones show the year instead
+/// (`"Mar 12  2023"`). `None` renders as blanks of the same width.
+pub fn format_date_ls(timestamp: Option<u64>, now: u64) -> String {
+    let Some(ts) = timestamp else {
+        return " ".repeat(12);
+    };
+    let (year, month, day) = civil_date(ts);
+    let month = MONTH_ABBREVIATIONS[month as usize - 1];
+    let recent = ts <= now && now - ts < SIX_MONTHS_SECS;
+    if recent {
+        let hour = (ts % 86400) / 3600;
+        let min = (ts % 3600) / 60;
+        format!("{} {:2} {:02}:{:02}", month, day, hour, min)
+    } else {
+        format!("{} {:2} {:>5}", month, day, year)
+    }
+}
+
+/// How far from `now` a timestamp may be and still read as relative time.
+const RELATIVE_WINDOW_SECS: u64 = 7 * 86400;
+
+/// Column width of [`format_date_listing`]: room for "59 minutes ago".
+pub const LISTING_DATE_WIDTH: usize = 14;
+
+/// Format the distance from `timestamp` to `now` in words: "just now",
+/// "5 minutes ago", "yesterday", "3 days ago" (or "in 2 hours",
+/// "tomorrow" for future times). A week or more away falls back to the
+/// absolute ISO date.
+pub fn format_relative_time(timestamp: u64, now: u64) -> String {
+    const UNITS: [(u64, &str); 3] = [(86400, "day"), (3600, "hour"), (60, "minute")];
+
+    let delta = timestamp.abs_diff(now);
+    let future = timestamp > now;
+    if delta >= RELATIVE_WINDOW_SECS {
+        return format_date_iso(timestamp);
+    }
+    let Some((count, unit)) = UNITS
+        .iter()
+        .find(|(secs, _)| delta >= *secs)
+        .map(|(secs, unit)| (delta / secs, *unit))
+    else {
+        return "just now".to_string();
+    };
+    match (unit, count, future) {
+        ("day", 1, false) => "yesterday".to_string(),
+        ("day", 1, true) => "tomorrow".to_string(),
+        _ => {
+            let plural = if count == 1 { "" } else { "s" };
+            if future {
+                format!("in {} {}{}", count, unit, plural)
+            } else {
+                format!("{} {}{} ago", count, unit, plural)
+            }
         }
     }
 }
 
+/// Date column for `ls -l`: relative time within a week of `now`, the
+/// `ls`-style absolute date otherwise, padded to [`LISTING_DATE_WIDTH`].
+pub fn format_date_listing(timestamp: Option<u64>, now: u64) -> String {
+    let date = match timestamp {
+        Some(ts) if ts.abs_diff(now) < RELATIVE_WINDOW_SECS => format_relative_time(ts, now),
+        _ => format_date_ls(timestamp, now),
+    };
+    format!("{:<width$}", date, width = LISTING_DATE_WIDTH)
+}
+
 /// Format Unix timestamp as ISO date (YYYY-MM-DD).
 ///
 /// Properly calculates year/month/day accounting for leap years.
 pub fn format_date_iso(timestamp: u64) -> String {
+    let (year, month, day) = civil_date(timestamp);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Weekday names starting from Sunday.
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// `date` output when no format is given: `Sat, 17 Oct 2026 14:03:05 +0900`.
+pub const DEFAULT_DATE_FORMAT: &str = "%a, %d %b %Y %H:%M:%S %z";
+
+/// Format a Unix timestamp with a strftime-like `format`, shifted to local
+/// time by `utc_offset_minutes` (minutes east of UTC).
+///
+/// Supported specifiers: `%Y %y %m %d %e %H %M %S %A %a %B %b %z %s %%`.
+/// Anything else is copied through unchanged, like GNU `date`.
+pub fn format_strftime(timestamp: u64, utc_offset_minutes: i32, format: &str) -> String {
+    let local = (timestamp as i64 + i64::from(utc_offset_minutes) * 60).max(0) as u64;
+    let (year, month, day) = civil_date(local);
+    let weekday = WEEKDAY_NAMES[((local / 86400 + 4) % 7) as usize];
+    let month_name = MONTH_NAMES[month as usize - 1];
+    let seconds_of_day = local % 86400;
+
+    let mut out = String::with_capacity(format.len() + 16);
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        let Some(spec) = chars.next() else {
+            out.push('%');
+            break;
+        };
+        match spec {
+            'Y' => out.push_str(&year.to_string()),
+            'y' => out.push_str(&format!("{:02}", year % 100)),
+            'm' => out.push_str(&format!("{month:02}")),
+            'd' => out.push_str(&format!("{day:02}")),
+            'e' => out.push_str(&format!("{day:2}")),
+            'H' => out.push_str(&format!("{:02}", seconds_of_day / 3600)),
+            'M' => out.push_str(&format!("{:02}", seconds_of_day % 3600 / 60)),
+            'S' => out.push_str(&format!("{:02}", seconds_of_day % 60)),
+            'A' => out.push_str(weekday),
+            'a' => out.push_str(&weekday[..3]),
+            'B' => out.push_str(month_name),
+            'b' => out.push_str(&month_name[..3]),
+            'z' => {
+                let sign = if utc_offset_minutes < 0 { '-' } else { '+' };
+                let offset = utc_offset_minutes.unsigned_abs();
+                out.push_str(&format!("{sign}{:02}{:02}", offset / 60, offset % 60));
+            }
+            's' => out.push_str(&timestamp.to_string()),
+            '%' => out.push('%'),
+            other => {
+                out.push('%');
+                out.push(other);
+            }
+        }
+    }
+    out
+}
+
+/// Split a Unix timestamp into a UTC `(year, month, day)`, months from 1.
+pub(crate) fn civil_date(timestamp: u64) -> (i64, i64, i64) {
     let days = timestamp / 86400;
     let mut year = 1970i64;
     let mut remaining_days = days as i64;
@@ -87,8 +231,7 @@ pub fn format_date_iso(timestamp: u64) -> String {
         month += 1;
     }
 
-    let day = remaining_days + 1;
-    format!("{:04}-{:02}-{:02}", year, month, day)
+    (year, month, remaining_days + 1)
 }
 
 /// Check if a year is a leap year.
@@ -175,6 +318,17 @@ pub fn reading_time_minutes(words: u32) -> u32 {
     ((words + READING_WPM / 2) / READING_WPM).max(1)
 }
 
+/// Whitespace-separated words in a markdown document, front matter
+/// excluded. Feeds [`reading_time_minutes`] when the manifest carries no
+/// `word_count`.
+pub fn markdown_word_count(source: &str) -> u32 {
+    let body = match super::frontmatter::split_front_matter(source) {
+        Ok(Some((_, body))) => body,
+        _ => source,
+    };
+    u32::try_from(body.split_whitespace().count()).unwrap_or(u32::MAX)
+}
+
 /// If `value` begins with a 10-character `YYYY-MM-DD` prefix, return that
 /// prefix as a borrowed slice. Otherwise return `None`. Used as a low-cost
 /// sortable key for content dates.
@@ -214,6 +368,110 @@ mod tests {
         assert_eq!(format_date_iso(1704067200), "2024-01-01");
     }
 
+    #[test]
+    fn test_format_date_ls_recent_shows_time() {
+        // 2024-03-12 14:03:00 UTC, viewed a day later.
+        let ts = 1_710_252_180;
+        assert_eq!(format_date_ls(Some(ts), ts + 86400), "Mar 12 14:03");
+    }
+
+    #[test]
+    fn test_format_date_ls_old_shows_year() {
+        // 2023-03-12 00:00:00 UTC, viewed a year later.
+        let ts = 1_678_579_200;
+        assert_eq!(format_date_ls(Some(ts), ts + 365 * 86400), "Mar 12  2023");
+    }
+
+    #[test]
+    fn test_format_date_ls_recency_spans_the_year_boundary() {
+        // 2023-12-30 09:05 UTC seen on 2024-01-02 is still recent.
+        let ts = 1_703_927_100;
+        let new_year = 1_704_153_600;
+        assert_eq!(format_date_ls(Some(ts), new_year), "Dec 30 09:05");
+        // Same calendar year, but more than six months ago.
+        let january = 1_704_067_200;
+        assert_eq!(
+            format_date_ls(Some(january), january + 200 * 86400),
+            "Jan  1  2024"
+        );
+    }
+
+    #[test]
+    fn test_format_date_ls_future_and_missing() {
+        let ts = 1_710_252_180;
+        assert_eq!(format_date_ls(Some(ts), ts - 60), "Mar 12  2024");
+        assert_eq!(format_date_ls(None, ts), " ".repeat(12));
+        assert_eq!(
+            format_date_ls(None, ts).len(),
+            format_date_ls(Some(ts), ts).len()
+        );
+    }
+
+    #[test]
+    fn test_format_relative_time_thresholds() {
+        let now = 1_710_252_180;
+        let cases = [
+            (0, "just now"),
+            (59, "just now"),
+            (60, "1 minute ago"),
+            (5 * 60, "5 minutes ago"),
+            (3599, "59 minutes ago"),
+            (3600, "1 hour ago"),
+            (23 * 3600, "23 hours ago"),
+            (86400, "yesterday"),
+            (2 * 86400 - 1, "yesterday"),
+            (2 * 86400, "2 days ago"),
+            (7 * 86400 - 1, "6 days ago"),
+        ];
+        for (ago, expected) in cases {
+            assert_eq!(format_relative_time(now - ago, now), expected, "{ago}s ago");
+        }
+    }
+
+    #[test]
+    fn test_format_relative_time_falls_back_to_absolute() {
+        let now = 1_710_252_180;
+        assert_eq!(
+            format_relative_time(now - 7 * 86400, now),
+            format_date_iso(now - 7 * 86400)
+        );
+        assert_eq!(format_relative_time(1_704_067_200, now), "2024-01-01");
+    }
+
+    #[test]
+    fn test_format_relative_time_future() {
+        let now = 1_710_252_180;
+        assert_eq!(format_relative_time(now + 30, now), "just now");
+        assert_eq!(format_relative_time(now + 2 * 3600, now), "in 2 hours");
+        assert_eq!(format_relative_time(now + 86400, now), "tomorrow");
+        assert_eq!(format_relative_time(now + 3 * 86400, now), "in 3 days");
+        assert_eq!(
+            format_relative_time(now + 30 * 86400, now),
+            format_date_iso(now + 30 * 86400)
+        );
+    }
+
+    #[test]
+    fn test_format_date_listing_switches_to_absolute_after_a_week() {
+        let now = 1_710_252_180;
+        assert_eq!(
+            format_date_listing(Some(now - 3 * 86400), now),
+            "3 days ago    "
+        );
+        assert_eq!(
+            format_date_listing(Some(now - 10 * 86400), now),
+            format!("{:<14}", format_date_ls(Some(now - 10 * 86400), now))
+        );
+        assert_eq!(
+            format_date_listing(None, now),
+            " ".repeat(LISTING_DATE_WIDTH)
+        );
+        assert_eq!(
+            format_date_listing(Some(now - 3599), now).len(),
+            LISTING_DATE_WIDTH
+        );
+    }
+
     #[test]
     fn test_format_eth_address() {
         let addr = "0x1234567890abcdef1234567890abcdef12345678";
@@ -277,6 +535,53 @@ mod tests {
         assert_eq!(reading_time_minutes(2_140), 9);
         assert_eq!(reading_time_minutes(2_300), 10);
     }
+
+    // 2026-10-17 05:03:09 UTC, a Saturday.
+    const OCT_17_2026: u64 = 1_792_213_389;
+
+    #[test]
+    fn format_strftime_substitutes_specifiers() {
+        assert_eq!(
+            format_strftime(OCT_17_2026, 0, "%Y-%m-%d %H:%M:%S"),
+            "2026-10-17 05:03:09"
+        );
+        assert_eq!(
+            format_strftime(OCT_17_2026, 0, "%A %a %B %b %y %e"),
+            "Saturday Sat October Oct 26 17"
+        );
+        assert_eq!(format_strftime(OCT_17_2026, 0, "%s"), "1792213389");
+    }
+
+    #[test]
+    fn format_strftime_applies_utc_offset() {
+        assert_eq!(
+            format_strftime(OCT_17_2026, 9 * 60, DEFAULT_DATE_FORMAT),
+            "Sat, 17 Oct 2026 14:03:09 +0900"
+        );
+        assert_eq!(
+            format_strftime(OCT_17_2026, -(7 * 60), "%d %H:%M %z"),
+            "16 22:03 -0700"
+        );
+        assert_eq!(format_strftime(OCT_17_2026, 330, "%z"), "+0530");
+    }
+
+    #[test]
+    fn format_strftime_keeps_unknown_specifiers() {
+        assert_eq!(format_strftime(0, 0, "100%% %Q %"), "100% %Q %");
+        assert_eq!(format_strftime(0, 0, "%A %Y"), "Thursday 1970");
+    }
+
+    #[test]
+    fn markdown_word_count_skips_front_matter() {
+        assert_eq!(markdown_word_count(""), 0);
+        assert_eq!(markdown_word_count("# Title\n\none two  three\n"), 5);
+        assert_eq!(
+            markdown_word_count("---\ntitle: Long title here\n---\nbody words"),
+            2
+        );
+        // A malformed block is counted as prose, as the reader renders it.
+        assert_eq!(markdown_word_count("---\ntitle: x\n"), 3);
+    }
 }
 
 #[cfg(test)]
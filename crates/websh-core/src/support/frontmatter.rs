@@ -0,0 +1,228 @@
+//! Leading front matter in markdown documents.
+//!
+//! Only the flat subset authors write by hand is understood: `key: value`
+//! scalars, inline `[a, b]` lists, and `- item` block lists. Keys other
+//! than the ones [`FrontMatter`] carries are skipped along with anything
+//! indented beneath them. A block that does not fit this shape is reported
+//! as malformed so callers can render it as ordinary content.
+
+use thiserror::Error;
+
+/// Keys read from a document's front matter block.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FrontMatter {
+    pub title: Option<String>,
+    pub date: Option<String>,
+    pub tags: Vec<String>,
+    pub draft: bool,
+}
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum FrontMatterError {
+    #[error("front matter is never closed with `---`")]
+    Unterminated,
+    #[error("front matter line {line} is not `key: value`: {text}")]
+    InvalidLine { line: usize, text: String },
+    #[error("front matter `draft` must be true or false, got `{0}`")]
+    InvalidDraft(String),
+}
+
+/// Split a leading `---` front matter block from `source`.
+///
+/// Returns `Ok(None)` when the document does not open with a fence, and
+/// otherwise the parsed keys with the body that follows the closing fence.
+pub fn split_front_matter(source: &str) -> Result<Option<(FrontMatter, &str)>, FrontMatterError> {
+    let mut lines = source.split_inclusive('\n');
+    if lines.next().map(str::trim_end) != Some("---") {
+        return Ok(None);
+    }
+
+    let mut offset = source.find('\n').map_or(source.len(), |index| index + 1);
+    let mut block = Vec::new();
+    for line in lines {
+        offset += line.len();
+        let text = line.trim_end();
+        if text == "---" || text == "..." {
+            let front_matter = parse_block(&block)?;
+            return Ok(Some((front_matter, &source[offset..])));
+        }
+        block.push(text);
+    }
+    Err(FrontMatterError::Unterminated)
+}
+
+fn parse_block(lines: &[&str]) -> Result<FrontMatter, FrontMatterError> {
+    let mut front_matter = FrontMatter::default();
+    let mut current_key: Option<&str> = None;
+
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let invalid = || FrontMatterError::InvalidLine {
+            line: index + 2,
+            text: trimmed.to_string(),
+        };
+
+        let indented = line.starts_with([' ', '\t']);
+        if let Some(item) = trimmed.strip_prefix('-') {
+            match current_key {
+                Some("tags") => push_tag(&mut front_matter.tags, item),
+                Some(_) => {}
+                None => return Err(invalid()),
+            }
+            continue;
+        }
+        if indented {
+            if current_key.is_none() {
+                return Err(invalid());
+            }
+            continue;
+        }
+
+        let (key, value) = trimmed.split_once(':').ok_or_else(invalid)?;
+        let key = key.trim();
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(invalid());
+        }
+        let value = unquote(value.trim());
+        current_key = Some(key);
+        match key {
+            "title" => front_matter.title = Some(value.to_string()).filter(|v| !v.is_empty()),
+            "date" => front_matter.date = Some(value.to_string()).filter(|v| !v.is_empty()),
+            "tags" => front_matter.tags = parse_inline_tags(value),
+            "draft" => front_matter.draft = parse_bool(value)?,
+            _ => {}
+        }
+    }
+    Ok(front_matter)
+}
+
+fn parse_inline_tags(value: &str) -> Vec<String> {
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|inner| inner.strip_suffix(']'))
+        .unwrap_or(value);
+    let mut tags = Vec::new();
+    for tag in inner.split(',') {
+        push_tag(&mut tags, tag);
+    }
+    tags
+}
+
+fn push_tag(tags: &mut Vec<String>, raw: &str) {
+    let tag = unquote(raw.trim());
+    if !tag.is_empty() {
+        tags.push(tag.to_string());
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool, FrontMatterError> {
+    match value.to_ascii_lowercase().as_str() {
+        "" | "false" | "no" | "off" => Ok(false),
+        "true" | "yes" | "on" => Ok(true),
+        _ => Err(FrontMatterError::InvalidDraft(value.to_string())),
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|inner| inner.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_opening_fence_means_no_front_matter() {
+        assert_eq!(split_front_matter("# Title\n\nbody\n"), Ok(None));
+        assert_eq!(split_front_matter(""), Ok(None));
+        assert_eq!(split_front_matter("text\n---\ntitle: x\n---\n"), Ok(None));
+    }
+
+    #[test]
+    fn parses_common_keys_and_returns_the_body() {
+        let source = "---\ntitle: \"Hello, world\"\ndate: 2026-04-26\ntags: [math, 'notes']\ndraft: true\n---\n\n# Body\n";
+        let (front_matter, body) = split_front_matter(source).unwrap().unwrap();
+        assert_eq!(
+            front_matter,
+            FrontMatter {
+                title: Some("Hello, world".to_string()),
+                date: Some("2026-04-26".to_string()),
+                tags: vec!["math".to_string(), "notes".to_string()],
+                draft: true,
+            }
+        );
+        assert_eq!(body, "\n# Body\n");
+    }
+
+    #[test]
+    fn partial_block_leaves_missing_keys_unset() {
+        let (front_matter, body) = split_front_matter("---\ntitle: Only\n---\nbody")
+            .unwrap()
+            .unwrap();
+        assert_eq!(front_matter.title.as_deref(), Some("Only"));
+        assert_eq!(front_matter.date, None);
+        assert!(front_matter.tags.is_empty());
+        assert!(!front_matter.draft);
+        assert_eq!(body, "body");
+    }
+
+    #[test]
+    fn reads_block_lists_and_skips_unknown_nested_keys() {
+        let source = "---\r\ntags:\r\n  - rust\r\n  - \"wasm\"\r\nauthor:\r\n  name: someone\r\n  links:\r\n    - a\r\ntitle: After\r\n---\r\nbody";
+        let (front_matter, body) = split_front_matter(source).unwrap().unwrap();
+        assert_eq!(front_matter.tags, vec!["rust", "wasm"]);
+        assert_eq!(front_matter.title.as_deref(), Some("After"));
+        assert_eq!(body, "body");
+    }
+
+    #[test]
+    fn empty_block_parses_to_defaults() {
+        let (front_matter, body) = split_front_matter("---\n---\nbody").unwrap().unwrap();
+        assert_eq!(front_matter, FrontMatter::default());
+        assert_eq!(body, "body");
+    }
+
+    #[test]
+    fn unterminated_block_is_malformed() {
+        assert_eq!(
+            split_front_matter("---\ntitle: x\n\nbody\n"),
+            Err(FrontMatterError::Unterminated)
+        );
+    }
+
+    #[test]
+    fn line_without_a_key_is_malformed() {
+        assert_eq!(
+            split_front_matter("---\ntitle: x\njust prose\n---\n"),
+            Err(FrontMatterError::InvalidLine {
+                line: 3,
+                text: "just prose".to_string(),
+            })
+        );
+        assert!(split_front_matter("---\n  indented: first\n---\n").is_err());
+        assert!(split_front_matter("---\n- orphan\n---\n").is_err());
+    }
+
+    #[test]
+    fn non_boolean_draft_is_malformed() {
+        assert_eq!(
+            split_front_matter("---\ndraft: maybe\n---\n"),
+            Err(FrontMatterError::InvalidDraft("maybe".to_string()))
+        );
+    }
+}
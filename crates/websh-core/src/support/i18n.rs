@@ -0,0 +1,504 @@
+//! Locales and the message catalog for user-facing text.
+//!
+//! The shell and the browser app look text up by [`Message`] key instead
+//! of writing English literals, so switching `LANG` changes command
+//! errors, help framing, boot lines and component labels together.
+//! Templates name their arguments in braces (`{path}`); every locale of a
+//! key takes the same arguments.
+//!
+//! Text that comes from content or from a command's own usage message is
+//! not translated.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Environment variable that selects the locale.
+pub const LANG_VAR: &str = "LANG";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    En,
+    Ko,
+}
+
+impl Locale {
+    pub const ALL: [Self; 2] = [Self::En, Self::Ko];
+
+    /// Language code, as `LANG` and `lang` take it.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Ko => "ko",
+        }
+    }
+
+    /// The language's own name for itself.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::En => "English",
+            Self::Ko => "한국어",
+        }
+    }
+
+    /// Locale of a BCP 47 tag or POSIX locale name (`ko`, `ko-KR`,
+    /// `ko_KR.UTF-8`), matched on the language alone. `None` when the
+    /// language is not supported.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let language = tag.trim().split(['-', '_', '.']).next()?;
+        Self::ALL
+            .into_iter()
+            .find(|locale| locale.as_str().eq_ignore_ascii_case(language))
+    }
+
+    /// The locale `LANG` selects, English when it is unset or unsupported.
+    pub fn from_env(env: &BTreeMap<String, String>) -> Self {
+        env.get(LANG_VAR)
+            .and_then(|tag| Self::parse(tag))
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A catalog key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Message {
+    // Command errors
+    NotFound,
+    LsNotFound,
+    CdNotFound,
+    NotADirectory,
+    LsNotADirectory,
+    CdNotADirectory,
+    IsADirectory,
+    CatIsADirectory,
+    InvalidPath,
+    PermissionDenied,
+    ReadOnly,
+    MissingOperand,
+    UnknownFilter,
+    CommandNotFound,
+    CommandNotFoundSuggestion,
+
+    // Help
+    HelpUsage,
+    HelpAliases,
+    HelpFlags,
+    HelpExamples,
+    HelpAvailableCommands,
+    HelpNoTopic,
+    HelpDidYouMean,
+    HelpSectionNavigation,
+    HelpSectionInformation,
+    HelpSectionSystem,
+    HelpSectionEnvironment,
+    HelpSectionWallet,
+    HelpSectionPipeFilters,
+    HelpSectionWrite,
+    HelpSectionSync,
+    ManMissingTopic,
+    ManNoEntry,
+
+    // `lang`
+    LangCurrent,
+    LangAvailable,
+    LangSet,
+    LangUnknown,
+
+    // Boot
+    BootInitializing,
+    BootComplete,
+    BootTips,
+    BootTipHelp,
+    BootTipArchiveBar,
+    BootTipTutorial,
+
+    // Terminal entry menu
+    MenuOpen,
+    MenuOpenInReader,
+    MenuPreview,
+    MenuCopyPath,
+    MenuCopyUrl,
+    MenuDownloadFolder,
+
+    // Status bar
+    StatusSession,
+    StatusNetwork,
+    StatusOffline,
+    WalletConnect,
+    WalletConnecting,
+    WalletDisconnect,
+    WalletAddress,
+    WalletNoChain,
+
+    // Reader toolbar
+    ReaderMode,
+    ReaderRendered,
+    ReaderEdit,
+    ReaderCancel,
+    ReaderSave,
+    ReaderPage,
+    ReaderPrint,
+    ReaderSaving,
+    ReaderUnsaved,
+    ReaderSynced,
+}
+
+impl Message {
+    pub const ALL: &'static [Self] = &[
+        Self::NotFound,
+        Self::LsNotFound,
+        Self::CdNotFound,
+        Self::NotADirectory,
+        Self::LsNotADirectory,
+        Self::CdNotADirectory,
+        Self::IsADirectory,
+        Self::CatIsADirectory,
+        Self::InvalidPath,
+        Self::PermissionDenied,
+        Self::ReadOnly,
+        Self::MissingOperand,
+        Self::UnknownFilter,
+        Self::CommandNotFound,
+        Self::CommandNotFoundSuggestion,
+        Self::HelpUsage,
+        Self::HelpAliases,
+        Self::HelpFlags,
+        Self::HelpExamples,
+        Self::HelpAvailableCommands,
+        Self::HelpNoTopic,
+        Self::HelpDidYouMean,
+        Self::HelpSectionNavigation,
+        Self::HelpSectionInformation,
+        Self::HelpSectionSystem,
+        Self::HelpSectionEnvironment,
+        Self::HelpSectionWallet,
+        Self::HelpSectionPipeFilters,
+        Self::HelpSectionWrite,
+        Self::HelpSectionSync,
+        Self::ManMissingTopic,
+        Self::ManNoEntry,
+        Self::LangCurrent,
+        Self::LangAvailable,
+        Self::LangSet,
+        Self::LangUnknown,
+        Self::BootInitializing,
+        Self::BootComplete,
+        Self::BootTips,
+        Self::BootTipHelp,
+        Self::BootTipArchiveBar,
+        Self::BootTipTutorial,
+        Self::MenuOpen,
+        Self::MenuOpenInReader,
+        Self::MenuPreview,
+        Self::MenuCopyPath,
+        Self::MenuCopyUrl,
+        Self::MenuDownloadFolder,
+        Self::StatusSession,
+        Self::StatusNetwork,
+        Self::StatusOffline,
+        Self::WalletConnect,
+        Self::WalletConnecting,
+        Self::WalletDisconnect,
+        Self::WalletAddress,
+        Self::WalletNoChain,
+        Self::ReaderMode,
+        Self::ReaderRendered,
+        Self::ReaderEdit,
+        Self::ReaderCancel,
+        Self::ReaderSave,
+        Self::ReaderPage,
+        Self::ReaderPrint,
+        Self::ReaderSaving,
+        Self::ReaderUnsaved,
+        Self::ReaderSynced,
+    ];
+
+    /// The key's template in `locale`, placeholders unfilled.
+    pub fn template(self, locale: Locale) -> &'static str {
+        let (en, ko) = self.templates();
+        match locale {
+            Locale::En => en,
+            Locale::Ko => ko,
+        }
+    }
+
+    /// The key's text in `locale`, for templates without arguments.
+    pub fn text(self, locale: Locale) -> &'static str {
+        self.template(locale)
+    }
+
+    /// The template in `locale` with each `{name}` replaced by its value
+    /// from `args`. Placeholders without a value are left as written.
+    pub fn format(self, locale: Locale, args: &[(&str, &str)]) -> String {
+        let mut out = String::new();
+        let mut rest = self.template(locale);
+        while let Some(open) = rest.find('{') {
+            out.push_str(&rest[..open]);
+            rest = &rest[open..];
+            let value = rest.find('}').and_then(|close| {
+                let name = &rest[1..close];
+                let (_, value) = args.iter().find(|(arg, _)| *arg == name)?;
+                Some((value, close + 1))
+            });
+            match value {
+                Some((value, len)) => {
+                    out.push_str(value);
+                    rest = &rest[len..];
+                }
+                None => {
+                    out.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+
+    fn templates(self) -> (&'static str, &'static str) {
+        match self {
+            Self::NotFound => (
+                "{cmd}: {path}: No such file or directory",
+                "{cmd}: {path}: 그런 파일이나 디렉터리가 없습니다",
+            ),
+            Self::LsNotFound => (
+                "ls: cannot access '{path}': No such file or directory",
+                "ls: '{path}'에 접근할 수 없습니다: 그런 파일이나 디렉터리가 없습니다",
+            ),
+            Self::CdNotFound => (
+                "cd: no such file or directory: {path}",
+                "cd: 그런 파일이나 디렉터리가 없습니다: {path}",
+            ),
+            Self::NotADirectory => (
+                "{cmd}: {path}: not a directory",
+                "{cmd}: {path}: 디렉터리가 아닙니다",
+            ),
+            Self::LsNotADirectory => (
+                "ls: cannot access '{path}': Not a directory",
+                "ls: '{path}'에 접근할 수 없습니다: 디렉터리가 아닙니다",
+            ),
+            Self::CdNotADirectory => (
+                "cd: not a directory: {path}",
+                "cd: 디렉터리가 아닙니다: {path}",
+            ),
+            Self::IsADirectory => (
+                "{cmd}: {path}: is a directory",
+                "{cmd}: {path}: 디렉터리입니다",
+            ),
+            Self::CatIsADirectory => ("cat: {path}: Is a directory", "cat: {path}: 디렉터리입니다"),
+            Self::InvalidPath => (
+                "{cmd}: invalid path '{path}'",
+                "{cmd}: 잘못된 경로 '{path}'",
+            ),
+            Self::PermissionDenied => (
+                "{cmd}: permission denied (admin login required)",
+                "{cmd}: 권한이 없습니다 (관리자 로그인 필요)",
+            ),
+            Self::ReadOnly => (
+                "{cmd}: read-only filesystem",
+                "{cmd}: 읽기 전용 파일 시스템",
+            ),
+            Self::MissingOperand => ("{cmd}: missing {operand}", "{cmd}: {operand} 누락"),
+            Self::UnknownFilter => (
+                "Pipe: unknown filter '{name}'. Supported: grep, head, tail, tee, wc, xargs",
+                "파이프: 알 수 없는 필터 '{name}'. 지원: grep, head, tail, tee, wc, xargs",
+            ),
+            Self::CommandNotFound => (
+                "Command not found: {name}. Type 'help' for available commands.",
+                "명령을 찾을 수 없습니다: {name}. 사용할 수 있는 명령은 'help'로 확인하세요.",
+            ),
+            Self::CommandNotFoundSuggestion => (
+                "Command not found: {name}. Did you mean '{suggestion}'?",
+                "명령을 찾을 수 없습니다: {name}. '{suggestion}'을(를) 찾으셨나요?",
+            ),
+            Self::HelpUsage => ("Usage: {synopsis}", "사용법: {synopsis}"),
+            Self::HelpAliases => ("Aliases: {aliases}", "별칭: {aliases}"),
+            Self::HelpFlags => ("Flags:", "플래그:"),
+            Self::HelpExamples => ("Examples:", "예시:"),
+            Self::HelpAvailableCommands => ("Available commands:", "사용할 수 있는 명령:"),
+            Self::HelpNoTopic => (
+                "help: no help topic for '{topic}'",
+                "help: '{topic}'에 대한 도움말이 없습니다",
+            ),
+            Self::HelpDidYouMean => (
+                ". Did you mean '{suggestion}'?",
+                ". '{suggestion}'을(를) 찾으셨나요?",
+            ),
+            Self::HelpSectionNavigation => ("Navigation", "탐색"),
+            Self::HelpSectionInformation => ("Information", "정보"),
+            Self::HelpSectionSystem => ("System", "시스템"),
+            Self::HelpSectionEnvironment => ("Environment", "환경"),
+            Self::HelpSectionWallet => ("Wallet", "지갑"),
+            Self::HelpSectionPipeFilters => ("Pipe Filters", "파이프 필터"),
+            Self::HelpSectionWrite => (
+                "Filesystem (write, admin-only)",
+                "파일 시스템 (쓰기, 관리자 전용)",
+            ),
+            Self::HelpSectionSync => ("Sync", "동기화"),
+            Self::ManMissingTopic => (
+                "What manual page do you want? Try 'man ls'.",
+                "어떤 매뉴얼 페이지를 원하시나요? 'man ls'를 입력해 보세요.",
+            ),
+            Self::ManNoEntry => (
+                "No manual entry for {topic}",
+                "{topic}에 대한 매뉴얼이 없습니다",
+            ),
+            Self::LangCurrent => ("Language: {name} ({code})", "언어: {name} ({code})"),
+            Self::LangAvailable => ("Available: {locales}", "사용 가능: {locales}"),
+            Self::LangSet => ("Language set to {name}", "언어를 {name}(으)로 바꿨습니다"),
+            Self::LangUnknown => (
+                "unknown language '{tag}' (expected {locales})",
+                "알 수 없는 언어 '{tag}' (가능한 값: {locales})",
+            ),
+            Self::BootInitializing => ("Initializing Terminal mode", "터미널 모드를 준비하는 중"),
+            Self::BootComplete => (
+                "Boot complete. Welcome to {app}",
+                "부팅 완료. {app}에 오신 것을 환영합니다",
+            ),
+            Self::BootTips => ("Tips:", "도움말:"),
+            Self::BootTipHelp => (
+                "  - Type 'help' for available commands",
+                "  - 사용할 수 있는 명령은 'help'로 확인하세요",
+            ),
+            Self::BootTipArchiveBar => (
+                "  - Use the archive bar to jump between home, ledger, and websh",
+                "  - 아카이브 바로 home, ledger, websh 사이를 오갈 수 있습니다",
+            ),
+            Self::BootTipTutorial => (
+                "  - New here? Type 'tutorial' for a short guided tour",
+                "  - 처음이신가요? 'tutorial'을 입력하면 짧은 안내가 시작됩니다",
+            ),
+            Self::MenuOpen => ("Open", "열기"),
+            Self::MenuOpenInReader => ("Open in Reader", "리더에서 열기"),
+            Self::MenuPreview => ("Preview", "미리 보기"),
+            Self::MenuCopyPath => ("Copy path", "경로 복사"),
+            Self::MenuCopyUrl => ("Copy URL", "URL 복사"),
+            Self::MenuDownloadFolder => ("Download folder", "폴더 내려받기"),
+            Self::StatusSession => ("session", "세션"),
+            Self::StatusNetwork => ("network", "네트워크"),
+            Self::StatusOffline => ("offline", "오프라인"),
+            Self::WalletConnect => ("connect wallet", "지갑 연결"),
+            Self::WalletConnecting => ("connecting…", "연결 중…"),
+            Self::WalletDisconnect => ("disconnect", "연결 해제"),
+            Self::WalletAddress => ("address", "주소"),
+            Self::WalletNoChain => ("no chain", "체인 없음"),
+            Self::ReaderMode => ("mode", "모드"),
+            Self::ReaderRendered => ("rendered", "보기"),
+            Self::ReaderEdit => ("edit", "편집"),
+            Self::ReaderCancel => ("cancel", "취소"),
+            Self::ReaderSave => ("save", "저장"),
+            Self::ReaderPage => ("page", "페이지"),
+            Self::ReaderPrint => ("print / save as pdf", "인쇄 / PDF로 저장"),
+            Self::ReaderSaving => ("saving…", "저장 중…"),
+            Self::ReaderUnsaved => ("unsaved", "저장 안 됨"),
+            Self::ReaderSynced => ("synced", "동기화됨"),
+        }
+    }
+}
+
+/// `{name}` placeholders of a template, in order.
+#[cfg(test)]
+fn placeholders(template: &str) -> Vec<&str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(lang: Option<&str>) -> BTreeMap<String, String> {
+        lang.map(|lang| (LANG_VAR.to_string(), lang.to_string()))
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn every_key_has_text_in_every_locale() {
+        for &message in Message::ALL {
+            for locale in Locale::ALL {
+                assert!(
+                    !message.template(locale).trim().is_empty(),
+                    "{message:?} is empty in {locale}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_locale_takes_the_same_arguments() {
+        for &message in Message::ALL {
+            let mut expected = placeholders(message.template(Locale::En));
+            expected.sort_unstable();
+            for locale in Locale::ALL {
+                let mut found = placeholders(message.template(locale));
+                found.sort_unstable();
+                assert_eq!(found, expected, "{message:?} in {locale}");
+            }
+        }
+    }
+
+    #[test]
+    fn all_lists_every_key_once() {
+        for (index, message) in Message::ALL.iter().enumerate() {
+            assert!(
+                !Message::ALL[..index].contains(message),
+                "{message:?} is listed twice"
+            );
+        }
+        // The last variant of the enum closes the list.
+        assert_eq!(Message::ALL.last(), Some(&Message::ReaderSynced));
+    }
+
+    #[test]
+    fn format_fills_named_placeholders() {
+        assert_eq!(
+            Message::NotFound.format(Locale::En, &[("cmd", "cat"), ("path", "a.md")]),
+            "cat: a.md: No such file or directory"
+        );
+        assert_eq!(
+            Message::NotFound.format(Locale::Ko, &[("cmd", "cat"), ("path", "a.md")]),
+            "cat: a.md: 그런 파일이나 디렉터리가 없습니다"
+        );
+        assert_eq!(
+            Message::HelpUsage.format(Locale::En, &[]),
+            "Usage: {synopsis}"
+        );
+        // Values are not expanded again.
+        assert_eq!(
+            Message::InvalidPath.format(Locale::En, &[("cmd", "{path}"), ("path", "x")]),
+            "{path}: invalid path 'x'"
+        );
+    }
+
+    #[test]
+    fn locales_parse_from_tags_and_posix_names() {
+        for (tag, expected) in [
+            ("ko", Some(Locale::Ko)),
+            ("ko-KR", Some(Locale::Ko)),
+            ("ko_KR.UTF-8", Some(Locale::Ko)),
+            ("EN-us", Some(Locale::En)),
+            ("en", Some(Locale::En)),
+            ("fr-FR", None),
+            ("", None),
+        ] {
+            assert_eq!(Locale::parse(tag), expected, "{tag:?}");
+        }
+    }
+
+    #[test]
+    fn env_selects_the_locale_with_english_as_fallback() {
+        assert_eq!(Locale::from_env(&env(Some("ko"))), Locale::Ko);
+        assert_eq!(Locale::from_env(&env(Some("de"))), Locale::En);
+        assert_eq!(Locale::from_env(&env(None)), Locale::En);
+    }
+}
@@ -4,6 +4,13 @@
 //! toolchain can compile this crate without pulling in browser dependencies.
 
 pub mod asset;
+pub mod checksum;
+pub mod feed;
 pub mod format;
+pub mod frontmatter;
+pub mod i18n;
+pub mod search;
+pub mod text;
+pub mod zip;
 
 pub use asset::{data_url_for_bytes, media_type_for_path};
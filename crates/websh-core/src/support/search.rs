@@ -0,0 +1,299 @@
+//! Full-text search primitives: markdown stripping, tokenizing, term
+//! frequency scoring, and match snippets.
+//!
+//! Everything here is pure; fetching the documents to search is left to the
+//! caller.
+
+use std::ops::Range;
+
+use super::frontmatter::split_front_matter;
+
+/// Characters of context kept on each side of a snippet's match.
+pub const SNIPPET_RADIUS: usize = 40;
+
+/// A ranked document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchHit<K> {
+    pub key: K,
+    /// Total occurrences of the query terms.
+    pub score: usize,
+    pub snippet: Snippet,
+}
+
+/// Text around a match. `highlight` is the byte range of the match within
+/// `text`; elided ends are marked with `…`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snippet {
+    pub text: String,
+    pub highlight: Range<usize>,
+}
+
+impl Snippet {
+    /// The snippet with the match wrapped in `open` / `close`.
+    pub fn marked(&self, open: &str, close: &str) -> String {
+        format!(
+            "{}{open}{}{close}{}",
+            &self.text[..self.highlight.start],
+            &self.text[self.highlight.clone()],
+            &self.text[self.highlight.end..],
+        )
+    }
+}
+
+/// Reduce markdown to its readable text on a single line: front matter,
+/// heading/list/quote markers, emphasis, code ticks, link targets, and HTML
+/// tags are dropped.
+pub fn strip_markdown(source: &str) -> String {
+    let body = match split_front_matter(source) {
+        Ok(Some((_, body))) => body,
+        _ => source,
+    };
+
+    let mut out = String::with_capacity(body.len());
+    for line in body.lines() {
+        let line = line.trim();
+        if line.starts_with("```") || line.starts_with("~~~") {
+            continue;
+        }
+        let line = strip_line_marker(line);
+        if line.is_empty() {
+            continue;
+        }
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        strip_inline(line, &mut out);
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn strip_line_marker(line: &str) -> &str {
+    let line = line.trim_start_matches('>').trim_start();
+    let heading = line.trim_start_matches('#');
+    if heading.len() != line.len() && (heading.is_empty() || heading.starts_with(' ')) {
+        return heading.trim_start();
+    }
+    for marker in ["- [ ] ", "- [x] ", "- ", "* ", "+ "] {
+        if let Some(rest) = line.strip_prefix(marker) {
+            return rest;
+        }
+    }
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits > 0
+        && let Some(rest) = line[digits..].strip_prefix(". ")
+    {
+        return rest;
+    }
+    line
+}
+
+fn strip_inline(line: &str, out: &mut String) {
+    let mut chars = line.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '*' | '_' | '`' | '~' => {}
+            '!' if line[index + 1..].starts_with('[') => {}
+            '[' => {
+                // `[text](target)` keeps `text`; the target is skipped below.
+            }
+            ']' if line[index + 1..].starts_with('(') => {
+                let close = line[index..].find(')').map(|offset| index + offset);
+                if let Some(close) = close {
+                    while chars.peek().is_some_and(|(next, _)| *next <= close) {
+                        chars.next();
+                    }
+                }
+            }
+            '<' => {
+                if let Some(offset) = line[index..].find('>') {
+                    let close = index + offset;
+                    while chars.peek().is_some_and(|(next, _)| *next <= close) {
+                        chars.next();
+                    }
+                    out.push(' ');
+                } else {
+                    out.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+}
+
+/// Lowercased alphanumeric words in `text`.
+pub fn tokenize(text: &str) -> Vec<String> {
+    tokens_with_ranges(text).map(|(_, token)| token).collect()
+}
+
+fn tokens_with_ranges(text: &str) -> impl Iterator<Item = (Range<usize>, String)> + '_ {
+    let mut start = None;
+    text.char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+        .filter_map(move |(index, c)| {
+            if c.is_alphanumeric() {
+                start.get_or_insert(index);
+                None
+            } else {
+                start
+                    .take()
+                    .map(|start| (start..index, text[start..index].to_lowercase()))
+            }
+        })
+}
+
+/// Query terms: the query's tokens, deduplicated in order.
+pub fn query_terms(query: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for token in tokenize(query) {
+        if !terms.contains(&token) {
+            terms.push(token);
+        }
+    }
+    terms
+}
+
+/// Total occurrences of `terms` among the words of `text`.
+pub fn term_frequency(text: &str, terms: &[String]) -> usize {
+    tokens_with_ranges(text)
+        .filter(|(_, token)| terms.contains(token))
+        .count()
+}
+
+/// Snippet around the first word of `text` matching one of `terms`, with
+/// up to `radius` characters of context on each side.
+pub fn snippet(text: &str, terms: &[String], radius: usize) -> Option<Snippet> {
+    let (range, _) = tokens_with_ranges(text).find(|(_, token)| terms.contains(token))?;
+
+    let start = text[..range.start]
+        .char_indices()
+        .rev()
+        .take(radius)
+        .last()
+        .map_or(range.start, |(index, _)| index);
+    let end = text[range.end..]
+        .char_indices()
+        .nth(radius)
+        .map_or(text.len(), |(index, _)| range.end + index);
+
+    let lead = if start > 0 { "…" } else { "" };
+    let trail = if end < text.len() { "…" } else { "" };
+    let before = text[start..range.start].trim_start();
+    let after = text[range.end..end].trim_end();
+    let highlight_start = lead.len() + before.len();
+    Some(Snippet {
+        text: format!("{lead}{before}{}{after}{trail}", &text[range.clone()]),
+        highlight: highlight_start..highlight_start + range.len(),
+    })
+}
+
+/// Rank `documents` (key, stripped text) for `query` by term frequency,
+/// best first, keeping at most `limit`. Ties keep input order; documents
+/// without a match are dropped.
+pub fn rank<'a, K>(
+    documents: impl IntoIterator<Item = (K, &'a str)>,
+    query: &str,
+    limit: usize,
+) -> Vec<SearchHit<K>> {
+    let terms = query_terms(query);
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let mut hits: Vec<_> = documents
+        .into_iter()
+        .filter_map(|(key, text)| {
+            let score = term_frequency(text, &terms);
+            if score == 0 {
+                return None;
+            }
+            let snippet = snippet(text, &terms, SNIPPET_RADIUS)?;
+            Some(SearchHit {
+                key,
+                score,
+                snippet,
+            })
+        })
+        .collect();
+    hits.sort_by_key(|hit| std::cmp::Reverse(hit.score));
+    hits.truncate(limit);
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(query: &str) -> Vec<String> {
+        query_terms(query)
+    }
+
+    #[test]
+    fn strips_markdown_syntax() {
+        let source = "---\ntitle: Hidden\n---\n# Heading\n\n- **Bold** item with [a link](https://x.y) and `code`\n> quoted <em>html</em>\n```rust\nlet x = 1;\n```\n1. numbered ![alt](img.png)\n";
+        assert_eq!(
+            strip_markdown(source),
+            "Heading Bold item with a link and code quoted html let x = 1; numbered alt"
+        );
+    }
+
+    #[test]
+    fn keeps_hash_words_that_are_not_headings() {
+        assert_eq!(strip_markdown("#hashtag stays"), "#hashtag stays");
+    }
+
+    #[test]
+    fn tokenizes_lowercase_words() {
+        assert_eq!(
+            tokenize("Rust, WASM & rust-lang: 2026!"),
+            vec!["rust", "wasm", "rust", "lang", "2026"]
+        );
+        assert_eq!(terms("rust Rust wasm"), vec!["rust", "wasm"]);
+    }
+
+    #[test]
+    fn term_frequency_counts_whole_words() {
+        let text = "Rust is fast. rust is safe. Trust nothing.";
+        assert_eq!(term_frequency(text, &terms("rust")), 2);
+        assert_eq!(term_frequency(text, &terms("rust safe")), 3);
+        assert_eq!(term_frequency(text, &terms("go")), 0);
+    }
+
+    #[test]
+    fn snippet_marks_first_match_with_context() {
+        let text = "one two three four five six seven";
+        let snippet = snippet(text, &terms("four"), 8).unwrap();
+        assert_eq!(snippet.marked("[", "]"), "…o three [four] five si…");
+        assert_eq!(&snippet.text[snippet.highlight.clone()], "four");
+    }
+
+    #[test]
+    fn snippet_without_elision_at_text_edges() {
+        let snippet = snippet("Hello world", &terms("hello"), 40).unwrap();
+        assert_eq!(snippet.marked("[", "]"), "[Hello] world");
+        assert!(super::snippet("Hello world", &terms("absent"), 40).is_none());
+    }
+
+    #[test]
+    fn snippet_respects_multibyte_characters() {
+        let text = "café crème brûlée — dessert";
+        let snippet = snippet(text, &terms("dessert"), 5).unwrap();
+        assert_eq!(snippet.marked("[", "]"), "…ée — [dessert]");
+    }
+
+    #[test]
+    fn ranks_by_term_frequency() {
+        let docs = [
+            ("a", "rust once"),
+            ("b", "nothing here"),
+            ("c", "rust rust and more rust"),
+            ("d", "rust twice, rust"),
+        ];
+        let hits = rank(docs, "rust", 2);
+        let keys: Vec<_> = hits.iter().map(|hit| (hit.key, hit.score)).collect();
+        assert_eq!(keys, vec![("c", 3), ("d", 2)]);
+    }
+
+    #[test]
+    fn empty_query_matches_nothing() {
+        assert!(rank([("a", "text")], " -- ", 10).is_empty());
+    }
+}
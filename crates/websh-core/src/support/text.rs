@@ -0,0 +1,115 @@
+//! Small string utilities shared across engine modules.
+
+/// Optimal string alignment distance: Levenshtein plus adjacent transpositions,
+/// so `gerp` is one edit away from `grep`.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+/// Expand the backslash escapes `echo -e` understands: `\\`, `\n`, `\t`,
+/// `\r`, `\a`, `\b`, `\e`, `\f`, `\v`, and `\c`, which drops the rest of
+/// the text. Returns the expanded text and whether `\c` cut it short.
+/// Unknown escapes are kept as written.
+pub fn expand_backslash_escapes(text: &str) -> (String, bool) {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        let Some(escaped) = chars.next() else {
+            out.push('\\');
+            break;
+        };
+        match escaped {
+            '\\' => out.push('\\'),
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            'r' => out.push('\r'),
+            'a' => out.push('\u{7}'),
+            'b' => out.push('\u{8}'),
+            'e' => out.push('\u{1b}'),
+            'f' => out.push('\u{c}'),
+            'v' => out.push('\u{b}'),
+            'c' => return (out, true),
+            other => {
+                out.push('\\');
+                out.push(other);
+            }
+        }
+    }
+    (out, false)
+}
+
+/// `addition` appended to `existing` as `>>` and `tee -a` do, starting on
+/// a new line when `existing` does not end with one.
+pub fn append_text(existing: &str, addition: &str) -> String {
+    let mut text = String::with_capacity(existing.len() + addition.len() + 1);
+    text.push_str(existing);
+    if !existing.is_empty() && !existing.ends_with('\n') {
+        text.push('\n');
+    }
+    text.push_str(addition);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn append_text_starts_on_a_fresh_line() {
+        assert_eq!(append_text("", "b\n"), "b\n");
+        assert_eq!(append_text("a\n", "b\n"), "a\nb\n");
+        assert_eq!(append_text("a", "b\n"), "a\nb\n");
+    }
+
+    #[test]
+    fn expands_known_escapes_and_keeps_unknown_ones() {
+        assert_eq!(
+            expand_backslash_escapes(r"a\tb\nc\\d\qe\"),
+            ("a\tb\nc\\d\\qe\\".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn backslash_c_stops_the_text() {
+        assert_eq!(
+            expand_backslash_escapes(r"kept\cdropped"),
+            ("kept".to_string(), true)
+        );
+    }
+
+    #[test]
+    fn edit_distance_counts_transpositions_once() {
+        assert_eq!(edit_distance("grep", "grep"), 0);
+        assert_eq!(edit_distance("gerp", "grep"), 1);
+        assert_eq!(edit_distance("cat", "cd"), 2);
+        assert_eq!(edit_distance("", "ls"), 2);
+    }
+}
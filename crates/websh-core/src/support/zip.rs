@@ -0,0 +1,404 @@
+//! Uncompressed ZIP archives.
+//!
+//! Entries are stored as-is (method 0), which keeps the writer small and
+//! is what the content needs: slides, images and PDFs are compressed
+//! already. Names are written as UTF-8 with general purpose flag bit 11
+//! set. Archives past the classic format's limits (65 535 entries or
+//! 4 GiB) are refused rather than written as ZIP64.
+
+use thiserror::Error;
+
+use super::format::civil_date;
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4b50;
+/// 2.0: the lowest version that knows about directories in names.
+const VERSION: u16 = 20;
+/// General purpose bit 11: names are UTF-8.
+const UTF8_NAMES: u16 = 1 << 11;
+const METHOD_STORED: u16 = 0;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ZipError {
+    #[error("invalid entry name `{0}`")]
+    InvalidName(String),
+    #[error("duplicate entry name `{0}`")]
+    DuplicateName(String),
+    #[error("too many entries for a zip archive")]
+    TooManyEntries,
+    #[error("archive is larger than 4 GiB")]
+    TooLarge,
+}
+
+/// CRC-32 (IEEE 802.3) of `bytes`, as ZIP headers record it.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, &byte| {
+        CRC32_TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut crc = index as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                0xedb8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[index] = crc;
+        index += 1;
+    }
+    table
+}
+
+/// An MS-DOS date and time, the only timestamp the classic headers carry.
+/// Seconds have two-second resolution and years start at 1980.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DosDateTime {
+    pub date: u16,
+    pub time: u16,
+}
+
+impl DosDateTime {
+    /// 1980-01-01 00:00:00, the earliest representable moment.
+    pub const EPOCH: Self = Self {
+        date: (1 << 5) | 1,
+        time: 0,
+    };
+
+    /// 2107-12-31 23:59:58, the latest representable moment.
+    pub const LATEST: Self = Self {
+        date: (127 << 9) | (12 << 5) | 31,
+        time: (23 << 11) | (59 << 5) | 29,
+    };
+
+    /// The UTC date and time of a Unix timestamp in seconds, clamped to
+    /// the representable range.
+    pub fn from_unix_secs(timestamp: u64) -> Self {
+        let (year, month, day) = civil_date(timestamp);
+        if year < 1980 {
+            return Self::EPOCH;
+        }
+        if year > 2107 {
+            return Self::LATEST;
+        }
+        let seconds = timestamp % 86_400;
+        let (hour, minute, second) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+        Self {
+            date: (((year - 1980) as u16) << 9) | ((month as u16) << 5) | day as u16,
+            time: ((hour as u16) << 11) | ((minute as u16) << 5) | (second as u16 / 2),
+        }
+    }
+}
+
+/// Builds a stored ZIP archive in memory.
+///
+/// Each [`add_file`](Self::add_file) appends a local header and the bytes;
+/// [`finish`](Self::finish) appends the central directory and the end
+/// record. Directories are implied by `/` in entry names.
+pub struct ZipWriter {
+    out: Vec<u8>,
+    central: Vec<u8>,
+    names: Vec<String>,
+    modified: DosDateTime,
+}
+
+impl ZipWriter {
+    /// Every entry is stamped with `modified`.
+    pub fn new(modified: DosDateTime) -> Self {
+        Self {
+            out: Vec::new(),
+            central: Vec::new(),
+            names: Vec::new(),
+            modified,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Append a file named `name` (relative, `/`-separated) holding `bytes`.
+    pub fn add_file(&mut self, name: &str, bytes: &[u8]) -> Result<(), ZipError> {
+        if !is_valid_name(name) {
+            return Err(ZipError::InvalidName(name.to_string()));
+        }
+        if self.names.iter().any(|existing| existing == name) {
+            return Err(ZipError::DuplicateName(name.to_string()));
+        }
+        if self.names.len() >= usize::from(u16::MAX) {
+            return Err(ZipError::TooManyEntries);
+        }
+        let name_len = u16::try_from(name.len()).map_err(|_| ZipError::InvalidName(name.into()))?;
+        let size = u32::try_from(bytes.len()).map_err(|_| ZipError::TooLarge)?;
+        let offset = u32::try_from(self.out.len()).map_err(|_| ZipError::TooLarge)?;
+        let end = self.out.len() + 30 + name.len() + bytes.len();
+        if u32::try_from(end).is_err() {
+            return Err(ZipError::TooLarge);
+        }
+        let crc = crc32(bytes);
+
+        let out = &mut self.out;
+        put_u32(out, LOCAL_HEADER_SIGNATURE);
+        put_u16(out, VERSION);
+        put_u16(out, UTF8_NAMES);
+        put_u16(out, METHOD_STORED);
+        put_u16(out, self.modified.time);
+        put_u16(out, self.modified.date);
+        put_u32(out, crc);
+        put_u32(out, size);
+        put_u32(out, size);
+        put_u16(out, name_len);
+        put_u16(out, 0);
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(bytes);
+
+        let central = &mut self.central;
+        put_u32(central, CENTRAL_HEADER_SIGNATURE);
+        put_u16(central, VERSION);
+        put_u16(central, VERSION);
+        put_u16(central, UTF8_NAMES);
+        put_u16(central, METHOD_STORED);
+        put_u16(central, self.modified.time);
+        put_u16(central, self.modified.date);
+        put_u32(central, crc);
+        put_u32(central, size);
+        put_u32(central, size);
+        put_u16(central, name_len);
+        // Extra field, comment, starting disk, internal and external
+        // attributes.
+        put_u16(central, 0);
+        put_u16(central, 0);
+        put_u16(central, 0);
+        put_u16(central, 0);
+        put_u32(central, 0);
+        put_u32(central, offset);
+        central.extend_from_slice(name.as_bytes());
+
+        self.names.push(name.to_string());
+        Ok(())
+    }
+
+    /// Append the central directory and end record and return the archive.
+    pub fn finish(self) -> Result<Vec<u8>, ZipError> {
+        let Self {
+            mut out,
+            central,
+            names,
+            ..
+        } = self;
+        let entries = u16::try_from(names.len()).map_err(|_| ZipError::TooManyEntries)?;
+        let central_offset = u32::try_from(out.len()).map_err(|_| ZipError::TooLarge)?;
+        let central_size = u32::try_from(central.len()).map_err(|_| ZipError::TooLarge)?;
+        if u32::try_from(out.len() + central.len() + 22).is_err() {
+            return Err(ZipError::TooLarge);
+        }
+        out.extend_from_slice(&central);
+        put_u32(&mut out, END_OF_CENTRAL_DIRECTORY_SIGNATURE);
+        put_u16(&mut out, 0);
+        put_u16(&mut out, 0);
+        put_u16(&mut out, entries);
+        put_u16(&mut out, entries);
+        put_u32(&mut out, central_size);
+        put_u32(&mut out, central_offset);
+        put_u16(&mut out, 0);
+        Ok(out)
+    }
+}
+
+/// Relative, `/`-separated, and without empty, `.` or `..` segments, so an
+/// archive never extracts outside its folder.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.contains('\\')
+        && name
+            .split('/')
+            .all(|segment| !matches!(segment, "" | "." | ".."))
+}
+
+fn put_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u16_at(bytes: &[u8], at: usize) -> u16 {
+        u16::from_le_bytes([bytes[at], bytes[at + 1]])
+    }
+
+    fn u32_at(bytes: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+    }
+
+    /// Central directory records of `archive`, parsed back through the end
+    /// record: `(name, crc, size, local header offset)`.
+    fn central_directory(archive: &[u8]) -> Vec<(String, u32, u32, u32)> {
+        let end = archive.len() - 22;
+        assert_eq!(u32_at(archive, end), END_OF_CENTRAL_DIRECTORY_SIGNATURE);
+        let count = u16_at(archive, end + 10);
+        assert_eq!(u16_at(archive, end + 8), count);
+        let size = u32_at(archive, end + 12) as usize;
+        let mut at = u32_at(archive, end + 16) as usize;
+        assert_eq!(at + size, end, "central directory ends at the end record");
+
+        let mut records = Vec::new();
+        for _ in 0..count {
+            assert_eq!(u32_at(archive, at), CENTRAL_HEADER_SIGNATURE);
+            assert_eq!(u16_at(archive, at + 8), UTF8_NAMES);
+            assert_eq!(u16_at(archive, at + 10), METHOD_STORED);
+            let name_len = u16_at(archive, at + 28) as usize;
+            let name = String::from_utf8(archive[at + 46..at + 46 + name_len].to_vec()).unwrap();
+            records.push((
+                name,
+                u32_at(archive, at + 16),
+                u32_at(archive, at + 24),
+                u32_at(archive, at + 42),
+            ));
+            at += 46 + name_len;
+        }
+        records
+    }
+
+    /// The name and data under the local header at `offset`.
+    fn local_entry(archive: &[u8], offset: usize) -> (&str, &[u8]) {
+        assert_eq!(u32_at(archive, offset), LOCAL_HEADER_SIGNATURE);
+        let size = u32_at(archive, offset + 22) as usize;
+        assert_eq!(u32_at(archive, offset + 18), size as u32);
+        let name_len = u16_at(archive, offset + 26) as usize;
+        let extra_len = u16_at(archive, offset + 28) as usize;
+        let name_start = offset + 30;
+        let data_start = name_start + name_len + extra_len;
+        (
+            std::str::from_utf8(&archive[name_start..name_start + name_len]).unwrap(),
+            &archive[data_start..data_start + size],
+        )
+    }
+
+    #[test]
+    fn crc32_matches_reference_vectors() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b"a"), 0xe8b7_be43);
+        assert_eq!(
+            crc32(b"The quick brown fox jumps over the lazy dog"),
+            0x414f_a339
+        );
+    }
+
+    #[test]
+    fn empty_archive_is_just_the_end_record() {
+        let archive = ZipWriter::new(DosDateTime::EPOCH).finish().unwrap();
+        assert_eq!(archive.len(), 22);
+        assert!(central_directory(&archive).is_empty());
+    }
+
+    #[test]
+    fn entries_round_trip_through_the_central_directory() {
+        let files: [(&str, &[u8]); 3] = [
+            ("talks/a.txt", b"hello"),
+            ("talks/empty.bin", b""),
+            ("talks/deck/slides.pdf", b"%PDF-1.7 \x00\xff"),
+        ];
+        let mut writer = ZipWriter::new(DosDateTime::EPOCH);
+        for (name, bytes) in files {
+            writer.add_file(name, bytes).unwrap();
+        }
+        assert_eq!(writer.len(), 3);
+        let archive = writer.finish().unwrap();
+
+        let records = central_directory(&archive);
+        assert_eq!(records.len(), files.len());
+        for ((name, bytes), (record_name, crc, size, offset)) in files.iter().zip(&records) {
+            assert_eq!(record_name, name);
+            assert_eq!(*crc, crc32(bytes));
+            assert_eq!(*size as usize, bytes.len());
+            let (local_name, data) = local_entry(&archive, *offset as usize);
+            assert_eq!(local_name, *name);
+            assert_eq!(data, *bytes);
+            assert_eq!(u32_at(&archive, *offset as usize + 14), *crc);
+        }
+    }
+
+    #[test]
+    fn local_headers_are_laid_out_back_to_back() {
+        let mut writer = ZipWriter::new(DosDateTime::EPOCH);
+        writer.add_file("a", b"12345").unwrap();
+        writer.add_file("bc", b"").unwrap();
+        let archive = writer.finish().unwrap();
+
+        let offsets: Vec<u32> = central_directory(&archive)
+            .into_iter()
+            .map(|(_, _, _, offset)| offset)
+            .collect();
+        // 30-byte header + 1-byte name + 5 bytes of data.
+        assert_eq!(offsets, [0, 36]);
+        let central_start = 36 + 30 + 2;
+        assert_eq!(u32_at(&archive, archive.len() - 6), central_start);
+        assert_eq!(archive.len(), central_start as usize + 46 + 1 + 46 + 2 + 22);
+    }
+
+    #[test]
+    fn headers_carry_the_modification_time() {
+        let modified = DosDateTime::from_unix_secs(1_700_000_000);
+        let mut writer = ZipWriter::new(modified);
+        writer.add_file("a", b"x").unwrap();
+        let archive = writer.finish().unwrap();
+        assert_eq!(u16_at(&archive, 10), modified.time);
+        assert_eq!(u16_at(&archive, 12), modified.date);
+    }
+
+    #[test]
+    fn dos_date_time_from_unix_seconds() {
+        // 2023-11-14 22:13:20 UTC.
+        let stamp = DosDateTime::from_unix_secs(1_700_000_000);
+        assert_eq!(stamp.date, (43 << 9) | (11 << 5) | 14);
+        assert_eq!(stamp.time, (22 << 11) | (13 << 5) | 10);
+        // Odd seconds round down to the two-second grid.
+        let stamp = DosDateTime::from_unix_secs(1_700_000_001);
+        assert_eq!(stamp.time & 0x1f, 10);
+        assert_eq!(DosDateTime::from_unix_secs(0), DosDateTime::EPOCH);
+    }
+
+    #[test]
+    fn rejects_names_that_escape_the_archive() {
+        let mut writer = ZipWriter::new(DosDateTime::EPOCH);
+        for name in ["", "/abs", "a//b", "../up", "a/./b", "dir/", "a\\b"] {
+            assert_eq!(
+                writer.add_file(name, b""),
+                Err(ZipError::InvalidName(name.to_string())),
+                "{name:?}"
+            );
+        }
+        assert!(writer.is_empty());
+    }
+
+    #[test]
+    fn rejects_duplicate_names() {
+        let mut writer = ZipWriter::new(DosDateTime::EPOCH);
+        writer.add_file("a.txt", b"1").unwrap();
+        assert_eq!(
+            writer.add_file("a.txt", b"2"),
+            Err(ZipError::DuplicateName("a.txt".to_string()))
+        );
+        assert_eq!(central_directory(&writer.finish().unwrap()).len(), 1);
+    }
+}
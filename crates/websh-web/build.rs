@@ -0,0 +1,28 @@
+//! Embeds the current git commit as `WEBSH_GIT_COMMIT` for `neofetch`.
+//!
+//! Builds outside a git checkout (e.g. from a source tarball) simply leave
+//! the variable unset.
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=../../.git/HEAD");
+    println!("cargo:rerun-if-changed=../../.git/refs/heads");
+    println!("cargo:rerun-if-env-changed=WEBSH_GIT_COMMIT");
+
+    if std::env::var_os("WEBSH_GIT_COMMIT").is_some() {
+        return;
+    }
+
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+    if let Some(commit) = commit.map(|commit| commit.trim().to_string())
+        && !commit.is_empty()
+    {
+        println!("cargo:rustc-env=WEBSH_GIT_COMMIT={commit}");
+    }
+}
@@ -4,6 +4,7 @@ use leptos::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 
 use super::{AppContext, AppEditModal, RuntimeServices};
+use crate::config::SCROLLBACK_VAR;
 use crate::features::RouterView;
 
 stylance::import_crate_style!(err_css, "src/app/error_boundary.module.css");
@@ -19,6 +20,17 @@ pub fn App() -> impl IntoView {
     }
     services.install_wallet_event_listeners();
 
+    Effect::new(move |_| {
+        crate::render::display::apply_display_settings_to_document(&ctx.display.get());
+    });
+
+    Effect::new(move |_| {
+        let lines = ctx.runtime_state.with(|rs| {
+            super::state::scrollback_lines(rs.env.get(SCROLLBACK_VAR).map(String::as_str))
+        });
+        ctx.terminal.set_scrollback(lines);
+    });
+
     let changes_signal = ctx.changes;
     let drafts_hydrated = ctx.drafts_hydrated;
     spawn_local(async move {
@@ -2,20 +2,35 @@
 
 use std::collections::BTreeMap;
 use std::rc::Rc;
+use std::sync::Arc;
 
 use futures_util::FutureExt;
 use leptos::prelude::*;
-
-use super::TerminalState;
-use crate::config::APP_NAME;
+use web_sys::AbortSignal;
+
+use super::usage::{clear_usage_stats, load_usage_stats, save_usage_stats};
+use super::{NavHistory, RingBuffer, TerminalState, UsageTracker};
+use crate::config::{APP_NAME, MAX_APP_EVENTS};
+use crate::platform::fetch::{FetchError, FetchPolicy, FetchProgress, fetch_bytes_with_progress};
+use crate::platform::page_uptime_ms;
+use crate::render::RenderedMarkdown;
+use crate::render::display::{self, DisplaySettings};
 use crate::runtime::content_cache::{ContentTextCache, ContentTextCacheKey};
+use crate::runtime::search_index::SearchIndex;
 use crate::runtime::{self, RuntimeLoad};
 use websh_core::domain::{
     ChangeSet, RuntimeMount, VirtualPath, WalletState, is_runtime_overlay_path,
 };
-use websh_core::filesystem::{ContentReadError, GlobalFs, display_path_for};
-use websh_core::ports::{LocalBoxFuture, StorageBackendRef};
+use websh_core::filesystem::{ContentReadError, DeepLink, GlobalFs, display_path_for};
+use websh_core::ports::{
+    LocalBoxFuture, ManifestDiff, StorageBackendRef, StorageError, lint_manifest_snapshot,
+};
 use websh_core::runtime::RuntimeStateSnapshot;
+use websh_core::shell::{
+    AdminStatus, AppEvent, AppEventKind, ManifestSync, Prompt, PromptVars, UsageReport, UsageStats,
+    render_prompt, tracking_enabled,
+};
+use websh_core::support::i18n::Locale;
 
 type TextReadResult = Result<String, ContentReadError>;
 type SharedTextRead = futures_util::future::Shared<LocalBoxFuture<'static, TextReadResult>>;
@@ -41,8 +56,13 @@ pub struct AppContext {
     pub global_fs: RwSignal<GlobalFs>,
     /// Current canonical working directory for shell surfaces.
     pub cwd: RwSignal<VirtualPath>,
+    /// A content page has been shown since the terminal last was; read once
+    /// by the terminal to announce where it reopens.
+    left_terminal: StoredValue<bool>,
     /// Wallet connection state.
     pub wallet: RwSignal<WalletState>,
+    /// ENS avatar resolved for the connected wallet's name.
+    pub wallet_avatar: RwSignal<Option<runtime::wallet::WalletAvatar>>,
     /// Installed browser wallet event listener handles. Stored so listener
     /// closures are not leaked and setup stays idempotent.
     wallet_event_listeners:
@@ -53,6 +73,20 @@ pub struct AppContext {
 
     /// Terminal state (history, commands).
     pub terminal: TerminalState,
+    /// Routes visited before and after the current one.
+    pub nav_history: RwSignal<NavHistory>,
+    /// Deep-link extras from the address bar, held for the view of that
+    /// route path to consume once.
+    deep_link: StoredValue<Option<(String, DeepLink)>>,
+    /// Latest change picked up by the manifest poller, until dismissed.
+    pub manifest_update: RwSignal<Option<ManifestDiff>>,
+    /// Root manifest before and after the latest change the poller found,
+    /// for `ls --diff`.
+    pub manifest_sync: StoredValue<Option<Arc<ManifestSync>>>,
+    /// Wallet, mount, manifest and fetch events, newest last, for `dmesg`.
+    events: StoredValue<RingBuffer<AppEvent>>,
+    /// Command, file and directory counters for `stats`.
+    usage: StoredValue<UsageTracker>,
 
     /// Staged + working-tree edits awaiting commit.
     pub changes: RwSignal<ChangeSet>,
@@ -69,6 +103,8 @@ pub struct AppContext {
     content_text_cache: StoredValue<ContentTextCache, LocalStorage>,
     /// Backend text reads already in flight, keyed like the text cache.
     content_text_inflight: StoredValue<BTreeMap<ContentTextCacheKey, SharedTextRead>, LocalStorage>,
+    /// Stripped markdown read by `search`, evicted alongside the text cache.
+    search_index: StoredValue<SearchIndex, LocalStorage>,
     /// Runtime mount declarations, effective write status, and scan jobs.
     pub mounts: RwSignal<runtime::MountLoadSet, LocalStorage>,
     /// Remote HEAD registry keyed by canonical mount roots.
@@ -77,6 +113,11 @@ pub struct AppContext {
     runtime_generation: StoredValue<u64, LocalStorage>,
     /// Browser-hydrated runtime state rendered under `/.websh/state`.
     pub runtime_state: RwSignal<RuntimeStateSnapshot>,
+    /// Visual effect settings derived from user variables; updates whenever
+    /// `runtime_state` changes.
+    pub display: Memo<DisplaySettings>,
+    /// Interface language from `LANG`; components relabel when it changes.
+    pub locale: Memo<Locale>,
 
     /// When `Some(path)`, the `EditModal` is open editing that path. `None` = closed.
     pub editor_open: RwSignal<Option<websh_core::domain::VirtualPath>>,
@@ -98,6 +139,11 @@ impl AppContext {
         let wallet = RwSignal::new(WalletState::default());
         let wallet_event_listeners = StoredValue::new_local(None);
         let runtime_state = RwSignal::new(super::RuntimeServices::runtime_state_snapshot());
+        let prefers_reduced_motion = display::prefers_reduced_motion();
+        let display = Memo::new(move |_| {
+            runtime_state.with(|rs| DisplaySettings::from_env(&rs.env, prefers_reduced_motion))
+        });
+        let locale = Memo::new(move |_| runtime_state.with(|rs| Locale::from_env(&rs.env)));
         let view_global_fs = Signal::derive_local(move || {
             Rc::new(global_fs.with(|base| {
                 changes.with(|cs| websh_core::runtime::build_content_view_global_fs(base, cs))
@@ -118,6 +164,7 @@ impl AppContext {
             StoredValue::new_local(initial_load.backends);
         let content_text_cache = StoredValue::new_local(ContentTextCache::default());
         let content_text_inflight = StoredValue::new_local(BTreeMap::new());
+        let search_index = StoredValue::new_local(SearchIndex::default());
         let mounts = RwSignal::new_local(initial_load.mounts);
         let remote_heads = RwSignal::new(initial_load.remote_heads);
         let runtime_generation = StoredValue::new_local(0_u64);
@@ -129,13 +176,21 @@ impl AppContext {
             // Shared state
             global_fs,
             cwd: RwSignal::new(VirtualPath::root()),
+            left_terminal: StoredValue::new(false),
             wallet,
+            wallet_avatar: RwSignal::new(None),
             wallet_event_listeners,
 
             theme,
 
             // Terminal state
             terminal: TerminalState::new(),
+            nav_history: RwSignal::new(NavHistory::default()),
+            deep_link: StoredValue::new(None),
+            manifest_update: RwSignal::new(None),
+            manifest_sync: StoredValue::new(None),
+            events: StoredValue::new(RingBuffer::new(MAX_APP_EVENTS)),
+            usage: StoredValue::new(UsageTracker::resume(load_usage_stats())),
 
             // Runtime filesystem/write state
             changes,
@@ -145,16 +200,98 @@ impl AppContext {
             backends,
             content_text_cache,
             content_text_inflight,
+            search_index,
             mounts,
             remote_heads,
             runtime_generation,
             runtime_state,
+            display,
+            locale,
 
             // Editor state
             editor_open,
         }
     }
 
+    /// Move `offset` entries through the navigation history. Returns the
+    /// route to navigate to, or `None` when the history is too short.
+    pub fn jump_history(&self, offset: isize) -> Option<String> {
+        self.nav_history
+            .try_update(|history| history.jump(offset).map(str::to_string))
+            .flatten()
+    }
+
+    /// Record that a content page replaced the terminal.
+    pub fn mark_left_terminal(&self) {
+        self.left_terminal.set_value(true);
+    }
+
+    /// Whether a content page was shown since the last call.
+    pub fn take_left_terminal(&self) -> bool {
+        self.left_terminal
+            .try_update_value(std::mem::take)
+            .unwrap_or(false)
+    }
+
+    /// Hold `link` for the next view of `url_path`, replacing any link not
+    /// yet consumed.
+    pub fn set_deep_link(&self, url_path: String, link: DeepLink) {
+        self.deep_link.set_value(Some((url_path, link)));
+    }
+
+    /// Take the held deep link if it was given for `url_path`. Any held link
+    /// is dropped either way, so it never applies to a later navigation.
+    pub fn take_deep_link(&self, url_path: &str) -> Option<DeepLink> {
+        self.deep_link
+            .try_update_value(Option::take)
+            .flatten()
+            .filter(|(path, _)| path == url_path)
+            .map(|(_, link)| link)
+    }
+
+    /// Append an event to the `dmesg` log, stamped with the page uptime.
+    pub fn log_event(&self, kind: AppEventKind) {
+        let at_ms = page_uptime_ms().map_or(0, |ms| ms as u64);
+        self.events.update_value(|events| {
+            events.push(AppEvent::new(at_ms, kind));
+        });
+    }
+
+    /// The logged events, oldest first.
+    pub fn events_snapshot(&self) -> Vec<AppEvent> {
+        self.events.with_value(RingBuffer::to_vec)
+    }
+
+    /// Count into the `stats` counters and store them, unless `TRACKING`
+    /// is off.
+    pub fn record_usage(&self, count: impl Fn(&mut UsageStats)) {
+        if !self
+            .runtime_state
+            .with_untracked(|rs| tracking_enabled(&rs.env))
+        {
+            return;
+        }
+        let now = uptime_seconds();
+        self.usage.update_value(|tracker| {
+            tracker.record(now, count);
+            save_usage_stats(tracker.all_time());
+        });
+    }
+
+    pub fn usage_report(&self) -> UsageReport {
+        let now = uptime_seconds();
+        self.usage
+            .try_update_value(|tracker| tracker.report(now))
+            .unwrap_or_default()
+    }
+
+    /// Zero this session's and the stored `stats` counters.
+    pub fn reset_usage(&self) {
+        let now = uptime_seconds();
+        self.usage.update_value(|tracker| tracker.reset(now));
+        clear_usage_stats();
+    }
+
     pub fn runtime_mounts_snapshot(&self) -> Vec<RuntimeMount> {
         self.mounts.with(|mounts| mounts.effective_mounts())
     }
@@ -176,18 +313,37 @@ impl AppContext {
         self.mounts.with(|mounts| mounts.is_loaded(root))
     }
 
-    /// Gets the current prompt string for display.
+    /// Gets the current prompt for display.
     ///
-    /// Format: `{username}@{app_name}:{path}`
+    /// Format: `{username}@{app_name}:{path}`, or the `PS1` template when
+    /// that variable is set.
     ///
     /// The username is derived from the wallet state:
     /// - ENS name if available
     /// - Shortened address (0x1234...5678) if connected
     /// - "guest" if disconnected
-    pub fn get_prompt(&self, cwd: &VirtualPath) -> String {
+    pub fn get_prompt(&self, cwd: &VirtualPath) -> Prompt {
         let display_path = display_path_for(cwd);
-        let username = self.wallet.get().display_name();
-        format!("{}@{}:{}", username, APP_NAME, display_path)
+        let (username, admin) = self.wallet.with(|wallet| {
+            (
+                wallet.display_name(),
+                matches!(
+                    websh_site::ACCESS_POLICY.admin_status(wallet),
+                    AdminStatus::Admin { .. }
+                ),
+            )
+        });
+        self.runtime_state.with(|rs| {
+            render_prompt(
+                rs.env.get("PS1").map(String::as_str),
+                PromptVars {
+                    user: &username,
+                    host: APP_NAME,
+                    path: &display_path,
+                    admin,
+                },
+            )
+        })
     }
 
     /// Best-effort lookup for the backend responsible for a canonical path.
@@ -214,20 +370,65 @@ impl AppContext {
     }
 
     pub async fn read_text(&self, path: &VirtualPath) -> Result<String, ContentReadError> {
+        self.read_text_tracked(path, None, None).await
+    }
+
+    /// [`Self::read_text`] that streams cache misses from the backend's
+    /// public URL and reports download progress into `progress`. Aborting
+    /// `signal` cancels the download.
+    pub async fn read_text_with_progress(
+        &self,
+        path: &VirtualPath,
+        progress: RwSignal<Option<FetchProgress>>,
+        signal: Option<&AbortSignal>,
+    ) -> Result<String, ContentReadError> {
+        self.read_text_tracked(path, Some(progress), signal).await
+    }
+
+    async fn read_text_tracked(
+        &self,
+        path: &VirtualPath,
+        progress: Option<RwSignal<Option<FetchProgress>>>,
+        signal: Option<&AbortSignal>,
+    ) -> Result<String, ContentReadError> {
         let generation = self.runtime_generation();
-        let result = self.read_text_for_generation(path, generation).await;
-        if self.runtime_generation() == generation {
+        let mut result = self
+            .read_text_for_generation(path, generation, progress, signal)
+            .await;
+        if self.runtime_generation() != generation {
+            result = self
+                .read_text_for_generation(path, self.runtime_generation(), progress, signal)
+                .await;
+        }
+        // A cancelled read is not a failed fetch; keep it out of `dmesg`.
+        if signal.is_some_and(AbortSignal::aborted) {
             return result;
         }
+        self.log_read_failure(path, result)
+    }
 
-        self.read_text_for_generation(path, self.runtime_generation())
-            .await
+    /// Log a backend read of `path` that failed in storage or on the
+    /// network, passing the result through.
+    fn log_read_failure<T>(
+        &self,
+        path: &VirtualPath,
+        result: Result<T, ContentReadError>,
+    ) -> Result<T, ContentReadError> {
+        if let Err(ContentReadError::Storage(error)) = &result {
+            self.log_event(AppEventKind::FetchFailed {
+                target: path.to_string(),
+                error: error.to_string(),
+            });
+        }
+        result
     }
 
     async fn read_text_for_generation(
         &self,
         path: &VirtualPath,
         generation: u64,
+        progress: Option<RwSignal<Option<FetchProgress>>>,
+        signal: Option<&AbortSignal>,
     ) -> Result<String, ContentReadError> {
         let fs = self.view_fs_for_path(path);
         if let Some(text) = fs.read_pending_text(path) {
@@ -250,7 +451,7 @@ impl AppContext {
                 return;
             }
 
-            let shared = shared_text_read(fs, backends, path.clone());
+            let shared = shared_text_read(fs, backends, path.clone(), progress, signal.cloned());
             inflight.insert(cache_key.clone(), shared.clone());
             read = Some(shared);
         });
@@ -271,10 +472,62 @@ impl AppContext {
         result
     }
 
+    /// `render(source)` for the text read from `path`, reused from the text
+    /// cache while that entry still holds `source`. Revisiting a post skips
+    /// both the fetch and the render.
+    pub fn render_cached(
+        &self,
+        path: &VirtualPath,
+        source: &str,
+        render: impl FnOnce(&str) -> RenderedMarkdown,
+    ) -> RenderedMarkdown {
+        let generation = self.runtime_generation();
+        let Ok(cache_key) = self
+            .backends
+            .with_value(|backends| content_cache_key_for_path(generation, backends, path))
+        else {
+            return render(source);
+        };
+        let mut cached = None;
+        self.content_text_cache
+            .update_value(|cache| cached = cache.get_rendered(&cache_key, source));
+        if let Some(rendered) = cached {
+            return rendered;
+        }
+
+        let rendered = render(source);
+        self.content_text_cache.update_value(|cache| {
+            cache.insert_rendered(&cache_key, source, rendered.clone());
+        });
+        rendered
+    }
+
     pub async fn read_bytes(&self, path: &VirtualPath) -> Result<Vec<u8>, ContentReadError> {
         let fs = self.view_fs_for_path(path);
         let backends = self.backends.with_value(|map| map.clone());
-        websh_core::filesystem::read_bytes(&fs, &backends, path).await
+        let result = websh_core::filesystem::read_bytes(&fs, &backends, path).await;
+        self.log_read_failure(path, result)
+    }
+
+    /// [`Self::read_bytes`] that streams from the backend's public URL and
+    /// reports download progress into `progress`. Aborting `signal`
+    /// cancels the download.
+    pub async fn read_bytes_with_progress(
+        &self,
+        path: &VirtualPath,
+        progress: RwSignal<Option<FetchProgress>>,
+        signal: Option<&AbortSignal>,
+    ) -> Result<Vec<u8>, ContentReadError> {
+        let fs = self.view_fs_for_path(path);
+        let backends = self.backends.with_value(|map| map.clone());
+        let result = match websh_core::filesystem::public_read_url(&fs, &backends, path) {
+            Ok(Some(url)) => stream_public_url(&url, progress, signal).await,
+            _ => websh_core::filesystem::read_bytes(&fs, &backends, path).await,
+        };
+        if signal.is_some_and(AbortSignal::aborted) {
+            return result;
+        }
+        self.log_read_failure(path, result)
     }
 
     pub fn public_read_url(&self, path: &VirtualPath) -> Result<Option<String>, ContentReadError> {
@@ -318,6 +571,7 @@ impl AppContext {
         self.content_text_cache
             .update_value(ContentTextCache::clear);
         self.content_text_inflight.update_value(BTreeMap::clear);
+        self.search_index.update_value(SearchIndex::clear);
     }
 
     pub fn evict_text_cache_path(&self, path: &VirtualPath) {
@@ -325,6 +579,8 @@ impl AppContext {
             .update_value(|cache| cache.evict_path(path));
         self.content_text_inflight
             .update_value(|inflight| evict_inflight_path(inflight, path));
+        self.search_index
+            .update_value(|index| index.evict_path(path));
     }
 
     pub fn evict_text_cache_mount(&self, mount_root: &VirtualPath) {
@@ -332,6 +588,22 @@ impl AppContext {
             .update_value(|cache| cache.evict_mount(mount_root));
         self.content_text_inflight
             .update_value(|inflight| evict_inflight_mount(inflight, mount_root));
+        self.search_index
+            .update_value(|index| index.evict_mount(mount_root));
+    }
+
+    /// Stripped text indexed by `search` for `path` in the current runtime
+    /// generation.
+    pub fn search_text(&self, path: &VirtualPath) -> Option<String> {
+        let generation = self.runtime_generation();
+        self.search_index
+            .with_value(|index| index.get(generation, path))
+    }
+
+    pub fn index_search_text(&self, path: VirtualPath, text: String) {
+        let generation = self.runtime_generation();
+        self.search_index
+            .update_value(|index| index.insert(generation, path, text));
     }
 
     pub fn runtime_generation(&self) -> u64 {
@@ -405,6 +677,10 @@ impl AppContext {
                     self.mounts.update(|mounts| {
                         mounts.mark_failed_if_current(&root, epoch, message.clone());
                     });
+                    self.log_event(AppEventKind::MountFailed {
+                        root: root.to_string(),
+                        error: format!("{error:?}"),
+                    });
                     return Err(message);
                 }
                 let failed_descendants = self
@@ -417,13 +693,23 @@ impl AppContext {
                 self.backends.update_value(|backends| {
                     backends.insert(root.clone(), result.backend);
                 });
+                let issues = lint_manifest_snapshot(&scan);
                 self.mounts.update(|mounts| {
                     mounts.mark_loaded_if_current(&root, epoch, total_files);
+                    mounts.record_manifest_issues(&root, issues);
+                });
+                self.log_event(AppEventKind::MountLoaded {
+                    root: root.to_string(),
+                    files: total_files,
                 });
                 Ok(())
             }
             Err(error) => {
                 self.evict_text_cache_mount(&root);
+                self.log_event(AppEventKind::MountFailed {
+                    root: root.to_string(),
+                    error: error.clone(),
+                });
                 self.mounts.update(|mounts| {
                     mounts.mark_failed_if_current(&root, epoch, error);
                 });
@@ -433,6 +719,10 @@ impl AppContext {
     }
 }
 
+fn uptime_seconds() -> u64 {
+    page_uptime_ms().map_or(0, |ms| (ms / 1000.0) as u64)
+}
+
 fn content_cache_key_for_path(
     generation: u64,
     backends: &BTreeMap<VirtualPath, StorageBackendRef>,
@@ -459,12 +749,44 @@ fn shared_text_read(
     fs: Rc<GlobalFs>,
     backends: BTreeMap<VirtualPath, StorageBackendRef>,
     path: VirtualPath,
+    progress: Option<RwSignal<Option<FetchProgress>>>,
+    signal: Option<AbortSignal>,
 ) -> SharedTextRead {
-    let read: LocalBoxFuture<'static, TextReadResult> =
-        Box::pin(async move { websh_core::filesystem::read_text(&fs, &backends, &path).await });
+    let read: LocalBoxFuture<'static, TextReadResult> = Box::pin(async move {
+        // Backends serve text reads from the same public URL, so streaming
+        // it directly only changes how the body arrives.
+        if let Some(progress) = progress
+            && let Ok(Some(url)) = websh_core::filesystem::public_read_url(&fs, &backends, &path)
+        {
+            return stream_public_url(&url, progress, signal.as_ref())
+                .await
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned());
+        }
+        websh_core::filesystem::read_text(&fs, &backends, &path).await
+    });
     read.shared()
 }
 
+async fn stream_public_url(
+    url: &str,
+    progress: RwSignal<Option<FetchProgress>>,
+    signal: Option<&AbortSignal>,
+) -> Result<Vec<u8>, ContentReadError> {
+    let policy =
+        FetchPolicy::default().with_retries(crate::runtime::state::configured_fetch_retries());
+    fetch_bytes_with_progress(url, signal, policy, |update| {
+        // The reader may unmount mid-download; a disposed signal is fine.
+        let _ = progress.try_set(Some(update));
+    })
+    .await
+    .map_err(|error| {
+        ContentReadError::Storage(match error {
+            FetchError::HttpError(404) => StorageError::NotFound(url.to_string()),
+            other => StorageError::NetworkError(other.to_string()),
+        })
+    })
+}
+
 fn evict_inflight_path(
     inflight: &mut BTreeMap<ContentTextCacheKey, SharedTextRead>,
     path: &VirtualPath,
@@ -594,6 +916,7 @@ mod tests {
             remote_heads: BTreeMap::new(),
             total_files: 0,
             mounts,
+            banner: None,
         });
     }
 
@@ -636,6 +959,42 @@ mod tests {
         assert_eq!(reads.get(), 1);
     }
 
+    #[wasm_bindgen_test(async)]
+    async fn render_cached_reuses_render_of_cached_text_only() {
+        let owner = Owner::new();
+        let reads = Rc::new(Cell::new(0));
+        let renders = Cell::new(0);
+        let path = VirtualPath::from_absolute("/post.md").expect("path");
+        let render = |source: &str| {
+            renders.set(renders.get() + 1);
+            RenderedMarkdown {
+                html: source.to_uppercase(),
+                ..RenderedMarkdown::default()
+            }
+        };
+
+        let ctx = owner.with(|| {
+            let ctx = AppContext::new();
+            apply_loaded_root_backend(ctx, counting_backend(reads.clone(), "remote", 0));
+            ctx.clear_text_cache();
+            ctx
+        });
+
+        let text = ctx.read_text(&path).await.unwrap();
+        assert_eq!(ctx.render_cached(&path, &text, render).html, "REMOTE");
+        assert_eq!(ctx.render_cached(&path, &text, render).html, "REMOTE");
+        assert_eq!(renders.get(), 1);
+
+        // A draft of the same path renders fresh.
+        assert_eq!(ctx.render_cached(&path, "draft", render).html, "DRAFT");
+        assert_eq!(renders.get(), 2);
+
+        ctx.evict_text_cache_path(&path);
+        let text = ctx.read_text(&path).await.unwrap();
+        assert_eq!(ctx.render_cached(&path, &text, render).html, "REMOTE");
+        assert_eq!((reads.get(), renders.get()), (2, 3));
+    }
+
     #[wasm_bindgen_test(async)]
     async fn concurrent_same_generation_read_text_calls_share_one_backend_request() {
         let owner = Owner::new();
@@ -687,6 +1046,75 @@ mod tests {
         assert_eq!(fresh_reads.get(), 1);
     }
 
+    #[wasm_bindgen_test]
+    fn deep_link_is_taken_once_and_only_for_its_route() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let ctx = AppContext::new();
+            let link = DeepLink {
+                heading: Some("setup".to_string()),
+                ..DeepLink::default()
+            };
+            ctx.set_deep_link("/blog/post.md".to_string(), link.clone());
+            assert_eq!(ctx.take_deep_link("/blog/post.md"), Some(link.clone()));
+            assert_eq!(ctx.take_deep_link("/blog/post.md"), None);
+
+            ctx.set_deep_link("/blog/post.md".to_string(), link);
+            assert_eq!(ctx.take_deep_link("/blog"), None);
+            assert_eq!(ctx.take_deep_link("/blog/post.md"), None);
+        });
+    }
+
+    #[wasm_bindgen_test]
+    fn event_log_keeps_the_newest_events() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let ctx = AppContext::new();
+            assert!(ctx.events_snapshot().is_empty());
+
+            for chain_id in 0..(MAX_APP_EVENTS as u64 + 5) {
+                ctx.log_event(AppEventKind::ChainChanged {
+                    chain_id: Some(chain_id),
+                });
+            }
+            let events = ctx.events_snapshot();
+            assert_eq!(events.len(), MAX_APP_EVENTS);
+            assert_eq!(
+                events.first().map(|event| &event.kind),
+                Some(&AppEventKind::ChainChanged { chain_id: Some(5) })
+            );
+            assert_eq!(
+                events.last().map(|event| &event.kind),
+                Some(&AppEventKind::ChainChanged {
+                    chain_id: Some(MAX_APP_EVENTS as u64 + 4)
+                })
+            );
+        });
+    }
+
+    #[wasm_bindgen_test]
+    fn jump_history_moves_between_stacks_within_bounds() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let ctx = AppContext::new();
+            assert_eq!(ctx.jump_history(-1), None);
+
+            ctx.nav_history.update(|history| {
+                for route in ["/websh", "/websh/blog", "/blog/post.md"] {
+                    history.observe(route);
+                }
+            });
+            assert_eq!(ctx.jump_history(-3), None);
+            assert_eq!(ctx.jump_history(-2).as_deref(), Some("/websh"));
+            assert_eq!(ctx.jump_history(1).as_deref(), Some("/websh/blog"));
+            assert_eq!(ctx.jump_history(2), None);
+
+            let snapshot = ctx.nav_history.with(NavHistory::snapshot);
+            assert_eq!(snapshot.back, vec!["/websh".to_string()]);
+            assert_eq!(snapshot.forward, vec!["/blog/post.md".to_string()]);
+        });
+    }
+
     #[wasm_bindgen_test]
     fn root_mount_status_tracks_root_runtime_loads() {
         let owner = Owner::new();
@@ -717,6 +1145,7 @@ mod tests {
                 remote_heads: BTreeMap::new(),
                 total_files: 0,
                 mounts: failed_mounts,
+                banner: None,
             });
             ctx.mark_mount_failed(&root, "manifest unavailable")
                 .expect("root mount should be declared");
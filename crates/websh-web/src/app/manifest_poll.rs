@@ -0,0 +1,197 @@
+//! Background revalidation of the root manifest.
+//!
+//! When `MANIFEST_POLL` holds an interval in seconds, the root backend is
+//! rescanned on that schedule. The manifest request revalidates against
+//! the HTTP cache (`Cache-Control: no-cache`), so an unchanged manifest
+//! costs a 304. A changed one reloads the runtime, reports the diff in the
+//! terminal and the site chrome, and moves off a route that was removed.
+
+use std::sync::Arc;
+
+use gloo_timers::future::TimeoutFuture;
+use leptos::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+
+use super::{AppContext, RuntimeServices};
+use crate::config::{MANIFEST_POLL_VAR, MIN_MANIFEST_POLL_SECS};
+use crate::features::router::{BuiltinRoute, route_request_needs_system_fs};
+use crate::platform::dom::{current_route_request, replace_request_path};
+use websh_core::domain::VirtualPath;
+use websh_core::filesystem::{RouteRequest, request_target_path, resolve_route};
+use websh_core::ports::{ManifestDiff, ScannedSubtree, diff_manifest_snapshots};
+use websh_core::shell::{AppEventKind, ManifestSync, OutputLine};
+
+/// How often a disabled poller checks whether it was turned on.
+const IDLE_RECHECK_MS: u32 = 60_000;
+
+/// Poll interval in seconds from the variable's value; `None` when polling
+/// is off. Intervals below the minimum are raised to it.
+pub(crate) fn poll_interval_secs(value: Option<&str>) -> Option<u32> {
+    let secs = value?.trim().parse::<u32>().ok()?;
+    (secs > 0).then(|| secs.max(MIN_MANIFEST_POLL_SECS))
+}
+
+/// Start the poller. It runs for the lifetime of the page and re-reads the
+/// interval before every wait, so `export`/`unset` take effect on the next
+/// tick.
+pub fn start_manifest_poll(ctx: AppContext) {
+    spawn_local(async move {
+        let root = VirtualPath::root();
+        let mut baseline: Option<ScannedSubtree> = None;
+        loop {
+            let interval = ctx.runtime_state.with_untracked(|rs| {
+                poll_interval_secs(rs.env.get(MANIFEST_POLL_VAR).map(String::as_str))
+            });
+            let Some(secs) = interval else {
+                baseline = None;
+                TimeoutFuture::new(IDLE_RECHECK_MS).await;
+                continue;
+            };
+
+            if let Some(backend) = ctx.backend_for_mount_root(&root) {
+                match backend.scan().await {
+                    Ok(scan) => {
+                        if let Some(previous) = baseline.replace(scan.clone()) {
+                            let diff = diff_manifest_snapshots(&previous, &scan);
+                            if !diff.is_empty() {
+                                ctx.manifest_sync.set_value(Some(Arc::new(ManifestSync {
+                                    previous,
+                                    current: scan,
+                                })));
+                                apply_manifest_update(ctx, diff).await;
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        leptos::logging::warn!("manifest poll: {error}");
+                        ctx.log_event(AppEventKind::FetchFailed {
+                            target: "manifest poll".to_string(),
+                            error: error.to_string(),
+                        });
+                    }
+                }
+            }
+
+            TimeoutFuture::new(secs.saturating_mul(1_000)).await;
+        }
+    });
+}
+
+async fn apply_manifest_update(ctx: AppContext, diff: ManifestDiff) {
+    let request = current_route_request();
+    let resolved_before = route_resolves(ctx, &request);
+
+    if let Err(error) = RuntimeServices::new(ctx).reload_runtime().await {
+        ctx.terminal.push_output(OutputLine::error(format!(
+            "manifest updated, but reloading failed: {error}"
+        )));
+        return;
+    }
+    ctx.terminal.push_output(OutputLine::info(format!(
+        "manifest updated: {}",
+        diff.summary()
+    )));
+    ctx.log_event(AppEventKind::ManifestSynced {
+        summary: diff.summary(),
+    });
+
+    if resolved_before && removes_route_target(&diff, &request) {
+        let target = nearest_route(&request.url_path, |path| {
+            route_resolves(ctx, &RouteRequest::new(path))
+        });
+        replace_request_path(&target);
+    }
+    ctx.manifest_update.set(Some(diff));
+}
+
+/// Whether `request` names a built-in route or resolves in the loaded tree.
+pub(crate) fn route_resolves(ctx: AppContext, request: &RouteRequest) -> bool {
+    if BuiltinRoute::detect(request).is_some() {
+        return true;
+    }
+    let fs = if route_request_needs_system_fs(request) {
+        ctx.system_global_fs
+    } else {
+        ctx.view_global_fs
+    };
+    fs.with_untracked(|fs| resolve_route(fs, request).is_some())
+}
+
+/// Whether a file removed from the root manifest is, or lies under, the
+/// route's target. Routes into other mounts may not resolve yet while those
+/// mounts rescan, so only removals justify leaving a route.
+fn removes_route_target(diff: &ManifestDiff, request: &RouteRequest) -> bool {
+    let Some((_, target)) = request_target_path(request) else {
+        return false;
+    };
+    diff.removed.iter().any(|path| {
+        VirtualPath::from_absolute(format!("/{path}"))
+            .is_ok_and(|removed| removed.starts_with(&target))
+    })
+}
+
+/// Closest ancestor of `url_path` (itself included) that `resolves`,
+/// falling back to `/`.
+pub(crate) fn nearest_route(url_path: &str, resolves: impl Fn(&str) -> bool) -> String {
+    let mut path = url_path.trim_end_matches('/');
+    while !path.is_empty() {
+        if resolves(path) {
+            return path.to_string();
+        }
+        path = path.rsplit_once('/').map_or("", |(parent, _)| parent);
+    }
+    "/".to_string()
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn poll_interval_is_off_unless_a_positive_number() {
+        assert_eq!(poll_interval_secs(None), None);
+        assert_eq!(poll_interval_secs(Some("")), None);
+        assert_eq!(poll_interval_secs(Some("0")), None);
+        assert_eq!(poll_interval_secs(Some("soon")), None);
+        assert_eq!(poll_interval_secs(Some("300")), Some(300));
+        assert_eq!(poll_interval_secs(Some(" 120 ")), Some(120));
+    }
+
+    #[wasm_bindgen_test]
+    fn poll_interval_is_raised_to_the_minimum() {
+        assert_eq!(poll_interval_secs(Some("1")), Some(MIN_MANIFEST_POLL_SECS));
+    }
+
+    #[wasm_bindgen_test]
+    fn nearest_route_walks_up_to_a_resolving_ancestor() {
+        let resolves = |path: &str| matches!(path, "/websh" | "/blog");
+        assert_eq!(nearest_route("/websh/blog/old", resolves), "/websh");
+        assert_eq!(nearest_route("/blog/", resolves), "/blog");
+        assert_eq!(nearest_route("/gone/deeper", resolves), "/");
+        assert_eq!(nearest_route("/", resolves), "/");
+    }
+
+    #[wasm_bindgen_test]
+    fn only_removals_under_the_route_move_it() {
+        let diff = ManifestDiff {
+            removed: vec!["blog/old/post.md".to_string()],
+            ..ManifestDiff::default()
+        };
+        assert!(removes_route_target(&diff, &RouteRequest::new("/blog/old")));
+        assert!(removes_route_target(
+            &diff,
+            &RouteRequest::new("/websh/blog/old")
+        ));
+        assert!(!removes_route_target(
+            &diff,
+            &RouteRequest::new("/blog/new")
+        ));
+        assert!(!removes_route_target(
+            &ManifestDiff::default(),
+            &RouteRequest::new("/blog/old")
+        ));
+    }
+}
@@ -3,13 +3,22 @@
 mod boot;
 mod context;
 mod editor;
+mod manifest_poll;
+mod nav_history;
 mod ring_buffer;
+mod scrollback;
 mod services;
 mod state;
+mod usage;
 
 pub use boot::App;
 pub use context::AppContext;
 pub use editor::AppEditModal;
+pub(crate) use manifest_poll::route_resolves;
+pub use manifest_poll::start_manifest_poll;
+pub use nav_history::NavHistory;
 pub use ring_buffer::RingBuffer;
+pub use scrollback::ScrollbackArchive;
 pub use services::RuntimeServices;
-pub use state::TerminalState;
+pub use state::{OutputSearch, TerminalState};
+pub use usage::UsageTracker;
@@ -0,0 +1,184 @@
+//! Back/forward stacks of visited routes.
+//!
+//! The router reports every route it lands on; `cd -N` and `dirs` read and
+//! move through the stacks. Browser back/forward show up as a visit of the
+//! neighbouring entry and are folded into a one-step jump.
+
+use websh_core::shell::NavigationHistory;
+
+/// Entries kept on each stack.
+const NAV_HISTORY_LIMIT: usize = 100;
+
+/// Visited route paths around the current one.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NavHistory {
+    /// Oldest first; the top of the stack is the last element.
+    back: Vec<String>,
+    current: Option<String>,
+    /// Farthest first; the top of the stack is the last element.
+    forward: Vec<String>,
+}
+
+impl NavHistory {
+    pub fn current(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    /// Record landing on `route`. The neighbouring back/forward entries are
+    /// treated as a one-step jump; anything else is a new visit, which drops
+    /// the forward stack.
+    pub fn observe(&mut self, route: &str) {
+        if self.current.as_deref() == Some(route) {
+            return;
+        }
+        if self.back.last().map(String::as_str) == Some(route) {
+            self.jump(-1);
+            return;
+        }
+        if self.forward.last().map(String::as_str) == Some(route) {
+            self.jump(1);
+            return;
+        }
+        if let Some(previous) = self.current.replace(route.to_string()) {
+            self.back.push(previous);
+            if self.back.len() > NAV_HISTORY_LIMIT {
+                self.back.remove(0);
+            }
+        }
+        self.forward.clear();
+    }
+
+    /// Move `offset` entries back (negative) or forward, carrying the
+    /// entries passed over to the other stack. Returns the new current
+    /// route, or `None` (leaving the stacks untouched) when the stack in
+    /// that direction is too short.
+    pub fn jump(&mut self, offset: isize) -> Option<&str> {
+        let steps = offset.unsigned_abs();
+        let (from, to) = if offset < 0 {
+            (&mut self.back, &mut self.forward)
+        } else {
+            (&mut self.forward, &mut self.back)
+        };
+        if steps == 0 || steps > from.len() {
+            return None;
+        }
+        let current = self.current.take()?;
+        to.push(current);
+        for _ in 1..steps {
+            to.extend(from.pop());
+        }
+        self.current = from.pop();
+        self.current.as_deref()
+    }
+
+    /// Forget every entry except the current one.
+    pub fn clear(&mut self) {
+        self.back.clear();
+        self.forward.clear();
+    }
+
+    /// The stacks as the shell sees them: nearest entries first.
+    pub fn snapshot(&self) -> NavigationHistory {
+        NavigationHistory {
+            back: self.back.iter().rev().cloned().collect(),
+            current: self.current.clone(),
+            forward: self.forward.iter().rev().cloned().collect(),
+        }
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn visited(routes: &[&str]) -> NavHistory {
+        let mut history = NavHistory::default();
+        for route in routes {
+            history.observe(route);
+        }
+        history
+    }
+
+    fn strings(routes: &[&str]) -> Vec<String> {
+        routes.iter().map(|route| route.to_string()).collect()
+    }
+
+    #[wasm_bindgen_test]
+    fn visits_push_the_previous_route_and_drop_forward() {
+        let mut history = visited(&["/a", "/b", "/c"]);
+        assert_eq!(history.jump(-2), Some("/a"));
+        history.observe("/d");
+        let snapshot = history.snapshot();
+        assert_eq!(snapshot.back, strings(&["/a"]));
+        assert_eq!(snapshot.current.as_deref(), Some("/d"));
+        assert!(snapshot.forward.is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn jump_moves_passed_entries_to_the_other_stack() {
+        let mut history = visited(&["/a", "/b", "/c", "/d"]);
+        assert_eq!(history.jump(-2), Some("/b"));
+        let snapshot = history.snapshot();
+        assert_eq!(snapshot.back, strings(&["/a"]));
+        assert_eq!(snapshot.forward, strings(&["/c", "/d"]));
+
+        assert_eq!(history.jump(2), Some("/d"));
+        let snapshot = history.snapshot();
+        assert_eq!(snapshot.back, strings(&["/c", "/b", "/a"]));
+        assert!(snapshot.forward.is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn jump_to_the_bottom_of_a_stack_and_no_further() {
+        let mut history = visited(&["/a", "/b", "/c"]);
+        assert_eq!(history.jump(-3), None);
+        assert_eq!(history.jump(-2), Some("/a"));
+        assert!(history.snapshot().back.is_empty());
+        assert_eq!(history.jump(-1), None);
+        assert_eq!(history.jump(3), None);
+        assert_eq!(history.jump(0), None);
+        assert_eq!(history.current(), Some("/a"));
+    }
+
+    #[wasm_bindgen_test]
+    fn jump_without_a_current_route_does_nothing() {
+        let mut history = NavHistory::default();
+        assert_eq!(history.jump(-1), None);
+        assert_eq!(history, NavHistory::default());
+    }
+
+    #[wasm_bindgen_test]
+    fn browser_back_and_forward_fold_into_single_steps() {
+        let mut history = visited(&["/a", "/b", "/c"]);
+        history.observe("/b");
+        assert_eq!(history.snapshot().forward, strings(&["/c"]));
+        history.observe("/c");
+        assert_eq!(history.snapshot().back, strings(&["/b", "/a"]));
+        history.observe("/c");
+        assert_eq!(history.snapshot().back, strings(&["/b", "/a"]));
+    }
+
+    #[wasm_bindgen_test]
+    fn clear_keeps_only_the_current_route() {
+        let mut history = visited(&["/a", "/b", "/c"]);
+        history.jump(-1);
+        history.clear();
+        let snapshot = history.snapshot();
+        assert!(snapshot.back.is_empty() && snapshot.forward.is_empty());
+        assert_eq!(snapshot.current.as_deref(), Some("/b"));
+    }
+
+    #[wasm_bindgen_test]
+    fn back_stack_is_capped() {
+        let mut history = NavHistory::default();
+        for index in 0..=NAV_HISTORY_LIMIT + 1 {
+            history.observe(&format!("/{index}"));
+        }
+        let snapshot = history.snapshot();
+        assert_eq!(snapshot.back.len(), NAV_HISTORY_LIMIT);
+        assert_eq!(snapshot.back.last().map(String::as_str), Some("/1"));
+    }
+}
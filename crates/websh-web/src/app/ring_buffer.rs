@@ -11,6 +11,8 @@ pub struct RingBuffer<T> {
     head: usize,
     len: usize,
     capacity: usize,
+    /// Number of elements pushed since creation or the last `clear`.
+    pushed: usize,
 }
 
 impl<T> RingBuffer<T> {
@@ -27,21 +29,24 @@ impl<T> RingBuffer<T> {
             head: 0,
             len: 0,
             capacity,
+            pushed: 0,
         }
     }
 
     /// Adds an element to the back of the buffer. O(1).
     ///
-    /// If at capacity, the oldest element is overwritten.
-    pub fn push(&mut self, item: T) {
+    /// If at capacity, the oldest element is overwritten and returned.
+    pub fn push(&mut self, item: T) -> Option<T> {
         let insert_index = (self.head + self.len) % self.capacity;
-        self.data[insert_index] = Some(item);
+        let evicted = self.data[insert_index].replace(item);
 
         if self.len == self.capacity {
             self.head = (self.head + 1) % self.capacity;
         } else {
             self.len += 1;
         }
+        self.pushed += 1;
+        evicted
     }
 
     /// Extends the buffer with elements from an iterator.
@@ -63,6 +68,16 @@ impl<T> RingBuffer<T> {
         self.data[actual_index].as_ref()
     }
 
+    /// Returns a mutable reference to the element at the given logical index.
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        let actual_index = (self.head + index) % self.capacity;
+        self.data[actual_index].as_mut()
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         self.len
@@ -78,6 +93,35 @@ impl<T> RingBuffer<T> {
         self.capacity
     }
 
+    /// Resizes the buffer to `capacity`, keeping the newest elements.
+    /// Returns the elements that no longer fit, oldest first. Stream
+    /// positions from [`total_pushed`](Self::total_pushed) stay valid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn set_capacity(&mut self, capacity: usize) -> Vec<T> {
+        assert!(capacity > 0, "RingBuffer capacity must be greater than 0");
+        if capacity == self.capacity {
+            return Vec::new();
+        }
+
+        let mut items: Vec<T> = (0..self.len)
+            .filter_map(|index| self.data[(self.head + index) % self.capacity].take())
+            .collect();
+        let evicted: Vec<T> = items.drain(..self.len.saturating_sub(capacity)).collect();
+        self.len = items.len();
+        self.data = items
+            .into_iter()
+            .map(Some)
+            .chain(std::iter::repeat_with(|| None))
+            .take(capacity)
+            .collect();
+        self.head = 0;
+        self.capacity = capacity;
+        evicted
+    }
+
     /// Clears all elements from the buffer.
     pub fn clear(&mut self) {
         for slot in &mut self.data {
@@ -85,6 +129,28 @@ impl<T> RingBuffer<T> {
         }
         self.head = 0;
         self.len = 0;
+        self.pushed = 0;
+    }
+
+    /// Returns the number of elements pushed since creation or the last
+    /// `clear`, including ones already overwritten.
+    ///
+    /// This is the position the next pushed element will occupy, so it can
+    /// be stored as a stable marker into the stream of elements.
+    #[inline]
+    pub fn total_pushed(&self) -> usize {
+        self.pushed
+    }
+
+    /// Returns an iterator over the elements at or after stream `position`
+    /// (as returned by [`total_pushed`](Self::total_pushed)), oldest first.
+    pub fn iter_from(&self, position: usize) -> Iter<'_, T> {
+        let oldest = self.pushed - self.len;
+        Iter {
+            buffer: self,
+            front: position.saturating_sub(oldest).min(self.len),
+            back: self.len,
+        }
     }
 
     /// Returns an iterator over references to the elements (oldest to newest).
@@ -214,6 +280,85 @@ mod tests {
 
     wasm_bindgen_test_configure!(run_in_browser);
 
+    #[wasm_bindgen_test]
+    fn test_iter_from_position() {
+        let mut buffer = RingBuffer::new(5);
+        buffer.extend([1, 2, 3]);
+        let marker = buffer.total_pushed();
+        buffer.extend([4, 5]);
+
+        assert_eq!(
+            buffer.iter_from(marker).copied().collect::<Vec<_>>(),
+            vec![4, 5]
+        );
+        assert_eq!(buffer.iter_from(0).count(), 5);
+        assert_eq!(buffer.iter_from(99).count(), 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_iter_from_after_overflow() {
+        let mut buffer = RingBuffer::new(3);
+        buffer.extend([1, 2]);
+        let marker = buffer.total_pushed();
+        buffer.extend([3, 4, 5, 6]);
+
+        // Marker still points at element 3; it has since been overwritten,
+        // so every remaining element is at or after the marker.
+        assert_eq!(buffer.total_pushed(), 6);
+        assert_eq!(
+            buffer.iter_from(marker).copied().collect::<Vec<_>>(),
+            vec![4, 5, 6]
+        );
+        assert_eq!(buffer.iter_from(5).copied().collect::<Vec<_>>(), vec![6]);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_clear_resets_total_pushed() {
+        let mut buffer = RingBuffer::new(3);
+        buffer.extend([1, 2]);
+        buffer.clear();
+        assert_eq!(buffer.total_pushed(), 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_get_mut_after_overflow() {
+        let mut buffer = RingBuffer::new(3);
+        buffer.extend([1, 2, 3, 4]);
+        *buffer.get_mut(0).unwrap() = 20;
+        assert_eq!(buffer.to_vec(), vec![20, 3, 4]);
+        assert!(buffer.get_mut(3).is_none());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_set_capacity_shrink_drops_oldest() {
+        let mut buffer = RingBuffer::new(4);
+        buffer.extend([1, 2, 3, 4, 5, 6]);
+        let marker = buffer.total_pushed() - 1;
+
+        assert_eq!(buffer.set_capacity(2), vec![3, 4]);
+        assert_eq!(buffer.to_vec(), vec![5, 6]);
+        assert_eq!(buffer.capacity(), 2);
+        assert_eq!(buffer.total_pushed(), 6);
+        assert_eq!(
+            buffer.iter_from(marker).copied().collect::<Vec<_>>(),
+            vec![6]
+        );
+
+        buffer.push(7);
+        assert_eq!(buffer.to_vec(), vec![6, 7]);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_set_capacity_grow_keeps_everything() {
+        let mut buffer = RingBuffer::new(3);
+        buffer.extend([1, 2, 3, 4]);
+
+        assert!(buffer.set_capacity(5).is_empty());
+        assert_eq!(buffer.to_vec(), vec![2, 3, 4]);
+        buffer.extend([5, 6, 7]);
+        assert_eq!(buffer.to_vec(), vec![3, 4, 5, 6, 7]);
+    }
+
     #[wasm_bindgen_test]
     fn test_new_buffer() {
         let buffer: RingBuffer<i32> = RingBuffer::new(5);
@@ -255,6 +400,25 @@ mod tests {
         assert_eq!(buffer.get(2), Some(&5));
     }
 
+    #[wasm_bindgen_test]
+    fn test_push_returns_evicted() {
+        let mut buffer = RingBuffer::new(2);
+        assert_eq!(buffer.push(1), None);
+        assert_eq!(buffer.push(2), None);
+        assert_eq!(buffer.push(3), Some(1));
+        assert_eq!(buffer.push(4), Some(2));
+        assert_eq!(buffer.to_vec(), vec![3, 4]);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_push_after_clear_evicts_nothing() {
+        let mut buffer = RingBuffer::new(2);
+        buffer.extend([1, 2]);
+        buffer.clear();
+        assert_eq!(buffer.push(3), None);
+        assert_eq!(buffer.push(4), None);
+    }
+
     #[wasm_bindgen_test]
     fn test_extend() {
         let mut buffer = RingBuffer::new(3);
@@ -0,0 +1,99 @@
+//! Capped archive for terminal output evicted from the ring buffer.
+
+use std::collections::VecDeque;
+
+/// Lines that scrolled out of the render buffer, oldest first.
+///
+/// Once full, archiving a line drops the oldest archived one.
+#[derive(Clone, Debug)]
+pub struct ScrollbackArchive<T> {
+    lines: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> ScrollbackArchive<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Archives `line` as the newest entry.
+    pub fn push(&mut self, line: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    pub fn extend(&mut self, lines: impl IntoIterator<Item = T>) {
+        for line in lines {
+            self.push(line);
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    /// The newest `count` archived lines, oldest first. These are the
+    /// lines directly above the render buffer.
+    pub fn latest(&self, count: usize) -> impl Iterator<Item = &T> {
+        self.lines
+            .iter()
+            .skip(self.lines.len().saturating_sub(count))
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn drops_oldest_line_when_full() {
+        let mut archive = ScrollbackArchive::new(3);
+        archive.extend([1, 2, 3, 4]);
+        assert_eq!(archive.len(), 3);
+        assert_eq!(
+            archive.latest(3).copied().collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn latest_returns_chunks_nearest_the_buffer() {
+        let mut archive = ScrollbackArchive::new(10);
+        archive.extend(1..=5);
+        assert_eq!(archive.latest(2).copied().collect::<Vec<_>>(), vec![4, 5]);
+        assert_eq!(
+            archive.latest(4).copied().collect::<Vec<_>>(),
+            vec![2, 3, 4, 5]
+        );
+        assert_eq!(archive.latest(99).count(), 5);
+        assert_eq!(archive.latest(0).count(), 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn zero_capacity_archives_nothing() {
+        let mut archive = ScrollbackArchive::new(0);
+        archive.push(1);
+        assert!(archive.is_empty());
+    }
+}
@@ -7,6 +7,7 @@ use websh_core::attestation::ledger::CONTENT_LEDGER_CONTENT_PATH;
 use websh_core::domain::{ChangeSet, VirtualPath, WalletState};
 use websh_core::ports::{CommitOutcome, StorageBackendRef};
 use websh_core::runtime::{self as core_runtime, RuntimeStateSnapshot};
+use websh_core::shell::AppEventKind;
 
 use crate::render::theme;
 use crate::runtime::{drafts, loader, state, storage_state, wallet};
@@ -139,7 +140,12 @@ impl RuntimeServices {
     }
 
     pub(crate) fn apply_successful_root_mount_load(&self, load: RuntimeLoad) -> u64 {
+        let files = load.total_files;
         let generation = self.apply_runtime_load(load);
+        self.ctx.log_event(AppEventKind::MountLoaded {
+            root: VirtualPath::root().to_string(),
+            files,
+        });
         self.start_ledger_prefetch(generation);
         generation
     }
@@ -159,7 +165,12 @@ impl RuntimeServices {
     }
 
     pub(crate) fn apply_failed_root_mount_load(&self, error: impl Into<String>) -> u64 {
+        let error = error.into();
         let generation = self.apply_runtime_load(loader::bootstrap_runtime_load());
+        self.ctx.log_event(AppEventKind::MountFailed {
+            root: VirtualPath::root().to_string(),
+            error: error.clone(),
+        });
         self.mark_root_mount_failed(error);
         generation
     }
@@ -237,6 +248,31 @@ impl RuntimeServices {
         wallet::resolve_ens(address).await
     }
 
+    /// Resolve the avatar for the connected wallet's ENS name in the
+    /// background, once per name. Clears it when there is no name.
+    pub fn refresh_wallet_avatar(&self) {
+        let ctx = self.ctx;
+        let Some(name) = ctx.wallet.with_untracked(ens_name_of) else {
+            ctx.wallet_avatar.set(None);
+            return;
+        };
+        let known = ctx
+            .wallet_avatar
+            .with_untracked(|avatar| avatar.as_ref().is_some_and(|a| a.ens_name == name));
+        if known {
+            return;
+        }
+        spawn_local(async move {
+            let url = wallet::resolve_ens_avatar(&name).await;
+            if ctx.wallet.with_untracked(ens_name_of).as_deref() == Some(name.as_str()) {
+                ctx.wallet_avatar.set(Some(wallet::WalletAvatar {
+                    ens_name: name,
+                    url,
+                }));
+            }
+        });
+    }
+
     pub async fn connect_wallet_with_session(
         &self,
     ) -> Result<wallet::ConnectOutcome, wallet::WalletError> {
@@ -261,6 +297,10 @@ impl RuntimeServices {
             ens_name: None,
             chain_id,
         });
+        self.ctx.log_event(AppEventKind::WalletConnected {
+            address: address.clone(),
+            chain_id,
+        });
 
         let ens_name = self.resolve_wallet_ens(&address).await;
         if ens_name.is_some() {
@@ -285,6 +325,10 @@ impl RuntimeServices {
         chain_id: Option<u64>,
         ens_name: Option<String>,
     ) -> Result<(), EnvironmentError> {
+        self.ctx.log_event(AppEventKind::WalletConnected {
+            address: address.clone(),
+            chain_id,
+        });
         self.ctx.wallet.set(WalletState::Connected {
             address,
             ens_name,
@@ -296,6 +340,7 @@ impl RuntimeServices {
     pub fn disconnect_wallet(&self) -> Result<(), EnvironmentError> {
         self.set_wallet_session(false)?;
         self.ctx.wallet.set(WalletState::Disconnected);
+        self.ctx.log_event(AppEventKind::WalletDisconnected);
         Ok(())
     }
 
@@ -308,15 +353,24 @@ impl RuntimeServices {
         let accounts_listener =
             match wallet::on_accounts_changed(move |account: Option<String>| match account {
                 Some(new_addr) => {
-                    services_for_accounts.ctx.wallet.update(|w| {
-                        if let WalletState::Connected { chain_id, .. } = w {
+                    let ctx = services_for_accounts.ctx;
+                    let mut changed = false;
+                    ctx.wallet.update(|w| {
+                        if let WalletState::Connected {
+                            address, chain_id, ..
+                        } = w
+                        {
+                            changed = *address != new_addr;
                             *w = WalletState::Connected {
-                                address: new_addr,
+                                address: new_addr.clone(),
                                 ens_name: None,
                                 chain_id: *chain_id,
                             };
                         }
                     });
+                    if changed {
+                        ctx.log_event(AppEventKind::AccountChanged { address: new_addr });
+                    }
                 }
                 None => {
                     let _ = services_for_accounts.disconnect_wallet();
@@ -333,11 +387,19 @@ impl RuntimeServices {
         let chain_listener = match wallet::on_chain_changed(move |chain_id_hex: String| {
             let new_chain_id = u64::from_str_radix(chain_id_hex.trim_start_matches("0x"), 16).ok();
 
-            services_for_chain.ctx.wallet.update(|w| {
+            let ctx = services_for_chain.ctx;
+            let mut changed = false;
+            ctx.wallet.update(|w| {
                 if let WalletState::Connected { chain_id, .. } = w {
+                    changed = *chain_id != new_chain_id;
                     *chain_id = new_chain_id;
                 }
             });
+            if changed {
+                ctx.log_event(AppEventKind::ChainChanged {
+                    chain_id: new_chain_id,
+                });
+            }
         }) {
             Ok(listener) => listener,
             Err(error) => {
@@ -498,4 +560,38 @@ mod tests {
             let _ = storage.remove_item(theme::STORAGE_KEY);
         });
     }
+
+    #[wasm_bindgen_test]
+    fn set_theme_rejects_unknown_theme_without_persisting() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let storage = web_sys::window()
+                .and_then(|window| window.local_storage().ok().flatten())
+                .expect("localStorage should be available");
+            let _ = storage.remove_item(theme::STORAGE_KEY);
+
+            let ctx = AppContext::new();
+            let before = ctx.theme.get_untracked();
+            let error = RuntimeServices::new(ctx)
+                .set_theme("neon-pink")
+                .expect_err("unknown theme should be rejected");
+
+            assert!(
+                error.starts_with("unknown theme 'neon-pink'. available: "),
+                "unexpected error: {error}"
+            );
+            for id in theme::theme_ids() {
+                assert!(error.contains(id), "error should list {id}: {error}");
+            }
+            assert_eq!(ctx.theme.get_untracked(), before);
+            assert_eq!(storage.get_item(theme::STORAGE_KEY).unwrap(), None);
+        });
+    }
+}
+
+fn ens_name_of(wallet: &WalletState) -> Option<String> {
+    match wallet {
+        WalletState::Connected { ens_name, .. } => ens_name.clone(),
+        _ => None,
+    }
 }
@@ -3,8 +3,12 @@
 use leptos::prelude::*;
 
 use super::ring_buffer::RingBuffer;
-use crate::config::{MAX_COMMAND_HISTORY, MAX_TERMINAL_HISTORY};
-use websh_core::shell::OutputLine;
+use super::scrollback::ScrollbackArchive;
+use crate::config::{
+    MAX_COMMAND_HISTORY, MAX_SCROLLBACK_ARCHIVE, MAX_TERMINAL_HISTORY, SCROLLBACK_LOAD_CHUNK,
+    SCROLLBACK_RANGE,
+};
+use websh_core::shell::{OutputLine, OutputLineId, Prompt, TutorialProgress, find_output_lines};
 
 // The state container structs in this module derive `Clone` and `Copy`.
 // This is intentional: every field is a Leptos reactive handle (`RwSignal`,
@@ -12,42 +16,177 @@ use websh_core::shell::OutputLine;
 // reactive arena. Copying one of these containers therefore duplicates a
 // handful of pointers, not the underlying state.
 
+/// Output lines to keep for a `SCROLLBACK` value: the default when unset
+/// or not a number, otherwise clamped to [`SCROLLBACK_RANGE`].
+pub(crate) fn scrollback_lines(value: Option<&str>) -> usize {
+    value
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .map_or(MAX_TERMINAL_HISTORY, |lines| {
+            lines.clamp(*SCROLLBACK_RANGE.start(), *SCROLLBACK_RANGE.end())
+        })
+}
+
 /// Terminal state managed with Leptos signals.
 #[derive(Clone, Copy)]
 pub struct TerminalState {
-    /// Terminal output history (bounded by `MAX_TERMINAL_HISTORY`).
+    /// Terminal output history (bounded by `MAX_TERMINAL_HISTORY`, or by
+    /// `SCROLLBACK` once [`set_scrollback`](Self::set_scrollback) applies it).
     pub history: RwSignal<RingBuffer<OutputLine>>,
+    /// Lines evicted from `history`, kept for "load earlier output".
+    pub archive: RwSignal<ScrollbackArchive<OutputLine>>,
+    /// How many of the newest archived lines are rendered above `history`.
+    pub loaded_earlier: RwSignal<usize>,
     /// Command history for up/down navigation.
     pub command_history: RwSignal<Vec<String>>,
     /// Current position in command history (for navigation).
     pub history_index: RwSignal<Option<usize>>,
+    /// Unsubmitted input stashed when history navigation starts; restored
+    /// when navigating back down past the newest entry.
+    pub draft: RwSignal<Option<String>>,
+    /// Output stream position where rendering starts. `clear` moves it past
+    /// the current output so earlier lines stay in `history` but are hidden.
+    pub display_offset: RwSignal<usize>,
+    /// Guided tour position; restored from localStorage at boot.
+    pub tutorial: RwSignal<TutorialProgress>,
+    /// Scrollback search started with `/pattern`, if any.
+    pub output_search: RwSignal<Option<OutputSearch>>,
 }
 
 impl TerminalState {
     pub fn new() -> Self {
         Self {
             history: RwSignal::new(RingBuffer::new(MAX_TERMINAL_HISTORY)),
+            archive: RwSignal::new(ScrollbackArchive::new(MAX_SCROLLBACK_ARCHIVE)),
+            loaded_earlier: RwSignal::new(0),
             command_history: RwSignal::new(Vec::new()),
             history_index: RwSignal::new(None),
+            draft: RwSignal::new(None),
+            display_offset: RwSignal::new(0),
+            tutorial: RwSignal::new(TutorialProgress::default()),
+            output_search: RwSignal::new(None),
         }
     }
 
     pub fn push_output(&self, line: OutputLine) {
-        self.history.update(|h| h.push(line));
+        self.append([line]);
     }
 
     pub fn push_lines(&self, lines: Vec<OutputLine>) {
         if lines.is_empty() {
             return;
         }
+        self.append(lines.into_iter().chain([OutputLine::empty()]));
+    }
+
+    /// Swap the buffered line `id` for `line` in place, for output that
+    /// updates while a command runs. Returns `false` once the line has
+    /// scrolled out of the buffer.
+    pub fn replace_line(&self, id: OutputLineId, line: OutputLine) -> bool {
+        let mut line = Some(line);
         self.history.update(|h| {
-            h.extend(lines);
-            h.push(OutputLine::empty());
+            if let Some(index) = h.iter().position(|existing| existing.id == id)
+                && let Some(slot) = h.get_mut(index)
+            {
+                *slot = line.take().expect("line is replaced once");
+            }
         });
+        line.is_none()
+    }
+
+    /// Resize `history` to keep `lines` output lines. Lines a shrink no
+    /// longer fits move to `archive`, as if they had scrolled out.
+    pub fn set_scrollback(&self, lines: usize) {
+        if self.history.with_untracked(|h| h.capacity()) == lines {
+            return;
+        }
+        let mut evicted = Vec::new();
+        self.history.update(|h| evicted = h.set_capacity(lines));
+        if !evicted.is_empty() {
+            self.archive.update(|a| a.extend(evicted));
+        }
+    }
+
+    /// Push lines into `history`, moving whatever they evict to `archive`.
+    fn append(&self, lines: impl IntoIterator<Item = OutputLine>) {
+        let mut evicted = Vec::new();
+        self.history.update(|h| {
+            evicted.extend(lines.into_iter().filter_map(|line| h.push(line)));
+        });
+        if !evicted.is_empty() {
+            self.archive.update(|a| a.extend(evicted));
+        }
+    }
+
+    /// Abandon the typed line (Ctrl+C): echo it with a `^C` marker and
+    /// leave history navigation.
+    pub fn interrupt(&self, prompt: impl Into<Prompt>, input: &str) {
+        self.push_output(OutputLine::command(prompt, format!("{input}^C")));
+        self.history_index.set(None);
+        self.draft.set(None);
+    }
+
+    /// Hide all current output without discarding it (`clear`).
+    pub fn clear_screen(&self) {
+        self.display_offset
+            .set(self.history.with(|h| h.total_pushed()));
+        self.loaded_earlier.set(0);
+    }
+
+    /// Show output hidden by `clear` again.
+    pub fn show_cleared(&self) {
+        self.display_offset.set(0);
+    }
+
+    /// Number of buffered lines currently hidden by `clear`.
+    pub fn hidden_line_count(&self) -> usize {
+        let offset = self.display_offset.get();
+        self.history.with(|h| h.len() - h.iter_from(offset).len())
+    }
+
+    /// Archived lines that may be shown: those after the `clear` point.
+    fn loadable_earlier(&self) -> usize {
+        let oldest = self.history.with(|h| h.total_pushed() - h.len());
+        let offset = self.display_offset.get();
+        self.archive
+            .with(|a| a.len().min(oldest.saturating_sub(offset)))
+    }
+
+    /// Number of archived lines not yet loaded back above the output.
+    pub fn earlier_line_count(&self) -> usize {
+        self.loadable_earlier()
+            .saturating_sub(self.loaded_earlier.get())
+    }
+
+    /// Reveal the next chunk of archived lines above the output.
+    pub fn load_earlier(&self) {
+        let loadable = self.loadable_earlier();
+        self.loaded_earlier
+            .update(|loaded| *loaded = (*loaded + SCROLLBACK_LOAD_CHUNK).min(loadable));
+    }
+
+    /// Loaded archived lines, oldest first, to render before `history`.
+    pub fn earlier_lines(&self) -> Vec<OutputLine> {
+        let count = self.loaded_earlier.get().min(self.loadable_earlier());
+        self.archive.with(|a| a.latest(count).cloned().collect())
+    }
+
+    /// Every rendered line, oldest first: loaded archived lines, then the
+    /// buffered lines after the `clear` point.
+    pub fn rendered_lines(&self) -> Vec<OutputLine> {
+        let mut lines = self.earlier_lines();
+        let offset = self.display_offset.get();
+        self.history
+            .with(|h| lines.extend(h.iter_from(offset).cloned()));
+        lines
     }
 
+    /// Discard all output (`reset`).
     pub fn clear_history(&self) {
+        self.output_search.set(None);
         self.history.update(|h| h.clear());
+        self.archive.update(|a| a.clear());
+        self.loaded_earlier.set(0);
+        self.display_offset.set(0);
     }
 
     pub fn add_to_command_history(&self, cmd: &str) {
@@ -62,31 +201,436 @@ impl TerminalState {
             });
         }
         self.history_index.set(None);
+        self.draft.set(None);
     }
 
-    pub fn navigate_history(&self, direction: i32) -> Option<String> {
+    /// Step through command history. `current` is the input line as typed;
+    /// it is stashed as the draft when navigation starts.
+    ///
+    /// Returns the new input line, or `None` when it should stay as is.
+    pub fn navigate_history(&self, direction: i32, current: &str) -> Option<String> {
         let current_index = self.history_index.get();
         let (new_index, result) = self.command_history.with(|history| {
             if history.is_empty() {
                 return (None, None);
             }
-            let new_index = match current_index {
-                None if direction < 0 => Some(history.len() - 1),
-                Some(i) if direction < 0 && i > 0 => Some(i - 1),
-                Some(i) if direction > 0 && i < history.len() - 1 => Some(i + 1),
-                Some(_) if direction > 0 => None,
-                _ => current_index,
-            };
-            let result = new_index.map(|i| history[i].clone());
-            (new_index, result)
+            match current_index {
+                None if direction < 0 => {
+                    let newest = history.len() - 1;
+                    (Some(newest), Some(history[newest].clone()))
+                }
+                Some(i) if direction < 0 => {
+                    let i = i.saturating_sub(1);
+                    (Some(i), Some(history[i].clone()))
+                }
+                Some(i) if direction > 0 && i < history.len() - 1 => {
+                    (Some(i + 1), Some(history[i + 1].clone()))
+                }
+                Some(_) if direction > 0 => (None, Some(self.draft.get().unwrap_or_default())),
+                _ => (current_index, None),
+            }
         });
+        if current_index.is_none() && new_index.is_some() {
+            self.draft.set(Some(current.to_string()));
+        } else if new_index.is_none() {
+            self.draft.set(None);
+        }
         self.history_index.set(new_index);
         result
     }
 }
 
+impl TerminalState {
+    /// Reverse incremental search over command history (Ctrl+R).
+    ///
+    /// Returns the newest entry containing `query` that is older than
+    /// `before` (or the newest overall when `before` is `None`), along with
+    /// its history index.
+    pub fn search_history(&self, query: &str, before: Option<usize>) -> Option<(usize, String)> {
+        self.command_history.with(|history| {
+            reverse_search(history, query, before).map(|idx| (idx, history[idx].clone()))
+        })
+    }
+}
+
+impl TerminalState {
+    /// Search the rendered scrollback for `query` (`/pattern`), starting at
+    /// the newest match. Returns the number of matching lines.
+    pub fn search_output(&self, query: &str) -> usize {
+        let lines = self.rendered_lines();
+        let search = OutputSearch::new(query, &lines);
+        let found = search.matches.len();
+        self.output_search.set(Some(search));
+        found
+    }
+
+    /// Move to the next older (`older`) or newer match (n / N). Returns
+    /// `false` when no search is open.
+    pub fn step_output_search(&self, older: bool) -> bool {
+        let mut open = false;
+        self.output_search.update(|search| {
+            if let Some(search) = search {
+                search.step(older);
+                open = true;
+            }
+        });
+        open
+    }
+
+    /// Close the scrollback search, dropping its highlights.
+    pub fn end_output_search(&self) {
+        self.output_search.set(None);
+    }
+}
+
+/// A search over the terminal scrollback: the lines matching `query` and
+/// the one currently scrolled to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutputSearch {
+    pub query: String,
+    /// Matching lines, oldest first.
+    pub matches: Vec<OutputLineId>,
+    /// Index into `matches` of the current line.
+    pub current: usize,
+}
+
+impl OutputSearch {
+    /// Search `lines`, positioned on the newest match.
+    pub fn new(query: &str, lines: &[OutputLine]) -> Self {
+        let matches = find_output_lines(lines, query);
+        Self {
+            query: query.to_string(),
+            current: matches.len().saturating_sub(1),
+            matches,
+        }
+    }
+
+    /// The line currently scrolled to.
+    pub fn current_line(&self) -> Option<OutputLineId> {
+        self.matches.get(self.current).copied()
+    }
+
+    pub fn is_match(&self, id: OutputLineId) -> bool {
+        self.matches.contains(&id)
+    }
+
+    /// Step one match older or newer, wrapping around at either end.
+    pub fn step(&mut self, older: bool) {
+        let len = self.matches.len();
+        if len == 0 {
+            return;
+        }
+        self.current = if older {
+            (self.current + len - 1) % len
+        } else {
+            (self.current + 1) % len
+        };
+    }
+}
+
+/// Find the newest entry before `before` that contains `query`.
+///
+/// Entries identical to the one at `before` are skipped so repeated Ctrl+R
+/// presses always move to a different command.
+fn reverse_search(history: &[String], query: &str, before: Option<usize>) -> Option<usize> {
+    if query.is_empty() {
+        return None;
+    }
+    let end = before.unwrap_or(history.len()).min(history.len());
+    let current = before.and_then(|idx| history.get(idx));
+    history[..end]
+        .iter()
+        .rposition(|cmd| cmd.contains(query) && Some(cmd) != current)
+}
+
 impl Default for TerminalState {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn history() -> Vec<String> {
+        ["ls -l", "cd /blog", "cat post.md", "ls", "cd /blog"]
+            .iter()
+            .map(|cmd| cmd.to_string())
+            .collect()
+    }
+
+    fn visible(state: &TerminalState) -> usize {
+        let offset = state.display_offset.get();
+        state.history.with(|h| h.iter_from(offset).count())
+    }
+
+    #[wasm_bindgen_test]
+    fn clear_screen_keeps_lines_in_buffer() {
+        let _owner = Owner::new();
+        let state = TerminalState::new();
+        state.push_output(OutputLine::text("one"));
+        state.push_output(OutputLine::text("two"));
+
+        state.clear_screen();
+        assert_eq!(state.history.with(|h| h.len()), 2);
+        assert_eq!(visible(&state), 0);
+        assert_eq!(state.hidden_line_count(), 2);
+
+        state.push_output(OutputLine::text("three"));
+        assert_eq!(state.history.with(|h| h.len()), 3);
+        assert_eq!(visible(&state), 1);
+
+        state.show_cleared();
+        assert_eq!(visible(&state), 3);
+        assert_eq!(state.hidden_line_count(), 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn interrupt_echoes_input_and_resets_navigation() {
+        let _owner = Owner::new();
+        let state = TerminalState::new();
+        state.add_to_command_history("ls");
+        assert_eq!(state.navigate_history(-1, "").as_deref(), Some("ls"));
+
+        state.interrupt("guest@websh:~", "cat dra");
+        assert_eq!(state.history_index.get(), None);
+        let echoed = state
+            .history
+            .with(|h| h.iter().next_back().cloned())
+            .unwrap();
+        assert_eq!(echoed.to_plain_text(), "guest@websh:~$ cat dra^C");
+        assert_eq!(state.command_history.with(|h| h.len()), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn navigating_back_down_restores_draft() {
+        let _owner = Owner::new();
+        let state = TerminalState::new();
+        state.add_to_command_history("ls");
+        state.add_to_command_history("cd /blog");
+
+        assert_eq!(
+            state.navigate_history(-1, "cat dra").as_deref(),
+            Some("cd /blog")
+        );
+        assert_eq!(
+            state.navigate_history(-1, "cd /blog").as_deref(),
+            Some("ls")
+        );
+        assert_eq!(state.navigate_history(-1, "ls").as_deref(), Some("ls"));
+        assert_eq!(state.navigate_history(1, "ls").as_deref(), Some("cd /blog"));
+        assert_eq!(
+            state.navigate_history(1, "cd /blog").as_deref(),
+            Some("cat dra")
+        );
+        assert_eq!(state.history_index.get(), None);
+        assert_eq!(state.draft.get(), None);
+        assert_eq!(state.navigate_history(1, "cat dra"), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn submitting_discards_draft() {
+        let _owner = Owner::new();
+        let state = TerminalState::new();
+        state.add_to_command_history("ls");
+        state.navigate_history(-1, "half typed");
+        state.add_to_command_history("ls");
+
+        assert_eq!(state.draft.get(), None);
+        assert_eq!(state.navigate_history(-1, "").as_deref(), Some("ls"));
+        assert_eq!(state.navigate_history(1, "ls").as_deref(), Some(""));
+    }
+
+    #[wasm_bindgen_test]
+    fn replace_line_swaps_buffered_line() {
+        let _owner = Owner::new();
+        let state = TerminalState::new();
+        let progress = OutputLine::info("working… (0/2)");
+        let id = progress.id;
+        state.push_output(progress);
+        state.push_output(OutputLine::text("after"));
+
+        let done = OutputLine::info("done");
+        let done_id = done.id;
+        assert!(state.replace_line(id, done));
+        assert_eq!(
+            state.history.with(|h| h.get(0).map(|line| line.id)),
+            Some(done_id)
+        );
+        assert!(!state.replace_line(id, OutputLine::info("stale")));
+        assert_eq!(state.history.with(|h| h.len()), 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn clear_history_discards_lines() {
+        let _owner = Owner::new();
+        let state = TerminalState::new();
+        state.push_output(OutputLine::text("one"));
+        state.clear_screen();
+
+        state.clear_history();
+        assert_eq!(state.history.with(|h| h.len()), 0);
+        assert_eq!(state.display_offset.get(), 0);
+        assert_eq!(state.hidden_line_count(), 0);
+    }
+
+    fn fill(state: &TerminalState, count: usize) {
+        for idx in 0..count {
+            state.push_output(OutputLine::text(format!("line {idx}")));
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn evicted_lines_move_to_archive() {
+        let _owner = Owner::new();
+        let state = TerminalState::new();
+        fill(&state, MAX_TERMINAL_HISTORY + 3);
+
+        assert_eq!(state.history.with(|h| h.len()), MAX_TERMINAL_HISTORY);
+        assert_eq!(state.archive.with(|a| a.len()), 3);
+        assert_eq!(state.earlier_line_count(), 3);
+        assert!(state.earlier_lines().is_empty());
+
+        state.load_earlier();
+        let texts: Vec<_> = state
+            .earlier_lines()
+            .iter()
+            .map(|line| line.to_plain_text())
+            .collect();
+        assert_eq!(texts, vec!["line 0", "line 1", "line 2"]);
+        assert_eq!(state.earlier_line_count(), 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn scrollback_values_are_clamped() {
+        assert_eq!(scrollback_lines(None), MAX_TERMINAL_HISTORY);
+        assert_eq!(scrollback_lines(Some("lots")), MAX_TERMINAL_HISTORY);
+        assert_eq!(scrollback_lines(Some(" 2500 ")), 2500);
+        assert_eq!(scrollback_lines(Some("5")), *SCROLLBACK_RANGE.start());
+        assert_eq!(scrollback_lines(Some("99999999")), *SCROLLBACK_RANGE.end());
+    }
+
+    #[wasm_bindgen_test]
+    fn shrinking_scrollback_keeps_the_newest_lines() {
+        let _owner = Owner::new();
+        let state = TerminalState::new();
+        fill(&state, 300);
+
+        state.set_scrollback(100);
+        let lines = state.history.with(|h| h.to_vec());
+        assert_eq!(lines.len(), 100);
+        assert_eq!(lines[0].to_plain_text(), "line 200");
+        assert_eq!(lines[99].to_plain_text(), "line 299");
+        assert_eq!(state.archive.with(|a| a.len()), 200);
+
+        fill(&state, 1);
+        assert_eq!(state.history.with(|h| h.len()), 100);
+        assert_eq!(state.archive.with(|a| a.len()), 201);
+    }
+
+    #[wasm_bindgen_test]
+    fn load_earlier_reveals_one_chunk_at_a_time() {
+        let _owner = Owner::new();
+        let state = TerminalState::new();
+        let archived = SCROLLBACK_LOAD_CHUNK + 5;
+        fill(&state, MAX_TERMINAL_HISTORY + archived);
+
+        state.load_earlier();
+        let chunk = state.earlier_lines();
+        assert_eq!(chunk.len(), SCROLLBACK_LOAD_CHUNK);
+        assert_eq!(chunk[0].to_plain_text(), "line 5");
+        assert_eq!(state.earlier_line_count(), 5);
+
+        state.load_earlier();
+        assert_eq!(state.earlier_lines().len(), archived);
+        assert_eq!(state.earlier_lines()[0].to_plain_text(), "line 0");
+    }
+
+    #[wasm_bindgen_test]
+    fn cleared_archive_lines_are_not_offered() {
+        let _owner = Owner::new();
+        let state = TerminalState::new();
+        fill(&state, 2);
+        state.clear_screen();
+        fill(&state, MAX_TERMINAL_HISTORY);
+
+        // The two cleared lines were evicted; they stay hidden.
+        assert_eq!(state.archive.with(|a| a.len()), 2);
+        assert_eq!(state.earlier_line_count(), 0);
+
+        state.show_cleared();
+        assert_eq!(state.earlier_line_count(), 2);
+
+        state.clear_history();
+        assert_eq!(state.archive.with(|a| a.len()), 0);
+        assert_eq!(state.earlier_line_count(), 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn reverse_search_finds_newest_match() {
+        assert_eq!(reverse_search(&history(), "ls", None), Some(3));
+        assert_eq!(reverse_search(&history(), "post", None), Some(2));
+        assert_eq!(reverse_search(&history(), "rm", None), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn reverse_search_cycles_to_older_matches() {
+        assert_eq!(reverse_search(&history(), "ls", Some(3)), Some(0));
+        assert_eq!(reverse_search(&history(), "ls", Some(0)), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn reverse_search_skips_duplicates_of_current_match() {
+        assert_eq!(reverse_search(&history(), "cd", None), Some(4));
+        assert_eq!(reverse_search(&history(), "cd", Some(4)), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn reverse_search_ignores_empty_query() {
+        assert_eq!(reverse_search(&history(), "", None), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn output_search_starts_at_newest_match_and_wraps() {
+        let lines = [
+            OutputLine::text("rust notes"),
+            OutputLine::empty(),
+            OutputLine::text("python notes"),
+            OutputLine::text("Rust again"),
+        ];
+        let mut search = OutputSearch::new("rust", &lines);
+        assert_eq!(search.matches, vec![lines[0].id, lines[3].id]);
+        assert_eq!(search.current_line(), Some(lines[3].id));
+
+        search.step(true);
+        assert_eq!(search.current_line(), Some(lines[0].id));
+        search.step(true);
+        assert_eq!(search.current_line(), Some(lines[3].id));
+        search.step(false);
+        assert_eq!(search.current_line(), Some(lines[0].id));
+        assert!(!search.is_match(lines[2].id));
+    }
+
+    #[wasm_bindgen_test]
+    fn output_search_covers_rendered_lines_only() {
+        let _owner = Owner::new();
+        let state = TerminalState::new();
+        state.push_output(OutputLine::text("old match"));
+        state.clear_screen();
+        state.push_output(OutputLine::text("new match"));
+        state.push_output(OutputLine::empty());
+
+        assert_eq!(state.search_output("match"), 1);
+        assert!(state.step_output_search(true));
+        state.end_output_search();
+        assert!(!state.step_output_search(true));
+
+        let mut empty = OutputSearch::new("zzz", &[]);
+        empty.step(false);
+        assert_eq!(empty.current_line(), None);
+    }
+}
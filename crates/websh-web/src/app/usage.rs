@@ -0,0 +1,133 @@
+//! Usage counters behind `stats`: this session's in memory, every
+//! session's in localStorage. Nothing is sent anywhere.
+
+use websh_core::shell::{UsageReport, UsageStats};
+
+use crate::config::USAGE_STATS_KEY;
+
+/// The session and all-time counters, with the time base for each.
+#[derive(Clone, Debug, Default)]
+pub struct UsageTracker {
+    all_time: UsageStats,
+    session: UsageStats,
+    /// All-time seconds before counting (re)started.
+    earlier_seconds: u64,
+    /// Page uptime, in seconds, when counting (re)started.
+    started_at: u64,
+}
+
+impl UsageTracker {
+    /// Continue from `all_time`, with this session starting now.
+    pub fn resume(all_time: UsageStats) -> Self {
+        Self {
+            earlier_seconds: all_time.seconds,
+            all_time,
+            ..Self::default()
+        }
+    }
+
+    /// Count into both spans, then stamp their time at `now` (page uptime
+    /// in seconds).
+    pub fn record(&mut self, now: u64, count: impl Fn(&mut UsageStats)) {
+        count(&mut self.session);
+        count(&mut self.all_time);
+        self.stamp(now);
+    }
+
+    pub fn report(&mut self, now: u64) -> UsageReport {
+        self.stamp(now);
+        UsageReport {
+            all_time: self.all_time.clone(),
+            session: self.session.clone(),
+        }
+    }
+
+    /// Zero both spans; time counts from `now` again.
+    pub fn reset(&mut self, now: u64) {
+        *self = Self {
+            started_at: now,
+            ..Self::default()
+        };
+    }
+
+    pub fn all_time(&self) -> &UsageStats {
+        &self.all_time
+    }
+
+    fn stamp(&mut self, now: u64) {
+        let elapsed = now.saturating_sub(self.started_at);
+        self.session.seconds = elapsed;
+        self.all_time.seconds = self.earlier_seconds.saturating_add(elapsed);
+    }
+}
+
+/// The stored all-time counters; empty on a first visit.
+pub fn load_usage_stats() -> UsageStats {
+    local_storage()
+        .and_then(|storage| storage.get_item(USAGE_STATS_KEY).ok().flatten())
+        .map(|json| UsageStats::from_json(&json))
+        .unwrap_or_default()
+}
+
+pub fn save_usage_stats(stats: &UsageStats) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.set_item(USAGE_STATS_KEY, &stats.to_json());
+    }
+}
+
+pub fn clear_usage_stats() {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(USAGE_STATS_KEY);
+    }
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn records_into_the_session_and_all_time() {
+        let mut earlier = UsageStats::default();
+        earlier.record_command("ls");
+        earlier.seconds = 600;
+        let mut tracker = UsageTracker::resume(earlier);
+
+        tracker.record(30, |stats| stats.record_command("ls"));
+        tracker.record(45, |stats| stats.record_file("/blog/a.md"));
+        let report = tracker.report(90);
+        assert_eq!(report.session.total_commands(), 1);
+        assert_eq!(report.all_time.total_commands(), 2);
+        assert_eq!(report.all_time.top_files(1), vec![("/blog/a.md", 1)]);
+        assert_eq!(report.session.seconds, 90);
+        assert_eq!(report.all_time.seconds, 690);
+    }
+
+    #[wasm_bindgen_test]
+    fn reset_zeroes_both_spans_and_restarts_the_clock() {
+        let mut tracker = UsageTracker::resume(UsageStats::default());
+        tracker.record(10, |stats| stats.record_command("ls"));
+        tracker.reset(100);
+        let report = tracker.report(130);
+        assert!(report.all_time.is_empty());
+        assert!(report.session.is_empty());
+        assert_eq!(report.session.seconds, 30);
+        assert_eq!(report.all_time.seconds, 30);
+    }
+
+    #[wasm_bindgen_test]
+    fn stored_counters_round_trip_through_local_storage() {
+        let mut stats = UsageStats::default();
+        stats.record_dir("~/blog");
+        save_usage_stats(&stats);
+        assert_eq!(load_usage_stats(), stats);
+        clear_usage_stats();
+        assert_eq!(load_usage_stats(), UsageStats::default());
+    }
+}
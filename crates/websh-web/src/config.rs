@@ -15,9 +15,40 @@ pub const APP_TAGLINE: &str = websh_site::APP_TAGLINE;
 /// Fetch request timeout in milliseconds.
 pub const FETCH_TIMEOUT_MS: i32 = 10000;
 
+/// Timeout for a content manifest, which can be large and gates the mount.
+pub const MANIFEST_FETCH_TIMEOUT_MS: i32 = 20000;
+
+/// Timeout for probing whether a bundled asset is deployed.
+pub const PROBE_FETCH_TIMEOUT_MS: i32 = 3000;
+
+/// Variable holding how many times a fetch that timed out or hit a network
+/// error is retried.
+pub const FETCH_RETRIES_VAR: &str = "FETCH_RETRIES";
+
+/// Retries when `FETCH_RETRIES` is unset or not a number.
+pub const DEFAULT_FETCH_RETRIES: u32 = 2;
+
+/// Most retries honoured; larger `FETCH_RETRIES` values are clamped.
+pub const MAX_FETCH_RETRIES: u32 = 5;
+
+/// Wait before the first retry, in milliseconds; it doubles per retry.
+pub const FETCH_RETRY_BASE_MS: i32 = 500;
+
+/// Longest wait between retries, in milliseconds.
+pub const FETCH_RETRY_MAX_MS: i32 = 8000;
+
 /// localStorage key for wallet session persistence.
 pub const WALLET_SESSION_KEY: &str = "websh.wallet_session";
 
+/// localStorage key holding how many tutorial steps the visitor completed.
+pub const TUTORIAL_PROGRESS_KEY: &str = "websh.tutorial";
+
+/// localStorage key holding the all-time `stats` counters.
+pub const USAGE_STATS_KEY: &str = "websh.stats";
+
+/// localStorage key holding the `session` snapshots and the autosave.
+pub const SESSION_SNAPSHOTS_KEY: &str = "websh.sessions";
+
 /// Wallet connection timeout in milliseconds.
 pub const WALLET_TIMEOUT_MS: i32 = 2000;
 
@@ -25,18 +56,82 @@ pub const WALLET_TIMEOUT_MS: i32 = 2000;
 pub const USER_VAR_PREFIX: &str = "user.";
 
 /// Default user variables initialized on first visit.
-/// THEME is omitted: the theme system writes `user.THEME` directly.
-pub const DEFAULT_USER_VARS: &[(&str, &str)] = &[("LANG", "en"), ("EDITOR", "vim")];
+/// THEME is omitted: the theme system writes `user.THEME` directly. LANG is
+/// detected from the browser language instead.
+pub const DEFAULT_USER_VARS: &[(&str, &str)] = &[("EDITOR", "vim")];
 
-/// Maximum number of terminal output lines to keep in history.
+/// Terminal output lines kept in history unless `SCROLLBACK` says otherwise.
 pub const MAX_TERMINAL_HISTORY: usize = 1000;
 
+/// Variable holding the number of output lines the terminal keeps.
+pub const SCROLLBACK_VAR: &str = "SCROLLBACK";
+
+/// Range of `SCROLLBACK` values honoured; others are clamped into it.
+pub const SCROLLBACK_RANGE: std::ops::RangeInclusive<usize> = 100..=10_000;
+
+/// Maximum number of output lines kept after they leave the terminal
+/// history, for "load earlier output".
+pub const MAX_SCROLLBACK_ARCHIVE: usize = 10_000;
+
+/// Archived output lines restored per "load earlier output" click.
+pub const SCROLLBACK_LOAD_CHUNK: usize = 200;
+
+/// Maximum number of app events kept for `dmesg`.
+pub const MAX_APP_EVENTS: usize = 256;
+
 /// Maximum number of command history entries to keep.
 pub const MAX_COMMAND_HISTORY: usize = 100;
 
+/// Maximum number of files listed by `search`.
+pub const SEARCH_RESULT_LIMIT: usize = 10;
+
+/// Filename used when a terminal transcript is downloaded instead of copied.
+pub const SESSION_TRANSCRIPT_FILENAME: &str = "websh-session.txt";
+
+/// Filename used when `feed -d` downloads the Atom document.
+pub const FEED_FILENAME: &str = "feed.xml";
+
+/// Files `export-zip` reads at once.
+pub const EXPORT_ZIP_CONCURRENCY: usize = 4;
+
+/// Archive size above which `export-zip` asks before downloading (50 MB).
+pub const EXPORT_ZIP_CONFIRM_BYTES: u64 = 50_000_000;
+
+/// Variable holding the manifest poll interval in seconds; unset or `0`
+/// turns polling off.
+pub const MANIFEST_POLL_VAR: &str = "MANIFEST_POLL";
+
+/// Shortest manifest poll interval honoured, in seconds.
+pub const MIN_MANIFEST_POLL_SECS: u32 = 30;
+
+/// Variable listing extra hosts `fetch` may reach, separated by commas or
+/// spaces, on top of the redirect allowlist.
+pub const FETCH_ALLOW_VAR: &str = "FETCH_ALLOW";
+
+/// Variable choosing the reader's PDF viewer: `pdfjs` opens PDFs in the
+/// pdf.js build bundled at [`PDFJS_VIEWER_PATH`] when it is deployed;
+/// unset or anything else embeds the browser's built-in viewer.
+pub const PDF_VIEWER_VAR: &str = "PDF_VIEWER";
+
+/// Site-relative path of the bundled pdf.js viewer page.
+pub const PDFJS_VIEWER_PATH: &str = "assets/vendor/pdfjs/web/viewer.html";
+
 /// Milliseconds per second for time formatting.
 pub const MS_PER_SECOND: f64 = 1000.0;
 
+/// Variable that types boot lines out character by character when `on`;
+/// unset or `off` prints them at once.
+pub const BOOT_TYPING_VAR: &str = "BOOT_TYPING";
+
+/// Variable holding the boot typing speed in characters per second.
+pub const BOOT_TYPING_SPEED_VAR: &str = "BOOT_TYPING_SPEED";
+
+/// Boot typing speed when `BOOT_TYPING_SPEED` is unset or not a number.
+pub const DEFAULT_BOOT_TYPING_SPEED: u32 = 120;
+
+/// Range of `BOOT_TYPING_SPEED` values honoured; others are clamped into it.
+pub const BOOT_TYPING_SPEED_RANGE: std::ops::RangeInclusive<u32> = 10..=2_000;
+
 /// Boot sequence animation delay constants (milliseconds).
 pub mod boot_delays {
     /// Delay after kernel init message.
@@ -45,4 +140,7 @@ pub mod boot_delays {
     pub const WASM_RUNTIME: i32 = 20;
     /// Delay after boot complete message.
     pub const BOOT_COMPLETE: i32 = 40;
+    /// Shortest pause between typed chunks; faster speeds type several
+    /// characters per chunk.
+    pub const MIN_TYPING_TICK: i32 = 16;
 }
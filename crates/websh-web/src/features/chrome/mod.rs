@@ -6,18 +6,26 @@
 
 use leptos::ev;
 use leptos::prelude::*;
+use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::spawn_local;
 
 use crate::app::AppContext;
 use crate::app::RuntimeServices;
 use crate::config::APP_NAME;
 use crate::features::ledger::routes::is_ledger_filter_route_segment;
+use crate::platform::dom::push_route;
 use crate::render::theme::THEMES;
+use crate::shared::components::breadcrumb::{
+    BreadcrumbLevel, BreadcrumbSegment, route_breadcrumb_segments,
+};
 use crate::shared::components::{MonoOverflow, MonoValue};
-use websh_core::domain::{VirtualPath, WalletState};
+use websh_core::domain::{DirEntry, WalletState};
 use websh_core::filesystem::{
-    RouteFrame, RouteSurface, request_path_for_canonical_path, route_cwd,
+    RouteFrame, RouteSurface, content_href_for_path, display_path_for,
+    request_path_for_canonical_path, route_cwd, route_for_user_path, shell_route_for_frame,
 };
+use websh_core::shell::{AutocompleteResult, autocomplete_path};
+use websh_core::support::i18n::Message;
 
 stylance::import_crate_style!(css, "src/features/chrome/site_chrome.module.css");
 
@@ -34,6 +42,8 @@ pub struct SiteChromeBreadcrumbItem {
     pub label: String,
     pub href: Option<String>,
     pub current: bool,
+    /// Filesystem level of a path segment; its separator lists siblings.
+    pub level: Option<BreadcrumbLevel>,
 }
 
 impl SiteChromeBreadcrumbItem {
@@ -42,6 +52,7 @@ impl SiteChromeBreadcrumbItem {
             label: label.into(),
             href: Some(href.into()),
             current: false,
+            level: None,
         }
     }
 
@@ -50,10 +61,51 @@ impl SiteChromeBreadcrumbItem {
             label: label.into(),
             href: None,
             current: true,
+            level: None,
+        }
+    }
+
+    pub fn with_level(mut self, level: BreadcrumbLevel) -> Self {
+        self.level = Some(level);
+        self
+    }
+}
+
+impl From<BreadcrumbSegment> for SiteChromeBreadcrumbItem {
+    fn from(segment: BreadcrumbSegment) -> Self {
+        let item = match segment.target {
+            Some(target) => Self::link(segment.label, route_href(&target.url_path)),
+            None => Self::current(segment.label),
+        };
+        Self {
+            level: segment.level,
+            ..item
         }
     }
 }
 
+/// One entry of a breadcrumb separator's sibling dropdown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SiblingLink {
+    label: String,
+    href: String,
+    current: bool,
+}
+
+/// Directories among `entries` (a listing of `level.parent`), linked on
+/// `level.surface`, with `level.path` marked current.
+fn sibling_links(entries: &[DirEntry], level: &BreadcrumbLevel) -> Vec<SiblingLink> {
+    entries
+        .iter()
+        .filter(|entry| entry.is_dir)
+        .map(|entry| SiblingLink {
+            label: entry.name.clone(),
+            href: route_href(&request_path_for_canonical_path(&entry.path, level.surface)),
+            current: entry.path == level.path,
+        })
+        .collect()
+}
+
 #[component]
 pub fn SiteChromeRoot(surface: SiteChromeSurface, children: Children) -> impl IntoView {
     let surface_class = match surface {
@@ -85,13 +137,23 @@ pub fn SiteChrome(route: Memo<RouteFrame>) -> impl IntoView {
             .unwrap_or("")
             .to_string()
     });
+    Effect::new(move |_| {
+        if route.with(|frame| frame.surface() == RouteSurface::Content) {
+            ctx.mark_left_terminal();
+        }
+    });
+    // From a content page the terminal opens in the directory on screen.
     let websh_href = Signal::derive(move || {
         let frame = route.get();
-        let cwd = match frame.surface() {
-            RouteSurface::Shell => route_cwd(&frame),
-            RouteSurface::Content => VirtualPath::root(),
-        };
-        route_href(&request_path_for_canonical_path(&cwd, RouteSurface::Shell))
+        match frame.surface() {
+            RouteSurface::Shell => route_href(&request_path_for_canonical_path(
+                &route_cwd(&frame),
+                RouteSurface::Shell,
+            )),
+            RouteSurface::Content => ctx
+                .view_global_fs
+                .with(|fs| route_href(&shell_route_for_frame(fs, &frame).url_path)),
+        }
     });
     let websh_active = Signal::derive(move || {
         let frame = route.get();
@@ -104,7 +166,7 @@ pub fn SiteChrome(route: Memo<RouteFrame>) -> impl IntoView {
                 <SiteChromeIdentity label=APP_NAME href=identity_href />
                 <SiteChromeWalletButton />
             </SiteChromeLead>
-            <SiteChromeBreadcrumb items=breadcrumbs />
+            <SiteChromePathBar route=route items=breadcrumbs />
             <SiteChromeActions>
                 <SiteChromeNav>
                     <SiteChromeSiteNavItems
@@ -116,10 +178,61 @@ pub fn SiteChrome(route: Memo<RouteFrame>) -> impl IntoView {
                 <SiteChromeDivider />
                 <SiteChromePalettePicker theme=theme />
             </SiteChromeActions>
+            <SiteChromeManifestNotice />
         </SiteChromeRoot>
     }
 }
 
+/// Files added to a notice before the rest collapse into a count.
+const MANIFEST_NOTICE_ITEMS: usize = 5;
+
+/// Toast for a manifest change picked up in the background, linking the
+/// files it added.
+#[component]
+fn SiteChromeManifestNotice() -> impl IntoView {
+    let ctx = use_context::<AppContext>().expect("AppContext must be provided");
+    let dismiss = move || ctx.manifest_update.set(None);
+
+    move || {
+        ctx.manifest_update.get().map(|diff| {
+            let more = diff.added.len().saturating_sub(MANIFEST_NOTICE_ITEMS);
+            let items = diff
+                .added
+                .iter()
+                .take(MANIFEST_NOTICE_ITEMS)
+                .map(|path| {
+                    let href = content_href_for_path(path);
+                    let label = path.clone();
+                    view! {
+                        <li>
+                            <a href=href on:click=move |_| dismiss()>{label}</a>
+                        </li>
+                    }
+                })
+                .collect_view();
+            view! {
+                <div class=css::manifestNotice role="status">
+                    <div class=css::manifestNoticeHead>
+                        <span>{format!("manifest updated: {}", diff.summary())}</span>
+                        <button
+                            type="button"
+                            class=css::manifestNoticeDismiss
+                            aria-label="dismiss"
+                            on:click=move |_| dismiss()
+                        >
+                            "×"
+                        </button>
+                    </div>
+                    <ul class=css::manifestNoticeItems>
+                        {items}
+                        {(more > 0).then(|| view! { <li>{format!("… and {more} more")}</li> })}
+                    </ul>
+                </div>
+            }
+        })
+    }
+}
+
 #[component]
 pub fn SiteChromeIdentity(label: &'static str, href: Signal<String>) -> impl IntoView {
     view! {
@@ -138,17 +251,50 @@ pub fn SiteChromeLead(children: Children) -> impl IntoView {
 pub fn SiteChromeBreadcrumb(
     items: Signal<Vec<SiteChromeBreadcrumbItem>>,
     #[prop(optional, default = "path")] aria_label: &'static str,
+    /// Called when the breadcrumb is clicked outside its links.
+    #[prop(optional)]
+    on_edit: Option<Callback<()>>,
 ) -> impl IntoView {
+    let ctx = use_context::<AppContext>().expect("AppContext must be provided");
+    // Index of the item whose separator has its sibling dropdown open.
+    let open_siblings = RwSignal::new(None::<usize>);
+    let on_click = move |ev: ev::MouseEvent| {
+        let Some(on_edit) = on_edit else {
+            return;
+        };
+        let on_control = ev
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+            .and_then(|element| element.closest("a, button").ok().flatten())
+            .is_some();
+        if !on_control {
+            on_edit.run(());
+        }
+    };
+
     view! {
-        <nav class=css::breadcrumb aria-label=aria_label>
+        <nav
+            class=css::breadcrumb
+            class=(css::breadcrumbEditable, on_edit.is_some())
+            aria-label=aria_label
+            title=on_edit.map(|_| "Click to go to a path")
+            on:click=on_click
+        >
             {move || {
                 items
                     .get()
                     .into_iter()
                     .enumerate()
                     .map(|(idx, item)| {
-                        let separator = (idx > 0).then(|| view! {
-                            <span class=css::separator aria-hidden="true">"/"</span>
+                        let separator = (idx > 0).then(|| match item.level.clone() {
+                            Some(level) => view! {
+                                <SiblingSeparator ctx=ctx index=idx level=level open=open_siblings />
+                            }
+                            .into_any(),
+                            None => view! {
+                                <span class=css::separator aria-hidden="true">"/"</span>
+                            }
+                            .into_any(),
                         });
                         let class_name = if item.current {
                             css::crumbCurrent.to_string()
@@ -177,6 +323,258 @@ pub fn SiteChromeBreadcrumb(
     }
 }
 
+/// A breadcrumb separator that opens a dropdown of the directories beside
+/// the following segment.
+#[component]
+fn SiblingSeparator(
+    ctx: AppContext,
+    index: usize,
+    level: BreadcrumbLevel,
+    open: RwSignal<Option<usize>>,
+) -> impl IntoView {
+    let is_open = move || open.get() == Some(index);
+    let close = move || open.set(None);
+    let toggle = move |_| open.update(|open| *open = (*open != Some(index)).then_some(index));
+    let level = StoredValue::new(level);
+    let siblings = move || {
+        level.with_value(|level| {
+            let entries = ctx
+                .view_global_fs
+                .with(|fs| fs.list_dir(&level.parent))
+                .unwrap_or_default();
+            sibling_links(&entries, level)
+        })
+    };
+
+    view! {
+        <span class=css::siblings>
+            <button
+                type="button"
+                class=css::separatorButton
+                title="Sibling directories"
+                aria-haspopup="menu"
+                aria-expanded=move || is_open().to_string()
+                on:click=toggle
+                on:keydown=move |ev: ev::KeyboardEvent| {
+                    if ev.key() == "Escape" {
+                        close();
+                    }
+                }
+            >
+                "/"
+            </button>
+            <Show when=is_open>
+                <button
+                    class=css::paletteDismiss
+                    type="button"
+                    aria-label="Close sibling directories"
+                    on:click=move |_| close()
+                ></button>
+                <div class=css::siblingMenu role="menu">
+                    {move || {
+                        let links = siblings();
+                        if links.is_empty() {
+                            return view! {
+                                <span class=css::siblingEmpty>"no directories"</span>
+                            }
+                            .into_any();
+                        }
+                        links
+                            .into_iter()
+                            .map(|link| {
+                                view! {
+                                    <a
+                                        href=link.href
+                                        role="menuitem"
+                                        class=css::siblingLink
+                                        class=(css::siblingCurrent, link.current)
+                                        aria-current=link.current.then_some("location")
+                                        on:click=move |_| close()
+                                    >
+                                        {link.label}
+                                    </a>
+                                }
+                            })
+                            .collect_view()
+                            .into_any()
+                    }}
+                </div>
+            </Show>
+        </span>
+    }
+}
+
+/// The route breadcrumb, editable as a "go to path" input.
+///
+/// Clicking the breadcrumb (or Cmd/Ctrl+Shift+G, and Ctrl+L outside the
+/// shell where it clears the terminal) swaps in an input holding the current
+/// path. Enter resolves it the way `cd` and `cat` do: directories open on
+/// the current surface, files in the reader. Tab completes, Escape cancels.
+#[component]
+fn SiteChromePathBar(
+    route: Memo<RouteFrame>,
+    items: Signal<Vec<SiteChromeBreadcrumbItem>>,
+) -> impl IntoView {
+    let ctx = use_context::<AppContext>().expect("AppContext must be provided");
+    let editing = RwSignal::new(false);
+    let draft = RwSignal::new(String::new());
+    let error = RwSignal::new(None::<String>);
+    let matches = RwSignal::new(Vec::<String>::new());
+    let input_ref = NodeRef::<leptos::html::Input>::new();
+
+    let start_editing = move || {
+        let frame = route.get_untracked();
+        let current = if frame.is_file() {
+            frame.resolution.node_path.clone()
+        } else {
+            route_cwd(&frame)
+        };
+        draft.set(display_path_for(&current));
+        error.set(None);
+        matches.set(Vec::new());
+        editing.set(true);
+    };
+    let stop_editing = move || {
+        editing.set(false);
+        error.set(None);
+        matches.set(Vec::new());
+    };
+
+    let shortcut = window_event_listener(ev::keydown, move |ev| {
+        let surface = route.with_untracked(RouteFrame::surface);
+        if is_path_bar_shortcut(&ev, surface) {
+            ev.prevent_default();
+            start_editing();
+        }
+    });
+    on_cleanup(move || shortcut.remove());
+
+    Effect::new(move |_| {
+        if editing.get()
+            && let Some(input) = input_ref.get()
+        {
+            let _ = input.focus();
+            input.select();
+        }
+    });
+
+    let go = move || {
+        let frame = route.get_untracked();
+        let raw = draft.get_untracked();
+        let result = ctx.view_global_fs.with_untracked(|fs| {
+            route_for_user_path(fs, &route_cwd(&frame), raw.trim(), frame.surface())
+        });
+        match result {
+            Ok(target) => {
+                stop_editing();
+                push_route(&target);
+            }
+            Err(err) => error.set(Some(err.to_string())),
+        }
+    };
+
+    let complete = move || {
+        let frame = route.get_untracked();
+        let raw = draft.get_untracked();
+        let result = ctx
+            .view_global_fs
+            .with_untracked(|fs| autocomplete_path(&raw, &route_cwd(&frame), fs));
+        match result {
+            AutocompleteResult::Single(path) => {
+                draft.set(path);
+                matches.set(Vec::new());
+            }
+            AutocompleteResult::Multiple(common, names) => {
+                draft.set(common);
+                matches.set(names);
+            }
+            AutocompleteResult::None => matches.set(Vec::new()),
+        }
+    };
+
+    let on_keydown = move |ev: ev::KeyboardEvent| match ev.key().as_str() {
+        "Enter" => {
+            ev.prevent_default();
+            go();
+        }
+        "Tab" => {
+            ev.prevent_default();
+            complete();
+        }
+        "Escape" => {
+            ev.prevent_default();
+            stop_editing();
+        }
+        _ => {}
+    };
+
+    let on_edit = Callback::new(move |()| start_editing());
+
+    view! {
+        <Show
+            when=move || editing.get()
+            fallback=move || view! { <SiteChromeBreadcrumb items=items on_edit=on_edit /> }
+        >
+            <div class=css::breadcrumb>
+                <div class=css::pathEdit>
+                    <input
+                        node_ref=input_ref
+                        class=css::pathInput
+                        class=(css::pathInputError, move || error.with(Option::is_some))
+                        type="text"
+                        spellcheck="false"
+                        autocomplete="off"
+                        aria-label="Go to path"
+                        aria-invalid=move || error.with(Option::is_some).to_string()
+                        prop:value=move || draft.get()
+                        on:input=move |ev| {
+                            draft.set(event_target_value(&ev));
+                            error.set(None);
+                            matches.set(Vec::new());
+                        }
+                        on:keydown=on_keydown
+                        on:blur=move |_| stop_editing()
+                    />
+                    {move || {
+                        error
+                            .get()
+                            .map(|message| {
+                                view! { <span class=css::pathError role="alert">{message}</span> }
+                                    .into_any()
+                            })
+                            .or_else(|| {
+                                let names = matches.get();
+                                (!names.is_empty())
+                                    .then(|| {
+                                        view! {
+                                            <span class=css::pathMatches>{names.join("  ")}</span>
+                                        }
+                                            .into_any()
+                                    })
+                            })
+                    }}
+                </div>
+            </div>
+        </Show>
+    }
+}
+
+/// Cmd/Ctrl+Shift+G opens the path bar anywhere; Ctrl+L only outside the
+/// shell, where the terminal keeps it for clearing the screen.
+fn is_path_bar_shortcut(ev: &ev::KeyboardEvent, surface: RouteSurface) -> bool {
+    let key = ev.key().to_ascii_lowercase();
+    let modifier = ev.ctrl_key() || ev.meta_key();
+    if modifier && ev.shift_key() && !ev.alt_key() && key == "g" {
+        return true;
+    }
+    ev.ctrl_key()
+        && !ev.shift_key()
+        && !ev.alt_key()
+        && !ev.meta_key()
+        && key == "l"
+        && !matches!(surface, RouteSurface::Shell)
+}
+
 #[component]
 pub fn SiteChromeActions(children: Children) -> impl IntoView {
     view! {
@@ -243,7 +641,10 @@ pub fn SiteChromeSiteNavItems(
 }
 
 #[component]
-pub fn SiteChromeChip(label: &'static str, value: Signal<String>) -> impl IntoView {
+pub fn SiteChromeChip(
+    #[prop(into)] label: Signal<&'static str>,
+    value: Signal<String>,
+) -> impl IntoView {
     view! {
         <span class=css::chip>
             <span class=css::chipKey>{label}</span>
@@ -274,14 +675,32 @@ pub fn SiteChromeWalletButton() -> impl IntoView {
     let (open, set_open) = signal(false);
 
     let session = Signal::derive(move || ctx.wallet.with(|w| w.display_name()));
+    // Track the ENS name; the avatar loads in the background and the chip
+    // stays text-only until (and unless) it arrives.
+    Effect::new(move |_| {
+        ctx.wallet.track();
+        RuntimeServices::new(ctx).refresh_wallet_avatar();
+    });
+    let avatar = Signal::derive(move || {
+        ctx.wallet_avatar
+            .with(|avatar| avatar.as_ref().and_then(|avatar| avatar.url.clone()))
+    });
+    let avatar_failed = move |_| {
+        ctx.wallet_avatar.update(|avatar| {
+            if let Some(avatar) = avatar {
+                avatar.url = None;
+            }
+        });
+    };
     let network = Signal::derive(move || {
         ctx.wallet.with(|wallet| {
             wallet
                 .chain_id()
                 .map(|id| websh_core::domain::chain_name(id).to_ascii_lowercase())
-                .unwrap_or_else(|| "offline".to_string())
+                .unwrap_or_else(|| Message::StatusOffline.text(ctx.locale.get()).to_string())
         })
     });
+    let label = move |message: Message| Signal::derive(move || message.text(ctx.locale.get()));
 
     let toggle = move |ev: ev::MouseEvent| {
         ev.stop_propagation();
@@ -306,8 +725,18 @@ pub fn SiteChromeWalletButton() -> impl IntoView {
                 on:click=toggle
                 on:keydown=trigger_keydown
             >
-                <SiteChromeChip label="session" value=session />
-                <SiteChromeChip label="network" value=network />
+                {move || avatar.get().map(|url| view! {
+                    <img
+                        class=css::walletAvatar
+                        src=url
+                        alt=""
+                        width="14"
+                        height="14"
+                        on:error=avatar_failed
+                    />
+                })}
+                <SiteChromeChip label=label(Message::StatusSession) value=session />
+                <SiteChromeChip label=label(Message::StatusNetwork) value=network />
             </button>
             <Show when=move || open.get()>
                 <button
@@ -354,28 +783,30 @@ fn SiteChromeWalletMenu(set_open: WriteSignal<bool>) -> impl IntoView {
 
     view! {
         <div class=css::walletMenu aria-label="Wallet" on:click=stop_inside on:keydown=close_on_escape>
-            {move || ctx.wallet.with(|state| match state {
+            {move || {
+                let locale = ctx.locale.get();
+                ctx.wallet.with(|state| match state {
                 WalletState::Disconnected => view! {
                     <button
                         class=css::walletMenuItem
                         type="button"
                         on:click=on_connect
                     >
-                        "connect wallet"
+                        {Message::WalletConnect.text(locale)}
                     </button>
                 }.into_any(),
                 WalletState::Connecting => view! {
-                    <span class=css::walletMenuStatus>"connecting…"</span>
+                    <span class=css::walletMenuStatus>{Message::WalletConnecting.text(locale)}</span>
                 }.into_any(),
                 WalletState::Connected { address, ens_name, chain_id } => {
                     let address_full = address.clone();
                     let ens = ens_name.clone();
                     let chain = chain_id
                         .map(|id| format!("{} · chain {}", websh_core::domain::chain_name(id).to_ascii_lowercase(), id))
-                        .unwrap_or_else(|| "no chain".to_string());
+                        .unwrap_or_else(|| Message::WalletNoChain.text(locale).to_string());
                     view! {
                         <div class=css::walletMenuRow>
-                            <span class=css::walletMenuKey>"address"</span>
+                            <span class=css::walletMenuKey>{Message::WalletAddress.text(locale)}</span>
                             <MonoValue
                                 value=address_full.clone()
                                 overflow=MonoOverflow::Middle { head: 10, tail: 8 }
@@ -389,7 +820,7 @@ fn SiteChromeWalletMenu(set_open: WriteSignal<bool>) -> impl IntoView {
                             </div>
                         })}
                         <div class=css::walletMenuRow>
-                            <span class=css::walletMenuKey>"network"</span>
+                            <span class=css::walletMenuKey>{Message::StatusNetwork.text(locale)}</span>
                             <span class=css::walletMenuVal>{chain}</span>
                         </div>
                         <span class=css::walletMenuDivider aria-hidden="true"></span>
@@ -398,11 +829,12 @@ fn SiteChromeWalletMenu(set_open: WriteSignal<bool>) -> impl IntoView {
                             type="button"
                             on:click=on_disconnect
                         >
-                            "disconnect"
+                            {Message::WalletDisconnect.text(locale)}
                         </button>
                     }.into_any()
                 }
-            })}
+                })
+            }}
         </div>
     }
 }
@@ -494,85 +926,71 @@ pub fn SiteChromePalettePicker(theme: RwSignal<&'static str>) -> impl IntoView {
 }
 
 fn route_breadcrumb_items(frame: &RouteFrame) -> Vec<SiteChromeBreadcrumbItem> {
-    match frame.surface() {
-        RouteSurface::Content => content_breadcrumb_items(frame),
-        RouteSurface::Shell => surface_breadcrumb_items("websh", RouteSurface::Shell, frame),
-    }
+    route_breadcrumb_segments(frame)
+        .into_iter()
+        .map(SiteChromeBreadcrumbItem::from)
+        .collect()
 }
 
-fn content_breadcrumb_items(frame: &RouteFrame) -> Vec<SiteChromeBreadcrumbItem> {
-    if frame.request.url_path == "/ledger" {
-        return canonical_breadcrumb_items(&VirtualPath::root(), RouteSurface::Content, None);
+fn route_href(path: &str) -> String {
+    if path == "/" {
+        HOME_HREF.to_string()
+    } else {
+        format!("#{path}")
     }
-
-    let path = VirtualPath::from_absolute(frame.request.url_path.clone()).unwrap_or_else(|_| {
-        if frame.is_file() {
-            frame.resolution.node_path.clone()
-        } else {
-            route_cwd(frame)
-        }
-    });
-    canonical_breadcrumb_items(&path, RouteSurface::Content, None)
 }
 
-fn surface_breadcrumb_items(
-    label: &'static str,
-    surface: RouteSurface,
-    frame: &RouteFrame,
-) -> Vec<SiteChromeBreadcrumbItem> {
-    canonical_breadcrumb_items(&route_cwd(frame), surface, Some(label))
-}
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+    use websh_core::domain::VirtualPath;
 
-fn canonical_breadcrumb_items(
-    path: &VirtualPath,
-    surface: RouteSurface,
-    surface_label: Option<&'static str>,
-) -> Vec<SiteChromeBreadcrumbItem> {
-    let mut items = Vec::new();
+    wasm_bindgen_test_configure!(run_in_browser);
 
-    if path.is_root() && surface_label.is_none() {
-        items.push(SiteChromeBreadcrumbItem::current("~"));
-        return items;
+    fn vp(path: &str) -> VirtualPath {
+        VirtualPath::from_absolute(path).unwrap()
     }
 
-    items.push(SiteChromeBreadcrumbItem::link("~", HOME_HREF));
-
-    if let Some(label) = surface_label {
-        if path.is_root() {
-            items.push(SiteChromeBreadcrumbItem::current(label));
-            return items;
+    fn entry(path: &str, is_dir: bool) -> DirEntry {
+        let path = vp(path);
+        DirEntry {
+            name: path.file_name().unwrap_or_default().to_string(),
+            path,
+            is_dir,
+            title: String::new(),
+            meta: None,
+            masked: false,
         }
-        items.push(SiteChromeBreadcrumbItem::link(
-            label,
-            route_href(&request_path_for_canonical_path(
-                &VirtualPath::root(),
-                surface,
-            )),
-        ));
     }
 
-    let segments = path.segments().collect::<Vec<_>>();
-    for idx in 0..segments.len() {
-        let label = segments[idx];
-        if idx + 1 == segments.len() {
-            items.push(SiteChromeBreadcrumbItem::current(label));
-        } else {
-            let path = VirtualPath::from_absolute(format!("/{}", segments[..=idx].join("/")))
-                .expect("route path");
-            items.push(SiteChromeBreadcrumbItem::link(
-                label,
-                route_href(&request_path_for_canonical_path(&path, surface)),
-            ));
-        }
+    #[wasm_bindgen_test]
+    fn siblings_are_directories_with_the_segment_marked() {
+        let level = BreadcrumbLevel {
+            parent: vp("/"),
+            path: vp("/blog"),
+            surface: RouteSurface::Content,
+        };
+        let entries = [
+            entry("/blog", true),
+            entry("/about.md", false),
+            entry("/work", true),
+        ];
+        let links = sibling_links(&entries, &level);
+        let labels: Vec<_> = links.iter().map(|link| link.label.as_str()).collect();
+        assert_eq!(labels, ["blog", "work"]);
+        assert!(links[0].current && !links[1].current);
+        assert_eq!(links[1].href, "#/work");
     }
 
-    items
-}
-
-fn route_href(path: &str) -> String {
-    if path == "/" {
-        HOME_HREF.to_string()
-    } else {
-        format!("#{path}")
+    #[wasm_bindgen_test]
+    fn shell_siblings_link_to_the_shell_surface() {
+        let level = BreadcrumbLevel {
+            parent: vp("/"),
+            path: vp("/blog"),
+            surface: RouteSurface::Shell,
+        };
+        let links = sibling_links(&[entry("/work", true)], &level);
+        assert_eq!(links[0].href, "#/websh/work");
     }
 }
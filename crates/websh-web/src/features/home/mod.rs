@@ -300,7 +300,9 @@ fn RecentFeed() -> impl IntoView {
         if root_content_readiness(ctx) != RootContentReadiness::Loaded {
             return Vec::new();
         }
-        ctx.view_global_fs.with(|fs| recent_items_from_fs(fs))
+        let wallet = ctx.wallet.get();
+        ctx.view_global_fs
+            .with(|fs| recent_items_from_fs(fs, &wallet))
     });
 
     view! {
@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-use websh_core::domain::VirtualPath;
+use websh_core::domain::{VirtualPath, WalletState};
 use websh_core::filesystem::{GlobalFs, content_href_for_path};
 
 pub(super) const TOC_ITEMS: &[TocItem] = &[
@@ -202,12 +202,14 @@ fn toc_countable_file(path: &VirtualPath) -> bool {
     )
 }
 
-pub(super) fn recent_items_from_fs(fs: &GlobalFs) -> Vec<RecentItem> {
+/// Newest dated items across the content categories, as `wallet` may list
+/// them. Masked entries are skipped too: a feed row is a link.
+pub(super) fn recent_items_from_fs(fs: &GlobalFs, wallet: &WalletState) -> Vec<RecentItem> {
     let mut items = Vec::new();
 
     for root in ["papers", "projects", "writing", "talks"] {
         let path = VirtualPath::from_absolute(format!("/{root}")).expect("constant category path");
-        collect_recent_items(fs, &path, &mut items);
+        collect_recent_items(fs, wallet, &path, &mut items);
     }
 
     items.sort_by(|left, right| {
@@ -221,14 +223,22 @@ pub(super) fn recent_items_from_fs(fs: &GlobalFs) -> Vec<RecentItem> {
     items
 }
 
-fn collect_recent_items(fs: &GlobalFs, path: &VirtualPath, out: &mut Vec<RecentItem>) {
-    let Some(entries) = fs.list_dir(path) else {
+fn collect_recent_items(
+    fs: &GlobalFs,
+    wallet: &WalletState,
+    path: &VirtualPath,
+    out: &mut Vec<RecentItem>,
+) {
+    let Some(entries) = fs.list_dir_for(path, wallet) else {
         return;
     };
 
     for entry in entries {
+        if entry.masked {
+            continue;
+        }
         if entry.is_dir {
-            collect_recent_items(fs, &entry.path, out);
+            collect_recent_items(fs, wallet, &entry.path, out);
             continue;
         }
 
@@ -284,6 +294,7 @@ fn category_label_for_path(path: &str) -> Option<String> {
 #[cfg(all(test, target_arch = "wasm32"))]
 mod tests {
     use super::*;
+    use leptos::prelude::*;
     use wasm_bindgen_test::*;
 
     wasm_bindgen_test_configure!(run_in_browser);
@@ -359,12 +370,13 @@ text = "also ignored"
                 },
             ],
             directories: Vec::new(),
+            banner: None,
         };
         let mut fs = GlobalFs::empty();
         fs.mount_scanned_subtree(VirtualPath::root(), &snapshot)
             .expect("mount snapshot");
 
-        let items = recent_items_from_fs(&fs);
+        let items = recent_items_from_fs(&fs, &WalletState::Disconnected);
         assert_eq!(items.len(), 2);
         assert_eq!(items[0].kind, "paper");
         assert_eq!(items[0].href, "#/papers/tabula");
@@ -372,6 +384,72 @@ text = "also ignored"
         assert_eq!(items[1].kind, "project");
     }
 
+    #[wasm_bindgen_test]
+    fn recent_items_reveal_restricted_entries_to_their_recipient() {
+        use websh_core::domain::{
+            AccessFilter, EntryExtensions, Fields, ListingVisibility, NodeKind, NodeMetadata,
+            Recipient, SCHEMA_VERSION,
+        };
+        use websh_core::ports::{ScannedFile, ScannedSubtree};
+
+        let make_meta = |listing: Option<ListingVisibility>| NodeMetadata {
+            schema: SCHEMA_VERSION,
+            kind: NodeKind::Page,
+            authored: Fields {
+                date: Some("2026-04-26".to_string()),
+                access: listing.map(|listing| AccessFilter {
+                    recipients: vec![Recipient {
+                        address: "0xabc".to_string(),
+                    }],
+                    listing,
+                }),
+                ..Fields::default()
+            },
+            derived: Fields::default(),
+        };
+        let snapshot = ScannedSubtree {
+            files: vec![
+                ScannedFile {
+                    path: "writing/public.md".to_string(),
+                    meta: make_meta(None),
+                    extensions: EntryExtensions::default(),
+                },
+                ScannedFile {
+                    path: "writing/masked.md".to_string(),
+                    meta: make_meta(Some(ListingVisibility::Masked)),
+                    extensions: EntryExtensions::default(),
+                },
+                ScannedFile {
+                    path: "writing/hidden.md".to_string(),
+                    meta: make_meta(Some(ListingVisibility::Hidden)),
+                    extensions: EntryExtensions::default(),
+                },
+            ],
+            directories: Vec::new(),
+            banner: None,
+        };
+        let mut fs = GlobalFs::empty();
+        fs.mount_scanned_subtree(VirtualPath::root(), &snapshot)
+            .expect("mount snapshot");
+
+        let wallet = RwSignal::new(WalletState::Disconnected);
+        let hrefs = Memo::new(move |_| {
+            let wallet = wallet.get();
+            recent_items_from_fs(&fs, &wallet)
+                .into_iter()
+                .map(|item| item.href)
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(hrefs.get_untracked(), ["#/writing/public"]);
+
+        wallet.set(WalletState::Connected {
+            address: "0xABC".to_string(),
+            ens_name: None,
+            chain_id: None,
+        });
+        assert_eq!(hrefs.get_untracked().len(), 3);
+    }
+
     #[wasm_bindgen_test]
     fn toc_counts_visible_content_files_under_each_directory() {
         use websh_core::domain::{EntryExtensions, Fields, NodeKind, NodeMetadata, SCHEMA_VERSION};
@@ -418,6 +496,7 @@ text = "also ignored"
                 },
             ],
             directories: Vec::new(),
+            banner: None,
         };
         let mut fs = GlobalFs::empty();
         fs.mount_scanned_subtree(VirtualPath::root(), &snapshot)
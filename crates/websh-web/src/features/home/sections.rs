@@ -3,10 +3,11 @@
 use gloo_timers::callback::Timeout;
 use leptos::ev;
 use leptos::prelude::*;
-use wasm_bindgen_futures::{JsFuture, spawn_local};
+use wasm_bindgen_futures::spawn_local;
 
 use crate::config::{APP_NAME, APP_VERSION};
 use crate::platform::breakpoints::{BP_SM, use_min_width};
+use crate::platform::dom::copy_to_clipboard;
 use crate::shared::components::{AttestationSigFooter, MonoOverflow, MonoTone, MonoValue};
 use websh_core::crypto::ack::{
     AckMembershipProof, AckReceipt, normalize_ack_name, public_proof_for_name, short_hash,
@@ -117,21 +118,6 @@ fn PublicKeyAppendix() -> impl IntoView {
     }
 }
 
-async fn copy_to_clipboard(text: &str) -> Result<(), String> {
-    let Some(window) = web_sys::window() else {
-        return Err("window not available".to_string());
-    };
-    let clipboard = window.navigator().clipboard();
-    JsFuture::from(clipboard.write_text(text))
-        .await
-        .map(|_| ())
-        .map_err(|error| {
-            error
-                .as_string()
-                .unwrap_or_else(|| "clipboard write failed".to_string())
-        })
-}
-
 fn public_key_block_header_line(line: &str) -> bool {
     matches!(
         line,
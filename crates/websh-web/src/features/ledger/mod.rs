@@ -10,7 +10,10 @@ use crate::features::chrome::SiteChrome;
 use crate::features::mempool::{
     LedgerFilterShape, Mempool, build_mempool_model, load_mempool_files,
 };
+use crate::features::reader::css as reader_css;
+use crate::render::render_markdown;
 use crate::runtime::MountLoadStatus;
+use crate::shared::components::markdown::MarkdownView;
 use crate::shared::components::{
     AttestationSigFooter, IdentifierStrip, MetaRow, MetaTable, MonoOverflow, MonoTone, MonoValue,
     SiteContentFrame, SiteSurface,
@@ -117,12 +120,75 @@ pub fn LedgerPage(route: Memo<RouteFrame>) -> impl IntoView {
                         })
                     }}
                 </Suspense>
+                <LedgerReadme route=route />
                 <AttestationSigFooter route=attestation_route show_pending=Signal::derive(|| true) />
             </SiteContentFrame>
         </SiteSurface>
     }
 }
 
+/// The listed directory's README, rendered below the chain. The file is
+/// fetched when the directory changes, not on every route update, and the
+/// text cache keeps revisits free. Narrow screens fold it behind a toggle.
+#[component]
+fn LedgerReadme(route: Memo<RouteFrame>) -> impl IntoView {
+    let ctx = use_context::<AppContext>().expect("AppContext must be provided");
+    let readme_path = Memo::new(move |_| {
+        let dir = route.with(|frame| frame.resolution.node_path.clone());
+        ctx.view_global_fs.with(|fs| fs.readme_for(&dir))
+    });
+    let readme = LocalResource::new(move || {
+        let path = readme_path.get();
+        async move {
+            let path = path?;
+            Some(
+                ctx.read_text(&path)
+                    .await
+                    .map(|markdown| render_markdown(&markdown))
+                    .map_err(|error| format!("{}: {error}", path.as_str())),
+            )
+        }
+    });
+    let expanded = RwSignal::new(false);
+
+    move || {
+        readme.get().flatten().map(|result| {
+            let body = match result {
+                Ok(rendered) => {
+                    let rendered = Signal::derive(move || rendered.clone());
+                    view! { <MarkdownView rendered=rendered class=reader_css::mdBody /> }.into_any()
+                }
+                Err(error) => view! {
+                    <p class=css::readmeError>{format!("README unavailable — {error}")}</p>
+                }
+                .into_any(),
+            };
+            view! {
+                <section
+                    class=move || {
+                        if expanded.get() {
+                            format!("{} {}", css::readme, css::readmeExpanded)
+                        } else {
+                            css::readme.to_string()
+                        }
+                    }
+                    aria-label="About this folder"
+                >
+                    <button
+                        type="button"
+                        class=css::readmeToggle
+                        aria-expanded=move || expanded.get().to_string()
+                        on:click=move |_| expanded.update(|open| *open = !*open)
+                    >
+                        "About this folder"
+                    </button>
+                    <div class=css::readmeBody>{body}</div>
+                </section>
+            }
+        })
+    }
+}
+
 #[component]
 fn LedgerIdentifier(model: LedgerModel) -> impl IntoView {
     view! {
@@ -1,20 +1,46 @@
+use leptos::prelude::*;
+use web_sys::AbortSignal;
+
 use crate::app::AppContext;
-use crate::platform::redirect::{UrlValidation, validate_redirect_url};
+use crate::config::PDF_VIEWER_VAR;
+use crate::platform::FetchProgress;
+use crate::platform::redirect::{
+    AUTO_REDIRECT_VAR, RedirectAction, UrlValidation, redirect_action, validate_redirect_url,
+};
 use crate::platform::{BrowserAssetUrl, object_url_for_bytes};
+use crate::render::display::parse_toggle;
 use crate::render::{RenderedMarkdown, render_markdown, rendered_from_html, sanitize_html};
 use websh_core::domain::VirtualPath;
 use websh_core::support::asset::data_url_for_bytes;
 
 use super::ReaderIntent;
+use super::views::PdfViewer;
+use super::views::pdf::bundled_viewer_available;
 
 #[derive(Clone)]
 pub(super) enum RendererContent {
     Markdown(RenderedMarkdown),
     Html(RenderedMarkdown),
     Text(String),
-    Pdf { url: BrowserAssetUrl },
-    Image { url: String },
+    Pdf {
+        url: BrowserAssetUrl,
+        viewer: PdfViewer,
+    },
+    Image {
+        url: String,
+    },
+    Audio {
+        url: BrowserAssetUrl,
+    },
+    Video {
+        url: BrowserAssetUrl,
+    },
     Redirecting,
+    /// Cross-origin `.link` target awaiting the visitor's go-ahead.
+    RedirectConfirm {
+        url: String,
+        host: String,
+    },
 }
 
 #[derive(Clone)]
@@ -27,29 +53,42 @@ pub(super) async fn load_reader_document(
     ctx: AppContext,
     path: VirtualPath,
     intent: ReaderIntent,
+    progress: RwSignal<Option<FetchProgress>>,
+    signal: Option<AbortSignal>,
 ) -> Result<ReaderDocument, String> {
+    let signal = signal.as_ref();
     let content = match intent {
         ReaderIntent::Markdown { .. } => {
             let markdown = ctx
-                .read_text(&path)
+                .read_text_with_progress(&path, progress, signal)
                 .await
                 .map_err(|error| error.to_string())?;
             return Ok(ReaderDocument {
-                content: RendererContent::Markdown(render_markdown(&markdown)),
+                content: RendererContent::Markdown(ctx.render_cached(
+                    &path,
+                    &markdown,
+                    render_markdown,
+                )),
                 raw_source: Some(markdown),
             });
         }
         ReaderIntent::Html { .. } => ctx
-            .read_text(&path)
+            .read_text_with_progress(&path, progress, signal)
             .await
-            .map(|html| RendererContent::Html(rendered_from_html(sanitize_html(&html))))
+            .map(|html| {
+                RendererContent::Html(
+                    ctx.render_cached(&path, &html, |html| rendered_from_html(sanitize_html(html))),
+                )
+            })
             .map_err(|error| error.to_string())?,
         ReaderIntent::Plain { .. } => ctx
-            .read_text(&path)
+            .read_text_with_progress(&path, progress, signal)
             .await
             .map(RendererContent::Text)
             .map_err(|error| error.to_string())?,
-        ReaderIntent::Asset { media_type, .. } => load_asset(ctx, &path, media_type).await?,
+        ReaderIntent::Asset { media_type, .. } => {
+            load_asset(ctx, &path, media_type, progress, signal).await?
+        }
         ReaderIntent::Redirect { .. } => load_redirect(ctx, &path).await?,
     };
 
@@ -63,31 +102,52 @@ async fn load_asset(
     ctx: AppContext,
     path: &VirtualPath,
     media_type: String,
+    progress: RwSignal<Option<FetchProgress>>,
+    signal: Option<&AbortSignal>,
 ) -> Result<RendererContent, String> {
     let public_url = ctx
         .public_read_url(path)
         .map_err(|error| error.to_string())?;
 
     if media_type == "application/pdf" {
+        let viewer = pdf_viewer(ctx).await;
         if let Some(url) = public_url
             .as_deref()
-            .filter(|url| can_embed_pdf_url(url))
+            .filter(|url| can_embed_pdf_url(url, viewer))
             .map(|url| BrowserAssetUrl::public(url.to_owned()))
         {
-            return Ok(RendererContent::Pdf { url });
+            return Ok(RendererContent::Pdf { url, viewer });
         }
         let bytes = ctx
-            .read_bytes(path)
+            .read_bytes_with_progress(path, progress, signal)
             .await
             .map_err(|error| error.to_string())?;
         let url = object_url_for_bytes(&bytes, &media_type)?;
-        Ok(RendererContent::Pdf { url })
+        Ok(RendererContent::Pdf { url, viewer })
+    } else if media_type.starts_with("audio/") || media_type.starts_with("video/") {
+        // Players stream straight from a public URL; private files play
+        // from an object URL over the fetched bytes.
+        let url = match public_url.filter(|url| can_load_public_url(url)) {
+            Some(url) => BrowserAssetUrl::public(url),
+            None => {
+                let bytes = ctx
+                    .read_bytes_with_progress(path, progress, signal)
+                    .await
+                    .map_err(|error| error.to_string())?;
+                object_url_for_bytes(&bytes, &media_type)?
+            }
+        };
+        if media_type.starts_with("audio/") {
+            Ok(RendererContent::Audio { url })
+        } else {
+            Ok(RendererContent::Video { url })
+        }
     } else {
-        if let Some(url) = public_url.filter(|url| can_render_image_url(url)) {
+        if let Some(url) = public_url.filter(|url| can_load_public_url(url)) {
             return Ok(RendererContent::Image { url });
         }
         let bytes = ctx
-            .read_bytes(path)
+            .read_bytes_with_progress(path, progress, signal)
             .await
             .map_err(|error| error.to_string())?;
         let url = data_url_for_bytes(&bytes, &media_type);
@@ -95,11 +155,30 @@ async fn load_asset(
     }
 }
 
-fn can_embed_pdf_url(url: &str) -> bool {
-    is_relative_public_url(url) || is_githubusercontent_url(url)
+/// The viewer `PDF_VIEWER` asks for, falling back to the browser's own
+/// when the bundled pdf.js viewer is not deployed.
+async fn pdf_viewer(ctx: AppContext) -> PdfViewer {
+    let requested = ctx
+        .runtime_state
+        .with_untracked(|rs| PdfViewer::requested(rs.env.get(PDF_VIEWER_VAR).map(String::as_str)));
+    if requested == PdfViewer::Native {
+        return PdfViewer::Native;
+    }
+    PdfViewer::resolve(requested, bundled_viewer_available().await)
+}
+
+/// Whether `viewer` may load `url` straight from its public location.
+/// pdf.js refuses a `file` from another origin, so the bundled viewer only
+/// takes site-relative URLs; other PDFs reach it as object URLs.
+fn can_embed_pdf_url(url: &str, viewer: PdfViewer) -> bool {
+    match viewer {
+        PdfViewer::Native => is_relative_public_url(url) || is_githubusercontent_url(url),
+        PdfViewer::Bundled => is_relative_public_url(url),
+    }
 }
 
-fn can_render_image_url(url: &str) -> bool {
+/// Whether images and media players may load `url` directly.
+fn can_load_public_url(url: &str) -> bool {
     is_relative_public_url(url) || url.trim_start().starts_with("https://")
 }
 
@@ -126,14 +205,35 @@ async fn load_redirect(ctx: AppContext, path: &VirtualPath) -> Result<RendererCo
         .read_text(path)
         .await
         .map_err(|error| error.to_string())?;
-    match validate_redirect_url(target.trim()) {
+    let mount_domains = ctx
+        .runtime_mount_for_path(path)
+        .and_then(|mount| mount.redirect_domains);
+    match validate_redirect_url(target.trim(), mount_domains.as_deref()) {
         UrlValidation::Valid(safe_url) => {
-            if let Some(window) = web_sys::window()
-                && window.location().set_href(&safe_url).is_err()
-            {
-                return Err("Failed to redirect".to_string());
+            let auto_redirect = ctx.runtime_state.with_untracked(|state| {
+                state
+                    .env
+                    .get(AUTO_REDIRECT_VAR)
+                    .and_then(|value| parse_toggle(value))
+                    .unwrap_or(false)
+            });
+            let origin = web_sys::window()
+                .and_then(|window| window.location().origin().ok())
+                .unwrap_or_default();
+            match redirect_action(&safe_url, &origin, auto_redirect) {
+                RedirectAction::Navigate => {
+                    if let Some(window) = web_sys::window()
+                        && window.location().set_href(&safe_url).is_err()
+                    {
+                        return Err("Failed to redirect".to_string());
+                    }
+                    Ok(RendererContent::Redirecting)
+                }
+                RedirectAction::Confirm { host } => Ok(RendererContent::RedirectConfirm {
+                    url: safe_url,
+                    host,
+                }),
             }
-            Ok(RendererContent::Redirecting)
         }
         UrlValidation::Invalid(error) => Err(format!("Redirect blocked: {error}")),
     }
@@ -148,30 +248,51 @@ mod tests {
 
     #[wasm_bindgen_test]
     fn pdf_direct_url_allows_relative_and_githubusercontent_sources() {
-        assert!(can_embed_pdf_url("./content/docs/file.pdf"));
-        assert!(can_embed_pdf_url("/content/docs/file.pdf"));
         assert!(can_embed_pdf_url(
-            "https://raw.githubusercontent.com/owner/repo/main/content/file.pdf"
+            "./content/docs/file.pdf",
+            PdfViewer::Native
+        ));
+        assert!(can_embed_pdf_url(
+            "/content/docs/file.pdf",
+            PdfViewer::Native
+        ));
+        assert!(can_embed_pdf_url(
+            "https://raw.githubusercontent.com/owner/repo/main/content/file.pdf",
+            PdfViewer::Native
         ));
     }
 
     #[wasm_bindgen_test]
     fn pdf_direct_url_rejects_non_csp_sources() {
         assert!(!can_embed_pdf_url(
-            "https://gateway.pinata.cloud/ipfs/cid/file.pdf"
+            "https://gateway.pinata.cloud/ipfs/cid/file.pdf",
+            PdfViewer::Native
+        ));
+        assert!(!can_embed_pdf_url(
+            "//gateway.pinata.cloud/ipfs/cid/file.pdf",
+            PdfViewer::Native
+        ));
+        assert!(!can_embed_pdf_url("javascript:alert(1)", PdfViewer::Native));
+    }
+
+    #[wasm_bindgen_test]
+    fn bundled_pdf_viewer_only_embeds_same_origin_urls() {
+        assert!(can_embed_pdf_url(
+            "./content/docs/file.pdf",
+            PdfViewer::Bundled
         ));
         assert!(!can_embed_pdf_url(
-            "//gateway.pinata.cloud/ipfs/cid/file.pdf"
+            "https://raw.githubusercontent.com/owner/repo/main/content/file.pdf",
+            PdfViewer::Bundled
         ));
-        assert!(!can_embed_pdf_url("javascript:alert(1)"));
     }
 
     #[wasm_bindgen_test]
     fn image_direct_url_allows_https_sources() {
-        assert!(can_render_image_url("./content/images/file.png"));
-        assert!(can_render_image_url(
+        assert!(can_load_public_url("./content/images/file.png"));
+        assert!(can_load_public_url(
             "https://gateway.pinata.cloud/ipfs/cid/file.png"
         ));
-        assert!(!can_render_image_url("http://example.com/file.png"));
+        assert!(!can_load_public_url("http://example.com/file.png"));
     }
 }
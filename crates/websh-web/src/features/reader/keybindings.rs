@@ -2,18 +2,82 @@ use leptos::prelude::*;
 
 use super::ReaderMode;
 
+/// Pixels moved by `j`/`k`: about two lines of body text.
+const LINE_STEP_PX: i32 = 48;
+
 #[derive(Clone, Copy)]
 pub(super) struct KeybindingTargets {
     pub(super) mode: RwSignal<ReaderMode>,
     pub(super) edit_visible: Memo<bool>,
     pub(super) saving: ReadSignal<bool>,
+    pub(super) surface: NodeRef<leptos::html::Div>,
     pub(super) on_save: Callback<()>,
     pub(super) on_preview: Callback<()>,
     pub(super) on_toggle_edit: Callback<()>,
+    /// Open the next (`true`) or previous sibling file.
+    pub(super) on_step: Callback<bool>,
+}
+
+/// What an unmodified key press does in the reader.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ReaderKey {
+    /// `r` in Edit mode.
+    Preview,
+    /// `e` in View mode.
+    Edit,
+    /// `j` / `k`: scroll by a couple of lines.
+    Lines(i32),
+    /// `d` / `u`: scroll by half the viewport.
+    HalfPage(i32),
+    /// `g`
+    Top,
+    /// `G`
+    Bottom,
+    /// `n` / `p` (or `]` / `[`): open the next or previous file in the
+    /// directory.
+    Sibling { next: bool },
+}
+
+/// Map a key press to its reader action. Navigation keys only apply while
+/// viewing; the editor keeps every letter.
+fn reader_key(key: &str, mode: ReaderMode) -> Option<ReaderKey> {
+    match (key, mode) {
+        ("r", ReaderMode::Edit) => Some(ReaderKey::Preview),
+        ("e", ReaderMode::View) => Some(ReaderKey::Edit),
+        (_, ReaderMode::Edit) => None,
+        ("j", _) => Some(ReaderKey::Lines(1)),
+        ("k", _) => Some(ReaderKey::Lines(-1)),
+        ("d", _) => Some(ReaderKey::HalfPage(1)),
+        ("u", _) => Some(ReaderKey::HalfPage(-1)),
+        ("g", _) => Some(ReaderKey::Top),
+        ("G", _) => Some(ReaderKey::Bottom),
+        ("n" | "]", _) => Some(ReaderKey::Sibling { next: true }),
+        ("p" | "[", _) => Some(ReaderKey::Sibling { next: false }),
+        _ => None,
+    }
+}
+
+/// New `scrollTop` for a scrolling key, clamped to the scrollable range.
+fn scroll_target(
+    key: ReaderKey,
+    scroll_top: i32,
+    client_height: i32,
+    scroll_height: i32,
+) -> Option<i32> {
+    let max = (scroll_height - client_height).max(0);
+    let target = match key {
+        ReaderKey::Lines(direction) => scroll_top + direction * LINE_STEP_PX,
+        ReaderKey::HalfPage(direction) => scroll_top + direction * (client_height / 2).max(1),
+        ReaderKey::Top => 0,
+        ReaderKey::Bottom => max,
+        _ => return None,
+    };
+    Some(target.clamp(0, max))
 }
 
 #[cfg(target_arch = "wasm32")]
 pub(super) fn install_reader_keybindings(targets: KeybindingTargets) {
+    use crate::platform::dom::is_editable_target;
     use crate::platform::wasm_cleanup::WasmCleanup;
     use leptos::prelude::on_cleanup;
     use wasm_bindgen::JsCast;
@@ -25,10 +89,6 @@ pub(super) fn install_reader_keybindings(targets: KeybindingTargets) {
 
     let closure = Closure::wrap(Box::new(move |ev: web_sys::KeyboardEvent| {
         let mode_now = targets.mode.get_untracked();
-        let in_textarea = ev
-            .target()
-            .and_then(|t| t.dyn_into::<web_sys::HtmlTextAreaElement>().ok())
-            .is_some();
 
         if (ev.meta_key() || ev.ctrl_key()) && ev.key() == "s" {
             ev.prevent_default();
@@ -38,18 +98,43 @@ pub(super) fn install_reader_keybindings(targets: KeybindingTargets) {
             return;
         }
 
-        if in_textarea || ev.meta_key() || ev.ctrl_key() || ev.alt_key() {
+        if is_editable_target(ev.target().as_ref())
+            || ev.meta_key()
+            || ev.ctrl_key()
+            || ev.alt_key()
+        {
             return;
         }
 
-        match ev.key().as_str() {
-            "r" if mode_now == ReaderMode::Edit && !targets.saving.get_untracked() => {
-                targets.on_preview.run(());
+        let Some(key) = reader_key(&ev.key(), mode_now) else {
+            return;
+        };
+        match key {
+            ReaderKey::Preview => {
+                if !targets.saving.get_untracked() {
+                    targets.on_preview.run(());
+                }
+            }
+            ReaderKey::Edit => {
+                if targets.edit_visible.get_untracked() {
+                    targets.on_toggle_edit.run(());
+                }
             }
-            "e" if mode_now == ReaderMode::View && targets.edit_visible.get_untracked() => {
-                targets.on_toggle_edit.run(());
+            ReaderKey::Sibling { next } => targets.on_step.run(next),
+            scroll => {
+                let Some(surface) = targets.surface.get_untracked() else {
+                    return;
+                };
+                if let Some(top) = scroll_target(
+                    scroll,
+                    surface.scroll_top(),
+                    surface.client_height(),
+                    surface.scroll_height(),
+                ) {
+                    ev.prevent_default();
+                    surface.set_scroll_top(top);
+                }
             }
-            _ => {}
         }
     }) as Box<dyn Fn(web_sys::KeyboardEvent)>);
 
@@ -65,3 +150,48 @@ pub(super) fn install_reader_keybindings(targets: KeybindingTargets) {
 
 #[cfg(not(target_arch = "wasm32"))]
 pub(super) fn install_reader_keybindings(_targets: KeybindingTargets) {}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn navigation_keys_only_apply_while_viewing() {
+        assert_eq!(reader_key("j", ReaderMode::View), Some(ReaderKey::Lines(1)));
+        assert_eq!(reader_key("G", ReaderMode::View), Some(ReaderKey::Bottom));
+        assert_eq!(
+            reader_key("p", ReaderMode::View),
+            Some(ReaderKey::Sibling { next: false })
+        );
+        assert_eq!(
+            reader_key("]", ReaderMode::View),
+            Some(ReaderKey::Sibling { next: true })
+        );
+        assert_eq!(
+            reader_key("[", ReaderMode::View),
+            Some(ReaderKey::Sibling { next: false })
+        );
+        assert_eq!(reader_key("[", ReaderMode::Edit), None);
+        assert_eq!(reader_key("j", ReaderMode::Edit), None);
+        assert_eq!(reader_key("e", ReaderMode::Edit), None);
+        assert_eq!(reader_key("r", ReaderMode::Edit), Some(ReaderKey::Preview));
+        assert_eq!(reader_key("x", ReaderMode::View), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn scroll_targets_stay_within_the_document() {
+        // 1000px of content in a 400px viewport: 600px scrollable.
+        let at = |key, top| scroll_target(key, top, 400, 1000);
+        assert_eq!(at(ReaderKey::Lines(1), 0), Some(LINE_STEP_PX));
+        assert_eq!(at(ReaderKey::Lines(-1), 10), Some(0));
+        assert_eq!(at(ReaderKey::HalfPage(1), 500), Some(600));
+        assert_eq!(at(ReaderKey::HalfPage(-1), 300), Some(100));
+        assert_eq!(at(ReaderKey::Top, 300), Some(0));
+        assert_eq!(at(ReaderKey::Bottom, 0), Some(600));
+        assert_eq!(scroll_target(ReaderKey::Bottom, 0, 400, 300), Some(0));
+        assert_eq!(at(ReaderKey::Sibling { next: true }, 0), None);
+    }
+}
@@ -0,0 +1,85 @@
+//! Lifecycle of the reader's document loads.
+//!
+//! Each load gets a generation number and its own abort handle. Starting a
+//! new load (the path changed, or the editor asked for a refetch) cancels
+//! the previous fetch, and a result whose generation is no longer current
+//! is dropped, so a slow earlier file can never land over a newer one.
+
+use web_sys::AbortSignal;
+
+use crate::platform::FetchAbort;
+
+/// Error a superseded load resolves to; it is never rendered.
+pub(super) const SUPERSEDED: &str = "superseded by a newer load";
+
+#[derive(Default)]
+pub(super) struct DocumentLoads {
+    generation: u64,
+    abort: Option<FetchAbort>,
+}
+
+/// The generation a load belongs to and the signal its fetches follow.
+pub(super) struct LoadTicket {
+    pub(super) generation: u64,
+    pub(super) signal: Option<AbortSignal>,
+}
+
+impl DocumentLoads {
+    /// Supersede the current load: cancel its fetch and hand out the next
+    /// generation. Without an `AbortController` the load still runs to
+    /// completion but stays guarded by its generation.
+    pub(super) fn begin(&mut self) -> LoadTicket {
+        self.cancel();
+        self.abort = FetchAbort::new().ok();
+        LoadTicket {
+            generation: self.generation,
+            signal: self.abort.as_ref().map(FetchAbort::signal),
+        }
+    }
+
+    pub(super) fn is_current(&self, generation: u64) -> bool {
+        self.generation == generation
+    }
+
+    /// Cancel the in-flight fetch and retire its generation, for a reader
+    /// that is going away.
+    pub(super) fn cancel(&mut self) {
+        if let Some(abort) = self.abort.take() {
+            abort.abort();
+        }
+        self.generation += 1;
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn a_new_load_supersedes_and_cancels_the_previous_one() {
+        let mut loads = DocumentLoads::default();
+        let first = loads.begin();
+        assert!(loads.is_current(first.generation));
+
+        let second = loads.begin();
+        assert!(!loads.is_current(first.generation));
+        assert!(loads.is_current(second.generation));
+        assert!(first.signal.unwrap().aborted());
+        assert!(!second.signal.as_ref().unwrap().aborted());
+    }
+
+    #[wasm_bindgen_test]
+    fn cancelling_aborts_the_fetch_and_retires_the_generation() {
+        let mut loads = DocumentLoads::default();
+        let ticket = loads.begin();
+        loads.cancel();
+        assert!(ticket.signal.unwrap().aborted());
+        assert!(!loads.is_current(ticket.generation));
+        // Cancelling again with nothing in flight is harmless.
+        loads.cancel();
+        assert!(!loads.is_current(ticket.generation));
+    }
+}
@@ -6,9 +6,14 @@
 use leptos::prelude::With;
 
 use crate::app::AppContext;
+use crate::platform::current_timestamp;
 use crate::shared::components::{FileMeta, file_meta_for_path, size_summary_parts};
-use websh_core::domain::{ImageDim, NodeKind, PageSize, VirtualPath};
-use websh_core::support::format::{format_date_iso, format_size};
+use websh_core::domain::{FsEntry, ImageDim, NodeKind, PageSize, VirtualPath, WalletState};
+use websh_core::filesystem::GlobalFs;
+use websh_core::support::format::{
+    format_date_iso, format_eth_address, format_relative_time, format_size,
+};
+use websh_core::support::frontmatter::FrontMatter;
 
 use super::intent::ReaderIntent;
 
@@ -17,6 +22,10 @@ pub struct ReaderMeta {
     pub title: String,
     pub canonical_path: VirtualPath,
     pub modified_iso: Option<String>,
+    /// Distance from `modified_iso` to when the reader opened, e.g.
+    /// "3 days ago". `None` once the modification is a week or more old,
+    /// where the absolute date says it all.
+    pub modified_relative: Option<String>,
     pub date: Option<String>,
     pub size_pretty: Option<String>,
     pub tags: Vec<String>,
@@ -37,15 +46,82 @@ pub struct ReaderMeta {
     /// Markdown word count (frontmatter excluded). Drives the
     /// `N words · M min` chip on the right side of the title strip.
     pub word_count: Option<u32>,
+    /// Viewer-relative state of the file's access filter; `None` for
+    /// unrestricted files.
+    pub access: Option<AccessNotice>,
+    /// Front matter marks the document `draft: true`.
+    pub draft: bool,
+}
+
+/// How a restricted file's recipient list relates to the connected wallet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccessNotice {
+    /// No wallet connected, so recipients cannot be matched yet.
+    ConnectWallet,
+    /// The connected wallet is a recipient.
+    Granted,
+    /// The connected wallet is not a recipient; these addresses are.
+    Required { recipients: Vec<String> },
+}
+
+impl AccessNotice {
+    pub fn message(&self) -> String {
+        match self {
+            Self::ConnectWallet => "Encrypted — connect wallet to read".to_string(),
+            Self::Granted => "You have access".to_string(),
+            Self::Required { recipients } if recipients.is_empty() => {
+                "Encrypted — no recipients".to_string()
+            }
+            Self::Required { recipients } => {
+                let addresses = recipients
+                    .iter()
+                    .map(|address| format_eth_address(address))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("Encrypted — requires {addresses}")
+            }
+        }
+    }
 }
 
 impl ReaderMeta {
+    /// Overlay keys from the document's own front matter: its title
+    /// replaces the filename, and its date and tags win over the manifest.
+    pub fn with_front_matter(mut self, front_matter: &FrontMatter) -> Self {
+        if let Some(title) = front_matter.title.as_deref().map(str::trim)
+            && !title.is_empty()
+        {
+            self.title = title.to_string();
+        }
+        if let Some(date) = front_matter.date.as_deref().map(str::trim)
+            && !date.is_empty()
+        {
+            self.date = Some(date.to_string());
+        }
+        if !front_matter.tags.is_empty() {
+            self.tags = front_matter.tags.clone();
+        }
+        self.draft = front_matter.draft;
+        self
+    }
+
     /// Display value for the single `Date` row — author-declared `date`
     /// preferred, mechanical `modified_iso` as fallback, `None` if neither.
     pub fn display_date(&self) -> Option<String> {
         self.date.clone().or_else(|| self.modified_iso.clone())
     }
 
+    /// Value for the `Date` row in the meta table: like
+    /// [`Self::display_date`], but a modification date also carries its
+    /// relative form, e.g. `"2026-04-30 (3 days ago)"`.
+    pub fn date_row_value(&self) -> Option<String> {
+        match (&self.date, &self.modified_iso, &self.modified_relative) {
+            (Some(date), _, _) => Some(date.clone()),
+            (None, Some(iso), Some(relative)) => Some(format!("{iso} ({relative})")),
+            (None, iso, _) => iso.clone(),
+        }
+    }
+
     /// Kind-aware size chunks, sharing logic with
     /// [`FileMeta::size_summary_parts`] so the same file produces the
     /// same chunks in the title strip and the ledger entry meta line.
@@ -61,11 +137,45 @@ impl ReaderMeta {
 
 pub fn reader_meta(ctx: AppContext, intent: &ReaderIntent) -> ReaderMeta {
     let node_path = node_path_for(intent);
-    let file_meta = ctx
-        .view_global_fs
-        .with(|fs| file_meta_for_path(fs, &node_path))
-        .unwrap_or_default();
-    build_reader_meta(intent, &node_path, file_meta)
+    let (file_meta, access) = ctx.view_global_fs.with(|fs| {
+        let access = ctx
+            .wallet
+            .with(|wallet| access_notice(fs, &node_path, wallet));
+        (file_meta_for_path(fs, &node_path), access)
+    });
+    let mut meta = build_reader_meta(
+        intent,
+        &node_path,
+        file_meta.unwrap_or_default(),
+        current_timestamp() / 1000,
+    );
+    meta.access = access;
+    meta
+}
+
+/// Access state for the file at `path`, or `None` when it is missing or
+/// carries no access filter. Readability comes from
+/// [`GlobalFs::get_permissions`] so the reader agrees with `ls -l`.
+fn access_notice(fs: &GlobalFs, path: &VirtualPath, wallet: &WalletState) -> Option<AccessNotice> {
+    let entry = fs.get_entry(path)?;
+    let FsEntry::File { meta, .. } = entry else {
+        return None;
+    };
+    let filter = meta.access()?;
+
+    if fs.get_permissions(entry, wallet, false).read {
+        Some(AccessNotice::Granted)
+    } else if wallet.is_connected() {
+        Some(AccessNotice::Required {
+            recipients: filter
+                .recipients
+                .iter()
+                .map(|recipient| recipient.address.clone())
+                .collect(),
+        })
+    } else {
+        Some(AccessNotice::ConnectWallet)
+    }
 }
 
 fn node_path_for(intent: &ReaderIntent) -> VirtualPath {
@@ -78,7 +188,12 @@ fn node_path_for(intent: &ReaderIntent) -> VirtualPath {
     }
 }
 
-fn build_reader_meta(intent: &ReaderIntent, node_path: &VirtualPath, meta: FileMeta) -> ReaderMeta {
+fn build_reader_meta(
+    intent: &ReaderIntent,
+    node_path: &VirtualPath,
+    meta: FileMeta,
+    now: u64,
+) -> ReaderMeta {
     let title = node_path
         .file_name()
         .map(|name| {
@@ -89,6 +204,10 @@ fn build_reader_meta(intent: &ReaderIntent, node_path: &VirtualPath, meta: FileM
         .unwrap_or_else(|| node_path.as_str().trim_matches('/').to_string());
 
     let modified_iso = meta.modified.map(format_date_iso);
+    let modified_relative = meta
+        .modified
+        .map(|modified| format_relative_time(modified, now))
+        .filter(|relative| Some(relative) != modified_iso.as_ref());
     let date = meta.clean_date();
     let size_pretty = meta.size.map(|size| format_size(Some(size), false));
     let tags = meta.clean_tags();
@@ -99,6 +218,7 @@ fn build_reader_meta(intent: &ReaderIntent, node_path: &VirtualPath, meta: FileM
         title,
         canonical_path: node_path.clone(),
         modified_iso,
+        modified_relative,
         date,
         size_pretty,
         tags,
@@ -109,6 +229,8 @@ fn build_reader_meta(intent: &ReaderIntent, node_path: &VirtualPath, meta: FileM
         page_count: meta.page_count,
         image_dimensions: meta.image_dimensions,
         word_count: meta.word_count,
+        access: None,
+        draft: false,
     }
 }
 
@@ -128,6 +250,9 @@ mod tests {
 
     wasm_bindgen_test_configure!(run_in_browser);
 
+    /// 2024-01-04 00:00:00 UTC, three days after `populated_meta` was modified.
+    const NOW: u64 = 1_704_326_400;
+
     fn vp(path: &str) -> VirtualPath {
         VirtualPath::from_absolute(path).expect("test path")
     }
@@ -149,11 +274,12 @@ mod tests {
         let intent = ReaderIntent::Markdown {
             node_path: vp("/blog/hello.md"),
         };
-        let meta = build_reader_meta(&intent, &vp("/blog/hello.md"), populated_meta());
+        let meta = build_reader_meta(&intent, &vp("/blog/hello.md"), populated_meta(), NOW);
         assert_eq!(meta.title, "hello");
         assert_eq!(meta.media_type_hint, Some("UTF-8 · CommonMark"));
         assert_eq!(meta.date.as_deref(), Some("2026-04-22"));
-        assert!(meta.modified_iso.is_some());
+        assert_eq!(meta.modified_iso.as_deref(), Some("2024-01-01"));
+        assert_eq!(meta.modified_relative.as_deref(), Some("3 days ago"));
         assert_eq!(meta.tags, vec!["paper", "draft"]);
     }
 
@@ -166,16 +292,32 @@ mod tests {
             size: Some(2048),
             ..FileMeta::default()
         };
-        let result = build_reader_meta(&intent, &vp("/notes/x.txt"), meta);
+        let result = build_reader_meta(&intent, &vp("/notes/x.txt"), meta, NOW);
         assert_eq!(result.title, "x");
         assert_eq!(result.media_type_hint, Some("UTF-8 · LF"));
         assert!(result.size_pretty.is_some());
         assert!(result.date.is_none());
         assert!(result.modified_iso.is_none());
+        assert!(result.modified_relative.is_none());
         assert!(result.tags.is_empty());
         assert_eq!(result.description, "");
     }
 
+    #[wasm_bindgen_test]
+    fn old_modification_has_no_relative_date() {
+        let intent = ReaderIntent::Markdown {
+            node_path: vp("/blog/hello.md"),
+        };
+        let meta = build_reader_meta(
+            &intent,
+            &vp("/blog/hello.md"),
+            populated_meta(),
+            NOW + 30 * 86_400,
+        );
+        assert_eq!(meta.modified_iso.as_deref(), Some("2024-01-01"));
+        assert!(meta.modified_relative.is_none());
+    }
+
     #[wasm_bindgen_test]
     fn pdf_intent_preserves_description() {
         let intent = ReaderIntent::Asset {
@@ -186,7 +328,7 @@ mod tests {
             description: Some("  We present a thing.  ".to_string()),
             ..FileMeta::default()
         };
-        let result = build_reader_meta(&intent, &vp("/papers/x.pdf"), meta);
+        let result = build_reader_meta(&intent, &vp("/papers/x.pdf"), meta, NOW);
         assert_eq!(result.title, "x");
         assert_eq!(result.media_type_hint, None);
         assert_eq!(result.description, "We present a thing.");
@@ -198,7 +340,7 @@ mod tests {
             node_path: vp("/cover.png"),
             media_type: "image/png".to_string(),
         };
-        let result = build_reader_meta(&intent, &vp("/cover.png"), FileMeta::default());
+        let result = build_reader_meta(&intent, &vp("/cover.png"), FileMeta::default(), NOW);
         assert_eq!(result.title, "cover");
         assert!(result.description.is_empty());
         assert!(result.size_pretty.is_none());
@@ -209,7 +351,7 @@ mod tests {
         let intent = ReaderIntent::Redirect {
             node_path: vp("/x.link"),
         };
-        let result = build_reader_meta(&intent, &vp("/x.link"), FileMeta::default());
+        let result = build_reader_meta(&intent, &vp("/x.link"), FileMeta::default(), NOW);
         assert_eq!(result.title, "x");
         assert_eq!(result.media_type_hint, None);
     }
@@ -219,6 +361,7 @@ mod tests {
             title: "x".to_string(),
             canonical_path: vp("/x"),
             modified_iso: modified_iso.map(String::from),
+            modified_relative: modified_iso.map(|_| "2 days ago".to_string()),
             date: date.map(String::from),
             size_pretty: None,
             tags: vec![],
@@ -229,9 +372,40 @@ mod tests {
             page_count: None,
             image_dimensions: None,
             word_count: None,
+            access: None,
+            draft: false,
         }
     }
 
+    #[wasm_bindgen_test]
+    fn front_matter_overrides_title_date_and_tags() {
+        let front_matter = FrontMatter {
+            title: Some("Proper Title".to_string()),
+            date: Some("2026-05-01".to_string()),
+            tags: vec!["rust".to_string()],
+            draft: true,
+        };
+        let meta = reader_meta_with(Some("2026-04-22"), None).with_front_matter(&front_matter);
+        assert_eq!(meta.title, "Proper Title");
+        assert_eq!(meta.date.as_deref(), Some("2026-05-01"));
+        assert_eq!(meta.tags, vec!["rust"]);
+        assert!(meta.draft);
+    }
+
+    #[wasm_bindgen_test]
+    fn partial_front_matter_keeps_manifest_values() {
+        let mut base = reader_meta_with(Some("2026-04-22"), None);
+        base.tags = vec!["notes".to_string()];
+        let meta = base.with_front_matter(&FrontMatter {
+            title: Some("  ".to_string()),
+            ..FrontMatter::default()
+        });
+        assert_eq!(meta.title, "x");
+        assert_eq!(meta.date.as_deref(), Some("2026-04-22"));
+        assert_eq!(meta.tags, vec!["notes"]);
+        assert!(!meta.draft);
+    }
+
     #[wasm_bindgen_test]
     fn display_date_cases() {
         let cases = [
@@ -246,12 +420,126 @@ mod tests {
         }
     }
 
+    #[wasm_bindgen_test]
+    fn date_row_value_adds_relative_to_modification_dates() {
+        let cases = [
+            (Some("2026-04-22"), Some("2026-04-30"), Some("2026-04-22")),
+            (None, Some("2026-04-30"), Some("2026-04-30 (2 days ago)")),
+            (None, None, None),
+        ];
+
+        for (date, modified, expected) in cases {
+            let m = reader_meta_with(date, modified);
+            assert_eq!(m.date_row_value().as_deref(), expected);
+        }
+    }
+
+    const RECIPIENT: &str = "0x1234567890123456789012345678901234567890";
+
+    fn restricted_fs() -> GlobalFs {
+        use websh_core::domain::{
+            AccessFilter, EntryExtensions, Fields, ListingVisibility, NodeMetadata, Recipient,
+            SCHEMA_VERSION,
+        };
+        use websh_core::ports::{ScannedFile, ScannedSubtree};
+
+        let meta = |access: Option<AccessFilter>| NodeMetadata {
+            schema: SCHEMA_VERSION,
+            kind: NodeKind::Page,
+            authored: Fields {
+                access,
+                ..Fields::default()
+            },
+            derived: Fields::default(),
+        };
+        let snapshot = ScannedSubtree {
+            files: vec![
+                ScannedFile {
+                    path: "private/key.md".to_string(),
+                    meta: meta(Some(AccessFilter {
+                        recipients: vec![Recipient {
+                            address: RECIPIENT.to_string(),
+                        }],
+                        listing: ListingVisibility::Listed,
+                    })),
+                    extensions: EntryExtensions::default(),
+                },
+                ScannedFile {
+                    path: "public.md".to_string(),
+                    meta: meta(None),
+                    extensions: EntryExtensions::default(),
+                },
+            ],
+            directories: Vec::new(),
+            banner: None,
+        };
+        let mut fs = GlobalFs::empty();
+        fs.mount_scanned_subtree(VirtualPath::root(), &snapshot)
+            .expect("mount snapshot");
+        fs
+    }
+
+    fn connected(address: &str) -> WalletState {
+        WalletState::Connected {
+            address: address.to_string(),
+            ens_name: None,
+            chain_id: Some(1),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn recipient_wallet_has_access() {
+        let fs = restricted_fs();
+        let wallet = connected(&RECIPIENT.to_uppercase().replace("0X", "0x"));
+        let notice = access_notice(&fs, &vp("/private/key.md"), &wallet);
+        assert_eq!(notice, Some(AccessNotice::Granted));
+        assert_eq!(notice.unwrap().message(), "You have access");
+    }
+
+    #[wasm_bindgen_test]
+    fn other_wallet_is_told_which_recipient_is_required() {
+        let fs = restricted_fs();
+        let wallet = connected("0xabcdefabcdefabcdefabcdefabcdefabcdefabcd");
+        let notice = access_notice(&fs, &vp("/private/key.md"), &wallet).expect("restricted");
+        assert_eq!(
+            notice,
+            AccessNotice::Required {
+                recipients: vec![RECIPIENT.to_string()],
+            }
+        );
+        assert_eq!(notice.message(), "Encrypted — requires 0x1234...7890");
+    }
+
+    #[wasm_bindgen_test]
+    fn disconnected_viewer_is_asked_to_connect() {
+        let fs = restricted_fs();
+        let notice = access_notice(&fs, &vp("/private/key.md"), &WalletState::Disconnected);
+        assert_eq!(notice, Some(AccessNotice::ConnectWallet));
+        assert_eq!(
+            notice.unwrap().message(),
+            "Encrypted — connect wallet to read"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn unrestricted_and_missing_files_have_no_notice() {
+        let fs = restricted_fs();
+        let wallet = connected(RECIPIENT);
+        assert_eq!(access_notice(&fs, &vp("/public.md"), &wallet), None);
+        assert_eq!(access_notice(&fs, &vp("/missing.md"), &wallet), None);
+    }
+
     #[wasm_bindgen_test]
     fn title_strips_extension() {
         let intent = ReaderIntent::Markdown {
             node_path: vp("/blog/some.thing.md"),
         };
-        let result = build_reader_meta(&intent, &vp("/blog/some.thing.md"), FileMeta::default());
+        let result = build_reader_meta(
+            &intent,
+            &vp("/blog/some.thing.md"),
+            FileMeta::default(),
+            NOW,
+        );
         assert_eq!(result.title, "some.thing"); // rsplit only trims last extension
     }
 }
@@ -13,8 +13,12 @@
 mod document;
 mod intent;
 mod keybindings;
+mod load;
 mod meta;
+mod prefetch;
+mod scroll;
 mod shell;
+mod sibling_nav;
 mod title_block;
 mod toolbar;
 mod views;
@@ -26,19 +30,28 @@ use wasm_bindgen_futures::spawn_local;
 
 use crate::app::AppContext;
 use crate::features::mempool::save_raw;
-use crate::platform::current_timestamp;
-use crate::platform::dom::{push_request_path, replace_request_path};
-use websh_core::filesystem::{RouteFrame, attestation_route_for_node_path, content_route_for_path};
+use crate::platform::dom::{
+    open_in_new_tab, print_document, push_request_path, replace_request_path,
+};
+use crate::platform::{FetchProgress, current_timestamp};
+use websh_core::domain::VirtualPath;
+use websh_core::filesystem::{
+    DeepLink, RouteFrame, RouteSurface, attestation_route_for_node_path, content_route_for_path,
+    request_path_for_canonical_path,
+};
 use websh_core::mempool::{derive_new_path, placeholder_frontmatter};
-use websh_core::support::format::format_date_iso;
+use websh_core::support::format::{format_date_iso, markdown_word_count};
 
 use document::{ReaderDocument, RendererContent, load_reader_document};
 use keybindings::{KeybindingTargets, install_reader_keybindings};
+use load::DocumentLoads;
 use meta::{ReaderMeta, reader_meta};
 use shell::{ReaderEditBindings, ReaderShell, ReaderShellState};
+use sibling_nav::SiblingNav;
 use views::{
-    AssetReaderView, HtmlReaderView, MarkdownEditorView, MarkdownReaderView, PdfReaderView,
-    PlainReaderView, RedirectingView,
+    AssetReaderView, AudioReaderView, HtmlReaderView, MarkdownEditorView, MarkdownReaderView,
+    PdfReaderView, PlainReaderView, ReaderLoadingView, RedirectConfirmView, RedirectingView,
+    VideoReaderView,
 };
 
 // One stylance import for the whole reader module. `views/*.rs` and
@@ -79,7 +92,7 @@ pub fn Reader(frame: Memo<ReaderFrame>) -> impl IntoView {
         Signal::derive(move || attestation_route_for_node_path(&canonical_path.get()));
 
     let intent_memo = Memo::new(move |_| frame.get().intent.clone());
-    let reader_meta_memo = Memo::new(move |_| reader_meta(ctx, &intent_memo.get()));
+    let manifest_meta = Memo::new(move |_| reader_meta(ctx, &intent_memo.get()));
 
     let author_mode = Memo::new(move |_| ctx.runtime_state.with(|rs| rs.github_token_present));
     let is_new_route = Memo::new(move |_| frame.get().request.url_path == "/new");
@@ -104,6 +117,7 @@ pub fn Reader(frame: Memo<ReaderFrame>) -> impl IntoView {
     let save_error = RwSignal::new(None::<String>);
     let saving = RwSignal::new(false);
     let refetch_epoch = RwSignal::new(0u32);
+    let load_progress = RwSignal::new(None::<FetchProgress>);
 
     // Author-mode redirect for /new — non-author lands on /ledger.
     Effect::new(move |_| {
@@ -131,15 +145,123 @@ pub fn Reader(frame: Memo<ReaderFrame>) -> impl IntoView {
         key
     });
 
+    // Each run of the document resource supersedes (and aborts) the one
+    // before it; leaving the reader aborts the last.
+    let loads = StoredValue::new_local(DocumentLoads::default());
+    on_cleanup(move || {
+        loads.try_update_value(DocumentLoads::cancel);
+    });
+
     let document = LocalResource::new({
         move || {
             let snapshot = frame.get();
             let path = snapshot.resolution.node_path.clone();
             let intent = snapshot.intent.clone();
             let _ = refetch_epoch.get();
-            async move { load_reader_document(ctx, path, intent).await }
+            load_progress.set(None);
+            let ticket = loads.try_update_value(DocumentLoads::begin);
+            async move {
+                let ticket = ticket.ok_or_else(|| load::SUPERSEDED.to_string())?;
+                let result =
+                    load_reader_document(ctx, path, intent, load_progress, ticket.signal).await;
+                if loads.try_with_value(|loads| loads.is_current(ticket.generation)) != Some(true) {
+                    return Err(load::SUPERSEDED.to_string());
+                }
+                result
+            }
+        }
+    });
+
+    // Once a file has loaded, fetch its neighbours ahead of a step to them.
+    Effect::new(move |_| {
+        if document.with(|result| matches!(result, Some(Ok(_)))) {
+            prefetch::prefetch_adjacent(ctx, canonical_path.get_untracked(), canonical_path);
+        }
+    });
+
+    // Front matter in the loaded markdown refines the manifest's view of it.
+    let front_matter = Memo::new(move |_| {
+        document
+            .get()
+            .and_then(|result| match result.ok()?.content {
+                RendererContent::Markdown(rendered) => rendered.front_matter,
+                _ => None,
+            })
+    });
+    // Without a manifest word count, count the loaded markdown so the
+    // title strip can still estimate reading time.
+    let source_word_count = Memo::new(move |_| {
+        document
+            .get()
+            .and_then(|result| result.ok()?.raw_source)
+            .map(|source| markdown_word_count(&source))
+    });
+    let reader_meta_memo = Memo::new(move |_| {
+        let mut meta = manifest_meta.get();
+        if let Some(front_matter) = front_matter.get() {
+            meta = meta.with_front_matter(&front_matter);
+        }
+        if meta.word_count.is_none() {
+            meta.word_count = source_word_count.get();
+        }
+        meta
+    });
+
+    // Reading progress through the surface, and the offset to offer
+    // resuming from when this path was read earlier in the session. A deep
+    // link to a heading or line takes the place of the resume prompt.
+    let surface = NodeRef::<leptos::html::Div>::new();
+    let progress = RwSignal::new(0.0_f64);
+    let resume_offset = RwSignal::new(None::<u32>);
+    let deep_link = RwSignal::new(DeepLink::default());
+    Effect::new(move |_| {
+        let path = canonical_path.get();
+        let link = frame
+            .with_untracked(|frame| ctx.take_deep_link(&frame.request.url_path))
+            .unwrap_or_default();
+        progress.set(0.0);
+        resume_offset.set(if link.heading.is_some() || link.line.is_some() {
+            None
+        } else {
+            scroll::saved_offset(path.as_str())
+        });
+        deep_link.set(link);
+    });
+    // Scroll to a linked heading once the document it lives in has loaded.
+    Effect::new(move |_| {
+        if !document.with(|result| matches!(result, Some(Ok(_)))) {
+            return;
+        }
+        if let Some(id) = deep_link
+            .try_update_untracked(|link| link.heading.take())
+            .flatten()
+        {
+            request_animation_frame(move || views::markdown::scroll_to_anchor(&id));
+        }
+    });
+    let on_scroll = Callback::new(move |()| {
+        let Some(surface) = surface.get_untracked() else {
+            return;
+        };
+        let top = surface.scroll_top();
+        progress.set(scroll::progress_fraction(
+            f64::from(top),
+            f64::from(surface.scroll_height()),
+            f64::from(surface.client_height()),
+        ));
+        if mode.get_untracked() == ReaderMode::View {
+            let path = canonical_path.get_untracked();
+            scroll::save_offset(path.as_str(), u32::try_from(top).unwrap_or(0));
         }
     });
+    let on_resume = move |_| {
+        if let (Some(offset), Some(surface)) =
+            (resume_offset.get_untracked(), surface.get_untracked())
+        {
+            surface.set_scroll_top(i32::try_from(offset).unwrap_or(i32::MAX));
+        }
+        resume_offset.set(None);
+    };
 
     let on_toggle_edit = move |()| {
         // Seed the editor only on first entry into Edit; the round-trip
@@ -249,19 +371,55 @@ pub fn Reader(frame: Memo<ReaderFrame>) -> impl IntoView {
         });
     };
 
+    // PDFs print best from the browser's own viewer, so open the raw file
+    // instead of printing the page around the embedded viewer.
+    let on_print = Callback::new(move |()| {
+        let pdf_url = document
+            .get_untracked()
+            .and_then(|result| match result.ok()?.content {
+                RendererContent::Pdf { url, .. } => Some(url.as_str().to_string()),
+                _ => None,
+            });
+        let result = match pdf_url {
+            Some(url) => open_in_new_tab(&url),
+            None => print_document(),
+        };
+        if let Err(error) = result {
+            leptos::logging::warn!("print: {error}");
+        }
+    });
+
     let on_edit_cb = Callback::new(on_toggle_edit);
     let on_preview_cb = Callback::new(on_preview);
     let on_cancel_cb = Callback::new(on_cancel);
     let on_save_cb = Callback::new(on_save);
     let on_input_dirty_cb = Callback::new(move |()| draft_dirty.set(true));
 
+    // `n`/`p` (or `]`/`[`) step through the directory in `ls` order,
+    // skipping subdirectories, hidden files and files the wallet can't read.
+    let on_step_cb = Callback::new(move |next: bool| {
+        let path = canonical_path.get_untracked();
+        let wallet = ctx.wallet.get_untracked();
+        let (previous, following) = ctx
+            .view_global_fs
+            .with_untracked(|fs| fs.adjacent_files(&path, &wallet));
+        if let Some(target) = if next { following } else { previous } {
+            push_request_path(&request_path_for_canonical_path(
+                &target,
+                RouteSurface::Content,
+            ));
+        }
+    });
+
     install_reader_keybindings(KeybindingTargets {
         mode,
         edit_visible,
         saving: saving.read_only(),
+        surface,
         on_save: on_save_cb,
         on_preview: on_preview_cb,
         on_toggle_edit: on_edit_cb,
+        on_step: on_step_cb,
     });
 
     let chrome_route = Memo::new(move |_| RouteFrame::from(frame.get()));
@@ -281,6 +439,9 @@ pub fn Reader(frame: Memo<ReaderFrame>) -> impl IntoView {
         attestation_route,
         show_pending,
         save_error: save_error.read_only(),
+        surface,
+        on_scroll,
+        on_print,
     };
 
     let edit_bindings = ReaderEditBindings {
@@ -296,15 +457,37 @@ pub fn Reader(frame: Memo<ReaderFrame>) -> impl IntoView {
 
     view! {
         <ReaderShell state=shell_state edit=edit_bindings>
+            <div
+                class=css::progressBar
+                aria-hidden="true"
+                style:transform=move || format!("scaleX({})", progress.get())
+            ></div>
+            {move || {
+                (mode.get() == ReaderMode::View && resume_offset.get().is_some()).then(|| view! {
+                    <div class=css::resumePrompt role="status">
+                        <span>"resume from where you left off?"</span>
+                        <button type="button" on:click=on_resume>"resume"</button>
+                        <button type="button" on:click=move |_| resume_offset.set(None)>
+                            "dismiss"
+                        </button>
+                    </div>
+                })
+            }}
             <Show
                 when=move || mode.get() == ReaderMode::Edit
                 fallback=move || view! {
                     <Suspense fallback=move || view! {
-                        <div class=css::loading>"Loading..."</div>
+                        <ReaderLoadingView progress=load_progress.read_only() />
                     }>
                         {move || {
+                            let line = deep_link.with(|link| link.line);
                             document.get().map(|result| {
-                                render_view_body(result, reader_meta_memo)
+                                render_view_body(
+                                    result,
+                                    canonical_path.get_untracked(),
+                                    reader_meta_memo,
+                                    line,
+                                )
                             })
                         }}
                     </Suspense>
@@ -319,7 +502,12 @@ pub fn Reader(frame: Memo<ReaderFrame>) -> impl IntoView {
     }
 }
 
-fn render_view_body(result: Result<ReaderDocument, String>, meta: Memo<ReaderMeta>) -> AnyView {
+fn render_view_body(
+    result: Result<ReaderDocument, String>,
+    path: VirtualPath,
+    meta: Memo<ReaderMeta>,
+    line: Option<u32>,
+) -> AnyView {
     let document = match result {
         Ok(document) => document,
         Err(error) => return view! { <div class=css::error>{error}</div> }.into_any(),
@@ -328,20 +516,25 @@ fn render_view_body(result: Result<ReaderDocument, String>, meta: Memo<ReaderMet
     match document.content {
         RendererContent::Markdown(rendered) => {
             let rendered = Signal::derive(move || rendered.clone());
-            view! { <MarkdownReaderView rendered=rendered /> }.into_any()
+            view! {
+                <MarkdownReaderView rendered=rendered />
+                <SiblingNav path=path />
+            }
+            .into_any()
         }
         RendererContent::Html(rendered) => {
             let rendered = Signal::derive(move || rendered.clone());
             view! { <HtmlReaderView rendered=rendered /> }.into_any()
         }
-        RendererContent::Text(text) => view! { <PlainReaderView text=text /> }.into_any(),
-        RendererContent::Pdf { url } => {
+        RendererContent::Text(text) => view! { <PlainReaderView text=text line=line /> }.into_any(),
+        RendererContent::Pdf { url, viewer } => {
             let title = Signal::derive(move || meta.get().title.clone());
             let m = meta.get_untracked();
             view! {
                 <PdfReaderView
                     title=title
                     url=url
+                    viewer=viewer
                     size_pretty=m.size_pretty
                     abstract_text=m.description
                     page_size=m.page_size
@@ -361,7 +554,16 @@ fn render_view_body(result: Result<ReaderDocument, String>, meta: Memo<ReaderMet
             }
             .into_any()
         }
+        RendererContent::Audio { url } => {
+            view! { <AudioReaderView url=url title=meta.get_untracked().title /> }.into_any()
+        }
+        RendererContent::Video { url } => {
+            view! { <VideoReaderView url=url title=meta.get_untracked().title /> }.into_any()
+        }
         RendererContent::Redirecting => view! { <RedirectingView /> }.into_any(),
+        RendererContent::RedirectConfirm { url, host } => {
+            view! { <RedirectConfirmView url=url host=host /> }.into_any()
+        }
     }
 }
 
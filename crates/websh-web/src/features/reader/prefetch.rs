@@ -0,0 +1,83 @@
+//! Background fetch of the files beside the open one, so stepping to a
+//! neighbour finds its text already in the content cache.
+
+use leptos::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+
+use crate::app::AppContext;
+use websh_core::domain::{FileType, VirtualPath};
+
+/// Fetch the neighbours of `path` into the content cache, one at a time,
+/// while the reader still shows `path`. Failures are dropped: the read is
+/// repeated, and reported, if the visitor opens the file.
+pub(super) fn prefetch_adjacent(ctx: AppContext, path: VirtualPath, open_path: Memo<VirtualPath>) {
+    let wallet = ctx.wallet.get_untracked();
+    let (previous, next) = ctx
+        .view_global_fs
+        .with_untracked(|fs| fs.adjacent_files(&path, &wallet));
+    let targets = prefetch_order(previous, next);
+    if targets.is_empty() {
+        return;
+    }
+
+    let generation = ctx.runtime_generation();
+    spawn_local(async move {
+        for target in targets {
+            let still_open = open_path
+                .try_get_untracked()
+                .is_some_and(|open| open == path);
+            if !still_open || ctx.runtime_generation() != generation {
+                return;
+            }
+            let _ = ctx.read_text(&target).await;
+        }
+    });
+}
+
+/// Neighbours worth fetching ahead, the next file first. Only small text
+/// types qualify; PDFs and images are fetched when opened.
+fn prefetch_order(previous: Option<VirtualPath>, next: Option<VirtualPath>) -> Vec<VirtualPath> {
+    [next, previous]
+        .into_iter()
+        .flatten()
+        .filter(|path| {
+            matches!(
+                FileType::from_path(path.as_str()),
+                FileType::Markdown | FileType::Link
+            )
+        })
+        .collect()
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn vp(path: &str) -> VirtualPath {
+        VirtualPath::from_absolute(path).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn prefetches_the_next_file_before_the_previous_one() {
+        assert_eq!(
+            prefetch_order(Some(vp("/blog/a.md")), Some(vp("/blog/c.link"))),
+            vec![vp("/blog/c.link"), vp("/blog/a.md")]
+        );
+        assert_eq!(prefetch_order(None, None), Vec::<VirtualPath>::new());
+    }
+
+    #[wasm_bindgen_test]
+    fn skips_large_and_binary_neighbours() {
+        assert_eq!(
+            prefetch_order(Some(vp("/papers/a.pdf")), Some(vp("/img/b.png"))),
+            Vec::<VirtualPath>::new()
+        );
+        assert_eq!(
+            prefetch_order(Some(vp("/blog/a.md")), Some(vp("/papers/b.pdf"))),
+            vec![vp("/blog/a.md")]
+        );
+    }
+}
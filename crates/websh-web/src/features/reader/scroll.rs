@@ -0,0 +1,138 @@
+//! Reading progress and per-path scroll positions.
+//!
+//! Offsets live in an in-memory map mirrored to sessionStorage, so
+//! reopening a file later in the same tab can offer to resume where the
+//! reader left off.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+const SESSION_KEY: &str = "websh.reader.scroll";
+/// Offsets below this (px) are too close to the top to be worth resuming.
+const MIN_RESUME_OFFSET: u32 = 200;
+
+thread_local! {
+    static POSITIONS: RefCell<Option<ScrollPositions>> = const { RefCell::new(None) };
+}
+
+/// Last scroll offset (px) per content path.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(super) struct ScrollPositions {
+    offsets: BTreeMap<String, u32>,
+}
+
+impl ScrollPositions {
+    /// Parse a stored snapshot; anything unreadable starts empty.
+    pub(super) fn from_json(raw: &str) -> Self {
+        serde_json::from_str(raw).unwrap_or_default()
+    }
+
+    pub(super) fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Offset worth resuming for `path`.
+    pub(super) fn get(&self, path: &str) -> Option<u32> {
+        self.offsets.get(path).copied()
+    }
+
+    /// Remember `offset` for `path`, forgetting it near the top. Returns
+    /// whether the stored value changed.
+    pub(super) fn record(&mut self, path: &str, offset: u32) -> bool {
+        if offset < MIN_RESUME_OFFSET {
+            return self.offsets.remove(path).is_some();
+        }
+        self.offsets.insert(path.to_string(), offset) != Some(offset)
+    }
+}
+
+/// Fraction of the scrollable distance covered, in `0.0..=1.0`. Content
+/// that fits the viewport counts as fully read.
+pub(super) fn progress_fraction(scroll_top: f64, scroll_height: f64, client_height: f64) -> f64 {
+    let scrollable = scroll_height - client_height;
+    if scrollable <= 0.0 {
+        return 1.0;
+    }
+    (scroll_top / scrollable).clamp(0.0, 1.0)
+}
+
+pub(super) fn saved_offset(path: &str) -> Option<u32> {
+    with_positions(|positions| positions.get(path))
+}
+
+pub(super) fn save_offset(path: &str, offset: u32) {
+    let snapshot =
+        with_positions(|positions| positions.record(path, offset).then(|| positions.to_json()));
+    if let Some(snapshot) = snapshot
+        && let Some(storage) = session_storage()
+    {
+        let _ = storage.set_item(SESSION_KEY, &snapshot);
+    }
+}
+
+fn with_positions<R>(f: impl FnOnce(&mut ScrollPositions) -> R) -> R {
+    POSITIONS.with(|cell| {
+        let mut slot = cell.borrow_mut();
+        let positions = slot.get_or_insert_with(|| {
+            session_storage()
+                .and_then(|storage| storage.get_item(SESSION_KEY).ok().flatten())
+                .map(|raw| ScrollPositions::from_json(&raw))
+                .unwrap_or_default()
+        });
+        f(positions)
+    })
+}
+
+fn session_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.session_storage().ok()?
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn records_offsets_per_path() {
+        let mut positions = ScrollPositions::default();
+        assert!(positions.record("/blog/a.md", 1_200));
+        assert!(!positions.record("/blog/a.md", 1_200));
+        assert!(positions.record("/blog/b.md", 640));
+        assert_eq!(positions.get("/blog/a.md"), Some(1_200));
+        assert_eq!(positions.get("/blog/b.md"), Some(640));
+        assert_eq!(positions.get("/blog/c.md"), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn offsets_near_the_top_are_forgotten() {
+        let mut positions = ScrollPositions::default();
+        positions.record("/a.md", 900);
+        assert!(positions.record("/a.md", 10));
+        assert_eq!(positions.get("/a.md"), None);
+        assert!(!positions.record("/b.md", 0));
+    }
+
+    #[wasm_bindgen_test]
+    fn round_trips_through_json() {
+        let mut positions = ScrollPositions::default();
+        positions.record("/a.md", 900);
+        assert_eq!(ScrollPositions::from_json(&positions.to_json()), positions);
+        assert_eq!(
+            ScrollPositions::from_json("not json"),
+            ScrollPositions::default()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn progress_is_clamped_fraction_of_scrollable_height() {
+        assert_eq!(progress_fraction(0.0, 2_000.0, 1_000.0), 0.0);
+        assert_eq!(progress_fraction(500.0, 2_000.0, 1_000.0), 0.5);
+        assert_eq!(progress_fraction(1_200.0, 2_000.0, 1_000.0), 1.0);
+        assert_eq!(progress_fraction(0.0, 800.0, 1_000.0), 1.0);
+    }
+}
@@ -33,6 +33,10 @@ pub struct ReaderShellState {
     pub attestation_route: Signal<String>,
     pub show_pending: Signal<bool>,
     pub save_error: ReadSignal<Option<String>>,
+    /// The scrolling surface; the reader reads and restores its offset.
+    pub surface: NodeRef<leptos::html::Div>,
+    pub on_scroll: Callback<()>,
+    pub on_print: Callback<()>,
 }
 
 /// Edit-mode reactive state and action callbacks — what the toolbar
@@ -57,7 +61,11 @@ pub fn ReaderShell(
     children: Children,
 ) -> impl IntoView {
     view! {
-        <div class=css::surface>
+        <div
+            class=css::surface
+            node_ref=state.surface
+            on:scroll=move |_| state.on_scroll.run(())
+        >
             <SiteChrome route=state.chrome_route />
             <main class=css::page>
                 <div class=css::content>
@@ -70,7 +78,7 @@ pub fn ReaderShell(
                     })}
                     {children()}
                 </div>
-                <ReaderToolbar edit=edit />
+                <ReaderToolbar edit=edit on_print=state.on_print />
                 <AttestationSigFooter
                     route=state.attestation_route
                     show_pending=state.show_pending
@@ -0,0 +1,73 @@
+//! "← previous" / "next →" cards under a markdown document, linking the
+//! files beside it in `ls` order — the same neighbours `n` / `p` and
+//! `[` / `]` open. Following a card swaps the reader content in place.
+
+use leptos::prelude::*;
+
+use crate::app::AppContext;
+use websh_core::domain::{DirEntry, VirtualPath};
+use websh_core::filesystem::{RouteSurface, request_path_for_canonical_path};
+
+use super::css;
+
+#[component]
+pub(super) fn SiblingNav(path: VirtualPath) -> impl IntoView {
+    let ctx = use_context::<AppContext>().expect("AppContext must be provided at root");
+    let neighbours = move || {
+        let wallet = ctx.wallet.get();
+        ctx.view_global_fs
+            .with(|fs| fs.adjacent_entries(&path, &wallet))
+    };
+
+    move || {
+        let (previous, next) = neighbours();
+        (previous.is_some() || next.is_some()).then(|| {
+            view! {
+                <nav class=css::siblingNav aria-label="Adjacent files">
+                    {previous.map(|entry| sibling_card(entry, false))}
+                    {next.map(|entry| sibling_card(entry, true))}
+                </nav>
+            }
+        })
+    }
+}
+
+fn sibling_card(entry: DirEntry, next: bool) -> impl IntoView {
+    let (label, rel, class) = if next {
+        (
+            "next →",
+            "next",
+            format!("{} {}", css::siblingCard, css::siblingNext),
+        )
+    } else {
+        ("← previous", "prev", css::siblingCard.to_string())
+    };
+    view! {
+        <a class=class href=sibling_href(&entry.path) rel=rel>
+            <span class=css::siblingLabel>{label}</span>
+            <span class=css::siblingTitle>{entry.title}</span>
+        </a>
+    }
+}
+
+/// Hash link opening `path` in the reader.
+fn sibling_href(path: &VirtualPath) -> String {
+    format!(
+        "#{}",
+        request_path_for_canonical_path(path, RouteSurface::Content)
+    )
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn sibling_links_are_content_hash_routes() {
+        let path = VirtualPath::from_absolute("/blog/part-2.md").unwrap();
+        assert_eq!(sibling_href(&path), "#/blog/part-2.md");
+    }
+}
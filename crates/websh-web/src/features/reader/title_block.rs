@@ -8,7 +8,7 @@
 //!   count for PDFs, pixel dimensions for images.
 //!
 //! The `MetaTable` below the title is the verbose breakdown
-//! (Type / Size / Date / Tags / Caption) and is unrelated to the strip.
+//! (Type / Size / Date / Access / Tags / Caption) and is unrelated to the strip.
 
 use leptos::prelude::*;
 
@@ -32,6 +32,9 @@ pub enum RowSpec {
     Date {
         value: String,
     },
+    Access {
+        value: String,
+    },
     Tags {
         items: Vec<String>,
     },
@@ -67,11 +70,17 @@ pub fn rows_for(intent: &ReaderIntent, meta: &ReaderMeta) -> Vec<RowSpec> {
     }
 
     if !matches!(intent, ReaderIntent::Redirect { .. })
-        && let Some(date) = meta.display_date()
+        && let Some(date) = meta.date_row_value()
     {
         rows.push(RowSpec::Date { value: date });
     }
 
+    if let Some(access) = &meta.access {
+        rows.push(RowSpec::Access {
+            value: access.message(),
+        });
+    }
+
     let wants_tags = matches!(intent, ReaderIntent::Markdown { .. })
         || matches!(intent, ReaderIntent::Asset { media_type, .. } if media_type == "application/pdf");
     if wants_tags && !meta.tags.is_empty() {
@@ -146,7 +155,12 @@ pub fn Ident(meta: Memo<ReaderMeta>) -> impl IntoView {
 pub fn TitleBlock(intent: Memo<ReaderIntent>, meta: Memo<ReaderMeta>) -> impl IntoView {
     view! {
         <div class=css::titleBlock>
-            <h1 class=css::title>{move || meta.get().title.clone()}</h1>
+            <h1 class=css::title>
+                {move || meta.get().title.clone()}
+                {move || meta.get().draft.then(|| view! {
+                    <span class=css::draftBadge>"draft"</span>
+                })}
+            </h1>
             {move || {
                 let i = intent.get();
                 let m = meta.get();
@@ -201,6 +215,17 @@ fn render_row(spec: RowSpec) -> AnyView {
             </MetaRow>
         }
         .into_any(),
+        RowSpec::Access { value } => view! {
+            <MetaRow
+                label="Access"
+                row_class=css::metaRow
+                key_class=css::metaKey
+                value_class=css::metaValue
+            >
+                {value}
+            </MetaRow>
+        }
+        .into_any(),
         RowSpec::Tags { items } => view! {
             <MetaRow
                 label="Tags"
@@ -245,6 +270,7 @@ mod tests {
             title: "x".to_string(),
             canonical_path: vp("/x.md"),
             modified_iso: modified_iso.map(String::from),
+            modified_relative: None,
             date: date.map(String::from),
             size_pretty: None,
             tags: vec![],
@@ -255,6 +281,8 @@ mod tests {
             page_count: None,
             image_dimensions: None,
             word_count: None,
+            access: None,
+            draft: false,
         }
     }
 
@@ -304,6 +332,26 @@ mod tests {
         assert!(rows.is_empty(), "redirect rows should be empty: {rows:?}");
     }
 
+    #[wasm_bindgen_test]
+    fn access_row_carries_the_notice_message() {
+        use super::super::meta::AccessNotice;
+
+        let intent = ReaderIntent::Markdown {
+            node_path: vp("/x.md"),
+        };
+        let mut m = meta_with(None, None);
+        assert!(
+            rows_for(&intent, &m)
+                .iter()
+                .all(|r| !matches!(r, RowSpec::Access { .. }))
+        );
+
+        m.access = Some(AccessNotice::Granted);
+        assert!(rows_for(&intent, &m).contains(&RowSpec::Access {
+            value: "You have access".to_string(),
+        }));
+    }
+
     #[wasm_bindgen_test]
     fn image_caption_appears_only_when_description_set() {
         let intent = ReaderIntent::Asset {
@@ -2,17 +2,21 @@
 //!
 //! Renders mode toggles (rendered ↔ edit) plus cancel/save actions when
 //! in Edit, with a right-side state chip showing dirty / saving / synced.
-//! Visibility follows `edit_visible`; the bar is hidden entirely for
-//! non-author / non-mempool routes.
+//! The mode row follows `edit_visible` and is hidden for non-author /
+//! non-mempool routes; every reader gets the `print` row in View.
 
 use leptos::prelude::*;
+use websh_core::support::i18n::Message;
 
 use super::ReaderMode;
 use super::css;
 use super::shell::ReaderEditBindings;
+use crate::app::AppContext;
 
 #[component]
-pub fn ReaderToolbar(edit: ReaderEditBindings) -> impl IntoView {
+pub fn ReaderToolbar(edit: ReaderEditBindings, on_print: Callback<()>) -> impl IntoView {
+    let ctx = use_context::<AppContext>().expect("AppContext must be provided at root");
+    let text = move |message: Message| move || message.text(ctx.locale.get());
     let visible = Memo::new(move |_| {
         edit.mode.get() == ReaderMode::Edit
             || (edit.mode.get() == ReaderMode::View && edit.can_edit.get())
@@ -32,7 +36,8 @@ pub fn ReaderToolbar(edit: ReaderEditBindings) -> impl IntoView {
             css::modefnOpt.to_string()
         }
     });
-    let state_text = Memo::new(move |_| state_label(edit.saving.get(), edit.dirty.get()));
+    let state_text =
+        Memo::new(move |_| state_label(edit.saving.get(), edit.dirty.get()).text(ctx.locale.get()));
     let state_class_name = Memo::new(move |_| {
         let modifier = state_class(edit.saving.get(), edit.dirty.get());
         if modifier.is_empty() {
@@ -42,12 +47,14 @@ pub fn ReaderToolbar(edit: ReaderEditBindings) -> impl IntoView {
         }
     });
 
+    let viewing = Memo::new(move |_| edit.mode.get() == ReaderMode::View);
+
     view! {
-        <Show when=move || visible.get()>
-            <div class=css::modefn>
+        <div class=css::modefn>
+            <Show when=move || visible.get()>
                 <div class=css::modefnRow>
                     <span class=css::modefnMark>"*"</span>
-                    <span class=css::modefnLab>"mode"</span>
+                    <span class=css::modefnLab>{text(Message::ReaderMode)}</span>
                     <button
                         type="button"
                         class=move || view_class.get()
@@ -60,7 +67,7 @@ pub fn ReaderToolbar(edit: ReaderEditBindings) -> impl IntoView {
                             }
                         }
                     >
-                        "rendered"
+                        {text(Message::ReaderRendered)}
                         <span class=css::modefnKbd>"r"</span>
                     </button>
                     <span class=css::modefnSep>"·"</span>
@@ -76,7 +83,7 @@ pub fn ReaderToolbar(edit: ReaderEditBindings) -> impl IntoView {
                             }
                         }
                     >
-                        "edit"
+                        {text(Message::ReaderEdit)}
                         <span class=css::modefnKbd>"e"</span>
                     </button>
                     <Show when=move || edit.mode.get() == ReaderMode::Edit>
@@ -90,7 +97,7 @@ pub fn ReaderToolbar(edit: ReaderEditBindings) -> impl IntoView {
                                     edit.on_cancel.run(());
                                 }
                             }
-                        >"cancel"</button>
+                        >{text(Message::ReaderCancel)}</button>
                         <span class=css::modefnSep>"·"</span>
                         <button
                             type="button"
@@ -102,25 +109,38 @@ pub fn ReaderToolbar(edit: ReaderEditBindings) -> impl IntoView {
                                 }
                             }
                         >
-                            "save"
+                            {text(Message::ReaderSave)}
                             <span class=css::modefnKbd>"⌘S"</span>
                         </button>
                     </Show>
                     <span class=css::modefnSpacer></span>
                     <span class=move || state_class_name.get()>{move || state_text.get()}</span>
                 </div>
-            </div>
-        </Show>
+            </Show>
+            <Show when=move || viewing.get()>
+                <div class=css::modefnRow>
+                    <span class=css::modefnMark>"*"</span>
+                    <span class=css::modefnLab>{text(Message::ReaderPage)}</span>
+                    <button
+                        type="button"
+                        class=css::modefnOpt
+                        on:click=move |_| on_print.run(())
+                    >
+                        {text(Message::ReaderPrint)}
+                    </button>
+                </div>
+            </Show>
+        </div>
     }
 }
 
-fn state_label(saving: bool, dirty: bool) -> &'static str {
+fn state_label(saving: bool, dirty: bool) -> Message {
     if saving {
-        "saving…"
+        Message::ReaderSaving
     } else if dirty {
-        "unsaved"
+        Message::ReaderUnsaved
     } else {
-        "synced"
+        Message::ReaderSynced
     }
 }
 
@@ -136,6 +156,7 @@ fn state_class(saving: bool, dirty: bool) -> &'static str {
 mod tests {
     use super::*;
     use wasm_bindgen_test::*;
+    use websh_core::support::i18n::Locale;
 
     wasm_bindgen_test_configure!(run_in_browser);
 
@@ -149,7 +170,7 @@ mod tests {
         ];
 
         for (saving, dirty, label, class) in cases {
-            assert_eq!(state_label(saving, dirty), label);
+            assert_eq!(state_label(saving, dirty).text(Locale::En), label);
             assert_eq!(state_class(saving, dirty), class);
         }
     }
@@ -1,4 +1,4 @@
-//! Image / generic asset view.
+//! Image / generic asset view, plus the native audio and video players.
 //!
 //! When `dimensions` are present in the manifest, they are echoed to the
 //! `<img>` tag's `width`/`height` attributes. Browsers use these to
@@ -10,6 +10,7 @@
 use leptos::prelude::*;
 
 use crate::features::reader::css;
+use crate::platform::BrowserAssetUrl;
 use websh_core::domain::ImageDim;
 
 #[component]
@@ -30,3 +31,39 @@ pub fn AssetReaderView(url: String, alt: String, dimensions: Option<ImageDim>) -
         </figure>
     }
 }
+
+/// `<audio controls>` over `url`; `title` names the track for assistive
+/// technology.
+#[component]
+pub fn AudioReaderView(url: BrowserAssetUrl, title: String) -> impl IntoView {
+    let url = StoredValue::new_local(url);
+    view! {
+        <figure class=css::mediaFigure>
+            <audio
+                class=css::audio
+                controls
+                preload="metadata"
+                aria-label=title
+                src=move || url.with_value(|url| url.as_str().to_string())
+            />
+        </figure>
+    }
+}
+
+/// `<video controls>` over `url`, sized like an image.
+#[component]
+pub fn VideoReaderView(url: BrowserAssetUrl, title: String) -> impl IntoView {
+    let url = StoredValue::new_local(url);
+    view! {
+        <figure class=css::mediaFigure>
+            <video
+                class=css::video
+                controls
+                preload="metadata"
+                playsinline
+                aria-label=title
+                src=move || url.with_value(|url| url.as_str().to_string())
+            />
+        </figure>
+    }
+}
@@ -0,0 +1,113 @@
+//! Loading placeholder — a skeleton of the page (title bar and shimmering
+//! paragraph blocks) under a progress bar while the body streams in. The
+//! bar is indeterminate when the server does not send `Content-Length`.
+
+use leptos::prelude::*;
+
+use crate::features::reader::css;
+use crate::platform::FetchProgress;
+use websh_core::support::format::format_size;
+
+/// Widths (percent) of the skeleton paragraph lines; each paragraph ends
+/// on a short line.
+const SKELETON_PARAGRAPHS: [&[u8]; 3] = [&[100, 96, 98, 62], &[97, 100, 88], &[99, 94, 100, 45]];
+
+#[component]
+pub fn ReaderLoadingView(progress: ReadSignal<Option<FetchProgress>>) -> impl IntoView {
+    let fraction = move || progress.get().and_then(|update| update.fraction());
+    view! {
+        <div class=css::loading role="status">
+            <div class=css::loadingLabel>{move || loading_label(progress.get())}</div>
+            <div
+                class=move || {
+                    if fraction().is_some() {
+                        css::progressTrack.to_string()
+                    } else {
+                        format!("{} {}", css::progressTrack, css::progressIndeterminate)
+                    }
+                }
+                role="progressbar"
+                aria-valuemin="0"
+                aria-valuemax="100"
+                aria-valuenow=move || fraction().map(|value| format!("{:.0}", value * 100.0))
+            >
+                <div
+                    class=css::progressFill
+                    style=move || {
+                        fraction()
+                            .map(|value| format!("width: {:.1}%", value * 100.0))
+                            .unwrap_or_default()
+                    }
+                ></div>
+            </div>
+            <div class=css::skeleton aria-hidden="true">
+                <div class=format!("{} {}", css::skeletonBlock, css::skeletonTitle)></div>
+                {SKELETON_PARAGRAPHS
+                    .iter()
+                    .map(|lines| view! {
+                        <div class=css::skeletonParagraph>
+                            {lines
+                                .iter()
+                                .map(|width| view! {
+                                    <div
+                                        class=format!("{} {}", css::skeletonBlock, css::skeletonLine)
+                                        style=format!("width: {width}%")
+                                    ></div>
+                                })
+                                .collect_view()}
+                        </div>
+                    })
+                    .collect_view()}
+            </div>
+        </div>
+    }
+}
+
+fn loading_label(progress: Option<FetchProgress>) -> String {
+    let Some(progress) = progress.filter(|update| update.received > 0) else {
+        return "Loading...".to_string();
+    };
+    let received = format_size(Some(progress.received), false);
+    match (progress.fraction(), progress.total) {
+        (Some(fraction), Some(total)) => format!(
+            "Loading... {:.0}% ({received} / {})",
+            fraction * 100.0,
+            format_size(Some(total), false)
+        ),
+        _ => format!("Loading... {received}"),
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn label_reports_percentage_only_with_known_length() {
+        assert_eq!(loading_label(None), "Loading...");
+        assert_eq!(
+            loading_label(Some(FetchProgress {
+                received: 0,
+                total: Some(2_000_000),
+            })),
+            "Loading..."
+        );
+        assert_eq!(
+            loading_label(Some(FetchProgress {
+                received: 500_000,
+                total: Some(2_000_000),
+            })),
+            "Loading... 25% (500.0K / 2.0M)"
+        );
+        assert_eq!(
+            loading_label(Some(FetchProgress {
+                received: 1_500,
+                total: None,
+            })),
+            "Loading... 1.5K"
+        );
+    }
+}
@@ -84,7 +84,7 @@ fn TocSide(entries: Signal<Vec<HeadingEntry>>) -> impl IntoView {
 }
 
 #[cfg(target_arch = "wasm32")]
-fn scroll_to_anchor(id: &str) {
+pub(crate) fn scroll_to_anchor(id: &str) {
     let Some(window) = web_sys::window() else {
         return;
     };
@@ -100,4 +100,4 @@ fn scroll_to_anchor(id: &str) {
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-fn scroll_to_anchor(_id: &str) {}
+pub(crate) fn scroll_to_anchor(_id: &str) {}
@@ -2,14 +2,16 @@
 
 pub mod asset;
 pub mod html;
+pub mod loading;
 pub mod markdown;
 pub mod pdf;
 pub mod plain;
 pub mod redirect;
 
-pub use asset::AssetReaderView;
+pub use asset::{AssetReaderView, AudioReaderView, VideoReaderView};
 pub use html::HtmlReaderView;
+pub use loading::ReaderLoadingView;
 pub use markdown::{MarkdownEditorView, MarkdownReaderView};
-pub use pdf::PdfReaderView;
+pub use pdf::{PdfReaderView, PdfViewer};
 pub use plain::PlainReaderView;
-pub use redirect::RedirectingView;
+pub use redirect::{RedirectConfirmView, RedirectingView};
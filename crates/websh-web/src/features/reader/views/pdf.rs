@@ -1,15 +1,87 @@
 //! PDF view — abstract section + iframe wrapper with fullscreen toggle.
+//!
+//! The frame shows either the browser's built-in viewer or a pdf.js build
+//! bundled with the site (`PDF_VIEWER=pdfjs`). Nothing is loaded from a
+//! third-party host, so PDFs keep working on offline and IPFS deployments.
+
+use std::cell::Cell;
 
 use leptos::prelude::*;
 
+use crate::config::{PDFJS_VIEWER_PATH, PROBE_FETCH_TIMEOUT_MS};
 use crate::features::reader::css;
-use crate::platform::BrowserAssetUrl;
+use crate::platform::{BrowserAssetUrl, FetchPolicy, fetch_content};
 use websh_core::domain::PageSize;
 
+/// Which viewer the PDF frame loads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PdfViewer {
+    /// The browser's own viewer, embedding the file directly.
+    Native,
+    /// The pdf.js viewer under [`PDFJS_VIEWER_PATH`].
+    Bundled,
+}
+
+impl PdfViewer {
+    /// The viewer `PDF_VIEWER` asks for: `pdfjs` (any case) picks the
+    /// bundled one, anything else the browser's.
+    pub fn requested(value: Option<&str>) -> Self {
+        match value.map(str::trim) {
+            Some(value) if value.eq_ignore_ascii_case("pdfjs") => Self::Bundled,
+            _ => Self::Native,
+        }
+    }
+
+    /// The viewer to use for a `requested` one: the bundled viewer only
+    /// when this deployment ships it, the browser's otherwise.
+    pub fn resolve(requested: Self, bundled_available: bool) -> Self {
+        match requested {
+            Self::Bundled if bundled_available => Self::Bundled,
+            _ => Self::Native,
+        }
+    }
+}
+
+/// Frame `src` showing the PDF at `url` in `viewer`. pdf.js resolves its
+/// `file` parameter against the viewer page, so `url` must be absolute
+/// for [`PdfViewer::Bundled`].
+pub fn pdf_frame_src(url: &str, viewer: PdfViewer) -> String {
+    match viewer {
+        // Fit-page-width hint for built-in viewers (Chrome/Firefox honor
+        // it, Safari ignores). Hash fragment, no network impact.
+        PdfViewer::Native => format!("{url}#view=FitH&zoom=page-width"),
+        PdfViewer::Bundled => format!(
+            "{PDFJS_VIEWER_PATH}?file={}#zoom=page-width",
+            String::from(js_sys::encode_uri_component(url))
+        ),
+    }
+}
+
+thread_local! {
+    static BUNDLED_VIEWER_AVAILABLE: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+/// Whether this deployment ships the pdf.js viewer, probed once per page.
+pub async fn bundled_viewer_available() -> bool {
+    if let Some(available) = BUNDLED_VIEWER_AVAILABLE.with(Cell::get) {
+        return available;
+    }
+    let available = fetch_content(
+        PDFJS_VIEWER_PATH,
+        None,
+        FetchPolicy::new(PROBE_FETCH_TIMEOUT_MS),
+    )
+    .await
+    .is_ok();
+    BUNDLED_VIEWER_AVAILABLE.with(|cell| cell.set(Some(available)));
+    available
+}
+
 #[component]
 pub fn PdfReaderView(
     title: Signal<String>,
     url: BrowserAssetUrl,
+    viewer: PdfViewer,
     size_pretty: Option<String>,
     abstract_text: String,
     page_size: Option<PageSize>,
@@ -18,10 +90,12 @@ pub fn PdfReaderView(
     let url = StoredValue::new_local(url);
     let download_url = move || url.with_value(|url| url.as_str().to_string());
     let open_url = move || url.with_value(|url| url.as_str().to_string());
-    // Fit-page-width hint for built-in viewers (Chrome/Firefox honor it,
-    // Safari ignores). Hash fragment, no network impact.
-    let viewer_url =
-        move || url.with_value(|url| format!("{}#view=FitH&zoom=page-width", url.as_str()));
+    let viewer_url = move || {
+        url.with_value(|url| match viewer {
+            PdfViewer::Native => pdf_frame_src(url.as_str(), viewer),
+            PdfViewer::Bundled => pdf_frame_src(&absolute_url(url.as_str()), viewer),
+        })
+    };
     let aspect_style =
         page_size.map(|geom| format!("aspect-ratio: {} / {};", geom.width, geom.height));
     let page_count_label =
@@ -82,6 +156,17 @@ pub fn PdfReaderView(
     }
 }
 
+/// `url` resolved against the page, so the pdf.js viewer (which lives
+/// deeper in the tree) finds the same file.
+fn absolute_url(url: &str) -> String {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.base_uri().ok().flatten())
+        .and_then(|base| web_sys::Url::new_with_base(url, &base).ok())
+        .map(|resolved| resolved.href())
+        .unwrap_or_else(|| url.to_string())
+}
+
 /// Mirror the browser's fullscreen state into `is_fullscreen` so Esc /
 /// native exit also flip the label.
 #[cfg(target_arch = "wasm32")]
@@ -142,3 +227,47 @@ fn toggle_fullscreen(frame_ref: NodeRef<leptos::html::Div>) {
 
 #[cfg(not(target_arch = "wasm32"))]
 fn toggle_fullscreen(_frame_ref: NodeRef<leptos::html::Div>) {}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn pdf_viewer_var_selects_the_bundled_viewer() {
+        assert_eq!(PdfViewer::requested(None), PdfViewer::Native);
+        assert_eq!(PdfViewer::requested(Some("native")), PdfViewer::Native);
+        assert_eq!(PdfViewer::requested(Some(" PDFJS ")), PdfViewer::Bundled);
+    }
+
+    #[wasm_bindgen_test]
+    fn missing_bundled_viewer_falls_back_to_native() {
+        assert_eq!(
+            PdfViewer::resolve(PdfViewer::Bundled, true),
+            PdfViewer::Bundled
+        );
+        assert_eq!(
+            PdfViewer::resolve(PdfViewer::Bundled, false),
+            PdfViewer::Native
+        );
+        assert_eq!(
+            PdfViewer::resolve(PdfViewer::Native, true),
+            PdfViewer::Native
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn frame_src_embeds_natively_or_points_pdfjs_at_the_file() {
+        assert_eq!(
+            pdf_frame_src("./content/paper.pdf", PdfViewer::Native),
+            "./content/paper.pdf#view=FitH&zoom=page-width"
+        );
+        assert_eq!(
+            pdf_frame_src("https://example.com/a b.pdf", PdfViewer::Bundled),
+            "assets/vendor/pdfjs/web/viewer.html\
+             ?file=https%3A%2F%2Fexample.com%2Fa%20b.pdf#zoom=page-width"
+        );
+    }
+}
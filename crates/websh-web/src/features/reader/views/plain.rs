@@ -1,12 +1,71 @@
-//! Plain-text view — `<pre>` wrapper.
+//! Plain-text view — `<pre>` wrapper, optionally marking one line.
 
 use leptos::prelude::*;
 
+use super::markdown::scroll_to_anchor;
 use crate::features::reader::css;
 
 #[component]
-pub fn PlainReaderView(text: String) -> impl IntoView {
+pub fn PlainReaderView(
+    text: String,
+    /// 1-based line to highlight and scroll to, from an `l=` deep link.
+    line: Option<u32>,
+) -> impl IntoView {
+    let Some((before, marked, after)) = line.and_then(|line| split_at_line(&text, line)) else {
+        return view! { <pre class=css::rawText>{text}</pre> }.into_any();
+    };
+    let (before, marked, after) = (before.to_string(), marked.to_string(), after.to_string());
+    let id = format!("L{}", line.unwrap_or_default());
+    request_animation_frame({
+        let id = id.clone();
+        move || scroll_to_anchor(&id)
+    });
     view! {
-        <pre class=css::rawText>{text}</pre>
+        <pre class=css::rawText>
+            {before}
+            <mark id=id class=css::lineHighlight>{marked}</mark>
+            {after}
+        </pre>
+    }
+    .into_any()
+}
+
+/// Split `text` around 1-based `line` (without its newline). `None` when
+/// the text has fewer lines.
+fn split_at_line(text: &str, line: u32) -> Option<(&str, &str, &str)> {
+    let index = usize::try_from(line.checked_sub(1)?).ok()?;
+    let mut start = 0;
+    for _ in 0..index {
+        start += text[start..].find('\n')? + 1;
+    }
+    if start >= text.len() {
+        return None;
+    }
+    let end = text[start..]
+        .find('\n')
+        .map_or(text.len(), |offset| start + offset);
+    Some((&text[..start], &text[start..end], &text[end..]))
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn splits_around_the_requested_line() {
+        let text = "one\ntwo\nthree\n";
+        assert_eq!(split_at_line(text, 1), Some(("", "one", "\ntwo\nthree\n")));
+        assert_eq!(split_at_line(text, 2), Some(("one\n", "two", "\nthree\n")));
+        assert_eq!(split_at_line("a\nb", 2), Some(("a\n", "b", "")));
+    }
+
+    #[wasm_bindgen_test]
+    fn lines_past_the_end_or_zero_do_not_split() {
+        assert_eq!(split_at_line("one\ntwo\n", 3), None);
+        assert_eq!(split_at_line("one", 4), None);
+        assert_eq!(split_at_line("one", 0), None);
     }
 }
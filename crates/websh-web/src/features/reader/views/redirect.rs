@@ -1,4 +1,5 @@
-//! Redirect placeholder — short message while the browser navigates away.
+//! Redirect views: a placeholder while the browser navigates away, and a
+//! confirmation step before leaving for another origin.
 
 use leptos::prelude::*;
 
@@ -10,3 +11,19 @@ pub fn RedirectingView() -> impl IntoView {
         <div class=css::redirecting>"Redirecting…"</div>
     }
 }
+
+/// Shows where a link leads and lets the visitor choose to follow it.
+#[component]
+pub fn RedirectConfirmView(url: String, host: String) -> impl IntoView {
+    view! {
+        <div class=css::redirectConfirm>
+            <p class=css::redirectNotice>"This link leaves the site for:"</p>
+            <p class=css::redirectTarget>
+                <code>{url.clone()}</code>
+            </p>
+            <a class=css::redirectContinue href=url rel="noopener noreferrer">
+                {format!("Continue to {host} →")}
+            </a>
+        </div>
+    }
+}
@@ -18,11 +18,16 @@ use wasm_bindgen::prelude::Closure;
 
 #[cfg(target_arch = "wasm32")]
 use crate::app::AppContext;
+use crate::features::chrome::SiteChrome;
 use crate::features::home::HomePage;
 use crate::features::ledger::LedgerPage;
 use crate::features::ledger::routes::{LEDGER_ROUTE, is_ledger_filter_route_segment};
 use crate::features::reader::{Reader, ReaderFrame};
 use crate::features::terminal::Shell;
+use crate::runtime::MountLoadStatus;
+use crate::shared::components::{SiteContentFrame, SiteSurface};
+
+stylance::import_crate_style!(not_found_css, "src/features/not_found.module.css");
 
 /// URL patterns that bypass the engine and produce a synthetic [`RouteFrame`].
 ///
@@ -61,7 +66,8 @@ use websh_core::domain::VirtualPath;
 use websh_core::filesystem::FsEngine;
 use websh_core::filesystem::{
     RenderIntent, ResolvedKind, RouteFrame, RouteRequest, RouteResolution, RouteSurface,
-    is_new_request_path,
+    content_href_for_path, display_path_for, is_new_request_path, request_path_for_canonical_path,
+    request_target_path,
 };
 
 /// Main application router.
@@ -77,6 +83,9 @@ pub fn RouterView() -> impl IntoView {
     let ctx = use_context::<AppContext>().expect("AppContext must be provided");
 
     // Raw request from URL hash (updated on hashchange).
+    #[cfg(target_arch = "wasm32")]
+    let _raw_request = RwSignal::new(hold_deep_link(ctx, current_route_request()));
+    #[cfg(not(target_arch = "wasm32"))]
     let _raw_request = RwSignal::new(current_route_request());
 
     // Set up hashchange event listener (runs once on mount).
@@ -86,7 +95,7 @@ pub fn RouterView() -> impl IntoView {
         use leptos::prelude::on_cleanup;
         use wasm_bindgen::JsCast;
         let closure = Closure::wrap(Box::new(move || {
-            _raw_request.set(current_route_request());
+            _raw_request.set(hold_deep_link(ctx, current_route_request()));
         }) as Box<dyn Fn()>);
 
         if let Some(window) = web_sys::window() {
@@ -122,6 +131,12 @@ pub fn RouterView() -> impl IntoView {
     let route = Memo::new(move |_| None::<RouteFrame>);
 
     install_terminal_focus_effect(_raw_request, route);
+    #[cfg(target_arch = "wasm32")]
+    install_document_head_effect(ctx, _raw_request, route);
+    #[cfg(target_arch = "wasm32")]
+    install_nav_history_effect(ctx, _raw_request);
+    #[cfg(target_arch = "wasm32")]
+    install_usage_effect(ctx, route);
 
     view! {
         {move || {
@@ -168,7 +183,7 @@ pub fn RouterView() -> impl IntoView {
                             .into_any()
                         }
                     },
-                    None => view! { <NotFound /> }.into_any(),
+                    None => view! { <NotFound request=request.clone() /> }.into_any(),
                 }
             }
         }}
@@ -191,7 +206,22 @@ fn new_compose_frame() -> RouteFrame {
     }
 }
 
-fn route_request_needs_system_fs(request: &RouteRequest) -> bool {
+/// Hand any deep-link extras on `request` to the context for the view of
+/// that route to consume, and drop them from the address bar so they apply
+/// only once. Returns the plain request the router resolves.
+#[cfg(target_arch = "wasm32")]
+fn hold_deep_link(ctx: AppContext, request: RouteRequest) -> RouteRequest {
+    if request.link.is_empty() {
+        return request;
+    }
+    let RouteRequest { url_path, link } = request;
+    ctx.set_deep_link(url_path.clone(), link);
+    let plain = RouteRequest::new(url_path);
+    crate::platform::dom::rewrite_route(&plain);
+    plain
+}
+
+pub(crate) fn route_request_needs_system_fs(request: &RouteRequest) -> bool {
     let trimmed = request.url_path.trim_matches('/');
     if is_runtime_state_request(trimmed) {
         return true;
@@ -275,13 +305,189 @@ fn install_terminal_focus_effect(
     });
 }
 
+/// Records every route landed on in the navigation history `dirs` shows.
+#[cfg(target_arch = "wasm32")]
+fn install_nav_history_effect(ctx: AppContext, raw_request: RwSignal<RouteRequest>) {
+    Effect::new(move |_| {
+        let request = raw_request.get();
+        ctx.nav_history
+            .update(|history| history.observe(&request.url_path));
+    });
+}
+
+/// Counts each directory visited and file opened for `stats`, once per
+/// arrival rather than on every filesystem reload.
+#[cfg(target_arch = "wasm32")]
+fn install_usage_effect(ctx: AppContext, route: Memo<Option<RouteFrame>>) {
+    use websh_core::filesystem::display_path_for;
+
+    Effect::new(move |prev: Option<Option<VirtualPath>>| {
+        let frame = route.get()?;
+        let path = frame.resolution.node_path.clone();
+        if prev.flatten().as_ref() != Some(&path) {
+            let shown = display_path_for(&path);
+            match frame.intent {
+                RenderIntent::DirectoryListing { .. } | RenderIntent::TerminalApp { .. } => {
+                    ctx.record_usage(|stats| stats.record_dir(&shown));
+                }
+                _ => ctx.record_usage(|stats| stats.record_file(&shown)),
+            }
+        }
+        Some(path)
+    });
+}
+
+/// Keeps `document.title` and the meta description in step with the route.
+#[cfg(target_arch = "wasm32")]
+fn install_document_head_effect(
+    ctx: AppContext,
+    raw_request: RwSignal<RouteRequest>,
+    route: Memo<Option<RouteFrame>>,
+) {
+    use crate::platform::dom::apply_document_head;
+    use crate::render::document_head::{HeadSubject, document_head};
+    use websh_core::domain::FsEntry;
+
+    Effect::new(move |_| {
+        let request = raw_request.get();
+        let head = match BuiltinRoute::detect(&request) {
+            Some(BuiltinRoute::Home) => document_head(HeadSubject::Home),
+            Some(BuiltinRoute::LedgerFilter) => {
+                document_head(HeadSubject::Section(request.url_path.trim_matches('/')))
+            }
+            Some(BuiltinRoute::NewCompose) => document_head(HeadSubject::Section("new")),
+            None => match route.get() {
+                None => document_head(HeadSubject::NotFound),
+                Some(RouteFrame {
+                    intent: RenderIntent::TerminalApp { .. },
+                    ..
+                }) => document_head(HeadSubject::Section("websh")),
+                Some(frame) => {
+                    let path = &frame.resolution.node_path;
+                    let mount_label = ctx
+                        .runtime_mounts_snapshot()
+                        .into_iter()
+                        .find(|mount| !path.is_root() && &mount.root == path)
+                        .map(|mount| mount.label);
+                    let fs = if route_request_needs_system_fs(&request) {
+                        ctx.system_global_fs
+                    } else {
+                        ctx.view_global_fs
+                    };
+                    fs.with(|fs| {
+                        document_head(HeadSubject::Node {
+                            path,
+                            meta: fs.get_entry(path).map(FsEntry::meta),
+                            mount_label: mount_label.as_deref(),
+                            is_directory: frame.resolution.kind == ResolvedKind::Directory,
+                        })
+                    })
+                }
+            },
+        };
+        apply_document_head(&head);
+    });
+}
+
+/// How many near-miss siblings the not-found view offers.
+const NOT_FOUND_SUGGESTIONS: usize = 5;
+
+/// Shown when a route names a path that does not exist. Points at the
+/// deepest existing ancestor and any similarly named siblings there.
 #[component]
-fn NotFound() -> impl IntoView {
+fn NotFound(request: RouteRequest) -> impl IntoView {
+    let ctx = use_context::<AppContext>().expect("AppContext must be provided");
+    let target = request_target_path(&request);
+    let display = target
+        .as_ref()
+        .map(|(_, path)| display_path_for(path))
+        .unwrap_or_else(|| request.url_path.clone());
+    let surface = target
+        .as_ref()
+        .map(|(surface, _)| *surface)
+        .unwrap_or(RouteSurface::Content);
+
+    let lookup = Memo::new(move |_| {
+        ctx.view_global_fs.with(|fs| match &target {
+            Some((_, path)) => (
+                fs.nearest_existing_ancestor(path),
+                fs.near_miss_suggestions(path, NOT_FOUND_SUGGESTIONS),
+            ),
+            None => (VirtualPath::root(), Vec::new()),
+        })
+    });
+    let root_loading = move || {
+        matches!(
+            ctx.mount_status_for(&VirtualPath::root()),
+            Some(MountLoadStatus::Loading { .. })
+        )
+    };
+    let chrome_route = Memo::new(move |_| directory_frame(lookup.get().0));
+
     view! {
-        <div style="padding: 2rem; font-family: monospace;">
-            <h1>"404"</h1>
-            <p>"No route matched the current path."</p>
-        </div>
+        <SiteSurface class=not_found_css::surface>
+            <SiteChrome route=chrome_route />
+            <SiteContentFrame class=not_found_css::page>
+                <p class=not_found_css::code>"404 · not found"</p>
+                <h1 class=not_found_css::path>{display}</h1>
+                <Show
+                    when=move || !root_loading()
+                    fallback=|| view! { <p class=not_found_css::hint>"mounting filesystems…"</p> }
+                >
+                    {move || {
+                        let (ancestor, suggestions) = lookup.get();
+                        let suggestions_view = (!suggestions.is_empty()).then(|| {
+                            view! {
+                                <p class=not_found_css::hint>"Did you mean:"</p>
+                                <ul class=not_found_css::suggestions>
+                                    {suggestions
+                                        .into_iter()
+                                        .map(|path| {
+                                            let href = not_found_href(&path, surface);
+                                            view! { <li><a href=href>{display_path_for(&path)}</a></li> }
+                                        })
+                                        .collect_view()}
+                                </ul>
+                            }
+                        });
+                        let ancestor_href = not_found_href(&ancestor, surface);
+                        let ancestor_label = format!("go to {}", display_path_for(&ancestor));
+                        view! {
+                            {suggestions_view}
+                            <div class=not_found_css::actions>
+                                <a class=not_found_css::action href=ancestor_href>{ancestor_label}</a>
+                                <a class=not_found_css::action href="#/">"home"</a>
+                            </div>
+                        }
+                    }}
+                </Show>
+            </SiteContentFrame>
+        </SiteSurface>
+    }
+}
+
+fn not_found_href(path: &VirtualPath, surface: RouteSurface) -> String {
+    match surface {
+        RouteSurface::Content => content_href_for_path(path.as_str()),
+        RouteSurface::Shell => format!("#{}", request_path_for_canonical_path(path, surface)),
+    }
+}
+
+fn directory_frame(node_path: VirtualPath) -> RouteFrame {
+    let request = RouteRequest::new(request_path_for_canonical_path(
+        &node_path,
+        RouteSurface::Content,
+    ));
+    RouteFrame {
+        request: request.clone(),
+        resolution: RouteResolution {
+            request_path: request.url_path,
+            surface: RouteSurface::Content,
+            node_path: node_path.clone(),
+            kind: ResolvedKind::Directory,
+            params: BTreeMap::new(),
+        },
+        intent: RenderIntent::DirectoryListing { node_path },
     }
 }
 
@@ -351,4 +557,35 @@ mod builtin_route_tests {
             );
         }
     }
+
+    #[wasm_bindgen_test]
+    fn ledger_filter_frames_list_the_requested_category() {
+        let ledger = ledger_filter_frame(RouteRequest::new("/ledger"));
+        assert_eq!(
+            ledger.intent,
+            RenderIntent::DirectoryListing {
+                node_path: VirtualPath::root()
+            }
+        );
+
+        let writing = ledger_filter_frame(RouteRequest::new("/writing"));
+        assert_eq!(writing.resolution.request_path, "/writing");
+        assert_eq!(
+            writing.intent,
+            RenderIntent::DirectoryListing {
+                node_path: VirtualPath::root().join("writing")
+            }
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn home_and_compose_frames_are_synthetic() {
+        let home = home_frame(RouteRequest::new("/"));
+        assert_eq!(home.resolution.surface, RouteSurface::Content);
+        assert_eq!(home.resolution.node_path, VirtualPath::root());
+
+        let compose = new_compose_frame();
+        assert_eq!(compose.request.url_path, "/new");
+        assert!(ReaderFrame::try_from(compose).is_ok());
+    }
 }
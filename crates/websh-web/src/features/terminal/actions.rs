@@ -1,16 +1,41 @@
+use futures_util::stream::{self, StreamExt};
 use leptos::prelude::*;
 
-use crate::app::AppContext;
 use crate::app::RuntimeServices;
-use crate::platform::dom::push_route;
+use crate::app::{AppContext, NavHistory};
+use crate::config::{
+    APP_NAME, EXPORT_ZIP_CONCURRENCY, EXPORT_ZIP_CONFIRM_BYTES, FEED_FILENAME, FETCH_ALLOW_VAR,
+    SEARCH_RESULT_LIMIT, SESSION_TRANSCRIPT_FILENAME,
+};
+use crate::platform::dom::{
+    copy_to_clipboard, download_bytes, download_file, download_text, push_route,
+};
+use crate::platform::fetch_response;
+use crate::platform::redirect::{UrlValidation, parse_host_list, validate_fetch_url};
 use crate::runtime::shell_execution_context;
-use websh_core::filesystem::route_cwd;
-use websh_core::shell::OutputLine;
+use websh_core::domain::{ChangeType, RuntimeMount, VirtualPath};
+use websh_core::filesystem::{RouteRequest, route_cwd};
+use websh_core::shell::{
+    AppEventKind, ChecksumTarget, CommandError, EntryCopy, EntryCopyKind, EntryRemoval, FeedOutput,
+    FileStage, OutputLine, OutputLineData, ZipExport, lines_to_plain_text,
+};
 use websh_core::shell::{
-    SideEffect, autocomplete, execute_pipeline_with_context, get_hint, parse_input_with_env,
+    ExecutionContext, ParsedCommand, Pipeline, SideEffect, apply_filter_in, autocomplete,
+    execute_pipeline_with_context, fetch_response_lines, file_stage_output, get_hint,
+    parse_input_with_substitution, session_env, substitution_text,
 };
+use websh_core::support::checksum::{self, ChecksumOutcome};
+use websh_core::support::feed::AtomFeed;
+use websh_core::support::format::format_size;
+use websh_core::support::search;
+use websh_core::support::text::append_text;
+use websh_core::support::zip::{DosDateTime, ZipWriter};
 
 use super::RouteContext;
+use super::hooks::HistorySearchCallback;
+use super::session;
+use super::transcript::{self, TranscriptHeader};
+use super::tutorial;
 
 fn handle_login(ctx: AppContext) {
     wasm_bindgen_futures::spawn_local(async move {
@@ -66,13 +91,565 @@ fn handle_logout(ctx: &AppContext) {
     }
 }
 
+fn handle_copy_session(ctx: AppContext) {
+    let transcript = ctx
+        .terminal
+        .history
+        .with_untracked(|history| lines_to_plain_text(history.iter()));
+    let line_count = transcript.lines().count();
+    wasm_bindgen_futures::spawn_local(async move {
+        match copy_to_clipboard(&transcript).await {
+            Ok(()) => ctx.terminal.push_output(OutputLine::success(format!(
+                "copy: {line_count} lines copied to clipboard"
+            ))),
+            Err(clipboard_error) => match download_text(SESSION_TRANSCRIPT_FILENAME, &transcript) {
+                Ok(()) => ctx.terminal.push_output(OutputLine::info(format!(
+                    "copy: clipboard unavailable ({clipboard_error}); downloaded {SESSION_TRANSCRIPT_FILENAME}"
+                ))),
+                Err(error) => ctx
+                    .terminal
+                    .push_output(OutputLine::error(format!("copy: {error}"))),
+            },
+        }
+    });
+}
+
+/// Run `fetch`: check `url` against the redirect allowlist plus
+/// `FETCH_ALLOW`, then print the response. When piped, only the body goes
+/// through `filters`.
+fn handle_fetch(ctx: AppContext, url: String, filters: Vec<ParsedCommand>) {
+    let extra_hosts = ctx.runtime_state.with_untracked(|rs| {
+        rs.env
+            .get(FETCH_ALLOW_VAR)
+            .map(|value| parse_host_list(value))
+            .unwrap_or_default()
+    });
+    let url = match validate_fetch_url(&url, &extra_hosts) {
+        UrlValidation::Valid(url) => url,
+        UrlValidation::Invalid(error) => {
+            ctx.terminal
+                .push_output(OutputLine::error(format!("fetch: {error}")));
+            return;
+        }
+    };
+
+    wasm_bindgen_futures::spawn_local(async move {
+        let pending = OutputLine::info(format!("fetching {url}…"));
+        let pending_id = pending.id;
+        ctx.terminal.push_output(pending);
+
+        let response = match fetch_response(&url).await {
+            Ok(response) => response,
+            Err(error) => {
+                ctx.log_event(AppEventKind::FetchFailed {
+                    target: url.clone(),
+                    error: error.to_string(),
+                });
+                let error = CommandError::Network {
+                    cmd: "fetch".to_string(),
+                    message: error.to_string(),
+                };
+                let line = OutputLine::error(error.to_string());
+                if !ctx.terminal.replace_line(pending_id, line.clone()) {
+                    ctx.terminal.push_output(line);
+                }
+                return;
+            }
+        };
+
+        let lines = fetch_response_lines(&response);
+        let mut output = if filters.is_empty() {
+            let mut output = lines.head;
+            output.extend(lines.body);
+            output
+        } else {
+            let locale = ctx.locale.get_untracked();
+            filters.iter().fold(lines.body, |body, stage| {
+                apply_filter_in(&stage.name, &stage.args, body, locale).output
+            })
+        };
+        output.extend(lines.truncated);
+        let mut output = output.into_iter();
+        let first = output
+            .next()
+            .unwrap_or_else(|| OutputLine::info("fetch: no output"));
+        if !ctx.terminal.replace_line(pending_id, first.clone()) {
+            ctx.terminal.push_output(first);
+        }
+        ctx.terminal.push_lines(output.collect());
+    });
+}
+
+/// Run an `xargs cat` / `xargs wc` stage: read its files in order, then
+/// print what the command and any later filters make of them.
+fn handle_read_files(ctx: AppContext, stage: FileStage) {
+    wasm_bindgen_futures::spawn_local(async move {
+        let mut texts = Vec::with_capacity(stage.files.len());
+        for file in &stage.files {
+            texts.push(
+                ctx.read_text(&file.path)
+                    .await
+                    .map_err(|error| error.to_string()),
+            );
+        }
+        let result = file_stage_output(&stage, texts, ctx.locale.get_untracked());
+        ctx.terminal.push_lines(result.output);
+    });
+}
+
+/// Read each markdown file (from the search index when already stripped),
+/// updating a progress line as files arrive, then print the ranked matches.
+fn handle_search(ctx: AppContext, query: String, paths: Vec<VirtualPath>) {
+    wasm_bindgen_futures::spawn_local(async move {
+        let total = paths.len();
+        let progress_line =
+            |done: usize| OutputLine::info(format!("searching… ({done}/{total} files)"));
+        let first = progress_line(0);
+        let mut progress_id = first.id;
+        ctx.terminal.push_output(first);
+
+        let mut documents = Vec::with_capacity(total);
+        let mut unreadable = 0;
+        for (done, path) in paths.into_iter().enumerate() {
+            let text = match ctx.search_text(&path) {
+                Some(text) => Some(text),
+                None => match ctx.read_text(&path).await {
+                    Ok(source) => {
+                        let text = search::strip_markdown(&source);
+                        ctx.index_search_text(path.clone(), text.clone());
+                        Some(text)
+                    }
+                    Err(_) => {
+                        unreadable += 1;
+                        None
+                    }
+                },
+            };
+            if let Some(text) = text {
+                documents.push((path, text));
+            }
+            let line = progress_line(done + 1);
+            let id = line.id;
+            if ctx.terminal.replace_line(progress_id, line) {
+                progress_id = id;
+            }
+        }
+
+        let hits = search::rank(
+            documents.iter().map(|(path, text)| (path, text.as_str())),
+            &query,
+            SEARCH_RESULT_LIMIT,
+        );
+        let mut summary = if hits.is_empty() {
+            format!("search: no matches for '{query}' in {total} files")
+        } else {
+            format!("search: {} of {total} files match '{query}'", hits.len())
+        };
+        if unreadable > 0 {
+            summary.push_str(&format!(" ({unreadable} unreadable)"));
+        }
+        let summary = if hits.is_empty() {
+            OutputLine::error(summary)
+        } else {
+            OutputLine::info(summary)
+        };
+        if !ctx.terminal.replace_line(progress_id, summary.clone()) {
+            ctx.terminal.push_output(summary);
+        }
+        ctx.terminal.push_lines(
+            hits.into_iter()
+                .map(|hit| {
+                    OutputLine::file_entry(hit.key.as_str(), hit.snippet.marked("«", "»"), false)
+                        .with_path(hit.key.clone())
+                })
+                .collect(),
+        );
+    });
+}
+
+/// Append `text` to the current content of `path` (`tee -a`).
+fn handle_append(ctx: AppContext, path: VirtualPath, text: String) {
+    wasm_bindgen_futures::spawn_local(async move {
+        let existing = match ctx.read_text(&path).await {
+            Ok(existing) => existing,
+            Err(error) => {
+                ctx.terminal.push_output(OutputLine::error(format!(
+                    "tee: cannot read '{}': {error}",
+                    path.as_str()
+                )));
+                return;
+            }
+        };
+        let change = ChangeType::UpdateFile {
+            content: append_text(&existing, &text),
+            meta: None,
+            extensions: None,
+        };
+        let timestamp_ms = crate::platform::current_timestamp();
+        ctx.evict_text_cache_path(&path);
+        ctx.changes
+            .update(|cs| cs.upsert_at(path, change, timestamp_ms));
+    });
+}
+
+/// Read each `sha256` target in turn and print its digest, or with `check`
+/// an OK/FAILED line per file and a closing summary.
+fn handle_checksum(ctx: AppContext, targets: Vec<ChecksumTarget>, check: bool) {
+    wasm_bindgen_futures::spawn_local(async move {
+        let mut outcomes = Vec::with_capacity(targets.len());
+        for target in targets {
+            let bytes = ctx.read_bytes(&target.path).await;
+            let line = match (bytes, target.expected.as_deref()) {
+                (Ok(bytes), Some(expected)) if check => {
+                    let outcome = ChecksumOutcome::of(&bytes, expected);
+                    outcomes.push(outcome);
+                    let text = checksum::check_line(&target.display, outcome);
+                    if outcome.is_match() {
+                        OutputLine::success(text)
+                    } else {
+                        OutputLine::error(text)
+                    }
+                }
+                (Ok(bytes), _) => OutputLine::text(checksum::sha256sum_line(
+                    &checksum::sha256_hex(&bytes),
+                    &target.display,
+                )),
+                (Err(_), _) if check => {
+                    outcomes.push(ChecksumOutcome::Unreadable);
+                    OutputLine::error(checksum::check_line(
+                        &target.display,
+                        ChecksumOutcome::Unreadable,
+                    ))
+                }
+                (Err(error), _) => {
+                    OutputLine::error(format!("sha256: {}: {error}", target.display))
+                }
+            };
+            ctx.terminal.push_output(line);
+        }
+
+        if check {
+            let summary = format!("sha256: {}", checksum::check_summary(&outcomes));
+            ctx.terminal
+                .push_output(if outcomes.iter().all(|outcome| outcome.is_match()) {
+                    OutputLine::info(summary)
+                } else {
+                    OutputLine::error(summary)
+                });
+        }
+    });
+}
+
+/// Read an `export-zip` folder a few files at a time, then pack the files
+/// into a stored ZIP and download it. Files that fail to read are reported
+/// and left out; the archive keeps the rest.
+pub(super) fn handle_export_zip(ctx: AppContext, export: ZipExport) {
+    let ZipExport {
+        filename,
+        files,
+        skipped,
+        total_bytes,
+    } = export;
+    if total_bytes > EXPORT_ZIP_CONFIRM_BYTES {
+        let message = format!(
+            "Download {filename} ({})?",
+            format_size(Some(total_bytes), false)
+        );
+        let confirmed = web_sys::window()
+            .and_then(|window| window.confirm_with_message(&message).ok())
+            .unwrap_or(false);
+        if !confirmed {
+            ctx.terminal
+                .push_output(OutputLine::info("export-zip: cancelled"));
+            return;
+        }
+    }
+    if !skipped.is_empty() {
+        let noun = if skipped.len() == 1 { "file" } else { "files" };
+        ctx.terminal.push_output(OutputLine::info(format!(
+            "export-zip: skipping {} encrypted {noun}: {}",
+            skipped.len(),
+            skipped.join(", ")
+        )));
+    }
+
+    wasm_bindgen_futures::spawn_local(async move {
+        let total = files.len();
+        let progress_line = |done: usize| OutputLine::info(format!("{done}/{total} files"));
+        let first = progress_line(0);
+        let mut progress_id = first.id;
+        ctx.terminal.push_output(first);
+
+        let mut reads = stream::iter(files.into_iter().enumerate().map(
+            |(index, file)| async move {
+                let bytes = ctx.read_bytes(&file.path).await;
+                (index, file, bytes)
+            },
+        ))
+        .buffer_unordered(EXPORT_ZIP_CONCURRENCY);
+        let mut read = Vec::with_capacity(total);
+        let mut failures = Vec::new();
+        let mut done = 0;
+        while let Some((index, file, bytes)) = reads.next().await {
+            match bytes {
+                Ok(bytes) => read.push((index, file.name, bytes)),
+                Err(error) => failures.push(format!("export-zip: {}: {error}", file.name)),
+            }
+            done += 1;
+            let line = progress_line(done);
+            let id = line.id;
+            if ctx.terminal.replace_line(progress_id, line) {
+                progress_id = id;
+            }
+        }
+        ctx.terminal
+            .push_lines(failures.into_iter().map(OutputLine::error).collect());
+        if read.is_empty() {
+            ctx.terminal.push_output(OutputLine::error(format!(
+                "export-zip: no files could be read for {filename}"
+            )));
+            return;
+        }
+
+        // Reads finish in any order; pack in the order the shell listed.
+        read.sort_by_key(|(index, ..)| *index);
+        let packed = read.len();
+        let modified = DosDateTime::from_unix_secs(crate::platform::current_timestamp() / 1000);
+        let mut writer = ZipWriter::new(modified);
+        let archive = read
+            .iter()
+            .try_for_each(|(_, name, bytes)| writer.add_file(name, bytes))
+            .and_then(|()| writer.finish());
+        let result = archive
+            .map_err(|error| error.to_string())
+            .and_then(|archive| {
+                download_bytes(&filename, &archive, "application/zip")?;
+                Ok(archive.len() as u64)
+            });
+        ctx.terminal.push_output(match result {
+            Ok(size) => OutputLine::success(format!(
+                "export-zip: saved {filename} ({packed} files, {})",
+                format_size(Some(size), false)
+            )),
+            Err(error) => OutputLine::error(format!("export-zip: {error}")),
+        });
+    });
+}
+
+/// Apply a `cp`/`mv` plan. Every file is read before any change is staged,
+/// so a failed read leaves the change set untouched.
+fn handle_transfer(ctx: AppContext, copies: Vec<EntryCopy>, remove_source: Option<EntryRemoval>) {
+    let label = if remove_source.is_some() { "mv" } else { "cp" };
+    wasm_bindgen_futures::spawn_local(async move {
+        let mut changes = Vec::with_capacity(copies.len() + 1);
+        for copy in copies {
+            let change = match copy.kind {
+                EntryCopyKind::Directory { meta } => ChangeType::CreateDirectory { meta },
+                EntryCopyKind::File {
+                    meta,
+                    extensions,
+                    overwrite,
+                } => {
+                    let content = match ctx.read_text(&copy.from).await {
+                        Ok(content) => content,
+                        Err(error) => {
+                            ctx.terminal.push_output(OutputLine::error(format!(
+                                "{label}: cannot read '{}': {error}",
+                                copy.from.as_str()
+                            )));
+                            return;
+                        }
+                    };
+                    if overwrite {
+                        ChangeType::UpdateFile {
+                            content,
+                            meta: Some(meta),
+                            extensions: Some(extensions),
+                        }
+                    } else {
+                        ChangeType::CreateFile {
+                            content,
+                            meta,
+                            extensions,
+                        }
+                    }
+                }
+            };
+            changes.push((copy.to, change));
+        }
+
+        let timestamp_ms = crate::platform::current_timestamp();
+        ctx.changes.update(|cs| {
+            for (path, change) in changes {
+                ctx.evict_text_cache_path(&path);
+                cs.upsert_at(path, change, timestamp_ms);
+            }
+            if let Some(source) = remove_source {
+                ctx.evict_text_cache_path(&source.path);
+                if cs.is_pending_create(&source.path) {
+                    cs.discard(&source.path);
+                } else {
+                    let change = if source.is_directory {
+                        ChangeType::DeleteDirectory
+                    } else {
+                        ChangeType::DeleteFile
+                    };
+                    cs.upsert_at(source.path, change, timestamp_ms);
+                }
+            }
+        });
+    });
+}
+
+fn handle_export_session(
+    ctx: &AppContext,
+    filename: &str,
+    html: bool,
+    cwd: &VirtualPath,
+    mount: Option<&RuntimeMount>,
+) {
+    let exported_at = String::from(js_sys::Date::new_0().to_iso_string());
+    let header = TranscriptHeader {
+        exported_at: &exported_at,
+        cwd,
+        mount,
+    };
+    let (content, media_type) = ctx.terminal.history.with_untracked(|history| {
+        if html {
+            (
+                transcript::render_html(&header, history.iter()),
+                "text/html;charset=utf-8",
+            )
+        } else {
+            (
+                transcript::render_text(&header, history.iter()),
+                "text/plain;charset=utf-8",
+            )
+        }
+    });
+    match download_file(filename, &content, media_type) {
+        Ok(()) => ctx.terminal.push_output(OutputLine::success(format!(
+            "export-session: saved {filename}"
+        ))),
+        Err(error) => ctx
+            .terminal
+            .push_output(OutputLine::error(format!("export-session: {error}"))),
+    }
+}
+
+/// Render `feed` with absolute links back to this page, then print, copy,
+/// or download it.
+fn handle_publish_feed(ctx: AppContext, feed: AtomFeed, output: FeedOutput) {
+    let page_url = web_sys::window()
+        .and_then(|window| window.location().href().ok())
+        .unwrap_or_default();
+    let xml = feed.to_xml(&page_url, APP_NAME);
+    let count = feed.entries.len();
+    match output {
+        FeedOutput::Print => ctx
+            .terminal
+            .push_lines(xml.lines().map(OutputLine::text).collect()),
+        FeedOutput::Copy => wasm_bindgen_futures::spawn_local(async move {
+            match copy_to_clipboard(&xml).await {
+                Ok(()) => ctx.terminal.push_output(OutputLine::success(format!(
+                    "feed: {count} entries copied to clipboard"
+                ))),
+                Err(error) => ctx
+                    .terminal
+                    .push_output(OutputLine::error(format!("feed: {error}"))),
+            }
+        }),
+        FeedOutput::Download => {
+            match download_file(FEED_FILENAME, &xml, "application/atom+xml;charset=utf-8") {
+                Ok(()) => ctx.terminal.push_output(OutputLine::success(format!(
+                    "feed: saved {FEED_FILENAME} ({count} entries)"
+                ))),
+                Err(error) => ctx
+                    .terminal
+                    .push_output(OutputLine::error(format!("feed: {error}"))),
+            }
+        }
+    }
+}
+
 pub(super) fn create_submit_callback(ctx: AppContext, route_ctx: RouteContext) -> Callback<String> {
     Callback::new(move |input: String| {
+        // `/pattern` searches the scrollback instead of running a command;
+        // a bare `/` closes the search.
+        if let Some(query) = output_search_query(&input) {
+            ctx.terminal.add_to_command_history(&input);
+            if query.is_empty() {
+                ctx.terminal.end_output_search();
+            } else {
+                ctx.terminal.search_output(query);
+            }
+            return;
+        }
+
         let current_frame = route_ctx.0.get();
         let cwd = route_cwd(&current_frame);
         let prompt = ctx.get_prompt(&cwd);
         let display_input = display_command(&input);
 
+        // Expand `!!` / `!n` before this line joins the history, so they
+        // refer to earlier commands rather than to the line itself. `$VAR`
+        // sees the built-ins too, so `export P=$PWD` works.
+        let runtime_state = ctx.runtime_state.get();
+        let wallet_state = ctx.wallet.get();
+        let env = session_env(&wallet_state, &cwd, &runtime_state.env);
+        let remote_head = ctx.remote_head_for_path(&cwd);
+        let runtime_mounts = ctx.runtime_mounts_snapshot();
+        let execution_context = || {
+            shell_execution_context(
+                &runtime_state,
+                ctx.terminal.history.with_untracked(|h| h.len()),
+                ctx.nav_history.with_untracked(NavHistory::snapshot),
+                ctx.events_snapshot(),
+                ctx.usage_report(),
+                ctx.manifest_sync.get_value(),
+            )
+        };
+        let run = |pipeline: &Pipeline, context: &ExecutionContext| {
+            ctx.changes.with_untracked(|changes| {
+                ctx.system_global_fs.with(|current_fs| {
+                    execute_pipeline_with_context(
+                        pipeline,
+                        &wallet_state,
+                        &runtime_mounts,
+                        current_fs,
+                        &cwd,
+                        changes,
+                        remote_head.as_deref(),
+                        context,
+                    )
+                })
+            })
+        };
+
+        // `$(...)` runs like a subshell: its output becomes arguments, its
+        // errors are shown after the command line, and its side effects
+        // (navigation, writes) are dropped.
+        let substitution_context = execution_context();
+        let mut substitution_errors = Vec::new();
+        let pipeline = ctx.terminal.command_history.with(|history| {
+            parse_input_with_substitution(&input, history, &env, &mut |inner| {
+                let output = run(inner, &substitution_context).output;
+                substitution_errors.extend(
+                    output
+                        .iter()
+                        .filter(|line| matches!(line.data, OutputLineData::Error(_)))
+                        .cloned(),
+                );
+                substitution_text(&output)
+            })
+        });
+
+        ctx.record_usage(|stats| {
+            for command in &pipeline.commands {
+                stats.record_command(&command.name);
+            }
+        });
+
         if !input.is_empty() {
             ctx.terminal
                 .push_output(OutputLine::command(prompt, &display_input));
@@ -80,53 +657,53 @@ pub(super) fn create_submit_callback(ctx: AppContext, route_ctx: RouteContext) -
                 ctx.terminal.add_to_command_history(&input);
             } else {
                 ctx.terminal.history_index.set(None);
+                ctx.terminal.draft.set(None);
             }
         }
+        ctx.terminal.push_lines(substitution_errors);
 
-        let runtime_state = ctx.runtime_state.get();
-        let pipeline = ctx
-            .terminal
-            .command_history
-            .with(|history| parse_input_with_env(&input, history, &runtime_state.env));
-
-        let wallet_state = ctx.wallet.get();
-        let remote_head = ctx.remote_head_for_path(&cwd);
-        let runtime_mounts = ctx.runtime_mounts_snapshot();
-        let execution_context = shell_execution_context(&runtime_state);
-        let result = ctx.changes.with_untracked(|changes| {
-            ctx.system_global_fs.with(|current_fs| {
-                execute_pipeline_with_context(
-                    &pipeline,
-                    &wallet_state,
-                    &runtime_mounts,
-                    current_fs,
-                    &cwd,
-                    changes,
-                    remote_head.as_deref(),
-                    &execution_context,
-                )
-            })
-        });
+        let result = run(&pipeline, &execution_context());
 
         ctx.terminal.push_lines(result.output);
 
         for effect in result.side_effects {
             dispatch_side_effect(&ctx, effect);
         }
+        tutorial::observe(ctx, &input, result.exit_code);
     })
 }
 
 pub(crate) fn dispatch_side_effect(ctx: &AppContext, effect: SideEffect) {
     match effect {
         SideEffect::Navigate(route) => push_route(&route),
+        SideEffect::JumpHistory { offset } => {
+            if let Some(route) = ctx.jump_history(offset) {
+                push_route(&RouteRequest::new(route));
+            }
+        }
+        SideEffect::ClearNavigationHistory => ctx.nav_history.update(NavHistory::clear),
         SideEffect::Login => handle_login(*ctx),
         SideEffect::Logout => handle_logout(ctx),
         SideEffect::SwitchView(_) => {}
         SideEffect::SwitchViewAndNavigate(_, route) => push_route(&route),
+        SideEffect::ClearScreen => ctx.terminal.clear_screen(),
         SideEffect::ClearHistory => ctx.terminal.clear_history(),
+        SideEffect::CopySession => handle_copy_session(*ctx),
+        SideEffect::ExportSession {
+            filename,
+            html,
+            cwd,
+            mount,
+        } => handle_export_session(ctx, &filename, html, &cwd, mount.as_ref()),
+        SideEffect::Tutorial(action) => tutorial::apply(*ctx, action),
+        SideEffect::Session(action) => session::apply(*ctx, action),
+        SideEffect::ResetUsageStats => ctx.reset_usage(),
+        SideEffect::PublishFeed { feed, output } => handle_publish_feed(*ctx, feed, output),
         SideEffect::ListThemes => {
             ctx.terminal
-                .push_lines(crate::render::theme::theme_output_lines());
+                .push_lines(crate::render::theme::theme_output_lines(
+                    ctx.theme.get_untracked(),
+                ));
         }
         SideEffect::SetTheme { theme } => match RuntimeServices::new(*ctx).set_theme(&theme) {
             Ok(theme_id) => {
@@ -174,6 +751,11 @@ pub(crate) fn dispatch_side_effect(ctx: &AppContext, effect: SideEffect) {
         SideEffect::UnstageAll => {
             ctx.changes.update(|cs| cs.unstage_all());
         }
+        SideEffect::TransferEntries {
+            copies,
+            remove_source,
+        } => handle_transfer(*ctx, copies, remove_source),
+        SideEffect::AppendFile { path, text } => handle_append(*ctx, path, text),
         SideEffect::SetAuthToken { token } => {
             match RuntimeServices::new(*ctx).set_github_token(&token) {
                 Ok(()) => {}
@@ -189,6 +771,31 @@ pub(crate) fn dispatch_side_effect(ctx: &AppContext, effect: SideEffect) {
             ))),
         },
         SideEffect::InvalidateRuntimeState => {}
+        SideEffect::ReportManifestIssues => {
+            let issues = ctx.mounts.with_untracked(|mounts| mounts.manifest_issues());
+            if issues.is_empty() {
+                ctx.terminal
+                    .push_output(OutputLine::success("manifest: no issues found"));
+            } else {
+                ctx.terminal
+                    .push_lines(super::boot::manifest_issue_lines(&issues));
+            }
+        }
+        SideEffect::SearchContent { query, paths } => handle_search(*ctx, query, paths),
+        SideEffect::Fetch { url, filters } => handle_fetch(*ctx, url, filters),
+        SideEffect::ReadFiles(stage) => handle_read_files(*ctx, stage),
+        SideEffect::Checksum { targets, check } => handle_checksum(*ctx, targets, check),
+        SideEffect::ExportZip(export) => handle_export_zip(*ctx, export),
+        SideEffect::DownloadDraft { filename, content } => {
+            match download_file(&filename, &content, "text/markdown;charset=utf-8") {
+                Ok(()) => ctx.terminal.push_output(OutputLine::success(format!(
+                    "export-draft: saved {filename}"
+                ))),
+                Err(error) => ctx
+                    .terminal
+                    .push_output(OutputLine::error(format!("export-draft: {error}"))),
+            }
+        }
         SideEffect::OpenEditor { path } => {
             ctx.editor_open.set(Some(path));
         }
@@ -268,6 +875,11 @@ fn display_command(input: &str) -> String {
     }
 }
 
+/// The pattern of a `/pattern` scrollback search line.
+fn output_search_query(input: &str) -> Option<&str> {
+    input.trim().strip_prefix('/').map(str::trim)
+}
+
 fn should_store_command_history(input: &str) -> bool {
     !is_sync_auth_set(input.trim_start())
 }
@@ -280,8 +892,38 @@ fn is_sync_auth_set(input: &str) -> bool {
     )
 }
 
-pub(super) fn create_history_nav_callback(ctx: AppContext) -> Callback<i32, Option<String>> {
-    Callback::new(move |direction: i32| ctx.terminal.navigate_history(direction))
+/// Ctrl+C: echo the abandoned line under the current prompt.
+pub(super) fn create_interrupt_callback(
+    ctx: AppContext,
+    route_ctx: RouteContext,
+) -> Callback<String> {
+    Callback::new(move |input: String| {
+        let prompt = ctx.get_prompt(&route_cwd(&route_ctx.0.get()));
+        ctx.terminal.interrupt(prompt, &display_command(&input));
+    })
+}
+
+/// Ctrl+L: clear the screen without echoing or recording a command.
+pub(super) fn create_clear_screen_callback(ctx: AppContext) -> Callback<()> {
+    Callback::new(move |()| ctx.terminal.clear_screen())
+}
+
+pub(super) fn create_output_search_step_callback(ctx: AppContext) -> Callback<bool, bool> {
+    Callback::new(move |older: bool| ctx.terminal.step_output_search(older))
+}
+
+pub(super) fn create_history_nav_callback(
+    ctx: AppContext,
+) -> Callback<(i32, String), Option<String>> {
+    Callback::new(move |(direction, current): (i32, String)| {
+        ctx.terminal.navigate_history(direction, &current)
+    })
+}
+
+pub(super) fn create_history_search_callback(ctx: AppContext) -> HistorySearchCallback {
+    Callback::new(move |(query, before): (String, Option<usize>)| {
+        ctx.terminal.search_history(&query, before)
+    })
 }
 
 pub(super) fn create_autocomplete_callback(
@@ -330,4 +972,15 @@ mod tests {
         assert!(!should_store_command_history("sync auth set ghp_secret"));
         assert!(should_store_command_history("sync auth clear"));
     }
+
+    #[wasm_bindgen_test]
+    fn slash_lines_search_the_scrollback() {
+        assert_eq!(output_search_query("/rust"), Some("rust"));
+        assert_eq!(
+            output_search_query("  /no such file "),
+            Some("no such file")
+        );
+        assert_eq!(output_search_query("/"), Some(""));
+        assert_eq!(output_search_query("ls /blog"), None);
+    }
 }
@@ -1,14 +1,33 @@
 //! Boot sequence logic
 //!
 //! Handles the initial terminal animation and applies the pure runtime loader.
+//! With `BOOT_TYPING=on` the boot lines are typed out at `BOOT_TYPING_SPEED`
+//! characters per second; any key prints the rest at once, and reduced
+//! motion keeps the instant output.
 
+use std::cell::Cell;
+use std::collections::BTreeMap;
+
+use leptos::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 
 use crate::app::AppContext;
 use crate::app::RuntimeServices;
-use crate::config::{APP_NAME, APP_TAGLINE, APP_VERSION, ASCII_BANNER, boot_delays};
-use websh_core::shell::OutputLine;
+use crate::app::start_manifest_poll;
+use crate::config::{
+    APP_NAME, APP_TAGLINE, APP_VERSION, ASCII_BANNER, BOOT_TYPING_SPEED_RANGE,
+    BOOT_TYPING_SPEED_VAR, BOOT_TYPING_VAR, DEFAULT_BOOT_TYPING_SPEED, boot_delays,
+};
+use crate::features::router::{BuiltinRoute, route_request_needs_system_fs};
+use crate::platform::dom::current_route_request;
+use crate::render::display::{AnimationMode, parse_toggle};
+use websh_core::filesystem::resolve_route;
+use websh_core::ports::{IssueSeverity, ManifestIssue};
+use websh_core::shell::{OutputLine, OutputLineData, OutputLineId};
 use websh_core::support::format::{format_elapsed, format_eth_address};
+use websh_core::support::i18n::Message;
+
+use super::{session, tutorial};
 
 /// Delay helper using setTimeout
 async fn delay(window: &web_sys::Window, ms: i32) {
@@ -18,6 +37,155 @@ async fn delay(window: &web_sys::Window, ms: i32) {
     let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
 }
 
+thread_local! {
+    /// Set by a keypress while boot lines are typing out.
+    static FAST_FORWARD: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Print the rest of a typed boot sequence at once. Harmless after boot.
+pub(crate) fn fast_forward() {
+    FAST_FORWARD.with(|flag| flag.set(true));
+}
+
+fn fast_forwarded() -> bool {
+    FAST_FORWARD.with(Cell::get)
+}
+
+/// How boot lines reach the terminal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct BootConfig {
+    /// Type lines out instead of printing them at once.
+    pub typing: bool,
+    /// Typing speed in characters per second.
+    pub chars_per_second: u32,
+}
+
+impl BootConfig {
+    /// Resolve from user variables. Typing is off unless `BOOT_TYPING` turns
+    /// it on, and stays off under reduced animations.
+    fn from_env(env: &BTreeMap<String, String>, animations: AnimationMode) -> Self {
+        let typing = animations == AnimationMode::Full
+            && env
+                .get(BOOT_TYPING_VAR)
+                .and_then(|value| parse_toggle(value))
+                .unwrap_or(false);
+        let chars_per_second = env
+            .get(BOOT_TYPING_SPEED_VAR)
+            .and_then(|value| value.trim().parse::<u32>().ok())
+            .map_or(DEFAULT_BOOT_TYPING_SPEED, |speed| {
+                speed.clamp(
+                    *BOOT_TYPING_SPEED_RANGE.start(),
+                    *BOOT_TYPING_SPEED_RANGE.end(),
+                )
+            });
+        Self {
+            typing,
+            chars_per_second,
+        }
+    }
+
+    /// Characters revealed per tick and the tick length in milliseconds;
+    /// `None` when lines print at once. Ticks never drop below
+    /// [`boot_delays::MIN_TYPING_TICK`], so faster speeds reveal more per
+    /// tick.
+    fn pace(self) -> Option<(usize, i32)> {
+        if !self.typing {
+            return None;
+        }
+        let speed = self.chars_per_second.max(1);
+        let per_char = i32::try_from(1000 / speed).unwrap_or(i32::MAX);
+        if per_char >= boot_delays::MIN_TYPING_TICK {
+            return Some((1, per_char));
+        }
+        let tick = boot_delays::MIN_TYPING_TICK as u32;
+        let chars = (speed * tick).div_ceil(1000) as usize;
+        Some((chars, boot_delays::MIN_TYPING_TICK))
+    }
+}
+
+/// Writes boot lines to the terminal, typed out when configured.
+struct BootOutput {
+    ctx: AppContext,
+    window: web_sys::Window,
+    pace: Option<(usize, i32)>,
+}
+
+impl BootOutput {
+    fn new(ctx: AppContext, window: web_sys::Window) -> Self {
+        let animations = ctx.display.get_untracked().animations;
+        let config = ctx
+            .runtime_state
+            .with_untracked(|rs| BootConfig::from_env(&rs.env, animations));
+        Self {
+            ctx,
+            window,
+            pace: config.pace(),
+        }
+    }
+
+    async fn push(&self, line: OutputLine) {
+        let Some((chars_per_tick, tick)) = self.pace else {
+            self.ctx.terminal.push_output(line);
+            return;
+        };
+        let total = typed_text(&line.data).map_or(0, |text| text.chars().count());
+        let mut shown_id = None;
+        let mut shown = chars_per_tick;
+        while shown < total && !fast_forwarded() {
+            if let Some(partial) = typed_prefix(&line.data, shown) {
+                shown_id = Some(self.show(shown_id, partial));
+            }
+            delay(&self.window, tick).await;
+            shown += chars_per_tick;
+        }
+        self.show(shown_id, line);
+    }
+
+    /// Like [`TerminalState::push_lines`](crate::app::TerminalState::push_lines):
+    /// the lines, then a blank one, or nothing for no lines.
+    async fn push_lines(&self, lines: Vec<OutputLine>) {
+        if lines.is_empty() {
+            return;
+        }
+        for line in lines {
+            self.push(line).await;
+        }
+        self.push(OutputLine::empty()).await;
+    }
+
+    /// Put `line` in place of the partly typed line `id`, or append it.
+    fn show(&self, id: Option<OutputLineId>, line: OutputLine) -> OutputLineId {
+        let new_id = line.id;
+        match id {
+            Some(id) if self.ctx.terminal.replace_line(id, line.clone()) => {}
+            _ => self.ctx.terminal.push_output(line),
+        }
+        new_id
+    }
+}
+
+/// The text a boot line types out; banners and blank lines appear whole.
+fn typed_text(data: &OutputLineData) -> Option<&str> {
+    match data {
+        OutputLineData::Text(text)
+        | OutputLineData::Error(text)
+        | OutputLineData::Success(text)
+        | OutputLineData::Info(text) => Some(text),
+        _ => None,
+    }
+}
+
+/// The first `chars` characters of a typed line, in the line's style.
+fn typed_prefix(data: &OutputLineData, chars: usize) -> Option<OutputLine> {
+    let prefix: String = typed_text(data)?.chars().take(chars).collect();
+    Some(match data {
+        OutputLineData::Error(_) => OutputLine::error(prefix),
+        OutputLineData::Success(_) => OutputLine::success(prefix),
+        OutputLineData::Info(_) => OutputLine::info(prefix),
+        _ => OutputLine::text(prefix),
+    })
+}
+
 /// Run the boot sequence
 ///
 /// Initializes the application by:
@@ -34,137 +202,351 @@ pub fn run(ctx: AppContext) {
         let services = RuntimeServices::new(ctx);
 
         services.init_default_env();
+        session::start_autosave(ctx);
+        FAST_FORWARD.with(|flag| flag.set(false));
+        let out = BootOutput::new(ctx, window.clone());
 
-        ctx.terminal.push_output(OutputLine::info(format!(
+        out.push(OutputLine::info(format!(
             "{} Booting websh kernel v{}",
             format_elapsed(elapsed()),
             APP_VERSION
-        )));
+        )))
+        .await;
         delay(&window, boot_delays::KERNEL_INIT).await;
 
-        ctx.terminal.push_output(OutputLine::success(format!(
+        out.push(OutputLine::success(format!(
             "{} WASM runtime initialized",
             format_elapsed(elapsed())
-        )));
+        )))
+        .await;
         delay(&window, boot_delays::WASM_RUNTIME).await;
 
-        ctx.terminal.push_output(OutputLine::text(format!(
+        out.push(OutputLine::text(format!(
             "{} Mounting filesystems...",
             format_elapsed(elapsed())
-        )));
+        )))
+        .await;
 
         services.mark_root_mount_loading();
+        let mut manifest_banner = None;
         match services.load_runtime().await {
             Ok(load) => {
                 let total_files = load.total_files;
+                manifest_banner = load.banner.clone();
                 let failed_mounts = load.mounts.failed_entries();
+                let manifest_issues = load.mounts.manifest_issues();
                 let scan_jobs = load.mounts.scan_jobs.clone();
                 let generation = services.apply_successful_root_mount_load(load);
                 services.start_mount_scans(generation, scan_jobs);
-                ctx.terminal.push_output(OutputLine::success(format!(
+                start_manifest_poll(ctx);
+                out.push(OutputLine::success(format!(
                     "{} Total: {} files mounted",
                     format_elapsed(elapsed()),
                     total_files
-                )));
+                )))
+                .await;
                 for failure in failed_mounts {
                     let error = failure.error().unwrap_or("unavailable");
-                    ctx.terminal.push_output(OutputLine::error(format!(
+                    out.push(OutputLine::error(format!(
                         "{} mount {} unavailable: {}",
                         format_elapsed(elapsed()),
                         failure.declared.label,
                         error
-                    )));
+                    )))
+                    .await;
+                }
+                out.push_lines(manifest_issue_lines(&manifest_issues)).await;
+                if let Some(missing) = unresolved_initial_route(ctx) {
+                    out.push(OutputLine::error(format!(
+                        "{} route #{} not found",
+                        format_elapsed(elapsed()),
+                        missing
+                    )))
+                    .await;
                 }
             }
             Err(error) => {
                 services.apply_failed_root_mount_load(error.clone());
-                ctx.terminal.push_output(OutputLine::error(format!(
+                out.push(OutputLine::error(format!(
                     "{} Failed to mount filesystems: {}",
                     format_elapsed(elapsed()),
                     error
-                )));
+                )))
+                .await;
             }
         }
 
         if services.wallet_available() && services.has_wallet_session() {
-            ctx.terminal.push_output(OutputLine::text(format!(
+            out.push(OutputLine::text(format!(
                 "{} Restoring wallet session...",
                 format_elapsed(elapsed())
-            )));
+            )))
+            .await;
 
             match services.wallet_account().await {
                 Some(address) => {
                     let short_addr = format_eth_address(&address);
-                    ctx.terminal.push_output(OutputLine::success(format!(
+                    out.push(OutputLine::success(format!(
                         "{} Connected: {}",
                         format_elapsed(elapsed()),
                         short_addr
-                    )));
+                    )))
+                    .await;
 
                     let chain_id = services.wallet_chain_id().await;
                     if let Some(id) = chain_id {
-                        ctx.terminal.push_output(OutputLine::info(format!(
+                        out.push(OutputLine::info(format!(
                             "{} Network: {} (chain_id={})",
                             format_elapsed(elapsed()),
                             websh_core::domain::chain_name(id),
                             id
-                        )));
+                        )))
+                        .await;
                     }
 
                     let ens_name = services.resolve_wallet_ens(&address).await;
                     if let Some(ref name) = ens_name {
-                        ctx.terminal.push_output(OutputLine::success(format!(
+                        out.push(OutputLine::success(format!(
                             "{} ENS resolved: {}",
                             format_elapsed(elapsed()),
                             name
-                        )));
+                        )))
+                        .await;
                     }
 
                     match services.restore_wallet_session(address, chain_id, ens_name) {
                         Ok(()) => {}
-                        Err(error) => ctx.terminal.push_output(OutputLine::error(format!(
-                            "wallet: failed to persist session: {error}"
-                        ))),
+                        Err(error) => {
+                            out.push(OutputLine::error(format!(
+                                "wallet: failed to persist session: {error}"
+                            )))
+                            .await
+                        }
                     }
                 }
                 None => {
                     match services.disconnect_wallet() {
                         Ok(()) => {}
-                        Err(error) => ctx.terminal.push_output(OutputLine::error(format!(
-                            "wallet: failed to clear session: {error}"
-                        ))),
+                        Err(error) => {
+                            out.push(OutputLine::error(format!(
+                                "wallet: failed to clear session: {error}"
+                            )))
+                            .await
+                        }
                     }
-                    ctx.terminal.push_output(OutputLine::text(format!(
+                    out.push(OutputLine::text(format!(
                         "{} Wallet session expired",
                         format_elapsed(elapsed())
-                    )));
+                    )))
+                    .await;
                 }
             }
         }
 
-        ctx.terminal.push_output(OutputLine::info(format!(
-            "{} Initializing Terminal mode",
-            format_elapsed(elapsed())
-        )));
+        let locale = ctx.locale.get_untracked();
+        out.push(OutputLine::info(format!(
+            "{} {}",
+            format_elapsed(elapsed()),
+            Message::BootInitializing.text(locale)
+        )))
+        .await;
         delay(&window, boot_delays::BOOT_COMPLETE).await;
 
-        ctx.terminal.push_output(OutputLine::success(format!(
-            "{} Boot complete. Welcome to {}",
+        out.push(OutputLine::success(format!(
+            "{} {}",
             format_elapsed(elapsed()),
-            APP_NAME
-        )));
-
-        ctx.terminal.push_output(OutputLine::empty());
-        ctx.terminal.push_output(OutputLine::ascii(ASCII_BANNER));
-        ctx.terminal.push_output(OutputLine::empty());
-        ctx.terminal.push_output(OutputLine::info(APP_TAGLINE));
-        ctx.terminal.push_output(OutputLine::empty());
-        ctx.terminal.push_output(OutputLine::text("Tips:"));
-        ctx.terminal
-            .push_output(OutputLine::text("  - Type 'help' for available commands"));
-        ctx.terminal.push_output(OutputLine::text(
-            "  - Use the archive bar to jump between home, ledger, and websh",
-        ));
-        ctx.terminal.push_output(OutputLine::empty());
+            Message::BootComplete.format(locale, &[("app", APP_NAME)])
+        )))
+        .await;
+
+        out.push(OutputLine::empty()).await;
+        out.push(OutputLine::ascii(ASCII_BANNER)).await;
+        out.push(OutputLine::empty()).await;
+        let banner_lines = manifest_banner_lines(manifest_banner.as_deref());
+        if !banner_lines.is_empty() {
+            out.push_lines(banner_lines).await;
+            out.push(OutputLine::empty()).await;
+        }
+        out.push(OutputLine::info(APP_TAGLINE)).await;
+        out.push(OutputLine::empty()).await;
+        out.push(OutputLine::text(Message::BootTips.text(locale)))
+            .await;
+        out.push(OutputLine::text(Message::BootTipHelp.text(locale)))
+            .await;
+        out.push(OutputLine::text(Message::BootTipArchiveBar.text(locale)))
+            .await;
+        if !tutorial::restore(ctx) {
+            out.push(OutputLine::text(Message::BootTipTutorial.text(locale)))
+                .await;
+        }
+        out.push(OutputLine::empty()).await;
     });
 }
+
+/// The page the visitor landed on, when it names nothing in the loaded tree.
+fn unresolved_initial_route(ctx: AppContext) -> Option<String> {
+    let request = current_route_request();
+    if BuiltinRoute::detect(&request).is_some() {
+        return None;
+    }
+    let fs = if route_request_needs_system_fs(&request) {
+        ctx.system_global_fs
+    } else {
+        ctx.view_global_fs
+    };
+    let resolved = fs.with_untracked(|fs| resolve_route(fs, &request).is_some());
+    (!resolved).then_some(request.url_path)
+}
+
+/// One terminal line per manifest lint finding; errors render as errors,
+/// warnings as info.
+pub(crate) fn manifest_issue_lines(issues: &[(String, ManifestIssue)]) -> Vec<OutputLine> {
+    issues
+        .iter()
+        .map(|(label, issue)| {
+            let text = format!("manifest {label}: {issue}");
+            match issue.severity {
+                IssueSeverity::Error => OutputLine::error(text),
+                IssueSeverity::Warning => OutputLine::info(text),
+            }
+        })
+        .collect()
+}
+
+/// Split a manifest-provided banner into terminal lines. Blank banners
+/// produce nothing so the stock boot output stays untouched.
+fn manifest_banner_lines(banner: Option<&str>) -> Vec<OutputLine> {
+    let Some(banner) = banner
+        .map(str::trim_end)
+        .filter(|text| !text.trim().is_empty())
+    else {
+        return Vec::new();
+    };
+    banner.lines().map(OutputLine::text).collect()
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+    use websh_core::shell::OutputLineData;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn env(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[wasm_bindgen_test]
+    fn boot_typing_is_off_by_default() {
+        let config = BootConfig::from_env(&BTreeMap::new(), AnimationMode::Full);
+        assert_eq!(
+            config,
+            BootConfig {
+                typing: false,
+                chars_per_second: DEFAULT_BOOT_TYPING_SPEED,
+            }
+        );
+        assert_eq!(config.pace(), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn boot_typing_follows_the_variables_unless_motion_is_reduced() {
+        let vars = env(&[("BOOT_TYPING", "on"), ("BOOT_TYPING_SPEED", "50")]);
+        let config = BootConfig::from_env(&vars, AnimationMode::Full);
+        assert!(config.typing);
+        assert_eq!(config.chars_per_second, 50);
+        assert!(!BootConfig::from_env(&vars, AnimationMode::Reduced).typing);
+
+        let clamped =
+            BootConfig::from_env(&env(&[("BOOT_TYPING_SPEED", "1")]), AnimationMode::Full);
+        assert_eq!(clamped.chars_per_second, *BOOT_TYPING_SPEED_RANGE.start());
+        let garbled =
+            BootConfig::from_env(&env(&[("BOOT_TYPING_SPEED", "x")]), AnimationMode::Full);
+        assert_eq!(garbled.chars_per_second, DEFAULT_BOOT_TYPING_SPEED);
+    }
+
+    #[wasm_bindgen_test]
+    fn fast_typing_reveals_several_characters_per_tick() {
+        let pace = |chars_per_second| {
+            BootConfig {
+                typing: true,
+                chars_per_second,
+            }
+            .pace()
+        };
+        assert_eq!(pace(50), Some((1, 20)));
+        assert_eq!(pace(125), Some((2, boot_delays::MIN_TYPING_TICK)));
+        assert_eq!(pace(2_000), Some((32, boot_delays::MIN_TYPING_TICK)));
+    }
+
+    #[wasm_bindgen_test]
+    fn typed_prefixes_keep_the_line_style() {
+        let line = OutputLine::success("12ms WASM runtime initialized");
+        assert_eq!(
+            typed_prefix(&line.data, 4).map(|line| line.data),
+            Some(OutputLineData::Success("12ms".to_string()))
+        );
+        assert!(typed_prefix(&OutputLine::ascii(ASCII_BANNER).data, 4).is_none());
+        assert!(typed_prefix(&OutputLine::empty().data, 4).is_none());
+    }
+
+    #[wasm_bindgen_test]
+    fn manifest_banner_lines_split_on_newlines() {
+        let lines = manifest_banner_lines(Some("welcome\n\nmind the gap\n"));
+        let data: Vec<_> = lines.into_iter().map(|line| line.data).collect();
+        assert_eq!(
+            data,
+            vec![
+                OutputLineData::Text("welcome".to_string()),
+                OutputLineData::Text(String::new()),
+                OutputLineData::Text("mind the gap".to_string()),
+            ]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn missing_or_blank_banner_emits_nothing() {
+        assert!(manifest_banner_lines(None).is_empty());
+        assert!(manifest_banner_lines(Some("  \n")).is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn manifest_issues_render_by_severity() {
+        let issues = vec![
+            (
+                "~".to_string(),
+                ManifestIssue {
+                    severity: IssueSeverity::Error,
+                    path: Some("a.md".to_string()),
+                    message: "duplicate entry; only one will be mounted".to_string(),
+                },
+            ),
+            (
+                "notes".to_string(),
+                ManifestIssue {
+                    severity: IssueSeverity::Warning,
+                    path: None,
+                    message: "odd".to_string(),
+                },
+            ),
+        ];
+        let data: Vec<_> = manifest_issue_lines(&issues)
+            .into_iter()
+            .map(|line| line.data)
+            .collect();
+        assert_eq!(
+            data,
+            vec![
+                OutputLineData::Error(
+                    "manifest ~: error: a.md: duplicate entry; only one will be mounted"
+                        .to_string()
+                ),
+                OutputLineData::Info("manifest notes: warning: odd".to_string()),
+            ]
+        );
+    }
+}
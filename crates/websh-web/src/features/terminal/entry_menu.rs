@@ -0,0 +1,491 @@
+//! Context menu for entries listed in terminal output.
+//!
+//! Right-click (or a long press on touch screens) on a listed name opens the
+//! menu at the pointer. Items are plain data so another surface can reuse
+//! the same definitions. "Preview" opens the markdown preview sheet, which
+//! the menu handle also owns.
+
+use leptos::ev;
+use leptos::portal::Portal;
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use websh_core::domain::VirtualPath;
+use websh_core::filesystem::{
+    RouteRequest, RouteSurface, content_href_for_path, request_path_for_canonical_path,
+};
+use websh_core::shell::OutputLine;
+use websh_core::support::i18n::Message;
+
+use crate::app::AppContext;
+use crate::platform::dom::{
+    absolute_hash_url, copy_to_clipboard, focus_terminal_input, push_route, window,
+};
+use crate::shared::icons::{self as ic, UiIcon};
+
+use super::output::{open_entry_command, path_command};
+use super::preview::{PreviewSheet, is_previewable};
+
+stylance::import_crate_style!(css, "src/features/terminal/entry_menu.module.css");
+
+/// Space kept between the menu and the viewport edges (px).
+const VIEWPORT_MARGIN: f64 = 8.0;
+/// How long a touch must be held to open the menu (ms).
+pub(super) const LONG_PRESS_MS: u32 = 500;
+
+/// The entry a menu was opened on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct MenuTarget {
+    pub(super) path: VirtualPath,
+    pub(super) is_dir: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum MenuAction {
+    Open,
+    OpenInReader,
+    Preview,
+    CopyPath,
+    CopyUrl,
+    DownloadFolder,
+}
+
+pub(super) struct MenuItem {
+    pub(super) label: Message,
+    pub(super) icon: Option<UiIcon>,
+    pub(super) enabled: fn(&MenuTarget) -> bool,
+    pub(super) action: MenuAction,
+}
+
+pub(super) const ENTRY_MENU_ITEMS: &[MenuItem] = &[
+    MenuItem {
+        label: Message::MenuOpen,
+        icon: Some(ic::CHEVRON_RIGHT),
+        enabled: always,
+        action: MenuAction::Open,
+    },
+    MenuItem {
+        label: Message::MenuOpenInReader,
+        icon: Some(ic::FILE),
+        enabled: is_file,
+        action: MenuAction::OpenInReader,
+    },
+    MenuItem {
+        label: Message::MenuPreview,
+        icon: None,
+        enabled: is_markdown,
+        action: MenuAction::Preview,
+    },
+    MenuItem {
+        label: Message::MenuCopyPath,
+        icon: None,
+        enabled: always,
+        action: MenuAction::CopyPath,
+    },
+    MenuItem {
+        label: Message::MenuCopyUrl,
+        icon: None,
+        enabled: always,
+        action: MenuAction::CopyUrl,
+    },
+    MenuItem {
+        label: Message::MenuDownloadFolder,
+        icon: Some(ic::FOLDER),
+        enabled: is_dir,
+        action: MenuAction::DownloadFolder,
+    },
+];
+
+fn always(_: &MenuTarget) -> bool {
+    true
+}
+
+fn is_file(target: &MenuTarget) -> bool {
+    !target.is_dir
+}
+
+fn is_dir(target: &MenuTarget) -> bool {
+    target.is_dir
+}
+
+fn is_markdown(target: &MenuTarget) -> bool {
+    is_file(target) && is_previewable(&target.path)
+}
+
+/// Top-left corner for a `size` menu opened at `anchor`, moved left/up as
+/// needed so it stays inside `viewport` with [`VIEWPORT_MARGIN`] to spare.
+/// A menu larger than the viewport is pinned to the top-left margin.
+pub(super) fn clamp_menu_position(
+    anchor: (f64, f64),
+    size: (f64, f64),
+    viewport: (f64, f64),
+) -> (f64, f64) {
+    let clamp = |start: f64, extent: f64, available: f64| {
+        let max = available - VIEWPORT_MARGIN - extent;
+        start.min(max).max(VIEWPORT_MARGIN)
+    };
+    (
+        clamp(anchor.0, size.0, viewport.0),
+        clamp(anchor.1, size.1, viewport.1),
+    )
+}
+
+/// Next enabled item after `current` (before it when `!forward`),
+/// wrapping around. Starts from the first or last enabled item when
+/// nothing is active.
+pub(super) fn step_enabled(
+    enabled: &[bool],
+    current: Option<usize>,
+    forward: bool,
+) -> Option<usize> {
+    let len = enabled.len();
+    (1..=len)
+        .map(|offset| match (current, forward) {
+            (Some(index), true) => (index + offset) % len,
+            (Some(index), false) => (index + len - offset) % len,
+            (None, true) => offset - 1,
+            (None, false) => len - offset,
+        })
+        .find(|&index| enabled[index])
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct OpenMenu {
+    pub(super) target: MenuTarget,
+    pub(super) x: f64,
+    pub(super) y: f64,
+}
+
+/// The terminal's entry menu, shared by every rendered listing, and the
+/// preview sheet it opens.
+#[derive(Clone, Copy)]
+pub struct EntryMenuHandle {
+    open: RwSignal<Option<OpenMenu>>,
+    preview: RwSignal<Option<VirtualPath>>,
+}
+
+impl EntryMenuHandle {
+    pub(super) fn new() -> Self {
+        Self {
+            open: RwSignal::new(None),
+            preview: RwSignal::new(None),
+        }
+    }
+
+    pub(super) fn open(&self, target: MenuTarget, x: f64, y: f64) {
+        self.open.set(Some(OpenMenu { target, x, y }));
+    }
+
+    pub(super) fn close(&self) {
+        if self.open.with_untracked(Option::is_some) {
+            self.open.set(None);
+        }
+    }
+
+    fn open_preview(&self, path: VirtualPath) {
+        self.preview.set(Some(path));
+    }
+
+    fn close_preview(&self) {
+        self.preview.set(None);
+    }
+}
+
+/// The open entry menu, if any, with a backdrop that closes it on any
+/// click or wheel outside, and the open preview sheet. Rendered into
+/// `<body>` so fixed positioning is relative to the viewport rather than
+/// the terminal container.
+#[component]
+pub(super) fn EntryMenu(handle: EntryMenuHandle, on_run: Callback<String>) -> impl IntoView {
+    view! {
+        <Portal>
+            {move || {
+                handle
+                    .open
+                    .get()
+                    .map(|menu| view! { <EntryMenuPanel handle=handle on_run=on_run menu=menu /> })
+            }}
+            {move || {
+                handle.preview.get().map(|path| {
+                    let on_close = Callback::new(move |()| handle.close_preview());
+                    view! { <PreviewSheet path=path on_close=on_close /> }
+                })
+            }}
+        </Portal>
+    }
+}
+
+#[component]
+fn EntryMenuPanel(
+    handle: EntryMenuHandle,
+    on_run: Callback<String>,
+    menu: OpenMenu,
+) -> impl IntoView {
+    let ctx = use_context::<AppContext>().expect("AppContext must be provided at root");
+    let panel_ref = NodeRef::<leptos::html::Div>::new();
+    let position = RwSignal::new((menu.x, menu.y));
+    let enabled: Vec<bool> = ENTRY_MENU_ITEMS
+        .iter()
+        .map(|item| (item.enabled)(&menu.target))
+        .collect();
+    let active = RwSignal::new(None::<usize>);
+
+    let focus_item = move |index: usize| {
+        if let Some(panel) = panel_ref.get_untracked()
+            && let Ok(items) = panel.query_selector_all("[role=\"menuitem\"]")
+            && let Some(item) = items
+                .item(index as u32)
+                .and_then(|node| node.dyn_into::<web_sys::HtmlElement>().ok())
+        {
+            let _ = item.focus();
+            active.set(Some(index));
+        }
+    };
+
+    // Measure once mounted, keep the panel on screen, and focus the first
+    // enabled item so the keyboard works straight away.
+    let first_enabled = step_enabled(&enabled, None, true);
+    Effect::new(move || {
+        let Some(panel) = panel_ref.get() else {
+            return;
+        };
+        let rect = panel.get_bounding_client_rect();
+        let viewport = window()
+            .map(|window| {
+                let size = |value: Result<wasm_bindgen::JsValue, _>| {
+                    value.ok().and_then(|value| value.as_f64()).unwrap_or(0.0)
+                };
+                (size(window.inner_width()), size(window.inner_height()))
+            })
+            .unwrap_or_default();
+        position.set(clamp_menu_position(
+            (menu.x, menu.y),
+            (rect.width(), rect.height()),
+            viewport,
+        ));
+        if let Some(index) = first_enabled {
+            focus_item(index);
+        }
+    });
+
+    let close = move || {
+        handle.close();
+        focus_terminal_input();
+    };
+
+    let target = menu.target.clone();
+    let run = move |action: MenuAction| {
+        let path = target.path.clone();
+        match action {
+            MenuAction::Open => on_run.run(open_entry_command(&path, target.is_dir)),
+            MenuAction::OpenInReader => push_route(&RouteRequest::new(
+                request_path_for_canonical_path(&path, RouteSurface::Content),
+            )),
+            MenuAction::Preview => handle.open_preview(path),
+            MenuAction::DownloadFolder => on_run.run(path_command("export-zip", &path)),
+            MenuAction::CopyPath | MenuAction::CopyUrl => {
+                let text = if action == MenuAction::CopyPath {
+                    Some(path.as_str().to_string())
+                } else {
+                    absolute_hash_url(&content_href_for_path(path.as_str()))
+                };
+                let what = if action == MenuAction::CopyPath {
+                    "path"
+                } else {
+                    "URL"
+                };
+                wasm_bindgen_futures::spawn_local(async move {
+                    let result = match text {
+                        Some(text) => copy_to_clipboard(&text).await,
+                        None => Err("page URL not available".to_string()),
+                    };
+                    match result {
+                        Ok(()) => ctx.terminal.push_output(OutputLine::success(format!(
+                            "{what} of {} copied to clipboard",
+                            path.as_str()
+                        ))),
+                        Err(error) => ctx
+                            .terminal
+                            .push_output(OutputLine::error(format!("copy {what}: {error}"))),
+                    }
+                });
+            }
+        }
+    };
+
+    let handle_keydown = {
+        let enabled = enabled.clone();
+        move |ev: ev::KeyboardEvent| {
+            let step = match ev.key().as_str() {
+                "ArrowDown" => Some(true),
+                "ArrowUp" => Some(false),
+                "Escape" | "Tab" => {
+                    ev.prevent_default();
+                    close();
+                    return;
+                }
+                _ => None,
+            };
+            if let Some(forward) = step {
+                ev.prevent_default();
+                if let Some(index) = step_enabled(&enabled, active.get_untracked(), forward) {
+                    focus_item(index);
+                }
+            }
+        }
+    };
+
+    let items = ENTRY_MENU_ITEMS
+        .iter()
+        .zip(enabled)
+        .enumerate()
+        .map(|(index, (item, enabled))| {
+            let action = item.action;
+            let run = run.clone();
+            let icon = item.icon.map(|icon| view! { <ic::SvgIcon icon=icon /> });
+            let label = item.label;
+            view! {
+                <button
+                    type="button"
+                    role="menuitem"
+                    class=css::item
+                    disabled=!enabled
+                    on:focus=move |_| active.set(Some(index))
+                    on:click=move |ev: ev::MouseEvent| {
+                        ev.stop_propagation();
+                        handle.close();
+                        run(action);
+                    }
+                >
+                    <span class=css::icon>{icon}</span>
+                    {move || label.text(ctx.locale.get())}
+                </button>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <div
+            class=css::backdrop
+            on:click=move |_| close()
+            on:contextmenu=move |ev: ev::MouseEvent| {
+                ev.prevent_default();
+                close();
+            }
+            on:wheel=move |_| close()
+        ></div>
+        <div
+            node_ref=panel_ref
+            class=css::menu
+            role="menu"
+            aria-label=format!("Actions for {}", menu.target.path.as_str())
+            style=move || {
+                let (x, y) = position.get();
+                format!("left: {x}px; top: {y}px")
+            }
+            on:click=|ev: ev::MouseEvent| ev.stop_propagation()
+            on:keydown=handle_keydown
+        >
+            {items}
+        </div>
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn target(is_dir: bool) -> MenuTarget {
+        MenuTarget {
+            path: VirtualPath::root().join("blog"),
+            is_dir,
+        }
+    }
+
+    fn enabled_actions(target: &MenuTarget) -> Vec<MenuAction> {
+        ENTRY_MENU_ITEMS
+            .iter()
+            .filter(|item| (item.enabled)(target))
+            .map(|item| item.action)
+            .collect()
+    }
+
+    #[wasm_bindgen_test]
+    fn reader_item_is_only_enabled_for_files_and_download_for_folders() {
+        assert_eq!(
+            enabled_actions(&target(false)),
+            vec![
+                MenuAction::Open,
+                MenuAction::OpenInReader,
+                MenuAction::CopyPath,
+                MenuAction::CopyUrl
+            ]
+        );
+        assert_eq!(
+            enabled_actions(&target(true)),
+            vec![
+                MenuAction::Open,
+                MenuAction::CopyPath,
+                MenuAction::CopyUrl,
+                MenuAction::DownloadFolder
+            ]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn preview_item_is_only_enabled_for_markdown_files() {
+        let markdown = MenuTarget {
+            path: VirtualPath::root().join("blog/post.md"),
+            is_dir: false,
+        };
+        assert!(enabled_actions(&markdown).contains(&MenuAction::Preview));
+        assert!(!enabled_actions(&target(false)).contains(&MenuAction::Preview));
+        let markdown_named_dir = MenuTarget {
+            is_dir: true,
+            ..markdown
+        };
+        assert!(!enabled_actions(&markdown_named_dir).contains(&MenuAction::Preview));
+    }
+
+    #[wasm_bindgen_test]
+    fn menu_that_fits_stays_at_the_pointer() {
+        assert_eq!(
+            clamp_menu_position((100.0, 50.0), (180.0, 120.0), (1024.0, 768.0)),
+            (100.0, 50.0)
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn menu_near_an_edge_is_pulled_inside() {
+        assert_eq!(
+            clamp_menu_position((1000.0, 700.0), (180.0, 120.0), (1024.0, 768.0)),
+            (836.0, 640.0)
+        );
+        assert_eq!(
+            clamp_menu_position((-20.0, 2.0), (180.0, 120.0), (1024.0, 768.0)),
+            (8.0, 8.0)
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn oversized_menu_is_pinned_to_the_top_left() {
+        assert_eq!(
+            clamp_menu_position((50.0, 50.0), (400.0, 900.0), (320.0, 480.0)),
+            (8.0, 8.0)
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn keyboard_steps_skip_disabled_items_and_wrap() {
+        let enabled = [true, false, true, true];
+        assert_eq!(step_enabled(&enabled, None, true), Some(0));
+        assert_eq!(step_enabled(&enabled, None, false), Some(3));
+        assert_eq!(step_enabled(&enabled, Some(0), true), Some(2));
+        assert_eq!(step_enabled(&enabled, Some(3), true), Some(0));
+        assert_eq!(step_enabled(&enabled, Some(2), false), Some(0));
+        assert_eq!(step_enabled(&enabled, Some(0), false), Some(3));
+        assert_eq!(step_enabled(&[false, false], None, true), None);
+    }
+}
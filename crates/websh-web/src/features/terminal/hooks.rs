@@ -69,29 +69,21 @@ impl TabCycleState {
 
     /// Build the completed input value from base and current selection.
     ///
-    /// Handles both command completion and path completion cases.
+    /// Handles command, path, and flag completion cases.
     pub fn build_completion(&self) -> Option<String> {
         self.base.with(|base| {
             let selected = self.current_match()?;
 
-            let completed = if base.contains(' ') {
-                // Path completion: base is "cmd prefix", selected is "name/"
-                let parts: Vec<&str> = base.rsplitn(2, '/').collect();
-                if parts.len() == 2 {
-                    // Has directory part
-                    format!("{}/{}", parts[1], selected.trim_end_matches('/'))
-                } else {
-                    // No directory, just command + name
-                    let cmd_parts: Vec<&str> = base.splitn(2, ' ').collect();
-                    if cmd_parts.len() == 2 {
-                        format!("{} {}", cmd_parts[0], selected.trim_end_matches('/'))
-                    } else {
-                        base.clone()
-                    }
+            let completed = match base.rsplit_once(' ') {
+                // Argument completion: base is "cmd args prefix", selected is
+                // "name/" for paths or "-flag" for flags. Only the last token
+                // is replaced; its directory part (if any) is kept.
+                Some((head, token)) => {
+                    let dir = token.rfind('/').map_or("", |idx| &token[..=idx]);
+                    format!("{} {}{}", head, dir, selected.trim_end_matches('/'))
                 }
-            } else {
                 // Command completion
-                selected
+                None => selected,
             };
 
             Some(completed)
@@ -141,3 +133,71 @@ impl Default for HintState {
         Self::new()
     }
 }
+
+/// Callback for reverse history search: `(query, before)` to the matching
+/// `(index, command)`.
+pub type HistorySearchCallback = Callback<(String, Option<usize>), Option<(usize, String)>>;
+
+/// State for reverse incremental history search (Ctrl+R).
+#[derive(Clone, Copy)]
+pub struct SearchState {
+    /// Whether search mode is active.
+    pub active: RwSignal<bool>,
+    /// Text typed since search mode started.
+    pub query: RwSignal<String>,
+    /// History index of the current match, if any.
+    pub match_index: RwSignal<Option<usize>>,
+    /// Whether the last lookup found nothing.
+    pub failed: RwSignal<bool>,
+    /// Input value before the search started, restored on cancel.
+    pub original: RwSignal<String>,
+}
+
+impl SearchState {
+    /// Create a new, inactive search state.
+    pub fn new() -> Self {
+        Self {
+            active: RwSignal::new(false),
+            query: RwSignal::new(String::new()),
+            match_index: RwSignal::new(None),
+            failed: RwSignal::new(false),
+            original: RwSignal::new(String::new()),
+        }
+    }
+
+    /// Check if search mode is active.
+    pub fn is_active(&self) -> bool {
+        self.active.get()
+    }
+
+    /// Enter search mode, remembering the current input for cancellation.
+    pub fn start(&self, original: String) {
+        self.original.set(original);
+        self.query.set(String::new());
+        self.match_index.set(None);
+        self.failed.set(false);
+        self.active.set(true);
+    }
+
+    /// Leave search mode.
+    pub fn stop(&self) {
+        if self.is_active() {
+            self.active.set(false);
+            self.query.set(String::new());
+            self.match_index.set(None);
+            self.failed.set(false);
+        }
+    }
+
+    /// Prompt label shown in place of the regular prompt while searching.
+    pub fn label(&self) -> String {
+        let prefix = if self.failed.get() { "failed " } else { "" };
+        format!("({}reverse-i-search)`{}`: ", prefix, self.query.get())
+    }
+}
+
+impl Default for SearchState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
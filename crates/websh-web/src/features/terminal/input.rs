@@ -1,29 +1,44 @@
 //! Terminal input component with autocompletion and history navigation.
 
+use gloo_timers::future::TimeoutFuture;
 use leptos::prelude::CollectView;
 use leptos::{ev, prelude::*};
 use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
 
-use super::hooks::{HintState, TabCycleState};
-use websh_core::shell::AutocompleteResult;
+use super::hooks::{HintState, HistorySearchCallback, SearchState, TabCycleState};
+use super::output::prompt_view;
+use websh_core::shell::{AutocompleteResult, Prompt};
 
 stylance::import_crate_style!(css, "src/features/terminal/input.module.css");
 
 /// Terminal input field with autocomplete, history navigation, and ghost text hints.
 #[component]
 pub fn Input(
-    #[prop(into)] prompt: Signal<String>,
+    #[prop(into)] prompt: Signal<Prompt>,
     on_submit: Callback<String>,
-    on_history_nav: Callback<i32, Option<String>>,
+    on_history_nav: Callback<(i32, String), Option<String>>,
+    on_history_search: HistorySearchCallback,
     on_autocomplete: Callback<String, AutocompleteResult>,
     on_get_hint: Callback<String, Option<String>>,
+    /// Ctrl+C: receives the abandoned input line.
+    on_interrupt: Callback<String>,
+    /// Ctrl+L: clear the screen, keeping the input line.
+    on_clear_screen: Callback<()>,
+    /// n / N on an empty line: step to the next older (`true`) or newer
+    /// scrollback match. Returns `false` when no search is open.
+    on_output_search_step: Callback<bool, bool>,
+    /// Text the input line starts with, e.g. a command from a deep link.
+    #[prop(optional)]
+    initial_value: String,
 ) -> impl IntoView {
     let input_ref = NodeRef::<leptos::html::Input>::new();
-    let (input_value, set_input_value) = signal(String::new());
+    let (input_value, set_input_value) = signal(initial_value);
 
     // State management using custom hooks
     let tab_state = TabCycleState::new();
     let hint_state = HintState::new();
+    let search_state = SearchState::new();
 
     // Focus input on mount
     Effect::new(move || {
@@ -40,10 +55,90 @@ pub fn Input(
         }
     };
 
+    // Caret position as a byte index into the input value.
+    let cursor_byte_index = move || -> Option<usize> {
+        let input = input_ref.get()?;
+        let value = input.value();
+        let start = input.selection_start().ok().flatten()?;
+        Some(utf16_to_byte_index(&value, start))
+    };
+
+    let has_selection = move || {
+        input_ref.get().is_some_and(|input| {
+            let start = input.selection_start().ok().flatten();
+            let end = input.selection_end().ok().flatten();
+            start.is_some() && start != end
+        })
+    };
+
     // Reset all transient state
     let reset_state = move || {
         tab_state.clear();
         hint_state.clear();
+        search_state.stop();
+    };
+
+    // Look up the current search query, older than `before` if given.
+    // On a miss the buffer keeps the previous match.
+    let run_search = move |before: Option<usize>| {
+        let query = search_state.query.get();
+        match on_history_search.run((query, before)) {
+            Some((idx, cmd)) => {
+                search_state.match_index.set(Some(idx));
+                search_state.failed.set(false);
+                set_input_value.set(cmd);
+            }
+            None => search_state
+                .failed
+                .set(!search_state.query.get().is_empty()),
+        }
+    };
+
+    // Handle a key while reverse-i-search is active. Returns false when the
+    // key ends the search and should get its regular handling too.
+    let handle_search_key = move |ev: &ev::KeyboardEvent| -> bool {
+        let key = ev.key();
+        match key.as_str() {
+            "r" if ev.ctrl_key() => {
+                ev.prevent_default();
+                run_search(search_state.match_index.get());
+            }
+            "Enter" => {
+                // Accept the match into the buffer without executing it.
+                ev.prevent_default();
+                search_state.stop();
+                move_cursor_to_end();
+            }
+            "Escape" => {
+                ev.prevent_default();
+                set_input_value.set(search_state.original.get());
+                search_state.stop();
+            }
+            "c" | "g" if ev.ctrl_key() => {
+                ev.prevent_default();
+                set_input_value.set(search_state.original.get());
+                search_state.stop();
+            }
+            "Backspace" => {
+                ev.prevent_default();
+                search_state.query.update(|query| {
+                    query.pop();
+                });
+                run_search(None);
+            }
+            _ if key.chars().count() == 1 && !ev.ctrl_key() && !ev.meta_key() && !ev.alt_key() => {
+                ev.prevent_default();
+                search_state.query.update(|query| query.push_str(&key));
+                run_search(None);
+            }
+            // Modifier keys alone keep the search open.
+            "Shift" | "Control" | "Alt" | "Meta" => {}
+            _ => {
+                search_state.stop();
+                return false;
+            }
+        }
+        true
     };
 
     // Handle Tab key for autocompletion
@@ -97,38 +192,58 @@ pub fn Input(
     };
 
     let handle_keydown = move |ev: ev::KeyboardEvent| {
-        match ev.key().as_str() {
-            "Tab" => {
+        if search_state.is_active() && handle_search_key(&ev) {
+            return;
+        }
+
+        let key = ev.key();
+        let plain = !ev.ctrl_key() && !ev.alt_key() && !ev.meta_key();
+        if plain
+            && matches!(key.as_str(), "n" | "N")
+            && input_value.get().is_empty()
+            && on_output_search_step.run(key == "n")
+        {
+            ev.prevent_default();
+            return;
+        }
+
+        let press = KeyPress {
+            key: &key,
+            ctrl: ev.ctrl_key(),
+            alt: ev.alt_key(),
+            meta: ev.meta_key(),
+        };
+        match key_action(&press) {
+            KeyAction::Complete => {
                 ev.prevent_default();
                 if let Some(completed) = handle_tab(input_value.get()) {
                     set_input_value.set(completed);
                     move_cursor_to_end();
                 }
             }
-            "Enter" => {
+            KeyAction::Submit => {
                 reset_state();
                 let value = input_value.get();
                 on_submit.run(value);
                 set_input_value.set(String::new());
             }
-            "ArrowUp" => {
+            KeyAction::HistoryPrev => {
                 ev.prevent_default();
                 reset_state();
-                if let Some(cmd) = on_history_nav.run(-1) {
+                if let Some(cmd) = on_history_nav.run((-1, input_value.get())) {
                     set_input_value.set(cmd);
                     move_cursor_to_end();
                 }
             }
-            "ArrowDown" => {
+            KeyAction::HistoryNext => {
                 ev.prevent_default();
                 reset_state();
-                if let Some(cmd) = on_history_nav.run(1) {
+                if let Some(cmd) = on_history_nav.run((1, input_value.get())) {
                     set_input_value.set(cmd);
-                } else {
-                    set_input_value.set(String::new());
+                    move_cursor_to_end();
                 }
             }
-            "ArrowRight" => {
+            KeyAction::AcceptHint => {
                 let value = input_value.get();
                 if let Some(completed) = handle_arrow_right(&value) {
                     ev.prevent_default();
@@ -136,31 +251,102 @@ pub fn Input(
                     move_cursor_to_end();
                 }
             }
-            "c" if ev.ctrl_key() => {
+            // With text selected, Ctrl+C keeps its copy meaning.
+            KeyAction::Interrupt if has_selection() => {}
+            KeyAction::Interrupt => {
+                ev.prevent_default();
                 reset_state();
+                on_interrupt.run(input_value.get());
                 set_input_value.set(String::new());
             }
-            "l" if ev.ctrl_key() => {
+            KeyAction::StartSearch => {
                 ev.prevent_default();
                 reset_state();
-                on_submit.run("clear".to_string());
+                search_state.start(input_value.get());
             }
-            "Escape" => {
+            KeyAction::ClearScreen => {
+                ev.prevent_default();
                 reset_state();
+                on_clear_screen.run(());
             }
-            _ => {
+            KeyAction::KillToStart => {
+                ev.prevent_default();
+                reset_state();
+                let value = input_value.get();
+                let cursor = cursor_byte_index().unwrap_or(value.len());
+                set_input_value.set(value[cursor..].to_string());
+                if let Some(input) = input_ref.get() {
+                    // Sync the DOM value first so the caret lands at 0.
+                    input.set_value(&value[cursor..]);
+                    let _ = input.set_selection_range(0, 0);
+                }
+            }
+            KeyAction::LineStart => {
+                ev.prevent_default();
+                if let Some(input) = input_ref.get() {
+                    let _ = input.set_selection_range(0, 0);
+                }
+            }
+            KeyAction::LineEnd => {
+                ev.prevent_default();
+                move_cursor_to_end();
+            }
+            KeyAction::Dismiss => {
+                reset_state();
+            }
+            KeyAction::Other => {
                 // Clear Tab cycling state on other keys
                 tab_state.clear();
             }
         }
     };
 
+    // Handle multi-line paste: submit every complete line, keep the last
+    // line in the buffer. Single-line pastes use the browser default.
+    let handle_paste = move |ev: ev::ClipboardEvent| {
+        let Some(text) = ev
+            .clipboard_data()
+            .and_then(|data| data.get_data("text").ok())
+        else {
+            return;
+        };
+        let Some(input) = input_ref.get() else { return };
+
+        let value = input.value();
+        let start = input.selection_start().ok().flatten().unwrap_or(0);
+        let end = input.selection_end().ok().flatten().unwrap_or(start);
+        let before = &value[..utf16_to_byte_index(&value, start)];
+        let after = &value[utf16_to_byte_index(&value, end)..];
+        let Some((commands, remainder)) = split_pasted_lines(&format!("{before}{text}{after}"))
+        else {
+            return;
+        };
+
+        ev.prevent_default();
+        reset_state();
+        set_input_value.set(remainder.clone());
+        move_cursor_to_end();
+        if !remainder.is_empty() {
+            hint_state.set(on_get_hint.run(remainder));
+        }
+
+        // Yield between commands so route changes (e.g. `cd`) land before
+        // the next line runs against the current directory.
+        spawn_local(async move {
+            for command in commands {
+                on_submit.run(command);
+                TimeoutFuture::new(0).await;
+            }
+        });
+    };
+
     let handle_input = move |ev: ev::Event| {
         let Some(target) = ev.target() else { return };
         let input = target.unchecked_into::<web_sys::HtmlInputElement>();
         let value = input.value();
         set_input_value.set(value.clone());
         tab_state.clear();
+        search_state.stop();
 
         // Update ghost text hint
         if value.is_empty() {
@@ -200,8 +386,21 @@ pub fn Input(
     view! {
         <div class=css::inputWrapper>
             <div class=css::line>
-                <span class=css::prompt>{prompt}</span>
-                <span class=css::separator>"$ "</span>
+                <Show
+                    when=move || search_state.active.get()
+                    fallback=move || {
+                        view! {
+                            <span class=css::prompt>
+                                {move || prompt.with(prompt_view)}
+                            </span>
+                            <span class=css::separator>
+                                {move || format!("{} ", prompt.with(|p| p.separator))}
+                            </span>
+                        }
+                    }
+                >
+                    <span class=css::searchLabel>{move || search_state.label()}</span>
+                </Show>
                 <div class=css::field>
                     // Ghost text overlay (shows input value + hint)
                     <div class=css::ghostOverlay>
@@ -220,6 +419,7 @@ pub fn Input(
                         prop:value=input_value
                         on:input=handle_input
                         on:keydown=handle_keydown
+                        on:paste=handle_paste
                     />
                 </div>
             </div>
@@ -229,3 +429,225 @@ pub fn Input(
         </div>
     }
 }
+
+/// A keydown reduced to what the input's key bindings look at.
+#[derive(Clone, Copy, Debug)]
+struct KeyPress<'a> {
+    key: &'a str,
+    ctrl: bool,
+    alt: bool,
+    meta: bool,
+}
+
+/// What a key press does in the input line (outside reverse-i-search).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum KeyAction {
+    Complete,
+    Submit,
+    HistoryPrev,
+    HistoryNext,
+    AcceptHint,
+    /// Ctrl+C: abandon the line.
+    Interrupt,
+    /// Ctrl+R: reverse-i-search.
+    StartSearch,
+    /// Ctrl+L
+    ClearScreen,
+    /// Ctrl+U: delete from the caret back to the start of the line.
+    KillToStart,
+    /// Ctrl+A
+    LineStart,
+    /// Ctrl+E
+    LineEnd,
+    Dismiss,
+    Other,
+}
+
+/// Map a key press to its action. Control bindings only fire for a bare
+/// Ctrl, so browser and OS shortcuts with Alt or Meta stay untouched.
+fn key_action(press: &KeyPress) -> KeyAction {
+    let ctrl_only = press.ctrl && !press.alt && !press.meta;
+    match press.key {
+        "Tab" => KeyAction::Complete,
+        "Enter" => KeyAction::Submit,
+        "ArrowUp" => KeyAction::HistoryPrev,
+        "ArrowDown" => KeyAction::HistoryNext,
+        "ArrowRight" => KeyAction::AcceptHint,
+        "Escape" => KeyAction::Dismiss,
+        "c" if ctrl_only => KeyAction::Interrupt,
+        "r" if ctrl_only => KeyAction::StartSearch,
+        "l" if ctrl_only => KeyAction::ClearScreen,
+        "u" if ctrl_only => KeyAction::KillToStart,
+        "a" if ctrl_only => KeyAction::LineStart,
+        "e" if ctrl_only => KeyAction::LineEnd,
+        _ => KeyAction::Other,
+    }
+}
+
+/// Split pasted text into commands to submit and the line left in the buffer.
+///
+/// Returns `None` when the text holds a single line, so the caller can fall
+/// back to the native paste. A line ending in `\` or `|` is a wrapped
+/// command and is joined with the next one. Trailing blank lines are ignored.
+fn split_pasted_lines(text: &str) -> Option<(Vec<String>, String)> {
+    if !text.contains('\n') {
+        return None;
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut pending: Option<String> = None;
+    for raw in text.split('\n') {
+        let line = raw.strip_suffix('\r').unwrap_or(raw);
+        let line = match pending.take() {
+            Some(head) => format!("{head}{line}"),
+            None => line.to_string(),
+        };
+        if let Some(head) = line.strip_suffix('\\') {
+            pending = Some(head.to_string());
+        } else if line.trim_end().ends_with('|') {
+            pending = Some(format!("{} ", line.trim_end()));
+        } else {
+            lines.push(line);
+        }
+    }
+    if let Some(head) = pending {
+        lines.push(head.trim_end().to_string());
+    }
+
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    let remainder = lines.pop().unwrap_or_default();
+    Some((lines, remainder))
+}
+
+/// Convert a UTF-16 offset (as reported by DOM selection APIs) to a byte index.
+fn utf16_to_byte_index(value: &str, offset: u32) -> usize {
+    let mut units = 0;
+    for (idx, ch) in value.char_indices() {
+        if units >= offset as usize {
+            return idx;
+        }
+        units += ch.len_utf16();
+    }
+    value.len()
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn owned(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    fn press(key: &str) -> KeyPress<'_> {
+        KeyPress {
+            key,
+            ctrl: false,
+            alt: false,
+            meta: false,
+        }
+    }
+
+    fn ctrl(key: &str) -> KeyPress<'_> {
+        KeyPress {
+            ctrl: true,
+            ..press(key)
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn ctrl_bindings_map_to_line_actions() {
+        assert_eq!(key_action(&ctrl("c")), KeyAction::Interrupt);
+        assert_eq!(key_action(&ctrl("l")), KeyAction::ClearScreen);
+        assert_eq!(key_action(&ctrl("u")), KeyAction::KillToStart);
+        assert_eq!(key_action(&ctrl("a")), KeyAction::LineStart);
+        assert_eq!(key_action(&ctrl("e")), KeyAction::LineEnd);
+        assert_eq!(key_action(&ctrl("r")), KeyAction::StartSearch);
+    }
+
+    #[wasm_bindgen_test]
+    fn letters_without_ctrl_are_plain_input() {
+        for key in ["c", "l", "u", "a", "e", "r"] {
+            assert_eq!(key_action(&press(key)), KeyAction::Other, "{key}");
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn ctrl_with_other_modifiers_is_left_to_the_browser() {
+        let with_meta = KeyPress {
+            meta: true,
+            ..ctrl("c")
+        };
+        let with_alt = KeyPress {
+            alt: true,
+            ..ctrl("l")
+        };
+        assert_eq!(key_action(&with_meta), KeyAction::Other);
+        assert_eq!(key_action(&with_alt), KeyAction::Other);
+        assert_eq!(key_action(&ctrl("C")), KeyAction::Other);
+    }
+
+    #[wasm_bindgen_test]
+    fn navigation_keys_map_regardless_of_modifiers() {
+        assert_eq!(key_action(&press("Tab")), KeyAction::Complete);
+        assert_eq!(key_action(&press("Enter")), KeyAction::Submit);
+        assert_eq!(key_action(&press("ArrowUp")), KeyAction::HistoryPrev);
+        assert_eq!(key_action(&press("ArrowDown")), KeyAction::HistoryNext);
+        assert_eq!(key_action(&press("ArrowRight")), KeyAction::AcceptHint);
+        assert_eq!(key_action(&press("Escape")), KeyAction::Dismiss);
+    }
+
+    #[wasm_bindgen_test]
+    fn single_line_paste_uses_native_behavior() {
+        assert_eq!(split_pasted_lines("ls -l"), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn multi_line_paste_submits_all_but_last() {
+        assert_eq!(
+            split_pasted_lines("cd /blog\nls\ncat post.md"),
+            Some((owned(&["cd /blog", "ls"]), "cat post.md".to_string()))
+        );
+        assert_eq!(
+            split_pasted_lines("pwd\r\nwhoami\r\n"),
+            Some((owned(&["pwd"]), "whoami".to_string()))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn trailing_blank_lines_are_ignored() {
+        assert_eq!(
+            split_pasted_lines("ls\npwd\n\n  \n"),
+            Some((owned(&["ls"]), "pwd".to_string()))
+        );
+        assert_eq!(
+            split_pasted_lines("\n\n"),
+            Some((Vec::new(), String::new()))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn wrapped_commands_are_joined() {
+        assert_eq!(
+            split_pasted_lines("ls |\ngrep md\n"),
+            Some((Vec::new(), "ls | grep md".to_string()))
+        );
+        assert_eq!(
+            split_pasted_lines("echo hello \\\nworld\npwd"),
+            Some((owned(&["echo hello world"]), "pwd".to_string()))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn utf16_offsets_map_to_byte_indices() {
+        assert_eq!(utf16_to_byte_index("abc", 2), 2);
+        assert_eq!(utf16_to_byte_index("한국", 1), 3);
+        assert_eq!(utf16_to_byte_index("a😀b", 3), 5);
+        assert_eq!(utf16_to_byte_index("ab", 10), 2);
+    }
+}
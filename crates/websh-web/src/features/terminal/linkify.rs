@@ -0,0 +1,176 @@
+//! URL detection for plain terminal output.
+//!
+//! Only `http(s)` URLs that pass [`validate_redirect_url`] become links; all
+//! other text, including rejected URLs, stays inert.
+
+use crate::platform::redirect::{UrlValidation, validate_redirect_url};
+
+/// A run of terminal text, either inert or a validated link.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum TextSegment {
+    Text(String),
+    Link { href: String, text: String },
+}
+
+/// Entities that end a URL when output already contains escaped HTML.
+const TERMINATING_ENTITIES: &[&str] = &["&lt;", "&gt;", "&quot;", "&#39;", "&#x27;"];
+
+/// Split `text` into inert runs and validated links.
+pub(super) fn linkify(text: &str) -> Vec<TextSegment> {
+    let mut segments = Vec::new();
+    let mut plain_start = 0;
+    let mut cursor = 0;
+
+    while let Some(offset) = find_url_start(&text[cursor..]) {
+        let start = cursor + offset;
+        let end = start + url_len(&text[start..]);
+        let url = &text[start..end];
+
+        if let UrlValidation::Valid(href) = validate_redirect_url(&url.replace("&amp;", "&"), None)
+        {
+            if plain_start < start {
+                segments.push(TextSegment::Text(text[plain_start..start].to_string()));
+            }
+            segments.push(TextSegment::Link {
+                href,
+                text: url.to_string(),
+            });
+            plain_start = end;
+        }
+        cursor = end.max(start + 1);
+    }
+
+    if plain_start < text.len() {
+        segments.push(TextSegment::Text(text[plain_start..].to_string()));
+    }
+    segments
+}
+
+fn find_url_start(text: &str) -> Option<usize> {
+    let lower = text.to_ascii_lowercase();
+    [lower.find("https://"), lower.find("http://")]
+        .into_iter()
+        .flatten()
+        .min()
+}
+
+/// Length of the URL at the start of `text`, without trailing punctuation.
+fn url_len(text: &str) -> usize {
+    let mut end = text
+        .char_indices()
+        .find(|(idx, ch)| {
+            ch.is_whitespace()
+                || matches!(ch, '<' | '>' | '"' | '\'' | '`')
+                || TERMINATING_ENTITIES
+                    .iter()
+                    .any(|entity| text[*idx..].starts_with(entity))
+        })
+        .map_or(text.len(), |(idx, _)| idx);
+
+    loop {
+        let candidate = &text[..end];
+        let Some(last) = candidate.chars().last() else {
+            break;
+        };
+        let strip = match last {
+            '.' | ',' | ';' | ':' | '!' | '?' => true,
+            ')' => candidate.matches(')').count() > candidate.matches('(').count(),
+            ']' => candidate.matches(']').count() > candidate.matches('[').count(),
+            '}' => candidate.matches('}').count() > candidate.matches('{').count(),
+            _ => false,
+        };
+        if !strip {
+            break;
+        }
+        end -= last.len_utf8();
+    }
+    end
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn link(url: &str) -> TextSegment {
+        TextSegment::Link {
+            href: url.to_string(),
+            text: url.to_string(),
+        }
+    }
+
+    fn text(value: &str) -> TextSegment {
+        TextSegment::Text(value.to_string())
+    }
+
+    #[wasm_bindgen_test]
+    fn plain_text_is_a_single_segment() {
+        assert_eq!(linkify("no links here"), vec![text("no links here")]);
+        assert!(linkify("").is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn links_allowed_urls_in_text() {
+        assert_eq!(
+            linkify("see https://github.com/0xwonj for code"),
+            vec![
+                text("see "),
+                link("https://github.com/0xwonj"),
+                text(" for code"),
+            ]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn strips_trailing_punctuation() {
+        assert_eq!(
+            linkify("visit https://github.com/0xwonj."),
+            vec![text("visit "), link("https://github.com/0xwonj"), text(".")]
+        );
+        assert_eq!(
+            linkify("(https://x.com/wonj)!"),
+            vec![text("("), link("https://x.com/wonj"), text(")!")]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn keeps_balanced_parentheses() {
+        assert_eq!(
+            linkify("https://github.com/a_(b)"),
+            vec![link("https://github.com/a_(b)")]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn stops_at_escaped_html_delimiters() {
+        assert_eq!(
+            linkify("&lt;https://github.com/0xwonj&gt;"),
+            vec![
+                text("&lt;"),
+                link("https://github.com/0xwonj"),
+                text("&gt;"),
+            ]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn decodes_escaped_ampersands_in_href_only() {
+        assert_eq!(
+            linkify("https://github.com/search?q=a&amp;type=code"),
+            vec![TextSegment::Link {
+                href: "https://github.com/search?q=a&type=code".to_string(),
+                text: "https://github.com/search?q=a&amp;type=code".to_string(),
+            }]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn leaves_rejected_urls_inert() {
+        assert_eq!(
+            linkify("https://evil.example/phish and https://"),
+            vec![text("https://evil.example/phish and https://")]
+        );
+    }
+}
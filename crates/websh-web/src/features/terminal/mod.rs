@@ -1,11 +1,18 @@
 mod actions;
 pub(crate) mod boot;
+mod entry_menu;
 mod hooks;
 mod input;
+mod linkify;
 mod output;
+mod preview;
+mod selection;
+mod session;
 pub(crate) mod shell;
 #[allow(clippy::module_inception)]
 mod terminal;
+mod transcript;
+mod tutorial;
 
 pub(crate) use actions::dispatch_side_effect;
 pub(crate) use input::Input;
@@ -1,7 +1,16 @@
+use std::ops::Range;
+
+use crate::platform::dom::focus_terminal_input;
 use crate::shared::icons as ic;
 use leptos::prelude::*;
-use websh_core::shell::{ListFormat, OutputLine, OutputLineData, TextStyle};
-use websh_core::support::format::{format_date_short, format_size};
+use websh_core::domain::VirtualPath;
+use websh_core::ports::EntryChange;
+use websh_core::shell::{ListFormat, OutputLine, OutputLineData, Prompt, TextStyle};
+use websh_core::support::format::format_size;
+
+use super::entry_menu::{EntryMenuHandle, LONG_PRESS_MS, MenuTarget};
+use super::linkify::{TextSegment, linkify};
+use super::selection::SelectionHandle;
 
 stylance::import_crate_style!(css, "src/features/terminal/output.module.css");
 
@@ -14,19 +23,268 @@ fn style_class(style: TextStyle) -> &'static str {
     }
 }
 
+/// Prompt text, with `PS1` colour runs in their list styles. Unstyled
+/// runs take the surrounding prompt colour.
+pub(super) fn prompt_view(prompt: &Prompt) -> impl IntoView + use<> {
+    prompt
+        .segments
+        .iter()
+        .map(|segment| match segment.style {
+            Some(style) => {
+                view! { <span class=style_class(style)>{segment.text.clone()}</span> }.into_any()
+            }
+            None => segment.text.clone().into_any(),
+        })
+        .collect_view()
+}
+
+/// Command that opens a listed entry: `cd` for directories, `cat` for files.
+pub(super) fn open_entry_command(path: &VirtualPath, is_dir: bool) -> String {
+    path_command(if is_dir { "cd" } else { "cat" }, path)
+}
+
+/// `verb path`, with the path quoted when the shell would split it.
+pub(super) fn path_command(verb: &str, path: &VirtualPath) -> String {
+    let raw = path.as_str();
+    let safe = raw
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '/' | '.' | '-' | '_'));
+    if safe {
+        format!("{verb} {raw}")
+    } else if raw.contains('\'') {
+        format!("{verb} \"{raw}\"")
+    } else {
+        format!("{verb} '{raw}'")
+    }
+}
+
+/// What a listed entry can do when interacted with; see [`Output`].
+#[derive(Clone, Copy)]
+struct EntryHandlers {
+    on_run: Option<Callback<String>>,
+    selection: Option<SelectionHandle>,
+    menu: Option<EntryMenuHandle>,
+}
+
+/// Split `text` into `(segment, highlighted)` runs. Ranges that overlap an
+/// earlier one or do not fall on character boundaries are ignored.
+fn highlight_segments<'a>(text: &'a str, highlights: &[Range<usize>]) -> Vec<(&'a str, bool)> {
+    let mut segments = Vec::new();
+    let mut cursor = 0;
+    for range in highlights {
+        if range.start < cursor || text.get(range.clone()).is_none() {
+            continue;
+        }
+        if range.start > cursor {
+            segments.push((&text[cursor..range.start], false));
+        }
+        segments.push((&text[range.clone()], true));
+        cursor = range.end;
+    }
+    if cursor < text.len() {
+        segments.push((&text[cursor..], false));
+    }
+    segments
+}
+
+fn render_highlighted(text: &str, highlights: &[Range<usize>]) -> AnyView {
+    highlight_segments(text, highlights)
+        .into_iter()
+        .map(|(segment, highlighted)| {
+            let segment = segment.to_string();
+            if highlighted {
+                view! { <mark class=css::matchHighlight>{segment}</mark> }.into_any()
+            } else {
+                segment.into_any()
+            }
+        })
+        .collect_view()
+        .into_any()
+}
+
+/// Name of a listed entry: styled by kind, suffixed with `/` for
+/// directories, badged when it is a local draft, and clickable when it has
+/// a path and `on_run` is set. Modifier-clicks go to `selection` instead of opening the entry, and a
+/// right-click or long press opens `menu`.
+fn entry_name(
+    name: String,
+    highlights: &[Range<usize>],
+    style: TextStyle,
+    encrypted: bool,
+    draft: bool,
+    path: Option<VirtualPath>,
+    handlers: EntryHandlers,
+) -> AnyView {
+    let EntryHandlers {
+        on_run,
+        selection,
+        menu,
+    } = handlers;
+    let is_dir = style == TextStyle::Directory;
+    let selectable = path.clone().zip(selection);
+    let menu_target = path
+        .clone()
+        .zip(menu)
+        .map(|(path, menu)| (MenuTarget { path, is_dir }, menu));
+    let open_command = path
+        .zip(on_run)
+        .map(|(path, on_run)| (open_entry_command(&path, is_dir), on_run));
+    let mut name_class = if is_dir {
+        format!("{} {}", style_class(style), css::fontBold)
+    } else {
+        style_class(style).to_string()
+    };
+    if open_command.is_some() {
+        name_class = format!("{} {}", name_class, css::entryLink);
+    }
+    let open_title = open_command.as_ref().map(|(command, _)| command.clone());
+    let selected_class = {
+        let selectable = selectable.clone();
+        move || {
+            let selected = selectable
+                .as_ref()
+                .is_some_and(|(path, selection)| selection.is_selected(path));
+            if selected {
+                format!("{} {}", name_class, css::entrySelected)
+            } else {
+                name_class.clone()
+            }
+        }
+    };
+    let long_press = StoredValue::new_local(None::<gloo_timers::callback::Timeout>);
+    let long_pressed = StoredValue::new(false);
+    let cancel_long_press = move || long_press.set_value(None);
+    let handle_context_menu = {
+        let menu_target = menu_target.clone();
+        move |ev: leptos::ev::MouseEvent| {
+            if let Some((target, menu)) = &menu_target {
+                ev.prevent_default();
+                cancel_long_press();
+                menu.open(
+                    target.clone(),
+                    f64::from(ev.client_x()),
+                    f64::from(ev.client_y()),
+                );
+            }
+        }
+    };
+    let handle_touch_start = move |ev: leptos::ev::TouchEvent| {
+        let (Some((target, menu)), Some(touch)) = (menu_target.clone(), ev.touches().get(0)) else {
+            return;
+        };
+        let (x, y) = (f64::from(touch.client_x()), f64::from(touch.client_y()));
+        long_pressed.set_value(false);
+        long_press.set_value(Some(gloo_timers::callback::Timeout::new(
+            LONG_PRESS_MS,
+            move || {
+                long_pressed.set_value(true);
+                menu.open(target, x, y);
+            },
+        )));
+    };
+    let handle_open = move |ev: leptos::ev::MouseEvent| {
+        // The click that ends a long press must not also open the entry.
+        if long_pressed.get_value() {
+            long_pressed.set_value(false);
+            return;
+        }
+        if let Some((path, selection)) = &selectable
+            && selection.click(path, &ev)
+        {
+            ev.prevent_default();
+            return;
+        }
+        if let Some((command, on_run)) = &open_command {
+            on_run.run(command.clone());
+            focus_terminal_input();
+        }
+    };
+    let suffix = if is_dir { "/" } else { "" };
+    let display_name = render_highlighted(&name, highlights);
+    let lock_marker = encrypted.then(|| {
+        view! {
+            <span class=css::lockIcon aria-label="encrypted">
+                <ic::SvgIcon icon=ic::LOCK />
+            </span>
+        }
+    });
+    let draft_marker = draft.then(|| {
+        view! { <span class=css::draftBadge title="local draft">"draft"</span> }
+    });
+
+    view! {
+        <span
+            class=selected_class
+            title=open_title
+            on:click=handle_open
+            on:contextmenu=handle_context_menu
+            on:touchstart=handle_touch_start
+            on:touchmove=move |_| cancel_long_press()
+            on:touchend=move |_| cancel_long_press()
+            on:touchcancel=move |_| cancel_long_press()
+        >
+            {display_name}
+            {suffix}
+            {lock_marker}
+            {draft_marker}
+        </span>
+    }
+    .into_any()
+}
+
+/// Message text with `highlights` marked, or with URLs linked when there
+/// are none.
+fn render_text(text: String, highlights: &[Range<usize>]) -> AnyView {
+    if !highlights.is_empty() {
+        return render_highlighted(&text, highlights);
+    }
+    linkify(&text)
+        .into_iter()
+        .map(|segment| match segment {
+            TextSegment::Text(text) => text.into_any(),
+            TextSegment::Link { href, text } => view! {
+                <a class=css::link href=href target="_blank" rel="noopener noreferrer">
+                    {text}
+                </a>
+            }
+            .into_any(),
+        })
+        .collect_view()
+        .into_any()
+}
+
+/// Render one terminal output line.
+///
+/// With `on_run`, listed entries that carry a path become clickable and run
+/// the matching `cd`/`cat` command through it. With `selection`, they can
+/// also be Ctrl/Cmd- and Shift-clicked into a multi-selection, and with
+/// `menu` they open a context menu on right-click or long press.
 #[component]
-pub fn Output(line: OutputLine) -> impl IntoView {
+pub fn Output(
+    line: OutputLine,
+    #[prop(optional)] on_run: Option<Callback<String>>,
+    #[prop(optional)] selection: Option<SelectionHandle>,
+    #[prop(optional)] menu: Option<EntryMenuHandle>,
+) -> impl IntoView {
+    let handlers = EntryHandlers {
+        on_run,
+        selection,
+        menu,
+    };
+    let highlights = line.highlights;
     match line.data {
         OutputLineData::Command { prompt, input } => view! {
             <div class=css::command>
-                <span class=format!("{} glow", css::textGreen)>{prompt}</span>
-                <span class=css::textDim>"$ "</span>
-                <span class=css::textFg>{input}</span>
+                <span class=format!("{} glow", css::textGreen)>{prompt_view(&prompt)}</span>
+                <span class=css::textDim>{format!("{} ", prompt.separator)}</span>
+                <span class=css::textFg>{render_highlighted(&input, &highlights)}</span>
             </div>
         }
         .into_any(),
         OutputLineData::Text(text) => view! {
-            <div class=format!("{} {}", css::line, css::textDim)>{text}</div>
+            <div class=format!("{} {}", css::line, css::textDim)>
+                {render_text(text, &highlights)}
+            </div>
         }
         .into_any(),
         OutputLineData::ListEntry {
@@ -34,35 +292,44 @@ pub fn Output(line: OutputLine) -> impl IntoView {
             description,
             style,
             encrypted,
+            draft,
             format,
+            path,
         } => {
-            let is_dir = style == TextStyle::Directory;
-            let name_class = if is_dir {
-                format!("{} {}", style_class(style), css::fontBold)
-            } else {
-                style_class(style).to_string()
-            };
-            let suffix = if is_dir { "/" } else { "" };
-            let display_name = format!("{}{}", name, suffix);
-            let lock_marker = encrypted.then(|| {
-                view! {
-                    <span class=css::lockIcon aria-label="encrypted">
-                        <ic::SvgIcon icon=ic::LOCK />
-                    </span>
-                }
-            });
-
+            let name_view = entry_name(name, &highlights, style, encrypted, draft, path, handlers);
             match format {
                 ListFormat::Short => view! {
                     <div class=css::listEntry>
-                        <span class=name_class>
-                            {display_name}
-                            {lock_marker}
-                        </span>
+                        {name_view}
                         <span class=css::textDim>{description}</span>
                     </div>
                 }
                 .into_any(),
+                ListFormat::Diff { change } => {
+                    let (marker, marker_class) = match change {
+                        Some(EntryChange::Added) => ("+", css::textGreen),
+                        Some(EntryChange::Modified) => ("~", css::textYellow),
+                        Some(EntryChange::Removed) => ("-", css::textRed),
+                        None => (" ", css::textDim),
+                    };
+                    let entry_class = if change == Some(EntryChange::Removed) {
+                        format!("{} {}", css::diffEntry, css::diffRemoved)
+                    } else {
+                        css::diffEntry.to_string()
+                    };
+                    view! {
+                        <div class=css::listEntry>
+                            <span class=entry_class>
+                                <span class=format!("{} {}", css::diffMarker, marker_class)>
+                                    {marker}
+                                </span>
+                                {name_view}
+                            </span>
+                            <span class=css::textDim>{description}</span>
+                        </div>
+                    }
+                    .into_any()
+                }
                 ListFormat::Long {
                     permissions,
                     size,
@@ -71,30 +338,59 @@ pub fn Output(line: OutputLine) -> impl IntoView {
                     <div class=css::longEntry>
                         <span class=css::textDim>{permissions}</span>
                         <span class=css::textDim>{format_size(size, true)}</span>
-                        <span class=css::textDim>{format_date_short(modified)}</span>
-                        <span class=name_class>
-                            {display_name}
-                            {lock_marker}
-                        </span>
+                        <span class=css::textDim>{modified}</span>
+                        {name_view}
                     </div>
                 }
                 .into_any(),
             }
         }
+        OutputLineData::ListRow(cells) => view! {
+            <div class=css::listRow>
+                {cells
+                    .into_iter()
+                    .map(|cell| {
+                        let width = format!("min-width: {}ch", cell.width);
+                        view! {
+                            <span class=css::listCell style=width>
+                                {entry_name(
+                                    cell.name,
+                                    &[],
+                                    cell.style,
+                                    cell.encrypted,
+                                    cell.draft,
+                                    cell.path,
+                                    handlers,
+                                )}
+                            </span>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+        }
+        .into_any(),
         OutputLineData::Error(text) => view! {
-            <div class=format!("{} {}", css::line, css::textRed)>{text}</div>
+            <div class=format!("{} {}", css::line, css::textRed)>
+                {render_highlighted(&text, &highlights)}
+            </div>
         }
         .into_any(),
         OutputLineData::Success(text) => view! {
-            <div class=format!("{} {}", css::line, css::textGreen)>{text}</div>
+            <div class=format!("{} {}", css::line, css::textGreen)>
+                {render_highlighted(&text, &highlights)}
+            </div>
         }
         .into_any(),
         OutputLineData::Info(text) => view! {
-            <div class=format!("{} {}", css::line, css::textYellow)>{text}</div>
+            <div class=format!("{} {}", css::line, css::textYellow)>
+                {render_highlighted(&text, &highlights)}
+            </div>
         }
         .into_any(),
         OutputLineData::Ascii(text) => view! {
-            <pre class=format!("{} glow", css::ascii)>{text}</pre>
+            <pre class=format!("{} glow", css::ascii)>
+                {render_highlighted(&text, &highlights)}
+            </pre>
         }
         .into_any(),
         OutputLineData::Empty => view! {
@@ -103,3 +399,44 @@ pub fn Output(line: OutputLine) -> impl IntoView {
         .into_any(),
     }
 }
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn open_command_uses_cd_for_dirs_and_cat_for_files() {
+        let dir = VirtualPath::root().join("blog");
+        let file = VirtualPath::root().join("blog/post.md");
+        assert_eq!(open_entry_command(&dir, true), "cd /blog");
+        assert_eq!(open_entry_command(&file, false), "cat /blog/post.md");
+    }
+
+    #[wasm_bindgen_test]
+    fn open_command_quotes_unusual_paths() {
+        let spaced = VirtualPath::root().join("my notes.md");
+        assert_eq!(open_entry_command(&spaced, false), "cat '/my notes.md'");
+        let quoted = VirtualPath::root().join("it's.md");
+        assert_eq!(open_entry_command(&quoted, false), "cat \"/it's.md\"");
+    }
+
+    #[wasm_bindgen_test]
+    fn highlight_segments_split_around_matches() {
+        assert_eq!(
+            highlight_segments("banana bread", &[1..3, 3..5]),
+            vec![("b", false), ("an", true), ("an", true), ("a bread", false)]
+        );
+        assert_eq!(highlight_segments("blog", &[]), vec![("blog", false)]);
+    }
+
+    #[wasm_bindgen_test]
+    fn highlight_segments_skip_invalid_ranges() {
+        assert_eq!(
+            highlight_segments("café", &[3..4, 0..2, 1..3, 8..9]),
+            vec![("ca", true), ("fé", false)]
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! Rendered preview of a markdown file listed in terminal output.
+//!
+//! Opened from the entry menu, the preview sheet loads the file the way the
+//! Reader does and renders it with the Reader's markdown styles. Long files
+//! show their opening portion and an "Open full" button into the Reader.
+
+use leptos::ev;
+use leptos::prelude::*;
+use websh_core::domain::VirtualPath;
+use websh_core::filesystem::{RouteRequest, RouteSurface, request_path_for_canonical_path};
+
+use crate::app::AppContext;
+use crate::features::reader::css as reader_css;
+use crate::platform::dom::{focus_terminal_input, push_route};
+use crate::render::{RenderedMarkdown, render_markdown};
+use crate::shared::components::markdown::MarkdownView;
+
+stylance::import_crate_style!(css, "src/features/terminal/preview.module.css");
+
+/// Bytes of markdown rendered before the preview stops and offers the
+/// Reader instead.
+pub(super) const PREVIEW_MAX_BYTES: usize = 16 * 1024;
+
+/// Whether `path` gets a rendered preview.
+pub(super) fn is_previewable(path: &VirtualPath) -> bool {
+    path.file_name()
+        .and_then(|name| name.rsplit_once('.'))
+        .is_some_and(|(_, ext)| ext.eq_ignore_ascii_case("md"))
+}
+
+/// The opening portion of `markdown` within `max_bytes`, and whether
+/// anything was cut. The cut falls on a paragraph break when one lies in
+/// the second half of the budget, otherwise on a line break or at the
+/// last character boundary.
+pub(super) fn preview_excerpt(markdown: &str, max_bytes: usize) -> (&str, bool) {
+    if markdown.len() <= max_bytes {
+        return (markdown, false);
+    }
+    let mut end = max_bytes;
+    while !markdown.is_char_boundary(end) {
+        end -= 1;
+    }
+    let head = &markdown[..end];
+    let cut = head
+        .rfind("\n\n")
+        .or_else(|| head.rfind('\n'))
+        .filter(|&at| at >= max_bytes / 2)
+        .unwrap_or(end);
+    (&markdown[..cut], true)
+}
+
+#[derive(Clone)]
+pub(super) struct MarkdownPreview {
+    pub(super) rendered: RenderedMarkdown,
+    pub(super) truncated: bool,
+}
+
+async fn load_preview(ctx: AppContext, path: &VirtualPath) -> Result<MarkdownPreview, String> {
+    let markdown = ctx
+        .read_text(path)
+        .await
+        .map_err(|error| error.to_string())?;
+    let (excerpt, truncated) = preview_excerpt(&markdown, PREVIEW_MAX_BYTES);
+    Ok(MarkdownPreview {
+        rendered: render_markdown(excerpt),
+        truncated,
+    })
+}
+
+/// Load the preview of `path`, like the Reader's document resource.
+pub(super) fn use_preview(
+    ctx: AppContext,
+    path: VirtualPath,
+) -> LocalResource<Result<MarkdownPreview, String>> {
+    LocalResource::new(move || {
+        let path = path.clone();
+        async move { load_preview(ctx, &path).await }
+    })
+}
+
+/// Preview sheet for `path`. `on_close` runs on Escape, the close button,
+/// a click outside, or after "Open full" navigates away.
+#[component]
+pub(super) fn PreviewSheet(path: VirtualPath, on_close: Callback<()>) -> impl IntoView {
+    let ctx = use_context::<AppContext>().expect("AppContext must be provided at root");
+    let preview = use_preview(ctx, path.clone());
+    let sheet_ref = NodeRef::<leptos::html::Aside>::new();
+
+    Effect::new(move || {
+        if let Some(sheet) = sheet_ref.get() {
+            let _ = sheet.focus();
+        }
+    });
+
+    let close = move || {
+        on_close.run(());
+        focus_terminal_input();
+    };
+    let reader_route = RouteRequest::new(request_path_for_canonical_path(
+        &path,
+        RouteSurface::Content,
+    ));
+    let open_full = StoredValue::new(reader_route);
+
+    let body = move || match preview.get() {
+        None => view! { <p class=css::status>"Loading…"</p> }.into_any(),
+        Some(Err(error)) => view! {
+            <p class=format!("{} {}", css::status, css::error)>
+                {format!("preview failed: {error}")}
+            </p>
+        }
+        .into_any(),
+        Some(Ok(loaded)) => {
+            let rendered = loaded.rendered;
+            let rendered = Signal::derive(move || rendered.clone());
+            view! {
+                <MarkdownView rendered=rendered class=reader_css::mdBody />
+                {loaded.truncated.then(|| view! {
+                    <p class=css::status>"Preview shows the beginning of this file."</p>
+                })}
+            }
+            .into_any()
+        }
+    };
+
+    view! {
+        <div class=css::backdrop on:click=move |_| close()></div>
+        <aside
+            node_ref=sheet_ref
+            class=css::sheet
+            tabindex="-1"
+            aria-label=format!("Preview of {}", path.as_str())
+            on:keydown=move |ev: ev::KeyboardEvent| {
+                if ev.key() == "Escape" {
+                    ev.prevent_default();
+                    close();
+                }
+            }
+        >
+            <header class=css::header>
+                <span class=css::title>{path.as_str().to_string()}</span>
+                <button
+                    type="button"
+                    class=css::action
+                    on:click=move |_| {
+                        on_close.run(());
+                        open_full.with_value(push_route);
+                    }
+                >
+                    "Open full"
+                </button>
+                <button type="button" class=css::action aria-label="Close preview" on:click=move |_| close()>
+                    "×"
+                </button>
+            </header>
+            <div class=css::body>{body}</div>
+        </aside>
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn only_markdown_files_are_previewable() {
+        let path = |value: &str| VirtualPath::from_absolute(value).unwrap();
+        assert!(is_previewable(&path("/blog/post.md")));
+        assert!(is_previewable(&path("/README.MD")));
+        assert!(!is_previewable(&path("/blog/page.html")));
+        assert!(!is_previewable(&path("/blog")));
+    }
+
+    #[wasm_bindgen_test]
+    fn short_files_are_previewed_whole() {
+        assert_eq!(
+            preview_excerpt("# Title\n\nBody", 64),
+            ("# Title\n\nBody", false)
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn long_files_are_cut_at_a_paragraph_break() {
+        let markdown = "first paragraph\n\nsecond paragraph\n\nthird paragraph";
+        assert_eq!(
+            preview_excerpt(markdown, 40),
+            ("first paragraph\n\nsecond paragraph", true)
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn cuts_without_a_late_break_stay_on_a_char_boundary() {
+        let markdown = "ab\n\ncdéfghijklmnop";
+        let (excerpt, truncated) = preview_excerpt(markdown, 7);
+        assert!(truncated);
+        assert_eq!(excerpt, "ab\n\ncd");
+    }
+}
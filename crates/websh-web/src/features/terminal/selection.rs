@@ -0,0 +1,229 @@
+//! Multi-selection of entries listed in terminal output.
+//!
+//! Ctrl/Cmd-click toggles an entry, Shift-click selects the run between the
+//! last toggled entry and the clicked one in listing order. Selected paths
+//! keep the order they were added in.
+
+use leptos::prelude::*;
+use websh_core::domain::VirtualPath;
+use websh_core::shell::{OutputLine, OutputLineData};
+
+use crate::app::TerminalState;
+
+/// Entries opened at once by "open all" before asking for confirmation.
+pub(super) const OPEN_ALL_CONFIRM_THRESHOLD: usize = 5;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(super) struct EntrySelection {
+    paths: Vec<VirtualPath>,
+    /// Where a Shift-click range starts.
+    anchor: Option<VirtualPath>,
+}
+
+impl EntrySelection {
+    pub(super) fn paths(&self) -> &[VirtualPath] {
+        &self.paths
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    pub(super) fn contains(&self, path: &VirtualPath) -> bool {
+        self.paths.contains(path)
+    }
+
+    /// Add `path` if absent, otherwise remove it. Either way it becomes the
+    /// anchor for the next range.
+    pub(super) fn toggle(&mut self, path: &VirtualPath) {
+        if let Some(index) = self.paths.iter().position(|selected| selected == path) {
+            self.paths.remove(index);
+        } else {
+            self.paths.push(path.clone());
+        }
+        self.anchor = Some(path.clone());
+    }
+
+    /// Replace the selection with the entries of `order` between the
+    /// anchor and `path`, inclusive. Without an anchor listed in `order`
+    /// this starts a new selection at `path`.
+    pub(super) fn select_range(&mut self, order: &[VirtualPath], path: &VirtualPath) {
+        let position = |target: &VirtualPath| order.iter().rposition(|listed| listed == target);
+        let (Some(anchor), Some(end)) = (self.anchor.as_ref().and_then(position), position(path))
+        else {
+            self.paths = vec![path.clone()];
+            self.anchor = Some(path.clone());
+            return;
+        };
+
+        let range = if anchor <= end {
+            &order[anchor..=end]
+        } else {
+            &order[end..=anchor]
+        };
+        self.paths.clear();
+        for listed in range {
+            if !self.paths.contains(listed) {
+                self.paths.push(listed.clone());
+            }
+        }
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.paths.clear();
+        self.anchor = None;
+    }
+}
+
+/// The terminal's entry selection, shared by every rendered listing.
+#[derive(Clone, Copy)]
+pub struct SelectionHandle {
+    pub(super) selection: RwSignal<EntrySelection>,
+    terminal: TerminalState,
+}
+
+impl SelectionHandle {
+    pub(super) fn new(terminal: TerminalState) -> Self {
+        Self {
+            selection: RwSignal::new(EntrySelection::default()),
+            terminal,
+        }
+    }
+
+    pub(super) fn is_selected(&self, path: &VirtualPath) -> bool {
+        self.selection.with(|selection| selection.contains(path))
+    }
+
+    /// Apply a modifier-click on `path`. Returns `false` for a plain click,
+    /// which opens the entry instead.
+    pub(super) fn click(&self, path: &VirtualPath, ev: &web_sys::MouseEvent) -> bool {
+        if ev.shift_key() {
+            let order = listed_paths(&self.terminal.rendered_lines());
+            self.selection
+                .update(|selection| selection.select_range(&order, path));
+        } else if ev.ctrl_key() || ev.meta_key() {
+            self.selection.update(|selection| selection.toggle(path));
+        } else {
+            return false;
+        }
+        true
+    }
+
+    pub(super) fn clear(&self) {
+        if self
+            .selection
+            .with_untracked(|selection| !selection.is_empty())
+        {
+            self.selection.update(EntrySelection::clear);
+        }
+    }
+}
+
+/// Paths of every listed entry in `lines`, in display order.
+pub(super) fn listed_paths<'a>(
+    lines: impl IntoIterator<Item = &'a OutputLine>,
+) -> Vec<VirtualPath> {
+    let mut paths = Vec::new();
+    for line in lines {
+        match &line.data {
+            OutputLineData::ListEntry {
+                path: Some(path), ..
+            } => paths.push(path.clone()),
+            OutputLineData::ListRow(cells) => {
+                paths.extend(cells.iter().filter_map(|cell| cell.path.clone()));
+            }
+            _ => {}
+        }
+    }
+    paths
+}
+
+/// Selected paths as clipboard text, one per line.
+pub(super) fn paths_text(paths: &[VirtualPath]) -> String {
+    paths
+        .iter()
+        .map(VirtualPath::as_str)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn vp(path: &str) -> VirtualPath {
+        VirtualPath::from_absolute(path).unwrap()
+    }
+
+    fn listing() -> Vec<VirtualPath> {
+        ["/a.md", "/b.md", "/c.md", "/d.md"]
+            .into_iter()
+            .map(vp)
+            .collect()
+    }
+
+    #[wasm_bindgen_test]
+    fn toggle_adds_and_removes_in_click_order() {
+        let mut selection = EntrySelection::default();
+        selection.toggle(&vp("/c.md"));
+        selection.toggle(&vp("/a.md"));
+        assert_eq!(selection.paths(), &[vp("/c.md"), vp("/a.md")]);
+        selection.toggle(&vp("/c.md"));
+        assert_eq!(selection.paths(), &[vp("/a.md")]);
+        assert!(!selection.contains(&vp("/c.md")));
+    }
+
+    #[wasm_bindgen_test]
+    fn range_spans_anchor_to_click_in_either_direction() {
+        let order = listing();
+        let mut selection = EntrySelection::default();
+        selection.toggle(&vp("/b.md"));
+        selection.select_range(&order, &vp("/d.md"));
+        assert_eq!(selection.paths(), &[vp("/b.md"), vp("/c.md"), vp("/d.md")]);
+
+        // The anchor stays put, so a second range replaces the first.
+        selection.select_range(&order, &vp("/a.md"));
+        assert_eq!(selection.paths(), &[vp("/a.md"), vp("/b.md")]);
+    }
+
+    #[wasm_bindgen_test]
+    fn range_without_anchor_starts_a_selection() {
+        let mut selection = EntrySelection::default();
+        selection.select_range(&listing(), &vp("/c.md"));
+        assert_eq!(selection.paths(), &[vp("/c.md")]);
+        selection.select_range(&listing(), &vp("/d.md"));
+        assert_eq!(selection.paths(), &[vp("/c.md"), vp("/d.md")]);
+    }
+
+    #[wasm_bindgen_test]
+    fn range_uses_the_latest_listing_of_a_path() {
+        let mut order = listing();
+        order.extend(listing());
+        let mut selection = EntrySelection::default();
+        selection.toggle(&vp("/d.md"));
+        selection.select_range(&order, &vp("/c.md"));
+        assert_eq!(selection.paths(), &[vp("/c.md"), vp("/d.md")]);
+    }
+
+    #[wasm_bindgen_test]
+    fn clear_drops_paths_and_anchor() {
+        let mut selection = EntrySelection::default();
+        selection.toggle(&vp("/a.md"));
+        selection.clear();
+        assert!(selection.is_empty());
+        selection.select_range(&listing(), &vp("/c.md"));
+        assert_eq!(selection.len(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn paths_text_is_one_path_per_line() {
+        assert_eq!(paths_text(&[vp("/a.md"), vp("/b/c.md")]), "/a.md\n/b/c.md");
+    }
+}
@@ -0,0 +1,203 @@
+//! `session` wiring: captures and applies the restorable state, keeps the
+//! named snapshots in localStorage, and autosaves the last session whenever
+//! the page is hidden.
+
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::closure::Closure;
+
+use crate::app::{AppContext, RuntimeServices, route_resolves};
+use crate::config::SESSION_SNAPSHOTS_KEY;
+use crate::platform::dom::{current_route_request, push_route};
+use crate::render::theme;
+use websh_core::filesystem::RouteRequest;
+use websh_core::shell::{
+    OutputLine, SessionAction, SessionChange, SessionSnapshot, SessionStore, plan_restore,
+    restore_lines,
+};
+
+pub(super) fn apply(ctx: AppContext, action: SessionAction) {
+    let mut store = load_store();
+    let lines = match action {
+        SessionAction::List => store.list_lines(),
+        SessionAction::Save(name) => match store.save(&name, capture(ctx)) {
+            Ok(replaced) => match save_store(&store) {
+                Ok(()) if replaced => vec![OutputLine::success(format!(
+                    "session: saved {name} (replaced the earlier one)"
+                ))],
+                Ok(()) => vec![OutputLine::success(format!("session: saved {name}"))],
+                Err(error) => vec![OutputLine::error(format!("session: {error}"))],
+            },
+            Err(error) => vec![OutputLine::error(format!("session: {error}"))],
+        },
+        SessionAction::Load(name) => match store.get(&name) {
+            Some(snapshot) => restore(ctx, &name, snapshot),
+            None => vec![OutputLine::error(format!(
+                "session: no snapshot named {name}; see 'session list'"
+            ))],
+        },
+        SessionAction::Remove(name) => {
+            if !store.remove(&name) {
+                vec![OutputLine::error(format!(
+                    "session: no snapshot named {name}"
+                ))]
+            } else {
+                match save_store(&store) {
+                    Ok(()) => vec![OutputLine::success(format!("session: removed {name}"))],
+                    Err(error) => vec![OutputLine::error(format!("session: {error}"))],
+                }
+            }
+        }
+        SessionAction::RestoreLast => match store.last() {
+            Some(snapshot) => restore(ctx, "last session", snapshot),
+            None => vec![OutputLine::error(
+                "session: nothing autosaved yet; the last session is saved when the page is hidden",
+            )],
+        },
+    };
+    ctx.terminal.push_lines(lines);
+}
+
+/// Save the last session every time the page is hidden: on a tab switch,
+/// a reload, or closing the tab. The listener lives as long as the page.
+pub(super) fn start_autosave(ctx: AppContext) {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    let hidden_document = document.clone();
+    let on_change = Closure::<dyn Fn()>::new(move || {
+        if hidden_document.hidden() {
+            let mut store = load_store();
+            store.set_last(capture(ctx));
+            if let Err(error) = save_store(&store) {
+                leptos::logging::warn!("session autosave: {error}");
+            }
+        }
+    });
+    let _ = document
+        .add_event_listener_with_callback("visibilitychange", on_change.as_ref().unchecked_ref());
+    on_change.forget();
+}
+
+/// The restorable state right now.
+fn capture(ctx: AppContext) -> SessionSnapshot {
+    let route = current_route_request().url_path;
+    let theme = ctx.theme.get_untracked();
+    ctx.runtime_state
+        .with_untracked(|rs| SessionSnapshot::capture(&route, theme, &rs.env))
+}
+
+/// Move from the current state to `target` and describe what changed.
+/// Parts that no longer apply are reported and skipped.
+fn restore(ctx: AppContext, name: &str, target: &SessionSnapshot) -> Vec<OutputLine> {
+    let plan = plan_restore(
+        &capture(ctx),
+        target,
+        |route| route_resolves(ctx, &RouteRequest::new(route)),
+        |id| theme::normalize_theme_id(id).is_some(),
+    );
+    let mut lines = restore_lines(name, &plan);
+    let services = RuntimeServices::new(ctx);
+    let mut route = None;
+    for change in plan.changes {
+        let applied = match change {
+            SessionChange::Route { to, .. } => {
+                route = Some(to);
+                Ok(())
+            }
+            SessionChange::Theme { to, .. } => services.set_theme(&to).map(|_| ()),
+            SessionChange::SetVar { key, to, .. } => services
+                .set_env_var(&key, &to)
+                .map_err(|error| format!("failed to set {key}: {error}")),
+            SessionChange::UnsetVar { key } => services
+                .unset_env_var(&key)
+                .map_err(|error| format!("failed to unset {key}: {error}")),
+        };
+        if let Err(error) = applied {
+            lines.push(OutputLine::error(format!("session: {error}")));
+        }
+    }
+    // Navigate last, once the theme and variables are in place.
+    if let Some(route) = route {
+        push_route(&RouteRequest::new(route));
+    }
+    lines
+}
+
+fn load_store() -> SessionStore {
+    local_storage()
+        .and_then(|storage| storage.get_item(SESSION_SNAPSHOTS_KEY).ok().flatten())
+        .map(|json| SessionStore::from_json(&json))
+        .unwrap_or_default()
+}
+
+fn save_store(store: &SessionStore) -> Result<(), String> {
+    local_storage()
+        .ok_or_else(|| "localStorage is not available".to_string())?
+        .set_item(SESSION_SNAPSHOTS_KEY, &store.to_json())
+        .map_err(|error| format!("failed to save snapshots: {error:?}"))
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn clear_store() {
+        if let Some(storage) = local_storage() {
+            let _ = storage.remove_item(SESSION_SNAPSHOTS_KEY);
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn store_round_trips_through_local_storage() {
+        clear_store();
+        assert_eq!(load_store(), SessionStore::default());
+
+        let mut store = SessionStore::default();
+        store
+            .save(
+                "work",
+                SessionSnapshot {
+                    route: "/websh".to_string(),
+                    ..SessionSnapshot::default()
+                },
+            )
+            .unwrap();
+        save_store(&store).unwrap();
+        assert_eq!(load_store(), store);
+        clear_store();
+    }
+
+    #[wasm_bindgen_test]
+    fn save_then_load_restores_variables() {
+        let owner = Owner::new();
+        owner.with(|| {
+            clear_store();
+            let ctx = AppContext::new();
+            let services = RuntimeServices::new(ctx);
+            services.set_env_var("SESSION_TEST", "before").unwrap();
+            apply(ctx, SessionAction::Save("test".to_string()));
+
+            services.set_env_var("SESSION_TEST", "after").unwrap();
+            services.set_env_var("SESSION_EXTRA", "1").unwrap();
+            apply(ctx, SessionAction::Load("test".to_string()));
+            ctx.runtime_state.with_untracked(|rs| {
+                assert_eq!(
+                    rs.env.get("SESSION_TEST").map(String::as_str),
+                    Some("before")
+                );
+                assert!(!rs.env.contains_key("SESSION_EXTRA"));
+            });
+
+            let _ = services.unset_env_var("SESSION_TEST");
+            clear_store();
+        });
+    }
+}
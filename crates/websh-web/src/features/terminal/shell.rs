@@ -10,8 +10,9 @@ use super::terminal::Terminal;
 use crate::app::AppContext;
 use crate::features::chrome::SiteChrome;
 use crate::platform::dom::replace_route;
+use websh_core::domain::VirtualPath;
 use websh_core::filesystem::{
-    RouteFrame, RouteSurface, request_path_for_canonical_path, route_cwd,
+    RouteFrame, RouteSurface, display_path_for, request_path_for_canonical_path, route_cwd,
 };
 use websh_core::shell::OutputLine;
 
@@ -37,13 +38,24 @@ fn setup_autoscroll_effect(
     });
 }
 
+/// Line announcing the terminal's directory when it reopens somewhere other
+/// than where it was left. `cd` inside the terminal never announces.
+fn reopen_notice(
+    left_terminal: bool,
+    last_cwd: &VirtualPath,
+    cwd: &VirtualPath,
+) -> Option<OutputLine> {
+    (left_terminal && last_cwd != cwd)
+        .then(|| OutputLine::info(format!("cwd: {}", display_path_for(cwd))))
+}
+
 /// Shell component for the terminal view.
 ///
 /// This is a container component that:
 /// - Receives the current route from the Router
 /// - Provides route context to child components
 /// - Handles boot sequence initialization
-/// - Provides terminal surface effects
+/// - Provides terminal surface effects, including the optional CRT overlay
 ///
 /// # Props
 /// - `route`: The current route frame (derived from URL + engine resolution)
@@ -56,7 +68,18 @@ pub fn Shell(route: Memo<RouteFrame>) -> impl IntoView {
 
     Effect::new(move |_| {
         let frame = route.get();
-        ctx.cwd.set(route_cwd(&frame));
+        let cwd = route_cwd(&frame);
+        let left_terminal = ctx.take_left_terminal();
+        // Before boot there is no session to tell.
+        if !ctx
+            .terminal
+            .history
+            .with_untracked(|history| history.is_empty())
+            && let Some(line) = reopen_notice(left_terminal, &ctx.cwd.get_untracked(), &cwd)
+        {
+            ctx.terminal.push_output(line);
+        }
+        ctx.cwd.set(cwd);
         match frame.surface() {
             RouteSurface::Shell => {
                 let canonical =
@@ -79,7 +102,45 @@ pub fn Shell(route: Memo<RouteFrame>) -> impl IntoView {
 
             <div class=css::main>
                 <Terminal output_ref=output_ref />
+                <Show when=move || ctx.display.with(|display| display.crt)>
+                    <div class=css::crt aria-hidden="true">
+                        <Show when=move || ctx.display.with(|display| display.show_scanlines())>
+                            <div class=css::scanlines></div>
+                        </Show>
+                        <Show when=move || ctx.display.with(|display| display.show_sweep())>
+                            <div class=css::sweep></div>
+                        </Show>
+                    </div>
+                </Show>
             </div>
         </div>
     }
 }
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+    use websh_core::shell::OutputLineData;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn vp(path: &str) -> VirtualPath {
+        VirtualPath::from_absolute(path).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn reopen_notice_announces_a_new_directory_after_a_content_page() {
+        let line = reopen_notice(true, &VirtualPath::root(), &vp("/blog")).unwrap();
+        assert!(matches!(line.data, OutputLineData::Info(ref text) if text == "cwd: /blog"));
+
+        let line = reopen_notice(true, &vp("/blog"), &VirtualPath::root()).unwrap();
+        assert!(matches!(line.data, OutputLineData::Info(ref text) if text == "cwd: ~"));
+    }
+
+    #[wasm_bindgen_test]
+    fn reopen_notice_stays_quiet_within_the_terminal_or_in_place() {
+        assert!(reopen_notice(false, &VirtualPath::root(), &vp("/blog")).is_none());
+        assert!(reopen_notice(true, &vp("/blog"), &vp("/blog")).is_none());
+    }
+}
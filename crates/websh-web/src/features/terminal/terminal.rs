@@ -4,15 +4,19 @@
 
 use leptos::prelude::*;
 
-use crate::app::AppContext;
+use crate::app::{AppContext, OutputSearch};
 use crate::features::terminal::{Input, Output, RouteContext};
-use crate::platform::dom::focus_terminal_input;
-use websh_core::filesystem::route_cwd;
+use crate::platform::dom::{copy_to_clipboard, focus_terminal_input, open_in_new_tab, window};
+use websh_core::filesystem::{content_href_for_path, route_cwd};
+use websh_core::shell::{OutputLine, OutputLineId, selection_zip_export};
 
 use super::actions::{
-    create_autocomplete_callback, create_hint_callback, create_history_nav_callback,
-    create_submit_callback,
+    create_autocomplete_callback, create_clear_screen_callback, create_hint_callback,
+    create_history_nav_callback, create_history_search_callback, create_interrupt_callback,
+    create_output_search_step_callback, create_submit_callback, handle_export_zip,
 };
+use super::entry_menu::{EntryMenu, EntryMenuHandle};
+use super::selection::{EntrySelection, OPEN_ALL_CONFIRM_THRESHOLD, SelectionHandle, paths_text};
 
 stylance::import_crate_style!(css, "src/features/terminal/terminal.module.css");
 
@@ -30,14 +34,100 @@ pub fn Terminal(output_ref: NodeRef<leptos::html::Div>) -> impl IntoView {
     // Callbacks need route access
     let on_submit = create_submit_callback(ctx, route_ctx);
     let on_history_nav = create_history_nav_callback(ctx);
+    let on_history_search = create_history_search_callback(ctx);
     let on_autocomplete = create_autocomplete_callback(ctx, route_ctx);
     let on_get_hint = create_hint_callback(ctx, route_ctx);
+    let on_interrupt = create_interrupt_callback(ctx, route_ctx);
+    let on_clear_screen = create_clear_screen_callback(ctx);
+    let on_output_search_step = create_output_search_step_callback(ctx);
 
+    // A `q=` deep link on the shell route pre-fills a search to run.
+    let initial_input = route_ctx
+        .0
+        .with_untracked(|route| ctx.take_deep_link(&route.request.url_path))
+        .and_then(|link| link.search)
+        .map(|query| search_command(&query))
+        .unwrap_or_default();
+
+    let selection = SelectionHandle::new(ctx.terminal);
+    let menu = EntryMenuHandle::new();
+    let has_selection = move || selection.selection.with(|selection| !selection.is_empty());
     let handle_click = move |_| focus_terminal_input();
+    let handle_keydown = move |ev: leptos::ev::KeyboardEvent| {
+        // Any key skips the rest of a typed boot sequence.
+        super::boot::fast_forward();
+        if ev.key() == "Escape" {
+            selection.clear();
+            ctx.terminal.end_output_search();
+        }
+    };
     let history_signal = ctx.terminal.history;
+    let display_offset = ctx.terminal.display_offset;
+    let output_search = ctx.terminal.output_search;
+    let searching = move || output_search.with(Option::is_some);
+
+    // Bring the current scrollback match into view.
+    Effect::new(move || {
+        let Some(id) = output_search.with(|search| search.as_ref()?.current_line()) else {
+            return;
+        };
+        if let Some(output) = output_ref.get()
+            && let Ok(Some(line)) = output.query_selector(&line_selector(id))
+        {
+            let options = web_sys::ScrollIntoViewOptions::new();
+            options.set_block(web_sys::ScrollLogicalPosition::Center);
+            line.scroll_into_view_with_scroll_into_view_options(&options);
+        }
+    });
+
+    // Each line is wrapped so a scrollback search can mark it.
+    let line_view = move |line: OutputLine| {
+        let id = line.id;
+        let class = move || output_search.with(|search| match_class(search.as_ref(), id));
+        view! {
+            <div class=class data-line-id=id.0.to_string()>
+                <Output line=line on_run=on_submit selection=selection menu=menu />
+            </div>
+        }
+    };
+
+    // Lines evicted from the buffer are archived; a marker loads them back
+    // in chunks. This leaves `history` untouched, so auto-scroll stays put.
+    let earlier_marker = move || {
+        let earlier = ctx.terminal.earlier_line_count();
+        (earlier > 0).then(|| {
+            let noun = if earlier == 1 { "line" } else { "lines" };
+            view! {
+                <button
+                    type="button"
+                    class=css::earlierMarker
+                    on:click=move |_| ctx.terminal.load_earlier()
+                >
+                    {format!("… {earlier} earlier {noun} — click to load")}
+                </button>
+            }
+        })
+    };
+
+    // Lines hidden by `clear` stay in the buffer; a marker reveals them.
+    let cleared_marker = move || {
+        let hidden = ctx.terminal.hidden_line_count();
+        (hidden > 0).then(|| {
+            let noun = if hidden == 1 { "line" } else { "lines" };
+            view! {
+                <button
+                    type="button"
+                    class=css::clearedMarker
+                    on:click=move |_| ctx.terminal.show_cleared()
+                >
+                    {format!("{hidden} earlier {noun} cleared — show")}
+                </button>
+            }
+        })
+    };
 
     view! {
-        <div class=css::container on:click=handle_click>
+        <div class=css::container on:click=handle_click on:keydown=handle_keydown>
             <div
                 node_ref=output_ref
                 class=css::output
@@ -45,23 +135,183 @@ pub fn Terminal(output_ref: NodeRef<leptos::html::Div>) -> impl IntoView {
                 aria-live="polite"
                 aria-relevant="additions text"
                 aria-label="Terminal output"
+                on:scroll=move |_| menu.close()
             >
+                {earlier_marker}
+                <For
+                    each=move || ctx.terminal.earlier_lines()
+                    key=|line| line.id
+                    children=line_view
+                />
+                {cleared_marker}
                 <For
-                    each=move || history_signal.with(|buf| buf.iter().cloned().collect::<Vec<_>>())
+                    each=move || {
+                        let offset = display_offset.get();
+                        history_signal.with(|buf| buf.iter_from(offset).cloned().collect::<Vec<_>>())
+                    }
                     key=|line| line.id
-                    children=|line| view! { <Output line=line /> }
+                    children=line_view
                 />
             </div>
 
+            <EntryMenu handle=menu on_run=on_submit />
+
+            <Show when=has_selection>
+                <SelectionBar handle=selection />
+            </Show>
+
+            <Show when=searching>
+                <OutputSearchBar />
+            </Show>
+
             <div class=css::inputArea>
                 <Input
                     prompt=prompt
                     on_submit=on_submit
                     on_history_nav=on_history_nav
+                    on_history_search=on_history_search
                     on_autocomplete=on_autocomplete
                     on_get_hint=on_get_hint
+                    on_interrupt=on_interrupt
+                    on_clear_screen=on_clear_screen
+                    on_output_search_step=on_output_search_step
+                    initial_value=initial_input
                 />
             </div>
         </div>
     }
 }
+
+/// `search` with `query` single-quoted, so the shell reads it back verbatim.
+fn search_command(query: &str) -> String {
+    format!("search '{}'", query.replace('\'', "'\"'\"'"))
+}
+
+/// Selector for the wrapper of output line `id`.
+fn line_selector(id: OutputLineId) -> String {
+    format!("[data-line-id=\"{}\"]", id.0)
+}
+
+/// Class marking line `id` under a scrollback search.
+fn match_class(search: Option<&OutputSearch>, id: OutputLineId) -> &'static str {
+    match search {
+        Some(search) if search.current_line() == Some(id) => css::searchCurrent,
+        Some(search) if search.is_match(id) => css::searchMatch,
+        _ => "",
+    }
+}
+
+/// Status of a `/pattern` scrollback search, with n / N / Esc as buttons.
+#[component]
+fn OutputSearchBar() -> impl IntoView {
+    let ctx = use_context::<AppContext>().expect("AppContext must be provided at root");
+    let terminal = ctx.terminal;
+    let status = move || {
+        terminal.output_search.with(|search| {
+            let Some(search) = search else {
+                return String::new();
+            };
+            match search.matches.len() {
+                0 => format!("/{}: no matches", search.query),
+                total => format!("/{}: {} of {total}", search.query, search.current + 1),
+            }
+        })
+    };
+
+    view! {
+        <div class=css::searchBar role="status" aria-label="Scrollback search">
+            <span class=css::selectionCount>{status}</span>
+            <button type="button" title="n" on:click=move |_| {
+                terminal.step_output_search(true);
+            }>"older"</button>
+            <button type="button" title="N" on:click=move |_| {
+                terminal.step_output_search(false);
+            }>"newer"</button>
+            <button type="button" title="Esc" on:click=move |_| terminal.end_output_search()>
+                "close"
+            </button>
+        </div>
+    }
+}
+
+/// Bar of bulk actions shown above the input while entries are selected.
+#[component]
+fn SelectionBar(handle: SelectionHandle) -> impl IntoView {
+    let ctx = use_context::<AppContext>().expect("AppContext must be provided at root");
+    let terminal = ctx.terminal;
+    let count = move || handle.selection.with(EntrySelection::len);
+    let selected = move || {
+        handle
+            .selection
+            .with(|selection| selection.paths().to_vec())
+    };
+
+    let copy_paths = move |_| {
+        let paths = selected();
+        let text = paths_text(&paths);
+        wasm_bindgen_futures::spawn_local(async move {
+            match copy_to_clipboard(&text).await {
+                Ok(()) => terminal.push_output(OutputLine::success(format!(
+                    "selection: {} paths copied to clipboard",
+                    paths.len()
+                ))),
+                Err(error) => {
+                    terminal.push_output(OutputLine::error(format!("selection: {error}")))
+                }
+            }
+        });
+    };
+
+    let open_all = move |_| {
+        let paths = selected();
+        if paths.len() > OPEN_ALL_CONFIRM_THRESHOLD {
+            let message = format!("Open {} entries in new tabs?", paths.len());
+            let confirmed = window()
+                .and_then(|window| window.confirm_with_message(&message).ok())
+                .unwrap_or(false);
+            if !confirmed {
+                return;
+            }
+        }
+        for path in &paths {
+            if let Err(error) = open_in_new_tab(&content_href_for_path(path.as_str())) {
+                terminal.push_output(OutputLine::error(format!("selection: {error}")));
+                break;
+            }
+        }
+    };
+
+    // Selected directories bring their files; the archive is packed the
+    // way `export-zip` packs a folder.
+    let download = move |_| {
+        let paths = selected();
+        let wallet = ctx.wallet.get_untracked();
+        let export = ctx
+            .system_global_fs
+            .with_untracked(|fs| selection_zip_export(&paths, &wallet, fs));
+        match export {
+            Some(export) => handle_export_zip(ctx, export),
+            None => terminal.push_output(OutputLine::error(
+                "selection: nothing selected can be downloaded",
+            )),
+        }
+    };
+
+    view! {
+        <div class=css::selectionBar role="toolbar" aria-label="Selected entries">
+            <span class=css::selectionCount>
+                {move || {
+                    let count = count();
+                    let noun = if count == 1 { "entry" } else { "entries" };
+                    format!("{count} {noun} selected")
+                }}
+            </span>
+            <button type="button" on:click=copy_paths>"copy paths"</button>
+            <button type="button" on:click=open_all>"open all"</button>
+            <button type="button" on:click=download>"download"</button>
+            <button type="button" title="Esc" on:click=move |_| handle.clear()>
+                "clear"
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,171 @@
+//! Session transcript rendering for `export-session`.
+//!
+//! Transcripts start with a short header (export time, mount, cwd) followed
+//! by the terminal output, either as plain text or as a self-contained HTML
+//! page with inline styles.
+
+use websh_core::domain::{RuntimeMount, VirtualPath};
+use websh_core::shell::{OutputLine, OutputLineData, TextStyle, lines_to_plain_text};
+
+const HTML_STYLE: &str = "body{margin:0;background:#0d1117;color:#c9d1d9}\
+pre{margin:0;padding:1.5rem;font:14px/1.5 ui-monospace,SFMono-Regular,Menlo,monospace;white-space:pre-wrap}\
+.meta{color:#8b949e}.prompt{color:#3fb950}.error{color:#f85149}.success{color:#3fb950}\
+.info{color:#d29922}.ascii{color:#58a6ff}.dir{color:#58a6ff;font-weight:bold}";
+
+/// Context printed at the top of an exported transcript.
+pub(super) struct TranscriptHeader<'a> {
+    pub exported_at: &'a str,
+    pub cwd: &'a VirtualPath,
+    pub mount: Option<&'a RuntimeMount>,
+}
+
+impl TranscriptHeader<'_> {
+    fn lines(&self) -> [String; 3] {
+        let mount = self.mount.map_or_else(
+            || "none".to_string(),
+            |mount| format!("{} ({})", mount.label, mount.root.as_str()),
+        );
+        [
+            format!("# exported: {}", self.exported_at),
+            format!("# mount: {mount}"),
+            format!("# cwd: {}", self.cwd.as_str()),
+        ]
+    }
+}
+
+/// Render a plain-text transcript.
+pub(super) fn render_text<'a>(
+    header: &TranscriptHeader<'_>,
+    lines: impl IntoIterator<Item = &'a OutputLine>,
+) -> String {
+    let mut out = String::from("# websh session transcript\n");
+    for line in header.lines() {
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out.push('\n');
+    out.push_str(&lines_to_plain_text(lines));
+    out
+}
+
+/// Render a standalone HTML transcript, colored by output line kind.
+pub(super) fn render_html<'a>(
+    header: &TranscriptHeader<'_>,
+    lines: impl IntoIterator<Item = &'a OutputLine>,
+) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>websh session transcript</title>\n<style>{HTML_STYLE}</style>\n\
+         </head>\n<body>\n<pre>"
+    );
+    for line in header.lines() {
+        push_span(&mut out, "meta", &line);
+        out.push('\n');
+    }
+    out.push('\n');
+    for line in lines {
+        match &line.data {
+            OutputLineData::Command { prompt, input } => {
+                push_span(&mut out, "prompt", &format!("{prompt}{}", prompt.separator));
+                out.push(' ');
+                out.push_str(&escape_html(input));
+            }
+            OutputLineData::Error(_) => push_span(&mut out, "error", &line.to_plain_text()),
+            OutputLineData::Success(_) => push_span(&mut out, "success", &line.to_plain_text()),
+            OutputLineData::Info(_) => push_span(&mut out, "info", &line.to_plain_text()),
+            OutputLineData::Ascii(_) => push_span(&mut out, "ascii", &line.to_plain_text()),
+            OutputLineData::ListEntry { style, .. } if *style == TextStyle::Directory => {
+                push_span(&mut out, "dir", &line.to_plain_text())
+            }
+            OutputLineData::Text(_)
+            | OutputLineData::ListEntry { .. }
+            | OutputLineData::ListRow(_) => out.push_str(&escape_html(&line.to_plain_text())),
+            OutputLineData::Empty => {}
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+fn push_span(out: &mut String, class: &str, text: &str) {
+    out.push_str("<span class=\"");
+    out.push_str(class);
+    out.push_str("\">");
+    out.push_str(&escape_html(text));
+    out.push_str("</span>");
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+    use websh_core::domain::RuntimeBackendKind;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn header<'a>(cwd: &'a VirtualPath, mount: Option<&'a RuntimeMount>) -> TranscriptHeader<'a> {
+        TranscriptHeader {
+            exported_at: "2026-01-02T03:04:05.000Z",
+            cwd,
+            mount,
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn text_transcript_has_header_then_output() {
+        let cwd = VirtualPath::root();
+        let mount = RuntimeMount::new(VirtualPath::root(), "~", RuntimeBackendKind::GitHub, true);
+        let lines = vec![
+            OutputLine::command("guest@websh:~", "ls"),
+            OutputLine::text("notes.md"),
+        ];
+        let text = render_text(&header(&cwd, Some(&mount)), &lines);
+        assert_eq!(
+            text,
+            "# websh session transcript\n\
+             # exported: 2026-01-02T03:04:05.000Z\n\
+             # mount: ~ (/)\n\
+             # cwd: /\n\
+             \n\
+             guest@websh:~$ ls\n\
+             notes.md\n"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn text_transcript_reports_missing_mount() {
+        let cwd = VirtualPath::root();
+        let text = render_text(&header(&cwd, None), &[]);
+        assert!(text.contains("# mount: none\n"));
+    }
+
+    #[wasm_bindgen_test]
+    fn html_transcript_escapes_and_classifies_lines() {
+        let cwd = VirtualPath::root();
+        let lines = vec![
+            OutputLine::command("guest@websh:~", "echo <b>"),
+            OutputLine::error("cat: a & b: No such file"),
+        ];
+        let html = render_html(&header(&cwd, None), &lines);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<span class=\"prompt\">guest@websh:~$</span> echo &lt;b&gt;"));
+        assert!(html.contains("<span class=\"error\">cat: a &amp; b: No such file</span>"));
+        assert!(!html.contains("<b>"));
+    }
+}
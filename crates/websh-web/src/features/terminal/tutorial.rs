@@ -0,0 +1,116 @@
+//! Guided tour wiring: runs `tutorial` actions, watches submitted commands,
+//! and keeps the completed step count in localStorage.
+
+use leptos::prelude::*;
+
+use crate::app::AppContext;
+use crate::config::TUTORIAL_PROGRESS_KEY;
+use websh_core::shell::{TutorialAction, TutorialProgress};
+
+/// Restore saved progress into the terminal state. Returns `false` for a
+/// first-time visitor, who has never started the tour.
+pub(super) fn restore(ctx: AppContext) -> bool {
+    let Some(step) = saved_step() else {
+        return false;
+    };
+    ctx.terminal
+        .tutorial
+        .set(TutorialProgress::resumed_at(step));
+    true
+}
+
+pub(super) fn apply(ctx: AppContext, action: TutorialAction) {
+    let lines = ctx
+        .terminal
+        .tutorial
+        .try_update(|progress| progress.apply(action))
+        .unwrap_or_default();
+    save(ctx);
+    ctx.terminal.push_lines(lines);
+}
+
+/// Feed a submitted command line to a running tour.
+pub(super) fn observe(ctx: AppContext, input: &str, exit_code: i32) {
+    if !ctx
+        .terminal
+        .tutorial
+        .with_untracked(TutorialProgress::is_active)
+    {
+        return;
+    }
+    let lines = ctx
+        .terminal
+        .tutorial
+        .try_update(|progress| progress.observe(input, exit_code))
+        .unwrap_or_default();
+    if !lines.is_empty() {
+        save(ctx);
+        ctx.terminal.push_lines(lines);
+    }
+}
+
+fn saved_step() -> Option<usize> {
+    local_storage()?
+        .get_item(TUTORIAL_PROGRESS_KEY)
+        .ok()
+        .flatten()
+        .map(|value| parse_step(&value))
+}
+
+fn save(ctx: AppContext) {
+    let step = ctx.terminal.tutorial.with_untracked(TutorialProgress::step);
+    if let Some(storage) = local_storage() {
+        let _ = storage.set_item(TUTORIAL_PROGRESS_KEY, &step.to_string());
+    }
+}
+
+/// Unreadable values count as a tour that was started but not advanced.
+fn parse_step(value: &str) -> usize {
+    value.trim().parse().unwrap_or(0)
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use leptos::prelude::Owner;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn parse_step_tolerates_garbage() {
+        assert_eq!(parse_step("3"), 3);
+        assert_eq!(parse_step(" 2\n"), 2);
+        assert_eq!(parse_step("done"), 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn progress_round_trips_through_storage() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let storage = local_storage().expect("localStorage should be available");
+            let _ = storage.remove_item(TUTORIAL_PROGRESS_KEY);
+
+            let ctx = AppContext::new();
+            assert!(!restore(ctx));
+
+            apply(ctx, TutorialAction::Start);
+            observe(ctx, "ls", 0);
+            assert_eq!(
+                storage.get_item(TUTORIAL_PROGRESS_KEY).unwrap().as_deref(),
+                Some("1")
+            );
+
+            let resumed = AppContext::new();
+            assert!(restore(resumed));
+            assert_eq!(resumed.terminal.tutorial.get_untracked().step(), 1);
+            assert!(!resumed.terminal.tutorial.get_untracked().is_active());
+
+            let _ = storage.remove_item(TUTORIAL_PROGRESS_KEY);
+        });
+    }
+}
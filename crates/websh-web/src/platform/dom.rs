@@ -1,8 +1,13 @@
 //! Browser DOM helpers owned by the web crate.
 
+use gloo_timers::callback::Timeout;
 use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
 use websh_core::filesystem::RouteRequest;
 
+use super::asset::object_url_for_bytes;
+use crate::render::document_head::DocumentHead;
+
 pub fn window() -> Option<web_sys::Window> {
     web_sys::window()
 }
@@ -25,27 +30,213 @@ pub fn focus_terminal_input() {
     focus_element("input");
 }
 
+/// Whether a key event aimed at `target` belongs to a text field (inputs,
+/// textareas, selects, or editable content), so page shortcuts stay out of
+/// its way.
+pub fn is_editable_target(target: Option<&web_sys::EventTarget>) -> bool {
+    let Some(element) = target.and_then(|target| target.dyn_ref::<web_sys::HtmlElement>()) else {
+        return false;
+    };
+    matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+        || element.is_content_editable()
+}
+
+/// Write text to the system clipboard.
+pub async fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    let Some(window) = window() else {
+        return Err("window not available".to_string());
+    };
+    let clipboard = window.navigator().clipboard();
+    JsFuture::from(clipboard.write_text(text))
+        .await
+        .map(|_| ())
+        .map_err(|error| {
+            error
+                .as_string()
+                .unwrap_or_else(|| "clipboard write failed".to_string())
+        })
+}
+
+/// Offer `text` as a file download named `filename`.
+pub fn download_text(filename: &str, text: &str) -> Result<(), String> {
+    download_file(filename, text, "text/plain;charset=utf-8")
+}
+
+/// Offer `text` as a file download named `filename` with the given media type.
+pub fn download_file(filename: &str, text: &str, media_type: &str) -> Result<(), String> {
+    download_bytes(filename, text.as_bytes(), media_type)
+}
+
+/// Offer `bytes` as a file download named `filename` with the given media type.
+pub fn download_bytes(filename: &str, bytes: &[u8], media_type: &str) -> Result<(), String> {
+    let url = object_url_for_bytes(bytes, media_type)?;
+    let document = window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| "document not available".to_string())?;
+    let anchor = document
+        .create_element("a")
+        .map_err(|error| format!("failed to create link: {error:?}"))?
+        .dyn_into::<web_sys::HtmlElement>()
+        .map_err(|_| "failed to create link".to_string())?;
+    let _ = anchor.set_attribute("href", url.as_str());
+    let _ = anchor.set_attribute("download", filename);
+    anchor.click();
+
+    // Revoking the object URL synchronously can cancel the download in some
+    // browsers; keep it alive briefly.
+    Timeout::new(1_000, move || drop(url)).forget();
+    Ok(())
+}
+
+/// Class set on `<html>` while a dialog opened by [`print_document`] is up.
+pub const PRINTING_CLASS: &str = "printing";
+
+/// Open the browser print dialog for the current page.
+///
+/// Before printing, `<html>` gets [`PRINTING_CLASS`] and every closed
+/// `<details>` is opened so collapsed content makes it onto paper. Both
+/// are undone on `afterprint`, which fires whether the dialog printed or
+/// was cancelled.
+pub fn print_document() -> Result<(), String> {
+    let window = window().ok_or_else(|| "window not available".to_string())?;
+    let document = window
+        .document()
+        .ok_or_else(|| "document not available".to_string())?;
+    let root = document
+        .document_element()
+        .ok_or_else(|| "document root not available".to_string())?;
+
+    let mut opened = Vec::new();
+    if let Ok(details) = document.query_selector_all("details:not([open])") {
+        for index in 0..details.length() {
+            if let Some(element) = details
+                .item(index)
+                .and_then(|node| node.dyn_into::<web_sys::Element>().ok())
+                && element.set_attribute("open", "").is_ok()
+            {
+                opened.push(element);
+            }
+        }
+    }
+    let _ = root.class_list().add_1(PRINTING_CLASS);
+
+    let restore = wasm_bindgen::closure::Closure::once_into_js(move || {
+        for element in opened {
+            let _ = element.remove_attribute("open");
+        }
+        let _ = root.class_list().remove_1(PRINTING_CLASS);
+    });
+    let options = web_sys::AddEventListenerOptions::new();
+    options.set_once(true);
+    window
+        .add_event_listener_with_callback_and_add_event_listener_options(
+            "afterprint",
+            restore.unchecked_ref(),
+            &options,
+        )
+        .map_err(|error| format!("failed to watch print dialog: {error:?}"))?;
+
+    window
+        .print()
+        .map_err(|error| format!("print failed: {error:?}"))
+}
+
+/// Open `url` in a new browser tab.
+pub fn open_in_new_tab(url: &str) -> Result<(), String> {
+    window()
+        .ok_or_else(|| "window not available".to_string())?
+        .open_with_url_and_target(url, "_blank")
+        .map(|_| ())
+        .map_err(|error| format!("failed to open {url}: {error:?}"))
+}
+
+/// Absolute URL of an in-app `#/…` href, resolved against the current page.
+pub fn absolute_hash_url(href: &str) -> Option<String> {
+    let current = window()?.location().href().ok()?;
+    let base = current.split('#').next().unwrap_or(&current);
+    Some(format!("{base}{href}"))
+}
+
+/// Width of the terminal output area in monospace characters.
+///
+/// Measures a hidden probe inside the output container so the result follows
+/// the terminal's actual font. `None` when the terminal is not mounted.
+pub fn terminal_columns() -> Option<usize> {
+    const PROBE_CHARS: usize = 100;
+
+    let document = window()?.document()?;
+    let output = document
+        .query_selector("[role=\"log\"][aria-label=\"Terminal output\"]")
+        .ok()
+        .flatten()?;
+    let probe = document.create_element("span").ok()?;
+    let _ = probe.set_attribute(
+        "style",
+        "position:absolute;visibility:hidden;white-space:pre;font-family:var(--font-mono)",
+    );
+    probe.set_text_content(Some(&"0".repeat(PROBE_CHARS)));
+    output.append_child(&probe).ok()?;
+    let char_width = probe.get_bounding_client_rect().width() / PROBE_CHARS as f64;
+    probe.remove();
+
+    let available = f64::from(output.client_width());
+    (char_width > 0.0 && available > 0.0).then(|| (available / char_width).floor() as usize)
+}
+
+thread_local! {
+    static DEFAULT_DESCRIPTION: std::cell::OnceCell<Option<String>> =
+        const { std::cell::OnceCell::new() };
+}
+
+/// Write the route's title and `<meta name="description">` to the document.
+/// A missing description restores whatever `index.html` shipped with.
+pub fn apply_document_head(head: &DocumentHead) {
+    let Some(document) = window().and_then(|window| window.document()) else {
+        return;
+    };
+    document.set_title(&head.title);
+
+    let Some(meta) = document
+        .query_selector("meta[name=\"description\"]")
+        .ok()
+        .flatten()
+    else {
+        return;
+    };
+    let default =
+        DEFAULT_DESCRIPTION.with(|cell| cell.get_or_init(|| meta.get_attribute("content")).clone());
+    if let Some(content) = head.description.as_deref().or(default.as_deref()) {
+        let _ = meta.set_attribute("content", content);
+    }
+}
+
 pub fn current_route_request() -> RouteRequest {
     RouteRequest::new(current_hash())
 }
 
 pub fn push_route(route: &RouteRequest) {
-    push_request_path(&route.url_path);
+    push_request_path(&route.to_path());
 }
 
 pub fn replace_route(route: &RouteRequest) {
-    replace_request_path(&route.url_path);
+    replace_request_path(&route.to_path());
 }
 
 pub fn push_request_path(path: &str) {
-    set_hash(&format!("#{}", RouteRequest::new(path).url_path));
+    set_hash(&format!("#{}", RouteRequest::new(path).to_path()));
 }
 
 pub fn replace_request_path(path: &str) {
-    replace_hash(&format!("#{}", RouteRequest::new(path).url_path));
+    replace_hash(&format!("#{}", RouteRequest::new(path).to_path()));
     dispatch_hashchange();
 }
 
+/// Rewrite the address bar to `route` without notifying the router, e.g.
+/// to drop deep-link extras once they have been consumed.
+pub fn rewrite_route(route: &RouteRequest) {
+    replace_hash(&format!("#{}", route.to_path()));
+}
+
 fn current_hash() -> String {
     window()
         .and_then(|w| w.location().hash().ok())
@@ -1,13 +1,28 @@
-//! Browser fetch helpers with timeout support.
+//! Browser fetch helpers with timeout and retry support.
+//!
+//! Each call site picks a [`FetchPolicy`]: how long an attempt may wait for
+//! the response and how many times a timeout or network error is retried.
+//! Retries back off exponentially with jitter; HTTP errors and
+//! cancellations are never retried.
 
-use js_sys::{Array, Promise};
+use std::future::Future;
+
+use js_sys::{Array, Promise, Uint8Array};
 use serde::de::DeserializeOwned;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{AbortController, Request, RequestInit, RequestMode, Response};
+use web_sys::{
+    AbortController, AbortSignal, ReadableStreamDefaultReader, ReadableStreamReadResult, Request,
+    RequestCache, RequestInit, RequestMode, Response,
+};
+
+use websh_core::shell::FetchResponse;
 
-use crate::config::FETCH_TIMEOUT_MS;
+use crate::config::{
+    DEFAULT_FETCH_RETRIES, FETCH_RETRY_BASE_MS, FETCH_RETRY_MAX_MS, FETCH_TIMEOUT_MS,
+    MAX_FETCH_RETRIES,
+};
 
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum FetchError {
@@ -29,6 +44,132 @@ pub enum FetchError {
     JsonParseError(String),
     #[error("request timed out")]
     Timeout,
+    #[error("redirected to a non-https URL")]
+    InsecureRedirect,
+    #[error("request cancelled")]
+    Aborted,
+    #[error("{last} (failed after {attempts} attempts)")]
+    Retried {
+        attempts: u32,
+        last: Box<FetchError>,
+    },
+}
+
+/// Timeout, retries and caching for one call site.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FetchPolicy {
+    /// How long each attempt waits for the response headers.
+    pub timeout_ms: i32,
+    /// Further attempts after a timeout or network error.
+    pub retries: u32,
+    /// Revalidate with the server instead of using the HTTP cache.
+    pub no_cache: bool,
+}
+
+impl FetchPolicy {
+    /// One attempt bounded by `timeout_ms`.
+    pub const fn new(timeout_ms: i32) -> Self {
+        Self {
+            timeout_ms,
+            retries: 0,
+            no_cache: false,
+        }
+    }
+
+    pub const fn with_retries(self, retries: u32) -> Self {
+        Self { retries, ..self }
+    }
+
+    pub const fn no_cache(self) -> Self {
+        Self {
+            no_cache: true,
+            ..self
+        }
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.retries.saturating_add(1)
+    }
+}
+
+impl Default for FetchPolicy {
+    fn default() -> Self {
+        Self::new(FETCH_TIMEOUT_MS)
+    }
+}
+
+/// Retries for a `FETCH_RETRIES` value: the default when unset or not a
+/// number, otherwise capped at [`MAX_FETCH_RETRIES`].
+pub fn fetch_retries(value: Option<&str>) -> u32 {
+    value
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .map_or(DEFAULT_FETCH_RETRIES, |retries| {
+            retries.min(MAX_FETCH_RETRIES)
+        })
+}
+
+/// Whether a failed attempt is worth repeating: timeouts and network
+/// failures are, HTTP errors and cancellations are not.
+pub fn should_retry(error: &FetchError) -> bool {
+    matches!(
+        error,
+        FetchError::Timeout | FetchError::NetworkError(_) | FetchError::ResponseReadFailed
+    )
+}
+
+/// Wait before retry number `retry` (from 1): the base doubles per retry up
+/// to [`FETCH_RETRY_MAX_MS`], and `jitter` in `0.0..1.0` picks a point in
+/// its upper half so clients that failed together spread out.
+pub fn backoff_delay_ms(retry: u32, jitter: f64) -> i32 {
+    let doubling = retry.saturating_sub(1).min(16);
+    let ceiling = FETCH_RETRY_BASE_MS
+        .saturating_mul(1 << doubling)
+        .min(FETCH_RETRY_MAX_MS);
+    let half = ceiling / 2;
+    half + (f64::from(ceiling - half) * jitter.clamp(0.0, 1.0)) as i32
+}
+
+/// Cancels the fetches handed its [`signal`](Self::signal). Dropping it
+/// leaves them running; call [`abort`](Self::abort) to stop them.
+#[derive(Clone, Debug)]
+pub struct FetchAbort(AbortController);
+
+impl FetchAbort {
+    pub fn new() -> Result<Self, FetchError> {
+        AbortController::new()
+            .map(Self)
+            .map_err(|_| FetchError::AbortControllerFailed)
+    }
+
+    pub fn signal(&self) -> AbortSignal {
+        self.0.signal()
+    }
+
+    pub fn abort(&self) {
+        self.0.abort();
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.0.signal().aborted()
+    }
+}
+
+/// Bytes received so far against the advertised `Content-Length`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FetchProgress {
+    pub received: u64,
+    pub total: Option<u64>,
+}
+
+impl FetchProgress {
+    /// Completed share in `0.0..=1.0`; `None` when the length is unknown.
+    /// Compressed transfers can deliver more than `Content-Length`, so the
+    /// ratio is clamped.
+    pub fn fraction(&self) -> Option<f64> {
+        self.total
+            .filter(|total| *total > 0)
+            .map(|total| (self.received as f64 / total as f64).min(1.0))
+    }
 }
 
 #[derive(Debug)]
@@ -64,48 +205,358 @@ pub async fn race_with_timeout(promise: Promise, timeout_ms: i32) -> RaceResult
     }
 }
 
-pub async fn fetch_json<T: DeserializeOwned>(url: &str) -> Result<T, FetchError> {
-    let text = fetch_url(url).await?;
+pub async fn fetch_json<T: DeserializeOwned>(
+    url: &str,
+    policy: FetchPolicy,
+) -> Result<T, FetchError> {
+    let text = fetch_content(url, None, policy).await?;
     serde_json::from_str(&text).map_err(|e| FetchError::JsonParseError(e.to_string()))
 }
 
-pub async fn fetch_content(url: &str) -> Result<String, FetchError> {
-    fetch_url(url).await
+/// GET `url` as text under `policy`. Aborting `signal` (see [`FetchAbort`])
+/// cancels the request, and any retry still to come, and resolves to
+/// [`FetchError::Aborted`].
+pub async fn fetch_content(
+    url: &str,
+    signal: Option<&AbortSignal>,
+    policy: FetchPolicy,
+) -> Result<String, FetchError> {
+    with_retries(policy, signal, || fetch_url(url, signal, policy)).await
+}
+
+/// Streaming variant of [`fetch_content`] for large bodies: reads the
+/// response in chunks and calls `on_progress` after each one. The timeout
+/// only covers the response headers, not the body transfer; `signal`
+/// cancels either. A retry reports progress from zero again.
+pub async fn fetch_bytes_with_progress(
+    url: &str,
+    signal: Option<&AbortSignal>,
+    policy: FetchPolicy,
+    on_progress: impl Fn(FetchProgress),
+) -> Result<Vec<u8>, FetchError> {
+    with_retries(policy, signal, || {
+        fetch_bytes_once(url, signal, policy, &on_progress)
+    })
+    .await
+}
+
+/// Run `attempt` until it succeeds, fails for good, or `policy` runs out of
+/// retries. A failure that used up several attempts comes back as
+/// [`FetchError::Retried`].
+async fn with_retries<T, F, Fut>(
+    policy: FetchPolicy,
+    signal: Option<&AbortSignal>,
+    mut attempt: F,
+) -> Result<T, FetchError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, FetchError>>,
+{
+    let mut attempts = 1;
+    loop {
+        match attempt().await {
+            Err(error) if should_retry(&error) && attempts < policy.attempts() => {
+                sleep(backoff_delay_ms(attempts, js_sys::Math::random())).await;
+                if signal.is_some_and(AbortSignal::aborted) {
+                    return Err(FetchError::Aborted);
+                }
+                attempts += 1;
+            }
+            Err(error) if should_retry(&error) && attempts > 1 => {
+                return Err(FetchError::Retried {
+                    attempts,
+                    last: Box::new(error),
+                });
+            }
+            result => return result,
+        }
+    }
 }
 
-async fn fetch_url(url: &str) -> Result<String, FetchError> {
+async fn sleep(ms: i32) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let promise = Promise::new(&mut |resolve, _| {
+        let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms);
+    });
+    let _ = JsFuture::from(promise).await;
+}
+
+async fn fetch_bytes_once(
+    url: &str,
+    signal: Option<&AbortSignal>,
+    policy: FetchPolicy,
+    on_progress: &impl Fn(FetchProgress),
+) -> Result<Vec<u8>, FetchError> {
+    let read_failed = || read_error(signal);
+    let resp = send_request(url, signal, policy).await?;
+    let total = resp
+        .headers()
+        .get("content-length")
+        .ok()
+        .flatten()
+        .and_then(|value| value.trim().parse::<u64>().ok());
+    let mut progress = FetchProgress { received: 0, total };
+    on_progress(progress);
+
+    let Some(body) = resp.body() else {
+        let buffer = JsFuture::from(resp.array_buffer().map_err(|_| read_failed())?)
+            .await
+            .map_err(|_| read_failed())?;
+        let bytes = Uint8Array::new(&buffer).to_vec();
+        progress.received = bytes.len() as u64;
+        on_progress(progress);
+        return Ok(bytes);
+    };
+
+    let reader: ReadableStreamDefaultReader = body.get_reader().unchecked_into();
+    let mut bytes = Vec::new();
+    loop {
+        let result: ReadableStreamReadResult = JsFuture::from(reader.read())
+            .await
+            .map_err(|_| read_failed())?
+            .unchecked_into();
+        if result.get_done().unwrap_or(true) {
+            break;
+        }
+        let chunk = Uint8Array::new(&result.get_value());
+        bytes.extend_from_slice(&chunk.to_vec());
+        progress.received = bytes.len() as u64;
+        on_progress(progress);
+    }
+    Ok(bytes)
+}
+
+async fn fetch_url(
+    url: &str,
+    signal: Option<&AbortSignal>,
+    policy: FetchPolicy,
+) -> Result<String, FetchError> {
+    let resp = send_request(url, signal, policy).await?;
+    let text = JsFuture::from(resp.text().map_err(|_| read_error(signal))?)
+        .await
+        .map_err(|_| read_error(signal))?;
+
+    text.as_string().ok_or(FetchError::InvalidContent)
+}
+
+/// A failed body read is a cancellation when the caller aborted it.
+fn read_error(signal: Option<&AbortSignal>) -> FetchError {
+    if signal.is_some_and(AbortSignal::aborted) {
+        FetchError::Aborted
+    } else {
+        FetchError::ResponseReadFailed
+    }
+}
+
+/// Response headers [`fetch_response`] keeps.
+const RESPONSE_HEADERS: &[&str] = &[
+    "content-type",
+    "content-length",
+    "last-modified",
+    "etag",
+    "cache-control",
+];
+
+/// GET `url` for the `fetch` command. Unlike [`fetch_content`], error
+/// statuses are returned as responses so the terminal can print them; a
+/// redirect that left https is refused. It is tried once.
+pub async fn fetch_response(url: &str) -> Result<FetchResponse, FetchError> {
+    let resp = send_any_request(url, None, FetchPolicy::default()).await?;
+    if !resp.url().is_empty() && !resp.url().starts_with("https://") {
+        return Err(FetchError::InsecureRedirect);
+    }
+    let headers = RESPONSE_HEADERS
+        .iter()
+        .filter_map(|name| {
+            let value = resp.headers().get(name).ok().flatten()?;
+            Some((name.to_string(), value))
+        })
+        .collect();
+    let body = JsFuture::from(resp.text().map_err(|_| FetchError::ResponseReadFailed)?)
+        .await
+        .map_err(|_| FetchError::ResponseReadFailed)?
+        .as_string()
+        .ok_or(FetchError::InvalidContent)?;
+    Ok(FetchResponse {
+        status: resp.status(),
+        status_text: resp.status_text(),
+        headers,
+        body,
+    })
+}
+
+/// Issue a CORS GET and wait (bounded by the policy's timeout) for a
+/// successful response.
+async fn send_request(
+    url: &str,
+    signal: Option<&AbortSignal>,
+    policy: FetchPolicy,
+) -> Result<Response, FetchError> {
+    let resp = send_any_request(url, signal, policy).await?;
+    if !resp.ok() {
+        return Err(FetchError::HttpError(resp.status()));
+    }
+    Ok(resp)
+}
+
+/// Issue a CORS GET and wait (bounded by the policy's timeout) for any
+/// response, whatever its status. The request follows both the timeout
+/// and the caller's `signal`.
+async fn send_any_request(
+    url: &str,
+    signal: Option<&AbortSignal>,
+    policy: FetchPolicy,
+) -> Result<Response, FetchError> {
     let window = web_sys::window().ok_or(FetchError::NoWindow)?;
+    if signal.is_some_and(AbortSignal::aborted) {
+        return Err(FetchError::Aborted);
+    }
 
     let opts = RequestInit::new();
     opts.set_method("GET");
     opts.set_mode(RequestMode::Cors);
+    if policy.no_cache {
+        opts.set_cache(RequestCache::NoCache);
+    }
     let abort = AbortController::new().map_err(|_| FetchError::AbortControllerFailed)?;
-    let signal = abort.signal();
-    opts.set_signal(Some(&signal));
+    let request_signal = match signal {
+        Some(signal) => AbortSignal::any(&Array::of2(&abort.signal(), signal)),
+        None => abort.signal(),
+    };
+    opts.set_signal(Some(&request_signal));
 
     let request = Request::new_with_str_and_init(url, &opts)
         .map_err(|_| FetchError::RequestCreationFailed)?;
 
     let fetch_promise = window.fetch_with_request(&request);
 
-    match race_with_timeout(fetch_promise, FETCH_TIMEOUT_MS).await {
+    match race_with_timeout(fetch_promise, policy.timeout_ms).await {
         RaceResult::TimedOut => {
             abort.abort();
             Err(FetchError::Timeout)
         }
+        RaceResult::Error(_) if signal.is_some_and(AbortSignal::aborted) => {
+            Err(FetchError::Aborted)
+        }
         RaceResult::Error(msg) => Err(FetchError::NetworkError(msg)),
-        RaceResult::Completed(result) => {
-            let resp: Response = result.dyn_into().map_err(|_| FetchError::InvalidContent)?;
+        RaceResult::Completed(result) => result.dyn_into().map_err(|_| FetchError::InvalidContent),
+    }
+}
 
-            if !resp.ok() {
-                return Err(FetchError::HttpError(resp.status()));
-            }
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
 
-            let text = JsFuture::from(resp.text().map_err(|_| FetchError::ResponseReadFailed)?)
-                .await
-                .map_err(|_| FetchError::ResponseReadFailed)?;
+    wasm_bindgen_test_configure!(run_in_browser);
 
-            text.as_string().ok_or(FetchError::InvalidContent)
+    #[wasm_bindgen_test]
+    fn progress_fraction_needs_a_known_length() {
+        let unknown = FetchProgress {
+            received: 512,
+            total: None,
+        };
+        assert_eq!(unknown.fraction(), None);
+        let empty = FetchProgress {
+            received: 0,
+            total: Some(0),
+        };
+        assert_eq!(empty.fraction(), None);
+        let half = FetchProgress {
+            received: 512,
+            total: Some(1024),
+        };
+        assert_eq!(half.fraction(), Some(0.5));
+        let over = FetchProgress {
+            received: 4096,
+            total: Some(1024),
+        };
+        assert_eq!(over.fraction(), Some(1.0));
+    }
+
+    #[wasm_bindgen_test]
+    fn abort_handle_cancels_its_signal() {
+        let abort = FetchAbort::new().unwrap();
+        let signal = abort.signal();
+        assert!(!abort.is_aborted());
+        abort.abort();
+        assert!(abort.is_aborted());
+        assert!(signal.aborted());
+    }
+
+    #[wasm_bindgen_test]
+    async fn aborted_signal_fails_before_the_request() {
+        let abort = FetchAbort::new().unwrap();
+        abort.abort();
+        let result = fetch_content(
+            "./manifest.json",
+            Some(&abort.signal()),
+            FetchPolicy::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(FetchError::Aborted)));
+    }
+
+    #[wasm_bindgen_test]
+    fn body_read_failures_report_cancellation_once_aborted() {
+        let abort = FetchAbort::new().unwrap();
+        assert!(matches!(
+            read_error(Some(&abort.signal())),
+            FetchError::ResponseReadFailed
+        ));
+        abort.abort();
+        assert!(matches!(
+            read_error(Some(&abort.signal())),
+            FetchError::Aborted
+        ));
+        assert!(matches!(read_error(None), FetchError::ResponseReadFailed));
+    }
+
+    #[wasm_bindgen_test]
+    fn retries_only_timeouts_and_network_failures() {
+        assert!(should_retry(&FetchError::Timeout));
+        assert!(should_retry(&FetchError::NetworkError("reset".into())));
+        assert!(should_retry(&FetchError::ResponseReadFailed));
+        for status in [400, 403, 404, 429, 500, 503] {
+            assert!(!should_retry(&FetchError::HttpError(status)), "{status}");
         }
+        assert!(!should_retry(&FetchError::Aborted));
+        assert!(!should_retry(&FetchError::JsonParseError("eof".into())));
+    }
+
+    #[wasm_bindgen_test]
+    fn backoff_doubles_up_to_the_cap_with_jitter_in_the_upper_half() {
+        assert_eq!(backoff_delay_ms(1, 0.0), FETCH_RETRY_BASE_MS / 2);
+        assert_eq!(backoff_delay_ms(1, 1.0), FETCH_RETRY_BASE_MS);
+        assert_eq!(backoff_delay_ms(2, 1.0), FETCH_RETRY_BASE_MS * 2);
+        assert_eq!(backoff_delay_ms(3, 0.5), FETCH_RETRY_BASE_MS * 3);
+        assert_eq!(backoff_delay_ms(30, 1.0), FETCH_RETRY_MAX_MS);
+        assert_eq!(backoff_delay_ms(30, 0.0), FETCH_RETRY_MAX_MS / 2);
+        // Out-of-range jitter stays inside the window.
+        assert_eq!(backoff_delay_ms(1, 7.0), FETCH_RETRY_BASE_MS);
+    }
+
+    #[wasm_bindgen_test]
+    fn retry_count_comes_from_the_variable() {
+        assert_eq!(fetch_retries(None), DEFAULT_FETCH_RETRIES);
+        assert_eq!(fetch_retries(Some("0")), 0);
+        assert_eq!(fetch_retries(Some(" 3 ")), 3);
+        assert_eq!(fetch_retries(Some("99")), MAX_FETCH_RETRIES);
+        assert_eq!(fetch_retries(Some("lots")), DEFAULT_FETCH_RETRIES);
+        assert_eq!(FetchPolicy::default().with_retries(2).attempts(), 3);
+    }
+
+    #[wasm_bindgen_test]
+    fn exhausted_retries_name_the_attempts() {
+        let error = FetchError::Retried {
+            attempts: 3,
+            last: Box::new(FetchError::Timeout),
+        };
+        assert_eq!(
+            error.to_string(),
+            "request timed out (failed after 3 attempts)"
+        );
     }
 }
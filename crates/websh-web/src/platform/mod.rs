@@ -10,5 +10,8 @@ pub mod time;
 pub mod wasm_cleanup;
 
 pub use asset::{BrowserAssetUrl, object_url_for_bytes};
-pub use fetch::{RaceResult, fetch_content, fetch_json, race_with_timeout};
-pub use time::current_timestamp;
+pub use fetch::{
+    FetchAbort, FetchPolicy, FetchProgress, RaceResult, fetch_bytes_with_progress, fetch_content,
+    fetch_json, fetch_response, race_with_timeout,
+};
+pub use time::{current_timestamp, page_uptime_ms};
@@ -22,6 +22,22 @@ const ALLOWED_REDIRECT_DOMAINS: &[&str] = &[
     "youtu.be",
 ];
 
+/// Hosts the page's CSP `connect-src` in `index.html` lets the browser
+/// reach, so the only ones `fetch` can talk to. A `*.` entry covers
+/// subdomains only, as in CSP.
+const FETCH_CONNECT_SOURCES: &[&str] = &[
+    "*.githubusercontent.com",
+    "api.github.com",
+    "*.infura.io",
+    "*.etherscan.io",
+    "*.cloudflare-eth.com",
+    "api.ensideas.com",
+];
+
+/// User variable (`on|off`) that skips the confirmation screen before
+/// cross-origin redirects. Off by default.
+pub const AUTO_REDIRECT_VAR: &str = "AUTO_REDIRECT";
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum UrlValidation {
     Valid(String),
@@ -34,13 +50,21 @@ pub enum UrlValidationError {
     Empty,
     #[error("URL must start with http:// or https://")]
     InvalidProtocol,
+    #[error("URL must use https://")]
+    HttpsRequired,
+    #[error("URL must not carry credentials")]
+    Credentials,
     #[error("URL has no host")]
     NoHost,
     #[error("Domain '{0}' is not allowed")]
     DomainNotAllowed(String),
+    #[error("Host '{0}' is blocked by the page CSP")]
+    BlockedByCsp(String),
 }
 
-pub fn validate_redirect_url(url: &str) -> UrlValidation {
+/// Check `url` against the built-in domain allowlist and, when given,
+/// `mount_domains` as well; a mount's list can only narrow what is allowed.
+pub fn validate_redirect_url(url: &str, mount_domains: Option<&[String]>) -> UrlValidation {
     let url = url.trim();
 
     if url.is_empty() {
@@ -56,13 +80,110 @@ pub fn validate_redirect_url(url: &str) -> UrlValidation {
         return UrlValidation::Invalid(UrlValidationError::NoHost);
     };
 
-    if !is_domain_allowed(&host) {
+    let on_mount_list = mount_domains.is_none_or(|domains| {
+        domains
+            .iter()
+            .any(|domain| host_matches(&host, &domain.trim().to_lowercase()))
+    });
+    if !is_domain_allowed(&host) || !on_mount_list {
         return UrlValidation::Invalid(UrlValidationError::DomainNotAllowed(host));
     }
 
     UrlValidation::Valid(url.to_string())
 }
 
+/// Check a `fetch` target: the redirect rules, narrowed to `https://`
+/// without credentials, with `extra_hosts` (and their subdomains) allowed
+/// beside the built-in domain list. Hosts outside the page CSP are refused
+/// first, since no allowlist can get a request to them past the browser.
+pub fn validate_fetch_url(url: &str, extra_hosts: &[String]) -> UrlValidation {
+    let url = url.trim();
+    if url.is_empty() {
+        return UrlValidation::Invalid(UrlValidationError::Empty);
+    }
+    let Some(rest) = strip_prefix_ignore_case(url, "https://") else {
+        return UrlValidation::Invalid(if strip_prefix_ignore_case(url, "http://").is_some() {
+            UrlValidationError::HttpsRequired
+        } else {
+            UrlValidationError::InvalidProtocol
+        });
+    };
+    if rest
+        .split(['/', '?', '#'])
+        .next()
+        .is_some_and(|authority| authority.contains('@'))
+    {
+        return UrlValidation::Invalid(UrlValidationError::Credentials);
+    }
+
+    let Some(host) = extract_host(url) else {
+        return UrlValidation::Invalid(UrlValidationError::NoHost);
+    };
+    if !is_connect_allowed(&host) {
+        return UrlValidation::Invalid(UrlValidationError::BlockedByCsp(host));
+    }
+    let extra_allowed = extra_hosts
+        .iter()
+        .map(|extra| extra.trim().to_lowercase())
+        .any(|extra| !extra.is_empty() && host_matches(&host, &extra));
+    if !extra_allowed {
+        return validate_redirect_url(url, None);
+    }
+    UrlValidation::Valid(url.to_string())
+}
+
+/// Hosts named in a `FETCH_ALLOW`-style value, split on commas and spaces.
+pub fn parse_host_list(value: &str) -> Vec<String> {
+    value
+        .split([',', ' '])
+        .map(str::trim)
+        .filter(|host| !host.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = text.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &text[prefix.len()..])
+}
+
+/// What the reader does with a validated redirect target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RedirectAction {
+    /// Navigate straight away.
+    Navigate,
+    /// Show the destination and wait for the visitor to continue.
+    Confirm { host: String },
+}
+
+/// Decide between navigating to `url` and asking first. Same-origin targets
+/// always navigate; cross-origin ones need confirmation unless
+/// `auto_redirect` is set.
+pub fn redirect_action(url: &str, page_origin: &str, auto_redirect: bool) -> RedirectAction {
+    let same_origin = url_origin(url)
+        .is_some_and(|origin| origin == page_origin.trim_end_matches('/').to_lowercase());
+    if same_origin || auto_redirect {
+        return RedirectAction::Navigate;
+    }
+    let host = extract_host(url).unwrap_or_else(|| url.to_string());
+    RedirectAction::Confirm { host }
+}
+
+/// `scheme://host[:port]`, lowercased, for `http(s)` URLs.
+fn url_origin(url: &str) -> Option<String> {
+    let (scheme, rest) = url.trim().split_once("://")?;
+    let scheme = scheme.to_lowercase();
+    if scheme != "http" && scheme != "https" {
+        return None;
+    }
+    let authority = rest.split(['/', '?', '#']).next()?;
+    if authority.is_empty() {
+        return None;
+    }
+    Some(format!("{scheme}://{}", authority.to_lowercase()))
+}
+
 fn extract_host(url: &str) -> Option<String> {
     let without_protocol = url
         .strip_prefix("https://")
@@ -86,7 +207,21 @@ fn is_domain_allowed(host: &str) -> bool {
 
     ALLOWED_REDIRECT_DOMAINS
         .iter()
-        .any(|allowed| host_lower == *allowed || host_lower.ends_with(&format!(".{allowed}")))
+        .any(|allowed| host_matches(&host_lower, allowed))
+}
+
+fn is_connect_allowed(host: &str) -> bool {
+    FETCH_CONNECT_SOURCES
+        .iter()
+        .any(|source| match source.strip_prefix("*.") {
+            Some(domain) => host.ends_with(&format!(".{domain}")),
+            None => host == *source,
+        })
+}
+
+/// `host` is `domain` or one of its subdomains.
+fn host_matches(host: &str, domain: &str) -> bool {
+    host == domain || host.ends_with(&format!(".{domain}"))
 }
 
 #[cfg(all(test, target_arch = "wasm32"))]
@@ -99,11 +234,11 @@ mod tests {
     #[wasm_bindgen_test]
     fn allows_exact_allowed_domains() {
         assert!(matches!(
-            validate_redirect_url("https://github.com/user/repo"),
+            validate_redirect_url("https://github.com/user/repo", None),
             UrlValidation::Valid(_)
         ));
         assert!(matches!(
-            validate_redirect_url("http://twitter.com/user"),
+            validate_redirect_url("http://twitter.com/user", None),
             UrlValidation::Valid(_)
         ));
     }
@@ -111,11 +246,11 @@ mod tests {
     #[wasm_bindgen_test]
     fn allows_www_and_subdomains() {
         assert!(matches!(
-            validate_redirect_url("https://www.github.com/user"),
+            validate_redirect_url("https://www.github.com/user", None),
             UrlValidation::Valid(_)
         ));
         assert!(matches!(
-            validate_redirect_url("https://api.github.com/repos"),
+            validate_redirect_url("https://api.github.com/repos", None),
             UrlValidation::Valid(_)
         ));
     }
@@ -123,11 +258,11 @@ mod tests {
     #[wasm_bindgen_test]
     fn rejects_invalid_protocols() {
         assert!(matches!(
-            validate_redirect_url("ftp://example.com"),
+            validate_redirect_url("ftp://example.com", None),
             UrlValidation::Invalid(UrlValidationError::InvalidProtocol)
         ));
         assert!(matches!(
-            validate_redirect_url("javascript:alert(1)"),
+            validate_redirect_url("javascript:alert(1)", None),
             UrlValidation::Invalid(UrlValidationError::InvalidProtocol)
         ));
     }
@@ -135,7 +270,7 @@ mod tests {
     #[wasm_bindgen_test]
     fn rejects_empty_urls() {
         assert!(matches!(
-            validate_redirect_url(""),
+            validate_redirect_url("", None),
             UrlValidation::Invalid(UrlValidationError::Empty)
         ));
     }
@@ -143,11 +278,105 @@ mod tests {
     #[wasm_bindgen_test]
     fn rejects_blocked_domains() {
         assert!(matches!(
-            validate_redirect_url("https://evil.com/phishing"),
+            validate_redirect_url("https://evil.com/phishing", None),
+            UrlValidation::Invalid(UrlValidationError::DomainNotAllowed(_))
+        ));
+    }
+
+    fn domains(list: &[&str]) -> Vec<String> {
+        list.iter().map(|domain| domain.to_string()).collect()
+    }
+
+    #[wasm_bindgen_test]
+    fn mount_allowlist_accepts_listed_hosts() {
+        let allowlist = domains(&["github.com", "Mirror.xyz"]);
+        assert!(matches!(
+            validate_redirect_url("https://github.com/user", Some(&allowlist)),
+            UrlValidation::Valid(_)
+        ));
+        assert!(matches!(
+            validate_redirect_url("https://www.mirror.xyz/post", Some(&allowlist)),
+            UrlValidation::Valid(_)
+        ));
+    }
+
+    #[wasm_bindgen_test]
+    fn mount_allowlist_rejects_hosts_off_the_list() {
+        let allowlist = domains(&["github.com"]);
+        assert_eq!(
+            validate_redirect_url("https://twitter.com/user", Some(&allowlist)),
+            UrlValidation::Invalid(UrlValidationError::DomainNotAllowed(
+                "twitter.com".to_string()
+            ))
+        );
+        assert!(matches!(
+            validate_redirect_url("https://github.com/user", Some(&[])),
             UrlValidation::Invalid(UrlValidationError::DomainNotAllowed(_))
         ));
     }
 
+    #[wasm_bindgen_test]
+    fn mount_allowlist_cannot_widen_builtin_list() {
+        let allowlist = domains(&["evil.com"]);
+        assert!(matches!(
+            validate_redirect_url("https://evil.com/phishing", Some(&allowlist)),
+            UrlValidation::Invalid(UrlValidationError::DomainNotAllowed(_))
+        ));
+    }
+
+    #[wasm_bindgen_test]
+    fn mount_allowlist_keeps_scheme_checks() {
+        let allowlist = domains(&["github.com"]);
+        assert!(matches!(
+            validate_redirect_url("javascript://github.com/%0aalert(1)", Some(&allowlist)),
+            UrlValidation::Invalid(UrlValidationError::InvalidProtocol)
+        ));
+        assert!(matches!(
+            validate_redirect_url("ftp://github.com", Some(&allowlist)),
+            UrlValidation::Invalid(UrlValidationError::InvalidProtocol)
+        ));
+    }
+
+    #[wasm_bindgen_test]
+    fn cross_origin_redirects_ask_for_confirmation() {
+        assert_eq!(
+            redirect_action("https://www.github.com/user", "https://wonj.me", false),
+            RedirectAction::Confirm {
+                host: "github.com".to_string()
+            }
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn same_origin_redirects_navigate() {
+        assert_eq!(
+            redirect_action("https://WONJ.me/blog?x#y", "https://wonj.me/", false),
+            RedirectAction::Navigate
+        );
+        assert_eq!(
+            redirect_action("https://wonj.me:8443/", "https://wonj.me", false),
+            RedirectAction::Confirm {
+                host: "wonj.me".to_string()
+            },
+            "a different port is a different origin"
+        );
+        assert_eq!(
+            redirect_action("http://wonj.me/", "https://wonj.me", false),
+            RedirectAction::Confirm {
+                host: "wonj.me".to_string()
+            },
+            "a different scheme is a different origin"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn auto_redirect_skips_confirmation() {
+        assert_eq!(
+            redirect_action("https://github.com/user", "https://wonj.me", true),
+            RedirectAction::Navigate
+        );
+    }
+
     #[wasm_bindgen_test]
     fn extracts_hosts_for_validation() {
         assert_eq!(
@@ -164,4 +393,90 @@ mod tests {
         );
         assert_eq!(extract_host("https://"), None);
     }
+
+    #[wasm_bindgen_test]
+    fn fetch_requires_https_without_credentials() {
+        assert!(matches!(
+            validate_fetch_url("https://api.github.com/zen", &[]),
+            UrlValidation::Valid(_)
+        ));
+        assert!(matches!(
+            validate_fetch_url("http://api.github.com/zen", &[]),
+            UrlValidation::Invalid(UrlValidationError::HttpsRequired)
+        ));
+        assert!(matches!(
+            validate_fetch_url("file:///etc/passwd", &[]),
+            UrlValidation::Invalid(UrlValidationError::InvalidProtocol)
+        ));
+        assert!(matches!(
+            validate_fetch_url("https://user:pw@github.com/", &[]),
+            UrlValidation::Invalid(UrlValidationError::Credentials)
+        ));
+        assert!(matches!(
+            validate_fetch_url("https://api.github.com/a@b", &[]),
+            UrlValidation::Valid(_)
+        ));
+    }
+
+    #[wasm_bindgen_test]
+    fn fetch_allows_extra_hosts_beside_the_builtin_list() {
+        let extra = parse_host_list("raw.githubusercontent.com, mainnet.infura.io");
+        assert_eq!(
+            extra,
+            domains(&["raw.githubusercontent.com", "mainnet.infura.io"])
+        );
+        assert!(matches!(
+            validate_fetch_url(
+                "https://raw.githubusercontent.com/0xwonj/websh/main/README.md",
+                &extra
+            ),
+            UrlValidation::Valid(_)
+        ));
+        assert!(matches!(
+            validate_fetch_url("https://api.etherscan.io/api", &extra),
+            UrlValidation::Valid(_)
+        ));
+        assert!(matches!(
+            validate_fetch_url("https://raw.githubusercontent.com/a/b/c", &[]),
+            UrlValidation::Invalid(UrlValidationError::DomainNotAllowed(_))
+        ));
+        assert!(matches!(
+            validate_fetch_url("https://infura.io/", &extra),
+            UrlValidation::Invalid(UrlValidationError::BlockedByCsp(_))
+        ));
+    }
+
+    #[wasm_bindgen_test]
+    fn fetch_refuses_hosts_outside_the_page_csp() {
+        let extra = parse_host_list("example.org");
+        assert_eq!(
+            validate_fetch_url("https://data.example.org/v1.json", &extra),
+            UrlValidation::Invalid(UrlValidationError::BlockedByCsp(
+                "data.example.org".to_string()
+            ))
+        );
+        assert!(matches!(
+            validate_fetch_url("https://github.com/", &[]),
+            UrlValidation::Invalid(UrlValidationError::BlockedByCsp(_))
+        ));
+        assert!(matches!(
+            validate_fetch_url("https://evil.com/", &[]),
+            UrlValidation::Invalid(UrlValidationError::BlockedByCsp(_))
+        ));
+    }
+
+    #[wasm_bindgen_test]
+    fn fetch_connect_sources_match_the_page_csp() {
+        let page = include_str!("../../../../index.html");
+        let connect_src = page
+            .split(';')
+            .map(str::trim)
+            .find_map(|directive| directive.strip_prefix("connect-src"))
+            .expect("index.html sets connect-src");
+        let csp_hosts: Vec<&str> = connect_src
+            .split_whitespace()
+            .filter_map(|source| source.strip_prefix("https://"))
+            .collect();
+        assert_eq!(csp_hosts, FETCH_CONNECT_SOURCES);
+    }
 }
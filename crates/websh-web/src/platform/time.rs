@@ -19,6 +19,24 @@ pub fn current_timestamp() -> u64 {
         .unwrap_or(0)
 }
 
+/// Milliseconds since the page started loading (`performance.now()`), or
+/// `None` when the browser does not expose it.
+#[cfg(target_arch = "wasm32")]
+pub fn page_uptime_ms() -> Option<f64> {
+    use wasm_bindgen::JsCast;
+
+    let window = web_sys::window()?;
+    let performance = js_sys::Reflect::get(&window, &"performance".into()).ok()?;
+    let now_fn = js_sys::Reflect::get(&performance, &"now".into()).ok()?;
+    let func = now_fn.dyn_ref::<js_sys::Function>()?;
+    func.call0(&performance).ok()?.as_f64()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn page_uptime_ms() -> Option<f64> {
+    None
+}
+
 #[cfg(all(test, target_arch = "wasm32"))]
 mod tests {
     use super::*;
@@ -0,0 +1,210 @@
+//! Display preference helpers.
+//!
+//! Visual effects are driven by user environment variables set through
+//! `export`, so they persist with the other user vars:
+//!
+//! - `CRT=on|off` toggles the CRT overlay (off by default).
+//! - `SCANLINES=on|off` toggles scanlines inside the overlay (on by default).
+//! - `ANIMATIONS=full|reduced` defaults to the `prefers-reduced-motion`
+//!   media query.
+//!
+//! CSS consumes the resolved animation mode from `html[data-animations]`;
+//! the overlay itself is rendered by the shell.
+
+use std::collections::BTreeMap;
+
+pub const CRT_VAR: &str = "CRT";
+pub const SCANLINES_VAR: &str = "SCANLINES";
+pub const ANIMATIONS_VAR: &str = "ANIMATIONS";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnimationMode {
+    Full,
+    Reduced,
+}
+
+impl AnimationMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::Reduced => "reduced",
+        }
+    }
+}
+
+/// Resolved visual effect settings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DisplaySettings {
+    pub crt: bool,
+    pub scanlines: bool,
+    pub animations: AnimationMode,
+}
+
+impl DisplaySettings {
+    /// Resolve settings from user variables. Unset or unrecognized values
+    /// fall back to the defaults.
+    pub fn from_env(env: &BTreeMap<String, String>, prefers_reduced_motion: bool) -> Self {
+        let default_animations = if prefers_reduced_motion {
+            AnimationMode::Reduced
+        } else {
+            AnimationMode::Full
+        };
+        Self {
+            crt: env
+                .get(CRT_VAR)
+                .and_then(|v| parse_toggle(v))
+                .unwrap_or(false),
+            scanlines: env
+                .get(SCANLINES_VAR)
+                .and_then(|v| parse_toggle(v))
+                .unwrap_or(true),
+            animations: env
+                .get(ANIMATIONS_VAR)
+                .and_then(|v| parse_animation_mode(v))
+                .unwrap_or(default_animations),
+        }
+    }
+
+    /// Whether the scanline layer is visible.
+    pub fn show_scanlines(&self) -> bool {
+        self.crt && self.scanlines
+    }
+
+    /// Whether the animated CRT sweep is visible.
+    pub fn show_sweep(&self) -> bool {
+        self.crt && self.animations == AnimationMode::Full
+    }
+}
+
+pub fn parse_toggle(raw: &str) -> Option<bool> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "on" | "true" | "yes" | "1" => Some(true),
+        "off" | "false" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+pub fn parse_animation_mode(raw: &str) -> Option<AnimationMode> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "full" | "on" | "normal" => Some(AnimationMode::Full),
+        "reduced" | "reduce" | "off" | "none" => Some(AnimationMode::Reduced),
+        _ => None,
+    }
+}
+
+/// Whether the browser asks for reduced motion.
+pub fn prefers_reduced_motion() -> bool {
+    #[cfg(target_arch = "wasm32")]
+    {
+        web_sys::window()
+            .and_then(|window| {
+                window
+                    .match_media("(prefers-reduced-motion: reduce)")
+                    .ok()
+                    .flatten()
+            })
+            .is_some_and(|query| query.matches())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        false
+    }
+}
+
+pub fn apply_display_settings_to_document(settings: &DisplaySettings) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let Some(root) = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.document_element())
+        else {
+            return;
+        };
+        let _ = root.set_attribute("data-animations", settings.animations.as_str());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = settings;
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn env(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[wasm_bindgen_test]
+    fn toggles_accept_common_spellings() {
+        for raw in ["on", "ON", " true ", "yes", "1"] {
+            assert_eq!(parse_toggle(raw), Some(true), "{raw}");
+        }
+        for raw in ["off", "False", "no", "0"] {
+            assert_eq!(parse_toggle(raw), Some(false), "{raw}");
+        }
+        assert_eq!(parse_toggle("maybe"), None);
+        assert_eq!(parse_toggle(""), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn animation_modes_parse() {
+        assert_eq!(
+            parse_animation_mode("reduced"),
+            Some(AnimationMode::Reduced)
+        );
+        assert_eq!(parse_animation_mode("OFF"), Some(AnimationMode::Reduced));
+        assert_eq!(parse_animation_mode("full"), Some(AnimationMode::Full));
+        assert_eq!(parse_animation_mode("fast"), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn defaults_keep_effects_off_and_follow_motion_preference() {
+        let settings = DisplaySettings::from_env(&BTreeMap::new(), false);
+        assert_eq!(
+            settings,
+            DisplaySettings {
+                crt: false,
+                scanlines: true,
+                animations: AnimationMode::Full,
+            }
+        );
+        assert!(!settings.show_scanlines());
+
+        let reduced = DisplaySettings::from_env(&BTreeMap::new(), true);
+        assert_eq!(reduced.animations, AnimationMode::Reduced);
+    }
+
+    #[wasm_bindgen_test]
+    fn explicit_variables_override_defaults() {
+        let settings = DisplaySettings::from_env(
+            &env(&[("CRT", "on"), ("SCANLINES", "off"), ("ANIMATIONS", "full")]),
+            true,
+        );
+        assert!(settings.crt);
+        assert!(!settings.show_scanlines());
+        assert!(settings.show_sweep());
+
+        let reduced =
+            DisplaySettings::from_env(&env(&[("CRT", "on"), ("ANIMATIONS", "reduced")]), false);
+        assert!(reduced.show_scanlines());
+        assert!(!reduced.show_sweep());
+    }
+
+    #[wasm_bindgen_test]
+    fn unrecognized_values_fall_back_to_defaults() {
+        let settings =
+            DisplaySettings::from_env(&env(&[("CRT", "sometimes"), ("ANIMATIONS", "x")]), true);
+        assert!(!settings.crt);
+        assert_eq!(settings.animations, AnimationMode::Reduced);
+    }
+}
@@ -0,0 +1,182 @@
+//! Per-route document title and meta description.
+//!
+//! The router resolves what a route points at; this module turns that into
+//! the strings the browser tab and link previews show. DOM writes live in
+//! [`crate::platform::dom::apply_document_head`].
+
+use websh_core::domain::{NodeMetadata, VirtualPath};
+
+use crate::config::APP_NAME;
+
+/// Title and description for the current route.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocumentHead {
+    pub title: String,
+    /// `None` restores the site-wide description from `index.html`.
+    pub description: Option<String>,
+}
+
+/// What the current route points at, as far as the document head cares.
+#[derive(Clone, Copy, Debug)]
+pub enum HeadSubject<'a> {
+    Home,
+    /// A built-in or app surface with a fixed name (ledger, compose, shell).
+    Section(&'a str),
+    NotFound,
+    Node {
+        path: &'a VirtualPath,
+        meta: Option<&'a NodeMetadata>,
+        /// Set when `path` is the root of a runtime mount.
+        mount_label: Option<&'a str>,
+        is_directory: bool,
+    },
+}
+
+pub fn document_head(subject: HeadSubject<'_>) -> DocumentHead {
+    match subject {
+        HeadSubject::Home => DocumentHead {
+            title: APP_NAME.to_string(),
+            description: None,
+        },
+        HeadSubject::Section(name) => DocumentHead {
+            title: suffixed(name),
+            description: None,
+        },
+        HeadSubject::NotFound => DocumentHead {
+            title: suffixed("not found"),
+            description: None,
+        },
+        HeadSubject::Node {
+            path,
+            meta,
+            mount_label,
+            is_directory,
+        } => {
+            let authored_title = meta.and_then(|meta| non_empty(meta.title()));
+            let name = path.file_name().unwrap_or(APP_NAME);
+            let title = match (mount_label, is_directory) {
+                (Some(label), _) => authored_title.unwrap_or(label).to_string(),
+                (None, true) => suffixed(authored_title.unwrap_or(name)),
+                (None, false) => authored_title.unwrap_or(name).to_string(),
+            };
+            DocumentHead {
+                title,
+                description: meta
+                    .and_then(|meta| non_empty(meta.description()))
+                    .map(str::to_string),
+            }
+        }
+    }
+}
+
+fn suffixed(name: &str) -> String {
+    format!("{name} — {APP_NAME}")
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+    use websh_core::domain::{Fields, NodeKind, SCHEMA_VERSION};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn meta(title: Option<&str>, description: Option<&str>) -> NodeMetadata {
+        NodeMetadata {
+            schema: SCHEMA_VERSION,
+            kind: NodeKind::Page,
+            authored: Fields {
+                title: title.map(str::to_string),
+                description: description.map(str::to_string),
+                ..Fields::default()
+            },
+            derived: Fields::default(),
+        }
+    }
+
+    fn path(value: &str) -> VirtualPath {
+        VirtualPath::from_absolute(value).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn files_use_manifest_title_then_filename() {
+        let post = path("/writing/hello.md");
+        let titled = meta(Some("Hello, world"), Some("A first post."));
+        let head = document_head(HeadSubject::Node {
+            path: &post,
+            meta: Some(&titled),
+            mount_label: None,
+            is_directory: false,
+        });
+        assert_eq!(head.title, "Hello, world");
+        assert_eq!(head.description.as_deref(), Some("A first post."));
+
+        let untitled = meta(Some("  "), None);
+        let head = document_head(HeadSubject::Node {
+            path: &post,
+            meta: Some(&untitled),
+            mount_label: None,
+            is_directory: false,
+        });
+        assert_eq!(head.title, "hello.md");
+        assert_eq!(head.description, None);
+    }
+
+    #[wasm_bindgen_test]
+    fn directories_are_suffixed_with_the_site_name() {
+        let dir = path("/writing");
+        let head = document_head(HeadSubject::Node {
+            path: &dir,
+            meta: Some(&meta(Some("Writing"), None)),
+            mount_label: None,
+            is_directory: true,
+        });
+        assert_eq!(head.title, format!("Writing — {APP_NAME}"));
+
+        let head = document_head(HeadSubject::Node {
+            path: &dir,
+            meta: None,
+            mount_label: None,
+            is_directory: true,
+        });
+        assert_eq!(head.title, format!("writing — {APP_NAME}"));
+    }
+
+    #[wasm_bindgen_test]
+    fn mount_roots_prefer_directory_title_over_label() {
+        let root = path("/mnt/notes");
+        let head = document_head(HeadSubject::Node {
+            path: &root,
+            meta: Some(&meta(Some("Field Notes"), Some("Scratch space."))),
+            mount_label: Some("notes"),
+            is_directory: true,
+        });
+        assert_eq!(head.title, "Field Notes");
+        assert_eq!(head.description.as_deref(), Some("Scratch space."));
+
+        let head = document_head(HeadSubject::Node {
+            path: &root,
+            meta: None,
+            mount_label: Some("notes"),
+            is_directory: true,
+        });
+        assert_eq!(head.title, "notes");
+    }
+
+    #[wasm_bindgen_test]
+    fn builtin_surfaces_have_fixed_titles() {
+        assert_eq!(document_head(HeadSubject::Home).title, APP_NAME);
+        assert_eq!(
+            document_head(HeadSubject::Section("ledger")).title,
+            format!("ledger — {APP_NAME}")
+        );
+        assert_eq!(
+            document_head(HeadSubject::NotFound).title,
+            format!("not found — {APP_NAME}")
+        );
+    }
+}
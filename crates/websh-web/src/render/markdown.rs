@@ -5,6 +5,7 @@
 use std::collections::{HashMap, HashSet};
 
 use comrak::{Options, markdown_to_html as comrak_markdown_to_html};
+use websh_core::support::frontmatter::{FrontMatter, split_front_matter};
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct RenderedMarkdown {
@@ -14,6 +15,9 @@ pub struct RenderedMarkdown {
     /// Empty for inputs with no qualifying headings or for non-markdown
     /// HTML inputs that lack `id` attributes on their headings.
     pub outline: Vec<HeadingEntry>,
+    /// Keys from a leading front matter block; `None` when the source has
+    /// none, or when it is malformed and rendered as content instead.
+    pub front_matter: Option<FrontMatter>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -39,8 +43,19 @@ pub fn sanitize_html(html: &str) -> String {
 
 /// Convert markdown content to sanitized HTML plus hydration metadata.
 pub fn render_markdown(markdown: &str) -> RenderedMarkdown {
-    let html = comrak_markdown_to_html(markdown, &markdown_options());
-    rendered_from_html(sanitize_html(&html))
+    let (front_matter, body) = match split_front_matter(markdown) {
+        Ok(Some((front_matter, body))) => (Some(front_matter), body),
+        Ok(None) => (None, markdown),
+        Err(error) => {
+            leptos::logging::warn!("markdown: rendering front matter as content: {error}");
+            (None, markdown)
+        }
+    };
+    let html = comrak_markdown_to_html(body, &markdown_options());
+    RenderedMarkdown {
+        front_matter,
+        ..rendered_from_html(sanitize_html(&html))
+    }
 }
 
 /// Convert a single inline markdown fragment to sanitized HTML plus hydration metadata.
@@ -57,6 +72,7 @@ pub fn rendered_from_html(html: String) -> RenderedMarkdown {
         html,
         has_math,
         outline,
+        front_matter: None,
     }
 }
 
@@ -239,7 +255,6 @@ fn markdown_options() -> Options<'static> {
     options.extension.tasklist = true;
     options.extension.footnotes = true;
     options.extension.autolink = true;
-    options.extension.front_matter_delimiter = Some("---".to_string());
     options.extension.header_id_prefix = Some(String::new());
     options.extension.math_dollars = true;
     options.extension.math_code = true;
@@ -664,6 +679,23 @@ mod tests {
         assert!(!rendered.html.contains("tags:"));
     }
 
+    #[wasm_bindgen_test]
+    fn render_markdown_returns_frontmatter_keys() {
+        let rendered = render_markdown("---\ntitle: Hello\ndraft: true\n---\n\nBody\n");
+        let front_matter = rendered.front_matter.expect("front matter");
+        assert_eq!(front_matter.title.as_deref(), Some("Hello"));
+        assert!(front_matter.draft);
+        assert!(!rendered.html.contains("title:"));
+    }
+
+    #[wasm_bindgen_test]
+    fn render_markdown_keeps_malformed_frontmatter_as_content() {
+        let rendered = render_markdown("---\ndraft: maybe\n---\n\nBody\n");
+        assert_eq!(rendered.front_matter, None);
+        assert!(rendered.html.contains("draft: maybe"));
+        assert!(rendered.html.contains("Body"));
+    }
+
     #[wasm_bindgen_test]
     fn render_markdown_keeps_safe_links() {
         let html = render_markdown("Writing [tabula](/#/papers/tabula).").html;
@@ -1,5 +1,7 @@
 //! Pure rendering helpers used by web features.
 
+pub mod display;
+pub mod document_head;
 pub mod markdown;
 pub mod theme;
 
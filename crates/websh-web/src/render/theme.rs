@@ -108,13 +108,15 @@ pub fn theme_label(id: &str) -> Option<&'static str> {
         .map(|theme| theme.label)
 }
 
-pub fn theme_output_lines() -> Vec<OutputLine> {
-    let mut lines = vec![OutputLine::text("available themes:")];
-    lines.extend(
-        THEMES
-            .iter()
-            .map(|theme| OutputLine::text(format!("  {:<18} {}", theme.id, theme.label))),
-    );
+/// The theme catalog with `current` marked by `*`.
+pub fn theme_output_lines(current: &str) -> Vec<OutputLine> {
+    let mut lines = vec![OutputLine::text(format!(
+        "available themes (current: {current}):"
+    ))];
+    lines.extend(THEMES.iter().map(|theme| {
+        let marker = if theme.id == current { '*' } else { ' ' };
+        OutputLine::text(format!("{marker} {:<18} {}", theme.id, theme.label))
+    }));
     lines
 }
 
@@ -224,6 +226,23 @@ mod tests {
         }
     }
 
+    #[wasm_bindgen_test]
+    fn theme_listing_covers_catalog() {
+        let lines = theme_output_lines(DEFAULT_THEME);
+        assert_eq!(lines.len(), THEMES.len() + 1);
+        let marked: Vec<_> = lines
+            .iter()
+            .map(OutputLine::to_plain_text)
+            .filter(|line| line.starts_with('*'))
+            .collect();
+        assert_eq!(marked.len(), 1);
+        assert!(marked[0].contains(DEFAULT_THEME));
+        for theme in THEMES {
+            assert_eq!(normalize_theme_id(theme.id), Some(theme.id));
+            assert_eq!(theme_label(theme.id), Some(theme.label));
+        }
+    }
+
     /// Guards against drift between `THEMES` and `index.html`.
     #[wasm_bindgen_test]
     fn index_html_lists_all_themes() {
@@ -2,6 +2,8 @@ use std::collections::{BTreeMap, VecDeque};
 
 use websh_core::domain::VirtualPath;
 
+use crate::render::RenderedMarkdown;
+
 const MAX_TEXT_CACHE_ENTRIES: usize = 64;
 const MAX_TEXT_CACHE_BYTES: usize = 512 * 1024;
 
@@ -15,6 +17,8 @@ pub struct ContentTextCacheKey {
 #[derive(Clone, Debug)]
 struct CacheEntry {
     text: String,
+    /// Reader render of `text`, counted against the same byte budget.
+    rendered: Option<RenderedMarkdown>,
     bytes: usize,
 }
 
@@ -42,7 +46,57 @@ impl ContentTextCache {
         self.remove(&key);
         self.total_bytes += bytes;
         self.order.push_back(key.clone());
-        self.entries.insert(key, CacheEntry { text, bytes });
+        self.entries.insert(
+            key,
+            CacheEntry {
+                text,
+                rendered: None,
+                bytes,
+            },
+        );
+        self.prune();
+    }
+
+    /// Render cached for `key`, provided it was made from `source`. A
+    /// source that differs (a pending draft, say) never sees it.
+    pub fn get_rendered(
+        &mut self,
+        key: &ContentTextCacheKey,
+        source: &str,
+    ) -> Option<RenderedMarkdown> {
+        let rendered = self
+            .entries
+            .get(key)
+            .filter(|entry| entry.text == source)?
+            .rendered
+            .clone()?;
+        self.touch(key);
+        Some(rendered)
+    }
+
+    /// Attach the render of `source` to the cached text for `key`. Ignored
+    /// unless that text is `source`, so a render never outlives its text.
+    pub fn insert_rendered(
+        &mut self,
+        key: &ContentTextCacheKey,
+        source: &str,
+        rendered: RenderedMarkdown,
+    ) {
+        let Some(entry) = self
+            .entries
+            .get_mut(key)
+            .filter(|entry| entry.text == source)
+        else {
+            return;
+        };
+        let previous = entry
+            .rendered
+            .replace(rendered)
+            .map_or(0, |old| old.html.len());
+        let added = entry.rendered.as_ref().map_or(0, |new| new.html.len());
+        entry.bytes = entry.bytes - previous + added;
+        self.total_bytes = self.total_bytes - previous + added;
+        self.touch(key);
         self.prune();
     }
 
@@ -105,3 +159,117 @@ impl ContentTextCache {
         }
     }
 }
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn key(rel_path: &str) -> ContentTextCacheKey {
+        ContentTextCacheKey {
+            generation: 1,
+            mount_root: VirtualPath::root(),
+            rel_path: rel_path.to_string(),
+        }
+    }
+
+    fn rendered(html: &str) -> RenderedMarkdown {
+        RenderedMarkdown {
+            html: html.to_string(),
+            ..RenderedMarkdown::default()
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn get_hits_inserted_text_and_misses_other_keys() {
+        let mut cache = ContentTextCache::default();
+        cache.insert(key("a.md"), "alpha".to_string());
+
+        assert_eq!(cache.get(&key("a.md")).as_deref(), Some("alpha"));
+        assert_eq!(cache.get(&key("b.md")), None);
+        let other_generation = ContentTextCacheKey {
+            generation: 2,
+            ..key("a.md")
+        };
+        assert_eq!(cache.get(&other_generation), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn evicts_least_recently_used_entry_first() {
+        let mut cache = ContentTextCache::default();
+        for index in 0..MAX_TEXT_CACHE_ENTRIES {
+            cache.insert(key(&format!("{index}.md")), index.to_string());
+        }
+        // Reading the oldest entry makes `1.md` the least recently used.
+        assert!(cache.get(&key("0.md")).is_some());
+
+        cache.insert(key("new.md"), "new".to_string());
+
+        assert_eq!(cache.get(&key("1.md")), None);
+        assert!(cache.get(&key("0.md")).is_some());
+        assert!(cache.get(&key("2.md")).is_some());
+        assert!(cache.get(&key("new.md")).is_some());
+    }
+
+    #[wasm_bindgen_test]
+    fn byte_budget_evicts_oldest_and_skips_oversized_text() {
+        let mut cache = ContentTextCache::default();
+        let half = "x".repeat(MAX_TEXT_CACHE_BYTES / 2);
+        cache.insert(key("a.md"), half.clone());
+        cache.insert(key("b.md"), half.clone());
+        cache.insert(key("c.md"), "c".to_string());
+
+        assert_eq!(cache.get(&key("a.md")), None);
+        assert!(cache.get(&key("b.md")).is_some());
+
+        cache.insert(key("huge.md"), "x".repeat(MAX_TEXT_CACHE_BYTES + 1));
+        assert_eq!(cache.get(&key("huge.md")), None);
+        assert!(cache.get(&key("b.md")).is_some());
+    }
+
+    #[wasm_bindgen_test]
+    fn rendered_output_is_served_only_for_its_source_text() {
+        let mut cache = ContentTextCache::default();
+        // No text cached yet: the render has nothing to attach to.
+        cache.insert_rendered(&key("a.md"), "# A", rendered("<h1>A</h1>"));
+        assert_eq!(cache.get_rendered(&key("a.md"), "# A"), None);
+
+        cache.insert(key("a.md"), "# A".to_string());
+        cache.insert_rendered(&key("a.md"), "# B", rendered("<h1>B</h1>"));
+        assert_eq!(cache.get_rendered(&key("a.md"), "# A"), None);
+
+        cache.insert_rendered(&key("a.md"), "# A", rendered("<h1>A</h1>"));
+        assert_eq!(
+            cache.get_rendered(&key("a.md"), "# A"),
+            Some(rendered("<h1>A</h1>"))
+        );
+        assert_eq!(cache.get_rendered(&key("a.md"), "# A draft"), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn rendered_output_is_dropped_with_its_text() {
+        let mut cache = ContentTextCache::default();
+        let path = VirtualPath::from_absolute("/a.md").unwrap();
+        cache.insert(key("a.md"), "# A".to_string());
+        cache.insert_rendered(&key("a.md"), "# A", rendered("<h1>A</h1>"));
+
+        cache.evict_path(&path);
+        cache.insert(key("a.md"), "# A".to_string());
+
+        assert_eq!(cache.get_rendered(&key("a.md"), "# A"), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn rendered_output_counts_against_the_byte_budget() {
+        let mut cache = ContentTextCache::default();
+        let half = "x".repeat(MAX_TEXT_CACHE_BYTES / 2);
+        cache.insert(key("a.md"), half.clone());
+        cache.insert(key("b.md"), "b".to_string());
+        cache.insert_rendered(&key("b.md"), "b", rendered(&half));
+
+        assert_eq!(cache.get(&key("a.md")), None);
+        assert!(cache.get_rendered(&key("b.md"), "b").is_some());
+    }
+}
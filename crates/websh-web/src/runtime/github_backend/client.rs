@@ -9,6 +9,10 @@ use websh_core::ports::{
     StorageError, StorageResult, parse_manifest_snapshot, serialize_manifest_snapshot,
 };
 
+use crate::config::MANIFEST_FETCH_TIMEOUT_MS;
+use crate::platform::fetch::{FetchError, FetchPolicy, fetch_content};
+use crate::runtime::state::configured_fetch_retries;
+
 use super::graphql::{BranchRef, CommitMessage, CreateCommitInput, build_file_changes};
 use super::path::{encoded_repo_relative_path, normalize_repo_prefix, prefixed_repo_path};
 
@@ -148,29 +152,28 @@ impl GitHubBackend {
         // (60/hr unauthenticated). Local commits already update the
         // in-memory GlobalFs synchronously, so the staleness window only
         // affects multi-tab/multi-user re-scans.
-        let resp = gloo_net::http::Request::get(&self.manifest_url())
-            .cache(web_sys::RequestCache::NoCache)
-            .send()
-            .await
-            .map_err(|e| StorageError::NetworkError(e.to_string()))?;
-        // A missing manifest is the canonical signal of a fresh / empty
-        // external mount. The bootstrap root is stricter because Home's
-        // root mount status needs to distinguish a failed root manifest
-        // from a genuinely empty external mount.
-        if resp.status() == 404 {
-            return if self.allow_missing_manifest {
-                Ok(ScannedSubtree::default())
-            } else {
-                Err(StorageError::NotFound(self.manifest_url()))
-            };
-        }
-        if !(200..300).contains(&resp.status()) {
-            return Err(map_http_status(resp.status(), None));
-        }
-        let body = resp
-            .text()
-            .await
-            .map_err(|e| StorageError::ValidationFailed(e.to_string()))?;
+        //
+        // The manifest gates the whole mount, so it gets a longer timeout
+        // and rides out dropped connections with `FETCH_RETRIES` retries.
+        let policy = FetchPolicy::new(MANIFEST_FETCH_TIMEOUT_MS)
+            .with_retries(configured_fetch_retries())
+            .no_cache();
+        let body = match fetch_content(&self.manifest_url(), None, policy).await {
+            Ok(body) => body,
+            // A missing manifest is the canonical signal of a fresh / empty
+            // external mount. The bootstrap root is stricter because Home's
+            // root mount status needs to distinguish a failed root manifest
+            // from a genuinely empty external mount.
+            Err(FetchError::HttpError(404)) => {
+                return if self.allow_missing_manifest {
+                    Ok(ScannedSubtree::default())
+                } else {
+                    Err(StorageError::NotFound(self.manifest_url()))
+                };
+            }
+            Err(FetchError::HttpError(status)) => return Err(map_http_status(status, None)),
+            Err(error) => return Err(StorageError::NetworkError(error.to_string())),
+        };
         parse_manifest_snapshot(&body)
     }
 
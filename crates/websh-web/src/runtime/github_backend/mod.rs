@@ -72,7 +72,8 @@ pub fn build_backend_for_declaration(
                 label,
                 RuntimeBackendKind::GitHub,
                 declaration.writable,
-            );
+            )
+            .with_redirect_domains(declaration.redirect_domains.clone());
 
             let backend =
                 GitHubBackend::new(repo, branch, mount_root, prefix, gateway).map_err(|error| {
@@ -132,9 +133,26 @@ mod tests {
             .expect("backend");
         assert_eq!(mount.root.as_str(), "/db");
         assert_eq!(mount.label, "db");
+        assert_eq!(mount.redirect_domains, None);
         assert_eq!(backend.backend_type(), "github");
     }
 
+    #[wasm_bindgen_test]
+    fn declaration_carries_redirect_domains_to_mount() {
+        let declaration = MountDeclaration {
+            backend: "github".to_string(),
+            mount_at: "/db".to_string(),
+            repo: Some("0xwonj/db".to_string()),
+            redirect_domains: Some(vec!["github.com".to_string()]),
+            ..Default::default()
+        };
+
+        let (mount, _) = build_backend_for_declaration(&declaration)
+            .expect("valid declaration")
+            .expect("backend");
+        assert_eq!(mount.redirect_domains, Some(vec!["github.com".to_string()]));
+    }
+
     #[wasm_bindgen_test]
     fn declaration_rejects_noncanonical_mount_root() {
         let declaration = MountDeclaration {
@@ -6,7 +6,7 @@ use websh_core::domain::{
     DerivedIndex, MountDeclaration, RuntimeBackendKind, RuntimeMount, VirtualPath,
 };
 use websh_core::filesystem::{BackendRegistry, GlobalFs};
-use websh_core::ports::StorageBackendRef;
+use websh_core::ports::{StorageBackendRef, lint_manifest_snapshot};
 use websh_core::runtime as core_runtime;
 use websh_site::BOOTSTRAP_SITE;
 
@@ -20,6 +20,8 @@ pub struct RuntimeLoad {
     pub remote_heads: BTreeMap<VirtualPath, String>,
     pub total_files: usize,
     pub mounts: MountLoadSet,
+    /// Boot banner declared by the bootstrap mount's manifest.
+    pub banner: Option<String>,
 }
 
 fn bootstrap_runtime_mounts() -> Vec<RuntimeMount> {
@@ -49,6 +51,7 @@ pub fn bootstrap_runtime_load() -> RuntimeLoad {
         remote_heads: BTreeMap::new(),
         total_files,
         mounts,
+        banner: None,
     }
 }
 
@@ -70,6 +73,7 @@ pub async fn load_runtime() -> Result<RuntimeLoad, String> {
         scans.push((root, scan));
     }
 
+    let banner = scans.iter().find_map(|(_, scan)| scan.banner.clone());
     let mut global_fs = core_runtime::assemble_global_fs(&scans)
         .map_err(|error| format!("assemble global filesystem: {error:?}"))?;
     let root_total_files = count_files(&global_fs, &VirtualPath::root());
@@ -77,6 +81,9 @@ pub async fn load_runtime() -> Result<RuntimeLoad, String> {
     for mount in bootstrap_runtime_mounts() {
         mounts.insert_loaded(mount, root_total_files);
     }
+    for (root, scan) in &scans {
+        mounts.record_manifest_issues(root, lint_manifest_snapshot(scan));
+    }
     apply_runtime_conventions(&mut global_fs, &mut backends, &mut mounts).await?;
     let total_files = count_files(&global_fs, &VirtualPath::root());
 
@@ -86,6 +93,7 @@ pub async fn load_runtime() -> Result<RuntimeLoad, String> {
         remote_heads: BTreeMap::new(),
         total_files,
         mounts,
+        banner,
     })
 }
 
@@ -299,6 +307,7 @@ fn fallback_mount_for_declaration(
         RuntimeBackendKind::GitHub,
         declaration.writable,
     )
+    .with_redirect_domains(declaration.redirect_domains.clone())
 }
 
 fn reserve_failed_mount_points(global: &mut GlobalFs, mounts: &MountLoadSet) {
@@ -9,6 +9,7 @@ pub(crate) mod github_backend;
 pub(crate) mod idb;
 pub(crate) mod loader;
 pub(crate) mod mounts;
+pub(crate) mod search_index;
 pub(crate) mod state;
 pub(crate) mod storage_state;
 mod system;
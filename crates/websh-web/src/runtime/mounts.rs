@@ -1,13 +1,14 @@
 use std::collections::BTreeMap;
 
 use websh_core::domain::{RuntimeMount, VirtualPath};
-use websh_core::ports::{ScannedSubtree, StorageBackendRef};
+use websh_core::ports::{ManifestIssue, ScannedSubtree, StorageBackendRef};
 
 #[derive(Clone)]
 pub struct MountLoadSet {
     pub entries: BTreeMap<VirtualPath, MountEntry>,
     pub scan_jobs: Vec<MountScanJob>,
     rejected_entries: Vec<MountEntry>,
+    manifest_issues: BTreeMap<VirtualPath, Vec<ManifestIssue>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -43,6 +44,7 @@ impl MountLoadSet {
             entries: BTreeMap::new(),
             scan_jobs: Vec::new(),
             rejected_entries: Vec::new(),
+            manifest_issues: BTreeMap::new(),
         }
     }
 
@@ -140,6 +142,31 @@ impl MountLoadSet {
             .collect()
     }
 
+    /// Replaces the manifest lint findings recorded for the mount at `root`.
+    pub fn record_manifest_issues(&mut self, root: &VirtualPath, issues: Vec<ManifestIssue>) {
+        if issues.is_empty() {
+            self.manifest_issues.remove(root);
+        } else {
+            self.manifest_issues.insert(root.clone(), issues);
+        }
+    }
+
+    /// Recorded manifest lint findings, paired with their mount's label.
+    pub fn manifest_issues(&self) -> Vec<(String, ManifestIssue)> {
+        self.manifest_issues
+            .iter()
+            .flat_map(|(root, issues)| {
+                let label = self
+                    .entries
+                    .get(root)
+                    .map_or_else(|| root.to_string(), |entry| entry.declared.label.clone());
+                issues
+                    .iter()
+                    .map(move |issue| (label.clone(), issue.clone()))
+            })
+            .collect()
+    }
+
     pub fn mark_loading(&mut self, root: &VirtualPath) -> Option<(RuntimeMount, u64)> {
         let entry = self.entries.get_mut(root)?;
         let epoch = entry.status.epoch().saturating_add(1);
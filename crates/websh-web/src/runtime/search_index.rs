@@ -0,0 +1,40 @@
+use std::collections::BTreeMap;
+
+use websh_core::domain::VirtualPath;
+
+/// Stripped markdown text for `search`, kept until the runtime generation
+/// changes (a manifest reload) or a path is evicted by a write.
+#[derive(Clone, Debug, Default)]
+pub struct SearchIndex {
+    generation: u64,
+    texts: BTreeMap<VirtualPath, String>,
+}
+
+impl SearchIndex {
+    pub fn get(&self, generation: u64, path: &VirtualPath) -> Option<String> {
+        if generation != self.generation {
+            return None;
+        }
+        self.texts.get(path).cloned()
+    }
+
+    pub fn insert(&mut self, generation: u64, path: VirtualPath, text: String) {
+        if generation != self.generation {
+            self.texts.clear();
+            self.generation = generation;
+        }
+        self.texts.insert(path, text);
+    }
+
+    pub fn evict_path(&mut self, path: &VirtualPath) {
+        self.texts.remove(path);
+    }
+
+    pub fn evict_mount(&mut self, mount_root: &VirtualPath) {
+        self.texts.retain(|path, _| !path.starts_with(mount_root));
+    }
+
+    pub fn clear(&mut self) {
+        self.texts.clear();
+    }
+}
@@ -5,9 +5,11 @@ use std::collections::BTreeMap;
 
 use thiserror::Error;
 
-use crate::config::{DEFAULT_USER_VARS, USER_VAR_PREFIX, WALLET_SESSION_KEY};
+use crate::config::{DEFAULT_USER_VARS, FETCH_RETRIES_VAR, USER_VAR_PREFIX, WALLET_SESSION_KEY};
+use crate::platform::fetch::fetch_retries;
 
 pub use websh_core::runtime::RuntimeStateSnapshot;
+use websh_core::support::i18n::{LANG_VAR, Locale};
 
 const GITHUB_TOKEN_KEY: &str = "websh.gh_token";
 
@@ -81,6 +83,12 @@ pub fn get_env_var(key: &str) -> Option<String> {
     with_state(|state| state.env.get(key).cloned())
 }
 
+/// Retries for fetches that should ride out a flaky connection, from
+/// `FETCH_RETRIES`.
+pub fn configured_fetch_retries() -> u32 {
+    fetch_retries(get_env_var(FETCH_RETRIES_VAR).as_deref())
+}
+
 pub fn set_env_var(key: &str, value: &str) -> Result<RuntimeStateSnapshot, EnvironmentError> {
     if !is_valid_var_name(key) {
         return Err(EnvironmentError::InvalidVariableName);
@@ -106,6 +114,9 @@ pub fn unset_env_var(key: &str) -> Result<RuntimeStateSnapshot, EnvironmentError
 }
 
 pub fn init_default_env() {
+    if get_env_var(LANG_VAR).is_none() {
+        let _ = set_env_var(LANG_VAR, browser_locale().as_str());
+    }
     for (key, value) in DEFAULT_USER_VARS {
         if get_env_var(key).is_none() {
             let _ = set_env_var(key, value);
@@ -113,6 +124,15 @@ pub fn init_default_env() {
     }
 }
 
+/// The supported locale of the browser's preferred language, English when
+/// it has none.
+fn browser_locale() -> Locale {
+    web_sys::window()
+        .and_then(|window| window.navigator().language())
+        .and_then(|language| Locale::parse(&language))
+        .unwrap_or_default()
+}
+
 pub fn github_token_for_commit() -> Option<String> {
     with_state(|state| state.github_token.clone())
 }
@@ -1,32 +1,63 @@
 //! Browser-provided shell execution context.
 
-use wasm_bindgen::JsCast;
+use std::sync::Arc;
+
 use websh_core::runtime::RuntimeStateSnapshot;
-use websh_core::shell::{ExecutionContext, SystemInfo};
+use websh_core::shell::{
+    AppEvent, ExecutionContext, ManifestSync, NavigationHistory, SystemInfo, UsageReport,
+};
 
 use crate::config::MS_PER_SECOND;
+use crate::platform::dom::terminal_columns;
+use crate::platform::{current_timestamp, page_uptime_ms};
+
+/// Columns taken by the indent in front of terminal listings.
+const LIST_INDENT_COLUMNS: usize = 2;
 
 /// Build the target context supplied to the core shell executor.
-pub fn shell_execution_context(runtime_state: &RuntimeStateSnapshot) -> ExecutionContext {
+///
+/// `history_lines` is the number of lines in the terminal output history;
+/// `navigation` is the route history `dirs` and `cd -N` work on, `events`
+/// the app event log `dmesg` prints, `usage` the counters `stats`
+/// reports, and `manifest_sync` the snapshots `ls --diff` compares.
+pub fn shell_execution_context(
+    runtime_state: &RuntimeStateSnapshot,
+    history_lines: usize,
+    navigation: NavigationHistory,
+    events: Vec<AppEvent>,
+    usage: UsageReport,
+    manifest_sync: Option<Arc<ManifestSync>>,
+) -> ExecutionContext {
     ExecutionContext {
         system_info: SystemInfo {
             uptime: get_uptime(),
             user_agent: get_user_agent(),
+            version: Some(app_version()),
+            viewport: get_viewport(),
+            history_lines: Some(history_lines),
         },
         env: runtime_state.env.clone(),
         access_policy: websh_site::ACCESS_POLICY,
         shell_text: websh_site::SHELL_TEXT,
+        terminal_columns: terminal_columns()
+            .map(|columns| columns.saturating_sub(LIST_INDENT_COLUMNS)),
+        now: current_timestamp() / 1000,
+        utc_offset_minutes: utc_offset_minutes(),
+        navigation,
+        events,
+        usage,
+        manifest_sync,
     }
 }
 
-fn get_uptime() -> Option<String> {
-    let window = web_sys::window()?;
-    let performance = js_sys::Reflect::get(&window, &"performance".into()).ok()?;
-    let now_fn = js_sys::Reflect::get(&performance, &"now".into()).ok()?;
-    let func = now_fn.dyn_ref::<js_sys::Function>()?;
-    let result = func.call0(&performance).ok()?;
-    let uptime_ms = result.as_f64()?;
+/// Browser time zone as minutes east of UTC (`getTimezoneOffset` counts
+/// the other way).
+fn utc_offset_minutes() -> i32 {
+    -(js_sys::Date::new_0().get_timezone_offset() as i32)
+}
 
+fn get_uptime() -> Option<String> {
+    let uptime_ms = page_uptime_ms()?;
     let uptime_secs = (uptime_ms / MS_PER_SECOND) as u64;
     let mins = uptime_secs / 60;
     let secs = uptime_secs % 60;
@@ -41,3 +72,19 @@ fn get_uptime() -> Option<String> {
 fn get_user_agent() -> Option<String> {
     web_sys::window()?.navigator().user_agent().ok()
 }
+
+/// Crate version plus the git commit embedded by `build.rs`, if any.
+fn app_version() -> String {
+    let version = env!("CARGO_PKG_VERSION");
+    match option_env!("WEBSH_GIT_COMMIT") {
+        Some(commit) => format!("{version} ({commit})"),
+        None => version.to_string(),
+    }
+}
+
+fn get_viewport() -> Option<String> {
+    let window = web_sys::window()?;
+    let width = window.inner_width().ok()?.as_f64()?;
+    let height = window.inner_height().ok()?.as_f64()?;
+    Some(format!("{}x{}", width as u32, height as u32))
+}
@@ -8,8 +8,10 @@ use wasm_bindgen::JsValue;
 use wasm_bindgen::prelude::Closure;
 use wasm_bindgen_futures::JsFuture;
 
+use websh_core::domain::{EnsAvatar, parse_ens_avatar};
+
 use crate::config::WALLET_TIMEOUT_MS;
-use crate::platform::fetch::{RaceResult, fetch_json, race_with_timeout};
+use crate::platform::fetch::{FetchPolicy, RaceResult, fetch_json, race_with_timeout};
 
 use super::state::EnvironmentError;
 
@@ -93,20 +95,47 @@ pub async fn get_account() -> Option<String> {
     }
 }
 
+const ENS_RESOLVE_URL: &str = "https://api.ensideas.com/ens/resolve";
+/// ENS metadata service; renders NFT avatars from their token metadata.
+const ENS_AVATAR_URL: &str = "https://metadata.ens.domains/mainnet/avatar";
+
 #[derive(Deserialize)]
 struct EnsResponse {
     name: Option<String>,
+    /// The name's `avatar` text record.
+    avatar: Option<String>,
 }
 
 pub async fn resolve_ens(address: &str) -> Option<String> {
-    let url = format!("https://api.ensideas.com/ens/resolve/{address}");
+    let url = format!("{ENS_RESOLVE_URL}/{address}");
 
-    match fetch_json::<EnsResponse>(&url).await {
+    match fetch_json::<EnsResponse>(&url, FetchPolicy::default()).await {
         Ok(response) => response.name,
         Err(_) => None,
     }
 }
 
+/// Image URL for the `avatar` record of ENS `name`, if it has a usable one.
+pub async fn resolve_ens_avatar(name: &str) -> Option<String> {
+    let name = String::from(js_sys::encode_uri_component(name));
+    let response =
+        fetch_json::<EnsResponse>(&format!("{ENS_RESOLVE_URL}/{name}"), FetchPolicy::default())
+            .await
+            .ok()?;
+    match parse_ens_avatar(&response.avatar?)? {
+        EnsAvatar::Url(url) => Some(url),
+        EnsAvatar::Nft { .. } => Some(format!("{ENS_AVATAR_URL}/{name}")),
+    }
+}
+
+/// Avatar lookup for one ENS name. `url` is `None` when the name has no
+/// usable avatar or the image failed to load.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WalletAvatar {
+    pub ens_name: String,
+    pub url: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ConnectOutcome {
     pub address: String,
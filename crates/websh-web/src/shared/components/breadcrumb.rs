@@ -1,153 +1,207 @@
-//! Shared breadcrumb navigation component.
+//! Shared breadcrumb segments and navigation component.
 //!
-//! Used by Reader and other surfaces to display current path with clickable segments.
-//! Supports mobile-responsive collapsed mode.
+//! [`route_breadcrumb_segments`] is the single place a route becomes
+//! breadcrumb segments: the `~` home segment, the surface label for the
+//! shell, then one segment per path component. The site chrome renders
+//! them as links; [`Breadcrumb`] renders them as icon buttons with an
+//! on-navigate callback. Supports mobile-responsive collapsed mode.
 
 use leptos::prelude::*;
 
 use crate::shared::icons as ic;
 use websh_core::domain::VirtualPath;
 use websh_core::filesystem::{
-    RouteFrame, RouteRequest, RouteSurface, request_path_for_canonical_path,
+    RouteFrame, RouteRequest, RouteSurface, request_path_for_canonical_path, route_cwd,
 };
 
 stylance::import_crate_style!(css, "src/shared/components/breadcrumb.module.css");
 
+/// What a breadcrumb segment stands for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BreadcrumbKind {
+    /// `~`, the site root.
+    Home,
+    /// A surface label such as `websh`.
+    Surface,
+    Directory,
+    File,
+}
+
+/// Where a path segment sits in the merged filesystem.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BreadcrumbLevel {
+    pub parent: VirtualPath,
+    pub path: VirtualPath,
+    pub surface: RouteSurface,
+}
+
 /// Segment data for breadcrumb rendering.
-#[derive(Clone)]
-struct BreadcrumbSegment {
-    /// Display label
-    label: String,
-    /// Icon to show
-    icon: ic::UiIcon,
-    /// Target route for navigation (None = current/disabled)
-    target: Option<RouteRequest>,
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BreadcrumbSegment {
+    pub label: String,
+    pub kind: BreadcrumbKind,
+    /// Route a click navigates to; `None` for the current segment.
+    pub target: Option<RouteRequest>,
+    /// Filesystem level of a path segment.
+    pub level: Option<BreadcrumbLevel>,
+}
+
+impl BreadcrumbSegment {
+    fn new(label: impl Into<String>, kind: BreadcrumbKind, target: Option<RouteRequest>) -> Self {
+        Self {
+            label: label.into(),
+            kind,
+            target,
+            level: None,
+        }
+    }
+}
+
+/// Breadcrumb segments for the route in `frame`.
+///
+/// Content routes follow the request path (so index routes keep their
+/// URL shape), falling back to the resolved node; the ledger sits at `~`.
+/// Shell routes show the `websh` surface and the working directory.
+pub fn route_breadcrumb_segments(frame: &RouteFrame) -> Vec<BreadcrumbSegment> {
+    match frame.surface() {
+        RouteSurface::Content => {
+            if frame.request.url_path == "/ledger" {
+                return breadcrumb_segments(&VirtualPath::root(), false, RouteSurface::Content);
+            }
+            let path =
+                VirtualPath::from_absolute(frame.request.url_path.clone()).unwrap_or_else(|_| {
+                    if frame.is_file() {
+                        frame.resolution.node_path.clone()
+                    } else {
+                        route_cwd(frame)
+                    }
+                });
+            breadcrumb_segments(&path, frame.is_file(), RouteSurface::Content)
+        }
+        RouteSurface::Shell => breadcrumb_segments(&route_cwd(frame), false, RouteSurface::Shell),
+    }
+}
+
+/// Segments for `path` on `surface`. `is_file` marks the last segment as a
+/// file. The last segment is always the current one and has no target.
+pub fn breadcrumb_segments(
+    path: &VirtualPath,
+    is_file: bool,
+    surface: RouteSurface,
+) -> Vec<BreadcrumbSegment> {
+    let surface_label = match surface {
+        RouteSurface::Content => None,
+        RouteSurface::Shell => Some("websh"),
+    };
+    let target =
+        |path: &VirtualPath| RouteRequest::new(request_path_for_canonical_path(path, surface));
+
+    if path.is_root() && surface_label.is_none() {
+        return vec![BreadcrumbSegment::new("~", BreadcrumbKind::Home, None)];
+    }
+
+    let mut segments = vec![BreadcrumbSegment::new(
+        "~",
+        BreadcrumbKind::Home,
+        Some(RouteRequest::new("/")),
+    )];
+
+    if let Some(label) = surface_label {
+        let surface_target = (!path.is_root()).then(|| target(&VirtualPath::root()));
+        segments.push(BreadcrumbSegment::new(
+            label,
+            BreadcrumbKind::Surface,
+            surface_target,
+        ));
+        if path.is_root() {
+            return segments;
+        }
+    }
+
+    let names = path.segments().collect::<Vec<_>>();
+    let mut parent = VirtualPath::root();
+    for (idx, name) in names.iter().enumerate() {
+        let segment_path = VirtualPath::from_absolute(format!("/{}", names[..=idx].join("/")))
+            .expect("route path");
+        let is_last = idx + 1 == names.len();
+        let kind = if is_last && is_file {
+            BreadcrumbKind::File
+        } else {
+            BreadcrumbKind::Directory
+        };
+        let segment_target = (!is_last).then(|| target(&segment_path));
+        let mut segment = BreadcrumbSegment::new(*name, kind, segment_target);
+        segment.level = Some(BreadcrumbLevel {
+            parent: std::mem::replace(&mut parent, segment_path.clone()),
+            path: segment_path,
+            surface,
+        });
+        segments.push(segment);
+    }
+
+    segments
 }
 
 /// Shared breadcrumb navigation component.
 ///
-/// Displays the current path as clickable segments for navigation.
-/// Automatically handles Root, Browse, and Read routes.
+/// Displays the current route as clickable segments and reports clicks
+/// through `on_navigate`.
 #[component]
 pub fn Breadcrumb(
     route: Memo<RouteFrame>,
     on_navigate: Callback<RouteRequest>,
-    /// Show root "/" segment
+    /// Show the `~` home segment ahead of the path
     #[prop(default = false)]
     show_root: bool,
 ) -> impl IntoView {
     view! {
         <nav class=css::breadcrumb>
             {move || {
-                let route = route.get();
-                let display = route.display_path();
-
-                // Handle Root specially
-                if route.is_root() {
-                    return view! {
-                        <SegmentCurrent icon=ic::SERVER label="/".to_string() />
-                    }.into_any();
-                }
-
-                let segments: Vec<&str> = display.split('/').filter(|s| !s.is_empty()).collect();
-
-                // Build segment data
-                let mut segment_data: Vec<BreadcrumbSegment> = Vec::new();
-
-                // Root segment (optional)
-                if show_root {
-                    segment_data.push(BreadcrumbSegment {
-                        label: "/".to_string(),
-                        icon: ic::SERVER,
-                        target: Some(RouteRequest::new("/")),
-                    });
-                }
-
-                // Path segments
-                for (idx, segment) in segments.iter().enumerate() {
-                    let is_last = idx == segments.len() - 1;
-                    let is_home_segment = *segment == "~";
-
-                    // Determine icon
-                    let icon = if is_home_segment {
-                        ic::HOME
-                    } else if is_last && route.is_file() {
-                        ic::FILE
-                    } else {
-                        ic::FOLDER
-                    };
-
-                    // Build target route for navigation
-                    // Use absolute path construction, not relative join
-                    let target = if is_last {
-                        None // Current segment is not clickable
-                    } else if is_home_segment {
-                        Some(RouteRequest::new("/"))
-                    } else {
-                        let path = canonical_segment_path(&segments, idx);
-                        Some(RouteRequest::new(request_path_for_canonical_path(
-                            &path,
-                            RouteSurface::Content,
-                        )))
-                    };
-
-                    segment_data.push(BreadcrumbSegment {
-                        label: segment.to_string(),
-                        icon,
-                        target,
-                    });
+                let mut segments = route_breadcrumb_segments(&route.get());
+                if !show_root && segments.len() > 1 {
+                    segments.remove(0);
                 }
 
-                // Render segments
-                let views: Vec<_> = segment_data
+                segments
                     .into_iter()
                     .enumerate()
-                    .map(|(idx, seg)| {
-                        let show_separator = idx > 0;
-
+                    .map(|(idx, segment)| {
+                        let icon = segment_icon(segment.kind, &segment.label);
                         view! {
                             <>
-                                {show_separator.then(|| view! {
+                                {(idx > 0).then(|| view! {
                                     <span class=css::separator>
                                         <ic::SvgIcon icon=ic::CHEVRON_RIGHT />
                                     </span>
                                 })}
-                                {if seg.target.is_some() {
-                                    let target = seg.target.clone().unwrap();
-                                    view! {
+                                {match segment.target {
+                                    Some(target) => view! {
                                         <SegmentLink
-                                            icon=seg.icon
-                                            label=seg.label.clone()
+                                            icon=icon
+                                            label=segment.label
                                             on_click=move || on_navigate.run(target.clone())
                                         />
-                                    }.into_any()
-                                } else {
-                                    view! {
-                                        <SegmentCurrent icon=seg.icon label=seg.label.clone() />
-                                    }.into_any()
+                                    }.into_any(),
+                                    None => view! {
+                                        <SegmentCurrent icon=icon label=segment.label />
+                                    }.into_any(),
                                 }}
                             </>
                         }
                     })
-                    .collect();
-
-                views.collect_view().into_any()
+                    .collect_view()
             }}
         </nav>
     }
 }
 
-fn canonical_segment_path(segments: &[&str], idx: usize) -> VirtualPath {
-    if segments.first() == Some(&"~") {
-        let rel = build_segment_path(segments, idx);
-        if rel.is_empty() {
-            return VirtualPath::root();
-        }
-        return VirtualPath::from_absolute(format!("/{rel}")).expect("constant path");
+fn segment_icon(kind: BreadcrumbKind, label: &str) -> ic::UiIcon {
+    match kind {
+        BreadcrumbKind::Home => ic::HOME,
+        BreadcrumbKind::Surface => ic::SERVER,
+        BreadcrumbKind::Directory => ic::FOLDER,
+        BreadcrumbKind::File => ic::file_icon(label),
     }
-
-    let abs = format!("/{}", segments[..=idx].join("/"));
-    VirtualPath::from_absolute(abs).expect("constant path")
 }
 
 /// Clickable breadcrumb segment.
@@ -178,41 +232,111 @@ fn SegmentCurrent(icon: ic::UiIcon, label: String) -> impl IntoView {
     }
 }
 
-/// Build the absolute path for a breadcrumb segment click.
-///
-/// `segments`: full breadcrumb segments from the current route, including
-/// any leading "~" mount alias.
-/// `idx`: the clicked segment's index into `segments`.
-///
-/// If segments starts with "~", the home mount alias is skipped when joining.
-fn build_segment_path(segments: &[&str], idx: usize) -> String {
-    let start_idx = if segments.first() == Some(&"~") { 1 } else { 0 };
-    if idx < start_idx {
-        return String::new();
-    }
-    segments[start_idx..=idx].join("/")
-}
-
 #[cfg(all(test, target_arch = "wasm32"))]
 mod tests {
-    use super::build_segment_path;
+    use super::*;
     use wasm_bindgen_test::*;
 
     wasm_bindgen_test_configure!(run_in_browser);
 
+    fn vp(path: &str) -> VirtualPath {
+        VirtualPath::from_absolute(path).unwrap()
+    }
+
+    fn labels(segments: &[BreadcrumbSegment]) -> Vec<&str> {
+        segments
+            .iter()
+            .map(|segment| segment.label.as_str())
+            .collect()
+    }
+
+    fn targets(segments: &[BreadcrumbSegment]) -> Vec<Option<&str>> {
+        segments
+            .iter()
+            .map(|segment| {
+                segment
+                    .target
+                    .as_ref()
+                    .map(|target| target.url_path.as_str())
+            })
+            .collect()
+    }
+
     #[wasm_bindgen_test]
-    fn build_segment_path_cases() {
-        let cases = [
-            (vec!["~", "blog", "posts"], 1, "blog"),
-            (vec!["~", "blog", "posts"], 2, "blog/posts"),
-            (vec!["work", "notes"], 0, "work"),
-            (vec!["work", "notes"], 1, "work/notes"),
-            (vec!["~", "blog"], 0, ""),
-            (vec!["~", "a", "b", "c", "d"], 4, "a/b/c/d"),
-        ];
-
-        for (segments, idx, expected) in cases {
-            assert_eq!(build_segment_path(&segments, idx), expected);
-        }
+    fn content_root_is_a_lone_current_home() {
+        let segments = breadcrumb_segments(&VirtualPath::root(), false, RouteSurface::Content);
+        assert_eq!(labels(&segments), ["~"]);
+        assert_eq!(targets(&segments), [None]);
+        assert_eq!(segments[0].kind, BreadcrumbKind::Home);
+    }
+
+    #[wasm_bindgen_test]
+    fn content_path_links_every_ancestor() {
+        let segments = breadcrumb_segments(&vp("/blog/2026/post.md"), true, RouteSurface::Content);
+        assert_eq!(labels(&segments), ["~", "blog", "2026", "post.md"]);
+        assert_eq!(
+            targets(&segments),
+            [Some("/"), Some("/blog"), Some("/blog/2026"), None]
+        );
+        let kinds: Vec<_> = segments.iter().map(|segment| segment.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                BreadcrumbKind::Home,
+                BreadcrumbKind::Directory,
+                BreadcrumbKind::Directory,
+                BreadcrumbKind::File,
+            ]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn directory_routes_end_in_a_current_directory() {
+        let segments = breadcrumb_segments(&vp("/blog"), false, RouteSurface::Content);
+        assert_eq!(segments[1].kind, BreadcrumbKind::Directory);
+        assert_eq!(segments[1].target, None);
+    }
+
+    #[wasm_bindgen_test]
+    fn shell_routes_go_through_the_surface_label() {
+        let root = breadcrumb_segments(&VirtualPath::root(), false, RouteSurface::Shell);
+        assert_eq!(labels(&root), ["~", "websh"]);
+        assert_eq!(targets(&root), [Some("/"), None]);
+
+        let nested = breadcrumb_segments(&vp("/blog/2026"), false, RouteSurface::Shell);
+        assert_eq!(labels(&nested), ["~", "websh", "blog", "2026"]);
+        assert_eq!(
+            targets(&nested),
+            [Some("/"), Some("/websh"), Some("/websh/blog"), None]
+        );
+        assert_eq!(nested[1].kind, BreadcrumbKind::Surface);
+    }
+
+    #[wasm_bindgen_test]
+    fn path_segments_carry_their_level() {
+        let segments = breadcrumb_segments(&vp("/blog/2026"), false, RouteSurface::Content);
+        let parents: Vec<_> = segments
+            .iter()
+            .map(|segment| segment.level.as_ref().map(|level| level.parent.as_str()))
+            .collect();
+        assert_eq!(parents, [None, Some("/"), Some("/blog")]);
+        assert_eq!(segments[2].level.as_ref().unwrap().path, vp("/blog/2026"));
+    }
+
+    #[wasm_bindgen_test]
+    fn file_segments_pick_an_icon_by_media_type() {
+        assert_eq!(
+            segment_icon(BreadcrumbKind::File, "intro.mp3"),
+            ic::FILE_MUSIC
+        );
+        assert_eq!(
+            segment_icon(BreadcrumbKind::File, "demo.webm"),
+            ic::FILE_PLAY
+        );
+        assert_eq!(segment_icon(BreadcrumbKind::File, "post.md"), ic::FILE);
+        assert_eq!(
+            segment_icon(BreadcrumbKind::Directory, "demo.webm"),
+            ic::FOLDER
+        );
     }
 }
@@ -5,11 +5,14 @@
 //! License: MIT, Copyright (c) 2019-2024 The Bootstrap Authors.
 
 use leptos::prelude::*;
+use websh_core::domain::FileType;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum UiIcon {
     ChevronRight,
     File,
+    FileMusic,
+    FilePlay,
     Folder,
     Home,
     Lock,
@@ -18,11 +21,23 @@ pub enum UiIcon {
 
 pub const CHEVRON_RIGHT: UiIcon = UiIcon::ChevronRight;
 pub const FILE: UiIcon = UiIcon::File;
+pub const FILE_MUSIC: UiIcon = UiIcon::FileMusic;
+pub const FILE_PLAY: UiIcon = UiIcon::FilePlay;
 pub const FOLDER: UiIcon = UiIcon::Folder;
 pub const HOME: UiIcon = UiIcon::Home;
 pub const LOCK: UiIcon = UiIcon::Lock;
 pub const SERVER: UiIcon = UiIcon::Server;
 
+/// Icon for a file named `name`: a note for audio, a play button for
+/// video, the plain page otherwise.
+pub fn file_icon(name: &str) -> UiIcon {
+    match FileType::from_path(name) {
+        FileType::Audio => FILE_MUSIC,
+        FileType::Video => FILE_PLAY,
+        _ => FILE,
+    }
+}
+
 #[component]
 pub fn SvgIcon(icon: UiIcon) -> impl IntoView {
     view! {
@@ -48,6 +63,18 @@ fn icon_paths(icon: UiIcon) -> AnyView {
         UiIcon::File => view! {
             <path d="M14 4.5V14a2 2 0 0 1-2 2H4a2 2 0 0 1-2-2V2a2 2 0 0 1 2-2h5.5zm-3 0A1.5 1.5 0 0 1 9.5 3V1H4a1 1 0 0 0-1 1v12a1 1 0 0 0 1 1h8a1 1 0 0 0 1-1V4.5z" />
         }.into_any(),
+        UiIcon::FileMusic => view! {
+            <>
+                <path d="M11 6.64a1 1 0 0 0-1.243-.97l-1 .25A1 1 0 0 0 8 6.89v4.306A2.6 2.6 0 0 0 7 11c-.5 0-.974.134-1.338.377-.36.24-.662.628-.662 1.123s.301.883.662 1.123c.364.243.839.377 1.338.377s.974-.134 1.338-.377c.36-.24.662-.628.662-1.123V8.89l2-.5z" />
+                <path d="M14 14V4.5L9.5 0H4a2 2 0 0 0-2 2v12a2 2 0 0 0 2 2h8a2 2 0 0 0 2-2M9.5 3A1.5 1.5 0 0 0 11 4.5h2V14a1 1 0 0 1-1 1H4a1 1 0 0 1-1-1V2a1 1 0 0 1 1-1h5.5z" />
+            </>
+        }.into_any(),
+        UiIcon::FilePlay => view! {
+            <>
+                <path d="M6 6.883v4.234a.5.5 0 0 0 .757.429l3.528-2.117a.5.5 0 0 0 0-.858L6.757 6.454a.5.5 0 0 0-.757.43z" />
+                <path d="M14 14V4.5L9.5 0H4a2 2 0 0 0-2 2v12a2 2 0 0 0 2 2h8a2 2 0 0 0 2-2M9.5 3A1.5 1.5 0 0 0 11 4.5h2V14a1 1 0 0 1-1 1H4a1 1 0 0 1-1-1V2a1 1 0 0 1 1-1h5.5z" />
+            </>
+        }.into_any(),
         UiIcon::Folder => view! {
             <path d="M9.828 3h3.982a2 2 0 0 1 1.992 2.181l-.637 7A2 2 0 0 1 13.174 14H2.825a2 2 0 0 1-1.991-1.819l-.637-7a2 2 0 0 1 .342-1.31L.5 3a2 2 0 0 1 2-2h3.672a2 2 0 0 1 1.414.586l.828.828A2 2 0 0 0 9.828 3m-8.322.12q.322-.119.684-.12h5.396l-.707-.707A1 1 0 0 0 6.172 2H2.5a1 1 0 0 0-1 .981z" />
         }.into_any(),